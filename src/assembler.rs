@@ -3,16 +3,33 @@
 //! Takes one or more skill directories and generates a complete plugin directory
 //! structure with a `plugin.json` manifest, `skills/` subdirectory containing
 //! the skill files.
+//!
+//! Assembly is atomic: the plugin is built in a hidden sibling of the output
+//! directory and moved into place only once every step has succeeded, so a
+//! failed build never leaves a half-written plugin behind.
 
-use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 
 use crate::errors::{AigentError, Result};
 use crate::fs_util::{is_regular_dir, is_regular_file};
 use crate::parser::{find_skill_md, read_properties};
+use crate::structure::LINK_RE;
+use crate::workspace::{glob_match_segment, glob_match_segments};
 
 /// Maximum recursion depth for directory operations.
 const MAX_RECURSION_DEPTH: usize = 10;
 
+/// Fixture file copied alongside each skill when tests are included.
+const FIXTURE_FILE: &str = "tests.yml";
+
+/// Glob patterns always excluded from assembly, in addition to
+/// [`AssembleOptions::exclude`]: dotfiles.
+pub const DEFAULT_EXCLUDE: &[&str] = &[".*"];
+
+/// Version written to generated `plugin.json` manifests.
+const PLUGIN_VERSION: &str = "0.1.0";
+
 /// Assembled skill metadata collected during plugin assembly.
 #[derive(Debug)]
 pub struct AssembleWarning {
@@ -31,6 +48,58 @@ pub struct AssembleOptions {
     pub name: Option<String>,
     /// Run validation on assembled skills.
     pub validate: bool,
+    /// Copy files linked from outside a skill directory into the plugin's
+    /// `resources/` directory and rewrite the links to match.
+    pub bundle_shared: bool,
+    /// Copy each skill's `tests.yml` fixture into the plugin (default: true).
+    pub include_tests: bool,
+    /// Glob patterns for auxiliary files to leave out of each skill, in
+    /// addition to [`DEFAULT_EXCLUDE`] (default: none).
+    ///
+    /// Patterns match paths relative to the skill directory. A pattern
+    /// without `/` matches a file or directory name at any depth (`*.tmp`);
+    /// one with `/` matches the whole path (`scripts/*.sh`, `docs/**`).
+    /// `SKILL.md` is never excluded.
+    pub exclude: Vec<String>,
+    /// Prefix for assembled skill names, so skills from different plugins
+    /// cannot collide (default: none).
+    ///
+    /// With `acme`, skill `helper` is assembled as `skills/acme-helper/`
+    /// with `name: acme-helper`, and `metadata.original-name: helper` keeps
+    /// the original name for display.
+    pub namespace: Option<String>,
+}
+
+impl Default for AssembleOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("dist"),
+            name: None,
+            validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            exclude: Vec::new(),
+            namespace: None,
+        }
+    }
+}
+
+/// A file copied into an assembled skill directory.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AssembledFile {
+    /// Path relative to the skill directory, with `/` separators.
+    pub path: String,
+    /// SHA-256 of the file contents, as 64 hex digits.
+    pub hash: String,
+}
+
+/// A skill included in an assembled plugin.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AssembledSkill {
+    /// Skill name (also its directory name under `skills/`).
+    pub name: String,
+    /// Files in the assembled skill directory, sorted by path.
+    pub files: Vec<AssembledFile>,
 }
 
 /// Result of a successful plugin assembly.
@@ -38,10 +107,21 @@ pub struct AssembleOptions {
 pub struct AssembleResult {
     /// Path to the assembled plugin directory.
     pub plugin_dir: PathBuf,
+    /// Plugin name written to `plugin.json`.
+    pub plugin_name: String,
+    /// Plugin version written to `plugin.json`.
+    pub version: String,
     /// Number of skills included.
     pub skills_count: usize,
+    /// Assembled skills with their file lists and content hashes.
+    pub skills: Vec<AssembledSkill>,
+    /// Number of files left out by [`AssembleOptions::exclude`] or
+    /// [`AssembleOptions::include_tests`], across all skills.
+    pub excluded_files: usize,
     /// Non-fatal warnings encountered during assembly.
     pub warnings: Vec<AssembleWarning>,
+    /// Whether an existing output directory was replaced.
+    pub replaced: bool,
 }
 
 /// Assemble skills into a plugin directory.
@@ -55,12 +135,26 @@ pub struct AssembleResult {
 /// │   │   └── SKILL.md
 /// │   └── <skill-2>/
 /// │       └── SKILL.md
+/// └── resources/        (only with `bundle_shared`)
 /// ```
 ///
+/// `SKILL.md` links that resolve inside the skill directory are copied as-is.
+/// Links escaping it (e.g., `../shared-docs/style.md`) would break in the
+/// plugin: with `bundle_shared`, existing targets are copied once per unique
+/// content into `resources/` and the links rewritten; otherwise, or when the
+/// target does not exist, they are reported as warnings.
+///
+/// The plugin is built in a temporary sibling of the output directory and
+/// renamed into place on success, replacing any previous output as a whole
+/// (see `swap_into_place`). On error, a previous output is left untouched.
+///
 /// # Errors
 ///
 /// Returns an error if:
 /// - No valid skills are found in the input directories
+/// - The namespace is not lowercase letters, digits, and single hyphens, or
+///   a namespaced name fails name validation
+/// - The output path is not a directory, or contains a source skill
 /// - The output directory cannot be created
 /// - Skill files cannot be read or copied
 pub fn assemble_plugin(skill_dirs: &[&Path], opts: &AssembleOptions) -> Result<AssembleResult> {
@@ -69,6 +163,9 @@ pub fn assemble_plugin(skill_dirs: &[&Path], opts: &AssembleOptions) -> Result<A
             message: "no skill directories provided".into(),
         });
     }
+    if let Some(ns) = &opts.namespace {
+        check_namespace(ns)?;
+    }
 
     // Collect valid skills.
     let mut skills: Vec<(String, PathBuf)> = Vec::new();
@@ -88,7 +185,11 @@ pub fn assemble_plugin(skill_dirs: &[&Path], opts: &AssembleOptions) -> Result<A
                         });
                         continue;
                     }
-                    skills.push((props.name.clone(), skill_path));
+                    let name = match &opts.namespace {
+                        Some(ns) => format!("{ns}-{}", props.name),
+                        None => props.name.clone(),
+                    };
+                    skills.push((name, skill_path));
                 }
                 Err(e) => {
                     warnings.push(AssembleWarning {
@@ -114,38 +215,99 @@ pub fn assemble_plugin(skill_dirs: &[&Path], opts: &AssembleOptions) -> Result<A
     // Determine plugin name.
     let plugin_name = opts.name.clone().unwrap_or_else(|| skills[0].0.clone());
 
-    // Create output directory structure.
+    // Build the plugin in a staging directory, then move it into place.
     let out = &opts.output_dir;
-    let skills_dir = out.join("skills");
+    check_output_dir(out, &skills)?;
+    let stage = sibling_path(out, "new")?;
+    remove_stale(&stage)?;
+    let built = build_plugin(&stage, out, &plugin_name, &skills, opts, &mut warnings);
+    let (assembled, excluded_files) = match built {
+        Ok(built) => built,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&stage);
+            return Err(e);
+        }
+    };
+    let replaced = swap_into_place(&stage, out, &mut warnings)?;
 
+    Ok(AssembleResult {
+        plugin_dir: out.clone(),
+        plugin_name,
+        version: PLUGIN_VERSION.to_string(),
+        skills_count: skills.len(),
+        skills: assembled,
+        excluded_files,
+        warnings,
+        replaced,
+    })
+}
+
+/// Write the complete plugin layout for `skills` into `stage`.
+///
+/// Warnings name paths under `out`, where the plugin ends up. Returns the
+/// assembled skills and the number of excluded files.
+fn build_plugin(
+    stage: &Path,
+    out: &Path,
+    plugin_name: &str,
+    skills: &[(String, PathBuf)],
+    opts: &AssembleOptions,
+    warnings: &mut Vec<AssembleWarning>,
+) -> Result<(Vec<AssembledSkill>, usize)> {
+    let skills_dir = stage.join("skills");
     std::fs::create_dir_all(&skills_dir)?;
 
     // Copy each skill into skills/<name>/.
-    for (name, skill_path) in &skills {
+    let mut shared = SharedResources::new(stage.join("resources"));
+    let mut excluded_files = 0;
+    for (name, skill_path) in skills {
         let dest_dir = skills_dir.join(name);
         std::fs::create_dir_all(&dest_dir)?;
+        let src_dir = skill_path.parent().unwrap_or(Path::new("."));
 
-        // Copy the SKILL.md file.
-        let dest_file = dest_dir.join("SKILL.md");
-        std::fs::copy(skill_path, &dest_file)?;
+        // Copy the SKILL.md file, fixing up links that escape the skill.
+        let content = std::fs::read_to_string(skill_path)?;
+        let content =
+            rewrite_escaping_links(&content, src_dir, opts.bundle_shared, &mut shared, warnings)?;
+        let content = match &opts.namespace {
+            Some(ns) => {
+                let original = &name[ns.len() + 1..];
+                namespace_frontmatter(&content, name, original)?
+            }
+            None => content,
+        };
+        std::fs::write(dest_dir.join("SKILL.md"), content)?;
+        if opts.namespace.is_some() {
+            check_namespaced_name(&dest_dir, name)?;
+        }
 
         // Copy any sibling files in the same directory as SKILL.md.
-        if let Some(src_dir) = skill_path.parent() {
-            copy_skill_files(src_dir, &dest_dir)?;
+        excluded_files += copy_skill_files(src_dir, &dest_dir, opts)?;
+
+        // A fixture that no longer parses is reported, not fatal.
+        let fixture = dest_dir.join(FIXTURE_FILE);
+        if opts.include_tests && is_regular_file(&fixture) {
+            let parsed = crate::parser::read_file_checked(&fixture)
+                .and_then(|content| crate::test_runner::parse_fixture(&content));
+            if let Err(e) = parsed {
+                warnings.push(AssembleWarning {
+                    dir: src_dir.to_path_buf(),
+                    message: format!("{name}: {e}"),
+                });
+            }
         }
     }
 
     // Validate assembled skills if requested.
     if opts.validate {
         let mut all_valid = true;
-        for (name, _) in &skills {
-            let dest_dir = skills_dir.join(name);
-            let diags = crate::validate(&dest_dir);
+        for (name, _) in skills {
+            let diags = crate::validate(&skills_dir.join(name));
             if diags.iter().any(|d| d.is_error()) {
                 all_valid = false;
                 for d in &diags {
                     warnings.push(AssembleWarning {
-                        dir: dest_dir.clone(),
+                        dir: out.join("skills").join(name),
                         message: format!("{name}: {d}"),
                     });
                 }
@@ -159,14 +321,236 @@ pub fn assemble_plugin(skill_dirs: &[&Path], opts: &AssembleOptions) -> Result<A
     }
 
     // Generate plugin.json.
-    let plugin_json = generate_plugin_json(&plugin_name, &skills)?;
-    std::fs::write(out.join("plugin.json"), plugin_json)?;
+    let plugin_json = generate_plugin_json(plugin_name, skills)?;
+    std::fs::write(stage.join("plugin.json"), plugin_json)?;
 
-    Ok(AssembleResult {
-        plugin_dir: out.clone(),
-        skills_count: skills.len(),
-        warnings,
-    })
+    // Record what was assembled, for build provenance.
+    let mut assembled = Vec::with_capacity(skills.len());
+    for (name, _) in skills {
+        let mut files = Vec::new();
+        list_files(&skills_dir.join(name), Path::new(""), &mut files, 0)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        assembled.push(AssembledSkill {
+            name: name.clone(),
+            files,
+        });
+    }
+    Ok((assembled, excluded_files))
+}
+
+/// Reject an output path that is not a directory or that contains one of
+/// the source skills, since replacing it would delete them.
+fn check_output_dir(out: &Path, skills: &[(String, PathBuf)]) -> Result<()> {
+    if std::fs::symlink_metadata(out).is_err() {
+        return Ok(());
+    }
+    if !out.is_dir() {
+        return Err(AigentError::Build {
+            message: format!("output path '{}' is not a directory", out.display()),
+        });
+    }
+    let out_canonical = out.canonicalize()?;
+    for (name, skill_path) in skills {
+        let src_dir = skill_path.parent().unwrap_or(Path::new("."));
+        if src_dir.canonicalize()?.starts_with(&out_canonical) {
+            return Err(AigentError::Build {
+                message: format!(
+                    "output directory '{}' contains source skill '{name}'; choose a separate output directory",
+                    out.display()
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Hidden sibling of `out` used while swapping: `.<name>.aigent-<tag>-<pid>`.
+///
+/// A sibling stays on the same filesystem, so renaming it is atomic.
+fn sibling_path(out: &Path, tag: &str) -> Result<PathBuf> {
+    let name = out.file_name().ok_or_else(|| AigentError::Build {
+        message: format!(
+            "output path '{}' must end in a directory name",
+            out.display()
+        ),
+    })?;
+    let parent = match out.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(parent)?;
+    Ok(parent.join(format!(
+        ".{}.aigent-{tag}-{}",
+        name.to_string_lossy(),
+        std::process::id()
+    )))
+}
+
+/// Remove `path` left behind by an interrupted run, if present.
+fn remove_stale(path: &Path) -> Result<()> {
+    if std::fs::symlink_metadata(path).is_ok() {
+        std::fs::remove_dir_all(path)?;
+    }
+    Ok(())
+}
+
+/// Move the finished plugin at `stage` to `out`, returning whether an
+/// existing `out` was replaced.
+///
+/// A directory cannot be renamed over another portably: Windows refuses,
+/// and Unix only allows it when the target is empty. So an existing `out`
+/// is first renamed aside to a backup, then `stage` renamed into place. If
+/// that second rename fails, the backup is renamed back, leaving the
+/// previous output as it was. The backup is deleted only once the new
+/// output is in place; failing to delete it is a warning.
+fn swap_into_place(stage: &Path, out: &Path, warnings: &mut Vec<AssembleWarning>) -> Result<bool> {
+    let discard_stage = |e: std::io::Error| {
+        let _ = std::fs::remove_dir_all(stage);
+        AigentError::from(e)
+    };
+    if std::fs::symlink_metadata(out).is_err() {
+        std::fs::rename(stage, out).map_err(discard_stage)?;
+        return Ok(false);
+    }
+
+    let backup = sibling_path(out, "old")?;
+    if let Err(e) = remove_stale(&backup) {
+        let _ = std::fs::remove_dir_all(stage);
+        return Err(e);
+    }
+    std::fs::rename(out, &backup).map_err(discard_stage)?;
+    if let Err(e) = std::fs::rename(stage, out) {
+        let _ = std::fs::rename(&backup, out);
+        return Err(discard_stage(e));
+    }
+    if let Err(e) = std::fs::remove_dir_all(&backup) {
+        warnings.push(AssembleWarning {
+            dir: out.to_path_buf(),
+            message: format!(
+                "could not remove previous output moved to {}: {e}",
+                backup.display()
+            ),
+        });
+    }
+    Ok(true)
+}
+
+/// Reject a namespace that is not lowercase letters, digits, and single
+/// hyphens, so prefixed names stay valid skill names.
+fn check_namespace(ns: &str) -> Result<()> {
+    let valid = !ns.is_empty()
+        && ns
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !ns.starts_with('-')
+        && !ns.ends_with('-')
+        && !ns.contains("--");
+    if valid {
+        Ok(())
+    } else {
+        Err(AigentError::Build {
+            message: format!(
+                "invalid namespace '{ns}': use lowercase letters, digits, and single hyphens"
+            ),
+        })
+    }
+}
+
+/// Rewrite `SKILL.md` content for a namespaced skill.
+///
+/// The top-level `name:` becomes `name`, and `original-name: original` is
+/// added to the `metadata:` block (created if absent) unless the block
+/// already has one or is written inline. Everything else is kept verbatim.
+fn namespace_frontmatter(content: &str, name: &str, original: &str) -> Result<String> {
+    let (_, rest) = crate::parser::split_preamble(content);
+    let (yaml, _) = crate::parser::split_frontmatter(rest)?;
+    let start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    let has_original = crate::parser::parse_frontmatter(content)
+        .ok()
+        .and_then(|(raw, _)| raw.get("metadata").cloned())
+        .is_some_and(|m| m.get("original-name").is_some());
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_name_block = false;
+    let mut metadata_line = None;
+    let mut inline_metadata = false;
+    for line in yaml.lines() {
+        if in_name_block && line.starts_with([' ', '\t']) {
+            continue;
+        }
+        in_name_block = false;
+        if let Some(value) = line.strip_prefix("name:") {
+            in_name_block = value.trim_start().starts_with(['|', '>']);
+            lines.push(format!("name: {}", yaml_scalar(name)));
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("metadata:") {
+            if value.trim().is_empty() {
+                metadata_line = Some(lines.len());
+            } else {
+                inline_metadata = true;
+            }
+        }
+        lines.push(line.to_string());
+    }
+
+    let entry = format!("original-name: {}", yaml_scalar(original));
+    if !has_original && !inline_metadata {
+        match metadata_line {
+            Some(i) => {
+                let indent = lines
+                    .get(i + 1)
+                    .map(|l| &l[..l.len() - l.trim_start().len()])
+                    .filter(|indent| !indent.is_empty())
+                    .unwrap_or("  ")
+                    .to_string();
+                lines.insert(i + 1, format!("{indent}{entry}"));
+            }
+            None => {
+                lines.push("metadata:".to_string());
+                lines.push(format!("  {entry}"));
+            }
+        }
+    }
+    Ok(format!(
+        "{}{}{}",
+        &content[..start],
+        lines.join(eol),
+        &content[start + yaml.len()..]
+    ))
+}
+
+/// `s` as a YAML scalar: bare when it is a plain skill name, otherwise
+/// single-quoted.
+fn yaml_scalar(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "''"))
+    }
+}
+
+/// Validate the name of a namespaced skill written to `dest_dir`: it must
+/// still be a valid name and match the directory.
+fn check_namespaced_name(dest_dir: &Path, name: &str) -> Result<()> {
+    let diags = crate::validate(dest_dir);
+    match diags
+        .iter()
+        .find(|d| d.is_error() && d.field == Some("name"))
+    {
+        Some(d) => Err(AigentError::Build {
+            message: format!("namespaced skill '{name}' is invalid: {d}"),
+        }),
+        None => Ok(()),
+    }
 }
 
 /// Check whether a skill name is unsafe for use as a directory component.
@@ -181,58 +565,338 @@ fn is_unsafe_name(name: &str) -> bool {
         || name == "."
 }
 
+/// Shared files copied into the plugin's `resources/` directory, keyed by
+/// SHA-256 content hash so identical files linked from several skills are
+/// stored once.
+struct SharedResources {
+    dir: PathBuf,
+    /// Content hash → file name under `resources/`.
+    by_hash: HashMap<String, String>,
+    /// File name under `resources/` → content hash.
+    by_name: HashMap<String, String>,
+}
+
+impl SharedResources {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            by_hash: HashMap::new(),
+            by_name: HashMap::new(),
+        }
+    }
+
+    /// Copy `src` into `resources/` unless identical content is already
+    /// there, returning its file name.
+    ///
+    /// The source file name is kept when free; a different file with the
+    /// same name gets the first 16 hex digits of its content hash appended
+    /// to its stem.
+    fn add(&mut self, src: &Path) -> Result<String> {
+        let bytes = std::fs::read(src)?;
+        let hash = crate::hash::sha256_hex(&bytes);
+        if let Some(name) = self.by_hash.get(&hash) {
+            return Ok(name.clone());
+        }
+
+        let file_name = src
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "resource".to_string());
+        let name = if self.by_name.contains_key(&file_name) {
+            let path = Path::new(&file_name);
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            match path.extension() {
+                Some(ext) => format!("{stem}-{}.{}", &hash[..16], ext.to_string_lossy()),
+                None => format!("{stem}-{}", &hash[..16]),
+            }
+        } else {
+            file_name
+        };
+
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.dir.join(&name), &bytes)?;
+        self.by_hash.insert(hash.clone(), name.clone());
+        self.by_name.insert(name.clone(), hash);
+        Ok(name)
+    }
+}
+
+/// Rewrite or report `SKILL.md` links that resolve outside `src_dir`.
+///
+/// URLs, anchors, links inside fenced code blocks, and links staying inside
+/// the skill directory are left untouched. Absolute links are never bundled
+/// and produce a warning. Relative escaping links to regular files (not
+/// symlinks) are bundled into `resources/` and rewritten when `bundle` is
+/// set; all other escaping links produce a warning naming the source skill
+/// and the resolved target.
+fn rewrite_escaping_links(
+    content: &str,
+    src_dir: &Path,
+    bundle: bool,
+    shared: &mut SharedResources,
+    warnings: &mut Vec<AssembleWarning>,
+) -> Result<String> {
+    let src_dir = &std::path::absolute(src_dir)?;
+    let skill_root = normalize_lexically(src_dir);
+    let fences = fenced_ranges(content);
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for cap in LINK_RE.captures_iter(content) {
+        let Some(m) = cap.name("path") else {
+            continue;
+        };
+        if fences.iter().any(|r| r.contains(&m.start())) {
+            continue;
+        }
+        let link = m.as_str();
+        if link.starts_with("http://") || link.starts_with("https://") || link.starts_with('#') {
+            continue;
+        }
+        let (path_part, fragment) = match link.find('#') {
+            Some(i) => link.split_at(i),
+            None => (link, ""),
+        };
+        if Path::new(path_part).is_absolute() {
+            warnings.push(AssembleWarning {
+                dir: src_dir.to_path_buf(),
+                message: format!("link '{link}' is an absolute path and is not bundled"),
+            });
+            continue;
+        }
+
+        let target = normalize_lexically(&src_dir.join(path_part));
+        if target.starts_with(&skill_root) {
+            continue;
+        }
+
+        if !is_regular_file(&target) {
+            let reason = if target.exists() {
+                "is not a regular file"
+            } else {
+                "does not exist"
+            };
+            warnings.push(AssembleWarning {
+                dir: src_dir.to_path_buf(),
+                message: format!(
+                    "link '{link}' escapes the skill directory and its target {reason}: {}",
+                    target.display()
+                ),
+            });
+            continue;
+        }
+        if !bundle {
+            warnings.push(AssembleWarning {
+                dir: src_dir.to_path_buf(),
+                message: format!(
+                    "link '{link}' escapes the skill directory and will break in the plugin: {}",
+                    target.display()
+                ),
+            });
+            continue;
+        }
+
+        let name = shared.add(&target)?;
+        out.push_str(&content[last..m.start()]);
+        out.push_str(&format!("../../resources/{name}{fragment}"));
+        last = m.end();
+    }
+
+    out.push_str(&content[last..]);
+    Ok(out)
+}
+
+/// Byte ranges of the fenced code blocks (```` ``` ```` or `~~~`) in
+/// `content`, fence lines included. An unclosed fence runs to the end.
+fn fenced_ranges(content: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut fence: Option<(&str, usize)> = None;
+    let mut pos = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        match fence {
+            Some((marker, start)) if trimmed.starts_with(marker) => {
+                ranges.push(start..pos + line.len());
+                fence = None;
+            }
+            Some(_) => {}
+            None if trimmed.starts_with("```") => fence = Some(("```", pos)),
+            None if trimmed.starts_with("~~~") => fence = Some(("~~~", pos)),
+            None => {}
+        }
+        pos += line.len();
+    }
+    if let Some((_, start)) = fence {
+        ranges.push(start..content.len());
+    }
+    ranges
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
 /// Copy non-SKILL.md files from source dir to destination dir.
 ///
 /// Copies reference files, scripts, etc. that the skill may depend on.
-/// Skips hidden files and the target/ directory.
-fn copy_skill_files(src: &Path, dest: &Path) -> Result<()> {
+/// Skips the target/ directory, paths matching `opts.exclude`, and
+/// `tests.yml` unless `opts.include_tests` is set. Returns the number of
+/// files skipped by the exclusion rules.
+fn copy_skill_files(src: &Path, dest: &Path, opts: &AssembleOptions) -> Result<usize> {
+    let mut excluded = 0;
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
         let name = entry.file_name();
         let name_str = name.to_string_lossy();
 
-        // Skip SKILL.md (already copied), hidden files, and target/.
-        if name_str == "SKILL.md"
-            || name_str == "skill.md"
-            || name_str.starts_with('.')
-            || name_str == "target"
-        {
+        // Skip SKILL.md (already copied) and target/.
+        if name_str == "SKILL.md" || name_str == "skill.md" || name_str == "target" {
             continue;
         }
 
         let src_path = entry.path();
         let dest_path = dest.join(&name);
+        let rel = Path::new(&name);
 
-        if is_regular_file(&src_path) {
+        if is_excluded(rel, &opts.exclude) || (!opts.include_tests && name_str == FIXTURE_FILE) {
+            excluded += count_files(&src_path, 0)?;
+        } else if is_regular_file(&src_path) {
             std::fs::copy(&src_path, &dest_path)?;
         } else if is_regular_dir(&src_path) {
-            copy_dir_recursive(&src_path, &dest_path, 0)?;
+            excluded += copy_dir_recursive(&src_path, &dest_path, rel, &opts.exclude, 0)?;
         }
     }
-    Ok(())
+    Ok(excluded)
 }
 
-/// Recursively copy a directory.
+/// Recursively copy a directory, skipping paths that match `exclude`.
+///
+/// `rel` is the path of `src` relative to the skill directory. Returns the
+/// number of files skipped.
 ///
 /// # Errors
 ///
 /// Returns an error if the recursion depth exceeds [`MAX_RECURSION_DEPTH`].
-fn copy_dir_recursive(src: &Path, dest: &Path, depth: usize) -> Result<()> {
+fn copy_dir_recursive(
+    src: &Path,
+    dest: &Path,
+    rel: &Path,
+    exclude: &[String],
+    depth: usize,
+) -> Result<usize> {
     if depth > MAX_RECURSION_DEPTH {
         return Err(AigentError::Build {
             message: format!("exceeded maximum directory depth ({MAX_RECURSION_DEPTH})"),
         });
     }
     std::fs::create_dir_all(dest)?;
+    let mut excluded = 0;
     for entry in std::fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
         let dest_path = dest.join(entry.file_name());
+        let entry_rel = rel.join(entry.file_name());
 
-        if is_regular_file(&src_path) {
+        if is_excluded(&entry_rel, exclude) {
+            excluded += count_files(&src_path, depth + 1)?;
+        } else if is_regular_file(&src_path) {
             std::fs::copy(&src_path, &dest_path)?;
         } else if is_regular_dir(&src_path) {
-            copy_dir_recursive(&src_path, &dest_path, depth + 1)?;
+            excluded += copy_dir_recursive(&src_path, &dest_path, &entry_rel, exclude, depth + 1)?;
+        }
+    }
+    Ok(excluded)
+}
+
+/// Returns `true` if `rel` (relative to the skill directory) matches one of
+/// the `exclude` globs or [`DEFAULT_EXCLUDE`].
+///
+/// A pattern without `/` is matched against the last path component only.
+fn is_excluded(rel: &Path, exclude: &[String]) -> bool {
+    let segments: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let mut patterns = DEFAULT_EXCLUDE
+        .iter()
+        .copied()
+        .chain(exclude.iter().map(String::as_str));
+    patterns.any(|pattern| {
+        if pattern.contains('/') {
+            let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+            glob_match_segments(&pattern, &segments)
+        } else {
+            segments
+                .last()
+                .is_some_and(|name| glob_match_segment(pattern, name))
+        }
+    })
+}
+
+/// Count the regular files at or beneath `path`.
+fn count_files(path: &Path, depth: usize) -> Result<usize> {
+    if is_regular_file(path) {
+        return Ok(1);
+    }
+    if !is_regular_dir(path) {
+        return Ok(0);
+    }
+    if depth > MAX_RECURSION_DEPTH {
+        return Err(AigentError::Build {
+            message: format!("exceeded maximum directory depth ({MAX_RECURSION_DEPTH})"),
+        });
+    }
+    let mut count = 0;
+    for entry in std::fs::read_dir(path)? {
+        count += count_files(&entry?.path(), depth + 1)?;
+    }
+    Ok(count)
+}
+
+/// Collect the files under `dir` with their content hashes.
+///
+/// `prefix` is the path of `dir` relative to the skill directory.
+fn list_files(
+    dir: &Path,
+    prefix: &Path,
+    files: &mut Vec<AssembledFile>,
+    depth: usize,
+) -> Result<()> {
+    if depth > MAX_RECURSION_DEPTH {
+        return Err(AigentError::Build {
+            message: format!("exceeded maximum directory depth ({MAX_RECURSION_DEPTH})"),
+        });
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let rel = prefix.join(entry.file_name());
+        if is_regular_file(&path) {
+            let bytes = std::fs::read(&path)?;
+            files.push(AssembledFile {
+                path: rel
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                hash: crate::hash::sha256_hex(&bytes),
+            });
+        } else if is_regular_dir(&path) {
+            list_files(&path, &rel, files, depth + 1)?;
         }
     }
     Ok(())
@@ -245,7 +909,7 @@ fn generate_plugin_json(name: &str, skills: &[(String, PathBuf)]) -> Result<Stri
     let json = serde_json::json!({
         "name": name,
         "description": format!("Plugin assembled from {} skill(s)", skills.len()),
-        "version": "0.1.0",
+        "version": PLUGIN_VERSION,
     });
 
     serde_json::to_string_pretty(&json).map_err(|e| AigentError::Build {
@@ -279,6 +943,9 @@ mod tests {
             output_dir: out.clone(),
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
         assert_eq!(result.skills_count, 1);
@@ -306,6 +973,9 @@ mod tests {
             output_dir: out.clone(),
             name: Some("my-plugin".into()),
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         let result = assemble_plugin(&[s1.as_path(), s2.as_path()], &opts).unwrap();
         assert_eq!(result.skills_count, 2);
@@ -326,6 +996,9 @@ mod tests {
             output_dir: out.clone(),
             name: Some("test-plugin".into()),
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         assemble_plugin(&[skill.as_path()], &opts).unwrap();
         let json_str = fs::read_to_string(out.join("plugin.json")).unwrap();
@@ -343,6 +1016,9 @@ mod tests {
             output_dir: out,
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         let result = assemble_plugin(&[], &opts);
         assert!(result.is_err());
@@ -365,6 +1041,9 @@ mod tests {
             output_dir: out.clone(),
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         assemble_plugin(&[skill_dir.as_path()], &opts).unwrap();
         assert!(out.join("skills/my-skill/reference.md").exists());
@@ -384,6 +1063,9 @@ mod tests {
             output_dir: out,
             name: None,
             validate: true,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         let result = assemble_plugin(&[skill.as_path()], &opts);
         assert!(result.is_err());
@@ -402,6 +1084,9 @@ mod tests {
             output_dir: out.clone(),
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         assemble_plugin(&[skill.as_path()], &opts).unwrap();
         let json_str = fs::read_to_string(out.join("plugin.json")).unwrap();
@@ -422,6 +1107,9 @@ mod tests {
             output_dir: out,
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         // Should fail because the only skill has an unsafe name.
         let result = assemble_plugin(&[skill.as_path()], &opts);
@@ -446,6 +1134,9 @@ mod tests {
             output_dir: out.clone(),
             name: None,
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         let result = assemble_plugin(&[bad.as_path(), good.as_path()], &opts).unwrap();
         assert_eq!(result.skills_count, 1);
@@ -466,6 +1157,9 @@ mod tests {
             output_dir: out.clone(),
             name: Some("test-plugin".into()),
             validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
         };
         assemble_plugin(&[skill.as_path()], &opts).unwrap();
         let diags = crate::plugin::manifest::validate_manifest(&out.join("plugin.json"));
@@ -485,6 +1179,36 @@ mod tests {
         assert!(json.get("skills").is_none());
     }
 
+    #[test]
+    fn assemble_result_lists_skill_files_with_hashes() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::create_dir_all(skill.join("references")).unwrap();
+        fs::write(skill.join("references/guide.md"), "Guide.").unwrap();
+        let out = parent.path().join("plugin");
+        let opts = AssembleOptions {
+            output_dir: out,
+            name: Some("my-plugin".into()),
+            validate: false,
+            bundle_shared: false,
+            include_tests: true,
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        assert_eq!(result.plugin_name, "my-plugin");
+        assert_eq!(result.version, "0.1.0");
+        assert_eq!(result.skills.len(), 1);
+        let skill = &result.skills[0];
+        assert_eq!(skill.name, "my-skill");
+        let paths: Vec<&str> = skill.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["SKILL.md", "references/guide.md"]);
+        assert_eq!(skill.files[1].hash, crate::hash::sha256_hex(b"Guide."));
+    }
+
     #[test]
     fn is_unsafe_name_detects_traversal() {
         assert!(is_unsafe_name("../etc/passwd"));
@@ -511,7 +1235,7 @@ mod tests {
             fs::write(current.join("file.txt"), format!("level {i}")).unwrap();
         }
 
-        copy_dir_recursive(&src, &dest, 0).unwrap();
+        copy_dir_recursive(&src, &dest, Path::new(""), &[], 0).unwrap();
 
         // Verify deepest file was copied.
         let mut check = dest.clone();
@@ -534,7 +1258,7 @@ mod tests {
             fs::create_dir_all(&current).unwrap();
         }
 
-        let result = copy_dir_recursive(&src, &dest, 0);
+        let result = copy_dir_recursive(&src, &dest, Path::new(""), &[], 0);
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
         assert!(
@@ -555,11 +1279,628 @@ mod tests {
             fs::create_dir_all(&current).unwrap();
         }
 
-        let result = copy_dir_recursive(&src, &dest, 0);
+        let result = copy_dir_recursive(&src, &dest, Path::new(""), &[], 0);
         let err_msg = result.unwrap_err().to_string();
         assert!(
             err_msg.contains(&MAX_RECURSION_DEPTH.to_string()),
             "error should contain the depth limit value, got: {err_msg}"
         );
     }
+
+    // ── Shared link bundling ─────────────────────────────────────────
+
+    /// Two skills linking one shared file, plus a link to a missing file.
+    fn make_shared_fixture(parent: &Path) -> (PathBuf, PathBuf) {
+        fs::create_dir_all(parent.join("shared-docs")).unwrap();
+        fs::write(parent.join("shared-docs/style.md"), "Style guide").unwrap();
+        let a = make_skill(
+            parent,
+            "skill-a",
+            "---\nname: skill-a\ndescription: First\n---\nSee [style](../shared-docs/style.md#tone) and [local](local.md).\n",
+        );
+        fs::write(a.join("local.md"), "Local").unwrap();
+        let b = make_skill(
+            parent,
+            "skill-b",
+            "---\nname: skill-b\ndescription: Second\n---\nSee [style](../shared-docs/style.md) and [gone](../missing/gone.md).\n",
+        );
+        (a, b)
+    }
+
+    fn bundle_opts(out: PathBuf, bundle_shared: bool) -> AssembleOptions {
+        AssembleOptions {
+            output_dir: out,
+            bundle_shared,
+            ..AssembleOptions::default()
+        }
+    }
+
+    #[test]
+    fn bundle_shared_copies_shared_file_once_and_rewrites_links() {
+        let parent = tempdir().unwrap();
+        let (a, b) = make_shared_fixture(parent.path());
+        let out = parent.path().join("output");
+        let result =
+            assemble_plugin(&[a.as_path(), b.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+
+        let resources: Vec<_> = fs::read_dir(out.join("resources"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(resources, vec![std::ffi::OsString::from("style.md")]);
+
+        let a_md = fs::read_to_string(out.join("skills/skill-a/SKILL.md")).unwrap();
+        assert!(a_md.contains("[style](../../resources/style.md#tone)"));
+        assert!(a_md.contains("[local](local.md)"));
+        let b_md = fs::read_to_string(out.join("skills/skill-b/SKILL.md")).unwrap();
+        assert!(b_md.contains("[style](../../resources/style.md)"));
+        assert!(b_md.contains("[gone](../missing/gone.md)"));
+
+        assert_eq!(result.warnings.len(), 1);
+        let w = &result.warnings[0];
+        assert!(w.dir.ends_with("skill-b"));
+        assert!(w.message.contains("does not exist"), "got: {}", w.message);
+        assert!(w.message.contains("gone.md"), "got: {}", w.message);
+    }
+
+    #[test]
+    fn bundle_shared_dedupes_identical_content_under_different_paths() {
+        let parent = tempdir().unwrap();
+        fs::create_dir_all(parent.path().join("one")).unwrap();
+        fs::create_dir_all(parent.path().join("two")).unwrap();
+        fs::write(parent.path().join("one/guide.md"), "Same").unwrap();
+        fs::write(parent.path().join("two/guide.md"), "Same").unwrap();
+        fs::write(parent.path().join("two/other.md"), "Same").unwrap();
+        let a = make_skill(
+            parent.path(),
+            "skill-a",
+            "---\nname: skill-a\ndescription: First\n---\n[g](../one/guide.md) [o](../two/other.md)\n",
+        );
+        let b = make_skill(
+            parent.path(),
+            "skill-b",
+            "---\nname: skill-b\ndescription: Second\n---\n[g](../two/guide.md)\n",
+        );
+        let out = parent.path().join("output");
+        assemble_plugin(&[a.as_path(), b.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+        assert_eq!(fs::read_dir(out.join("resources")).unwrap().count(), 1);
+        let b_md = fs::read_to_string(out.join("skills/skill-b/SKILL.md")).unwrap();
+        assert!(b_md.contains("[g](../../resources/guide.md)"));
+    }
+
+    #[test]
+    fn bundle_shared_renames_different_content_with_same_name() {
+        let parent = tempdir().unwrap();
+        fs::create_dir_all(parent.path().join("one")).unwrap();
+        fs::create_dir_all(parent.path().join("two")).unwrap();
+        fs::write(parent.path().join("one/guide.md"), "First").unwrap();
+        fs::write(parent.path().join("two/guide.md"), "Second").unwrap();
+        let a = make_skill(
+            parent.path(),
+            "skill-a",
+            "---\nname: skill-a\ndescription: First\n---\n[g](../one/guide.md) [h](../two/guide.md)\n",
+        );
+        let out = parent.path().join("output");
+        assemble_plugin(&[a.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+        assert_eq!(fs::read_dir(out.join("resources")).unwrap().count(), 2);
+        let md = fs::read_to_string(out.join("skills/skill-a/SKILL.md")).unwrap();
+        assert!(md.contains("[g](../../resources/guide.md)"));
+        assert!(md.contains("[h](../../resources/guide-"));
+    }
+
+    #[test]
+    fn escaping_links_warn_without_bundle_shared() {
+        let parent = tempdir().unwrap();
+        let (a, b) = make_shared_fixture(parent.path());
+        let out = parent.path().join("output");
+        let result = assemble_plugin(
+            &[a.as_path(), b.as_path()],
+            &bundle_opts(out.clone(), false),
+        )
+        .unwrap();
+        assert!(!out.join("resources").exists());
+        let a_md = fs::read_to_string(out.join("skills/skill-a/SKILL.md")).unwrap();
+        assert!(a_md.contains("[style](../shared-docs/style.md#tone)"));
+        // Two links to the existing shared file, one to the missing file.
+        assert_eq!(result.warnings.len(), 3);
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.dir.ends_with("skill-a") && w.message.contains("style.md")));
+    }
+
+    #[test]
+    fn bundle_shared_rejects_absolute_links() {
+        let parent = tempdir().unwrap();
+        let secret = parent.path().join("secret.txt");
+        fs::write(&secret, "token").unwrap();
+        let link = secret.display().to_string();
+        let a = make_skill(
+            parent.path(),
+            "skill-a",
+            &format!("---\nname: skill-a\ndescription: First\n---\n[s]({link})\n"),
+        );
+        let out = parent.path().join("output");
+        let result = assemble_plugin(&[a.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+        assert!(!out.join("resources").exists());
+        let md = fs::read_to_string(out.join("skills/skill-a/SKILL.md")).unwrap();
+        assert!(md.contains(&format!("[s]({link})")));
+        assert_eq!(result.warnings.len(), 1);
+        assert!(
+            result.warnings[0].message.contains("absolute path"),
+            "got: {}",
+            result.warnings[0].message
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn bundle_shared_skips_symlinked_targets() {
+        let parent = tempdir().unwrap();
+        fs::create_dir_all(parent.path().join("shared")).unwrap();
+        let secret = parent.path().join("secret.txt");
+        fs::write(&secret, "token").unwrap();
+        std::os::unix::fs::symlink(&secret, parent.path().join("shared/style.md")).unwrap();
+        let a = make_skill(
+            parent.path(),
+            "skill-a",
+            "---\nname: skill-a\ndescription: First\n---\n[s](../shared/style.md)\n",
+        );
+        let out = parent.path().join("output");
+        let result = assemble_plugin(&[a.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+        assert!(!out.join("resources").exists());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(
+            result.warnings[0].message.contains("not a regular file"),
+            "got: {}",
+            result.warnings[0].message
+        );
+    }
+
+    #[test]
+    fn bundle_shared_ignores_links_in_code_fences() {
+        let parent = tempdir().unwrap();
+        fs::create_dir_all(parent.path().join("shared")).unwrap();
+        fs::write(parent.path().join("shared/style.md"), "Style").unwrap();
+        let a = make_skill(
+            parent.path(),
+            "skill-a",
+            "---\nname: skill-a\ndescription: First\n---\n\
+             ```markdown\n[s](../shared/style.md)\n```\n\
+             ~~~\n[t](../shared/style.md)\n~~~\n",
+        );
+        let out = parent.path().join("output");
+        let result = assemble_plugin(&[a.as_path()], &bundle_opts(out.clone(), true)).unwrap();
+        assert!(!out.join("resources").exists());
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        let md = fs::read_to_string(out.join("skills/skill-a/SKILL.md")).unwrap();
+        assert!(md.contains("[s](../shared/style.md)"));
+        assert!(md.contains("[t](../shared/style.md)"));
+    }
+
+    #[test]
+    fn normalize_lexically_resolves_parent_components() {
+        assert_eq!(
+            normalize_lexically(Path::new("/a/b/../c/./d.md")),
+            PathBuf::from("/a/c/d.md")
+        );
+        assert_eq!(
+            normalize_lexically(Path::new("a/../../b")),
+            PathBuf::from("../b")
+        );
+    }
+
+    // ── Test fixtures ────────────────────────────────────────────────
+
+    const FIXTURE: &str = "queries:\n  - input: \"do things\"\n    should_match: true\n";
+
+    #[test]
+    fn assemble_copies_tests_yml_by_default() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join("tests.yml"), FIXTURE).unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        assert!(opts.include_tests);
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        assert_eq!(
+            fs::read_to_string(out.join("skills/my-skill/tests.yml")).unwrap(),
+            FIXTURE
+        );
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn assemble_without_tests_skips_tests_yml() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join("tests.yml"), FIXTURE).unwrap();
+        fs::write(skill.join("reference.md"), "# Ref\n").unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            include_tests: false,
+            ..AssembleOptions::default()
+        };
+        assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        assert!(!out.join("skills/my-skill/tests.yml").exists());
+        assert!(out.join("skills/my-skill/reference.md").exists());
+    }
+
+    // ── Exclude globs ────────────────────────────────────────────────
+
+    #[test]
+    fn assemble_exclude_skips_matching_files() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join("scratch.tmp"), "x").unwrap();
+        fs::write(skill.join("EXAMPLES.md"), "# Examples\n").unwrap();
+        fs::create_dir(skill.join("scripts")).unwrap();
+        fs::write(skill.join("scripts/cache.tmp"), "x").unwrap();
+        fs::write(skill.join("scripts/run.sh"), "echo\n").unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            exclude: vec!["*.tmp".into()],
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        let dest = out.join("skills/my-skill");
+        assert!(!dest.join("scratch.tmp").exists());
+        assert!(!dest.join("scripts/cache.tmp").exists());
+        assert!(dest.join("EXAMPLES.md").exists());
+        assert!(dest.join("scripts/run.sh").exists());
+        assert!(dest.join("SKILL.md").exists());
+        assert_eq!(result.excluded_files, 2);
+    }
+
+    #[test]
+    fn assemble_excludes_dotfiles_by_default() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join(".env"), "SECRET=1\n").unwrap();
+        fs::create_dir(skill.join("docs")).unwrap();
+        fs::write(skill.join("docs/.DS_Store"), "x").unwrap();
+        fs::write(skill.join("docs/guide.md"), "# Guide\n").unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        let dest = out.join("skills/my-skill");
+        assert!(!dest.join(".env").exists());
+        assert!(!dest.join("docs/.DS_Store").exists());
+        assert!(dest.join("docs/guide.md").exists());
+        assert_eq!(result.excluded_files, 2);
+    }
+
+    #[test]
+    fn assemble_excludes_dotfiles_with_custom_patterns() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join(".env"), "SECRET=1\n").unwrap();
+        fs::write(skill.join(crate::validator::DRAFT_MARKER), "").unwrap();
+        fs::create_dir(skill.join(".git")).unwrap();
+        fs::write(skill.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(skill.join("scratch.tmp"), "x").unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            exclude: vec!["*.tmp".into()],
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        let dest = out.join("skills/my-skill");
+        assert!(!dest.join(".env").exists());
+        assert!(!dest.join(crate::validator::DRAFT_MARKER).exists());
+        assert!(!dest.join(".git").exists());
+        assert!(!dest.join("scratch.tmp").exists());
+        assert_eq!(result.excluded_files, 4);
+    }
+
+    #[test]
+    fn assemble_exclude_never_drops_skill_md() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            exclude: vec!["*.md".into(), "**".into()],
+            ..AssembleOptions::default()
+        };
+        assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        assert!(out.join("skills/my-skill/SKILL.md").exists());
+    }
+
+    #[test]
+    fn is_excluded_matches_names_and_paths() {
+        let patterns = vec!["*.tmp".to_string(), "scripts/*.sh".to_string()];
+        assert!(is_excluded(Path::new("a.tmp"), &patterns));
+        assert!(is_excluded(Path::new("deep/dir/a.tmp"), &patterns));
+        assert!(is_excluded(Path::new("scripts/run.sh"), &patterns));
+        assert!(!is_excluded(Path::new("tools/scripts/run.sh"), &patterns));
+        assert!(!is_excluded(Path::new("EXAMPLES.md"), &patterns));
+    }
+
+    #[test]
+    fn assemble_warns_on_malformed_tests_yml() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(skill.join("tests.yml"), "queries: not-a-list\n").unwrap();
+        let out = parent.path().join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[skill.as_path()], &opts).unwrap();
+        assert_eq!(result.skills_count, 1);
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(result.warnings[0]
+            .message
+            .starts_with("my-skill: parse error: invalid tests.yml:"));
+        assert!(out.join("skills/my-skill/tests.yml").exists());
+    }
+
+    // ── Namespacing ──────────────────────────────────────────────────
+
+    fn namespaced_opts(out: &Path, ns: &str) -> AssembleOptions {
+        AssembleOptions {
+            output_dir: out.to_path_buf(),
+            namespace: Some(ns.to_string()),
+            validate: true,
+            ..AssembleOptions::default()
+        }
+    }
+
+    #[test]
+    fn namespace_prefixes_directory_and_name() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "helper",
+            "---\nname: helper\ndescription: Helps with things\n---\nBody.\n",
+        );
+        let out = parent.path().join("output");
+        let result = assemble_plugin(&[skill.as_path()], &namespaced_opts(&out, "acme")).unwrap();
+        assert_eq!(result.skills[0].name, "acme-helper");
+        assert!(!out.join("skills/helper").exists());
+        let dest = out.join("skills/acme-helper");
+        let props = read_properties(&dest).unwrap();
+        assert_eq!(props.name, "acme-helper");
+        assert!(crate::validate(&dest).iter().all(|d| !d.is_error()));
+        assert_eq!(
+            fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+            "---\nname: acme-helper\ndescription: Helps with things\nmetadata:\n  original-name: helper\n---\nBody.\n"
+        );
+    }
+
+    #[test]
+    fn namespace_adds_original_name_to_existing_metadata() {
+        let content = "<!-- note -->\n---\nname: helper\nmetadata:\n    version: 1.0.0\ndescription: Helps\n---\nBody.\n";
+        assert_eq!(
+            namespace_frontmatter(content, "acme-helper", "helper").unwrap(),
+            "<!-- note -->\n---\nname: acme-helper\nmetadata:\n    original-name: helper\n    version: 1.0.0\ndescription: Helps\n---\nBody.\n"
+        );
+        let already = "---\nname: helper\nmetadata:\n  original-name: helper\n---\n";
+        assert_eq!(
+            namespace_frontmatter(already, "acme-helper", "helper").unwrap(),
+            "---\nname: acme-helper\nmetadata:\n  original-name: helper\n---\n"
+        );
+    }
+
+    #[test]
+    fn invalid_namespace_is_rejected() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "helper",
+            "---\nname: helper\ndescription: Helps with things\n---\nBody.\n",
+        );
+        let out = parent.path().join("output");
+        for ns in ["", "Acme", "acme-", "ac--me", "ac/me"] {
+            let err = assemble_plugin(&[skill.as_path()], &namespaced_opts(&out, ns)).unwrap_err();
+            assert!(err.to_string().contains("invalid namespace"), "{ns}: {err}");
+        }
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn namespaced_name_over_the_length_limit_fails() {
+        let parent = tempdir().unwrap();
+        let name = "a".repeat(60);
+        let skill = make_skill(
+            parent.path(),
+            &name,
+            &format!("---\nname: {name}\ndescription: Helps with things\n---\nBody.\n"),
+        );
+        let out = parent.path().join("output");
+        let err = assemble_plugin(&[skill.as_path()], &namespaced_opts(&out, "acme")).unwrap_err();
+        assert!(err.to_string().contains("namespaced skill"), "{err}");
+        assert!(!out.exists());
+    }
+
+    // ── Atomic assembly ──────────────────────────────────────────────
+
+    /// Every file under `dir` with its contents, keyed by relative path.
+    fn snapshot(dir: &Path) -> std::collections::BTreeMap<PathBuf, Vec<u8>> {
+        fn walk(root: &Path, dir: &Path, files: &mut std::collections::BTreeMap<PathBuf, Vec<u8>>) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(root, &path, files);
+                } else {
+                    let rel = path.strip_prefix(root).unwrap().to_path_buf();
+                    files.insert(rel, fs::read(&path).unwrap());
+                }
+            }
+        }
+        let mut files = std::collections::BTreeMap::new();
+        walk(dir, dir, &mut files);
+        files
+    }
+
+    /// Names of hidden staging or backup directories left next to `out`.
+    fn leftovers(out: &Path) -> Vec<String> {
+        fs::read_dir(out.parent().unwrap())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|n| n.contains(".aigent-"))
+            .collect()
+    }
+
+    /// Assemble a first skill into `<parent>/output` and create a second one
+    /// for a later run. Returns (first skill, second skill, output dir).
+    fn assemble_twice_setup(parent: &Path) -> (PathBuf, PathBuf, PathBuf) {
+        let good = make_skill(
+            parent,
+            "good-skill",
+            "---\nname: good-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        fs::write(good.join("reference.md"), "# Reference\n").unwrap();
+        let out = parent.join("output");
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let first = assemble_plugin(&[good.as_path()], &opts).unwrap();
+        assert!(!first.replaced);
+        let bad = make_skill(
+            parent,
+            "bad-skill",
+            "---\nname: bad-skill\ndescription: Does other things\n---\nBody.\n",
+        );
+        (good, bad, out)
+    }
+
+    #[test]
+    fn failed_copy_leaves_previous_output_untouched() {
+        let parent = tempdir().unwrap();
+        let (good, bad, out) = assemble_twice_setup(parent.path());
+        let before = snapshot(&out);
+
+        // A directory tree deeper than MAX_RECURSION_DEPTH cannot be copied,
+        // so the second skill fails after the first has been written.
+        let mut deep = bad.join("assets");
+        for i in 0..=MAX_RECURSION_DEPTH {
+            deep = deep.join(format!("level-{i}"));
+        }
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("file.txt"), "deep").unwrap();
+
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let err = assemble_plugin(&[good.as_path(), bad.as_path()], &opts).unwrap_err();
+        assert!(err.to_string().contains("depth"), "{err}");
+        assert_eq!(snapshot(&out), before);
+        assert!(leftovers(&out).is_empty(), "{:?}", leftovers(&out));
+    }
+
+    #[test]
+    fn failed_validation_leaves_previous_output_untouched() {
+        let parent = tempdir().unwrap();
+        let (good, bad, out) = assemble_twice_setup(parent.path());
+        let before = snapshot(&out);
+        fs::write(
+            bad.join("SKILL.md"),
+            "---\nname: bad-skill\ndescription: <b>tags</b>\n---\nBody.\n",
+        )
+        .unwrap();
+
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            validate: true,
+            ..AssembleOptions::default()
+        };
+        assert!(assemble_plugin(&[good.as_path(), bad.as_path()], &opts).is_err());
+        assert_eq!(snapshot(&out), before);
+        assert!(leftovers(&out).is_empty(), "{:?}", leftovers(&out));
+    }
+
+    #[test]
+    fn successful_run_replaces_previous_output() {
+        let parent = tempdir().unwrap();
+        let (_good, bad, out) = assemble_twice_setup(parent.path());
+        fs::write(out.join("stale.txt"), "from an older build").unwrap();
+
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let result = assemble_plugin(&[bad.as_path()], &opts).unwrap();
+        assert!(result.replaced);
+        assert!(out.join("skills/bad-skill/SKILL.md").exists());
+        assert!(!out.join("skills/good-skill").exists());
+        assert!(!out.join("stale.txt").exists());
+        assert!(leftovers(&out).is_empty(), "{:?}", leftovers(&out));
+    }
+
+    #[test]
+    fn output_containing_a_source_skill_is_rejected() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        let opts = AssembleOptions {
+            output_dir: parent.path().to_path_buf(),
+            ..AssembleOptions::default()
+        };
+        let err = assemble_plugin(&[skill.as_path()], &opts).unwrap_err();
+        assert!(err.to_string().contains("contains source skill 'my-skill'"));
+        assert!(skill.join("SKILL.md").exists());
+    }
+
+    #[test]
+    fn output_path_that_is_a_file_is_rejected() {
+        let parent = tempdir().unwrap();
+        let skill = make_skill(
+            parent.path(),
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+        );
+        let out = parent.path().join("output");
+        fs::write(&out, "not a directory").unwrap();
+        let opts = AssembleOptions {
+            output_dir: out.clone(),
+            ..AssembleOptions::default()
+        };
+        let err = assemble_plugin(&[skill.as_path()], &opts).unwrap_err();
+        assert!(err.to_string().contains("is not a directory"));
+        assert_eq!(fs::read_to_string(&out).unwrap(), "not a directory");
+    }
 }
@@ -1,18 +1,37 @@
 use super::util::{capitalize_first, to_title_case};
 use super::ClarityAssessment;
+use crate::validator::RESERVED_WORDS;
 
-/// Filler words to remove from purpose strings during name derivation.
-const FILLER_WORDS: &[&str] = &[
+/// Default filler words removed from purpose strings during name derivation.
+///
+/// Extend rather than replace it by concatenating with your own words, e.g.
+/// `[DEFAULT_STOPWORDS, &["really"]].concat()`, and pass the result to
+/// [`derive_name_with`].
+pub const DEFAULT_STOPWORDS: &[&str] = &[
     "a", "an", "the", "to", "for", "from", "with", "and", "or", "that", "which", "this", "my",
     "of", "in", "on", "is", "are", "be",
 ];
 
 /// Derive a kebab-case skill name from a natural language description.
 ///
-/// Steps: lowercase → remove filler words → gerund-form first word →
-/// join with hyphens → sanitize → truncate to 64 characters.
+/// Steps: lowercase → remove filler and reserved words → gerund-form first
+/// word → join with hyphens → sanitize → drop reserved-word segments →
+/// truncate to 64 characters. Reserved words ("claude", "anthropic") are
+/// dropped so the derived name passes validation.
+///
+/// Uses [`DEFAULT_STOPWORDS`] as the filler-word list.
 #[must_use]
 pub fn derive_name(purpose: &str) -> String {
+    derive_name_with(purpose, DEFAULT_STOPWORDS)
+}
+
+/// Derive a kebab-case skill name using a custom filler-word list.
+///
+/// Same steps as [`derive_name`], but words in `stopwords` are removed
+/// instead of [`DEFAULT_STOPWORDS`]. Matching ignores ASCII case and
+/// surrounding punctuation. Reserved words are always removed.
+#[must_use]
+pub fn derive_name_with(purpose: &str, stopwords: &[&str]) -> String {
     let lower = purpose.to_lowercase();
 
     // Split into words, filter fillers.
@@ -20,7 +39,8 @@ pub fn derive_name(purpose: &str) -> String {
         .split_whitespace()
         .filter(|w| {
             let stripped = w.trim_matches(|c: char| !c.is_alphanumeric());
-            !FILLER_WORDS.contains(&stripped)
+            !stopwords.iter().any(|s| s.eq_ignore_ascii_case(stripped))
+                && !RESERVED_WORDS.contains(&stripped)
         })
         .collect();
 
@@ -49,8 +69,15 @@ pub fn derive_name(purpose: &str) -> String {
         .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-')
         .collect();
 
+    // Drop reserved words left inside compound words (e.g., "claude-code").
+    let segments: Vec<&str> = sanitized
+        .split('-')
+        .filter(|seg| !RESERVED_WORDS.contains(seg))
+        .collect();
+    let without_reserved = segments.join("-");
+
     // Collapse consecutive hyphens and trim.
-    let collapsed = collapse_hyphens(&sanitized);
+    let collapsed = collapse_hyphens(&without_reserved);
     let trimmed = collapsed.trim_matches('-');
 
     if trimmed.is_empty() {
@@ -372,6 +399,97 @@ mod tests {
         assert_eq!(name, "tooling-processing-data");
     }
 
+    #[test]
+    fn derive_name_default_strips_articles() {
+        let name = derive_name("Manage the logs for a service");
+        assert_eq!(name, "managing-logs-service");
+    }
+
+    #[test]
+    fn derive_name_with_custom_list_keeps_service() {
+        let name = derive_name_with("Monitor the service really well", &["the", "really"]);
+        assert_eq!(name, "monitoring-service-well");
+    }
+
+    #[test]
+    fn derive_name_with_service_stopword_drops_it() {
+        let name = derive_name_with("Monitor the service", &["the", "service"]);
+        assert_eq!(name, "monitoring");
+    }
+
+    #[test]
+    fn derive_name_with_extended_default_list() {
+        let stopwords = [DEFAULT_STOPWORDS, &["really"]].concat();
+        let name = derive_name_with("Really parse the config files", &stopwords);
+        assert_eq!(name, "parsing-config-files");
+    }
+
+    #[test]
+    fn derive_name_with_empty_list_keeps_all_words() {
+        let name = derive_name_with("Parse the files", &[]);
+        assert_eq!(name, "parsing-the-files");
+    }
+
+    #[test]
+    fn derive_name_drops_reserved_words() {
+        let name = derive_name("Integrate with Claude");
+        assert_eq!(name, "integrating");
+        let name = derive_name("Summarize Anthropic release notes");
+        assert_eq!(name, "summarizing-release-notes");
+    }
+
+    #[test]
+    fn derive_name_drops_reserved_leading_word_before_gerund() {
+        let name = derive_name("Claude review pull requests");
+        assert_eq!(name, "reviewing-pull-requests");
+    }
+
+    #[test]
+    fn derive_name_drops_reserved_segment_in_compound_word() {
+        let name = derive_name("Configure claude-code hooks");
+        assert!(!name.split('-').any(|seg| seg == "claude"), "got: {name}");
+        assert_eq!(name, "configuring-code-hooks");
+    }
+
+    #[test]
+    fn derive_name_keeps_reserved_word_as_substring() {
+        let name = derive_name("Parse claudette notes");
+        assert_eq!(name, "parsing-claudette-notes");
+    }
+
+    #[test]
+    fn derive_name_only_reserved_words_falls_back() {
+        assert_eq!(derive_name("Claude Anthropic"), "my-skill");
+    }
+
+    #[test]
+    fn derived_names_with_reserved_words_pass_validation() {
+        for purpose in [
+            "Integrate with Claude",
+            "Anthropic API client",
+            "claude-code helper",
+        ] {
+            let name = derive_name(purpose);
+            let diags = crate::validator::validate_metadata(
+                &std::collections::HashMap::from([
+                    (
+                        "name".to_string(),
+                        serde_yaml_ng::Value::String(name.clone()),
+                    ),
+                    (
+                        "description".to_string(),
+                        serde_yaml_ng::Value::String("Does things".to_string()),
+                    ),
+                ]),
+                None,
+            );
+            assert!(
+                !diags.iter().any(|d| d.code == crate::diagnostics::E007),
+                "{purpose} → {name}: {diags:?}"
+            );
+        }
+    }
+
     #[test]
     fn derive_name_special_characters_stripped() {
         let name = derive_name("Process PDFs!");
@@ -155,36 +155,7 @@ pub fn llm_assess_clarity(provider: &dyn LlmProvider, purpose: &str) -> Result<C
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    /// A simple mock provider that returns a pre-configured response.
-    struct MockProvider {
-        response: String,
-    }
-
-    impl MockProvider {
-        fn new(response: &str) -> Self {
-            Self {
-                response: response.to_string(),
-            }
-        }
-    }
-
-    impl LlmProvider for MockProvider {
-        fn generate(&self, _system: &str, _user: &str) -> Result<String> {
-            Ok(self.response.clone())
-        }
-    }
-
-    /// A mock provider that always returns an error.
-    struct FailingProvider;
-
-    impl LlmProvider for FailingProvider {
-        fn generate(&self, _system: &str, _user: &str) -> Result<String> {
-            Err(AigentError::Build {
-                message: "mock LLM failure".to_string(),
-            })
-        }
-    }
+    use crate::builder::providers::mock::MockProvider;
 
     #[test]
     fn mock_provider_returns_expected_text() {
@@ -217,14 +188,14 @@ mod tests {
 
     #[test]
     fn llm_description_generation_falls_back_on_error() {
-        let provider = FailingProvider;
+        let provider = MockProvider::failing("mock LLM failure");
         let result = llm_generate_description(&provider, "Process PDFs", "processing-pdfs");
         assert!(result.is_err(), "should return error for fallback");
     }
 
     #[test]
     fn llm_body_generation_falls_back_on_error() {
-        let provider = FailingProvider;
+        let provider = MockProvider::failing("mock LLM failure");
         let result = llm_generate_body(&provider, "Process PDFs", "processing-pdfs", "Desc.");
         assert!(result.is_err(), "should return error for fallback");
     }
@@ -2,12 +2,13 @@
 pub mod deterministic;
 /// LLM-enhanced skill generation and provider trait.
 pub mod llm;
-/// LLM provider implementations (Anthropic, OpenAI, Google, Ollama).
+/// LLM provider implementations (Anthropic, OpenAI, Google, Ollama, mock).
 pub mod providers;
 /// Template generation for `init` command.
 pub mod template;
 mod util;
 
+pub use deterministic::DEFAULT_STOPWORDS;
 pub use llm::LlmProvider;
 pub use template::SkillTemplate;
 
@@ -15,6 +16,7 @@ use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::io::Write as _;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use crate::errors::{AigentError, Result};
 use crate::models::SkillProperties;
@@ -49,7 +51,7 @@ use deterministic::{generate_body, generate_description};
 use llm::{detect_provider, llm_derive_name, llm_generate_body, llm_generate_description};
 
 /// User input for skill generation.
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct SkillSpec {
     /// Natural language description of what the skill should do.
     pub purpose: String,
@@ -71,6 +73,28 @@ pub struct SkillSpec {
     pub minimal: bool,
     /// Template variant for generating the skill structure.
     pub template: SkillTemplate,
+    /// Provider to generate with instead of one detected from the
+    /// environment (e.g., a [`MockProvider`](providers::mock::MockProvider)
+    /// in tests). Ignored when `no_llm` is set.
+    pub provider: Option<Arc<dyn LlmProvider>>,
+}
+
+impl std::fmt::Debug for SkillSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SkillSpec")
+            .field("purpose", &self.purpose)
+            .field("name", &self.name)
+            .field("tools", &self.tools)
+            .field("compatibility", &self.compatibility)
+            .field("license", &self.license)
+            .field("extra_files", &self.extra_files)
+            .field("output_dir", &self.output_dir)
+            .field("no_llm", &self.no_llm)
+            .field("minimal", &self.minimal)
+            .field("template", &self.template)
+            .field("provider", &self.provider.as_ref().map(|_| "<provider>"))
+            .finish()
+    }
 }
 
 /// Result of skill generation.
@@ -82,13 +106,71 @@ pub struct BuildResult {
     pub files: HashMap<String, String>,
     /// Directory where the skill was created.
     pub output_dir: PathBuf,
-    /// Warnings collected during generation (e.g., LLM fallback notices).
+    /// Warnings collected during generation (e.g., LLM fallback notices, or
+    /// why the purpose looked unclear).
     ///
     /// These replace the previous `eprintln!` calls, giving library consumers
     /// structured access to non-fatal issues that occurred during the build.
     pub warnings: Vec<String>,
 }
 
+/// A generated skill that failed validation.
+///
+/// Carried by [`AigentError::InvalidDraft`]. [`build_skill`] removes the
+/// files it wrote from the output directory, but the generated content (which
+/// may have cost LLM calls) is kept here so callers can inspect, repair, or
+/// [`save`](Self::save) it elsewhere.
+#[derive(Debug, Clone)]
+pub struct SkillDraft {
+    /// Skill name the draft was generated for.
+    pub name: String,
+    /// Generated files, keyed by relative path (includes `SKILL.md`).
+    pub files: HashMap<String, String>,
+    /// Validation diagnostics for the generated skill.
+    pub diagnostics: Vec<crate::diagnostics::Diagnostic>,
+    /// Output directory the skill was written to before cleanup.
+    pub output_dir: PathBuf,
+    /// Warnings collected during generation (e.g., LLM fallback notices, or
+    /// why the purpose looked unclear).
+    pub warnings: Vec<String>,
+}
+
+impl SkillDraft {
+    /// The conventional place to save this draft: `<name>.draft` next to
+    /// the output directory.
+    #[must_use]
+    pub fn default_save_dir(&self) -> PathBuf {
+        self.output_dir
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(format!("{}.draft", self.name))
+    }
+
+    /// Write the draft's files into `dir` and mark it as a draft, so
+    /// recursive discovery skips it.
+    ///
+    /// Returns `AigentError::AlreadyExists` if `dir` already holds a
+    /// `SKILL.md`, leaving it untouched.
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        if let Some(content) = self.files.get("SKILL.md") {
+            write_exclusive(&dir.join("SKILL.md"), content.as_bytes())?;
+        }
+        for (rel_path, content) in &self.files {
+            if rel_path == "SKILL.md" {
+                continue;
+            }
+            let full_path = dir.join(rel_path);
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&full_path, content)?;
+        }
+        mark_draft(dir)?;
+        Ok(dir.to_path_buf())
+    }
+}
+
 /// Clarity assessment result.
 #[derive(Debug)]
 pub struct ClarityAssessment {
@@ -105,18 +187,53 @@ pub struct ClarityAssessment {
 /// directory is determined from `spec.output_dir` (if provided) or derived
 /// from the skill name.
 ///
-/// Returns `AigentError::Build` if the output directory already contains a
-/// SKILL.md or if the generated output fails validation.
+/// Generation uses `spec.provider` when set, otherwise a provider detected
+/// from the environment (deterministic generation if there is none, or if
+/// `spec.no_llm` is set).
+///
+/// The purpose is checked with [`assess_clarity`] first. An unclear purpose
+/// does not stop the build; its follow-up questions are reported in
+/// [`BuildResult::warnings`] instead.
+///
+/// Returns `AigentError::AlreadyExists` if the output directory already
+/// contains a SKILL.md. Returns `AigentError::InvalidDraft` if the generated
+/// output fails validation; the written files are removed, and the error
+/// carries the generated content and diagnostics.
 pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
-    // 0. Select provider (unless no_llm).
-    let provider: Option<Box<dyn LlmProvider>> = if spec.no_llm { None } else { detect_provider() };
+    // 0. Select provider (unless no_llm): the spec's override, else detect.
+    if spec.no_llm {
+        return build_with_provider(spec, None);
+    }
+    if let Some(provider) = &spec.provider {
+        return build_with_provider(spec, Some(provider.as_ref()));
+    }
+    let detected: Option<Box<dyn LlmProvider>> = detect_provider();
+    build_with_provider(spec, detected.as_deref())
+}
+
+/// Build a skill, generating with `provider` when one is given.
+fn build_with_provider(
+    spec: &SkillSpec,
+    provider: Option<&dyn LlmProvider>,
+) -> Result<BuildResult> {
     let mut warnings = Vec::new();
 
+    // 0. Flag an unclear purpose without refusing to build.
+    let assessment = assess_clarity(&spec.purpose);
+    if !assessment.clear {
+        warnings.extend(
+            assessment
+                .questions
+                .iter()
+                .map(|q| format!("purpose may be unclear: {q}")),
+        );
+    }
+
     // 1. Derive name (LLM with fallback to deterministic).
     let name = if let Some(explicit) = &spec.name {
         explicit.clone()
-    } else if let Some(ref prov) = provider {
-        match llm_derive_name(prov.as_ref(), &spec.purpose) {
+    } else if let Some(prov) = provider {
+        match llm_derive_name(prov, &spec.purpose) {
             Ok(n) => n,
             Err(e) => {
                 warnings.push(format!(
@@ -136,8 +253,8 @@ pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
         .unwrap_or_else(|| PathBuf::from(&name));
 
     // 3. Generate description (LLM with fallback).
-    let description = if let Some(ref prov) = provider {
-        match llm_generate_description(prov.as_ref(), &spec.purpose, &name) {
+    let description = if let Some(prov) = provider {
+        match llm_generate_description(prov, &spec.purpose, &name) {
             Ok(d) => d,
             Err(e) => {
                 warnings.push(format!(
@@ -156,14 +273,17 @@ pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
         description,
         license: spec.license.clone(),
         compatibility: spec.compatibility.clone(),
-        allowed_tools: spec.tools.clone(),
+        allowed_tools: spec
+            .tools
+            .clone()
+            .or_else(|| spec.template.allowed_tools(&name)),
         metadata: None,
     };
 
     // 5. Generate body (LLM with fallback).
-    let body = if let Some(ref prov) = provider {
+    let mut body = if let Some(prov) = provider {
         match llm_generate_body(
-            prov.as_ref(),
+            prov,
             &spec.purpose,
             &properties.name,
             &properties.description,
@@ -180,6 +300,10 @@ pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
         generate_body(&spec.purpose, &properties.name, &properties.description)
     };
 
+    if let Some(section) = template::generated_body_section(spec.template, &name) {
+        body = format!("{}\n\n{section}", body.trim_end());
+    }
+
     // 6. Serialize SkillProperties to YAML frontmatter.
     let yaml = serde_yaml_ng::to_string(&properties).map_err(|e| AigentError::Build {
         message: format!("failed to serialize frontmatter: {e}"),
@@ -221,6 +345,19 @@ pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
         }
     }
 
+    // 10a. Write the template's supporting files, unless given as extra files.
+    for (rel_path, file_content) in template::template_files(spec.template, &name) {
+        if files.contains_key(&rel_path) {
+            continue;
+        }
+        let full_path = output_dir.join(&rel_path);
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, &file_content)?;
+        files.insert(rel_path, file_content);
+    }
+
     // 10b. Scaffold supporting directories unless minimal.
     if !spec.minimal {
         scaffold_dirs(&output_dir)?;
@@ -232,19 +369,17 @@ pub fn build_skill(spec: &SkillSpec) -> Result<BuildResult> {
     if !errors.is_empty() {
         // Best-effort cleanup of files we just wrote, to avoid leaving
         // invalid artifacts on disk that block subsequent runs.
-        let _ = std::fs::remove_file(&skill_md_path);
-        if let Some(ref extra) = spec.extra_files {
-            for rel_path in extra.keys() {
-                let full_path = output_dir.join(rel_path);
-                let _ = std::fs::remove_file(&full_path);
-            }
+        for rel_path in files.keys() {
+            let _ = std::fs::remove_file(output_dir.join(rel_path));
         }
-        let error_msgs: Vec<String> = errors.iter().map(|d| d.to_string()).collect();
-        return Err(AigentError::Build {
-            message: format!(
-                "generated skill failed validation:\n{}",
-                error_msgs.join("\n")
-            ),
+        return Err(AigentError::InvalidDraft {
+            draft: Box::new(SkillDraft {
+                name,
+                files,
+                diagnostics: diags,
+                output_dir,
+                warnings,
+            }),
         });
     }
 
@@ -266,6 +401,16 @@ pub fn derive_name(purpose: &str) -> String {
     deterministic::derive_name(purpose)
 }
 
+/// Derive a kebab-case skill name using a custom filler-word list.
+///
+/// Like [`derive_name`], but removes the words in `stopwords` instead of
+/// [`DEFAULT_STOPWORDS`]. Extend the defaults with
+/// `[DEFAULT_STOPWORDS, &["really"]].concat()`.
+#[must_use]
+pub fn derive_name_with(purpose: &str, stopwords: &[&str]) -> String {
+    deterministic::derive_name_with(purpose, stopwords)
+}
+
 /// Evaluate if a purpose description is clear enough for autonomous generation.
 ///
 /// Uses deterministic heuristics based on word count, question marks, and
@@ -347,6 +492,21 @@ pub fn init_skill(dir: &Path, tmpl: SkillTemplate, minimal: bool) -> Result<Path
     Ok(dir.join("SKILL.md"))
 }
 
+/// Mark a skill directory as a draft by writing a [`DRAFT_MARKER`] file.
+///
+/// Recursive discovery skips draft skills unless drafts are explicitly
+/// included. Delete the marker to publish the skill. Returns the marker path.
+///
+/// [`DRAFT_MARKER`]: crate::validator::DRAFT_MARKER
+pub fn mark_draft(dir: &Path) -> Result<PathBuf> {
+    let path = dir.join(crate::validator::DRAFT_MARKER);
+    std::fs::write(
+        &path,
+        "This skill is a draft: recursive discovery skips it.\nDelete this file to publish the skill.\n",
+    )?;
+    Ok(path)
+}
+
 /// Create `examples/` and `scripts/` subdirectories with `.gitkeep` files.
 ///
 /// Only creates each directory if it doesn't already exist, so template-generated
@@ -476,11 +636,23 @@ fn confirm(prompt: &str, reader: &mut dyn std::io::BufRead) -> Result<bool> {
 
 #[cfg(test)]
 mod tests {
+    use super::providers::mock::MockProvider;
     use super::*;
     use tempfile::tempdir;
 
     // ── init_skill tests (24-29) ──────────────────────────────────────
 
+    #[test]
+    fn mark_draft_hides_skill_from_discovery() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("my-skill");
+        init_skill(&dir, SkillTemplate::Minimal, true).unwrap();
+        let marker = mark_draft(&dir).unwrap();
+        assert_eq!(marker, dir.join(crate::validator::DRAFT_MARKER));
+        assert!(crate::validator::is_draft(&dir));
+        assert!(crate::validator::discover_skills(parent.path()).is_empty());
+    }
+
     #[test]
     fn init_creates_skill_md_in_empty_dir() {
         let parent = tempdir().unwrap();
@@ -776,6 +948,52 @@ mod tests {
         assert!(result.files.contains_key("examples/example.txt"));
     }
 
+    #[test]
+    fn build_with_mcp_server_template() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("issue-tracker");
+        let spec = SkillSpec {
+            purpose: "Triage issues with the tracker server".to_string(),
+            name: Some("issue-tracker".to_string()),
+            output_dir: Some(dir.clone()),
+            no_llm: true,
+            template: SkillTemplate::McpServer,
+            ..Default::default()
+        };
+        let result = build_skill(&spec).unwrap();
+        assert_eq!(
+            result.properties.allowed_tools.as_deref(),
+            Some("mcp__issue-tracker__*")
+        );
+        let content = std::fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert!(content.contains("## Server setup"));
+        assert!(dir.join("mcp.json").exists());
+        assert!(result.files.contains_key("mcp.json"));
+    }
+
+    #[test]
+    fn build_template_keeps_explicit_tools_and_extra_files() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("issue-tracker");
+        let extra = HashMap::from([("mcp.json".to_string(), "{}".to_string())]);
+        let spec = SkillSpec {
+            purpose: "Triage issues with the tracker server".to_string(),
+            name: Some("issue-tracker".to_string()),
+            tools: Some("mcp__tracker__search".to_string()),
+            output_dir: Some(dir.clone()),
+            no_llm: true,
+            extra_files: Some(extra),
+            template: SkillTemplate::McpServer,
+            ..Default::default()
+        };
+        let result = build_skill(&spec).unwrap();
+        assert_eq!(
+            result.properties.allowed_tools.as_deref(),
+            Some("mcp__tracker__search")
+        );
+        assert_eq!(std::fs::read_to_string(dir.join("mcp.json")).unwrap(), "{}");
+    }
+
     #[test]
     fn build_spec_with_all_optional_fields() {
         let parent = tempdir().unwrap();
@@ -791,6 +1009,7 @@ mod tests {
             minimal: false,
             extra_files: None,
             template: SkillTemplate::Minimal,
+            provider: None,
         };
         let result = build_skill(&spec).unwrap();
         assert_eq!(result.properties.name, "full-skill");
@@ -961,6 +1180,136 @@ mod tests {
         );
     }
 
+    // ── provider override ─────────────────────────────────────────────
+
+    #[test]
+    fn build_uses_spec_provider() {
+        let parent = tempdir().unwrap();
+        let provider = Arc::new(MockProvider::with_responses([
+            "summarizing-notes",
+            "Summarizes meeting notes into action items. Use when reviewing meetings.",
+            "## Steps\n\n1. Read the notes.\n2. List the action items.",
+        ]));
+        let spec = SkillSpec {
+            purpose: "Summarize meeting notes".to_string(),
+            output_dir: Some(parent.path().join("summarizing-notes")),
+            minimal: true,
+            provider: Some(provider.clone()),
+            ..Default::default()
+        };
+        let result = build_skill(&spec).unwrap();
+        assert_eq!(result.properties.name, "summarizing-notes");
+        assert!(result
+            .properties
+            .description
+            .starts_with("Summarizes meeting"));
+        assert!(result.files["SKILL.md"].contains("2. List the action items."));
+        assert!(
+            result.warnings.is_empty(),
+            "warnings: {:?}",
+            result.warnings
+        );
+        assert_eq!(provider.calls().len(), 3);
+    }
+
+    #[test]
+    fn build_falls_back_when_spec_provider_fails() {
+        let parent = tempdir().unwrap();
+        let spec = SkillSpec {
+            purpose: "Process PDF files".to_string(),
+            name: Some("processing-pdfs".to_string()),
+            output_dir: Some(parent.path().join("processing-pdfs")),
+            minimal: true,
+            provider: Some(Arc::new(MockProvider::failing("offline"))),
+            ..Default::default()
+        };
+        let result = build_skill(&spec).unwrap();
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings.iter().all(|w| w.contains("offline")));
+    }
+
+    #[test]
+    fn no_llm_ignores_spec_provider() {
+        let parent = tempdir().unwrap();
+        let provider = Arc::new(MockProvider::new("unused"));
+        let spec = SkillSpec {
+            purpose: "Process PDF files".to_string(),
+            name: Some("processing-pdfs".to_string()),
+            output_dir: Some(parent.path().join("processing-pdfs")),
+            no_llm: true,
+            minimal: true,
+            provider: Some(provider.clone()),
+            ..Default::default()
+        };
+        build_skill(&spec).unwrap();
+        assert!(provider.calls().is_empty());
+    }
+
+    // ── failed validation keeps the draft ─────────────────────────────
+
+    fn invalid_draft(parent: &Path) -> SkillDraft {
+        let spec = SkillSpec {
+            purpose: "Format bold text".to_string(),
+            name: Some("bold-text".to_string()),
+            output_dir: Some(parent.join("bold-text")),
+            minimal: true,
+            provider: Some(Arc::new(MockProvider::new(
+                "Formats <b>bold</b> text. Use when formatting.",
+            ))),
+            ..Default::default()
+        };
+        match build_skill(&spec) {
+            Err(AigentError::InvalidDraft { draft }) => *draft,
+            other => panic!("expected InvalidDraft, got: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn build_invalid_llm_output_returns_draft_and_cleans_up() {
+        let parent = tempdir().unwrap();
+        let draft = invalid_draft(parent.path());
+        assert_eq!(draft.name, "bold-text");
+        assert!(draft
+            .diagnostics
+            .iter()
+            .any(|d| d.code == crate::diagnostics::E012));
+        assert!(draft.files["SKILL.md"].contains("<b>bold</b>"));
+        // The output directory is still cleaned up.
+        assert!(!parent.path().join("bold-text/SKILL.md").exists());
+    }
+
+    #[test]
+    fn invalid_draft_error_lists_validation_errors() {
+        let parent = tempdir().unwrap();
+        let err = AigentError::InvalidDraft {
+            draft: Box::new(invalid_draft(parent.path())),
+        };
+        let msg = err.to_string();
+        assert!(
+            msg.starts_with("build error: generated skill failed validation:\n"),
+            "{msg}"
+        );
+        assert!(msg.contains("XML"), "{msg}");
+    }
+
+    #[test]
+    fn saved_draft_is_recoverable() {
+        let parent = tempdir().unwrap();
+        let draft = invalid_draft(parent.path());
+        let dir = draft.save(&draft.default_save_dir()).unwrap();
+        assert_eq!(dir, parent.path().join("bold-text.draft"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("SKILL.md")).unwrap(),
+            draft.files["SKILL.md"]
+        );
+        assert!(crate::validator::is_draft(&dir));
+        // A second save refuses to overwrite.
+        assert!(matches!(
+            draft.save(&dir),
+            Err(AigentError::AlreadyExists { .. })
+        ));
+    }
+
     #[test]
     fn build_result_has_empty_warnings_on_deterministic() {
         let parent = tempdir().unwrap();
@@ -979,4 +1328,23 @@ mod tests {
             result.warnings
         );
     }
+
+    #[test]
+    fn build_warns_about_unclear_purpose() {
+        let parent = tempdir().unwrap();
+        let spec = SkillSpec {
+            purpose: "Maybe PDFs?".to_string(),
+            name: Some("handling-pdfs".to_string()),
+            output_dir: Some(parent.path().join("handling-pdfs")),
+            no_llm: true,
+            minimal: true,
+            ..Default::default()
+        };
+        let result = build_skill(&spec).unwrap();
+        assert!(result.output_dir.join("SKILL.md").exists());
+        assert_eq!(
+            result.warnings,
+            ["purpose may be unclear: Can you provide more detail about what the skill should do?"]
+        );
+    }
 }
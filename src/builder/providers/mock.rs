@@ -0,0 +1,156 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::builder::llm::LlmProvider;
+use crate::errors::{AigentError, Result};
+
+/// Function that answers a `(system, user)` prompt pair.
+type Responder = dyn Fn(&str, &str) -> Result<String> + Send + Sync;
+
+/// One prompt received by a [`MockProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// System prompt.
+    pub system: String,
+    /// User message.
+    pub user: String,
+}
+
+/// In-memory LLM provider with canned or programmable responses.
+///
+/// Built directly rather than from the environment, so tests can exercise
+/// the LLM generation path without API keys or network calls. Pass it to
+/// [`build_skill`](crate::build_skill) through
+/// [`SkillSpec::provider`](crate::SkillSpec::provider). Every prompt is
+/// recorded and available from [`calls`](Self::calls).
+pub struct MockProvider {
+    responder: Box<Responder>,
+    calls: Mutex<Vec<MockCall>>,
+}
+
+impl MockProvider {
+    /// A provider that answers every prompt with `response`.
+    #[must_use]
+    pub fn new(response: impl Into<String>) -> Self {
+        let response = response.into();
+        Self::from_fn(move |_, _| Ok(response.clone()))
+    }
+
+    /// A provider that answers prompts with `responses` in order.
+    ///
+    /// Prompts after the last response get an error, so a test notices
+    /// calls it did not plan for.
+    #[must_use]
+    pub fn with_responses<I, S>(responses: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let queue: Mutex<VecDeque<String>> =
+            Mutex::new(responses.into_iter().map(Into::into).collect());
+        Self::from_fn(move |_, _| {
+            queue
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .pop_front()
+                .ok_or_else(|| AigentError::Build {
+                    message: "mock provider has no responses left".to_string(),
+                })
+        })
+    }
+
+    /// A provider whose every prompt fails with `message`.
+    #[must_use]
+    pub fn failing(message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self::from_fn(move |_, _| {
+            Err(AigentError::Build {
+                message: message.clone(),
+            })
+        })
+    }
+
+    /// A provider that answers each prompt by calling `responder` with the
+    /// system prompt and user message.
+    #[must_use]
+    pub fn from_fn<F>(responder: F) -> Self
+    where
+        F: Fn(&str, &str) -> Result<String> + Send + Sync + 'static,
+    {
+        Self {
+            responder: Box::new(responder),
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Prompts received so far, oldest first.
+    #[must_use]
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for MockProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockProvider")
+            .field("calls", &self.calls().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl LlmProvider for MockProvider {
+    fn generate(&self, system: &str, user: &str) -> Result<String> {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(MockCall {
+                system: system.to_string(),
+                user: user.to_string(),
+            });
+        (self.responder)(system, user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_response_repeats() {
+        let provider = MockProvider::new("hello");
+        assert_eq!(provider.generate("s", "u").unwrap(), "hello");
+        assert_eq!(provider.generate("s", "u").unwrap(), "hello");
+    }
+
+    #[test]
+    fn queued_responses_run_out() {
+        let provider = MockProvider::with_responses(["one", "two"]);
+        assert_eq!(provider.generate("s", "u").unwrap(), "one");
+        assert_eq!(provider.generate("s", "u").unwrap(), "two");
+        assert!(provider.generate("s", "u").is_err());
+    }
+
+    #[test]
+    fn failing_provider_errors() {
+        let err = MockProvider::failing("offline")
+            .generate("s", "u")
+            .unwrap_err();
+        assert!(err.to_string().contains("offline"));
+    }
+
+    #[test]
+    fn from_fn_sees_prompts_and_calls_are_recorded() {
+        let provider = MockProvider::from_fn(|system, user| Ok(format!("{system}/{user}")));
+        assert_eq!(provider.generate("sys", "msg").unwrap(), "sys/msg");
+        assert_eq!(
+            provider.calls(),
+            vec![MockCall {
+                system: "sys".to_string(),
+                user: "msg".to_string(),
+            }]
+        );
+    }
+}
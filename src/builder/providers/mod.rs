@@ -2,11 +2,14 @@
 //!
 //! Each module provides a struct implementing `LlmProvider` with a
 //! `from_env()` constructor that reads API keys from environment variables.
+//! The exception is `mock`, which is constructed directly for tests.
 
 /// Anthropic Claude API provider.
 pub mod anthropic;
 /// Google Gemini API provider.
 pub mod google;
+/// In-memory provider with canned responses, for tests.
+pub mod mock;
 /// Ollama local model provider.
 pub mod ollama;
 /// OpenAI API provider.
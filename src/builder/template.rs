@@ -24,6 +24,25 @@ pub enum SkillTemplate {
     CodeSkill,
     /// SKILL.md with Claude Code extension fields
     ClaudeCode,
+    /// SKILL.md allowing an MCP server's tools + mcp.json
+    McpServer,
+}
+
+impl SkillTemplate {
+    /// The `allowed-tools` value this template sets for a skill named
+    /// `name`, if any.
+    ///
+    /// [`McpServer`](Self::McpServer) allows every tool of the server named
+    /// after the skill (`mcp__<name>__*`).
+    #[must_use]
+    pub fn allowed_tools(self, name: &str) -> Option<String> {
+        match self {
+            Self::CodeSkill => Some("Bash(./scripts/run.sh *)".to_string()),
+            Self::ClaudeCode => Some("Bash(*), Read, Write, Glob".to_string()),
+            Self::McpServer => Some(format!("mcp__{name}__*")),
+            Self::Minimal | Self::ReferenceGuide | Self::DomainSpecific | Self::Workflow => None,
+        }
+    }
 }
 
 /// Generate template files for a given template variant and skill name.
@@ -74,11 +93,28 @@ pub fn template_files(template: SkillTemplate, dir_name: &str) -> HashMap<String
         SkillTemplate::ClaudeCode => {
             files.insert("SKILL.md".to_string(), claude_code_skill_md(&name, &title));
         }
+        SkillTemplate::McpServer => {
+            files.insert("SKILL.md".to_string(), mcp_server_skill_md(&name, &title));
+            files.insert("mcp.json".to_string(), mcp_json(&name));
+        }
     }
 
     files
 }
 
+/// Body section a template adds to generated (rather than scaffolded)
+/// skills, such as the server setup notes of
+/// [`McpServer`](SkillTemplate::McpServer).
+///
+/// Used by [`build_skill`](super::build_skill), which writes its own body
+/// but keeps the parts of the template that a generic body would lack.
+pub(crate) fn generated_body_section(template: SkillTemplate, name: &str) -> Option<String> {
+    match template {
+        SkillTemplate::McpServer => Some(mcp_setup_section(name)),
+        _ => None,
+    }
+}
+
 /// Generate a SKILL.md template for `init` (backward-compatible wrapper).
 ///
 /// The `dir_name` is used as the `name` field (kebab-cased) and the heading
@@ -304,6 +340,57 @@ fn claude_code_skill_md(name: &str, title: &str) -> String {
     )
 }
 
+fn mcp_server_skill_md(name: &str, title: &str) -> String {
+    let tools = SkillTemplate::McpServer
+        .allowed_tools(name)
+        .unwrap_or_default();
+    let setup = mcp_setup_section(name);
+    format!(
+        "---\n\
+         name: {name}\n\
+         description: Describe what this skill does and when to use it\n\
+         allowed-tools: {tools}\n\
+         ---\n\
+         \n\
+         # {title}\n\
+         \n\
+         ## Quick start\n\
+         \n\
+         [Add quick start instructions here]\n\
+         \n\
+         {setup}\
+         \n\
+         ## Usage\n\
+         \n\
+         [Describe which server tools to call and when]\n"
+    )
+}
+
+fn mcp_setup_section(name: &str) -> String {
+    format!(
+        "## Server setup\n\
+         \n\
+         This skill uses the tools of the `{name}` MCP server. Register the\n\
+         server from [mcp.json](mcp.json) with your MCP client (for Claude Code,\n\
+         add the entry to the project's `.mcp.json`), then check that its tools\n\
+         are listed as `mcp__{name}__<tool>`.\n"
+    )
+}
+
+fn mcp_json(name: &str) -> String {
+    format!(
+        "{{\n\
+         \x20\x20\"mcpServers\": {{\n\
+         \x20\x20\x20\x20\"{name}\": {{\n\
+         \x20\x20\x20\x20\x20\x20\"command\": \"{name}-server\",\n\
+         \x20\x20\x20\x20\x20\x20\"args\": [],\n\
+         \x20\x20\x20\x20\x20\x20\"env\": {{}}\n\
+         \x20\x20\x20\x20}}\n\
+         \x20\x20}}\n\
+         }}\n"
+    )
+}
+
 // ── Utility ────────────────────────────────────────────────────────────
 
 /// Convert a string to kebab-case: lowercase, replace non-alphanumeric with
@@ -410,6 +497,40 @@ mod tests {
         assert!(files.contains_key("SKILL.md"));
     }
 
+    #[test]
+    fn mcp_server_template_allows_server_tools() {
+        let files = template_files(SkillTemplate::McpServer, "issue-tracker");
+        assert_eq!(files.len(), 2);
+        let content = files.get("SKILL.md").unwrap();
+        assert!(content.contains("allowed-tools: mcp__issue-tracker__*"));
+        assert!(content.contains("## Server setup"));
+        assert!(content.contains("[mcp.json](mcp.json)"));
+    }
+
+    #[test]
+    fn mcp_server_template_stub_is_valid_json() {
+        let files = template_files(SkillTemplate::McpServer, "issue-tracker");
+        let stub: serde_json::Value = serde_json::from_str(files.get("mcp.json").unwrap()).unwrap();
+        assert_eq!(
+            stub["mcpServers"]["issue-tracker"]["command"],
+            "issue-tracker-server"
+        );
+    }
+
+    #[test]
+    fn allowed_tools_match_template_frontmatter() {
+        for &t in SkillTemplate::value_variants() {
+            let files = template_files(t, "test-skill");
+            let declared = files
+                .get("SKILL.md")
+                .unwrap()
+                .lines()
+                .find_map(|l| l.strip_prefix("allowed-tools: "))
+                .map(str::to_string);
+            assert_eq!(declared, t.allowed_tools("test-skill"), "{t:?}");
+        }
+    }
+
     #[test]
     fn template_names_derive_from_dir_name() {
         let files = template_files(SkillTemplate::Minimal, "My Cool Skill");
@@ -433,6 +554,7 @@ mod tests {
             SkillTemplate::Workflow,
             SkillTemplate::CodeSkill,
             SkillTemplate::ClaudeCode,
+            SkillTemplate::McpServer,
         ];
         for t in templates {
             let files = template_files(t, "test-skill");
@@ -0,0 +1,391 @@
+//! On-disk cache of validation results.
+//!
+//! Validating an unchanged skill again gives the same diagnostics, so watch
+//! mode and repeated CI runs can reuse earlier results. A
+//! [`ValidationCache`] stores each result as a JSON file under
+//! [`CACHE_DIR`], named by a hash of everything validation reads:
+//! - the `SKILL.md` content and file name, and the skill directory path;
+//! - the nearest `_defaults.yml`, if any;
+//! - the validation target and placeholder policy;
+//! - the aigent version, so an upgrade never reuses stale results.
+//!
+//! Entries are written to a temporary file and renamed into place, so
+//! concurrent runs sharing a cache never read a partial entry. An entry
+//! that cannot be read or parsed counts as a miss.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::diagnostics::{Diagnostic, DiagnosticCode, Severity, ValidationTarget};
+use crate::errors::Result;
+use crate::parser::{find_skill_md, read_file_checked};
+use crate::placeholders::PlaceholderPolicy;
+use crate::validator::validate_with_policy;
+
+/// Default cache directory name, relative to the working directory.
+pub const CACHE_DIR: &str = ".aigent-cache";
+
+/// Validation results cached on disk, keyed by content hash.
+///
+/// Counts hits and misses, so callers can report how much work the cache
+/// saved.
+#[derive(Debug)]
+pub struct ValidationCache {
+    dir: PathBuf,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl ValidationCache {
+    /// A cache stored in `dir`. The directory is created on the first write.
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Directory holding the cache entries.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of lookups answered from the cache.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that found no usable entry.
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Diagnostics stored under `key`, or `None` on a miss.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Vec<Diagnostic>> {
+        let found = std::fs::read_to_string(self.entry_path(key))
+            .ok()
+            .and_then(|json| serde_json::from_str::<Vec<CachedDiagnostic>>(&json).ok())
+            .and_then(|cached| cached.into_iter().map(CachedDiagnostic::restore).collect());
+        let counter = if found.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    /// Store `diagnostics` under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AigentError::Io` if the cache directory or entry cannot be
+    /// written.
+    pub fn put(&self, key: &str, diagnostics: &[Diagnostic]) -> Result<()> {
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+        std::fs::create_dir_all(&self.dir)?;
+        // The snippet is left out of a diagnostic's serialized form, so it
+        // is added to each entry here.
+        let entries = diagnostics
+            .iter()
+            .map(|d| {
+                let mut entry = serde_json::to_value(d)?;
+                if let Some(snippet) = &d.snippet {
+                    entry["snippet"] = snippet.as_str().into();
+                }
+                Ok(entry)
+            })
+            .collect::<serde_json::Result<Vec<_>>>()
+            .map_err(std::io::Error::other)?;
+        let json = serde_json::to_string(&entries).map_err(std::io::Error::other)?;
+        let temp = self.dir.join(format!(
+            ".{key}.{}.{}.tmp",
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&temp, json)?;
+        if let Err(e) = std::fs::rename(&temp, self.entry_path(key)) {
+            let _ = std::fs::remove_file(&temp);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Path of the entry for `key`.
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Cache key for validating `dir` with `target` and `policy`: the SHA-256
+/// of every input, as hex.
+///
+/// Returns `None` if the skill has no readable `SKILL.md`; such results are
+/// cheap to recompute and not cached.
+#[must_use]
+pub fn validation_cache_key(
+    dir: &Path,
+    target: ValidationTarget,
+    policy: &PlaceholderPolicy,
+) -> Option<String> {
+    let path = find_skill_md(dir)?;
+    let content = read_file_checked(&path).ok()?;
+    let defaults = crate::defaults::find_defaults(dir).map(|p| {
+        (
+            p.display().to_string(),
+            std::fs::read(&p).unwrap_or_default(),
+        )
+    });
+    let mut substitutions: Vec<_> = policy.substitutions.iter().collect();
+    substitutions.sort();
+
+    let mut hasher = Sha256::new();
+    // Each input is length-prefixed, so adjacent inputs cannot run together.
+    let mut feed = |bytes: &[u8]| {
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(bytes);
+    };
+    feed(env!("CARGO_PKG_VERSION").as_bytes());
+    feed(dir.to_string_lossy().as_bytes());
+    feed(
+        path.file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .as_bytes(),
+    );
+    feed(content.as_bytes());
+    match &defaults {
+        Some((defaults_path, bytes)) => {
+            feed(defaults_path.as_bytes());
+            feed(bytes);
+        }
+        None => feed(b""),
+    }
+    feed(format!("{target:?}").as_bytes());
+    feed(&[u8::from(policy.allow)]);
+    feed(&(policy.nominal_length as u64).to_le_bytes());
+    for (name, value) in substitutions {
+        feed(name.as_bytes());
+        feed(value.as_bytes());
+    }
+    Some(crate::hash::to_hex(&hasher.finalize()))
+}
+
+/// Validate `dir` like [`validate_with_policy`], reusing a cached result
+/// when the skill and configuration are unchanged.
+///
+/// Fresh results are stored for next time. Failing to write the cache is
+/// not an error: the results are still returned.
+#[must_use]
+pub fn validate_cached(
+    dir: &Path,
+    target: ValidationTarget,
+    policy: &PlaceholderPolicy,
+    cache: &ValidationCache,
+) -> Vec<Diagnostic> {
+    let Some(key) = validation_cache_key(dir, target, policy) else {
+        return validate_with_policy(dir, target, policy);
+    };
+    if let Some(diags) = cache.get(&key) {
+        return diags;
+    }
+    let diags = validate_with_policy(dir, target, policy);
+    let _ = cache.put(&key, &diags);
+    diags
+}
+
+/// Field names that validation diagnostics carry.
+///
+/// A cached field name is mapped back to one of these so the restored
+/// [`Diagnostic::field`] stays a `&'static str`.
+const FIELD_NAMES: &[&str] = &["name", "description", "compatibility", "metadata", "body"];
+
+/// A [`Diagnostic`] as read back from a cache entry.
+#[derive(Deserialize)]
+struct CachedDiagnostic {
+    severity: String,
+    code: String,
+    message: String,
+    field: Option<String>,
+    suggestion: Option<String>,
+    snippet: Option<String>,
+}
+
+impl CachedDiagnostic {
+    /// Convert back to a [`Diagnostic`], or `None` if the entry names an
+    /// unknown severity, code, or field.
+    fn restore(self) -> Option<Diagnostic> {
+        let severity = match self.severity.as_str() {
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "info" => Severity::Info,
+            "hint" => Severity::Hint,
+            _ => return None,
+        };
+        let code = *DiagnosticCode::all()
+            .iter()
+            .find(|c| c.as_str() == self.code)?;
+        let field = match self.field {
+            Some(field) => Some(*FIELD_NAMES.iter().find(|f| **f == field)?),
+            None => None,
+        };
+        Some(Diagnostic {
+            severity,
+            code,
+            message: self.message,
+            field,
+            suggestion: self.suggestion,
+            snippet: self.snippet,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{E003, W001};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn make_skill(parent: &Path, name: &str, description: &str) -> PathBuf {
+        let dir = parent.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: {description}\nmystery: 1\n---\nBody\n"),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn render(diags: &[Diagnostic]) -> String {
+        serde_json::to_string(diags).unwrap()
+    }
+
+    #[test]
+    fn second_run_of_unchanged_skill_hits_cache() {
+        let parent = tempdir().unwrap();
+        let dir = make_skill(parent.path(), "my-skill", "Does things");
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let policy = PlaceholderPolicy::default();
+
+        let first = validate_cached(&dir, ValidationTarget::Standard, &policy, &cache);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert!(!first.is_empty(), "expected a W001 for `mystery`");
+
+        let second = validate_cached(&dir, ValidationTarget::Standard, &policy, &cache);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(render(&first), render(&second));
+        assert_eq!(second[0].field, first[0].field);
+    }
+
+    #[test]
+    fn changed_content_misses() {
+        let parent = tempdir().unwrap();
+        let dir = make_skill(parent.path(), "my-skill", "Does things");
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let policy = PlaceholderPolicy::default();
+
+        let _ = validate_cached(&dir, ValidationTarget::Standard, &policy, &cache);
+        make_skill(parent.path(), "my-skill", "Does other things");
+        let _ = validate_cached(&dir, ValidationTarget::Standard, &policy, &cache);
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    }
+
+    #[test]
+    fn key_depends_on_target_and_policy() {
+        let parent = tempdir().unwrap();
+        let dir = make_skill(parent.path(), "my-skill", "Does things");
+        let policy = PlaceholderPolicy::default();
+        let standard = validation_cache_key(&dir, ValidationTarget::Standard, &policy).unwrap();
+        let permissive = validation_cache_key(&dir, ValidationTarget::Permissive, &policy).unwrap();
+        let allowing = PlaceholderPolicy {
+            allow: true,
+            ..PlaceholderPolicy::default()
+        };
+        let allowed = validation_cache_key(&dir, ValidationTarget::Standard, &allowing).unwrap();
+        assert_ne!(standard, permissive);
+        assert_ne!(standard, allowed);
+        assert_eq!(
+            standard,
+            validation_cache_key(&dir, ValidationTarget::Standard, &policy).unwrap()
+        );
+    }
+
+    #[test]
+    fn corrupt_entry_is_a_miss() {
+        let parent = tempdir().unwrap();
+        let dir = make_skill(parent.path(), "my-skill", "Does things");
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let policy = PlaceholderPolicy::default();
+        let key = validation_cache_key(&dir, ValidationTarget::Standard, &policy).unwrap();
+        fs::create_dir_all(cache.dir()).unwrap();
+        fs::write(cache.dir().join(format!("{key}.json")), "not json").unwrap();
+
+        let diags = validate_cached(&dir, ValidationTarget::Standard, &policy, &cache);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(
+            render(&diags),
+            render(&validate_with_policy(
+                &dir,
+                ValidationTarget::Standard,
+                &policy
+            ))
+        );
+    }
+
+    #[test]
+    fn snippet_survives_the_cache() {
+        let parent = tempdir().unwrap();
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let d = Diagnostic::new(Severity::Error, E003, "bad")
+            .with_field("name")
+            .with_snippet("my_skill\n  ^");
+        cache.put("k", &[d]).unwrap();
+        let restored = cache.get("k").unwrap();
+        assert_eq!(restored[0].snippet.as_deref(), Some("my_skill\n  ^"));
+    }
+
+    #[test]
+    fn entry_with_unknown_field_is_a_miss() {
+        let parent = tempdir().unwrap();
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let d = Diagnostic::new(Severity::Warning, W001, "unknown field").with_field("metadata");
+        cache.put("k", &[d]).unwrap();
+        assert_eq!(cache.get("k").unwrap()[0].field, Some("metadata"));
+
+        let json = fs::read_to_string(cache.dir().join("k.json")).unwrap();
+        fs::write(
+            cache.dir().join("k.json"),
+            json.replace("\"metadata\"", "\"mystery\""),
+        )
+        .unwrap();
+        assert!(cache.get("k").is_none());
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    }
+
+    #[test]
+    fn missing_skill_md_is_not_cached() {
+        let parent = tempdir().unwrap();
+        let cache = ValidationCache::new(parent.path().join(CACHE_DIR));
+        let diags = validate_cached(
+            parent.path(),
+            ValidationTarget::Standard,
+            &PlaceholderPolicy::default(),
+            &cache,
+        );
+        assert!(diags[0].is_error());
+        assert_eq!((cache.hits(), cache.misses()), (0, 0));
+        assert!(!cache.dir().exists());
+    }
+}
@@ -0,0 +1,455 @@
+//! Removal of generated artifacts.
+//!
+//! aigent's write-side features leave files behind by convention:
+//! - [`BACKUP_FILE`](crate::clean::BACKUP_FILE) — a copy of `SKILL.md` kept before a rewrite.
+//! - [`CACHE_FILE`](crate::clean::CACHE_FILE) — cached results such as scores.
+//! - [`CACHE_DIR`] — the validation cache (see [`crate::cache`]).
+//! - `<name>.draft/` — a draft saved from a failed build (see
+//!   [`SkillDraft::default_save_dir`](crate::builder::SkillDraft::default_save_dir)).
+//!   Only directories that also hold a
+//!   [`DRAFT_MARKER`](crate::validator::DRAFT_MARKER) file count, so a
+//!   hand-made `notes.draft/` is left alone.
+//! - An assembled plugin directory, when named explicitly with
+//!   [`CleanOptions::dist`].
+//!
+//! [`clean_artifacts`] finds and removes them under a root. Symlinks are
+//! never followed or removed, and nothing outside the root is touched.
+
+use std::path::{Path, PathBuf};
+
+pub use crate::cache::CACHE_DIR;
+use crate::errors::{AigentError, Result};
+use crate::validator::DRAFT_MARKER;
+
+/// File name of a `SKILL.md` backup.
+pub const BACKUP_FILE: &str = "SKILL.md.bak";
+
+/// File name of aigent's result cache.
+pub const CACHE_FILE: &str = ".aigent-cache.json";
+
+/// Directory name suffix of a saved build draft.
+pub const DRAFT_SUFFIX: &str = ".draft";
+
+/// Options for [`clean_artifacts`].
+#[derive(Debug, Clone, Default)]
+pub struct CleanOptions {
+    /// Search subdirectories of the root, not just the root itself.
+    pub recursive: bool,
+    /// List artifacts without removing them.
+    pub dry_run: bool,
+    /// Assembled plugin directory to remove as well. Must be inside the
+    /// root; skipped if it does not exist or is a symlink.
+    pub dist: Option<PathBuf>,
+}
+
+/// Kind of generated artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    /// A [`BACKUP_FILE`].
+    Backup,
+    /// A [`CACHE_FILE`] or [`CACHE_DIR`].
+    Cache,
+    /// A `<name>.draft/` directory with a draft marker.
+    Draft,
+    /// The directory named by [`CleanOptions::dist`].
+    Dist,
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Backup => "backup",
+            Self::Cache => "cache",
+            Self::Draft => "draft",
+            Self::Dist => "dist",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A generated artifact found by [`clean_artifacts`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Artifact {
+    /// Path to the file or directory.
+    pub path: PathBuf,
+    /// What kind of artifact it is.
+    pub kind: ArtifactKind,
+    /// Size in bytes; for a directory, the total of the regular files in it.
+    pub bytes: u64,
+}
+
+/// Outcome of [`clean_artifacts`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct CleanReport {
+    /// Artifacts removed, or found in a dry run, sorted by path.
+    pub artifacts: Vec<Artifact>,
+    /// Whether this was a dry run (nothing was removed).
+    pub dry_run: bool,
+}
+
+impl CleanReport {
+    /// Total size of the artifacts in bytes.
+    #[must_use]
+    pub fn bytes(&self) -> u64 {
+        self.artifacts.iter().map(|a| a.bytes).sum()
+    }
+}
+
+/// Find and remove aigent's generated artifacts under `root`.
+///
+/// Without [`recursive`](CleanOptions::recursive), only the root's own
+/// entries are considered. Recursive search skips symlinked directories
+/// and `.git`, and does not look inside draft or dist directories, which
+/// are removed whole.
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a directory, if
+/// [`dist`](CleanOptions::dist) is the root or lies outside it, or if an
+/// artifact cannot be removed. Artifacts removed before the failure stay
+/// removed.
+pub fn clean_artifacts(root: &Path, options: &CleanOptions) -> Result<CleanReport> {
+    if !root.is_dir() {
+        return Err(invalid_input(format!(
+            "not a directory: {}",
+            root.display()
+        )));
+    }
+
+    let mut artifacts = Vec::new();
+    let dist = match &options.dist {
+        Some(dist) => dist_artifact(root, dist)?,
+        None => None,
+    };
+    let dist_canonical = dist.as_ref().and_then(|a| a.path.canonicalize().ok());
+    scan(
+        root,
+        options.recursive,
+        dist_canonical.as_deref(),
+        &mut artifacts,
+    );
+    artifacts.extend(dist);
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if !options.dry_run {
+        for artifact in &artifacts {
+            // Re-check right before removal: never remove through a symlink.
+            if crate::fs_util::is_symlink(&artifact.path) {
+                continue;
+            }
+            if crate::fs_util::is_regular_dir(&artifact.path) {
+                std::fs::remove_dir_all(&artifact.path)?;
+            } else {
+                std::fs::remove_file(&artifact.path)?;
+            }
+        }
+    }
+
+    Ok(CleanReport {
+        artifacts,
+        dry_run: options.dry_run,
+    })
+}
+
+/// Resolve [`CleanOptions::dist`] to an artifact, checking it is inside
+/// `root`.
+fn dist_artifact(root: &Path, dist: &Path) -> Result<Option<Artifact>> {
+    if !crate::fs_util::is_regular_dir(dist) {
+        return Ok(None);
+    }
+    let root_canonical = root.canonicalize()?;
+    let dist_canonical = dist.canonicalize()?;
+    if dist_canonical == root_canonical || !dist_canonical.starts_with(&root_canonical) {
+        return Err(invalid_input(format!(
+            "dist directory {} must be inside {}",
+            dist.display(),
+            root.display()
+        )));
+    }
+    Ok(Some(Artifact {
+        path: dist.to_path_buf(),
+        kind: ArtifactKind::Dist,
+        bytes: dir_size(dist),
+    }))
+}
+
+/// Collect the artifacts among `dir`'s entries, descending into
+/// subdirectories when `recursive`.
+fn scan(dir: &Path, recursive: bool, dist: Option<&Path>, artifacts: &mut Vec<Artifact>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = path.symlink_metadata() else {
+            continue;
+        };
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if meta.is_file() {
+            let kind = match name.as_ref() {
+                BACKUP_FILE => ArtifactKind::Backup,
+                CACHE_FILE => ArtifactKind::Cache,
+                _ => continue,
+            };
+            artifacts.push(Artifact {
+                path,
+                kind,
+                bytes: meta.len(),
+            });
+        } else if meta.is_dir() {
+            if is_draft_dir(&path, &name) || name == CACHE_DIR {
+                let bytes = dir_size(&path);
+                let kind = if name == CACHE_DIR {
+                    ArtifactKind::Cache
+                } else {
+                    ArtifactKind::Draft
+                };
+                artifacts.push(Artifact { path, kind, bytes });
+            } else if recursive
+                && name != ".git"
+                && dist.is_none_or(|d| path.canonicalize().ok().as_deref() != Some(d))
+            {
+                scan(&path, recursive, dist, artifacts);
+            }
+        }
+    }
+}
+
+/// Returns `true` if `path`, named `name`, is a saved build draft.
+fn is_draft_dir(path: &Path, name: &str) -> bool {
+    name.len() > DRAFT_SUFFIX.len()
+        && name.ends_with(DRAFT_SUFFIX)
+        && crate::fs_util::is_regular_file(&path.join(DRAFT_MARKER))
+}
+
+/// Total size of the regular files under `dir`, not following symlinks.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.path().symlink_metadata().ok()?;
+            if meta.is_file() {
+                Some(meta.len())
+            } else if meta.is_dir() {
+                Some(dir_size(&entry.path()))
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+/// An `InvalidInput` I/O error with `message`.
+fn invalid_input(message: String) -> AigentError {
+    AigentError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        message,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// Create a tree with one artifact of each kind and decoys with similar
+    /// names. Returns the root.
+    fn make_tree() -> tempfile::TempDir {
+        let root = tempdir().unwrap();
+        let r = root.path();
+        fs::create_dir_all(r.join("skill/nested")).unwrap();
+        fs::write(r.join("SKILL.md.bak"), "old").unwrap();
+        fs::write(r.join("skill/.aigent-cache.json"), "{}").unwrap();
+        fs::write(r.join("skill/nested/SKILL.md.bak"), "older").unwrap();
+        fs::create_dir(r.join("bold-text.draft")).unwrap();
+        fs::write(r.join("bold-text.draft").join(DRAFT_MARKER), "").unwrap();
+        fs::write(r.join("bold-text.draft/SKILL.md"), "draft body").unwrap();
+
+        // Decoys.
+        fs::write(r.join("skill/SKILL.md"), "keep").unwrap();
+        fs::write(r.join("SKILL.md.bak.txt"), "keep").unwrap();
+        fs::write(r.join("skill/aigent-cache.json"), "keep").unwrap();
+        fs::write(r.join(".aigent-cache.json.old"), "keep").unwrap();
+        fs::create_dir(r.join("notes.draft")).unwrap();
+        fs::write(r.join("notes.draft/todo.md"), "keep").unwrap();
+        fs::create_dir(r.join(".draft")).unwrap();
+        fs::write(r.join(".draft").join(DRAFT_MARKER), "").unwrap();
+        fs::create_dir(r.join("SKILL.md.bak.d")).unwrap();
+        root
+    }
+
+    fn kinds(report: &CleanReport) -> Vec<ArtifactKind> {
+        report.artifacts.iter().map(|a| a.kind).collect()
+    }
+
+    #[test]
+    fn recursive_clean_removes_only_artifacts() {
+        let root = make_tree();
+        let r = root.path();
+        let report = clean_artifacts(
+            r,
+            &CleanOptions {
+                recursive: true,
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            kinds(&report),
+            vec![
+                ArtifactKind::Backup,
+                ArtifactKind::Draft,
+                ArtifactKind::Cache,
+                ArtifactKind::Backup
+            ]
+        );
+        assert_eq!(report.bytes(), 3 + 10 + 2 + 5);
+        assert!(!r.join("SKILL.md.bak").exists());
+        assert!(!r.join("bold-text.draft").exists());
+        assert!(!r.join("skill/.aigent-cache.json").exists());
+        assert!(!r.join("skill/nested/SKILL.md.bak").exists());
+        for decoy in [
+            "skill/SKILL.md",
+            "SKILL.md.bak.txt",
+            "skill/aigent-cache.json",
+            ".aigent-cache.json.old",
+            "notes.draft/todo.md",
+            ".draft",
+            "SKILL.md.bak.d",
+        ] {
+            assert!(r.join(decoy).exists(), "decoy removed: {decoy}");
+        }
+    }
+
+    #[test]
+    fn non_recursive_clean_stays_at_root() {
+        let root = make_tree();
+        let report = clean_artifacts(root.path(), &CleanOptions::default()).unwrap();
+        assert_eq!(
+            kinds(&report),
+            vec![ArtifactKind::Backup, ArtifactKind::Draft]
+        );
+        assert!(root.path().join("skill/.aigent-cache.json").exists());
+    }
+
+    #[test]
+    fn validation_cache_dir_is_a_cache_artifact() {
+        let root = tempdir().unwrap();
+        let cache = root.path().join(CACHE_DIR);
+        fs::create_dir(&cache).unwrap();
+        fs::write(cache.join("0123.json"), "[]").unwrap();
+        let report = clean_artifacts(root.path(), &CleanOptions::default()).unwrap();
+        assert_eq!(kinds(&report), vec![ArtifactKind::Cache]);
+        assert_eq!(report.bytes(), 2);
+        assert!(!cache.exists());
+    }
+
+    #[test]
+    fn dry_run_removes_nothing() {
+        let root = make_tree();
+        let report = clean_artifacts(
+            root.path(),
+            &CleanOptions {
+                recursive: true,
+                dry_run: true,
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.artifacts.len(), 4);
+        assert!(root.path().join("SKILL.md.bak").exists());
+        assert!(root.path().join("bold-text.draft").exists());
+    }
+
+    #[test]
+    fn dist_is_removed_when_inside_root() {
+        let root = make_tree();
+        let dist = root.path().join("dist");
+        fs::create_dir_all(dist.join("skills/a")).unwrap();
+        fs::write(dist.join("skills/a/SKILL.md.bak"), "x").unwrap();
+        fs::write(dist.join("plugin.json"), "{}").unwrap();
+        let report = clean_artifacts(
+            root.path(),
+            &CleanOptions {
+                recursive: true,
+                dist: Some(dist.clone()),
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap();
+        let dist_entries: Vec<_> = report
+            .artifacts
+            .iter()
+            .filter(|a| a.path.starts_with(&dist))
+            .collect();
+        assert_eq!(dist_entries.len(), 1, "{dist_entries:?}");
+        assert_eq!(dist_entries[0].kind, ArtifactKind::Dist);
+        assert_eq!(dist_entries[0].bytes, 3);
+        assert!(!dist.exists());
+    }
+
+    #[test]
+    fn dist_outside_root_is_rejected() {
+        let root = make_tree();
+        let outside = tempdir().unwrap();
+        let err = clean_artifacts(
+            root.path(),
+            &CleanOptions {
+                dist: Some(outside.path().to_path_buf()),
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must be inside"), "{err}");
+        assert!(outside.path().exists());
+        assert!(root.path().join("SKILL.md.bak").exists());
+
+        let err = clean_artifacts(
+            root.path(),
+            &CleanOptions {
+                dist: Some(root.path().to_path_buf()),
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must be inside"), "{err}");
+    }
+
+    #[test]
+    fn missing_root_is_an_error() {
+        let root = tempdir().unwrap();
+        assert!(clean_artifacts(&root.path().join("missing"), &CleanOptions::default()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_skipped() {
+        let root = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+        fs::write(outside.path().join("SKILL.md.bak"), "outside").unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("linked")).unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("SKILL.md.bak"),
+            root.path().join("SKILL.md.bak"),
+        )
+        .unwrap();
+        let report = clean_artifacts(
+            root.path(),
+            &CleanOptions {
+                recursive: true,
+                dist: Some(root.path().join("linked")),
+                ..CleanOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(report.artifacts.is_empty(), "{:?}", report.artifacts);
+        assert!(outside.path().join("SKILL.md.bak").exists());
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+
+pub(crate) fn run(skill_dir: &Path, opts: &aigent::HistoryOptions, format: super::Format) {
+    let dir = super::resolve_skill_dir(skill_dir);
+    let result = match aigent::bisect(&dir, opts) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("aigent bisect: {e}");
+            super::exit_for(&e);
+        }
+    };
+    match format {
+        super::Format::Text => {
+            print!("{}", aigent::history::format_bisect_text(&result));
+        }
+        super::Format::Json => {
+            println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        }
+    }
+    // A regression, or a skill that failed throughout, is a finding.
+    if result.regression.is_some() || result.is_failing() {
+        super::exit(super::Exit::Findings);
+    }
+}
@@ -1,26 +1,70 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-pub(crate) fn run(skill_dirs: Vec<PathBuf>, output: PathBuf, name: Option<String>, validate: bool) {
-    let dirs: Vec<&std::path::Path> = skill_dirs.iter().map(|p| p.as_path()).collect();
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run(
+    skill_dirs: Vec<PathBuf>,
+    output: PathBuf,
+    name: Option<String>,
+    validate: bool,
+    bundle_shared: bool,
+    no_tests: bool,
+    exclude: Vec<String>,
+    namespace: Option<String>,
+    manifest_out: Option<PathBuf>,
+) {
+    let dirs: Vec<&Path> = skill_dirs.iter().map(|p| p.as_path()).collect();
     let opts = aigent::AssembleOptions {
         output_dir: output,
         name,
         validate,
+        bundle_shared,
+        include_tests: !no_tests,
+        exclude,
+        namespace,
     };
     match aigent::assemble_plugin(&dirs, &opts) {
         Ok(result) => {
             for w in &result.warnings {
                 eprintln!("warning: {}: {}", w.dir.display(), w.message);
             }
+            if let Some(path) = &manifest_out {
+                write_manifest(path, &result);
+            }
             println!(
                 "Assembled {} skill(s) into {}",
                 result.skills_count,
                 result.plugin_dir.display()
             );
+            if result.replaced {
+                eprintln!(
+                    "Replaced previous output in {}",
+                    result.plugin_dir.display()
+                );
+            }
+            if result.excluded_files > 0 {
+                eprintln!("Excluded {} file(s)", result.excluded_files);
+            }
         }
         Err(e) => {
             eprintln!("aigent build: {e}");
-            std::process::exit(1);
+            super::exit_for(&e);
         }
     }
 }
+
+/// Write a JSON record of the assembled plugin (name, version, skills with
+/// their files and content hashes) for build provenance.
+fn write_manifest(path: &Path, result: &aigent::AssembleResult) {
+    let manifest = serde_json::json!({
+        "name": result.plugin_name,
+        "version": result.version,
+        "plugin_dir": result.plugin_dir.display().to_string(),
+        "hash_algorithm": "sha256",
+        "skills": result.skills,
+    });
+    let json = serde_json::to_string_pretty(&manifest).unwrap();
+    std::fs::write(path, json + "\n").unwrap_or_else(|e| {
+        eprintln!("aigent build: failed to write {}: {e}", path.display());
+        super::exit(super::Exit::Failure);
+    });
+}
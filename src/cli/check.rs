@@ -1,17 +1,25 @@
 use std::path::PathBuf;
 
-use aigent::diagnostics::{Diagnostic, ValidationTarget};
+use aigent::diagnostics::{Diagnostic, DiagnosticCode, ValidationTarget};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     skill_dirs: Vec<PathBuf>,
     format: super::Format,
     target: super::Target,
     no_validate: bool,
-    structure: bool,
+    structure: Option<aigent::StructureOptions>,
     recursive: bool,
+    discovery: aigent::DiscoveryOptions,
     apply_fixes: bool,
+    fix_interactive: bool,
+    extract_reference: Option<usize>,
+    verbose: bool,
+    summary_report: bool,
+    metrics: bool,
+    lint: &aigent::LintOptions,
 ) {
-    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive);
+    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive, discovery);
     for w in &disc_warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
@@ -21,7 +29,7 @@ pub(crate) fn run(
         } else {
             eprintln!("Usage: aigent check <skill-dir> [<skill-dir>...]");
         }
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
     }
 
     let mut all_diags: Vec<(PathBuf, Vec<Diagnostic>)> = Vec::new();
@@ -36,7 +44,7 @@ pub(crate) fn run(
 
             // Apply fixes if requested.
             if apply_fixes {
-                match aigent::apply_fixes(dir, &diags) {
+                match aigent::apply_fixes(dir, &with_lint(&diags, dir, lint)) {
                     Ok(count) if count > 0 => {
                         eprintln!("Applied {count} fix(es) to {}", dir.display());
                         diags = aigent::validate_with_target(dir, target_val);
@@ -47,48 +55,143 @@ pub(crate) fn run(
                     }
                 }
             }
-        }
 
-        // Always run semantic lint checks (the core of `check`).
-        match aigent::read_properties(dir) {
-            Ok(props) => {
-                let body = aigent::read_body(dir).unwrap_or_default();
-                diags.extend(aigent::lint(&props, &body));
+            // Move overlong body content into REFERENCE.md if requested.
+            if let Some(keep) = extract_reference {
+                match aigent::extract_reference(dir, keep) {
+                    Ok(Some(r)) => {
+                        eprintln!(
+                            "Moved {} line(s) into {}",
+                            r.moved_lines,
+                            r.reference_path.display()
+                        );
+                        diags = aigent::validate_with_target(dir, target_val);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        eprintln!(
+                            "warning: could not extract reference from {}: {e}",
+                            dir.display()
+                        );
+                    }
+                }
             }
-            Err(e) => {
-                // Report parse failures as diagnostics rather than silently skipping.
-                diags.push(Diagnostic::new(
-                    aigent::Severity::Error,
-                    "E000",
-                    format!("cannot read properties: {e}"),
-                ));
+
+            // Step through fixes one by one if requested.
+            if fix_interactive {
+                let mut stdin = std::io::stdin().lock();
+                match aigent::apply_fixes_interactive(
+                    dir,
+                    &with_lint(&diags, dir, lint),
+                    &mut stdin,
+                ) {
+                    Ok(result) => {
+                        if result.accepted + result.rejected > 0 {
+                            eprintln!(
+                                "{}: {} fix(es) accepted, {} rejected",
+                                dir.display(),
+                                result.accepted,
+                                result.rejected
+                            );
+                        }
+                        if result.accepted > 0 {
+                            diags = aigent::validate_with_target(dir, target_val);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("aigent check: {}: {e}", dir.display());
+                        super::exit_for(&e);
+                    }
+                }
             }
         }
 
+        // Always run semantic lint checks (the core of `check`).
+        diags.extend(lint_dir(dir, lint));
+
         // Append structure checks if requested.
-        if structure {
-            diags.extend(aigent::validate_structure(dir));
+        if let Some(options) = &structure {
+            diags.extend(aigent::validate_structure_with(dir, options));
+        }
+
+        // Annotate body readability if requested.
+        if metrics {
+            let body = aigent::read_body(dir).unwrap_or_default();
+            diags.push(aigent::metrics_diagnostic(&aigent::body_metrics(&body)));
+        }
+
+        // Hints are soft style nudges; only show them when asked.
+        if !verbose {
+            diags.retain(|d| !d.is_hint());
         }
 
         all_diags.push((dir.clone(), diags));
     }
 
+    report(format, &all_diags, summary_report);
+}
+
+/// Check `SKILL.md` content read from stdin (`--stdin`).
+///
+/// `stdin_path` names the real file for display and the name/directory
+/// check; without it the content is reported as `<stdin>`. Directory-based
+/// checks (structure, fixes) do not apply.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_stdin(
+    format: super::Format,
+    target: super::Target,
+    no_validate: bool,
+    verbose: bool,
+    summary_report: bool,
+    metrics: bool,
+    lint: &aigent::LintOptions,
+    stdin_path: Option<PathBuf>,
+) {
+    let content = super::read_stdin("check");
+    let (path, dir) = super::stdin_source(stdin_path);
+
+    let mut diags = Vec::new();
+    if !no_validate {
+        diags.extend(aigent::validate_content_in_dir(
+            &content,
+            dir.as_deref(),
+            target.into(),
+            &aigent::PlaceholderPolicy::default(),
+        ));
+    }
+    diags.extend(lint_content(&content, lint));
+    if metrics {
+        let body = aigent::parse_frontmatter(&content)
+            .map(|(_, body)| body)
+            .unwrap_or_default();
+        diags.push(aigent::metrics_diagnostic(&aigent::body_metrics(&body)));
+    }
+    if !verbose {
+        diags.retain(|d| !d.is_hint());
+    }
+
+    report(format, &[(path, diags)], summary_report);
+}
+
+/// Print check results in `format`, exiting 1 if any has an error.
+fn report(format: super::Format, all_diags: &[(PathBuf, Vec<Diagnostic>)], summary_report: bool) {
     let has_errors = all_diags
         .iter()
         .any(|(_, d)| d.iter().any(|d| d.is_error()));
 
     match format {
         super::Format::Text => {
+            let styler = super::stderr_styler();
             let multi = all_diags.len() > 1;
-            for (dir, diags) in &all_diags {
+            for (dir, diags) in all_diags {
                 if multi && !diags.is_empty() {
                     eprintln!("{}:", dir.display());
                 }
                 for d in diags {
                     if multi {
-                        eprintln!("  {d}");
+                        eprintln!("  {}", styler.diagnostic(d));
                     } else {
-                        eprintln!("{d}");
+                        eprintln!("{}", styler.diagnostic(d));
                     }
                 }
             }
@@ -105,13 +208,16 @@ pub(crate) fn run(
                     })
                     .count();
                 let ok = total - errors - warnings;
-                eprintln!("\n{total} skills: {ok} ok, {errors} errors, {warnings} warnings only");
+                eprintln!("\n{}", styler.summary(total, ok, errors, warnings));
             } else {
                 let total_diags: usize = all_diags.iter().map(|(_, d)| d.len()).sum();
                 if total_diags == 0 {
                     eprintln!("ok");
                 }
             }
+            if summary_report {
+                print_code_frequency(&aigent::diagnostics::code_frequency(all_diags));
+            }
         }
         super::Format::Json => {
             let entries: Vec<serde_json::Value> = all_diags
@@ -123,12 +229,95 @@ pub(crate) fn run(
                     })
                 })
                 .collect();
-            let json = serde_json::to_string_pretty(&entries).unwrap();
-            println!("{json}");
+            let json = if summary_report {
+                let summary: Vec<serde_json::Value> =
+                    aigent::diagnostics::code_frequency(all_diags)
+                        .into_iter()
+                        .map(|(code, count, skills)| {
+                            serde_json::json!({
+                                "code": code,
+                                "count": count,
+                                "skills": skills,
+                            })
+                        })
+                        .collect();
+                serde_json::json!({
+                    "skills": entries,
+                    "summary": summary,
+                })
+            } else {
+                serde_json::Value::Array(entries)
+            };
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
         }
     }
 
     if has_errors {
-        std::process::exit(1);
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// Print the `--summary-report` table: diagnostic codes by frequency, with
+/// the number of skills each affects.
+fn print_code_frequency(frequency: &[(DiagnosticCode, usize, usize)]) {
+    eprintln!("\nDiagnostics by frequency:");
+    if frequency.is_empty() {
+        eprintln!("  (none)");
+        return;
+    }
+    eprintln!("  {:<6} {:>6} {:>6}", "Code", "Count", "Skills");
+    for (code, count, skills) in frequency {
+        eprintln!("  {:<6} {count:>6} {skills:>6}", code.as_str());
+    }
+}
+
+/// Validation diagnostics plus lint diagnostics, for the fixers.
+///
+/// Some lint findings (description spacing) are fixable, so the fixers see
+/// both sets. The lint diagnostics are recomputed after fixing.
+fn with_lint(
+    diags: &[Diagnostic],
+    dir: &std::path::Path,
+    lint: &aigent::LintOptions,
+) -> Vec<Diagnostic> {
+    let mut all = diags.to_vec();
+    all.extend(lint_dir(dir, lint));
+    all
+}
+
+/// Run semantic lint checks on `SKILL.md` content held in memory.
+///
+/// Parse failures are reported as an `E000` diagnostic, as in [`lint_dir`].
+fn lint_content(content: &str, lint: &aigent::LintOptions) -> Vec<Diagnostic> {
+    match aigent::parse_properties(content) {
+        Ok(props) => {
+            let body = aigent::parse_frontmatter(content)
+                .map(|(_, body)| body)
+                .unwrap_or_default();
+            aigent::lint_with_options(&props, &body, lint)
+        }
+        Err(e) => vec![Diagnostic::new(
+            aigent::Severity::Error,
+            aigent::diagnostics::E000,
+            format!("cannot read properties: {e}"),
+        )],
+    }
+}
+
+/// Run the lint rules selected by `lint` on a skill directory.
+///
+/// Parse failures are reported as an `E000` diagnostic rather than silently
+/// skipped.
+pub(crate) fn lint_dir(dir: &std::path::Path, lint: &aigent::LintOptions) -> Vec<Diagnostic> {
+    match aigent::read_properties(dir) {
+        Ok(props) => {
+            let body = aigent::read_body(dir).unwrap_or_default();
+            aigent::lint_with_options(&props, &body, lint)
+        }
+        Err(e) => vec![Diagnostic::new(
+            aigent::Severity::Error,
+            aigent::diagnostics::E000,
+            format!("cannot read properties: {e}"),
+        )],
     }
 }
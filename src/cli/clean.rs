@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+pub(crate) fn run(path: PathBuf, recursive: bool, dry_run: bool, dist: Option<PathBuf>) {
+    let options = aigent::CleanOptions {
+        recursive,
+        dry_run,
+        dist,
+    };
+    let report = match aigent::clean_artifacts(&path, &options) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("aigent clean: {e}");
+            super::exit_for(&e);
+        }
+    };
+    let verb = if report.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+    for artifact in &report.artifacts {
+        println!("{verb} {}: {}", artifact.kind, artifact.path.display());
+    }
+    let count = report.artifacts.len();
+    if count == 0 {
+        println!("No artifacts found");
+    } else {
+        let noun = if count == 1 { "artifact" } else { "artifacts" };
+        let freed = if report.dry_run {
+            "would be freed"
+        } else {
+            "freed"
+        };
+        println!("{verb} {count} {noun}, {} bytes {freed}", report.bytes());
+    }
+}
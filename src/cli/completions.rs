@@ -0,0 +1,10 @@
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+pub(crate) fn run(shell: Shell) {
+    let mut cmd = super::Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+}
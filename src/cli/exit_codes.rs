@@ -0,0 +1,88 @@
+//! Process exit codes shared by every command.
+//!
+//! [`EXIT_CODES`] is the single source for both the codes the commands use
+//! and the `--help-exit-codes` listing, so the two cannot drift.
+
+use aigent::AigentError;
+
+/// Outcome of a command, as reported by its exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Exit {
+    /// The command succeeded and found nothing to report or change.
+    Success,
+    /// Findings: errors in the skills, a failed gate, or output that
+    /// changed (or would change).
+    Findings,
+    /// Usage error: invalid flags or arguments, no skills to act on, or a
+    /// feature missing from this build.
+    Usage,
+    /// I/O or internal error: a file could not be read or written, or the
+    /// command could not run to completion.
+    Failure,
+}
+
+/// Every exit code with its meaning, in ascending order.
+pub(crate) const EXIT_CODES: &[(Exit, &str)] = &[
+    (Exit::Success, "Success: no errors found and nothing changed"),
+    (
+        Exit::Findings,
+        "Findings: errors found, a score or test gate failed, or files were (or would be) changed",
+    ),
+    (
+        Exit::Usage,
+        "Usage error: invalid flags or arguments, no skills found, or a feature missing from this build",
+    ),
+    (
+        Exit::Failure,
+        "I/O or internal error: a file could not be read or written, or the command could not run",
+    ),
+];
+
+impl Exit {
+    /// Numeric process exit code.
+    pub(crate) const fn code(self) -> i32 {
+        match self {
+            Self::Success => 0,
+            Self::Findings => 1,
+            Self::Usage => 2,
+            Self::Failure => 3,
+        }
+    }
+
+    /// Exit code for a library error.
+    ///
+    /// Problems in the skills themselves are findings; an existing target or
+    /// an invalid path argument is a usage error; other I/O errors are
+    /// failures.
+    pub(crate) fn for_error(error: &AigentError) -> Self {
+        match error {
+            AigentError::Parse { .. }
+            | AigentError::Validation { .. }
+            | AigentError::Yaml(_)
+            | AigentError::Build { .. }
+            | AigentError::InvalidDraft { .. } => Self::Findings,
+            AigentError::AlreadyExists { .. } => Self::Usage,
+            AigentError::Io(e) if e.kind() == std::io::ErrorKind::InvalidInput => Self::Usage,
+            AigentError::Io(_) => Self::Failure,
+        }
+    }
+}
+
+/// Exit the process with `code`.
+pub(crate) fn exit(code: Exit) -> ! {
+    std::process::exit(code.code())
+}
+
+/// Exit with the code for `error` (see [`Exit::for_error`]).
+pub(crate) fn exit_for(error: &AigentError) -> ! {
+    exit(Exit::for_error(error))
+}
+
+/// The exit-code listing printed by `--help-exit-codes`.
+pub(crate) fn format_exit_codes() -> String {
+    let mut out = String::from("Exit codes:\n");
+    for (exit, meaning) in EXIT_CODES {
+        out.push_str(&format!("  {}  {meaning}\n", exit.code()));
+    }
+    out
+}
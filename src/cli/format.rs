@@ -1,7 +1,13 @@
 use std::path::PathBuf;
 
-pub(crate) fn run(skill_dirs: Vec<PathBuf>, check: bool, recursive: bool) {
-    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive);
+pub(crate) fn run(
+    skill_dirs: Vec<PathBuf>,
+    check: bool,
+    recursive: bool,
+    discovery: aigent::DiscoveryOptions,
+    keep_preamble: bool,
+) {
+    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive, discovery);
     for w in &disc_warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
@@ -11,20 +17,21 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, check: bool, recursive: bool) {
         } else {
             eprintln!("Usage: aigent format <skill-dir> [<skill-dir>...]");
         }
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
     }
 
+    let options = aigent::FormatOptions { keep_preamble };
     let mut any_changed = false;
     let mut any_error = false;
     for dir in &dirs {
-        match aigent::format_skill(dir) {
+        match aigent::format_skill_with(dir, &options) {
             Ok(result) => {
                 if result.changed {
                     any_changed = true;
                     if check {
                         eprintln!("Would reformat: {}", dir.display());
                         let diff = aigent::diff_skill(&result, &dir.display().to_string());
-                        eprint!("{diff}");
+                        eprint!("{}", super::stderr_styler().diff(&diff));
                     } else {
                         let path = aigent::find_skill_md(dir).unwrap();
                         if !aigent::is_regular_file(&path) {
@@ -32,11 +39,11 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, check: bool, recursive: bool) {
                                 "aigent format: target is no longer a regular file: {}",
                                 path.display()
                             );
-                            std::process::exit(1);
+                            super::exit(super::Exit::Failure);
                         }
                         std::fs::write(&path, &result.content).unwrap_or_else(|e| {
                             eprintln!("aigent format: failed to write {}: {e}", path.display());
-                            std::process::exit(1);
+                            super::exit(super::Exit::Failure);
                         });
                         eprintln!("Formatted {}", dir.display());
                     }
@@ -55,6 +62,36 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, check: bool, recursive: bool) {
     }
 
     if any_error || (check && any_changed) {
-        std::process::exit(1);
+        super::exit(super::Exit::Findings);
     }
 }
+
+/// Format `SKILL.md` content read from stdin (`--stdin`).
+///
+/// Writes the formatted content to stdout. With `check`, prints a diff to
+/// stderr instead and exits 1 if the content would change.
+pub(crate) fn run_stdin(check: bool, keep_preamble: bool) {
+    let original = super::read_stdin("format");
+    let options = aigent::FormatOptions { keep_preamble };
+    let content = aigent::format_content_with(&original, &options).unwrap_or_else(|e| {
+        eprintln!("aigent format: <stdin>: {e}");
+        super::exit_for(&e);
+    });
+    let result = aigent::FormatResult {
+        changed: content != original,
+        content,
+        original,
+    };
+
+    if !check {
+        print!("{}", result.content);
+        return;
+    }
+    if result.changed {
+        eprintln!("Would reformat: <stdin>");
+        let diff = aigent::diff_skill(&result, "<stdin>");
+        eprint!("{}", super::stderr_styler().diff(&diff));
+        super::exit(super::Exit::Findings);
+    }
+    eprintln!("ok");
+}
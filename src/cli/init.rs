@@ -2,15 +2,24 @@ use std::path::PathBuf;
 
 use aigent::builder::template::SkillTemplate;
 
-pub(crate) fn run(dir: Option<PathBuf>, template: SkillTemplate, minimal: bool) {
+pub(crate) fn run(dir: Option<PathBuf>, template: SkillTemplate, minimal: bool, draft: bool) {
     let target = dir.unwrap_or_else(|| PathBuf::from("."));
     match aigent::init_skill(&target, template, minimal) {
         Ok(path) => {
             println!("Created {}", path.display());
+            if draft {
+                match aigent::mark_draft(&target) {
+                    Ok(marker) => println!("Marked as draft: {}", marker.display()),
+                    Err(e) => {
+                        eprintln!("aigent init: {e}");
+                        super::exit_for(&e);
+                    }
+                }
+            }
         }
         Err(e) => {
             eprintln!("aigent init: {e}");
-            std::process::exit(1);
+            super::exit_for(&e);
         }
     }
 }
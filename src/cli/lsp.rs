@@ -0,0 +1,18 @@
+pub(crate) fn run() {
+    #[cfg(feature = "lsp")]
+    {
+        let stdin = std::io::stdin().lock();
+        let stdout = std::io::stdout().lock();
+        if let Err(e) = aigent::lsp::serve(stdin, stdout) {
+            eprintln!("aigent lsp: {e}");
+            super::exit(super::Exit::Failure);
+        }
+    }
+    #[cfg(not(feature = "lsp"))]
+    {
+        eprintln!(
+            "The language server requires the 'lsp' feature. Rebuild with: cargo build --features lsp"
+        );
+        super::exit(super::Exit::Usage);
+    }
+}
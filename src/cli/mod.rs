@@ -1,15 +1,24 @@
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
 use clap::{Parser, Subcommand, ValueEnum};
 
 use aigent::builder::template::SkillTemplate;
 use aigent::diagnostics::ValidationTarget;
 
+use exit_codes::{exit, exit_for, Exit};
+
+mod bisect;
 mod build;
 mod check;
+mod clean;
+mod completions;
 mod doc;
+mod exit_codes;
 mod format;
 mod init;
+mod lsp;
 mod new;
 mod probe;
 mod prompt;
@@ -21,12 +30,14 @@ mod validate;
 mod validate_plugin;
 #[cfg(feature = "watch")]
 mod watch;
+mod workspace;
 
 #[derive(Parser)]
 #[command(
     name = "aigent",
     version,
-    about = "AI agent skill builder and validator"
+    about = "AI agent skill builder and validator",
+    after_long_help = exit_codes::format_exit_codes()
 )]
 pub struct Cli {
     #[command(subcommand)]
@@ -35,6 +46,18 @@ pub struct Cli {
     /// Show project information
     #[arg(long)]
     about: bool,
+
+    /// List the exit codes shared by every command
+    #[arg(long, hide = true)]
+    help_exit_codes: bool,
+
+    /// When to color text output: auto (terminal only, honoring NO_COLOR), always, or never
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: aigent::ColorChoice,
+
+    /// Disable colored output (same as --color never)
+    #[arg(long, global = true)]
+    no_color: bool,
 }
 
 /// Output format for validation results.
@@ -47,6 +70,40 @@ enum Format {
     Json,
 }
 
+/// Output format for commands that also produce CI test reports.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum ReportFormat {
+    /// Human-readable text output (default)
+    #[default]
+    Text,
+    /// JSON output
+    Json,
+    /// JUnit XML report for CI test-report ingestion
+    Junit,
+}
+
+/// Output format for `score`, which can also render markdown for PR comments.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum ScoreFormat {
+    /// Human-readable text output (default)
+    #[default]
+    Text,
+    /// JSON output
+    Json,
+    /// Markdown table of checks, points, and status
+    Markdown,
+}
+
+/// Output format for the `doc` skill catalog.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum DocFormat {
+    /// Markdown document (default)
+    #[default]
+    Markdown,
+    /// JSON array of catalog entries
+    Json,
+}
+
 /// Validation target profile for controlling known-field detection.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 enum Target {
@@ -69,6 +126,44 @@ impl From<Target> for ValidationTarget {
     }
 }
 
+/// Metric `bisect` evaluates at each revision.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum BisectMetric {
+    /// Quality score below the minimum (default)
+    #[default]
+    Score,
+    /// Any validation error
+    Validate,
+}
+
+impl From<BisectMetric> for aigent::HistoryMetric {
+    fn from(m: BisectMetric) -> Self {
+        match m {
+            BisectMetric::Score => aigent::HistoryMetric::Score,
+            BisectMetric::Validate => aigent::HistoryMetric::Validate,
+        }
+    }
+}
+
+/// Which skills cross-skill conflict detection compares.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum ConflictScope {
+    /// Every pair of validated skills (default)
+    #[default]
+    Global,
+    /// Only skills in the same parent directory
+    Sibling,
+}
+
+impl From<ConflictScope> for aigent::ConflictScope {
+    fn from(s: ConflictScope) -> Self {
+        match s {
+            ConflictScope::Global => aigent::ConflictScope::Global,
+            ConflictScope::Sibling => aigent::ConflictScope::Sibling { levels: 1 },
+        }
+    }
+}
+
 /// Output format for prompt generation.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 enum PromptOutputFormat {
@@ -94,6 +189,46 @@ impl From<PromptOutputFormat> for aigent::prompt::PromptFormat {
     }
 }
 
+/// Order of skills in generated prompts.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+enum PromptSort {
+    /// Order the directories were given in
+    #[default]
+    Input,
+    /// Alphabetical by skill name
+    Name,
+    /// Highest metadata.priority first (unset is 0), ties by name
+    Priority,
+}
+
+/// Agent environment to shape prompt instructions for.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PromptTarget {
+    /// Bare API: read the skill's SKILL.md and follow it
+    Standard,
+    /// Claude Code: invoke with the Skill tool or a /<name> slash command
+    ClaudeCode,
+}
+
+impl From<PromptTarget> for aigent::prompt::PromptTarget {
+    fn from(t: PromptTarget) -> Self {
+        match t {
+            PromptTarget::Standard => aigent::prompt::PromptTarget::Standard,
+            PromptTarget::ClaudeCode => aigent::prompt::PromptTarget::ClaudeCode,
+        }
+    }
+}
+
+impl From<PromptSort> for aigent::prompt::PromptSort {
+    fn from(s: PromptSort) -> Self {
+        match s {
+            PromptSort::Input => aigent::prompt::PromptSort::Input,
+            PromptSort::Name => aigent::prompt::PromptSort::Name,
+            PromptSort::Priority => aigent::prompt::PromptSort::Priority,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 #[command(next_display_order = None)]
 enum Commands {
@@ -103,23 +238,71 @@ enum Commands {
         #[arg(default_value = ".")]
         skill_dirs: Vec<PathBuf>,
         /// Output format
-        #[arg(long, value_enum, default_value_t = Format::Text)]
-        format: Format,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        /// With --format json, wrap the results in an object with a summary of counts
+        #[arg(long)]
+        summary: bool,
         /// Validation target profile
         #[arg(long, value_enum, default_value_t = Target::Standard)]
         target: Target,
         /// Run directory structure checks
         #[arg(long)]
         structure: bool,
+        /// With --structure, also check files under examples/ (parse, shebang, referenced)
+        #[arg(long, requires = "structure")]
+        check_examples: bool,
         /// Discover skills recursively
         #[arg(long)]
         recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
         /// Apply automatic fixes for fixable issues
         #[arg(long)]
         apply_fixes: bool,
         /// Watch for changes and re-validate (requires 'watch' feature)
         #[arg(long)]
         watch: bool,
+        /// Check each root's INDEX.md against discovered skills (implies --recursive)
+        #[arg(long)]
+        index: bool,
+        /// Which skills cross-skill conflict detection compares
+        #[arg(long, value_enum, default_value_t = ConflictScope::Global)]
+        conflict_scope: ConflictScope,
+        /// Treat ${NAME} placeholders as deploy-time values: mask them for
+        /// character and length checks and list them in an info note
+        #[arg(long)]
+        allow_placeholders: bool,
+        /// With --allow-placeholders, characters counted for each placeholder
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = aigent::placeholders::DEFAULT_NOMINAL_LENGTH,
+            requires = "allow_placeholders"
+        )]
+        placeholder_length: usize,
+        /// Substitute VALUE for ${KEY} before validating (repeatable)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_substitution)]
+        substitute: Vec<(String, String)>,
+        /// Read SKILL.md content from stdin instead of skill directories
+        #[arg(
+            long,
+            conflicts_with_all = ["skill_dirs", "recursive", "structure", "apply_fixes", "watch", "index"]
+        )]
+        stdin: bool,
+        /// With --stdin, the file's real path (for the name/directory check and display)
+        #[arg(long, value_name = "PATH", requires = "stdin")]
+        stdin_path: Option<PathBuf>,
+        /// Reuse results for unchanged skills from .aigent-cache/
+        #[arg(long, overrides_with = "no_cache")]
+        cache: bool,
+        /// Validate every skill from scratch (the default; overrides --cache)
+        #[arg(long, overrides_with = "cache")]
+        no_cache: bool,
     },
     /// Run validate + semantic quality checks (superset of validate)
     #[command(alias = "lint")]
@@ -136,15 +319,65 @@ enum Commands {
         /// Skip spec conformance checks (semantic quality only)
         #[arg(long)]
         no_validate: bool,
+        /// Run every quality pass: structure checks, including examples/ files
+        #[arg(long)]
+        all: bool,
         /// Run directory structure checks
         #[arg(long)]
         structure: bool,
+        /// With --structure, also check files under examples/ (parse, shebang, referenced)
+        #[arg(long, requires = "structure")]
+        check_examples: bool,
         /// Discover skills recursively
         #[arg(long)]
         recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
         /// Apply automatic fixes for fixable issues
         #[arg(long)]
         apply_fixes: bool,
+        /// Step through fixable issues and choose which fixes to apply
+        #[arg(long, conflicts_with = "apply_fixes")]
+        fix_interactive: bool,
+        /// With --apply-fixes, fix an overlong body (W002) by moving content
+        /// after the first HEADINGS headings into REFERENCE.md
+        #[arg(
+            long,
+            value_name = "HEADINGS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            requires = "apply_fixes"
+        )]
+        extract_reference: Option<usize>,
+        /// Also show hints (soft style suggestions)
+        #[arg(long, short)]
+        verbose: bool,
+        /// After all skills, report diagnostic codes by frequency and the
+        /// number of skills each affects
+        #[arg(long)]
+        summary_report: bool,
+        /// Add body readability metrics as an info annotation (I016)
+        #[arg(long)]
+        metrics: bool,
+        /// Skip the lint rule with this code (repeatable, or comma-separated)
+        #[arg(long, value_name = "CODE", value_delimiter = ',', value_parser = parse_lint_code)]
+        lint_disable: Vec<aigent::diagnostics::DiagnosticCode>,
+        /// Run only the lint rules with these codes (comma-separated)
+        #[arg(long, value_name = "CODES", value_delimiter = ',', value_parser = parse_lint_code)]
+        lint_only: Option<Vec<aigent::diagnostics::DiagnosticCode>>,
+        /// Read SKILL.md content from stdin instead of skill directories
+        #[arg(
+            long,
+            conflicts_with_all = ["skill_dirs", "recursive", "all", "structure", "apply_fixes", "fix_interactive", "extract_reference"]
+        )]
+        stdin: bool,
+        /// With --stdin, the file's real path (for the name/directory check and display)
+        #[arg(long, value_name = "PATH", requires = "stdin")]
+        stdin_path: Option<PathBuf>,
     },
     /// Read skill properties as JSON
     #[command(alias = "read-properties")]
@@ -156,18 +389,39 @@ enum Commands {
     /// Generate prompt from skill directories
     #[command(alias = "to-prompt")]
     Prompt {
-        /// Paths to skill directories [default: .]
-        #[arg(default_value = ".")]
+        /// Paths to skill directories [default: . unless --group is given]
         skill_dirs: Vec<PathBuf>,
+        /// Add skills under PATH to a section labeled LABEL (repeatable;
+        /// sections follow argument order, positional paths form "default")
+        #[arg(long = "group", value_name = "LABEL=PATH", value_parser = parse_group)]
+        groups: Vec<(String, PathBuf)>,
         /// Output format
         #[arg(long, value_enum, default_value_t = PromptOutputFormat::Xml)]
         format: PromptOutputFormat,
         /// Show estimated token budget
         #[arg(long)]
         budget: bool,
+        /// Show the deduplicated allowed-tools of all skills (to stderr)
+        #[arg(long, alias = "dedupe-tools")]
+        tools_summary: bool,
         /// Write output to file instead of stdout (exit 0 = unchanged, 1 = changed)
         #[arg(long)]
         output: Option<PathBuf>,
+        /// Include skills marked `metadata.deprecated: true`
+        #[arg(long)]
+        include_deprecated: bool,
+        /// Add each SKILL.md's SHA-256 and modification time to its entry
+        #[arg(long)]
+        provenance: bool,
+        /// Leave out the skill with this name (repeatable)
+        #[arg(long, value_name = "NAME")]
+        exclude: Vec<String>,
+        /// Order of skills in the prompt
+        #[arg(long, value_enum, default_value_t = PromptSort::Input)]
+        sort: PromptSort,
+        /// Add instructions for invoking skills in this environment
+        #[arg(long, value_enum)]
+        target: Option<PromptTarget>,
     },
     /// Create a new skill from a natural language description
     #[command(alias = "create")]
@@ -189,12 +443,54 @@ enum Commands {
         /// Skip scaffolding of examples/ and scripts/ directories
         #[arg(long)]
         minimal: bool,
+        /// Template whose tools, body sections, and supporting files to add
+        #[arg(long, value_enum, default_value_t = SkillTemplate::Minimal)]
+        template: SkillTemplate,
+        /// Don't score the created skill
+        #[arg(long)]
+        no_score: bool,
     },
     /// Score a skill against best-practices checklist
     Score {
-        /// Path to skill directory or SKILL.md file [default: .]
-        #[arg(name = "skill-dir", default_value = ".")]
+        /// Paths to skill directories or SKILL.md files [default: .]
+        #[arg(default_value = ".")]
+        skill_dirs: Vec<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ScoreFormat::Text)]
+        format: ScoreFormat,
+        /// Discover skills recursively and print a score distribution summary
+        #[arg(long)]
+        recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
+        /// Minimum acceptable score (exit 1 if any skill scores below) [default: 100]
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..=100))]
+        min: Option<u32>,
+        /// Compare against an older revision of the skill and report changes
+        #[arg(long, value_name = "OLD_DIR", conflicts_with = "recursive")]
+        against: Option<PathBuf>,
+    },
+    /// Find the commit where a skill's score or validation regressed
+    Bisect {
+        /// Path to the skill directory or SKILL.md file [default: .]
+        #[arg(default_value = ".")]
         skill_dir: PathBuf,
+        /// Metric to evaluate at each revision
+        #[arg(long, value_enum, default_value_t = BisectMetric::Score)]
+        metric: BisectMetric,
+        /// Only examine commits after this revision
+        #[arg(long, value_name = "REF")]
+        since: Option<String>,
+        /// Minimum passing score for --metric score [default: 100]
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..=100))]
+        min: Option<u32>,
+        /// Examine at most this many of the most recent revisions
+        #[arg(long, value_name = "N", default_value_t = aigent::history::DEFAULT_MAX_REVISIONS)]
+        max_revisions: usize,
         /// Output format
         #[arg(long, value_enum, default_value_t = Format::Text)]
         format: Format,
@@ -204,24 +500,64 @@ enum Commands {
         /// Paths to skill directories [default: .]
         #[arg(default_value = ".")]
         skill_dirs: Vec<PathBuf>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DocFormat::Markdown)]
+        format: DocFormat,
+        /// List each skill with only the first sentence of its description (markdown only)
+        #[arg(long)]
+        compact: bool,
         /// Write output to file instead of stdout
         #[arg(long)]
         output: Option<PathBuf>,
+        /// Also write a JSON index (name, description, location, tags) to this file
+        #[arg(long, value_name = "PATH")]
+        index: Option<PathBuf>,
+        /// Add each SKILL.md's SHA-256 and modification time to its entry
+        #[arg(long)]
+        provenance: bool,
         /// Discover skills recursively
         #[arg(long)]
         recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
     },
     /// Probe skill activation against a sample query
     Probe {
         /// Paths to skill directories or SKILL.md files [default: .]
         #[arg(default_value = ".")]
         skill_dirs: Vec<PathBuf>,
-        /// Sample user query to test activation against
-        #[arg(long, short)]
-        query: String,
+        /// Sample user query to test activation against (with --suggest, the
+        /// single query to analyze)
+        #[arg(long, short, required_unless_present = "suggest")]
+        query: Option<String>,
         /// Output format
         #[arg(long, value_enum, default_value_t = Format::Text)]
         format: Format,
+        /// Match tokens exactly (no stemming or synonym expansion)
+        #[arg(long, conflicts_with = "suggest")]
+        exact: bool,
+        /// Rank query terms the description misses and draft a revised description
+        #[arg(long)]
+        suggest: bool,
+        /// With --suggest, read queries from FILE (one per line; # starts a comment)
+        /// instead of the should_match queries in tests.yml
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "suggest",
+            conflicts_with = "query"
+        )]
+        queries: Option<PathBuf>,
+        /// With --suggest, draft deterministically even if an LLM provider is configured
+        #[arg(long, requires = "suggest")]
+        no_llm: bool,
+        /// Append the query to FILE (a tests.yml) with should_match set from the result
+        #[arg(long, value_name = "FILE", conflicts_with = "suggest")]
+        record: Option<PathBuf>,
     },
     /// Assemble skills into a Claude Code plugin
     Build {
@@ -237,6 +573,21 @@ enum Commands {
         /// Run validation on assembled skills
         #[arg(long)]
         validate: bool,
+        /// Copy files linked from outside a skill into resources/ and rewrite the links
+        #[arg(long)]
+        bundle_shared: bool,
+        /// Write a JSON manifest of the assembled skills, files, and hashes
+        #[arg(long, value_name = "FILE")]
+        manifest_out: Option<PathBuf>,
+        /// Do not copy skill tests.yml fixtures into the plugin
+        #[arg(long)]
+        no_tests: bool,
+        /// Leave files matching a glob out of each skill (repeatable; dotfiles are always excluded)
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+        /// Prefix each skill's directory and name with PREFIX- to avoid collisions
+        #[arg(long, value_name = "PREFIX")]
+        namespace: Option<String>,
     },
     /// Run fixture-based test suite from tests.yml
     Test {
@@ -244,14 +595,35 @@ enum Commands {
         #[arg(default_value = ".")]
         skill_dirs: Vec<PathBuf>,
         /// Output format
-        #[arg(long, value_enum, default_value_t = Format::Text)]
-        format: Format,
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
         /// Discover skills recursively
         #[arg(long)]
         recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
         /// Generate a starter tests.yml for skills that lack one
         #[arg(long)]
         generate: bool,
+        /// Only run test cases whose input contains this substring (case-insensitive)
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only run test cases tagged TAG (repeatable: any of the tags)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// With --tag, also run test cases that have no tags
+        #[arg(long, requires = "tags")]
+        include_untagged: bool,
+        /// Watch for changes and re-run tests (requires 'watch' feature)
+        #[arg(long)]
+        watch: bool,
+        /// List cases recorded by `probe --record` that are pending review
+        #[arg(long, conflicts_with_all = ["generate", "watch", "filter", "tags"])]
+        review_recorded: bool,
     },
     /// Check a skill for upgrade opportunities
     Upgrade {
@@ -283,6 +655,18 @@ enum Commands {
         /// Discover skills recursively
         #[arg(long)]
         recursive: bool,
+        /// Include draft skills in recursive discovery
+        #[arg(long)]
+        include_drafts: bool,
+        /// Follow symlinked directories in recursive discovery
+        #[arg(long)]
+        follow_symlinks: bool,
+        /// Keep a leading HTML comment before the frontmatter
+        #[arg(long)]
+        keep_preamble: bool,
+        /// Format SKILL.md content from stdin and write the result to stdout
+        #[arg(long, conflicts_with_all = ["skill_dirs", "recursive"])]
+        stdin: bool,
     },
     /// Validate a Claude Code plugin directory
     ValidatePlugin {
@@ -292,6 +676,15 @@ enum Commands {
         /// Output format
         #[arg(long, value_enum, default_value_t = Format::Text)]
         format: Format,
+        /// Schema for hook events and command fields: a Claude Code version,
+        /// `latest`, or `permissive` [default: plugin.json
+        /// `minClaudeCodeVersion`, else latest]
+        #[arg(long, value_name = "SCHEMA")]
+        plugin_schema: Option<aigent::PluginSchema>,
+        /// Warn if the plugin name matches a known plugin listed in FILE
+        /// (one name per line)
+        #[arg(long, value_name = "FILE")]
+        registry: Option<PathBuf>,
     },
     /// Initialize a skill directory with a template SKILL.md
     Init {
@@ -303,58 +696,269 @@ enum Commands {
         /// Skip scaffolding of examples/ and scripts/ directories
         #[arg(long)]
         minimal: bool,
+        /// Mark the skill as a draft, skipped by recursive discovery
+        #[arg(long)]
+        draft: bool,
+    },
+    /// Remove generated artifacts (backups, caches, saved drafts)
+    Clean {
+        /// Directory to clean [default: .]
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Also clean subdirectories
+        #[arg(long)]
+        recursive: bool,
+        /// List artifacts without removing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Assembled plugin directory to remove (must be inside the path)
+        #[arg(long, value_name = "DIR")]
+        dist: Option<PathBuf>,
+    },
+    /// Run a command across the collections in aigent.toml
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommand,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Run a language server on stdio for live SKILL.md diagnostics
+    /// (requires 'lsp' feature)
+    Lsp,
+}
+
+/// Commands that iterate the collections declared in `aigent.toml`.
+#[derive(Subcommand)]
+enum WorkspaceCommand {
+    /// Validate + lint each collection with its target, profile, and min score
+    Check {
+        /// Workspace root containing aigent.toml [default: .]
+        #[arg(name = "workspace-dir", default_value = ".")]
+        root: PathBuf,
+    },
+    /// Generate each collection's prompt (written to prompt_output if set)
+    Prompt {
+        /// Workspace root containing aigent.toml [default: .]
+        #[arg(name = "workspace-dir", default_value = ".")]
+        root: PathBuf,
+    },
+    /// Generate each collection's skill catalog (written to doc_output if set)
+    Doc {
+        /// Workspace root containing aigent.toml [default: .]
+        #[arg(name = "workspace-dir", default_value = ".")]
+        root: PathBuf,
+    },
+    /// Run fixture test suites in each collection
+    Test {
+        /// Workspace root containing aigent.toml [default: .]
+        #[arg(name = "workspace-dir", default_value = ".")]
+        root: PathBuf,
     },
 }
 
 pub fn run(cli: Cli) {
+    let color = if cli.no_color {
+        aigent::ColorChoice::Never
+    } else {
+        cli.color
+    };
+    let _ = COLOR.set(color);
+
     if cli.about {
         print_about();
         return;
     }
+    if cli.help_exit_codes {
+        print!("{}", exit_codes::format_exit_codes());
+        return;
+    }
 
     match cli.command {
         Some(Commands::Validate {
             skill_dirs,
             format,
+            summary,
             target,
             structure,
+            check_examples,
             recursive,
+            include_drafts,
+            follow_symlinks,
             apply_fixes,
             watch,
-        }) => validate::run(
+            index,
+            conflict_scope,
+            allow_placeholders,
+            placeholder_length,
+            substitute,
+            stdin,
+            stdin_path,
+            cache,
+            no_cache,
+        }) => {
+            let policy = aigent::PlaceholderPolicy {
+                allow: allow_placeholders,
+                nominal_length: placeholder_length,
+                substitutions: substitute.into_iter().collect(),
+            };
+            if stdin {
+                validate::run_stdin(format, summary, target, &policy, stdin_path);
+            } else {
+                validate::run(
+                    skill_dirs,
+                    format,
+                    summary,
+                    target,
+                    structure.then_some(aigent::StructureOptions { check_examples }),
+                    recursive,
+                    aigent::DiscoveryOptions {
+                        include_drafts,
+                        follow_symlinks,
+                    },
+                    apply_fixes,
+                    watch,
+                    index,
+                    conflict_scope.into(),
+                    &policy,
+                    (cache && !no_cache).then(|| aigent::ValidationCache::new(aigent::CACHE_DIR)),
+                );
+            }
+        }
+        Some(Commands::Check {
             skill_dirs,
             format,
             target,
+            no_validate,
+            all,
             structure,
+            check_examples,
             recursive,
+            include_drafts,
+            follow_symlinks,
             apply_fixes,
-            watch,
-        ),
-        Some(Commands::Check {
-            skill_dirs,
+            fix_interactive,
+            extract_reference,
+            verbose,
+            summary_report,
+            metrics,
+            lint_disable,
+            lint_only,
+            stdin,
+            stdin_path,
+        }) => {
+            let lint = aigent::LintOptions {
+                enable: lint_only,
+                disable: lint_disable,
+            };
+            if stdin {
+                check::run_stdin(
+                    format,
+                    target,
+                    no_validate,
+                    verbose,
+                    summary_report,
+                    metrics,
+                    &lint,
+                    stdin_path,
+                );
+            } else {
+                check::run(
+                    skill_dirs,
+                    format,
+                    target,
+                    no_validate,
+                    // --all implies every opt-in quality pass.
+                    (structure || all).then_some(aigent::StructureOptions {
+                        check_examples: check_examples || all,
+                    }),
+                    recursive,
+                    aigent::DiscoveryOptions {
+                        include_drafts,
+                        follow_symlinks,
+                    },
+                    apply_fixes,
+                    fix_interactive,
+                    extract_reference,
+                    verbose,
+                    summary_report,
+                    metrics,
+                    &lint,
+                );
+            }
+        }
+        Some(Commands::Properties { skill_dir }) => properties::run(skill_dir),
+        Some(Commands::Prompt {
+            mut skill_dirs,
+            groups,
             format,
+            budget,
+            tools_summary,
+            output,
+            include_deprecated,
+            provenance,
+            exclude,
+            sort,
             target,
-            no_validate,
-            structure,
+        }) => {
+            if skill_dirs.is_empty() && groups.is_empty() {
+                skill_dirs.push(PathBuf::from("."));
+            }
+            prompt::run(
+                skill_dirs,
+                groups,
+                format,
+                budget,
+                tools_summary,
+                output,
+                aigent::prompt::CollectOptions {
+                    include_deprecated,
+                    provenance,
+                },
+                exclude,
+                sort,
+                target.map(Into::into),
+            )
+        }
+        Some(Commands::Score {
+            skill_dirs,
+            format,
             recursive,
-            apply_fixes,
-        }) => check::run(
+            include_drafts,
+            follow_symlinks,
+            min,
+            against,
+        }) => score::run(
             skill_dirs,
             format,
-            target,
-            no_validate,
-            structure,
             recursive,
-            apply_fixes,
+            aigent::DiscoveryOptions {
+                include_drafts,
+                follow_symlinks,
+            },
+            min,
+            against,
         ),
-        Some(Commands::Properties { skill_dir }) => properties::run(skill_dir),
-        Some(Commands::Prompt {
-            skill_dirs,
+        Some(Commands::Bisect {
+            skill_dir,
+            metric,
+            since,
+            min,
+            max_revisions,
             format,
-            budget,
-            output,
-        }) => prompt::run(skill_dirs, format, budget, output),
-        Some(Commands::Score { skill_dir, format }) => score::run(skill_dir, format),
+        }) => bisect::run(
+            &skill_dir,
+            &aigent::HistoryOptions {
+                metric: metric.into(),
+                since,
+                max_revisions,
+                min,
+            },
+            format,
+        ),
         Some(Commands::New {
             purpose,
             name,
@@ -362,29 +966,104 @@ pub fn run(cli: Cli) {
             no_llm,
             interactive,
             minimal,
-        }) => new::run(purpose, name, dir, no_llm, interactive, minimal),
+            template,
+            no_score,
+        }) => new::run(
+            purpose,
+            name,
+            dir,
+            no_llm,
+            interactive,
+            minimal,
+            template,
+            no_score,
+        ),
         Some(Commands::Doc {
             skill_dirs,
+            format,
+            compact,
             output,
+            index,
+            provenance,
             recursive,
-        }) => doc::run(skill_dirs, output, recursive),
+            include_drafts,
+            follow_symlinks,
+        }) => doc::run(
+            skill_dirs,
+            format,
+            compact,
+            output,
+            index,
+            provenance,
+            recursive,
+            aigent::DiscoveryOptions {
+                include_drafts,
+                follow_symlinks,
+            },
+        ),
         Some(Commands::Probe {
             skill_dirs,
             query,
             format,
-        }) => probe::run(skill_dirs, query, format),
+            exact,
+            suggest,
+            queries,
+            no_llm,
+            record,
+        }) => match query {
+            Some(query) if !suggest => probe::run(skill_dirs, query, format, exact, record),
+            query => probe::run_suggest(skill_dirs, query, queries, format, no_llm),
+        },
         Some(Commands::Build {
             skill_dirs,
             output,
             name,
             validate,
-        }) => build::run(skill_dirs, output, name, validate),
+            bundle_shared,
+            manifest_out,
+            no_tests,
+            exclude,
+            namespace,
+        }) => build::run(
+            skill_dirs,
+            output,
+            name,
+            validate,
+            bundle_shared,
+            no_tests,
+            exclude,
+            namespace,
+            manifest_out,
+        ),
         Some(Commands::Test {
             skill_dirs,
             format,
             recursive,
+            include_drafts,
+            follow_symlinks,
+            generate,
+            filter,
+            tags,
+            include_untagged,
+            watch,
+            review_recorded,
+        }) => test::run(
+            skill_dirs,
+            format,
+            recursive,
+            aigent::DiscoveryOptions {
+                include_drafts,
+                follow_symlinks,
+            },
             generate,
-        }) => test::run(skill_dirs, format, recursive, generate),
+            aigent::TestOptions {
+                filter,
+                tags,
+                include_untagged,
+            },
+            watch,
+            review_recorded,
+        ),
         Some(Commands::Upgrade {
             skill_dir,
             apply,
@@ -396,19 +1075,59 @@ pub fn run(cli: Cli) {
             skill_dirs,
             check,
             recursive,
-        }) => format::run(skill_dirs, check, recursive),
-        Some(Commands::ValidatePlugin { plugin_dir, format }) => {
-            validate_plugin::run(plugin_dir, format)
+            include_drafts,
+            follow_symlinks,
+            keep_preamble,
+            stdin,
+        }) => {
+            if stdin {
+                format::run_stdin(check, keep_preamble);
+            } else {
+                format::run(
+                    skill_dirs,
+                    check,
+                    recursive,
+                    aigent::DiscoveryOptions {
+                        include_drafts,
+                        follow_symlinks,
+                    },
+                    keep_preamble,
+                );
+            }
+        }
+        Some(Commands::ValidatePlugin {
+            plugin_dir,
+            format,
+            plugin_schema,
+            registry,
+        }) => validate_plugin::run(plugin_dir, format, plugin_schema, registry),
+        Some(Commands::Workspace { command }) => {
+            let (action, root) = match command {
+                WorkspaceCommand::Check { root } => (workspace::Action::Check, root),
+                WorkspaceCommand::Prompt { root } => (workspace::Action::Prompt, root),
+                WorkspaceCommand::Doc { root } => (workspace::Action::Doc, root),
+                WorkspaceCommand::Test { root } => (workspace::Action::Test, root),
+            };
+            workspace::run(action, root)
         }
         Some(Commands::Init {
             dir,
             template,
             minimal,
-        }) => init::run(dir, template, minimal),
+            draft,
+        }) => init::run(dir, template, minimal, draft),
+        Some(Commands::Clean {
+            path,
+            recursive,
+            dry_run,
+            dist,
+        }) => clean::run(path, recursive, dry_run, dist),
+        Some(Commands::Completions { shell }) => completions::run(shell),
+        Some(Commands::Lsp) => lsp::run(),
         None => {
             eprintln!("Usage: aigent <command> [args]");
             eprintln!("Run `aigent --help` for details.");
-            std::process::exit(1);
+            exit(Exit::Usage);
         }
     }
 }
@@ -428,6 +1147,86 @@ fn print_about() {
     );
 }
 
+/// Color choice from the global `--color`/`--no-color` flags.
+static COLOR: OnceLock<aigent::ColorChoice> = OnceLock::new();
+
+/// Styler for text written to stderr.
+fn stderr_styler() -> aigent::Styler {
+    let choice = COLOR.get().copied().unwrap_or_default();
+    aigent::Styler::new(choice.for_stream(std::io::stderr().is_terminal()))
+}
+
+/// Styler for text written to stdout.
+fn stdout_styler() -> aigent::Styler {
+    let choice = COLOR.get().copied().unwrap_or_default();
+    aigent::Styler::new(choice.for_stream(std::io::stdout().is_terminal()))
+}
+
+/// Read `SKILL.md` content piped to `--stdin`, exiting on a read error.
+fn read_stdin(command: &str) -> String {
+    let mut content = String::new();
+    if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut content) {
+        eprintln!("aigent {command}: failed to read stdin: {e}");
+        exit(Exit::Failure);
+    }
+    content
+}
+
+/// Resolve `--stdin-path` into the path shown in reports (`<stdin>` when
+/// absent) and the skill directory used for the name/directory check.
+///
+/// A path ending in `SKILL.md` (any casing) names the file, so its parent is
+/// the skill directory; any other path is taken as the directory itself.
+fn stdin_source(stdin_path: Option<PathBuf>) -> (PathBuf, Option<PathBuf>) {
+    let Some(path) = stdin_path else {
+        return (PathBuf::from("<stdin>"), None);
+    };
+    let is_skill_md = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.eq_ignore_ascii_case("SKILL.md"));
+    let dir = if is_skill_md {
+        path.parent().map(std::path::Path::to_path_buf)
+    } else {
+        Some(path.clone())
+    };
+    (path, dir)
+}
+
+/// Parse a `--substitute KEY=VALUE` argument.
+fn parse_substitution(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{s}'")),
+    }
+}
+
+/// Parse a `LABEL=PATH` prompt group.
+fn parse_group(s: &str) -> Result<(String, PathBuf), String> {
+    match s.split_once('=') {
+        Some((label, path)) if !label.is_empty() && !path.is_empty() => {
+            Ok((label.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected LABEL=PATH, got '{s}'")),
+    }
+}
+
+/// Parse a lint rule code for `check --lint-disable` and `--lint-only`.
+fn parse_lint_code(s: &str) -> Result<aigent::diagnostics::DiagnosticCode, String> {
+    let codes = aigent::linter::lint_codes();
+    codes
+        .iter()
+        .copied()
+        .find(|c| c.as_str().eq_ignore_ascii_case(s.trim()))
+        .ok_or_else(|| {
+            let known: Vec<&str> = codes.iter().map(|c| c.as_str()).collect();
+            format!(
+                "not a lint rule code (expected one of {})",
+                known.join(", ")
+            )
+        })
+}
+
 /// If path points to a SKILL.md file, resolve to its parent directory.
 fn resolve_skill_dir(path: &std::path::Path) -> PathBuf {
     if path.is_file() {
@@ -441,7 +1240,8 @@ fn resolve_skill_dir(path: &std::path::Path) -> PathBuf {
 
 /// Resolve a list of input paths into skill directories, collecting discovery warnings.
 ///
-/// When `recursive` is true, discovers skills under each path recursively.
+/// When `recursive` is true, discovers skills under each path recursively
+/// with the given discovery options (drafts, symlink following).
 /// File paths (e.g., `path/to/SKILL.md`) are resolved to their parent
 /// directory before recursive discovery.
 /// When false, treats each path as a direct skill directory (resolving
@@ -449,6 +1249,7 @@ fn resolve_skill_dir(path: &std::path::Path) -> PathBuf {
 fn resolve_dirs(
     paths: &[PathBuf],
     recursive: bool,
+    opts: aigent::DiscoveryOptions,
 ) -> (Vec<PathBuf>, Vec<aigent::DiscoveryWarning>) {
     let mut dirs = Vec::new();
     let mut warnings = Vec::new();
@@ -457,7 +1258,7 @@ fn resolve_dirs(
             // If the user passes a SKILL.md file path, resolve to its parent
             // before running recursive discovery.
             let resolved = resolve_skill_dir(path);
-            let (found, warns) = aigent::discover_skills_verbose(&resolved);
+            let (found, warns) = aigent::discover_skills_verbose_with(&resolved, &opts);
             dirs.extend(found);
             warnings.extend(warns);
         } else {
@@ -1,5 +1,8 @@
 use std::path::PathBuf;
 
+use aigent::builder::template::SkillTemplate;
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     purpose: String,
     name: Option<String>,
@@ -7,6 +10,8 @@ pub(crate) fn run(
     no_llm: bool,
     interactive: bool,
     minimal: bool,
+    template: SkillTemplate,
+    no_score: bool,
 ) {
     let spec = aigent::SkillSpec {
         purpose,
@@ -14,6 +19,7 @@ pub(crate) fn run(
         output_dir: dir,
         no_llm,
         minimal,
+        template,
         ..Default::default()
     };
     let result = if interactive {
@@ -27,15 +33,39 @@ pub(crate) fn run(
             for w in &result.warnings {
                 eprintln!("warning: {w}");
             }
-            println!(
-                "Created skill '{}' at {}",
-                result.properties.name,
-                result.output_dir.display()
-            );
+            // Interactive builds already walk through validation; the score
+            // is the quick quality read for one-shot generation.
+            if interactive || no_score {
+                println!(
+                    "Created skill '{}' at {}",
+                    result.properties.name,
+                    result.output_dir.display()
+                );
+            } else {
+                let score = aigent::score(&result.output_dir);
+                println!(
+                    "Created skill '{}' at {} (score: {}/{})",
+                    result.properties.name,
+                    result.output_dir.display(),
+                    score.total,
+                    score.max
+                );
+            }
         }
         Err(e) => {
             eprintln!("aigent new: {e}");
-            std::process::exit(1);
+            // Keep generated content that failed validation instead of
+            // discarding it.
+            if let aigent::AigentError::InvalidDraft { draft } = &e {
+                match draft.save(&draft.default_save_dir()) {
+                    Ok(dir) => eprintln!(
+                        "Saved the generated draft to {} (skipped by discovery until fixed)",
+                        dir.display()
+                    ),
+                    Err(e) => eprintln!("aigent new: could not save draft: {e}"),
+                }
+            }
+            super::exit_for(&e);
         }
     }
 }
@@ -1,15 +1,28 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-pub(crate) fn run(skill_dirs: Vec<PathBuf>, query: String, format: super::Format) {
+pub(crate) fn run(
+    skill_dirs: Vec<PathBuf>,
+    query: String,
+    format: super::Format,
+    exact: bool,
+    record: Option<PathBuf>,
+) {
     let dirs: Vec<PathBuf> = skill_dirs
         .iter()
         .map(|p| super::resolve_skill_dir(p))
         .collect();
+    let opts = aigent::MatchOptions { stemming: !exact };
     let mut results = Vec::new();
     let mut had_errors = false;
+    let mut recorded = None;
     for dir in &dirs {
-        match aigent::test_skill(dir, &query) {
-            Ok(result) => results.push(result),
+        match aigent::test_skill_with(dir, &query, &opts) {
+            Ok(result) => {
+                if record.as_deref().is_some_and(|path| records_for(path, dir)) {
+                    recorded = Some(activated(&result));
+                }
+                results.push(result);
+            }
             Err(e) => {
                 eprintln!("aigent probe: {}: {e}", dir.display());
                 had_errors = true;
@@ -24,11 +37,15 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, query: String, format: super::Format
     });
     match format {
         super::Format::Text => {
+            let styler = super::stdout_styler();
             for (i, result) in results.iter().enumerate() {
                 if i > 0 {
                     println!();
                 }
-                print!("{}", aigent::tester::format_test_result(result));
+                print!(
+                    "{}",
+                    aigent::tester::format_test_result_styled(result, &styler)
+                );
             }
         }
         super::Format::Json => {
@@ -42,6 +59,7 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, query: String, format: super::Format
                         "activation": format!("{:?}", result.query_match),
                         "score": result.score,
                         "estimated_tokens": result.estimated_tokens,
+                        "estimated_tokens_breakdown": result.estimated_tokens_breakdown,
                         "validation_errors": result.diagnostics.iter()
                             .filter(|d| d.is_error()).count(),
                         "validation_warnings": result.diagnostics.iter()
@@ -57,7 +75,141 @@ pub(crate) fn run(skill_dirs: Vec<PathBuf>, query: String, format: super::Format
             }
         }
     }
+    if let Some(path) = &record {
+        // A single probed skill owns the file even if it lives elsewhere.
+        let matched = recorded.or(match results.as_slice() {
+            [only] => Some(activated(only)),
+            _ => None,
+        });
+        let Some(matched) = matched else {
+            eprintln!(
+                "aigent probe: --record: {} is not in any probed skill directory",
+                path.display()
+            );
+            super::exit(super::Exit::Usage);
+        };
+        match aigent::append_recorded_case(path, &query, matched) {
+            Ok(true) => eprintln!(
+                "Recorded query in {} (should_match: {matched}, pending review)",
+                path.display()
+            ),
+            Ok(false) => eprintln!("Query already in {}; not recorded", path.display()),
+            Err(e) => {
+                eprintln!("aigent probe: --record: {e}");
+                super::exit_for(&e);
+            }
+        }
+    }
     if had_errors && results.is_empty() {
-        std::process::exit(1);
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// Whether the probed query activated the skill.
+fn activated(result: &aigent::TestResult) -> bool {
+    !matches!(result.query_match, aigent::tester::QueryMatch::None)
+}
+
+/// Whether `path` (a tests.yml) belongs to the skill in `dir`.
+fn records_for(path: &Path, dir: &Path) -> bool {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    match (parent.canonicalize(), dir.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Suggest description improvements (`--suggest`).
+///
+/// Queries come from `query`, then `queries_file`, then each skill's
+/// `tests.yml` positives.
+pub(crate) fn run_suggest(
+    skill_dirs: Vec<PathBuf>,
+    query: Option<String>,
+    queries_file: Option<PathBuf>,
+    format: super::Format,
+    no_llm: bool,
+) {
+    let given_queries = query.map(|q| vec![q]).or_else(|| {
+        queries_file.map(|path| match std::fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(str::to_string)
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                eprintln!("aigent probe: {}: {e}", path.display());
+                super::exit(super::Exit::Failure);
+            }
+        })
+    });
+    let provider = if no_llm {
+        None
+    } else {
+        aigent::builder::llm::detect_provider()
+    };
+
+    let mut reports = Vec::new();
+    let mut had_errors = false;
+    for dir in skill_dirs.iter().map(|p| super::resolve_skill_dir(p)) {
+        let queries = match &given_queries {
+            Some(queries) => Ok(queries.clone()),
+            None => aigent::positive_queries(&dir),
+        };
+        let result = queries.and_then(|queries| {
+            let props = aigent::read_properties(&dir)?;
+            Ok((props, queries))
+        });
+        let (props, queries) = match result {
+            Ok(found) => found,
+            Err(e) => {
+                eprintln!("aigent probe: {}: {e}", dir.display());
+                had_errors = true;
+                continue;
+            }
+        };
+        if queries.is_empty() {
+            eprintln!("aigent probe: {}: no queries to analyze", dir.display());
+            had_errors = true;
+            continue;
+        }
+        let queries: Vec<&str> = queries.iter().map(String::as_str).collect();
+        let mut report = aigent::suggest_description_improvements(&props, &queries);
+        if let (Some(provider), false) = (&provider, report.terms.is_empty()) {
+            match aigent::llm_revise_description(provider.as_ref(), &report, &queries) {
+                Ok(draft) => report.draft = Some(draft),
+                Err(e) => eprintln!(
+                    "warning: {}: LLM draft failed, using deterministic draft: {e}",
+                    dir.display()
+                ),
+            }
+        }
+        reports.push(report);
+    }
+
+    match format {
+        super::Format::Text => {
+            for (i, report) in reports.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print!("{}", aigent::format_suggestions(report));
+            }
+        }
+        super::Format::Json => {
+            let json = if reports.len() == 1 {
+                serde_json::to_string_pretty(&reports[0])
+            } else {
+                serde_json::to_string_pretty(&reports)
+            };
+            println!("{}", json.unwrap());
+        }
+    }
+    if had_errors && reports.is_empty() {
+        super::exit(super::Exit::Findings);
     }
 }
@@ -1,27 +1,132 @@
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256};
+
+/// SHA-256 of an existing file, streamed, or `None` if it cannot be read.
+fn hash_file(path: &Path) -> Option<Vec<u8>> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hasher.finalize().to_vec())
+}
+
+/// Skills to render, either as one list or in labeled sections.
+enum Collected {
+    Flat(Vec<aigent::prompt::SkillEntry>),
+    Grouped(Vec<aigent::prompt::SkillGroup>),
+}
+
+impl Collected {
+    fn write(
+        &self,
+        format: aigent::prompt::PromptFormat,
+        target: Option<aigent::prompt::PromptTarget>,
+        mut out: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        match (self, target) {
+            (Self::Flat(entries), Some(target)) => {
+                aigent::prompt::write_entries_for(entries, format, target, &mut out)
+            }
+            (Self::Flat(entries), None) => aigent::prompt::write_entries(entries, format, &mut out),
+            (Self::Grouped(groups), Some(target)) => {
+                aigent::prompt::write_groups_for(groups, format, target, &mut out)
+            }
+            (Self::Grouped(groups), None) => aigent::prompt::write_groups(groups, format, &mut out),
+        }
+    }
+
+    /// Drop skills named in `names`, returning the names that matched no
+    /// skill in any section.
+    fn exclude<'a>(&mut self, names: &'a [String]) -> Vec<&'a str> {
+        let matched: Vec<&str> = match self {
+            Self::Flat(entries) => aigent::prompt::exclude_entries(entries, names),
+            Self::Grouped(groups) => groups
+                .iter_mut()
+                .flat_map(|g| aigent::prompt::exclude_entries(&mut g.entries, names))
+                .collect(),
+        };
+        names
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !matched.contains(name))
+            .collect()
+    }
+
+    /// Budget and tool reports requested on the command line, for stderr.
+    fn reports(&self, budget: bool, tools_summary: bool) -> String {
+        let mut out = String::new();
+        if budget {
+            out.push_str(&match self {
+                Self::Flat(entries) => aigent::prompt::format_budget(entries),
+                Self::Grouped(groups) => aigent::prompt::format_group_budget(groups),
+            });
+        }
+        if tools_summary {
+            let requirements = match self {
+                Self::Flat(entries) => aigent::prompt::tool_requirements(entries),
+                Self::Grouped(groups) => {
+                    aigent::prompt::tool_requirements(groups.iter().flat_map(|g| &g.entries))
+                }
+            };
+            out.push_str(&aigent::prompt::format_tool_requirements(&requirements));
+        }
+        out
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     skill_dirs: Vec<PathBuf>,
+    groups: Vec<(String, PathBuf)>,
     format: super::PromptOutputFormat,
     budget: bool,
+    tools_summary: bool,
     output: Option<PathBuf>,
+    opts: aigent::prompt::CollectOptions,
+    exclude: Vec<String>,
+    sort: super::PromptSort,
+    target: Option<aigent::prompt::PromptTarget>,
 ) {
-    let dirs: Vec<&std::path::Path> = skill_dirs.iter().map(|p| p.as_path()).collect();
+    let dirs: Vec<&Path> = skill_dirs.iter().map(|p| p.as_path()).collect();
     let prompt_format: aigent::prompt::PromptFormat = format.into();
-    let (entries, warnings) = aigent::prompt::collect_skills_verbose(&dirs);
+    let (mut collected, warnings) = if groups.is_empty() {
+        let (mut entries, warnings) = aigent::prompt::collect_skills_verbose_with(&dirs, &opts);
+        aigent::prompt::sort_entries(&mut entries, sort.into());
+        (Collected::Flat(entries), warnings)
+    } else {
+        // Positional paths form the default section, ahead of labeled ones.
+        let mut sources: Vec<(&str, &[&Path])> = Vec::new();
+        if !dirs.is_empty() {
+            sources.push((aigent::prompt::DEFAULT_GROUP, &dirs));
+        }
+        let group_dirs: Vec<[&Path; 1]> = groups.iter().map(|(_, p)| [p.as_path()]).collect();
+        for ((label, _), dir) in groups.iter().zip(&group_dirs) {
+            sources.push((label, dir));
+        }
+        let (mut groups, warnings) = aigent::prompt::collect_skills_labeled(&sources, &opts);
+        for group in &mut groups {
+            aigent::prompt::sort_entries(&mut group.entries, sort.into());
+        }
+        (Collected::Grouped(groups), warnings)
+    };
     for w in &warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
-    let content = aigent::prompt::format_entries(&entries, prompt_format);
+    for name in collected.exclude(&exclude) {
+        eprintln!("warning: --exclude {name}: no skill with this name was found");
+    }
+    let write = |out: &mut dyn Write| collected.write(prompt_format, target, out);
 
     if let Some(output_path) = output {
-        // Diff-aware file output: compare with existing, only write on change.
-        let changed = if output_path.exists() {
-            let existing = std::fs::read_to_string(&output_path).unwrap_or_default();
-            existing != content
-        } else {
-            true
-        };
+        // Diff-aware file output: compare hashes so neither the new prompt
+        // nor the existing file has to be held in memory.
+        let mut new_hash = Sha256::new();
+        if let Err(e) = write(&mut new_hash) {
+            eprintln!("aigent to-prompt: failed to render prompt: {e}");
+            super::exit(super::Exit::Failure);
+        }
+        let changed = hash_file(&output_path).as_deref() != Some(new_hash.finalize().as_slice());
 
         if changed {
             if let Some(parent) = output_path.parent() {
@@ -30,28 +135,37 @@ pub(crate) fn run(
                         "aigent to-prompt: failed to create directory {}: {e}",
                         parent.display()
                     );
-                    std::process::exit(1);
+                    super::exit(super::Exit::Failure);
                 });
             }
-            std::fs::write(&output_path, &content).unwrap_or_else(|e| {
+            let written = std::fs::File::create(&output_path).and_then(|file| {
+                let mut out = std::io::BufWriter::new(file);
+                write(&mut out)?;
+                out.flush()
+            });
+            written.unwrap_or_else(|e| {
                 eprintln!(
                     "aigent to-prompt: failed to write {}: {e}",
                     output_path.display()
                 );
-                std::process::exit(1);
+                super::exit(super::Exit::Failure);
             });
             eprintln!("Updated {}", output_path.display());
-            if budget {
-                eprint!("{}", aigent::prompt::format_budget(&entries));
-            }
-            std::process::exit(1);
+            eprint!("{}", collected.reports(budget, tools_summary));
+            super::exit(super::Exit::Findings);
         } else {
             eprintln!("Unchanged {}", output_path.display());
         }
     } else {
-        println!("{content}");
-        if budget {
-            eprint!("{}", aigent::prompt::format_budget(&entries));
+        let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+        let written = write(&mut out)
+            .and_then(|()| writeln!(out))
+            .and_then(|()| out.flush());
+        if let Err(e) = written {
+            eprintln!("aigent to-prompt: failed to write output: {e}");
+            super::exit(super::Exit::Failure);
         }
+        drop(out);
+        eprint!("{}", collected.reports(budget, tools_summary));
     }
 }
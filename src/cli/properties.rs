@@ -8,7 +8,7 @@ pub(crate) fn run(skill_dir: PathBuf) {
         }
         Err(e) => {
             eprintln!("aigent properties: {e}");
-            std::process::exit(1);
+            super::exit_for(&e);
         }
     }
 }
@@ -1,21 +1,114 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-pub(crate) fn run(skill_dir: PathBuf, format: super::Format) {
-    let dir = super::resolve_skill_dir(&skill_dir);
+pub(crate) fn run(
+    skill_dirs: Vec<PathBuf>,
+    format: super::ScoreFormat,
+    recursive: bool,
+    discovery: aigent::DiscoveryOptions,
+    min: Option<u32>,
+    against: Option<PathBuf>,
+) {
+    if let Some(old_dir) = against {
+        if skill_dirs.len() != 1 {
+            eprintln!("aigent score: --against takes exactly one skill directory");
+            super::exit(super::Exit::Usage);
+        }
+        run_diff(&skill_dirs[0], &old_dir, format, min);
+        return;
+    }
+
+    if !recursive && skill_dirs.len() == 1 {
+        run_single(&skill_dirs[0], format, min);
+        return;
+    }
+
+    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive, discovery);
+    for w in &disc_warnings {
+        eprintln!("warning: {}: {}", w.path.display(), w.message);
+    }
+    if dirs.is_empty() {
+        eprintln!("No SKILL.md files found under the specified path(s).");
+        super::exit(super::Exit::Usage);
+    }
+
+    let dir_refs: Vec<&Path> = dirs.iter().map(|d| d.as_path()).collect();
+    let scores = aigent::score_skills(&dir_refs);
+    let gate = min.unwrap_or_else(|| scores.first().map_or(100, |s| s.result.max));
+    let summary = aigent::summarize_scores(&scores, gate);
+
+    match format {
+        super::ScoreFormat::Text => {
+            eprint!("{}", aigent::scorer::format_summary_text(&scores, &summary));
+        }
+        super::ScoreFormat::Json => {
+            let json = serde_json::json!({
+                "skills": scores,
+                "summary": summary,
+            });
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        super::ScoreFormat::Markdown => {
+            print!(
+                "{}",
+                aigent::scorer::format_summary_markdown(&scores, &summary)
+            );
+        }
+    }
+
+    // Exit with non-zero if any skill is below the gate.
+    if summary.below_gate > 0 {
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// Score a single skill and print its full breakdown.
+fn run_single(skill_dir: &Path, format: super::ScoreFormat, min: Option<u32>) {
+    let dir = super::resolve_skill_dir(skill_dir);
     let result = aigent::score(&dir);
 
     match format {
-        super::Format::Text => {
-            eprint!("{}", aigent::scorer::format_text(&result));
+        super::ScoreFormat::Text => {
+            eprint!(
+                "{}",
+                aigent::scorer::format_text_styled(&result, &super::stderr_styler())
+            );
         }
-        super::Format::Json => {
+        super::ScoreFormat::Json => {
             let json = serde_json::to_string_pretty(&result).unwrap();
             println!("{json}");
         }
+        super::ScoreFormat::Markdown => {
+            print!("{}", aigent::scorer::format_markdown(&result));
+        }
+    }
+
+    // Exit with non-zero if score is below the gate (default: not perfect).
+    if result.total < min.unwrap_or(result.max) {
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// Compare a skill against an older revision and print the changes.
+fn run_diff(skill_dir: &Path, old_dir: &Path, format: super::ScoreFormat, min: Option<u32>) {
+    let dir = super::resolve_skill_dir(skill_dir);
+    let old = super::resolve_skill_dir(old_dir);
+    let diff = aigent::score_diff(&old, &dir);
+
+    match format {
+        super::ScoreFormat::Text => {
+            eprint!("{}", aigent::scorer::format_diff_markdown(&diff));
+        }
+        super::ScoreFormat::Json => {
+            let json = serde_json::to_string_pretty(&diff).unwrap();
+            println!("{json}");
+        }
+        super::ScoreFormat::Markdown => {
+            print!("{}", aigent::scorer::format_diff_markdown(&diff));
+        }
     }
 
-    // Exit with non-zero if score is below 100 (not perfect).
-    if result.total < result.max {
-        std::process::exit(1);
+    // The gate applies to the new revision, as for a single skill.
+    if diff.new_total < min.unwrap_or(diff.max) {
+        super::exit(super::Exit::Findings);
     }
 }
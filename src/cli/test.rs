@@ -1,12 +1,33 @@
 use std::path::PathBuf;
 
+use aigent::{SuiteTotals, TestOptions};
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     skill_dirs: Vec<PathBuf>,
-    format: super::Format,
+    format: super::ReportFormat,
     recursive: bool,
+    discovery: aigent::DiscoveryOptions,
     generate: bool,
+    options: TestOptions,
+    watch: bool,
+    review_recorded: bool,
 ) {
-    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive);
+    // Watch mode: re-run the suites on SKILL.md / tests.yml changes.
+    #[cfg(feature = "watch")]
+    if watch && !generate {
+        super::watch::run_test_watch_mode(&skill_dirs, format, recursive, discovery, &options);
+        return;
+    }
+    #[cfg(not(feature = "watch"))]
+    if watch && !generate {
+        eprintln!(
+            "Watch mode requires the 'watch' feature. Rebuild with: cargo build --features watch"
+        );
+        super::exit(super::Exit::Usage);
+    }
+
+    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive, discovery);
     for w in &disc_warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
@@ -16,7 +37,12 @@ pub(crate) fn run(
         } else {
             eprintln!("Usage: aigent test <skill-dir> [<skill-dir>...]");
         }
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
+    }
+
+    if review_recorded {
+        review(&dirs, format);
+        return;
     }
 
     if generate {
@@ -33,7 +59,7 @@ pub(crate) fn run(
                                 "aigent test: failed to write {}: {e}",
                                 fixture_path.display()
                             );
-                            std::process::exit(1);
+                            super::exit(super::Exit::Failure);
                         });
                         eprintln!("Generated {}", fixture_path.display());
                     }
@@ -45,32 +71,98 @@ pub(crate) fn run(
             }
         }
         if any_error {
-            std::process::exit(1);
+            super::exit(super::Exit::Findings);
         }
         return;
     }
 
-    let mut total_passed = 0;
-    let mut total_failed = 0;
+    if !run_suites(&dirs, format, &options) {
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// List the cases recorded by `probe --record` that are pending review.
+///
+/// Directories without a `tests.yml` are skipped.
+fn review(dirs: &[PathBuf], format: super::ReportFormat) {
+    let mut pending = Vec::new();
+    for dir in dirs {
+        let path = dir.join("tests.yml");
+        if !path.exists() {
+            continue;
+        }
+        match aigent::recorded_cases(&path) {
+            Ok(cases) if !cases.is_empty() => pending.push((path, cases)),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("aigent test: {}: {e}", path.display());
+                super::exit_for(&e);
+            }
+        }
+    }
+    match format {
+        super::ReportFormat::Json => {
+            let json: Vec<serde_json::Value> = pending
+                .iter()
+                .map(|(path, cases)| {
+                    serde_json::json!({
+                        "path": path.display().to_string(),
+                        "recorded": cases,
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        super::ReportFormat::Text => {
+            if pending.is_empty() {
+                println!("No recorded cases pending review.");
+            }
+            for (path, cases) in &pending {
+                println!("{}:", path.display());
+                for case in cases {
+                    let expected = if case.should_match {
+                        "match"
+                    } else {
+                        "no match"
+                    };
+                    println!("  [{expected}] {}", case.input);
+                }
+            }
+        }
+        super::ReportFormat::Junit => {
+            eprintln!("aigent test: --review-recorded supports text and json output");
+            super::exit(super::Exit::Usage);
+        }
+    }
+}
+
+/// Run the test suite for each directory and print the results.
+///
+/// Results are collected before the final report, so several directories
+/// produce one JSON document (`{"suites": [{path, result}], "totals": ...}`)
+/// or one JUnit report, and text output ends with a per-suite table.
+///
+/// Returns `true` when every executed case passed. A `--filter` or `--tag`
+/// that selects no cases in any directory is reported and counts as a
+/// failure, so a typo in the selection cannot silently turn a CI run green.
+pub(crate) fn run_suites(
+    dirs: &[PathBuf],
+    format: super::ReportFormat,
+    options: &TestOptions,
+) -> bool {
     let mut any_error = false;
+    let mut results = Vec::new();
 
-    for dir in &dirs {
-        match aigent::run_test_suite(dir) {
+    for dir in dirs {
+        match aigent::run_test_suite(dir, options) {
             Ok(result) => {
-                match format {
-                    super::Format::Text => {
-                        if dirs.len() > 1 {
-                            eprintln!("{}:", dir.display());
-                        }
-                        eprint!("{}", aigent::format_test_suite(&result));
-                    }
-                    super::Format::Json => {
-                        let json = serde_json::to_string_pretty(&result).unwrap();
-                        println!("{json}");
+                if matches!(format, super::ReportFormat::Text) {
+                    if dirs.len() > 1 {
+                        eprintln!("{}:", dir.display());
                     }
+                    eprint!("{}", aigent::format_test_suite(&result));
                 }
-                total_passed += result.passed;
-                total_failed += result.failed;
+                results.push((dir.clone(), result));
             }
             Err(e) => {
                 eprintln!("aigent test: {}: {e}", dir.display());
@@ -79,14 +171,51 @@ pub(crate) fn run(
         }
     }
 
-    if dirs.len() > 1 {
-        eprintln!(
-            "\nTotal: {total_passed} passed, {total_failed} failed, {} total",
-            total_passed + total_failed
-        );
+    let totals = SuiteTotals::from_results(&results);
+    match format {
+        super::ReportFormat::Text => {
+            if dirs.len() > 1 {
+                eprint!("\n{}", aigent::format_test_summary(&results));
+            }
+        }
+        super::ReportFormat::Json => {
+            let json = if dirs.len() > 1 {
+                let suites: Vec<serde_json::Value> = results
+                    .iter()
+                    .map(|(dir, result)| {
+                        serde_json::json!({
+                            "path": dir.display().to_string(),
+                            "result": result,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({ "suites": suites, "totals": totals })
+            } else {
+                match results.first() {
+                    Some((_, result)) => serde_json::to_value(result).unwrap(),
+                    None => serde_json::Value::Null,
+                }
+            };
+            if !json.is_null() {
+                println!("{}", serde_json::to_string_pretty(&json).unwrap());
+            }
+        }
+        super::ReportFormat::Junit => print!("{}", aigent::junit::test_report(&results)),
     }
 
-    if total_failed > 0 || any_error {
-        std::process::exit(1);
+    let mut no_matches = false;
+    if !any_error && totals.passed + totals.failed == 0 {
+        if let Some(filter) = &options.filter {
+            eprintln!("aigent test: no test cases match filter '{filter}'");
+            no_matches = true;
+        } else if !options.tags.is_empty() {
+            eprintln!(
+                "aigent test: no test cases tagged '{}'",
+                options.tags.join("' or '")
+            );
+            no_matches = true;
+        }
     }
+
+    totals.failed == 0 && !any_error && !no_matches
 }
@@ -50,11 +50,12 @@ pub(crate) fn run(
             } else {
                 match format {
                     super::Format::Text => {
+                        let styler = super::stderr_styler();
                         for msg in &full_messages {
                             eprintln!("{msg}");
                         }
                         for s in &suggestions {
-                            eprintln!("{s}");
+                            eprintln!("{}", styler.paint(aigent::Style::Suggestion, s));
                         }
                         let fix_count = suggestions
                             .iter()
@@ -103,27 +104,26 @@ pub(crate) fn run(
                 let has_unapplied_fixes =
                     !apply && suggestions.iter().any(|s| s.kind == SuggestionKind::Fix);
                 if has_unapplied_fixes || has_full_errors {
-                    std::process::exit(1);
+                    super::exit(super::Exit::Findings);
                 }
             }
         }
         Err(e) => {
             eprintln!("aigent upgrade: {e}");
-            std::process::exit(1);
+            super::exit_for(&e);
         }
     }
 }
 
 /// Extract frontmatter lines from SKILL.md content (between the `---` delimiters).
 ///
-/// Returns the lines without the delimiters.
+/// Returns the lines without the delimiters. An indented `---` inside a
+/// block scalar is kept as content (see `aigent::parser::split_frontmatter`).
 fn extract_frontmatter_lines(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .skip(1) // skip opening ---
-        .take_while(|l| l.trim_end() != "---")
-        .map(|l| l.to_string())
-        .collect()
+    let (_, content) = aigent::parser::split_preamble(content);
+    aigent::parser::split_frontmatter(content)
+        .map(|(yaml, _)| yaml.lines().map(|l| l.to_string()).collect())
+        .unwrap_or_default()
 }
 
 /// Run upgrade analysis on a skill directory.
@@ -205,11 +205,7 @@ fn run_upgrade(
     }
 
     // U002: Check for missing trigger phrase in description.
-    let desc_lower = props.description.to_lowercase();
-    let has_trigger = aigent::linter::TRIGGER_PHRASES
-        .iter()
-        .any(|p| desc_lower.contains(p));
-    if !has_trigger {
+    if props.trigger_clauses().is_empty() {
         suggestions.push(Suggestion {
             code: U002,
             kind: SuggestionKind::Info,
@@ -1,15 +1,22 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use aigent::diagnostics::{Diagnostic, ValidationTarget};
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run(
     skill_dirs: Vec<PathBuf>,
-    format: super::Format,
+    format: super::ReportFormat,
+    summary: bool,
     target: super::Target,
-    structure: bool,
+    structure: Option<aigent::StructureOptions>,
     recursive: bool,
+    discovery: aigent::DiscoveryOptions,
     apply_fixes: bool,
     watch: bool,
+    index: bool,
+    conflict_scope: aigent::ConflictScope,
+    policy: &aigent::PlaceholderPolicy,
+    cache: Option<aigent::ValidationCache>,
 ) {
     // Watch mode: re-run validation on filesystem changes.
     #[cfg(feature = "watch")]
@@ -20,7 +27,10 @@ pub(crate) fn run(
             target,
             structure,
             recursive,
+            discovery,
             apply_fixes,
+            policy,
+            cache.as_ref(),
         );
         return;
     }
@@ -29,11 +39,14 @@ pub(crate) fn run(
         eprintln!(
             "Watch mode requires the 'watch' feature. Rebuild with: cargo build --features watch"
         );
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
     }
 
+    // Index checks need the full collection under each root.
+    let recursive = recursive || index;
+
     // Resolve directories: expand --recursive, resolve file paths.
-    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive);
+    let (dirs, disc_warnings) = super::resolve_dirs(&skill_dirs, recursive, discovery);
     for w in &disc_warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
@@ -43,14 +56,14 @@ pub(crate) fn run(
         } else {
             eprintln!("Usage: aigent validate <skill-dir> [<skill-dir>...]");
         }
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
     }
 
     let mut all_diags: Vec<(PathBuf, Vec<Diagnostic>)> = Vec::new();
     let target_val: ValidationTarget = target.into();
 
     for dir in &dirs {
-        let mut diags = aigent::validate_with_target(dir, target_val);
+        let mut diags = validate_dir(dir, target_val, policy, cache.as_ref());
 
         // Apply fixes if requested.
         if apply_fixes {
@@ -58,7 +71,7 @@ pub(crate) fn run(
                 Ok(count) if count > 0 => {
                     eprintln!("Applied {count} fix(es) to {}", dir.display());
                     // Re-validate after fixes.
-                    diags = aigent::validate_with_target(dir, target_val);
+                    diags = validate_dir(dir, target_val, policy, cache.as_ref());
                 }
                 Ok(_) => {}
                 Err(e) => {
@@ -68,8 +81,8 @@ pub(crate) fn run(
         }
 
         // Append structure checks if requested.
-        if structure {
-            diags.extend(aigent::validate_structure(dir));
+        if let Some(options) = &structure {
+            diags.extend(aigent::validate_structure_with(dir, options));
         }
 
         all_diags.push((dir.clone(), diags));
@@ -78,21 +91,131 @@ pub(crate) fn run(
     // Run cross-skill conflict detection for multi-dir validation.
     let conflict_diags = if all_diags.len() > 1 {
         let skill_dirs_refs: Vec<&std::path::Path> = dirs.iter().map(|p| p.as_path()).collect();
-        let (entries, coll_warnings) = aigent::collect_skills_verbose(&skill_dirs_refs);
+        let opts = aigent::CollectOptions {
+            include_deprecated: true,
+            provenance: false,
+        };
+        let (entries, coll_warnings) = aigent::collect_skills_verbose_with(&skill_dirs_refs, &opts);
         for w in &coll_warnings {
             eprintln!("warning: {}: {}", w.path.display(), w.message);
         }
-        aigent::detect_conflicts(&entries)
+
+        // Deprecated skills must point at a successor in the validated set.
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        for (dir, diags) in &mut all_diags {
+            if let Ok(props) = aigent::read_properties(dir) {
+                diags.extend(aigent::validate_superseded_by(&props, &names));
+            }
+        }
+
+        aigent::detect_conflicts_with(
+            &entries,
+            &aigent::ConflictOptions {
+                scope: conflict_scope,
+                ..aigent::ConflictOptions::default()
+            },
+        )
     } else {
         vec![]
     };
 
+    // Check each root's INDEX.md against the skills discovered under it.
+    let index_diags: Vec<(PathBuf, Vec<Diagnostic>)> = if index {
+        skill_dirs
+            .iter()
+            .map(|root| {
+                let root = super::resolve_skill_dir(root);
+                (root.join("INDEX.md"), aigent::validate_index(&root))
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    report(format, summary, all_diags, conflict_diags, index_diags);
+}
+
+/// Validate one skill directory, through `cache` when given.
+pub(crate) fn validate_dir(
+    dir: &Path,
+    target: ValidationTarget,
+    policy: &aigent::PlaceholderPolicy,
+    cache: Option<&aigent::ValidationCache>,
+) -> Vec<Diagnostic> {
+    match cache {
+        Some(cache) => aigent::validate_cached(dir, target, policy, cache),
+        None => aigent::validate_with_policy(dir, target, policy),
+    }
+}
+
+/// Validate `SKILL.md` content read from stdin (`--stdin`).
+///
+/// `stdin_path` names the real file for display and the name/directory
+/// check; without it the content is reported as `<stdin>`.
+pub(crate) fn run_stdin(
+    format: super::ReportFormat,
+    summary: bool,
+    target: super::Target,
+    policy: &aigent::PlaceholderPolicy,
+    stdin_path: Option<PathBuf>,
+) {
+    let content = super::read_stdin("validate");
+    let (path, dir) = super::stdin_source(stdin_path);
+    let diags = aigent::validate_content_in_dir(&content, dir.as_deref(), target.into(), policy);
+    report(format, summary, vec![(path, diags)], vec![], vec![]);
+}
+
+/// Skill counts for a validation summary.
+struct Tally {
+    /// Skills validated.
+    total: usize,
+    /// Skills with no errors and no warnings.
+    ok: usize,
+    /// Skills with at least one error.
+    errors: usize,
+    /// Skills with warnings but no errors.
+    warnings: usize,
+}
+
+impl Tally {
+    fn of(all_diags: &[(PathBuf, Vec<Diagnostic>)]) -> Self {
+        let total = all_diags.len();
+        let errors = all_diags
+            .iter()
+            .filter(|(_, d)| d.iter().any(|d| d.is_error()))
+            .count();
+        let warnings = all_diags
+            .iter()
+            .filter(|(_, d)| d.iter().any(|d| d.is_warning()) && !d.iter().any(|d| d.is_error()))
+            .count();
+        Self {
+            total,
+            ok: total - errors - warnings,
+            errors,
+            warnings,
+        }
+    }
+}
+
+/// Print validation results in `format`, exiting 1 if any has an error.
+///
+/// With `summary`, JSON output is an object holding the per-path results
+/// under `skills` and the [`Tally`] under `summary`.
+fn report(
+    format: super::ReportFormat,
+    summary: bool,
+    all_diags: Vec<(PathBuf, Vec<Diagnostic>)>,
+    conflict_diags: Vec<Diagnostic>,
+    index_diags: Vec<(PathBuf, Vec<Diagnostic>)>,
+) {
     let has_errors = all_diags
         .iter()
+        .chain(&index_diags)
         .any(|(_, d)| d.iter().any(|d| d.is_error()));
 
     match format {
-        super::Format::Text => {
+        super::ReportFormat::Text => {
+            let styler = super::stderr_styler();
             let multi = all_diags.len() > 1;
             for (dir, diags) in &all_diags {
                 if multi && !diags.is_empty() {
@@ -100,9 +223,9 @@ pub(crate) fn run(
                 }
                 for d in diags {
                     if multi {
-                        eprintln!("  {d}");
+                        eprintln!("  {}", styler.diagnostic(d));
                     } else {
-                        eprintln!("{d}");
+                        eprintln!("{}", styler.diagnostic(d));
                     }
                 }
             }
@@ -110,33 +233,38 @@ pub(crate) fn run(
             if !conflict_diags.is_empty() {
                 eprintln!("\nCross-skill conflicts:");
                 for d in &conflict_diags {
-                    eprintln!("  {d}");
+                    eprintln!("  {}", styler.diagnostic(d));
+                }
+            }
+            // Print index consistency warnings.
+            for (index_path, diags) in &index_diags {
+                if !diags.is_empty() {
+                    eprintln!("\nIndex ({}):", index_path.display());
+                    for d in diags {
+                        eprintln!("  {}", styler.diagnostic(d));
+                    }
                 }
             }
             // Print summary for multi-dir, or "ok" for clean single-dir.
             if multi {
-                let total = all_diags.len();
-                let errors = all_diags
-                    .iter()
-                    .filter(|(_, d)| d.iter().any(|d| d.is_error()))
-                    .count();
-                let warnings = all_diags
-                    .iter()
-                    .filter(|(_, d)| {
-                        d.iter().any(|d| d.is_warning()) && !d.iter().any(|d| d.is_error())
-                    })
-                    .count();
-                let ok = total - errors - warnings;
-                eprintln!("\n{total} skills: {ok} ok, {errors} errors, {warnings} warnings only");
+                let tally = Tally::of(&all_diags);
+                eprintln!(
+                    "\n{}",
+                    styler.summary(tally.total, tally.ok, tally.errors, tally.warnings)
+                );
             } else {
-                let total_diags: usize =
-                    all_diags.iter().map(|(_, d)| d.len()).sum::<usize>() + conflict_diags.len();
+                let total_diags: usize = all_diags
+                    .iter()
+                    .chain(&index_diags)
+                    .map(|(_, d)| d.len())
+                    .sum::<usize>()
+                    + conflict_diags.len();
                 if total_diags == 0 {
                     eprintln!("ok");
                 }
             }
         }
-        super::Format::Json => {
+        super::ReportFormat::Json => {
             // Always emit consistent array-of-objects format.
             let mut entries: Vec<serde_json::Value> = all_diags
                 .iter()
@@ -154,12 +282,41 @@ pub(crate) fn run(
                     "diagnostics": conflict_diags,
                 }));
             }
-            let json = serde_json::to_string_pretty(&entries).unwrap();
-            println!("{json}");
+            // Append index consistency diagnostics, keyed by INDEX.md path.
+            for (index_path, diags) in &index_diags {
+                entries.push(serde_json::json!({
+                    "path": index_path.display().to_string(),
+                    "diagnostics": diags,
+                }));
+            }
+            let json = if summary {
+                let tally = Tally::of(&all_diags);
+                serde_json::json!({
+                    "skills": entries,
+                    "summary": {
+                        "total": tally.total,
+                        "errors": tally.errors,
+                        "warnings": tally.warnings,
+                        "ok": tally.ok,
+                    },
+                })
+            } else {
+                serde_json::Value::Array(entries)
+            };
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        super::ReportFormat::Junit => {
+            // Cross-skill and index results become their own test cases.
+            let mut cases = all_diags.clone();
+            if !conflict_diags.is_empty() {
+                cases.push((PathBuf::from("<cross-skill>"), conflict_diags.clone()));
+            }
+            cases.extend(index_diags.iter().cloned());
+            print!("{}", aigent::junit::validation_report(&cases));
         }
     }
 
     if has_errors {
-        std::process::exit(1);
+        super::exit(super::Exit::Findings);
     }
 }
@@ -2,18 +2,40 @@ use std::path::PathBuf;
 
 use aigent::diagnostics::Diagnostic;
 
-pub(crate) fn run(plugin_dir: PathBuf, format: super::Format) {
+pub(crate) fn run(
+    plugin_dir: PathBuf,
+    format: super::Format,
+    plugin_schema: Option<aigent::PluginSchema>,
+    registry: Option<PathBuf>,
+) {
+    let registry = registry.map(|path| {
+        aigent::read_plugin_registry(&path).unwrap_or_else(|e| {
+            eprintln!(
+                "aigent validate-plugin: cannot read registry {}: {e}",
+                path.display()
+            );
+            super::exit_for(&e);
+        })
+    });
+
     let mut all_diags: Vec<(String, Vec<Diagnostic>)> = Vec::new();
 
     // Validate manifest
     let manifest_path = plugin_dir.join("plugin.json");
-    let manifest_diags = aigent::validate_manifest(&manifest_path);
+    // An explicit --plugin-schema wins over the manifest's declared version.
+    let schema = plugin_schema
+        .or_else(|| aigent::declared_schema(&manifest_path))
+        .unwrap_or_default();
+    let mut manifest_diags = aigent::validate_manifest(&manifest_path);
+    if let Some(registry) = &registry {
+        manifest_diags.extend(aigent::check_registry_collisions(&manifest_path, registry));
+    }
     all_diags.push(("plugin.json".to_string(), manifest_diags));
 
     // Validate hooks if hooks.json exists
     let hooks_path = plugin_dir.join("hooks.json");
     if hooks_path.exists() {
-        let hooks_diags = aigent::validate_hooks(&hooks_path);
+        let hooks_diags = aigent::validate_hooks_with(&hooks_path, &schema);
         all_diags.push(("hooks.json".to_string(), hooks_diags));
     }
 
@@ -40,7 +62,7 @@ pub(crate) fn run(plugin_dir: PathBuf, format: super::Format) {
                 let path = entry.path();
                 if path.extension().is_some_and(|e| e == "md") {
                     let label = format!("commands/{}", path.file_name().unwrap().to_string_lossy());
-                    let cmd_diags = aigent::validate_command(&path);
+                    let cmd_diags = aigent::validate_command_with(&path, &schema);
                     all_diags.push((label, cmd_diags));
                 }
             }
@@ -74,12 +96,13 @@ pub(crate) fn run(plugin_dir: PathBuf, format: super::Format) {
 
     match format {
         super::Format::Text => {
+            let styler = super::stderr_styler();
             let total_diags: usize = all_diags.iter().map(|(_, d)| d.len()).sum();
             for (label, diags) in &all_diags {
                 if !diags.is_empty() {
                     eprintln!("{label}:");
                     for d in diags {
-                        eprintln!("  {d}");
+                        eprintln!("  {}", styler.diagnostic(d));
                     }
                 }
             }
@@ -103,6 +126,6 @@ pub(crate) fn run(plugin_dir: PathBuf, format: super::Format) {
     }
 
     if has_errors {
-        std::process::exit(1);
+        super::exit(super::Exit::Findings);
     }
 }
@@ -1,39 +1,101 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 
 use aigent::diagnostics::ValidationTarget;
+use aigent::TestOptions;
 
 /// Run watch mode: re-validate on filesystem changes.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn run_watch_mode(
     skill_dirs: &[PathBuf],
-    _format: super::Format,
+    _format: super::ReportFormat,
     target: super::Target,
-    structure: bool,
+    structure: Option<aigent::StructureOptions>,
     recursive: bool,
+    discovery: aigent::DiscoveryOptions,
     apply_fixes: bool,
+    policy: &aigent::PlaceholderPolicy,
+    cache: Option<&aigent::ValidationCache>,
 ) {
-    let (dirs, disc_warnings) = super::resolve_dirs(skill_dirs, recursive);
+    let target_val: ValidationTarget = target.into();
+    watch_loop(
+        skill_dirs,
+        recursive,
+        discovery,
+        |path| !is_cache_path(path),
+        |dirs| {
+            run_validation_pass(
+                dirs,
+                target_val,
+                structure.as_ref(),
+                apply_fixes,
+                policy,
+                cache,
+            );
+        },
+    );
+}
+
+/// Run test watch mode: re-run test suites when `SKILL.md` or `tests.yml` changes.
+pub(crate) fn run_test_watch_mode(
+    skill_dirs: &[PathBuf],
+    format: super::ReportFormat,
+    recursive: bool,
+    discovery: aigent::DiscoveryOptions,
+    options: &TestOptions,
+) {
+    watch_loop(skill_dirs, recursive, discovery, is_test_input, |dirs| {
+        super::test::run_suites(dirs, format, options);
+    });
+}
+
+/// Returns `true` if `path` is inside a validation cache directory, whose
+/// writes must not trigger another pass.
+fn is_cache_path(path: &Path) -> bool {
+    path.components()
+        .any(|c| c.as_os_str() == aigent::CACHE_DIR)
+}
+
+/// Returns `true` if a changed path can affect a test suite result.
+fn is_test_input(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("SKILL.md" | "skill.md" | "tests.yml")
+    )
+}
+
+/// Resolve skill directories, run `pass` once, then re-run it on changes.
+///
+/// Only events touching a path accepted by `relevant` trigger a re-run.
+/// Directories are re-resolved before each pass so newly added skills are
+/// picked up.
+fn watch_loop(
+    skill_dirs: &[PathBuf],
+    recursive: bool,
+    discovery: aigent::DiscoveryOptions,
+    relevant: fn(&Path) -> bool,
+    mut pass: impl FnMut(&[PathBuf]),
+) {
+    let (dirs, disc_warnings) = super::resolve_dirs(skill_dirs, recursive, discovery);
     for w in &disc_warnings {
         eprintln!("warning: {}: {}", w.path.display(), w.message);
     }
     if dirs.is_empty() {
         eprintln!("No SKILL.md files found.");
-        std::process::exit(1);
+        super::exit(super::Exit::Usage);
     }
 
-    let target_val: ValidationTarget = target.into();
-
-    // Run initial validation.
-    run_validation_pass(&dirs, target_val, structure, apply_fixes);
+    // Run initial pass.
+    pass(&dirs);
 
     // Set up file watcher.
     let (tx, rx) = mpsc::channel();
     let mut watcher = RecommendedWatcher::new(tx, Config::default()).unwrap_or_else(|e| {
         eprintln!("aigent watch: failed to create watcher: {e}");
-        std::process::exit(1);
+        super::exit(super::Exit::Failure);
     });
 
     // Watch all parent directories of skill dirs.
@@ -61,7 +123,17 @@ pub(crate) fn run_watch_mode(
 
     loop {
         match rx.recv() {
-            Ok(_event) => {
+            Ok(event) => {
+                // Ignore events that cannot affect the result; watcher errors
+                // always trigger a re-run to be safe.
+                let is_relevant = match &event {
+                    Ok(ev) => ev.paths.iter().any(|p| relevant(p)),
+                    Err(_) => true,
+                };
+                if !is_relevant {
+                    continue;
+                }
+
                 // Debounce: skip if we ran too recently.
                 if last_run.elapsed() < debounce {
                     // Drain pending events.
@@ -73,15 +145,15 @@ pub(crate) fn run_watch_mode(
                 eprint!("\x1b[2J\x1b[H");
 
                 // Re-resolve dirs in case new skills appeared.
-                let (dirs, disc_warnings) = super::resolve_dirs(skill_dirs, recursive);
+                let (dirs, disc_warnings) = super::resolve_dirs(skill_dirs, recursive, discovery);
                 for w in &disc_warnings {
                     eprintln!("warning: {}: {}", w.path.display(), w.message);
                 }
-                run_validation_pass(&dirs, target_val, structure, apply_fixes);
+                pass(&dirs);
 
                 last_run = Instant::now();
 
-                // Drain any queued events during validation.
+                // Drain any queued events during the pass.
                 while rx.try_recv().is_ok() {}
             }
             Err(e) => {
@@ -96,26 +168,29 @@ pub(crate) fn run_watch_mode(
 fn run_validation_pass(
     dirs: &[PathBuf],
     target: ValidationTarget,
-    structure: bool,
+    structure: Option<&aigent::StructureOptions>,
     apply_fixes: bool,
+    policy: &aigent::PlaceholderPolicy,
+    cache: Option<&aigent::ValidationCache>,
 ) {
+    let styler = super::stderr_styler();
     let mut total_errors = 0;
     let mut total_warnings = 0;
 
     for dir in dirs {
-        let mut diags = aigent::validate_with_target(dir, target);
+        let mut diags = super::validate::validate_dir(dir, target, policy, cache);
 
         if apply_fixes {
             if let Ok(count) = aigent::apply_fixes(dir, &diags) {
                 if count > 0 {
                     eprintln!("Applied {count} fix(es) to {}", dir.display());
-                    diags = aigent::validate_with_target(dir, target);
+                    diags = super::validate::validate_dir(dir, target, policy, cache);
                 }
             }
         }
 
-        if structure {
-            diags.extend(aigent::validate_structure(dir));
+        if let Some(options) = structure {
+            diags.extend(aigent::validate_structure_with(dir, options));
         }
 
         let has_errors = diags.iter().any(|d| d.is_error());
@@ -133,9 +208,9 @@ fn run_validation_pass(
             }
             for d in &diags {
                 if dirs.len() > 1 {
-                    eprintln!("  {d}");
+                    eprintln!("  {}", styler.diagnostic(d));
                 } else {
-                    eprintln!("{d}");
+                    eprintln!("{}", styler.diagnostic(d));
                 }
             }
         }
@@ -143,5 +218,8 @@ fn run_validation_pass(
 
     let total = dirs.len();
     let ok = total - total_errors - total_warnings;
-    eprintln!("\n{total} skills: {ok} ok, {total_errors} errors, {total_warnings} warnings only");
+    eprintln!(
+        "\n{}",
+        styler.summary(total, ok, total_errors, total_warnings)
+    );
 }
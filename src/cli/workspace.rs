@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+
+use aigent::diagnostics::Diagnostic;
+use aigent::workspace::{CheckProfile, CollectionConfig, WorkspaceConfig};
+use aigent::TestOptions;
+
+/// Workspace subcommand selected on the command line.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Action {
+    Check,
+    Prompt,
+    Doc,
+    Test,
+}
+
+pub(crate) fn run(action: Action, root: PathBuf) {
+    let config = aigent::load_workspace(&root).unwrap_or_else(|e| {
+        eprintln!("aigent workspace: {e}");
+        super::exit_for(&e);
+    });
+
+    let mut failed = Vec::new();
+    for collection in &config.collections {
+        let name = collection.display_name();
+        eprintln!("== {name} ==");
+        let dirs = collection.skill_dirs(&root);
+        let ok = if dirs.is_empty() {
+            eprintln!("{name}: no skills found");
+            true
+        } else {
+            match action {
+                Action::Check => check_collection(collection, &dirs),
+                Action::Prompt => prompt_collection(collection, &root, &dirs),
+                Action::Doc => doc_collection(collection, &root, &dirs),
+                Action::Test => test_collection(collection, &dirs),
+            }
+        };
+        if !ok {
+            failed.push(name);
+        }
+        eprintln!();
+    }
+
+    print_workspace_summary(&config, &failed);
+    if !failed.is_empty() {
+        super::exit(super::Exit::Findings);
+    }
+}
+
+/// Print the combined result line for all collections.
+fn print_workspace_summary(config: &WorkspaceConfig, failed: &[String]) {
+    let total = config.collections.len();
+    let passed = total - failed.len();
+    if failed.is_empty() {
+        eprintln!("Workspace: {total} collections: {passed} passed, 0 failed");
+    } else {
+        eprintln!(
+            "Workspace: {total} collections: {passed} passed, {} failed ({})",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+}
+
+/// Run the collection's check profile and score gate over its skills.
+///
+/// Returns `false` if any skill has errors (or warnings, under the strict
+/// profile) or scores below `min_score`.
+fn check_collection(collection: &CollectionConfig, dirs: &[PathBuf]) -> bool {
+    let name = collection.display_name();
+    let styler = super::stderr_styler();
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut below_gate = Vec::new();
+
+    for dir in dirs {
+        let mut diags: Vec<Diagnostic> = aigent::validate_with_target(dir, collection.target);
+        if collection.profile != CheckProfile::Spec {
+            diags.extend(super::check::lint_dir(dir, &aigent::LintOptions::default()));
+        }
+        if collection.profile == CheckProfile::Strict {
+            diags.extend(aigent::validate_structure(dir));
+        }
+
+        if diags.iter().any(|d| d.is_error()) {
+            errors += 1;
+        } else if diags.iter().any(|d| d.is_warning()) {
+            warnings += 1;
+        }
+        if !diags.is_empty() {
+            eprintln!("{}:", dir.display());
+            for d in &diags {
+                eprintln!("  {}", styler.diagnostic(d));
+            }
+        }
+
+        if let Some(min) = collection.min_score {
+            let result = aigent::score(dir);
+            if result.total < min {
+                below_gate.push(format!("{} ({})", dir.display(), result.total));
+            }
+        }
+    }
+
+    let total = dirs.len();
+    let ok = total - errors - warnings;
+    let mut summary = format!("{name}: {}", styler.summary(total, ok, errors, warnings));
+    if let Some(min) = collection.min_score {
+        summary.push_str(&format!(", {} below min score {min}", below_gate.len()));
+    }
+    eprintln!("{summary}");
+    for entry in &below_gate {
+        eprintln!("  below min score: {entry}");
+    }
+
+    let strict_warnings = collection.profile == CheckProfile::Strict && warnings > 0;
+    errors == 0 && !strict_warnings && below_gate.is_empty()
+}
+
+/// Generate the collection's prompt, writing to `prompt_output` if set.
+///
+/// Like `prompt --output`, returns `false` when the output file changed so
+/// CI can detect stale generated prompts.
+fn prompt_collection(collection: &CollectionConfig, root: &Path, dirs: &[PathBuf]) -> bool {
+    let dir_refs: Vec<&Path> = dirs.iter().map(|d| d.as_path()).collect();
+    let (entries, warnings) = aigent::collect_skills_verbose(&dir_refs);
+    for w in &warnings {
+        eprintln!("warning: {}: {}", w.path.display(), w.message);
+    }
+    let content = aigent::format_entries(&entries, aigent::PromptFormat::Xml);
+    emit_output(
+        collection,
+        collection.prompt_output.as_deref(),
+        root,
+        &content,
+        entries.len(),
+    )
+}
+
+/// Generate the collection's skill catalog, writing to `doc_output` if set.
+fn doc_collection(collection: &CollectionConfig, root: &Path, dirs: &[PathBuf]) -> bool {
+    let dir_refs: Vec<&Path> = dirs.iter().map(|d| d.as_path()).collect();
+    let opts = aigent::CollectOptions {
+        include_deprecated: true,
+        provenance: false,
+    };
+    let (entries, warnings) = aigent::collect_skills_verbose_with(&dir_refs, &opts);
+    for w in &warnings {
+        eprintln!("warning: {}: {}", w.path.display(), w.message);
+    }
+    let content = aigent::docgen::format_markdown(&aigent::catalog_entries(&entries));
+    emit_output(
+        collection,
+        collection.doc_output.as_deref(),
+        root,
+        &content,
+        entries.len(),
+    )
+}
+
+/// Print generated content to stdout, or write it to `output` (relative to
+/// the workspace root) if it changed. Returns `false` if a file was updated.
+fn emit_output(
+    collection: &CollectionConfig,
+    output: Option<&Path>,
+    root: &Path,
+    content: &str,
+    count: usize,
+) -> bool {
+    let name = collection.display_name();
+    let Some(output) = output else {
+        println!("{content}");
+        eprintln!("{name}: {count} skills");
+        return true;
+    };
+
+    let path = root.join(output);
+    let existing = std::fs::read_to_string(&path).ok();
+    if existing.as_deref() == Some(content) {
+        eprintln!("{name}: {count} skills, unchanged {}", path.display());
+        return true;
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).unwrap_or_else(|e| {
+            eprintln!(
+                "aigent workspace: failed to create directory {}: {e}",
+                parent.display()
+            );
+            super::exit(super::Exit::Failure);
+        });
+    }
+    std::fs::write(&path, content).unwrap_or_else(|e| {
+        eprintln!("aigent workspace: failed to write {}: {e}", path.display());
+        super::exit(super::Exit::Failure);
+    });
+    eprintln!("{name}: {count} skills, updated {}", path.display());
+    false
+}
+
+/// Run fixture test suites for the collection's skills that have `tests.yml`.
+fn test_collection(collection: &CollectionConfig, dirs: &[PathBuf]) -> bool {
+    let name = collection.display_name();
+    let with_fixtures: Vec<PathBuf> = dirs
+        .iter()
+        .filter(|d| d.join("tests.yml").is_file())
+        .cloned()
+        .collect();
+    let skipped = dirs.len() - with_fixtures.len();
+
+    let ok = if with_fixtures.is_empty() {
+        true
+    } else {
+        super::test::run_suites(
+            &with_fixtures,
+            super::ReportFormat::Text,
+            &TestOptions::default(),
+        )
+    };
+    eprintln!(
+        "{name}: {} suites {}, {skipped} skills without tests.yml",
+        with_fixtures.len(),
+        if ok { "passed" } else { "failed" }
+    );
+    ok
+}
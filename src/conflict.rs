@@ -1,13 +1,17 @@
 //! Cross-skill conflict detection for skill collections.
 //!
 //! Analyzes collections of skills for potential conflicts: name collisions,
-//! description similarity, and token budget overruns. Uses diagnostic codes
-//! C001–C003.
+//! description similarity, token budget overruns, and near-duplicate names.
+//! Uses diagnostic codes C001–C003 and C005. [`detect_activation_overlap`] adds a behavioral check (C004)
+//! that probes skills against sample queries with the tester's matcher.
 
 use std::collections::HashSet;
+use std::path::Path;
 
-use crate::diagnostics::{Diagnostic, Severity, C001, C002, C003};
+use crate::diagnostics::{Diagnostic, Severity, C001, C002, C003, C004, C005};
+use crate::models::first_sentence;
 use crate::prompt::{estimate_tokens, SkillEntry};
+use crate::tester::{compute_query_match_with, QueryMatch};
 
 /// Default similarity threshold for description overlap detection.
 ///
@@ -16,17 +20,95 @@ use crate::prompt::{estimate_tokens, SkillEntry};
 /// `--similarity-threshold` to adjust.
 const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.7;
 
+/// Default similarity at which a C002 overlap is a warning rather than info.
+///
+/// Pairs this similar are likely near-duplicates; pairs between the main
+/// threshold and this band are merely related.
+const DEFAULT_WARNING_THRESHOLD: f64 = 0.9;
+
 /// Token budget warning threshold.
 ///
 /// Total estimated token usage above this threshold triggers a C003 warning.
 const TOKEN_BUDGET_THRESHOLD: usize = 4000;
 
+/// Default name similarity at or above which two names are flagged (C005).
+///
+/// High enough that `pdf-extract`/`pdf-extractor` (85%) is flagged while
+/// `pdf-tools`/`pdf-utils` (67%) is not.
+const DEFAULT_NAME_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Options controlling conflict detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConflictOptions {
+    /// Jaccard similarity at or above which a C002 overlap is reported.
+    pub similarity_threshold: f64,
+    /// Similarity at or above which a C002 overlap is a warning. Overlaps
+    /// below this band (but above `similarity_threshold`) are info.
+    pub warning_threshold: f64,
+    /// Name similarity at or above which two different names are reported
+    /// as near-duplicates (C005).
+    pub name_similarity_threshold: f64,
+    /// Which pairs of skills are compared (C001, C002, C005).
+    pub scope: ConflictScope,
+}
+
+impl Default for ConflictOptions {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
+            warning_threshold: DEFAULT_WARNING_THRESHOLD,
+            name_similarity_threshold: DEFAULT_NAME_SIMILARITY_THRESHOLD,
+            scope: ConflictScope::Global,
+        }
+    }
+}
+
+/// Which pairs of skills the pairwise conflict checks compare.
+///
+/// In a monorepo, skills in separate plugins may share vocabulary without
+/// ever being loaded together; scoping keeps checks within plugin
+/// boundaries. The token budget (C003) always covers the whole collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictScope {
+    /// Compare every pair of skills.
+    #[default]
+    Global,
+    /// Compare only skills whose directories share the ancestor `levels`
+    /// path levels up. `levels: 1` compares siblings (same parent
+    /// directory); `levels: 2` also compares skills in sibling directories
+    /// of that parent, and so on.
+    Sibling {
+        /// Path levels above each skill directory that must coincide.
+        levels: usize,
+    },
+}
+
+impl ConflictScope {
+    /// Returns `true` if skills `a` and `b` are compared under this scope.
+    fn includes(self, a: &SkillEntry, b: &SkillEntry) -> bool {
+        match self {
+            Self::Global => true,
+            Self::Sibling { levels } => scope_root(a, levels) == scope_root(b, levels),
+        }
+    }
+}
+
+/// The directory `levels` above an entry's skill directory.
+///
+/// `location` is the `SKILL.md` path, so the skill directory is one level
+/// up from it.
+fn scope_root(entry: &SkillEntry, levels: usize) -> Option<&Path> {
+    Path::new(&entry.location).ancestors().nth(levels + 1)
+}
+
 /// Detect conflicts across a collection of skills.
 ///
-/// Runs three checks:
+/// Runs four checks:
 /// - C001: Name collisions (same name in different locations)
-/// - C002: Description similarity above threshold
+/// - C002: Description similarity above threshold (info, or a warning for
+///   near-duplicates)
 /// - C003: Total token budget exceeded
+/// - C005: Near-duplicate names (e.g. `pdf-extract` and `pdf-extractor`)
 ///
 /// # Arguments
 ///
@@ -34,35 +116,56 @@ const TOKEN_BUDGET_THRESHOLD: usize = 4000;
 ///
 /// # Returns
 ///
-/// A list of warning diagnostics. Empty means no conflicts detected.
+/// A list of diagnostics. Empty means no conflicts detected.
 #[must_use]
 pub fn detect_conflicts(entries: &[SkillEntry]) -> Vec<Diagnostic> {
-    detect_conflicts_with_threshold(entries, DEFAULT_SIMILARITY_THRESHOLD)
+    detect_conflicts_with(entries, &ConflictOptions::default())
 }
 
 /// Detect conflicts with a custom similarity threshold.
 ///
 /// Same as [`detect_conflicts`] but allows overriding the Jaccard similarity
-/// threshold for C002 checks.
+/// threshold for C002 checks. The warning band keeps its default.
 #[must_use]
 pub fn detect_conflicts_with_threshold(
     entries: &[SkillEntry],
     similarity_threshold: f64,
 ) -> Vec<Diagnostic> {
+    detect_conflicts_with(
+        entries,
+        &ConflictOptions {
+            similarity_threshold,
+            ..ConflictOptions::default()
+        },
+    )
+}
+
+/// Detect conflicts with explicit options.
+///
+/// C002 overlaps at or above `warning_threshold` are warnings; those between
+/// `similarity_threshold` and `warning_threshold` are info. Name and
+/// description checks only compare skills within `opts.scope`.
+#[must_use]
+pub fn detect_conflicts_with(entries: &[SkillEntry], opts: &ConflictOptions) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    diags.extend(check_name_collisions(entries));
-    diags.extend(check_description_similarity(entries, similarity_threshold));
+    diags.extend(check_name_collisions(entries, opts.scope));
+    diags.extend(check_description_similarity(entries, opts));
     diags.extend(check_token_budget(entries));
+    diags.extend(check_name_similarity(entries, opts));
     diags
 }
 
 /// C001: Check for name collisions across skill directories.
-fn check_name_collisions(entries: &[SkillEntry]) -> Vec<Diagnostic> {
+///
+/// Reports each entry whose name already appeared earlier within `scope`.
+fn check_name_collisions(entries: &[SkillEntry], scope: ConflictScope) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    let mut seen: HashSet<&str> = HashSet::new();
 
-    for entry in entries {
-        if !seen.insert(&entry.name) {
+    for (j, entry) in entries.iter().enumerate() {
+        let collides = entries[..j]
+            .iter()
+            .any(|earlier| earlier.name == entry.name && scope.includes(earlier, entry));
+        if collides {
             diags.push(
                 Diagnostic::new(
                     Severity::Warning,
@@ -86,7 +189,7 @@ fn check_name_collisions(entries: &[SkillEntry]) -> Vec<Diagnostic> {
 /// Uses Jaccard similarity (word overlap ratio) to detect skills that
 /// might trigger on the same queries. Pre-tokenizes descriptions once
 /// before the O(n^2) comparison loop to avoid repeated per-pair allocations.
-fn check_description_similarity(entries: &[SkillEntry], threshold: f64) -> Vec<Diagnostic> {
+fn check_description_similarity(entries: &[SkillEntry], opts: &ConflictOptions) -> Vec<Diagnostic> {
     // Pre-tokenize once: O(n)
     let token_sets: Vec<HashSet<String>> =
         entries.iter().map(|e| tokenize(&e.description)).collect();
@@ -95,11 +198,19 @@ fn check_description_similarity(entries: &[SkillEntry], threshold: f64) -> Vec<D
     let mut diags = Vec::new();
     for i in 0..entries.len() {
         for j in (i + 1)..entries.len() {
+            if !opts.scope.includes(&entries[i], &entries[j]) {
+                continue;
+            }
             let sim = jaccard_from_sets(&token_sets[i], &token_sets[j]);
-            if sim >= threshold {
+            if sim >= opts.similarity_threshold {
+                let severity = if sim >= opts.warning_threshold {
+                    Severity::Warning
+                } else {
+                    Severity::Info
+                };
                 diags.push(
                     Diagnostic::new(
-                        Severity::Warning,
+                        severity,
                         C002,
                         format!(
                             "description overlap ({:.0}%): '{}' and '{}'",
@@ -118,6 +229,85 @@ fn check_description_similarity(entries: &[SkillEntry], threshold: f64) -> Vec<D
     diags
 }
 
+/// C005: Check for confusingly similar names between skills.
+///
+/// Two different names are near-duplicates when their normalized edit
+/// similarity, or the overlap of their hyphen-separated segments, reaches
+/// `opts.name_similarity_threshold` — e.g. `pdf-extract` and
+/// `pdf-extractor`, or `extract-pdf` and `pdf-extract`. Identical names are
+/// left to C001.
+fn check_name_similarity(entries: &[SkillEntry], opts: &ConflictOptions) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    for i in 0..entries.len() {
+        for j in (i + 1)..entries.len() {
+            let (a, b) = (&entries[i].name, &entries[j].name);
+            if a == b || !opts.scope.includes(&entries[i], &entries[j]) {
+                continue;
+            }
+            let sim = name_similarity(a, b);
+            if sim >= opts.name_similarity_threshold {
+                diags.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        C005,
+                        format!("similar names ({:.0}%): '{a}' and '{b}'", sim * 100.0),
+                    )
+                    .with_field("name")
+                    .with_suggestion(
+                        "Rename one of the skills so the names are easy to tell apart",
+                    ),
+                );
+            }
+        }
+    }
+    diags
+}
+
+/// Similarity of two skill names, from 0.0 (unrelated) to 1.0 (identical).
+///
+/// The higher of the normalized edit similarity and the Jaccard overlap of
+/// the names' hyphen-separated segments. Segment overlap only counts when
+/// both names have at least two segments, so a one-word name is not
+/// matched against every name that contains it.
+pub(crate) fn name_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (a.to_lowercase(), b.to_lowercase());
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    let edit = 1.0 - edit_distance(&a, &b) as f64 / max_len as f64;
+
+    let segments = |s: &str| -> HashSet<String> {
+        s.split('-')
+            .filter(|seg| !seg.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (sa, sb) = (segments(&a), segments(&b));
+    let overlap = if sa.len() >= 2 && sb.len() >= 2 {
+        jaccard_from_sets(&sa, &sb)
+    } else {
+        0.0
+    };
+    edit.max(overlap)
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 /// C003: Check total token budget across all skills.
 fn check_token_budget(entries: &[SkillEntry]) -> Vec<Diagnostic> {
     let total: usize = entries.iter().map(estimate_entry_tokens).sum();
@@ -139,6 +329,78 @@ fn check_token_budget(entries: &[SkillEntry]) -> Vec<Diagnostic> {
     }
 }
 
+/// Detect skills that strongly activate on the same queries.
+///
+/// Probes every skill against each sample query using the same weighted
+/// matcher as `aigent probe`, and emits a C004 warning for each pair of
+/// skills that both reach a strong match on at least one query. Where
+/// description similarity (C002) is a proxy, this checks the behavior that
+/// actually causes conflicts: two skills competing for one request.
+///
+/// If `sample_queries` is empty, one query is generated per skill from the
+/// first sentence of its description, so each skill is checked against
+/// what the others claim to handle.
+#[must_use]
+pub fn detect_activation_overlap(
+    entries: &[SkillEntry],
+    sample_queries: &[&str],
+) -> Vec<Diagnostic> {
+    let queries: Vec<&str> = if sample_queries.is_empty() {
+        entries
+            .iter()
+            .map(|e| first_sentence(&e.description))
+            .filter(|q| !q.is_empty())
+            .collect()
+    } else {
+        sample_queries.to_vec()
+    };
+
+    // strong[i] holds the indices of the queries skill i strongly matches.
+    let strong: Vec<HashSet<usize>> = entries
+        .iter()
+        .map(|e| {
+            queries
+                .iter()
+                .enumerate()
+                .filter(|(_, q)| {
+                    let (category, _) = compute_query_match_with(q, &e.name, &e.description, true);
+                    category == QueryMatch::Strong
+                })
+                .map(|(qi, _)| qi)
+                .collect()
+        })
+        .collect();
+
+    let mut diags = Vec::new();
+    for i in 0..entries.len() {
+        for j in (i + 1)..entries.len() {
+            let mut shared: Vec<usize> = strong[i].intersection(&strong[j]).copied().collect();
+            if shared.is_empty() {
+                continue;
+            }
+            shared.sort_unstable();
+            diags.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    C004,
+                    format!(
+                        "activation overlap: '{}' and '{}' both strongly match {} of {} sample queries (e.g., \"{}\")",
+                        entries[i].name,
+                        entries[j].name,
+                        shared.len(),
+                        queries.len(),
+                        queries[shared[0]],
+                    ),
+                )
+                .with_field("description")
+                .with_suggestion("Narrow one description so only one skill handles these queries"),
+            );
+        }
+    }
+
+    diags
+}
+
 /// Estimate tokens for a single skill entry.
 ///
 /// Estimates from name + description only, since those are the fields
@@ -152,7 +414,7 @@ fn estimate_entry_tokens(entry: &SkillEntry) -> usize {
 ///
 /// Splits on whitespace, trims non-alphanumeric characters, lowercases,
 /// and collects into a `HashSet`.
-fn tokenize(s: &str) -> HashSet<String> {
+pub(crate) fn tokenize(s: &str) -> HashSet<String> {
     s.split_whitespace()
         .map(|w| {
             w.trim_matches(|c: char| !c.is_alphanumeric())
@@ -165,7 +427,7 @@ fn tokenize(s: &str) -> HashSet<String> {
 /// Compute Jaccard similarity between two pre-tokenized sets.
 ///
 /// Returns 0.0 if both sets are empty.
-fn jaccard_from_sets(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+pub(crate) fn jaccard_from_sets(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
     let intersection = a.intersection(b).count();
     let union = a.union(b).count();
     if union == 0 {
@@ -194,6 +456,9 @@ mod tests {
             name: name.to_string(),
             description: description.to_string(),
             location: format!("skills/{name}"),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
         }
     }
 
@@ -273,6 +538,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn c002_severity_follows_similarity_band() {
+        let opts = ConflictOptions {
+            similarity_threshold: 0.5,
+            warning_threshold: 0.9,
+            ..ConflictOptions::default()
+        };
+        // 19 of 20 distinct words shared → 0.95.
+        let shared: Vec<String> = (0..19).map(|i| format!("word{i}")).collect();
+        let near = vec![
+            make_entry("skill-a", &shared.join(" ")),
+            make_entry("skill-b", &format!("{} extra", shared.join(" "))),
+        ];
+        let diags = detect_conflicts_with(&near, &opts);
+        let c002: Vec<_> = diags.iter().filter(|d| d.code == C002).collect();
+        assert_eq!(c002.len(), 1, "got: {diags:?}");
+        assert!(c002[0].is_warning(), "0.95 overlap should warn: {c002:?}");
+
+        // 3 of 5 distinct words shared → 0.6.
+        let loose = vec![
+            make_entry("skill-a", "alpha beta gamma delta"),
+            make_entry("skill-b", "alpha beta gamma epsilon"),
+        ];
+        let diags = detect_conflicts_with(&loose, &opts);
+        let c002: Vec<_> = diags.iter().filter(|d| d.code == C002).collect();
+        assert_eq!(c002.len(), 1, "got: {diags:?}");
+        assert!(c002[0].is_info(), "0.6 overlap should be info: {c002:?}");
+    }
+
     // ── C003: Token budget ───────────────────────────────────────────
 
     #[test]
@@ -401,6 +695,193 @@ mod tests {
         assert!(tokens.is_empty());
     }
 
+    // ── C005: Similar names ──────────────────────────────────────────
+
+    #[test]
+    fn c005_suffix_variant_names_warn() {
+        let entries = vec![
+            make_entry("pdf-extract", "Extracts text from PDF files"),
+            make_entry("pdf-extractor", "Converts spreadsheets to CSV"),
+        ];
+        let diags = detect_conflicts(&entries);
+        let c005: Vec<_> = diags.iter().filter(|d| d.code == C005).collect();
+        assert_eq!(c005.len(), 1, "got: {diags:?}");
+        assert!(c005[0].is_warning());
+        assert!(c005[0]
+            .message
+            .contains("'pdf-extract' and 'pdf-extractor'"));
+    }
+
+    #[test]
+    fn c005_reordered_segments_warn() {
+        let entries = vec![
+            make_entry("extract-pdf-text", "Extracts text from PDF files"),
+            make_entry("pdf-text-extract", "Converts spreadsheets to CSV"),
+        ];
+        let diags = detect_conflicts(&entries);
+        assert!(diags.iter().any(|d| d.code == C005), "got: {diags:?}");
+    }
+
+    #[test]
+    fn c005_distinct_names_silent() {
+        let entries = vec![
+            make_entry("pdf-tools", "Extracts text from PDF files"),
+            make_entry("pdf-utils", "Converts spreadsheets to CSV"),
+            make_entry("pdf", "Reads documents"),
+            make_entry("processing-pdfs", "Fills forms"),
+        ];
+        let diags = detect_conflicts(&entries);
+        assert!(!diags.iter().any(|d| d.code == C005), "got: {diags:?}");
+    }
+
+    #[test]
+    fn c005_identical_names_left_to_c001() {
+        let entries = vec![
+            make_entry("my-skill", "First skill"),
+            make_entry("my-skill", "Second skill"),
+        ];
+        let diags = detect_conflicts(&entries);
+        assert!(!diags.iter().any(|d| d.code == C005), "got: {diags:?}");
+    }
+
+    #[test]
+    fn c005_threshold_is_configurable() {
+        let entries = vec![
+            make_entry("pdf-tools", "Extracts text from PDF files"),
+            make_entry("pdf-utils", "Converts spreadsheets to CSV"),
+        ];
+        let opts = ConflictOptions {
+            name_similarity_threshold: 0.6,
+            ..ConflictOptions::default()
+        };
+        let diags = detect_conflicts_with(&entries, &opts);
+        assert!(diags.iter().any(|d| d.code == C005), "got: {diags:?}");
+    }
+
+    // ── Conflict scope ───────────────────────────────────────────────
+
+    fn entry_at(location: &str, name: &str, description: &str) -> SkillEntry {
+        SkillEntry {
+            location: location.to_string(),
+            ..make_entry(name, description)
+        }
+    }
+
+    fn scoped(levels: usize) -> ConflictOptions {
+        ConflictOptions {
+            scope: ConflictScope::Sibling { levels },
+            ..ConflictOptions::default()
+        }
+    }
+
+    #[test]
+    fn sibling_scope_ignores_skills_in_different_parents() {
+        let entries = vec![
+            entry_at(
+                "plugins/docs/skills/pdf-extract/SKILL.md",
+                "pdf-extract",
+                "Extracts text from PDF files",
+            ),
+            entry_at(
+                "plugins/forms/skills/pdf-extractor/SKILL.md",
+                "pdf-extractor",
+                "Extracts text from PDF files",
+            ),
+        ];
+        let global = detect_conflicts(&entries);
+        assert!(global.iter().any(|d| d.code == C002), "got: {global:?}");
+        assert!(global.iter().any(|d| d.code == C005), "got: {global:?}");
+
+        let sibling = detect_conflicts_with(&entries, &scoped(1));
+        assert!(sibling.is_empty(), "got: {sibling:?}");
+        // Three levels up, both skills are under `plugins/`.
+        let wider = detect_conflicts_with(&entries, &scoped(3));
+        assert!(wider.iter().any(|d| d.code == C002), "got: {wider:?}");
+    }
+
+    #[test]
+    fn sibling_scope_still_compares_same_parent() {
+        let entries = vec![
+            entry_at("skills/my-skill/SKILL.md", "my-skill", "First skill"),
+            entry_at("skills/copy/SKILL.md", "my-skill", "Second skill"),
+            entry_at("other/my-skill/SKILL.md", "my-skill", "Third skill"),
+        ];
+        let diags = detect_conflicts_with(&entries, &scoped(1));
+        assert_eq!(diags.iter().filter(|d| d.code == C001).count(), 1);
+        let global = detect_conflicts(&entries);
+        assert_eq!(global.iter().filter(|d| d.code == C001).count(), 2);
+    }
+
+    #[test]
+    fn edit_distance_counts_characters() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("äb", "ab"), 1);
+        assert!((name_similarity("pdf-extract", "pdf-extractor") - 11.0 / 13.0).abs() < 1e-9);
+        assert!((name_similarity("pdf-tools", "pdf-utils") - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    // ── C004: Activation overlap ─────────────────────────────────────
+
+    #[test]
+    fn activation_overlap_flags_skills_matching_same_query() {
+        let entries = vec![
+            make_entry(
+                "pdf-extractor",
+                "Extracts text from PDF files. Use when working with PDF documents.",
+            ),
+            make_entry(
+                "pdf-reader",
+                "Reads text from PDF files. Use when reading PDF documents.",
+            ),
+            make_entry(
+                "csv-formatter",
+                "Formats CSV tables. Use when cleaning spreadsheet exports.",
+            ),
+        ];
+        let diags = detect_activation_overlap(&entries, &["extract text from pdf files"]);
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, C004);
+        assert!(diags[0].is_warning());
+        assert!(diags[0]
+            .message
+            .contains("'pdf-extractor' and 'pdf-reader'"));
+        assert!(diags[0].message.contains("1 of 1 sample queries"));
+    }
+
+    #[test]
+    fn activation_overlap_ignores_distinct_skills() {
+        let entries = vec![
+            make_entry("pdf-extractor", "Extracts text from PDF files."),
+            make_entry("csv-formatter", "Formats CSV tables for spreadsheets."),
+        ];
+        let diags = detect_activation_overlap(&entries, &["extract pdf text", "format csv tables"]);
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    #[test]
+    fn activation_overlap_generates_queries_from_descriptions() {
+        let entries = vec![
+            make_entry(
+                "pdf-extractor",
+                "Extracts text from PDF files. Use when working with PDF documents.",
+            ),
+            make_entry(
+                "pdf-text",
+                "Extracts text from PDF files quickly. Handles scanned pages.",
+            ),
+        ];
+        let diags = detect_activation_overlap(&entries, &[]);
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert!(diags[0].message.contains("Extracts text from PDF files"));
+    }
+
+    #[test]
+    fn activation_overlap_empty_collection() {
+        assert!(detect_activation_overlap(&[], &[]).is_empty());
+        assert!(detect_activation_overlap(&[], &["query"]).is_empty());
+    }
+
     // ── jaccard_from_sets ───────────────────────────────────────────────
 
     #[test]
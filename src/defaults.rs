@@ -0,0 +1,200 @@
+//! Shared frontmatter defaults from `_defaults.yml`.
+//!
+//! Skills in a collection often share `license` or `compatibility`. A
+//! `_defaults.yml` in a parent directory holds those shared values: during
+//! validation its top-level keys fill in frontmatter fields a skill leaves
+//! out. `SKILL.md` files on disk are never changed.
+//!
+//! Precedence and scope:
+//! - A skill's own value always wins over a default.
+//! - Only the nearest ancestor's `_defaults.yml` applies; files further up
+//!   the tree are ignored, not merged.
+//! - The search stops at the repository or workspace root: the first
+//!   ancestor holding `.git` or `aigent.toml`.
+//! - Keys are layered whole. A skill's `metadata:` block replaces a default
+//!   `metadata:` block rather than merging with it.
+//! - `name` and `description` identify a skill and are never taken from
+//!   defaults.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde_yaml_ng::Value;
+
+use crate::errors::{AigentError, Result};
+use crate::parser::read_file_checked;
+
+/// File name of a shared defaults file.
+pub const DEFAULTS_FILE: &str = "_defaults.yml";
+
+/// Frontmatter keys that are never filled in from defaults.
+const PER_SKILL_KEYS: &[&str] = &["name", "description"];
+
+/// Find the `_defaults.yml` that applies to a skill directory.
+///
+/// Searches the skill's parent directory and then each further ancestor,
+/// returning the first `_defaults.yml` found. The search ends at the first
+/// directory (the skill's own included) that holds `.git` or `aigent.toml`,
+/// so a stray file above the repository never applies.
+#[must_use]
+pub fn find_defaults(skill_dir: &Path) -> Option<PathBuf> {
+    let dir = std::fs::canonicalize(skill_dir).unwrap_or_else(|_| skill_dir.to_path_buf());
+    for ancestor in dir.ancestors() {
+        if ancestor != dir {
+            let candidate = ancestor.join(DEFAULTS_FILE);
+            if crate::fs_util::is_regular_file(&candidate) {
+                return Some(candidate);
+            }
+        }
+        if is_search_root(ancestor) {
+            break;
+        }
+    }
+    None
+}
+
+/// Whether `dir` is a repository or workspace root, where the search for
+/// `_defaults.yml` stops.
+fn is_search_root(dir: &Path) -> bool {
+    dir.join(".git").exists() || dir.join(crate::workspace::WORKSPACE_FILE).is_file()
+}
+
+/// Read and parse a `_defaults.yml` file.
+///
+/// An empty file yields no defaults.
+///
+/// # Errors
+///
+/// Returns `AigentError::Io` if the file cannot be read,
+/// `AigentError::Yaml` if it is not valid YAML, and `AigentError::Parse` if
+/// it is not a mapping with string keys.
+pub fn load_defaults(path: &Path) -> Result<HashMap<String, Value>> {
+    let content = read_file_checked(path)?;
+    match serde_yaml_ng::from_str::<Value>(&content)? {
+        Value::Null => Ok(HashMap::new()),
+        Value::Mapping(mapping) => mapping
+            .into_iter()
+            .map(|(key, value)| match key {
+                Value::String(key) => Ok((key, value)),
+                other => Err(AigentError::Parse {
+                    message: format!("{DEFAULTS_FILE} key is not a string: {other:?}"),
+                }),
+            })
+            .collect(),
+        _ => Err(AigentError::Parse {
+            message: format!("{DEFAULTS_FILE} must be a mapping of frontmatter fields"),
+        }),
+    }
+}
+
+/// Fill in `metadata` keys that are missing, taking values from `defaults`.
+///
+/// Keys already present in `metadata` are left alone, and `name` and
+/// `description` are never added.
+pub fn apply_defaults(metadata: &mut HashMap<String, Value>, defaults: &HashMap<String, Value>) {
+    for (key, value) in defaults {
+        if !PER_SKILL_KEYS.contains(&key.as_str()) && !metadata.contains_key(key) {
+            metadata.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn yaml(s: &str) -> HashMap<String, Value> {
+        serde_yaml_ng::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn apply_fills_missing_keys_only() {
+        let mut metadata = yaml("name: a\nlicense: Apache-2.0\n");
+        apply_defaults(
+            &mut metadata,
+            &yaml("license: MIT\ncompatibility: Claude Code\n"),
+        );
+        assert_eq!(metadata["license"], Value::from("Apache-2.0"));
+        assert_eq!(metadata["compatibility"], Value::from("Claude Code"));
+    }
+
+    #[test]
+    fn apply_never_sets_name_or_description() {
+        let mut metadata = HashMap::new();
+        apply_defaults(&mut metadata, &yaml("name: shared\ndescription: shared\n"));
+        assert!(metadata.is_empty(), "{metadata:?}");
+    }
+
+    #[test]
+    fn find_uses_nearest_ancestor() {
+        let root = tempdir().unwrap();
+        let group = root.path().join("group");
+        let skill = group.join("my-skill");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(root.path().join(DEFAULTS_FILE), "license: MIT\n").unwrap();
+        assert_eq!(
+            find_defaults(&skill).unwrap(),
+            fs::canonicalize(root.path()).unwrap().join(DEFAULTS_FILE)
+        );
+
+        fs::write(group.join(DEFAULTS_FILE), "license: BSD\n").unwrap();
+        assert_eq!(
+            find_defaults(&skill).unwrap(),
+            fs::canonicalize(&group).unwrap().join(DEFAULTS_FILE)
+        );
+    }
+
+    #[test]
+    fn find_stops_at_repository_and_workspace_root() {
+        for marker in [".git", crate::workspace::WORKSPACE_FILE] {
+            let outer = tempdir().unwrap();
+            let repo = outer.path().join("repo");
+            let skill = repo.join("skills/my-skill");
+            fs::create_dir_all(&skill).unwrap();
+            fs::write(outer.path().join(DEFAULTS_FILE), "license: MIT\n").unwrap();
+            if marker == ".git" {
+                fs::create_dir(repo.join(marker)).unwrap();
+            } else {
+                fs::write(repo.join(marker), "").unwrap();
+            }
+            assert_eq!(find_defaults(&skill), None, "{marker}");
+
+            fs::write(repo.join(DEFAULTS_FILE), "license: BSD\n").unwrap();
+            assert_eq!(
+                find_defaults(&skill).unwrap(),
+                fs::canonicalize(&repo).unwrap().join(DEFAULTS_FILE),
+                "{marker}"
+            );
+        }
+    }
+
+    #[test]
+    fn find_ignores_defaults_inside_skill_dir() {
+        let root = tempdir().unwrap();
+        let skill = root.path().join("my-skill");
+        fs::create_dir(&skill).unwrap();
+        fs::write(skill.join(DEFAULTS_FILE), "license: MIT\n").unwrap();
+        assert_eq!(find_defaults(&skill), None);
+    }
+
+    #[test]
+    fn load_accepts_empty_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(DEFAULTS_FILE);
+        fs::write(&path, "").unwrap();
+        assert!(load_defaults(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn load_rejects_non_mapping() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(DEFAULTS_FILE);
+        fs::write(&path, "- license\n").unwrap();
+        assert!(matches!(
+            load_defaults(&path),
+            Err(AigentError::Parse { .. })
+        ));
+    }
+}
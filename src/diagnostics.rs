@@ -5,7 +5,8 @@
 
 use std::fmt;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
 
 /// Severity of a diagnostic message.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -17,35 +18,80 @@ pub enum Severity {
     Warning,
     /// An informational suggestion for improvement.
     Info,
+    /// A soft style nudge, below info ("you might consider").
+    Hint,
+}
+
+impl Severity {
+    /// Numeric level for threshold filtering: `Hint` = -1, `Info` = 0,
+    /// `Warning` = 1, `Error` = 2.
+    ///
+    /// Serialized alongside the string form as `severity_level`, so JSON
+    /// consumers can filter with comparisons such as `severity_level >= 1`.
+    #[must_use]
+    pub fn level(self) -> i8 {
+        match self {
+            Self::Hint => -1,
+            Self::Info => 0,
+            Self::Warning => 1,
+            Self::Error => 2,
+        }
+    }
 }
 
 /// A structured diagnostic message from validation or linting.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Serializes with `severity` as a string and `severity_level` as its
+/// numeric [`Severity::level`]; `field` and `suggestion` are omitted when
+/// unset. `snippet` is never serialized: it is only shown in text output.
+#[derive(Debug, Clone)]
 pub struct Diagnostic {
     /// Severity level.
     pub severity: Severity,
-    /// Stable error code (e.g., `"E001"`, `"W001"`, `"I001"`).
-    pub code: &'static str,
+    /// Stable diagnostic code (serialized as e.g. `"E001"`, `"W001"`, `"I001"`).
+    pub code: DiagnosticCode,
     /// Human-readable message.
     pub message: String,
     /// Field that caused the diagnostic (e.g., `"name"`, `"description"`).
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub field: Option<&'static str>,
     /// Suggested fix (actionable text).
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub suggestion: Option<String>,
+    /// Snippet pointing into the offending value (see [`caret_snippet`]),
+    /// shown under the message in text output.
+    pub snippet: Option<String>,
+}
+
+impl Serialize for Diagnostic {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let len = 4 + usize::from(self.field.is_some()) + usize::from(self.suggestion.is_some());
+        let mut state = serializer.serialize_struct("Diagnostic", len)?;
+        state.serialize_field("severity", &self.severity)?;
+        state.serialize_field("severity_level", &self.severity.level())?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        if let Some(field) = self.field {
+            state.serialize_field("field", field)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            state.serialize_field("suggestion", suggestion)?;
+        }
+        state.end()
+    }
 }
 
 impl Diagnostic {
     /// Create a new diagnostic with the given severity, code, and message.
     #[must_use]
-    pub fn new(severity: Severity, code: &'static str, message: impl Into<String>) -> Self {
+    pub fn new(severity: Severity, code: DiagnosticCode, message: impl Into<String>) -> Self {
         Self {
             severity,
             code,
             message: message.into(),
             field: None,
             suggestion: None,
+            snippet: None,
         }
     }
 
@@ -63,6 +109,19 @@ impl Diagnostic {
         self
     }
 
+    /// Set the snippet shown under the message in text output.
+    #[must_use]
+    pub fn with_snippet(mut self, snippet: impl Into<String>) -> Self {
+        self.snippet = Some(snippet.into());
+        self
+    }
+
+    /// The diagnostic code as a string (e.g., `"E001"`).
+    #[must_use]
+    pub fn code_str(&self) -> &'static str {
+        self.code.as_str()
+    }
+
     /// Returns `true` if this diagnostic is an error.
     #[must_use]
     pub fn is_error(&self) -> bool {
@@ -80,218 +139,403 @@ impl Diagnostic {
     pub fn is_info(&self) -> bool {
         self.severity == Severity::Info
     }
+
+    /// Returns `true` if this diagnostic is a hint.
+    #[must_use]
+    pub fn is_hint(&self) -> bool {
+        self.severity == Severity::Hint
+    }
 }
 
 /// Display format preserves backward compatibility:
 /// - Errors: `"message"` (no prefix)
 /// - Warnings: `"warning: message"`
 /// - Info: `"info: message"`
+/// - Hint: `"hint: message"`
 impl fmt::Display for Diagnostic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.severity {
-            Severity::Error => write!(f, "{}", self.message),
-            Severity::Warning => write!(f, "warning: {}", self.message),
-            Severity::Info => write!(f, "info: {}", self.message),
+            Severity::Error => write!(f, "{}", self.message)?,
+            Severity::Warning => write!(f, "warning: {}", self.message)?,
+            Severity::Info => write!(f, "info: {}", self.message)?,
+            Severity::Hint => write!(f, "hint: {}", self.message)?,
+        }
+        for line in self.snippet.iter().flat_map(|s| s.lines()) {
+            write!(f, "\n    {line}")?;
         }
+        Ok(())
     }
 }
 
-// ── Error code constants ────────────────────────────────────────────────
-
-// Infrastructure errors (E000)
-
-/// Infrastructure error (file not found, IO error, parse failure).
-pub const E000: &str = "E000";
-
-// Name validation errors (E001–E009)
-
-/// Name must not be empty.
-pub const E001: &str = "E001";
-/// Name exceeds 64 characters.
-pub const E002: &str = "E002";
-/// Name contains invalid character.
-pub const E003: &str = "E003";
-/// Name starts with hyphen.
-pub const E004: &str = "E004";
-/// Name ends with hyphen.
-pub const E005: &str = "E005";
-/// Name contains consecutive hyphens.
-pub const E006: &str = "E006";
-/// Name contains reserved word.
-pub const E007: &str = "E007";
-/// Name contains XML/HTML tags (reserved; currently caught by E003 character validation).
-pub const E008: &str = "E008";
-/// Name does not match directory name.
-pub const E009: &str = "E009";
-
-// Description validation errors (E010–E012)
-
-/// Description must not be empty.
-pub const E010: &str = "E010";
-/// Description exceeds 1024 characters.
-pub const E011: &str = "E011";
-/// Description contains XML/HTML tags.
-pub const E012: &str = "E012";
-
-// Compatibility validation errors (E013)
-
-/// Compatibility exceeds 500 characters.
-pub const E013: &str = "E013";
-
-// Field type errors (E014–E016)
-
-/// `name` field is not a string.
-pub const E014: &str = "E014";
-/// `description` field is not a string.
-pub const E015: &str = "E015";
-/// `compatibility` field is not a string.
-pub const E016: &str = "E016";
-
-// Missing field errors (E017–E018)
-
-/// Missing required field `name`.
-pub const E017: &str = "E017";
-/// Missing required field `description`.
-pub const E018: &str = "E018";
-
-// Warning codes (W001–W002)
-
-/// Unexpected metadata field.
-pub const W001: &str = "W001";
-/// Body exceeds 500 lines.
-pub const W002: &str = "W002";
-
-// Structure validation codes (S001–S006)
-
-/// Referenced file does not exist.
-pub const S001: &str = "S001";
-/// Script missing execute permission (Unix only).
-pub const S002: &str = "S002";
-/// Reference depth exceeds 1 level.
-pub const S003: &str = "S003";
-/// Excessive directory nesting depth.
-pub const S004: &str = "S004";
-/// Symlink detected in skill directory.
-pub const S005: &str = "S005";
-/// Path traversal in reference link.
-pub const S006: &str = "S006";
-
-// Conflict detection codes (C001–C003)
-
-/// Name collision across skill directories.
-pub const C001: &str = "C001";
-/// Description overlap between skills.
-pub const C002: &str = "C002";
-/// Total token budget exceeded.
-pub const C003: &str = "C003";
-
-// ── Plugin manifest codes (P001–P010) ──────────────────────────────────
-
-/// JSON syntax error in plugin.json.
-pub const P001: &str = "P001";
-/// `name` field missing in plugin.json.
-pub const P002: &str = "P002";
-/// `name` not kebab-case or contains spaces.
-pub const P003: &str = "P003";
-/// `version` not semver format (x.y.z).
-pub const P004: &str = "P004";
-/// `description` empty or missing.
-pub const P005: &str = "P005";
-/// Custom path uses an absolute filesystem path (only relative paths are allowed).
-pub const P006: &str = "P006";
-/// Declared component path does not exist on filesystem.
-pub const P007: &str = "P007";
-/// Hardcoded credential/token detected in string values.
-pub const P008: &str = "P008";
-/// MCP server URL uses HTTP/WS instead of HTTPS/WSS.
-pub const P009: &str = "P009";
-/// Missing recommended field (author, homepage, license).
-pub const P010: &str = "P010";
-/// Path traversal (`..`) in plugin path override.
-pub const P011: &str = "P011";
-
-// ── Hook validation codes (H001–H011) ──────────────────────────────────
-
-/// Invalid JSON syntax in hooks file.
-pub const H001: &str = "H001";
-/// Invalid hooks structure (not an object of event arrays).
-pub const H002: &str = "H002";
-/// Unknown event name.
-pub const H003: &str = "H003";
-/// Hook entry missing `hooks` array.
-pub const H004: &str = "H004";
-/// Hook missing `type` field.
-pub const H005: &str = "H005";
-/// Unknown hook type (not `command` or `prompt`).
-pub const H006: &str = "H006";
-/// Command hook missing `command` field.
-pub const H007: &str = "H007";
-/// Prompt hook missing `prompt` field.
-pub const H008: &str = "H008";
-/// Timeout outside recommended range (5–600 seconds).
-pub const H009: &str = "H009";
-/// Hardcoded absolute path in hook command.
-pub const H010: &str = "H010";
-/// Prompt hook on suboptimal event.
-pub const H011: &str = "H011";
-
-// ── Agent file validation codes (A001–A010) ────────────────────────────
-
-/// Agent frontmatter missing (no `---` delimiters).
-pub const A001: &str = "A001";
-/// Required agent field missing (name, description, model, color).
-pub const A002: &str = "A002";
-/// Agent name not kebab-case.
-pub const A003: &str = "A003";
-/// Agent name is generic.
-pub const A004: &str = "A004";
-/// Agent name length outside 3–50 chars.
-pub const A005: &str = "A005";
-/// Agent description length outside 10–5000 chars.
-pub const A006: &str = "A006";
-/// Agent model not one of: inherit, sonnet, opus, haiku.
-pub const A007: &str = "A007";
-/// Agent color not one of: blue, cyan, green, yellow, magenta, red.
-pub const A008: &str = "A008";
-/// Agent system prompt (body) missing or too short (<20 chars).
-pub const A009: &str = "A009";
-/// Agent system prompt too long (>10k chars).
-pub const A010: &str = "A010";
-
-// ── Command file validation codes (K001–K007) ──────────────────────────
-
-/// Command frontmatter syntax error (if `---` present but invalid YAML).
-pub const K001: &str = "K001";
-/// Command description exceeds 60 chars.
-pub const K002: &str = "K002";
-/// Command model not one of: sonnet, opus, haiku.
-pub const K003: &str = "K003";
-/// Command description does not start with a verb.
-pub const K004: &str = "K004";
-/// Command body is empty.
-pub const K005: &str = "K005";
-/// Command allowed-tools invalid format.
-pub const K006: &str = "K006";
-/// Missing command description (recommended for discoverability).
-pub const K007: &str = "K007";
-
-// ── Cross-component consistency codes (X001–X006) ──────────────────────
-
-/// Component directory is empty (no valid files found).
-pub const X001: &str = "X001";
-/// Command hook references script that doesn't exist.
-pub const X002: &str = "X002";
-/// Orphaned file in component directory (not referenced).
-pub const X003: &str = "X003";
-/// Naming inconsistency across components.
-pub const X004: &str = "X004";
-/// Total token budget across all skills exceeds threshold.
-pub const X005: &str = "X005";
-/// Duplicate component names across types.
-pub const X006: &str = "X006";
+// ── Diagnostic codes ────────────────────────────────────────────────────
+
+/// Defines [`DiagnosticCode`] and its lookup tables from a single list so
+/// the variants, [`DiagnosticCode::all`], and the string forms cannot drift.
+macro_rules! diagnostic_codes {
+    ($($(#[$doc:meta])* $code:ident,)*) => {
+        /// Stable machine-readable diagnostic code.
+        ///
+        /// Displays and serializes as its string form (e.g., `"E001"`). Every
+        /// variant is also re-exported at module level, so `diagnostics::E001`
+        /// names `DiagnosticCode::E001`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+        pub enum DiagnosticCode {
+            $($(#[$doc])* $code,)*
+        }
+
+        impl DiagnosticCode {
+            /// Every diagnostic code, in declaration order.
+            #[must_use]
+            pub const fn all() -> &'static [DiagnosticCode] {
+                &[$(DiagnosticCode::$code,)*]
+            }
+
+            /// The stable string form of this code (e.g., `"E001"`).
+            #[must_use]
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(DiagnosticCode::$code => stringify!($code),)*
+                }
+            }
+        }
+    };
+}
+
+diagnostic_codes! {
+
+    // Infrastructure errors (E000)
+
+    /// Infrastructure error (file not found, IO error, parse failure).
+    E000,
+
+    // Name validation errors (E001–E009)
+
+    /// Name must not be empty.
+    E001,
+    /// Name exceeds 64 characters.
+    E002,
+    /// Name contains invalid character.
+    E003,
+    /// Name starts with hyphen.
+    E004,
+    /// Name ends with hyphen.
+    E005,
+    /// Name contains consecutive hyphens.
+    E006,
+    /// Name contains reserved word.
+    E007,
+    /// Name contains XML/HTML tags (reserved; currently caught by E003 character validation).
+    E008,
+    /// Name does not match directory name.
+    E009,
+
+    // Description validation errors (E010–E012)
+
+    /// Description must not be empty.
+    E010,
+    /// Description exceeds 1024 characters.
+    E011,
+    /// Description contains XML/HTML tags.
+    E012,
+
+    // Compatibility validation errors (E013)
+
+    /// Compatibility exceeds 500 characters.
+    E013,
+
+    // Field type errors (E014–E016)
+
+    /// `name` field is not a string.
+    E014,
+    /// `description` field is not a string.
+    E015,
+    /// `compatibility` field is not a string.
+    E016,
+
+    // Missing field errors (E017–E018)
+
+    /// Missing required field `name`.
+    E017,
+    /// Missing required field `description`.
+    E018,
+
+    // Metadata block errors (E019–E020)
+
+    /// `metadata` is not a mapping.
+    E019,
+    /// `metadata` has a key that is not a string.
+    E020,
+
+    // Skill file errors (E021)
+
+    /// Both `SKILL.md` and `skill.md` exist in the skill directory.
+    E021,
+
+    // Frontmatter syntax errors (E022)
+
+    /// Frontmatter line indented with a tab.
+    E022,
+
+    // Directory name case errors (E023)
+
+    /// Name differs from the directory name only in letter case.
+    E023,
+
+    // Warning codes (W001–W014)
+
+    /// Unexpected metadata field.
+    W001,
+    /// Body exceeds 500 lines.
+    W002,
+    /// `compatibility` names only non-Claude-Code targets but Claude Code extension fields are used.
+    W003,
+    /// Body contains a TODO, FIXME, or placeholder marker.
+    W004,
+    /// `metadata.superseded-by` names a skill that is not in the validated set.
+    W005,
+    /// `SKILL.md` uses CRLF or mixed line endings.
+    W006,
+    /// `_defaults.yml` could not be read or parsed and was ignored.
+    W007,
+    /// A `metadata` value is nested more than 2 levels deep.
+    W008,
+    /// Serialized `metadata` exceeds the size cap.
+    W009,
+    /// The skill file is named `skill.md` instead of `SKILL.md`.
+    W010,
+    /// Body exceeds the estimated token budget.
+    W011,
+    /// `metadata.priority` is not an integer.
+    W012,
+    /// `SKILL.md` is close to the file size limit.
+    W013,
+    /// Description only restates the skill name.
+    W014,
+    /// `metadata.authors` is not a list of names, or omits `metadata.author`.
+    W015,
+
+    // Structure validation codes (S001–S014)
+
+    /// Referenced file does not exist.
+    S001,
+    /// Script missing execute permission (Unix only).
+    S002,
+    /// Reference depth exceeds 1 level.
+    S003,
+    /// Excessive directory nesting depth.
+    S004,
+    /// Symlink detected in skill directory.
+    S005,
+    /// Path traversal in reference link.
+    S006,
+    /// Skill present on disk but missing from `INDEX.md`.
+    S007,
+    /// `INDEX.md` entry points to a nonexistent skill.
+    S008,
+    /// `INDEX.md` not found at the index root.
+    S009,
+    /// The same file is referenced via differently-spelled paths.
+    S010,
+    /// `.json` file under `examples/` does not parse as JSON.
+    S011,
+    /// `.yml`/`.yaml` file under `examples/` does not parse as YAML.
+    S012,
+    /// `.sh` file under `examples/` has no shebang line.
+    S013,
+    /// File under `examples/` is not referenced from SKILL.md or a linked document.
+    S014,
+
+    // Conflict detection codes (C001–C005)
+
+    /// Name collision across skill directories.
+    C001,
+    /// Description overlap between skills.
+    C002,
+    /// Total token budget exceeded.
+    C003,
+    /// Skills strongly activate on the same sample queries.
+    C004,
+    /// Near-duplicate names between skills.
+    C005,
+
+    // ── Linter info codes (I001–I005) ──────────────────────────────────
+
+    /// Description uses first or second person.
+    I001,
+    /// Description lacks a trigger phrase ("Use when…").
+    I002,
+    /// Name does not use gerund form.
+    I003,
+    /// Name is overly generic.
+    I004,
+    /// Description is overly vague.
+    I005,
+
+    // ── Validator info codes (I006) ────────────────────────────────────
+
+    /// `compatibility` names Claude Code but the validation target is `standard`.
+    I006,
+
+    // ── Linter info codes (I007–I010) ──────────────────────────────────
+
+    /// Deprecated skill has no `metadata.removal-date`.
+    I007,
+    /// Description is phrased as a question.
+    I008,
+    /// Body is empty or has only headings.
+    I009,
+    /// Description repeats the body's opening line.
+    I010,
+
+    // ── Placeholder info codes (I011) ──────────────────────────────────
+
+    /// `${NAME}` placeholders found under an allow-placeholders policy.
+    I011,
+
+    // ── Description grammar codes (I012–I015) ──────────────────────────
+
+    /// Description contains consecutive spaces.
+    I012,
+    /// Description has leading or trailing whitespace.
+    I013,
+    /// Description has unbalanced brackets or quotes.
+    I014,
+    /// Description ends with a comma or a conjunction.
+    I015,
+
+    // ── Body metrics codes (I016) ──────────────────────────────────────
+
+    /// Body readability metrics (`check --metrics`).
+    I016,
+
+    // ── Body terminology codes (I017) ──────────────────────────────────
+
+    /// Body never mentions a key term from the skill name.
+    I017,
+
+    // ── Plugin manifest codes (P001–P013) ──────────────────────────────────
+
+    /// JSON syntax error in plugin.json.
+    P001,
+    /// `name` field missing in plugin.json.
+    P002,
+    /// `name` not kebab-case or contains spaces.
+    P003,
+    /// `version` not semver format (x.y.z).
+    P004,
+    /// `description` empty or missing.
+    P005,
+    /// Custom path uses an absolute filesystem path (only relative paths are allowed).
+    P006,
+    /// Declared component path does not exist on filesystem.
+    P007,
+    /// Hardcoded credential/token detected in string values.
+    P008,
+    /// MCP server URL uses HTTP/WS instead of HTTPS/WSS.
+    P009,
+    /// Missing recommended field (author, homepage, license).
+    P010,
+    /// Path traversal (`..`) in plugin path override.
+    P011,
+    /// `minClaudeCodeVersion` is not a valid version.
+    P012,
+    /// Plugin name collides with a known plugin in the registry.
+    P013,
+
+    // ── Hook validation codes (H001–H011) ──────────────────────────────────
+
+    /// Invalid JSON syntax in hooks file.
+    H001,
+    /// Invalid hooks structure (not an object of event arrays).
+    H002,
+    /// Unknown event name.
+    H003,
+    /// Hook entry missing `hooks` array.
+    H004,
+    /// Hook missing `type` field.
+    H005,
+    /// Unknown hook type (not `command` or `prompt`).
+    H006,
+    /// Command hook missing `command` field.
+    H007,
+    /// Prompt hook missing `prompt` field.
+    H008,
+    /// Timeout outside recommended range (5–600 seconds).
+    H009,
+    /// Hardcoded absolute path in hook command.
+    H010,
+    /// Prompt hook on suboptimal event.
+    H011,
+
+    // ── Agent file validation codes (A001–A010) ────────────────────────────
+
+    /// Agent frontmatter missing (no `---` delimiters).
+    A001,
+    /// Required agent field missing (name, description, model, color).
+    A002,
+    /// Agent name not kebab-case.
+    A003,
+    /// Agent name is generic.
+    A004,
+    /// Agent name length outside 3–50 chars.
+    A005,
+    /// Agent description length outside 10–5000 chars.
+    A006,
+    /// Agent model not one of: inherit, sonnet, opus, haiku.
+    A007,
+    /// Agent color not one of: blue, cyan, green, yellow, magenta, red.
+    A008,
+    /// Agent system prompt (body) missing or too short (<20 chars).
+    A009,
+    /// Agent system prompt too long (>10k chars).
+    A010,
+
+    // ── Command file validation codes (K001–K008) ──────────────────────────
+
+    /// Command frontmatter syntax error (if `---` present but invalid YAML).
+    K001,
+    /// Command description exceeds 60 chars.
+    K002,
+    /// Command model not one of: sonnet, opus, haiku.
+    K003,
+    /// Command description does not start with a verb.
+    K004,
+    /// Command body is empty.
+    K005,
+    /// Command allowed-tools invalid format.
+    K006,
+    /// Missing command description (recommended for discoverability).
+    K007,
+    /// Unknown command frontmatter field for the selected plugin schema.
+    K008,
+
+    // ── Cross-component consistency codes (X001–X006) ──────────────────────
+
+    /// Component directory is empty (no valid files found).
+    X001,
+    /// Command hook references script that doesn't exist.
+    X002,
+    /// Orphaned file in component directory (not referenced).
+    X003,
+    /// Naming inconsistency across components.
+    X004,
+    /// Total token budget across all skills exceeds threshold.
+    X005,
+    /// Duplicate component names across types.
+    X006,
+}
+
+pub use DiagnosticCode::*;
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Validation target profile for controlling which fields are considered known.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum ValidationTarget {
     /// Standard Anthropic specification fields only.
     #[default]
@@ -302,6 +546,99 @@ pub enum ValidationTarget {
     Permissive,
 }
 
+// ── Message helpers ─────────────────────────────────────────────────────
+
+/// Characters of context shown on each side of a highlighted character.
+pub const SNIPPET_CONTEXT: usize = 8;
+
+/// Format a length-limit message, e.g. `"description exceeds 1024 characters (1187 of 1024)"`.
+///
+/// `count` and `limit` are in characters (Unicode scalar values), matching
+/// how the validator measures field lengths.
+#[must_use]
+pub fn length_exceeded(subject: &str, count: usize, limit: usize) -> String {
+    format!("{subject} exceeds {limit} characters ({count} of {limit})")
+}
+
+/// Escape a character for display in a diagnostic message.
+///
+/// Common control characters use their familiar escapes (`\n`, `\r`, `\t`);
+/// other control characters use `\u{..}`. Everything else is returned as-is.
+#[must_use]
+pub fn escape_char(c: char) -> String {
+    match c {
+        '\n' => "\\n".to_string(),
+        '\r' => "\\r".to_string(),
+        '\t' => "\\t".to_string(),
+        c if c.is_control() => format!("\\u{{{:x}}}", u32::from(c)),
+        c => c.to_string(),
+    }
+}
+
+/// Build a caret-style snippet highlighting the character at `char_index`.
+///
+/// Returns two lines: up to `context` characters on each side of the target
+/// (with `…` marking elided text and control characters escaped), and a
+/// caret under the target. Indexing is by character, never by byte, so
+/// multi-byte input cannot cause a slicing panic; the caret column is the
+/// display width of the text before the target, so it stays aligned after
+/// wide (CJK, emoji) and zero-width (combining) characters. An out-of-range
+/// index places the caret just past the end of the value.
+#[must_use]
+pub fn caret_snippet(value: &str, char_index: usize, context: usize) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let index = char_index.min(chars.len());
+    let start = index.saturating_sub(context);
+    let end = (index + context + 1).min(chars.len());
+
+    let mut line = String::new();
+    let mut caret_col = 0;
+    if start > 0 {
+        line.push('…');
+    }
+    for (i, &c) in chars[start..end].iter().enumerate() {
+        if start + i == index {
+            caret_col = line.width();
+        }
+        line.push_str(&escape_char(c));
+    }
+    if index == chars.len() {
+        caret_col = line.width();
+    }
+    if end < chars.len() {
+        line.push('…');
+    }
+
+    format!("{line}\n{}^", " ".repeat(caret_col))
+}
+
+/// Count how often each diagnostic code occurs across a set of results.
+///
+/// Returns `(code, count, skills)` per code: `count` is the total number of
+/// diagnostics with that code and `skills` the number of results with at
+/// least one. Sorted by count, then skills, both descending, then by code.
+#[must_use]
+pub fn code_frequency<P>(results: &[(P, Vec<Diagnostic>)]) -> Vec<(DiagnosticCode, usize, usize)> {
+    let mut counts: std::collections::HashMap<DiagnosticCode, (usize, usize)> =
+        std::collections::HashMap::new();
+    for (_, diags) in results {
+        let mut seen = std::collections::HashSet::new();
+        for d in diags {
+            let entry = counts.entry(d.code).or_default();
+            entry.0 += 1;
+            if seen.insert(d.code) {
+                entry.1 += 1;
+            }
+        }
+    }
+    let mut frequency: Vec<_> = counts
+        .into_iter()
+        .map(|(code, (count, skills))| (code, count, skills))
+        .collect();
+    frequency.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)).then(a.0.cmp(&b.0)));
+    frequency
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,7 +657,7 @@ mod tests {
 
     #[test]
     fn info_display_with_prefix() {
-        let d = Diagnostic::new(Severity::Info, "I001", "description uses first person");
+        let d = Diagnostic::new(Severity::Info, I001, "description uses first person");
         assert_eq!(d.to_string(), "info: description uses first person");
     }
 
@@ -342,10 +679,20 @@ mod tests {
 
     #[test]
     fn is_info_true_for_info() {
-        let d = Diagnostic::new(Severity::Info, "I001", "test");
+        let d = Diagnostic::new(Severity::Info, I001, "test");
         assert!(!d.is_error());
         assert!(!d.is_warning());
         assert!(d.is_info());
+        assert!(!d.is_hint());
+    }
+
+    #[test]
+    fn is_hint_true_for_hints() {
+        let d = Diagnostic::new(Severity::Hint, I001, "test");
+        assert!(!d.is_error());
+        assert!(!d.is_warning());
+        assert!(!d.is_info());
+        assert!(d.is_hint());
     }
 
     #[test]
@@ -399,6 +746,43 @@ mod tests {
         assert_eq!(json["suggestion"], "Remove the field");
     }
 
+    #[test]
+    fn serialize_json_severity_levels() {
+        for (severity, expected) in [
+            (Severity::Error, "error"),
+            (Severity::Warning, "warning"),
+            (Severity::Info, "info"),
+            (Severity::Hint, "hint"),
+        ] {
+            let json = serde_json::to_value(Diagnostic::new(severity, I001, "test")).unwrap();
+            assert_eq!(json["severity"], expected);
+            assert_eq!(json["severity_level"], severity.level());
+        }
+    }
+
+    #[test]
+    fn serialized_error_carries_numeric_severity_level() {
+        let d = Diagnostic::new(Severity::Error, E001, "name must not be empty");
+        let text = serde_json::to_string(&d).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["severity_level"], 2);
+    }
+
+    #[test]
+    fn severity_levels_are_ordered() {
+        assert!(Severity::Hint.level() < Severity::Info.level());
+        assert_eq!(Severity::Info.level(), 0);
+        assert_eq!(Severity::Warning.level(), 1);
+        assert_eq!(Severity::Error.level(), 2);
+    }
+
+    #[test]
+    fn display_hint_has_prefix() {
+        let d = Diagnostic::new(Severity::Hint, I001, "consider a shorter name");
+        assert_eq!(d.to_string(), "hint: consider a shorter name");
+    }
+
     #[test]
     fn serialize_json_omits_none_fields() {
         let d = Diagnostic::new(Severity::Error, E001, "test");
@@ -407,15 +791,39 @@ mod tests {
         assert!(json.get("suggestion").is_none());
     }
 
+    #[test]
+    fn code_frequency_counts_occurrences_and_skills() {
+        let results = vec![
+            (
+                "a",
+                vec![
+                    Diagnostic::new(Severity::Warning, W001, "x"),
+                    Diagnostic::new(Severity::Warning, W001, "y"),
+                    Diagnostic::new(Severity::Info, I002, "z"),
+                ],
+            ),
+            ("b", vec![Diagnostic::new(Severity::Info, I002, "z")]),
+            ("c", vec![Diagnostic::new(Severity::Error, E001, "e")]),
+            ("d", vec![]),
+        ];
+        assert_eq!(
+            code_frequency(&results),
+            vec![(I002, 2, 2), (W001, 2, 1), (E001, 1, 1)]
+        );
+        assert!(code_frequency::<&str>(&[]).is_empty());
+    }
+
     #[test]
     fn error_codes_are_unique() {
         let codes = [
             E000, E001, E002, E003, E004, E005, E006, E007, E008, E009, E010, E011, E012, E013,
-            E014, E015, E016, E017, E018, W001, W002, S001, S002, S003, S004, S005, S006, C001,
-            C002, C003, P001, P002, P003, P004, P005, P006, P007, P008, P009, P010, P011, H001,
-            H002, H003, H004, H005, H006, H007, H008, H009, H010, H011, A001, A002, A003, A004,
-            A005, A006, A007, A008, A009, A010, K001, K002, K003, K004, K005, K006, K007, X001,
-            X002, X003, X004, X005, X006,
+            E014, E015, E016, E017, E018, E019, E020, E021, E022, E023, W001, W002, W003, W004,
+            W005, W006, W007, W008, W009, W010, W011, W012, W013, W014, W015, S001, S002, S003,
+            S004, S005, S006, S007, S008, S009, S010, S011, S012, S013, S014, C001, C002, C003,
+            C004, C005, I006, I011, P001, P002, P003, P004, P005, P006, P007, P008, P009, P010,
+            P011, P012, P013, H001, H002, H003, H004, H005, H006, H007, H008, H009, H010, H011,
+            A001, A002, A003, A004, A005, A006, A007, A008, A009, A010, K001, K002, K003, K004,
+            K005, K006, K007, K008, X001, X002, X003, X004, X005, X006,
         ];
         let mut seen = std::collections::HashSet::new();
         for code in &codes {
@@ -423,9 +831,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn diagnostic_codes_stringify_to_unique_stable_values() {
+        let expected = [
+            "E000", "E001", "E002", "E003", "E004", "E005", "E006", "E007", "E008", "E009", "E010",
+            "E011", "E012", "E013", "E014", "E015", "E016", "E017", "E018", "E019", "E020", "E021",
+            "E022", "E023", "W001", "W002", "W003", "W004", "W005", "W006", "W007", "W008", "W009",
+            "W010", "W011", "W012", "W013", "W014", "W015", "S001", "S002", "S003", "S004", "S005",
+            "S006", "S007", "S008", "S009", "S010", "S011", "S012", "S013", "S014", "C001", "C002",
+            "C003", "C004", "C005", "I001", "I002", "I003", "I004", "I005", "I006", "I007", "I008",
+            "I009", "I010", "I011", "I012", "I013", "I014", "I015", "I016", "I017", "P001", "P002",
+            "P003", "P004", "P005", "P006", "P007", "P008", "P009", "P010", "P011", "P012", "P013",
+            "H001", "H002", "H003", "H004", "H005", "H006", "H007", "H008", "H009", "H010", "H011",
+            "A001", "A002", "A003", "A004", "A005", "A006", "A007", "A008", "A009", "A010", "K001",
+            "K002", "K003", "K004", "K005", "K006", "K007", "K008", "X001", "X002", "X003", "X004",
+            "X005", "X006",
+        ];
+        let all = DiagnosticCode::all();
+        assert_eq!(all.len(), expected.len());
+        let mut seen = std::collections::HashSet::new();
+        for (code, want) in all.iter().zip(expected) {
+            assert_eq!(code.to_string(), want);
+            assert_eq!(code.as_str(), want);
+            assert!(seen.insert(code.to_string()), "duplicate code: {code}");
+        }
+    }
+
+    #[test]
+    fn diagnostic_code_serializes_as_string() {
+        let d = Diagnostic::new(Severity::Error, E001, "name must not be empty");
+        assert_eq!(d.code_str(), "E001");
+        let json = serde_json::to_value(&d).unwrap();
+        assert_eq!(json["code"], "E001");
+    }
+
     #[test]
     fn validation_target_default_is_standard() {
         let target = ValidationTarget::default();
         assert_eq!(target, ValidationTarget::Standard);
     }
+
+    // ── Message helpers ─────────────────────────────────────────────
+
+    #[test]
+    fn length_exceeded_includes_count_and_limit() {
+        assert_eq!(
+            length_exceeded("description", 1187, 1024),
+            "description exceeds 1024 characters (1187 of 1024)"
+        );
+    }
+
+    #[test]
+    fn escape_char_escapes_controls_only() {
+        assert_eq!(escape_char('\n'), "\\n");
+        assert_eq!(escape_char('\t'), "\\t");
+        assert_eq!(escape_char('\u{7}'), "\\u{7}");
+        assert_eq!(escape_char('_'), "_");
+        assert_eq!(escape_char('é'), "é");
+    }
+
+    #[test]
+    fn snippet_is_shown_in_text_but_not_serialized() {
+        let d = Diagnostic::new(Severity::Error, E003, "bad")
+            .with_field("name")
+            .with_snippet("my_skill\n  ^");
+        assert_eq!(d.to_string(), "bad\n    my_skill\n      ^");
+        let json = serde_json::to_value(&d).unwrap();
+        assert_eq!(json["message"], "bad");
+        assert!(json.get("snippet").is_none());
+    }
+
+    #[test]
+    fn caret_snippet_short_value() {
+        assert_eq!(
+            caret_snippet("my_skill", 2, SNIPPET_CONTEXT),
+            "my_skill\n  ^"
+        );
+    }
+
+    #[test]
+    fn caret_snippet_elides_long_context() {
+        let snippet = caret_snippet("abcdefghij_klmnopqrst", 10, 3);
+        assert_eq!(snippet, "…hij_klm…\n    ^");
+    }
+
+    #[test]
+    fn caret_snippet_multibyte_boundaries() {
+        // Every character is multi-byte; slicing by byte offset would panic.
+        let value = "日本語_テスト";
+        assert_eq!(caret_snippet(value, 3, 2), "…本語_テス…\n     ^");
+        for i in 0..=value.chars().count() + 2 {
+            let _ = caret_snippet(value, i, 1);
+        }
+    }
+
+    #[test]
+    fn caret_snippet_mixed_width_emoji_and_combining() {
+        let value = "a👍e\u{301}_z";
+        let snippet = caret_snippet(value, 4, SNIPPET_CONTEXT);
+        assert_eq!(snippet, "a👍e\u{301}_z\n    ^");
+    }
+
+    #[test]
+    fn caret_snippet_escapes_control_characters() {
+        let snippet = caret_snippet("ab\ncd", 2, SNIPPET_CONTEXT);
+        assert_eq!(snippet, "ab\\ncd\n  ^");
+    }
+
+    #[test]
+    fn caret_snippet_out_of_range_index_points_past_end() {
+        assert_eq!(caret_snippet("abc", 10, SNIPPET_CONTEXT), "abc\n   ^");
+        assert_eq!(caret_snippet("", 0, SNIPPET_CONTEXT), "\n^");
+    }
 }
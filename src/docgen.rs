@@ -0,0 +1,433 @@
+//! Skill catalog generation for `aigent doc`.
+//!
+//! Collected skills are first turned into [`CatalogEntry`] values, one per
+//! skill, sorted by name. The markdown and JSON renderers both work from
+//! that list, so the two output formats always describe the same catalog.
+//! [`format_index`] renders a smaller JSON index from the same list for
+//! tools that only need to look skills up.
+//!
+//! [`format_index`]: crate::docgen::format_index
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::parser::{read_body, read_properties};
+use crate::prompt::{estimate_tokens, Provenance, SkillEntry};
+use crate::readability::{body_metrics, BodyMetrics};
+
+/// One skill in a generated catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    /// Skill name from frontmatter.
+    pub name: String,
+    /// Skill description from frontmatter.
+    pub description: String,
+    /// Path to the `SKILL.md` file.
+    pub location: String,
+    /// Compatibility string, if set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compatibility: Option<String>,
+    /// License identifier, if set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Whether the skill is marked `metadata.deprecated: true`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Replacement skill from `metadata.superseded-by`, if set.
+    #[serde(rename = "superseded-by", skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    /// Tags from `metadata.tags`, if any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Scalar values from the frontmatter `metadata:` block, keyed by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, serde_json::Value>,
+    /// Body size, if the body could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<BodyStats>,
+    /// Body readability metrics, if the body could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<BodyMetrics>,
+    /// `SKILL.md` hash and modification time, if collected with provenance.
+    #[serde(flatten)]
+    pub provenance: Option<Provenance>,
+}
+
+/// Size of a skill's markdown body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyStats {
+    /// Number of lines.
+    pub lines: usize,
+    /// Number of whitespace-separated words.
+    pub words: usize,
+    /// Estimated token count (see [`estimate_tokens`]).
+    pub tokens: usize,
+}
+
+/// Build catalog entries for collected skills, sorted by name.
+///
+/// Optional fields come from re-reading each skill's `SKILL.md`. If that
+/// fails, the entry keeps only the name, description, and location.
+#[must_use]
+pub fn catalog_entries(entries: &[SkillEntry]) -> Vec<CatalogEntry> {
+    let mut catalog: Vec<CatalogEntry> = entries.iter().map(catalog_entry).collect();
+    catalog.sort_by(|a, b| a.name.cmp(&b.name));
+    catalog
+}
+
+/// Build the catalog entry for one collected skill.
+fn catalog_entry(entry: &SkillEntry) -> CatalogEntry {
+    // entry.location is a file path to SKILL.md; the readers expect the parent directory.
+    let loc_path = Path::new(&entry.location);
+    let skill_dir = loc_path.parent().unwrap_or(loc_path);
+
+    let mut item = CatalogEntry {
+        name: entry.name.clone(),
+        description: entry.description.clone(),
+        location: entry.location.clone(),
+        compatibility: None,
+        license: None,
+        deprecated: false,
+        superseded_by: None,
+        tags: Vec::new(),
+        metadata: BTreeMap::new(),
+        body: None,
+        metrics: None,
+        provenance: entry.provenance.clone(),
+    };
+
+    if let Ok(props) = read_properties(skill_dir) {
+        item.deprecated = props.is_deprecated();
+        item.superseded_by = props.superseded_by().map(String::from);
+        item.tags = props.tags();
+        item.metadata = scalar_metadata(props.metadata.as_ref());
+        item.compatibility = props.compatibility;
+        item.license = props.license;
+    }
+    if let Ok(body) = read_body(skill_dir) {
+        item.body = Some(BodyStats {
+            lines: body.lines().count(),
+            words: body.split_whitespace().count(),
+            tokens: estimate_tokens(&body),
+        });
+        item.metrics = Some(body_metrics(&body));
+    }
+    item
+}
+
+/// Scalar entries of the nested `metadata:` mapping, converted to JSON.
+fn scalar_metadata(
+    metadata: Option<&std::collections::HashMap<String, serde_yaml_ng::Value>>,
+) -> BTreeMap<String, serde_json::Value> {
+    let Some(mapping) = metadata
+        .and_then(|m| m.get("metadata"))
+        .and_then(serde_yaml_ng::Value::as_mapping)
+    else {
+        return BTreeMap::new();
+    };
+    mapping
+        .iter()
+        .filter_map(|(key, value)| {
+            let key = key.as_str()?;
+            let value = match value {
+                serde_yaml_ng::Value::Bool(b) => serde_json::Value::from(*b),
+                serde_yaml_ng::Value::Number(_) | serde_yaml_ng::Value::String(_) => {
+                    serde_json::to_value(value).ok()?
+                }
+                _ => return None,
+            };
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+/// Render a catalog as markdown documentation.
+///
+/// Active skills are listed first. Deprecated skills follow under a separate
+/// "Deprecated" heading, linking to their successor when `superseded-by` is
+/// set. Missing fields are omitted.
+#[must_use]
+pub fn format_markdown(catalog: &[CatalogEntry]) -> String {
+    let mut out = String::from("# Skill Catalog\n");
+
+    let (deprecated, active): (Vec<_>, Vec<_>) = catalog.iter().partition(|e| e.deprecated);
+    for entry in &active {
+        push_markdown_entry(&mut out, "##", entry);
+    }
+    if !deprecated.is_empty() {
+        out.push_str("\n## Deprecated\n");
+        for entry in &deprecated {
+            push_markdown_entry(&mut out, "###", entry);
+        }
+    }
+
+    out
+}
+
+/// Render a catalog as a compact markdown list.
+///
+/// One bullet per skill with only the first sentence of its description
+/// (see [`SkillProperties::description_first_sentence`]). Deprecated skills
+/// are marked but not separated.
+///
+/// [`SkillProperties::description_first_sentence`]: crate::SkillProperties::description_first_sentence
+#[must_use]
+pub fn format_markdown_compact(catalog: &[CatalogEntry]) -> String {
+    let mut out = String::from("# Skill Catalog\n\n");
+    for entry in catalog {
+        let lead = crate::models::first_sentence(&entry.description)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let marker = if entry.deprecated {
+            " (deprecated)"
+        } else {
+            ""
+        };
+        let _ = write!(out, "- **{}**{marker} — {lead}", entry.name);
+        if let Some(p) = &entry.provenance {
+            let _ = write!(out, " <!-- {} -->", p.label());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Append one catalog entry under a heading of the given level.
+fn push_markdown_entry(out: &mut String, heading: &str, entry: &CatalogEntry) {
+    let _ = writeln!(out, "\n{heading} {}", entry.name);
+    let _ = writeln!(out, "> {}", entry.description);
+
+    if let Some(successor) = &entry.superseded_by {
+        let _ = writeln!(out, "\n**Superseded by**: [{successor}](#{successor})");
+    }
+    if let Some(compat) = &entry.compatibility {
+        let _ = writeln!(out, "\n**Compatibility**: {compat}");
+    }
+    if let Some(license) = &entry.license {
+        let _ = writeln!(out, "**License**: {license}");
+    }
+
+    let _ = writeln!(out, "**Location**: `{}`", entry.location);
+    if let Some(p) = &entry.provenance {
+        let _ = writeln!(out, "<!-- {} -->", p.label());
+    }
+    out.push_str("\n---\n");
+}
+
+/// Render a catalog as a pretty-printed JSON array.
+#[must_use]
+pub fn format_json(catalog: &[CatalogEntry]) -> String {
+    serde_json::to_string_pretty(catalog).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// One skill in the JSON index written by `doc --index`.
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    name: &'a str,
+    description: &'a str,
+    location: &'a str,
+    tags: &'a [String],
+}
+
+/// Render a catalog as a pretty-printed JSON index.
+///
+/// Each item carries only the name, description, location, and tags, in
+/// catalog order. `tags` is always present, empty when the skill has none.
+#[must_use]
+pub fn format_index(catalog: &[CatalogEntry]) -> String {
+    let index: Vec<IndexEntry<'_>> = catalog
+        .iter()
+        .map(|entry| IndexEntry {
+            name: &entry.name,
+            description: &entry.description,
+            location: &entry.location,
+            tags: &entry.tags,
+        })
+        .collect();
+    serde_json::to_string_pretty(&index).unwrap_or_else(|_| "[]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// Write a skill and return its collected entry.
+    fn collect(parent: &Path, name: &str, content: &str) -> SkillEntry {
+        let dir = parent.join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+        let (entries, _) = crate::prompt::collect_skills_verbose_with(
+            &[dir.as_path()],
+            &crate::prompt::CollectOptions {
+                include_deprecated: true,
+                ..Default::default()
+            },
+        );
+        entries.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn compact_markdown_lists_first_sentences() {
+        let parent = tempdir().unwrap();
+        let entry = collect(
+            parent.path(),
+            "pdf-tool",
+            "---\nname: pdf-tool\ndescription: >-\n  Extracts text from PDFs, e.g. scanned\n  forms. Use when reading PDFs.\n---\nBody.\n",
+        );
+        let md = format_markdown_compact(&catalog_entries(&[entry]));
+        assert_eq!(
+            md,
+            "# Skill Catalog\n\n- **pdf-tool** — Extracts text from PDFs, e.g. scanned forms.\n"
+        );
+    }
+
+    #[test]
+    fn json_omits_absent_optional_fields() {
+        let parent = tempdir().unwrap();
+        let entry = collect(
+            parent.path(),
+            "plain",
+            "---\nname: plain\ndescription: A plain skill\n---\nOne two three.\n",
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&catalog_entries(&[entry]))).unwrap();
+        let item = json[0].as_object().unwrap();
+        assert_eq!(item["name"], "plain");
+        assert_eq!(item["description"], "A plain skill");
+        assert!(item.contains_key("location"));
+        for key in [
+            "compatibility",
+            "license",
+            "deprecated",
+            "superseded-by",
+            "metadata",
+        ] {
+            assert!(!item.contains_key(key), "unexpected {key}: {item:?}");
+        }
+        assert_eq!(item["body"]["lines"], 1);
+        assert_eq!(item["body"]["words"], 3);
+        assert_eq!(item["metrics"]["sentences"], 1);
+        assert_eq!(item["metrics"]["code_blocks"], 0);
+    }
+
+    #[test]
+    fn json_includes_present_optional_fields() {
+        let parent = tempdir().unwrap();
+        let entry = collect(
+            parent.path(),
+            "full",
+            "---\nname: full\ndescription: A full skill\nlicense: MIT\n\
+             compatibility: Claude Code\nmetadata:\n  version: '1.2'\n  \
+             deprecated: true\n  superseded-by: plain\n  tags: [a, b]\n---\nBody.\n",
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&catalog_entries(&[entry]))).unwrap();
+        let item = &json[0];
+        assert_eq!(item["license"], "MIT");
+        assert_eq!(item["compatibility"], "Claude Code");
+        assert_eq!(item["deprecated"], true);
+        assert_eq!(item["superseded-by"], "plain");
+        assert_eq!(item["metadata"]["version"], "1.2");
+        assert_eq!(item["tags"], serde_json::json!(["a", "b"]));
+        assert!(
+            item["metadata"].get("tags").is_none(),
+            "non-scalar metadata should be skipped: {item}"
+        );
+    }
+
+    #[test]
+    fn catalog_is_sorted_by_name() {
+        let parent = tempdir().unwrap();
+        let b = collect(
+            parent.path(),
+            "beta",
+            "---\nname: beta\ndescription: B\n---\n",
+        );
+        let a = collect(
+            parent.path(),
+            "alpha",
+            "---\nname: alpha\ndescription: A\n---\n",
+        );
+        let catalog = catalog_entries(&[b, a]);
+        let names: Vec<&str> = catalog.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn provenance_is_a_markdown_comment_and_json_fields() {
+        let parent = tempdir().unwrap();
+        let mut entry = collect(
+            parent.path(),
+            "tracked",
+            "---\nname: tracked\ndescription: Tracked skill\n---\nBody.\n",
+        );
+        entry.provenance = Some(Provenance {
+            sha256: "ab12".to_string(),
+            mtime: Some(1_700_000_000),
+        });
+        let catalog = catalog_entries(&[entry]);
+        let comment = "<!-- sha256:ab12/mtime:1700000000 -->";
+        assert!(format_markdown(&catalog).contains(&format!("SKILL.md`\n{comment}\n")));
+        assert!(format_markdown_compact(&catalog).contains(&format!("Tracked skill {comment}\n")));
+        let json: serde_json::Value = serde_json::from_str(&format_json(&catalog)).unwrap();
+        assert_eq!(json[0]["sha256"], "ab12");
+        assert_eq!(json[0]["mtime"], 1_700_000_000);
+    }
+
+    #[test]
+    fn index_has_only_lookup_fields() {
+        let parent = tempdir().unwrap();
+        let tagged = collect(
+            parent.path(),
+            "tagged",
+            "---\nname: tagged\ndescription: T\nlicense: MIT\nmetadata:\n  tags: 'pdf, forms'\n---\nBody.\n",
+        );
+        let plain = collect(
+            parent.path(),
+            "plain",
+            "---\nname: plain\ndescription: P\n---\n",
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_index(&catalog_entries(&[tagged, plain]))).unwrap();
+        let items = json.as_array().unwrap();
+        assert_eq!(items[0]["name"], "plain");
+        assert_eq!(items[0]["tags"], serde_json::json!([]));
+        assert_eq!(items[1]["description"], "T");
+        assert_eq!(items[1]["tags"], serde_json::json!(["pdf", "forms"]));
+        assert!(items[1]["location"].as_str().unwrap().ends_with("SKILL.md"));
+        let mut keys: Vec<&String> = items[1].as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["description", "location", "name", "tags"]);
+    }
+
+    #[test]
+    fn markdown_lists_deprecated_skills_last() {
+        let entry = |name: &str, deprecated: bool| CatalogEntry {
+            name: name.to_string(),
+            description: format!("{name} skill"),
+            location: format!("{name}/SKILL.md"),
+            compatibility: None,
+            license: None,
+            deprecated,
+            superseded_by: deprecated.then(|| "new".to_string()),
+            tags: Vec::new(),
+            metadata: BTreeMap::new(),
+            body: None,
+            metrics: None,
+            provenance: None,
+        };
+        let md = format_markdown(&[entry("legacy", true), entry("new", false)]);
+        assert_eq!(
+            md,
+            "# Skill Catalog\n\n## new\n> new skill\n**Location**: `new/SKILL.md`\n\n---\n\
+             \n## Deprecated\n\n### legacy\n> legacy skill\n\n**Superseded by**: [new](#new)\n\
+             **Location**: `legacy/SKILL.md`\n\n---\n"
+        );
+    }
+}
@@ -34,6 +34,16 @@ pub enum AigentError {
         message: String,
     },
 
+    /// Generated skill failed validation.
+    ///
+    /// The draft holds the generated files and diagnostics so they can be
+    /// recovered after the output directory is cleaned up.
+    #[error("build error: generated skill failed validation:\n{}", format_draft_errors(&draft.diagnostics))]
+    InvalidDraft {
+        /// The generated skill and its validation diagnostics.
+        draft: Box<crate::builder::SkillDraft>,
+    },
+
     /// Path already exists (e.g., SKILL.md during init/new).
     #[error("already exists: {}", path.display())]
     AlreadyExists {
@@ -58,16 +68,26 @@ fn format_validation_errors(errors: &[Diagnostic]) -> String {
     }
 }
 
+/// Format the errors among a draft's diagnostics, one per line.
+fn format_draft_errors(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .filter(|d| d.is_error())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Convenience alias for `Result<T, AigentError>`.
 pub type Result<T> = std::result::Result<T, AigentError>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::diagnostics::{Severity, E001, E002};
+    use crate::diagnostics::{DiagnosticCode, Severity, E001, E002};
 
     /// Helper to create a Diagnostic from severity, code, and message.
-    fn diag(severity: Severity, code: &'static str, msg: &str) -> Diagnostic {
+    fn diag(severity: Severity, code: DiagnosticCode, msg: &str) -> Diagnostic {
         Diagnostic::new(severity, code, msg)
     }
 
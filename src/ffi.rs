@@ -0,0 +1,355 @@
+//! C ABI for validating, linting, and scoring in-memory `SKILL.md` content.
+//!
+//! Enabled with the `ffi` feature. Build a shared library with:
+//!
+//! ```text
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! Every entry point takes NUL-terminated UTF-8 strings, writes a newly
+//! allocated JSON string to `out_json`, and returns a status code
+//! ([`AIGENT_OK`] on success). Strings written to `out_json` must be
+//! released with [`aigent_free_string`]. On failure `out_json` is set to
+//! null. Panics are caught and reported as [`AIGENT_ERR_INTERNAL`]; they
+//! never unwind across the boundary.
+//!
+//! [`AIGENT_OK`]: crate::ffi::AIGENT_OK
+//! [`AIGENT_ERR_INTERNAL`]: crate::ffi::AIGENT_ERR_INTERNAL
+//! [`aigent_free_string`]: crate::ffi::aigent_free_string
+//!
+//! ```c
+//! int aigent_validate_content(const char *content, const char *dir_hint,
+//!                             int target, char **out_json);
+//! int aigent_lint_content(const char *content, char **out_json);
+//! int aigent_score_content(const char *content, const char *dir_hint,
+//!                          char **out_json);
+//! void aigent_free_string(char *s);
+//! ```
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, Severity, ValidationTarget, E000};
+
+/// The call succeeded and `out_json` holds the result.
+pub const AIGENT_OK: c_int = 0;
+/// A required pointer was null, a string was not UTF-8, or `target` was out
+/// of range.
+pub const AIGENT_ERR_INVALID_ARGUMENT: c_int = 1;
+/// The call panicked or the result could not be serialized.
+pub const AIGENT_ERR_INTERNAL: c_int = 2;
+
+/// `target` value for [`ValidationTarget::Standard`].
+pub const AIGENT_TARGET_STANDARD: c_int = 0;
+/// `target` value for [`ValidationTarget::ClaudeCode`].
+pub const AIGENT_TARGET_CLAUDE_CODE: c_int = 1;
+/// `target` value for [`ValidationTarget::Permissive`].
+pub const AIGENT_TARGET_PERMISSIVE: c_int = 2;
+
+/// Validate `SKILL.md` content and write the diagnostics as a JSON array.
+///
+/// `dir_hint` may be null; when set, the skill name is checked against the
+/// directory name it points to (the directory need not exist). `target` is
+/// one of the `AIGENT_TARGET_*` constants.
+///
+/// # Safety
+///
+/// `content` must be a valid NUL-terminated string, `dir_hint` null or a
+/// valid NUL-terminated string, and `out_json` a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn aigent_validate_content(
+    content: *const c_char,
+    dir_hint: *const c_char,
+    target: c_int,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    run(out_json, || {
+        let content = required_str(content)?;
+        let dir_hint = optional_str(dir_hint)?;
+        let target = match target {
+            AIGENT_TARGET_STANDARD => ValidationTarget::Standard,
+            AIGENT_TARGET_CLAUDE_CODE => ValidationTarget::ClaudeCode,
+            AIGENT_TARGET_PERMISSIVE => ValidationTarget::Permissive,
+            _ => return Err(AIGENT_ERR_INVALID_ARGUMENT),
+        };
+        let diags = crate::validate_content(content, dir_hint.map(Path::new), target);
+        serde_json::to_string(&diags).map_err(|_| AIGENT_ERR_INTERNAL)
+    })
+}
+
+/// Run semantic lint checks on `SKILL.md` content and write the
+/// diagnostics as a JSON array.
+///
+/// Content that cannot be parsed yields a single `E000` diagnostic.
+///
+/// # Safety
+///
+/// `content` must be a valid NUL-terminated string and `out_json` a valid
+/// pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn aigent_lint_content(
+    content: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    run(out_json, || {
+        let content = required_str(content)?;
+        let diags = match crate::parse_properties(content) {
+            Ok(props) => {
+                let body = crate::parse_frontmatter(content)
+                    .map(|(_, body)| body)
+                    .unwrap_or_default();
+                crate::lint(&props, &body)
+            }
+            Err(e) => vec![Diagnostic::new(
+                Severity::Error,
+                E000,
+                format!("cannot read properties: {e}"),
+            )],
+        };
+        serde_json::to_string(&diags).map_err(|_| AIGENT_ERR_INTERNAL)
+    })
+}
+
+/// Score `SKILL.md` content and write the score result as a JSON object.
+///
+/// `dir_hint` may be null; see [`aigent_validate_content`].
+///
+/// # Safety
+///
+/// `content` must be a valid NUL-terminated string, `dir_hint` null or a
+/// valid NUL-terminated string, and `out_json` a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn aigent_score_content(
+    content: *const c_char,
+    dir_hint: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    run(out_json, || {
+        let content = required_str(content)?;
+        let dir_hint = optional_str(dir_hint)?;
+        let result = crate::score_content(content, dir_hint.map(Path::new));
+        serde_json::to_string(&result).map_err(|_| AIGENT_ERR_INTERNAL)
+    })
+}
+
+/// Release a string returned through `out_json`. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer written to `out_json` by one of the
+/// `aigent_*` functions, and must not be freed twice.
+#[no_mangle]
+pub unsafe extern "C" fn aigent_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Run `f`, write its JSON to `out_json`, and map failures to status codes.
+unsafe fn run(
+    out_json: *mut *mut c_char,
+    f: impl FnOnce() -> std::result::Result<String, c_int>,
+) -> c_int {
+    if out_json.is_null() {
+        return AIGENT_ERR_INVALID_ARGUMENT;
+    }
+    *out_json = std::ptr::null_mut();
+    let json = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(json)) => json,
+        Ok(Err(code)) => return code,
+        Err(_) => return AIGENT_ERR_INTERNAL,
+    };
+    // Serialized JSON never contains interior NULs.
+    match CString::new(json) {
+        Ok(s) => {
+            *out_json = s.into_raw();
+            AIGENT_OK
+        }
+        Err(_) => AIGENT_ERR_INTERNAL,
+    }
+}
+
+/// Borrow a required C string as UTF-8.
+unsafe fn required_str<'a>(s: *const c_char) -> std::result::Result<&'a str, c_int> {
+    if s.is_null() {
+        return Err(AIGENT_ERR_INVALID_ARGUMENT);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| AIGENT_ERR_INVALID_ARGUMENT)
+}
+
+/// Borrow an optional C string as UTF-8; null maps to `None`.
+unsafe fn optional_str<'a>(s: *const c_char) -> std::result::Result<Option<&'a str>, c_int> {
+    if s.is_null() {
+        Ok(None)
+    } else {
+        required_str(s).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "---\nname: processing-pdfs\ndescription: Processes PDF files and extracts text. Use when working with PDFs.\n---\nBody.\n";
+
+    /// Call an entry point, take ownership of the JSON, and free it.
+    fn call(f: impl FnOnce(*mut *mut c_char) -> c_int) -> (c_int, Option<serde_json::Value>) {
+        let mut out: *mut c_char = std::ptr::null_mut();
+        let code = f(&mut out);
+        if out.is_null() {
+            return (code, None);
+        }
+        let json = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_string();
+        unsafe { aigent_free_string(out) };
+        (code, Some(serde_json::from_str(&json).unwrap()))
+    }
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    // ── aigent_validate_content ──────────────────────────────────────
+
+    #[test]
+    fn validate_valid_content_returns_empty_array() {
+        let content = c(VALID);
+        let (code, json) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 0, out)
+        });
+        assert_eq!(code, AIGENT_OK);
+        assert_eq!(json.unwrap(), serde_json::json!([]));
+    }
+
+    #[test]
+    fn validate_reports_diagnostics_as_json() {
+        let content = c("---\nname: Bad_Name\ndescription: Does things\n---\n");
+        let (code, json) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 0, out)
+        });
+        assert_eq!(code, AIGENT_OK);
+        let json = json.unwrap();
+        let diags = json.as_array().unwrap();
+        assert!(!diags.is_empty());
+        assert_eq!(diags[0]["severity"], "error");
+    }
+
+    #[test]
+    fn validate_uses_dir_hint_for_name_match() {
+        let content = c(VALID);
+        let dir = c("/skills/other-name");
+        let (code, json) =
+            call(|out| unsafe { aigent_validate_content(content.as_ptr(), dir.as_ptr(), 0, out) });
+        assert_eq!(code, AIGENT_OK);
+        assert!(json
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|d| d["code"] == "E009"));
+    }
+
+    #[test]
+    fn validate_target_controls_extension_fields() {
+        let content =
+            c("---\nname: my-skill\ndescription: Does things\nargument-hint: \"[file]\"\n---\n");
+        let (_, standard) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 0, out)
+        });
+        let (_, claude_code) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 1, out)
+        });
+        assert!(standard
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|d| d["code"] == "W001"));
+        assert_eq!(claude_code.unwrap(), serde_json::json!([]));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_arguments() {
+        let content = c(VALID);
+        let (code, json) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 7, out)
+        });
+        assert_eq!(code, AIGENT_ERR_INVALID_ARGUMENT);
+        assert!(json.is_none());
+
+        let (code, json) = call(|out| unsafe {
+            aigent_validate_content(std::ptr::null(), std::ptr::null(), 0, out)
+        });
+        assert_eq!(code, AIGENT_ERR_INVALID_ARGUMENT);
+        assert!(json.is_none());
+
+        let code = unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 0, std::ptr::null_mut())
+        };
+        assert_eq!(code, AIGENT_ERR_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn validate_rejects_non_utf8_content() {
+        let content = CString::new(vec![0xff, 0xfe]).unwrap();
+        let (code, json) = call(|out| unsafe {
+            aigent_validate_content(content.as_ptr(), std::ptr::null(), 0, out)
+        });
+        assert_eq!(code, AIGENT_ERR_INVALID_ARGUMENT);
+        assert!(json.is_none());
+    }
+
+    // ── aigent_lint_content ──────────────────────────────────────────
+
+    #[test]
+    fn lint_returns_info_diagnostics() {
+        let content = c("---\nname: pdf-helper\ndescription: Helps\n---\n");
+        let (code, json) = call(|out| unsafe { aigent_lint_content(content.as_ptr(), out) });
+        assert_eq!(code, AIGENT_OK);
+        let json = json.unwrap();
+        let codes: Vec<&str> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["code"].as_str().unwrap())
+            .collect();
+        assert!(codes.contains(&"I005"), "got: {codes:?}");
+    }
+
+    #[test]
+    fn lint_unparseable_content_reports_e000() {
+        let content = c("no frontmatter");
+        let (code, json) = call(|out| unsafe { aigent_lint_content(content.as_ptr(), out) });
+        assert_eq!(code, AIGENT_OK);
+        assert_eq!(json.unwrap()[0]["code"], "E000");
+    }
+
+    // ── aigent_score_content ─────────────────────────────────────────
+
+    #[test]
+    fn score_matches_library_result() {
+        let content = c(VALID);
+        let (code, json) =
+            call(|out| unsafe { aigent_score_content(content.as_ptr(), std::ptr::null(), out) });
+        assert_eq!(code, AIGENT_OK);
+        let expected = crate::score_content(VALID, None);
+        assert_eq!(json.unwrap()["total"], expected.total);
+    }
+
+    // ── memory hygiene ───────────────────────────────────────────────
+
+    #[test]
+    fn free_string_accepts_null() {
+        unsafe { aigent_free_string(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn run_catches_panics() {
+        let mut out: *mut c_char = std::ptr::null_mut();
+        let code = unsafe { run(&mut out, || panic!("boom")) };
+        assert_eq!(code, AIGENT_ERR_INTERNAL);
+        assert!(out.is_null());
+    }
+}
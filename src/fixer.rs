@@ -2,16 +2,27 @@
 //!
 //! Reads a SKILL.md file, applies fixes for diagnostics that have
 //! suggestions, and writes the result back. Currently supports fixing
-//! frontmatter fields only (name and description).
+//! frontmatter fields (name and description), tab indentation, line
+//! endings, and a lowercase `skill.md` file name. Spacing in the description (I012, I013)
+//! is fixed too, so lint diagnostics can be passed alongside validation
+//! ones.
+//!
+//! Overlong bodies (W002) have a separate, opt-in fix,
+//! [`extract_reference`], because it moves content into a new file.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use regex::Regex;
 
-use crate::diagnostics::{Diagnostic, E002, E003, E006, E012};
-use crate::errors::Result;
-use crate::parser::{find_skill_md, read_file_checked};
+use crate::diagnostics::{Diagnostic, E002, E003, E006, E012, E022, I012, I013, W006, W010};
+use crate::errors::{AigentError, Result};
+use crate::linter::collapse_description_spaces;
+use crate::parser::{
+    find_skill_md, frontmatter_indent_width, read_file_checked, skill_md_names, split_frontmatter,
+    tab_indented_lines,
+};
+use crate::validator::{body_line_count, MAX_BODY_LINES};
 
 /// Regex for matching the `name` field line in frontmatter.
 static NAME_RE: LazyLock<Regex> =
@@ -29,11 +40,13 @@ static TAG_RE: LazyLock<Regex> =
 /// Apply automatic fixes to a SKILL.md file based on diagnostics.
 ///
 /// Only fixes diagnostics that have a suggestion and are in the fixable
-/// set (E002, E003, E006, E012). Returns the number of fixes applied.
+/// set (E002, E003, E006, E012, E022, I012, I013, W006, W010). Returns the number
+/// of fixes applied. The W010 fix renames `skill.md` to `SKILL.md` after any
+/// content fixes are written.
 ///
 /// # Errors
 ///
-/// Returns `AigentError::Io` if the file cannot be read or written.
+/// Returns `AigentError::Io` if the file cannot be read, written, or renamed.
 /// Returns `AigentError::Parse` if the file cannot be parsed.
 pub fn apply_fixes(dir: &Path, diagnostics: &[Diagnostic]) -> Result<usize> {
     let path = find_skill_md(dir).ok_or_else(|| crate::errors::AigentError::Parse {
@@ -45,56 +58,273 @@ pub fn apply_fixes(dir: &Path, diagnostics: &[Diagnostic]) -> Result<usize> {
     let mut fix_count = 0;
 
     for diag in diagnostics {
-        if diag.suggestion.is_none() {
-            continue;
+        if let Some(fixed) = fix_one(&modified, diag) {
+            modified = fixed;
+            fix_count += 1;
         }
-        let before = modified.clone();
-        match diag.code {
-            E002 => {
-                // Truncate name at hyphen boundary.
-                if let Some(truncated) = extract_quoted_value(&diag.suggestion) {
-                    modified = fix_frontmatter_field(&modified, "name", &truncated);
-                }
-            }
-            E003 => {
-                // Lowercase the entire name.
-                if diag
-                    .suggestion
-                    .as_deref()
-                    .is_some_and(|s| s.starts_with("Use lowercase:"))
-                {
-                    modified = lowercase_name_in_frontmatter(&modified);
-                }
+    }
+
+    if fix_count > 0 && modified != content {
+        write_back(&path, &modified)?;
+    }
+
+    if diagnostics
+        .iter()
+        .any(|d| d.code == W010 && d.suggestion.is_some())
+        && rename_to_uppercase(dir)?
+    {
+        fix_count += 1;
+    }
+
+    Ok(fix_count)
+}
+
+/// Rename `skill.md` to `SKILL.md` (the W010 fix).
+///
+/// Does nothing unless the directory listing shows `skill.md` and no
+/// `SKILL.md`, so a directory holding both is left for a human to merge.
+/// The rename goes through a temporary name because renaming straight to a
+/// name that differs only in case is a no-op on some case-insensitive
+/// filesystems. Returns `true` if the file was renamed.
+fn rename_to_uppercase(dir: &Path) -> Result<bool> {
+    let Some(names) = skill_md_names(dir) else {
+        return Ok(false);
+    };
+    if !names.lowercase || names.uppercase {
+        return Ok(false);
+    }
+    rename_through(
+        &dir.join("skill.md"),
+        &dir.join(".skill.md.aigent-rename"),
+        &dir.join("SKILL.md"),
+    )?;
+    Ok(true)
+}
+
+/// Rename `from` to `to` by way of `temp`.
+///
+/// If the second rename fails, `temp` is renamed back to `from` so the file
+/// is not left under the temporary name, and the error is returned.
+fn rename_through(from: &Path, temp: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::rename(from, temp)?;
+    if let Err(e) = std::fs::rename(temp, to) {
+        std::fs::rename(temp, from)?;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Outcome of an interactive fix session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InteractiveFixResult {
+    /// Number of fixes accepted and written.
+    pub accepted: usize,
+    /// Number of fixes rejected (including those left after quitting).
+    pub rejected: usize,
+}
+
+/// Interactively apply fixes, asking before each one.
+///
+/// For each diagnostic with an applicable fix, prints the diagnostic and the
+/// proposed change as a unified diff to stderr, then reads an answer from
+/// `reader`: `y` accepts, `n` rejects, `a` accepts this and all remaining
+/// fixes, `q` rejects this and all remaining fixes. Each proposal is derived
+/// from the content with earlier accepted fixes already applied, so fixes
+/// that touch the same line compose. Accepted fixes are written in one batch
+/// at the end.
+///
+/// # Errors
+///
+/// Returns `AigentError::Io` if the file cannot be read or written, or if
+/// `reader` runs out of input before all fixes are answered (nothing is
+/// written in that case). Returns `AigentError::Parse` if no SKILL.md is found.
+pub fn apply_fixes_interactive(
+    dir: &Path,
+    diagnostics: &[Diagnostic],
+    reader: &mut dyn std::io::BufRead,
+) -> Result<InteractiveFixResult> {
+    let path = find_skill_md(dir).ok_or_else(|| crate::errors::AigentError::Parse {
+        message: "SKILL.md not found".to_string(),
+    })?;
+
+    let content = read_file_checked(&path)?;
+    let mut modified = content.clone();
+    let mut result = InteractiveFixResult::default();
+    let mut accept_all = false;
+    let mut prompted = false;
+    let label = path.display().to_string();
+
+    let mut pending = diagnostics.iter();
+    for diag in pending.by_ref() {
+        let Some(fixed) = fix_one(&modified, diag) else {
+            continue;
+        };
+
+        if !accept_all {
+            // Separate proposals; piped answers are not echoed.
+            if prompted {
+                eprintln!();
             }
-            E006 => {
-                // Collapse consecutive hyphens.
-                if let Some(collapsed) = extract_quoted_value(&diag.suggestion) {
-                    modified = fix_frontmatter_field(&modified, "name", &collapsed);
-                }
+            prompted = true;
+            eprintln!("{} [{}]", diag, diag.code);
+            let diff = similar::TextDiff::from_lines(&modified, &fixed);
+            eprint!(
+                "{}",
+                diff.unified_diff()
+                    .header(&label, &format!("{label} (fixed)"))
+            );
+        }
+        let answer = if accept_all {
+            FixAnswer::All
+        } else {
+            ask(reader)?
+        };
+        match answer {
+            FixAnswer::Yes | FixAnswer::All => {
+                modified = fixed;
+                result.accepted += 1;
+                accept_all |= answer == FixAnswer::All;
             }
-            E012 => {
-                // Strip XML tags from description.
-                modified = strip_xml_from_description(&modified);
+            FixAnswer::No => result.rejected += 1,
+            FixAnswer::Quit => {
+                result.rejected += 1;
+                break;
             }
-            _ => {}
         }
-        if modified != before {
-            fix_count += 1;
+    }
+    if prompted {
+        eprintln!();
+    }
+    // Fixes left after quitting count as rejected.
+    let mut remaining = modified.clone();
+    for diag in pending {
+        if let Some(fixed) = fix_one(&remaining, diag) {
+            remaining = fixed;
+            result.rejected += 1;
         }
     }
 
-    if fix_count > 0 && modified != content {
-        // Re-check that the target is still a regular file to reduce the TOCTOU
-        // window between initial read and write-back.
-        if !crate::fs_util::is_regular_file(&path) {
-            return Err(crate::errors::AigentError::Io(std::io::Error::other(
-                format!("target is no longer a regular file: {}", path.display()),
+    if result.accepted > 0 && modified != content {
+        write_back(&path, &modified)?;
+    }
+
+    Ok(result)
+}
+
+/// An answer to an interactive fix prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixAnswer {
+    Yes,
+    No,
+    All,
+    Quit,
+}
+
+/// Prompt for a fix decision until a valid answer is read.
+fn ask(reader: &mut dyn std::io::BufRead) -> Result<FixAnswer> {
+    loop {
+        eprint!("Apply this fix? [y/n/a/q] ");
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            eprintln!();
+            return Err(crate::errors::AigentError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "no answer on input (interactive fixes need a terminal or piped answers)",
             )));
         }
-        std::fs::write(&path, &modified)?;
+        match line.trim().to_lowercase().as_str() {
+            "y" | "yes" => return Ok(FixAnswer::Yes),
+            "n" | "no" => return Ok(FixAnswer::No),
+            "a" | "all" => return Ok(FixAnswer::All),
+            "q" | "quit" => return Ok(FixAnswer::Quit),
+            _ => eprintln!("Please answer y (yes), n (no), a (all), or q (quit)."),
+        }
     }
+}
 
-    Ok(fix_count)
+/// Apply the fix for a single diagnostic to `content`.
+///
+/// Returns `None` if the diagnostic has no suggestion, is not in the fixable
+/// set, or its fix would not change the content.
+fn fix_one(content: &str, diag: &Diagnostic) -> Option<String> {
+    diag.suggestion.as_ref()?;
+    let fixed = match diag.code {
+        E002 => {
+            // Truncate name at hyphen boundary.
+            let truncated = extract_quoted_value(&diag.suggestion)?;
+            fix_frontmatter_field(content, "name", &truncated)
+        }
+        E003 if diag
+            .suggestion
+            .as_deref()
+            .is_some_and(|s| s.starts_with("Use lowercase:")) =>
+        {
+            // Lowercase the entire name.
+            lowercase_name_in_frontmatter(content)
+        }
+        E006 => {
+            // Collapse consecutive hyphens.
+            let collapsed = extract_quoted_value(&diag.suggestion)?;
+            fix_frontmatter_field(content, "name", &collapsed)
+        }
+        E012 => {
+            // Strip XML tags from description.
+            strip_xml_from_description(content)
+        }
+        E022 => {
+            // Replace tab indentation in the frontmatter with spaces.
+            replace_indent_tabs(content)
+        }
+        I012 => {
+            // Collapse repeated spaces in the description.
+            rewrite_description(content, collapse_description_spaces)?
+        }
+        I013 => {
+            // Trim whitespace around the description.
+            rewrite_description(content, |v| v.trim().to_string())?
+        }
+        W006 => {
+            // Normalize CRLF line endings to LF; a lone CR is not a line
+            // ending to W006, so it is left alone.
+            content.replace("\r\n", "\n")
+        }
+        _ => return None,
+    };
+    (fixed != content).then_some(fixed)
+}
+
+/// Replace the leading tabs of tab-indented frontmatter lines with spaces,
+/// using the indent width of the space-indented lines (2 if none).
+///
+/// Only lines reported by E022 change; the body is left alone.
+fn replace_indent_tabs(content: &str) -> String {
+    let lines = tab_indented_lines(content);
+    let indent = " ".repeat(frontmatter_indent_width(content));
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if lines.contains(&i) {
+                let rest = line.trim_start_matches('\t');
+                indent.repeat(line.len() - rest.len()) + rest
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Write fixed content back to `path`.
+fn write_back(path: &Path, content: &str) -> Result<()> {
+    // Re-check that the target is still a regular file to reduce the TOCTOU
+    // window between initial read and write-back.
+    if !crate::fs_util::is_regular_file(path) {
+        return Err(crate::errors::AigentError::Io(std::io::Error::other(
+            format!("target is no longer a regular file: {}", path.display()),
+        )));
+    }
+    std::fs::write(path, content)?;
+    Ok(())
 }
 
 /// Extract a single-quoted value from a suggestion string.
@@ -141,10 +371,204 @@ fn strip_xml_from_description(content: &str) -> String {
         .to_string()
 }
 
+/// Rewrite a single-line `description` value with `rewrite`, keeping its
+/// quotes.
+///
+/// Returns `None` for block scalars (`|`, `>`) and values continued on
+/// following lines, where a line-based edit could change the meaning.
+fn rewrite_description(content: &str, rewrite: impl Fn(&str) -> String) -> Option<String> {
+    let caps = DESCRIPTION_RE.captures(content)?;
+    let value = caps.get(1)?;
+    let raw = value.as_str().trim_end();
+    if raw.is_empty() || raw.starts_with('|') || raw.starts_with('>') {
+        return None;
+    }
+    let next_line = content[value.end()..].lines().nth(1).unwrap_or("");
+    if next_line.starts_with([' ', '\t']) && !next_line.trim().is_empty() {
+        return None;
+    }
+    let quoted = raw.len() >= 2
+        && ((raw.starts_with('"') && raw.ends_with('"'))
+            || (raw.starts_with('\'') && raw.ends_with('\'')));
+    let replacement = if quoted {
+        let quote = &raw[..1];
+        format!("{quote}{}{quote}", rewrite(&raw[1..raw.len() - 1]))
+    } else {
+        rewrite(raw)
+    };
+    if replacement.trim().is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}{replacement}{}",
+        &content[..value.start()],
+        &content[value.start() + raw.len()..]
+    ))
+}
+
+/// File that [`extract_reference`] moves body content into.
+pub const REFERENCE_FILE: &str = "REFERENCE.md";
+
+/// Line that replaces the extracted content in `SKILL.md`.
+const REFERENCE_LINK: &str = "For the full reference, see [REFERENCE.md](REFERENCE.md).";
+
+/// Outcome of moving part of a long body into [`REFERENCE_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceExtraction {
+    /// Path of the written reference file.
+    pub reference_path: PathBuf,
+    /// Number of body lines moved into the reference file.
+    pub moved_lines: usize,
+    /// Number of body lines left in `SKILL.md`, including the link.
+    pub body_lines: usize,
+}
+
+/// Fix an overlong body (W002) by moving content into `REFERENCE.md`.
+///
+/// Keeps the body up to the first `keep_headings` markdown headings (headings
+/// inside code fences are ignored) and moves everything from the next heading
+/// on into `REFERENCE.md`, leaving a link in its place. Returns `Ok(None)` if
+/// the body is within the [`MAX_BODY_LINES`] limit.
+///
+/// The split is checked before anything is written: the kept and moved parts
+/// must reassemble to the original body, and the shortened `SKILL.md` must
+/// parse and be within the limit. Nothing is written if a check fails, and
+/// `REFERENCE.md` is removed again if `SKILL.md` cannot be rewritten.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if no SKILL.md is found or its frontmatter
+/// cannot be split off. Returns `AigentError::Build` if the body has no
+/// heading after the first `keep_headings`, if the kept part would still
+/// exceed the limit, or if `REFERENCE.md` already exists. Returns
+/// `AigentError::Io` if a file cannot be read or written.
+pub fn extract_reference(dir: &Path, keep_headings: usize) -> Result<Option<ReferenceExtraction>> {
+    let path = find_skill_md(dir).ok_or_else(|| AigentError::Parse {
+        message: "SKILL.md not found".to_string(),
+    })?;
+    let content = read_file_checked(&path)?;
+    let (_, body) = split_frontmatter(&content)?;
+    if body_line_count(body) <= MAX_BODY_LINES {
+        return Ok(None);
+    }
+
+    let headings = heading_offsets(body);
+    let Some(&split) = headings.get(keep_headings) else {
+        return Err(AigentError::Build {
+            message: format!(
+                "body has {} heading(s); need more than {keep_headings} to extract a reference",
+                headings.len()
+            ),
+        });
+    };
+    let (kept, moved) = body.split_at(split);
+    let kept = kept.trim_end();
+    let frontmatter = &content[..content.len() - body.len()];
+    let new_body = if kept.is_empty() {
+        format!("{REFERENCE_LINK}\n")
+    } else {
+        format!("{kept}\n\n{REFERENCE_LINK}\n")
+    };
+    let new_content = format!("{frontmatter}{new_body}");
+    let mut reference = moved.to_string();
+    if !reference.ends_with('\n') {
+        reference.push('\n');
+    }
+
+    // Never lose content: the new SKILL.md without its link, followed by
+    // REFERENCE.md, must give back the original file, up to the whitespace
+    // dropped at the split.
+    let restored_head = new_content
+        .strip_suffix(&format!("{REFERENCE_LINK}\n"))
+        .map(str::trim_end);
+    let restored = restored_head
+        .and_then(|head| content.strip_prefix(head))
+        .is_some_and(|tail| tail.trim() == reference.trim_end());
+    if !restored {
+        return Err(AigentError::Build {
+            message: "reference extraction would lose body content".to_string(),
+        });
+    }
+    let (_, checked_body) = crate::parser::parse_frontmatter(&new_content)?;
+    let body_lines = body_line_count(&checked_body);
+    if body_lines > MAX_BODY_LINES {
+        return Err(AigentError::Build {
+            message: format!(
+                "keeping {keep_headings} heading(s) leaves {body_lines} body lines \
+                 (limit {MAX_BODY_LINES}); keep fewer headings"
+            ),
+        });
+    }
+
+    let reference_path = path.with_file_name(REFERENCE_FILE);
+    write_new(&reference_path, &reference)?;
+    if let Err(e) = write_back(&path, &new_content) {
+        let _ = std::fs::remove_file(&reference_path);
+        return Err(e);
+    }
+
+    Ok(Some(ReferenceExtraction {
+        reference_path,
+        moved_lines: reference.lines().count(),
+        body_lines,
+    }))
+}
+
+/// Byte offsets of the lines in `body` that start an ATX heading.
+///
+/// Lines inside fenced code blocks (```` ``` ```` or `~~~`) are skipped.
+fn heading_offsets(body: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut pos = 0;
+    for line in body.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None if is_heading(line) => offsets.push(pos),
+            None => {}
+        }
+        pos += line.len();
+    }
+    offsets
+}
+
+/// Returns `true` if `line` is an ATX heading (`#` to `######`, then a space or end of line).
+fn is_heading(line: &str) -> bool {
+    let line = line.trim_end();
+    let hashes = line.len() - line.trim_start_matches('#').len();
+    (1..=6).contains(&hashes) && (line.len() == hashes || line[hashes..].starts_with(' '))
+}
+
+/// Write `content` to a file that must not exist yet.
+fn write_new(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => AigentError::Build {
+                message: format!("{} already exists", path.display()),
+            },
+            _ => AigentError::Io(e),
+        })?;
+    if let Err(e) = file.write_all(content.as_bytes()) {
+        drop(file);
+        let _ = std::fs::remove_file(path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::diagnostics::Severity;
+    use crate::diagnostics::{Severity, W002};
     use std::fs;
     use tempfile::tempdir;
 
@@ -197,6 +621,56 @@ mod tests {
         assert!(!result.contains("<script>"));
     }
 
+    #[test]
+    fn apply_fixes_e022_replaces_tab_indentation() {
+        let (_parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: desc\nmetadata:\n\tversion: '1'\n\ttags:\n\t\t- a\n---\n\tBody\n",
+        );
+        let diags = crate::validate(&dir);
+        assert_eq!(
+            diags.iter().filter(|d| d.code == E022).count(),
+            3,
+            "{diags:?}"
+        );
+        assert!(diags[0].message.contains("line 5"), "{diags:?}");
+
+        apply_fixes(&dir, &diags).unwrap();
+        let result = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(
+            result,
+            "---\nname: my-skill\ndescription: desc\nmetadata:\n  version: '1'\n  tags:\n    - a\n---\n\tBody\n"
+        );
+        assert!(crate::validate(&dir).is_empty());
+    }
+
+    #[test]
+    fn e022_fix_follows_existing_indent_width() {
+        let content = "---\nname: x\nmetadata:\n    a: 1\n\tb: 2\n---\n";
+        assert_eq!(
+            replace_indent_tabs(content),
+            "---\nname: x\nmetadata:\n    a: 1\n    b: 2\n---\n"
+        );
+    }
+
+    #[test]
+    fn apply_fixes_works_with_scalar_metadata() {
+        let (_parent, dir) = make_skill_dir(
+            "myskill",
+            "---\nname: MySkill\ndescription: A valid skill\nmetadata: just a string\n---\n",
+        );
+        let diags = crate::validate(&dir);
+        assert!(diags.iter().any(|d| d.code == crate::diagnostics::E019));
+
+        let count = apply_fixes(&dir, &diags).unwrap();
+        assert_eq!(count, 1);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(
+            content,
+            "---\nname: myskill\ndescription: A valid skill\nmetadata: just a string\n---\n"
+        );
+    }
+
     #[test]
     fn apply_fixes_e003_uppercase() {
         let (_parent, dir) = make_skill_dir(
@@ -286,6 +760,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apply_fixes_i012_collapses_description_spaces() {
+        let (_parent, dir) = make_skill_dir(
+            "test",
+            "---\nname: test\ndescription: \"Runs  `a  b`  fast\"\n---\nBody\n",
+        );
+        let diags = vec![Diagnostic::new(
+            Severity::Info,
+            I012,
+            "description contains consecutive spaces",
+        )
+        .with_field("description")
+        .with_suggestion("Collapse repeated spaces into one")];
+
+        assert_eq!(apply_fixes(&dir, &diags).unwrap(), 1);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(
+            content,
+            "---\nname: test\ndescription: \"Runs `a  b` fast\"\n---\nBody\n"
+        );
+    }
+
+    #[test]
+    fn apply_fixes_i013_trims_quoted_description() {
+        let (_parent, dir) = make_skill_dir(
+            "test",
+            "---\r\nname: test\r\ndescription: '  Processes files. '\r\n---\r\n",
+        );
+        let diags = vec![Diagnostic::new(
+            Severity::Info,
+            I013,
+            "description has leading or trailing whitespace",
+        )
+        .with_field("description")
+        .with_suggestion("Remove the surrounding whitespace")];
+
+        assert_eq!(apply_fixes(&dir, &diags).unwrap(), 1);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(
+            content,
+            "---\r\nname: test\r\ndescription: 'Processes files.'\r\n---\r\n"
+        );
+    }
+
+    #[test]
+    fn apply_fixes_i012_skips_block_scalars() {
+        let original = "---\nname: test\ndescription: >\n  Runs  fast\n---\n";
+        let (_parent, dir) = make_skill_dir("test", original);
+        let diags = vec![Diagnostic::new(Severity::Info, I012, "spaces")
+            .with_suggestion("Collapse repeated spaces into one")];
+
+        assert_eq!(apply_fixes(&dir, &diags).unwrap(), 0);
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), original);
+    }
+
+    #[test]
+    fn apply_fixes_w006_normalizes_line_endings() {
+        let (_parent, dir) = make_skill_dir(
+            "test",
+            "---\r\nname: test\r\ndescription: desc\n---\r\nBody\r\n",
+        );
+        let diags =
+            vec![
+                Diagnostic::new(Severity::Warning, W006, "SKILL.md has mixed line endings")
+                    .with_suggestion("Normalize line endings to LF"),
+            ];
+
+        let count = apply_fixes(&dir, &diags).unwrap();
+        assert_eq!(count, 1);
+
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(content, "---\nname: test\ndescription: desc\n---\nBody\n");
+    }
+
+    #[test]
+    fn apply_fixes_w006_leaves_lone_cr_alone() {
+        let (_parent, dir) = make_skill_dir(
+            "test",
+            "---\r\nname: test\r\ndescription: desc\r\n---\r\nBody\rmore\r\n",
+        );
+        let diags = crate::validate(&dir);
+        assert!(diags.iter().any(|d| d.code == W006), "{diags:?}");
+
+        apply_fixes(&dir, &diags).unwrap();
+
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert_eq!(
+            content,
+            "---\nname: test\ndescription: desc\n---\nBody\rmore\n"
+        );
+        assert!(!crate::validate(&dir).iter().any(|d| d.code == W006));
+    }
+
+    #[test]
+    fn apply_fixes_w010_renames_lowercase_skill_md() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("test");
+        fs::create_dir(&dir).unwrap();
+        let content = "---\nname: test\ndescription: desc\n---\nBody\n";
+        fs::write(dir.join("skill.md"), content).unwrap();
+
+        let diags = crate::validate(&dir);
+        assert!(diags.iter().any(|d| d.code == W010), "{diags:?}");
+        let count = apply_fixes(&dir, &diags).unwrap();
+        assert_eq!(count, 1);
+
+        // Compare listed names so the check holds on case-insensitive
+        // filesystems, where probing either casing finds the file.
+        let names = skill_md_names(&dir).unwrap();
+        assert!(names.uppercase && !names.lowercase, "{names:?}");
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), content);
+        assert!(!crate::validate(&dir).iter().any(|d| d.code == W010));
+    }
+
+    #[test]
+    fn rename_through_restores_original_name_when_second_rename_fails() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("skill.md");
+        let temp = dir.path().join(".skill.md.aigent-rename");
+        let to = dir.path().join("target");
+        fs::write(&from, "content").unwrap();
+        // A non-empty directory at the target makes the second rename fail.
+        fs::create_dir(&to).unwrap();
+        fs::write(to.join("keep"), "").unwrap();
+
+        assert!(rename_through(&from, &temp, &to).is_err());
+        assert_eq!(fs::read_to_string(&from).unwrap(), "content");
+        assert!(!temp.exists());
+    }
+
+    #[test]
+    fn apply_fixes_w010_leaves_uppercase_file_alone() {
+        let (_parent, dir) = make_skill_dir("test", "---\nname: test\ndescription: desc\n---\n");
+        let diags = vec![Diagnostic::new(Severity::Warning, W010, "skill.md")
+            .with_suggestion("Rename skill.md to SKILL.md")];
+        assert_eq!(apply_fixes(&dir, &diags).unwrap(), 0);
+        assert!(skill_md_names(&dir).unwrap().uppercase);
+    }
+
     #[test]
     fn apply_fixes_no_fixable_diagnostics() {
         let (_parent, dir) = make_skill_dir("test", "---\nname: test\ndescription: desc\n---\n");
@@ -339,4 +952,237 @@ mod tests {
             "name should be lowercased: {content}"
         );
     }
+
+    // ── apply_fixes_interactive ──────────────────────────────────────
+
+    const MIXED: &str =
+        "---\nname: My-Skill\ndescription: Shows <b>bold</b> examples\n---\nBody.\n";
+
+    fn mixed_diags() -> Vec<Diagnostic> {
+        vec![
+            Diagnostic::new(Severity::Error, E003, "name contains uppercase characters")
+                .with_field("name")
+                .with_suggestion("Use lowercase: 'my-skill'"),
+            Diagnostic::new(Severity::Error, E012, "description contains XML/HTML tags")
+                .with_field("description")
+                .with_suggestion("Remove XML tags from description"),
+        ]
+    }
+
+    fn run_interactive(answers: &str) -> (tempfile::TempDir, String, Result<InteractiveFixResult>) {
+        let (parent, dir) = make_skill_dir("my-skill", MIXED);
+        let mut input = std::io::Cursor::new(answers.as_bytes().to_vec());
+        let result = apply_fixes_interactive(&dir, &mixed_diags(), &mut input);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        (parent, content, result)
+    }
+
+    #[test]
+    fn interactive_accepts_and_rejects_individually() {
+        let (_parent, content, result) = run_interactive("y\nn\n");
+        assert_eq!(
+            result.unwrap(),
+            InteractiveFixResult {
+                accepted: 1,
+                rejected: 1
+            }
+        );
+        assert!(content.contains("name: my-skill"));
+        assert!(content.contains("<b>bold</b>"));
+    }
+
+    #[test]
+    fn interactive_all_accepts_remaining_without_prompting() {
+        let (_parent, content, result) = run_interactive("a\n");
+        assert_eq!(result.unwrap().accepted, 2);
+        assert!(content.contains("name: my-skill"));
+        assert!(content.contains("description: Shows bold examples"));
+    }
+
+    #[test]
+    fn interactive_quit_rejects_remaining_and_keeps_accepted() {
+        let (_parent, content, result) = run_interactive("y\nq\n");
+        assert_eq!(
+            result.unwrap(),
+            InteractiveFixResult {
+                accepted: 1,
+                rejected: 1
+            }
+        );
+        assert!(content.contains("name: my-skill"));
+        assert!(content.contains("<b>bold</b>"));
+    }
+
+    #[test]
+    fn interactive_quit_first_counts_all_rejected() {
+        let (_parent, content, result) = run_interactive("q\n");
+        assert_eq!(
+            result.unwrap(),
+            InteractiveFixResult {
+                accepted: 0,
+                rejected: 2
+            }
+        );
+        assert_eq!(content, MIXED);
+    }
+
+    #[test]
+    fn interactive_reprompts_on_invalid_answer() {
+        let (_parent, _content, result) = run_interactive("maybe\nn\nY\n");
+        assert_eq!(
+            result.unwrap(),
+            InteractiveFixResult {
+                accepted: 1,
+                rejected: 1
+            }
+        );
+    }
+
+    #[test]
+    fn interactive_eof_errors_without_writing() {
+        let (_parent, content, result) = run_interactive("y\n");
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("no answer on input"), "got: {err}");
+        assert_eq!(content, MIXED, "nothing is written when input runs out");
+    }
+
+    #[test]
+    fn interactive_proposals_build_on_accepted_fixes() {
+        // E002 truncation and E006 hyphen collapsing both rewrite the name
+        // line; the second proposal must start from the first's result.
+        let (_parent, dir) = make_skill_dir(
+            "a-b",
+            "---\nname: a--b-extra\ndescription: Does things\n---\n",
+        );
+        let diags = vec![
+            Diagnostic::new(Severity::Error, E006, "consecutive hyphens")
+                .with_suggestion("Collapse to: 'a-b-extra'"),
+            Diagnostic::new(Severity::Error, E002, "too long")
+                .with_suggestion("Truncate to: 'a-b'"),
+        ];
+        let mut input = std::io::Cursor::new(b"y\ny\n".to_vec());
+        let result = apply_fixes_interactive(&dir, &diags, &mut input).unwrap();
+        assert_eq!(result.accepted, 2);
+        let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        assert!(content.contains("name: a-b\n"), "got: {content}");
+    }
+
+    // ── Reference extraction (W002) ──
+
+    /// A body with a title, an overview, and `sections` long sections.
+    fn long_body(sections: usize, lines_per_section: usize) -> String {
+        let mut body = String::from("# Long skill\n\nIntro.\n\n## Overview\n\nShort overview.\n");
+        for s in 0..sections {
+            body.push_str(&format!("\n## Section {s}\n\n"));
+            for l in 0..lines_per_section {
+                body.push_str(&format!("Line {l} of section {s}.\n"));
+            }
+        }
+        body
+    }
+
+    const FRONTMATTER: &str = "---\nname: long\ndescription: A long skill\n---\n";
+
+    #[test]
+    fn extract_reference_moves_content_after_kept_headings() {
+        let body = long_body(3, 200);
+        let (_parent, dir) = make_skill_dir("long", &format!("{FRONTMATTER}{body}"));
+
+        let result = extract_reference(&dir, 2).unwrap().unwrap();
+        let skill = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        let reference = fs::read_to_string(&result.reference_path).unwrap();
+
+        assert_eq!(result.reference_path, dir.join(REFERENCE_FILE));
+        assert!(skill.starts_with(FRONTMATTER));
+        assert!(skill.contains("Short overview."));
+        assert!(skill.ends_with("[REFERENCE.md](REFERENCE.md).\n"));
+        assert!(!skill.contains("## Section 0"));
+        assert!(reference.starts_with("## Section 0\n"));
+        assert_eq!(result.moved_lines, reference.lines().count());
+
+        // Nothing is lost: the kept part plus the reference is the original body.
+        let kept = skill[FRONTMATTER.len()..]
+            .strip_suffix(&format!("\n\n{REFERENCE_LINK}\n"))
+            .unwrap();
+        assert_eq!(format!("{kept}\n\n{reference}"), body);
+
+        let diags = crate::validator::validate(&dir);
+        assert!(!diags.iter().any(|d| d.code == W002), "{diags:?}");
+    }
+
+    #[test]
+    fn extract_reference_ignores_headings_in_code_fences() {
+        let mut body = String::from("# Title\n\n```sh\n# not a heading\n```\n");
+        body.push_str(&"filler\n".repeat(20));
+        body.push_str("## Details\n");
+        body.push_str(&"detail\n".repeat(500));
+        let (_parent, dir) = make_skill_dir("long", &format!("{FRONTMATTER}{body}"));
+
+        extract_reference(&dir, 1).unwrap().unwrap();
+        let skill = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+        let reference = fs::read_to_string(dir.join(REFERENCE_FILE)).unwrap();
+        assert!(skill.contains("# not a heading"));
+        assert!(reference.starts_with("## Details\n"));
+    }
+
+    #[test]
+    fn extract_reference_skips_short_body() {
+        let (_parent, dir) = make_skill_dir("short", &format!("{FRONTMATTER}{}", long_body(1, 5)));
+        assert_eq!(extract_reference(&dir, 1).unwrap(), None);
+        assert!(!dir.join(REFERENCE_FILE).exists());
+    }
+
+    #[test]
+    fn extract_reference_counts_lines_like_w002() {
+        // 500 lines after the separator line: at the limit, so no W002.
+        let body = format!("\n# Title\n\n## Section\n{}", "line\n".repeat(497));
+        let content = format!("{FRONTMATTER}{body}");
+        let (_parent, dir) = make_skill_dir("long", &content);
+        assert!(!crate::validate(&dir).iter().any(|d| d.code == W002));
+        assert_eq!(extract_reference(&dir, 1).unwrap(), None);
+
+        // One more line: W002 fires and the extraction runs.
+        let content = format!("{content}line\n");
+        fs::write(dir.join("SKILL.md"), &content).unwrap();
+        assert!(crate::validate(&dir).iter().any(|d| d.code == W002));
+        assert!(extract_reference(&dir, 1).unwrap().is_some());
+    }
+
+    #[test]
+    fn extract_reference_errors_without_enough_headings() {
+        let content = format!("{FRONTMATTER}# Title\n{}", "line\n".repeat(600));
+        let (_parent, dir) = make_skill_dir("long", &content);
+
+        let err = extract_reference(&dir, 1).unwrap_err();
+        assert!(err.to_string().contains("1 heading(s)"), "{err}");
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), content);
+        assert!(!dir.join(REFERENCE_FILE).exists());
+    }
+
+    #[test]
+    fn extract_reference_errors_when_kept_part_is_still_too_long() {
+        let content = format!("{FRONTMATTER}{}", long_body(2, 520));
+        let (_parent, dir) = make_skill_dir("long", &content);
+
+        // Keeping the title, overview, and first long section leaves > 500 lines.
+        let err = extract_reference(&dir, 3).unwrap_err();
+        assert!(err.to_string().contains("keep fewer headings"), "{err}");
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), content);
+        assert!(!dir.join(REFERENCE_FILE).exists());
+    }
+
+    #[test]
+    fn extract_reference_never_overwrites_existing_reference() {
+        let content = format!("{FRONTMATTER}{}", long_body(3, 200));
+        let (_parent, dir) = make_skill_dir("long", &content);
+        fs::write(dir.join(REFERENCE_FILE), "existing\n").unwrap();
+
+        let err = extract_reference(&dir, 2).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), content);
+        assert_eq!(
+            fs::read_to_string(dir.join(REFERENCE_FILE)).unwrap(),
+            "existing\n"
+        );
+    }
 }
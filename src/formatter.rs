@@ -4,9 +4,13 @@
 //! It is idempotent — running it twice produces no further changes.
 
 use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_yaml_ng::Value;
 
 use crate::errors::{AigentError, Result};
-use crate::parser::{find_skill_md, read_file_checked};
+use crate::parser::{find_skill_md, read_file_checked, split_frontmatter, split_preamble};
 
 /// Result of formatting a single skill.
 #[derive(Debug)]
@@ -19,6 +23,18 @@ pub struct FormatResult {
     pub original: String,
 }
 
+/// Options controlling how `SKILL.md` content is formatted.
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// Keep a leading HTML comment before the opening `---`.
+    ///
+    /// By default the preamble (blank lines and a single HTML comment, see
+    /// [`split_preamble`]) is removed so the
+    /// file starts with `---`. When `true`, the comment is kept directly
+    /// above the frontmatter; leading blank lines are always removed.
+    pub keep_preamble: bool,
+}
+
 /// Generate a unified diff between the original and formatted content.
 ///
 /// Returns an empty string if the content has not changed.
@@ -60,12 +76,21 @@ const KEY_ORDER: &[&str] = &[
 /// Returns an error if the SKILL.md file cannot be found or read,
 /// or if the frontmatter is malformed (no `---` delimiters).
 pub fn format_skill(dir: &Path) -> Result<FormatResult> {
+    format_skill_with(dir, &FormatOptions::default())
+}
+
+/// Format a SKILL.md file with explicit [`FormatOptions`].
+///
+/// # Errors
+///
+/// Same as [`format_skill`].
+pub fn format_skill_with(dir: &Path, options: &FormatOptions) -> Result<FormatResult> {
     let path = find_skill_md(dir).ok_or_else(|| AigentError::Parse {
         message: "no SKILL.md found".into(),
     })?;
     let original = read_file_checked(&path)?;
 
-    let content = format_content(&original)?;
+    let content = format_content_with(&original, options)?;
     let changed = content != original;
 
     Ok(FormatResult {
@@ -81,53 +106,44 @@ pub fn format_skill(dir: &Path) -> Result<FormatResult> {
 ///
 /// Returns an error if the content lacks valid `---` frontmatter delimiters.
 pub fn format_content(original: &str) -> Result<String> {
-    // Normalize CRLF to LF so byte-offset arithmetic works correctly.
-    let content = original.replace("\r\n", "\n");
-
-    // Split into frontmatter and body.
-    if !content.starts_with("---") {
-        return Err(AigentError::Parse {
-            message: "SKILL.md must start with --- delimiter".into(),
-        });
-    }
-
-    let after_first = &content[3..];
-    let close_pos = after_first.find("\n---\n").or_else(|| {
-        // Handle case where closing --- is at end of file.
-        if after_first.ends_with("\n---") {
-            Some(after_first.len() - 4)
-        } else {
-            None
-        }
-    });
+    format_content_with(original, &FormatOptions::default())
+}
 
-    let close_pos = close_pos.ok_or_else(|| AigentError::Parse {
-        message: "missing closing --- delimiter".into(),
-    })?;
+/// Format SKILL.md content with explicit [`FormatOptions`].
+///
+/// # Errors
+///
+/// Same as [`format_content`].
+pub fn format_content_with(original: &str, options: &FormatOptions) -> Result<String> {
+    // Normalize CRLF to LF so byte-offset arithmetic works correctly.
+    let normalized = original.replace("\r\n", "\n");
 
-    // Skip the \n after opening ---; handle empty frontmatter (close_pos == 0).
-    let yaml_str = if close_pos > 0 {
-        &after_first[1..close_pos]
-    } else {
-        ""
-    };
-    let body_start = close_pos + 5; // skip \n---\n
-    let body = if body_start <= after_first.len() {
-        &after_first[body_start..]
+    // Drop (or keep, trimmed) an editor-inserted preamble before `---`.
+    let (preamble, content) = split_preamble(&normalized);
+    let preamble = preamble.trim();
+    let prefix = if options.keep_preamble && !preamble.is_empty() {
+        format!("{preamble}\n")
     } else {
-        ""
+        String::new()
     };
 
+    // Split into frontmatter and body.
+    let (yaml_str, body) = split_frontmatter(content)?;
+
     let formatted_yaml = format_frontmatter(yaml_str);
     let formatted_body = format_body(body);
 
-    Ok(format!("---\n{formatted_yaml}\n---\n{formatted_body}"))
+    Ok(format!(
+        "{prefix}---\n{formatted_yaml}\n---\n{formatted_body}"
+    ))
 }
 
 /// Format YAML frontmatter with canonical key ordering.
 ///
 /// Preserves values exactly as-is (including multiline blocks, quoting,
-/// and comments). Only reorders top-level keys.
+/// and comments), except that single-line string values under `metadata`
+/// get canonical quoting (see [`normalize_metadata_quoting`]). Otherwise
+/// only reorders top-level keys.
 fn format_frontmatter(yaml: &str) -> String {
     let blocks = parse_yaml_blocks(yaml);
 
@@ -187,8 +203,12 @@ fn format_frontmatter(yaml: &str) -> String {
 
     // Emit known-order keys.
     for (_, block) in &ordered {
-        if let YamlBlock::Key { raw, .. } = block {
-            lines.push(raw.clone());
+        if let YamlBlock::Key { name, raw } = block {
+            if name == "metadata" {
+                lines.push(normalize_metadata_quoting(raw));
+            } else {
+                lines.push(raw.clone());
+            }
         }
     }
 
@@ -294,33 +314,154 @@ fn parse_yaml_blocks(yaml: &str) -> Vec<YamlBlock> {
     blocks
 }
 
-/// Format the markdown body.
+/// Boolean and null spellings from YAML 1.1.
+///
+/// YAML 1.2 reads most of these as strings, but YAML 1.1 parsers still in
+/// use read them as booleans or null, so strings spelled this way stay
+/// quoted.
+const YAML11_KEYWORDS: &[&str] = &[
+    "y", "Y", "yes", "Yes", "YES", "n", "N", "no", "No", "NO", "true", "True", "TRUE", "false",
+    "False", "FALSE", "on", "On", "ON", "off", "Off", "OFF", "null", "Null", "NULL", "~",
+];
+
+/// Regex for a single-line `key: value` entry in a nested mapping.
+static ENTRY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<indent> +)(?P<key>[A-Za-z0-9_.-]+): +(?P<value>\S.*)$")
+        .expect("mapping entry regex must compile")
+});
+
+/// Apply canonical quoting to the string values in a `metadata` block.
+///
+/// Each single-line string value is written bare when that is safe, and
+/// single-quoted otherwise, so `version: "1.0"` and `version: '1.0'` both
+/// become `version: '1.0'`. Numbers, booleans, and null are left alone, as
+/// are values with a trailing comment, anchors, aliases, tags, block and
+/// multi-line scalars, and strings that need double-quote escapes. A value
+/// is only rewritten when the new spelling parses to the same string.
+fn normalize_metadata_quoting(raw: &str) -> String {
+    let mut out = Vec::new();
+    // Lines indented deeper than this continue a block or multi-line scalar.
+    let mut continuation_indent: Option<usize> = None;
+    for line in raw.lines() {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if let Some(limit) = continuation_indent {
+            if line.trim().is_empty() || indent > limit {
+                out.push(line.to_string());
+                continue;
+            }
+            continuation_indent = None;
+        }
+        let Some(caps) = ENTRY_RE.captures(line) else {
+            out.push(line.to_string());
+            continue;
+        };
+        let value = caps["value"].trim_end();
+        let parsed = parse_scalar(value);
+        if value.starts_with(['|', '>']) || parsed.is_none() {
+            continuation_indent = Some(indent);
+        }
+        let requoted = match parsed {
+            Some(Value::String(s))
+                if !value.contains(" #") && !value.starts_with(['|', '>', '&', '*', '!']) =>
+            {
+                canonical_scalar(&s)
+                    .filter(|c| parse_scalar(c).is_some_and(|v| v == Value::String(s.clone())))
+            }
+            _ => None,
+        };
+        match requoted {
+            Some(canonical) => {
+                out.push(format!("{}{}: {canonical}", &caps["indent"], &caps["key"]))
+            }
+            None => out.push(line.to_string()),
+        }
+    }
+    out.join("\n")
+}
+
+/// Parse `value` as the value of a YAML mapping entry.
+///
+/// Returns `None` if it does not parse on its own line.
+fn parse_scalar(value: &str) -> Option<Value> {
+    let mapping: serde_yaml_ng::Mapping = serde_yaml_ng::from_str(&format!("k: {value}")).ok()?;
+    mapping.get("k").cloned()
+}
+
+/// The canonical spelling of the string `s`: bare when safe, otherwise
+/// single-quoted. Returns `None` for strings with control characters,
+/// which need double-quote escapes.
+fn canonical_scalar(s: &str) -> Option<String> {
+    if s.chars().any(char::is_control) {
+        None
+    } else if is_safe_bare(s) {
+        Some(s.to_string())
+    } else {
+        Some(format!("'{}'", s.replace('\'', "''")))
+    }
+}
+
+/// Returns `true` if `s` can be written without quotes.
+///
+/// The bare text must read back as the same string, must not be a
+/// [`YAML11_KEYWORDS`] spelling, and must not look like a number, version,
+/// date, or time (only digits and `+-._:eE`), which some YAML readers
+/// would not treat as a string.
+fn is_safe_bare(s: &str) -> bool {
+    let numeric_like = s.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'))
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | '_' | ':' | 'e' | 'E'));
+    !s.is_empty()
+        && s.trim() == s
+        && !numeric_like
+        && !YAML11_KEYWORDS.contains(&s)
+        && parse_scalar(s).is_some_and(|v| v == Value::String(s.to_string()))
+}
+
+/// Format the markdown body of a `SKILL.md` file.
 ///
 /// Normalizations:
 /// - Remove trailing whitespace from each line.
-/// - Ensure file ends with exactly one newline.
-/// - Collapse 3+ consecutive blank lines into 2.
-fn format_body(body: &str) -> String {
+/// - Collapse runs of 3 or more blank lines into a single blank line.
+/// - Ensure the body ends with exactly one newline.
+///
+/// Lines inside fenced code blocks (```` ``` ```` or `~~~`) are kept
+/// exactly, including trailing whitespace and blank lines.
+#[must_use]
+pub fn format_body(body: &str) -> String {
     if body.is_empty() {
         return String::from("\n");
     }
 
-    let lines: Vec<String> = body.lines().map(|l| l.trim_end().to_string()).collect();
+    let mut result: Vec<&str> = Vec::new();
+    let mut blank_run = 0;
+    let mut fence: Option<&str> = None;
 
-    // Collapse consecutive blank lines (max 2).
-    let mut result = Vec::new();
-    let mut blank_count = 0;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+                result.push(line.trim_end());
+            } else {
+                result.push(line);
+            }
+            continue;
+        }
 
-    for line in &lines {
+        let line = line.trim_end();
         if line.is_empty() {
-            blank_count += 1;
-            if blank_count <= 2 {
-                result.push(String::new());
-            }
-        } else {
-            blank_count = 0;
-            result.push(line.clone());
+            blank_run += 1;
+            result.push(line);
+            continue;
+        }
+        collapse_blank_run(&mut result, blank_run);
+        blank_run = 0;
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+        } else if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
         }
+        result.push(line);
     }
 
     // Remove trailing blank lines.
@@ -334,6 +475,14 @@ fn format_body(body: &str) -> String {
     out
 }
 
+/// Reduce a run of `run` blank lines just pushed onto `lines` to a single
+/// blank line when it is 3 or more lines long.
+fn collapse_blank_run(lines: &mut Vec<&str>, run: usize) {
+    if run >= 3 {
+        lines.truncate(lines.len() - (run - 1));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,10 +549,126 @@ mod tests {
         let input = "---\nname: my-skill\ndescription: Does things\n---\nParagraph 1.\n\n\n\n\nParagraph 2.\n";
         let result = format_content(input).unwrap();
         assert!(
-            !result.contains("\n\n\n\n"),
-            "should collapse to max 2 blank lines"
+            result.contains("Paragraph 1.\n\nParagraph 2."),
+            "four blank lines should collapse to one: {result:?}"
+        );
+    }
+
+    // ── Metadata quoting ─────────────────────────────────────────────
+
+    /// Format a skill whose `metadata` block is `entries`, and return the
+    /// formatted `metadata` block.
+    fn format_metadata(entries: &str) -> String {
+        let input = format!(
+            "---\nname: my-skill\ndescription: Does things\nmetadata:\n{entries}---\nBody.\n"
         );
-        assert!(result.contains("Paragraph 1.\n\n\nParagraph 2."));
+        let result = format_content(&input).unwrap();
+        let start = result.find("metadata:\n").unwrap() + "metadata:\n".len();
+        let end = result.rfind("\n---\n").unwrap();
+        format!("{}\n", &result[start..end])
+    }
+
+    #[test]
+    fn metadata_quoting_is_canonical() {
+        for input in ["  version: '1.0'\n", "  version: \"1.0\"\n"] {
+            assert_eq!(format_metadata(input), "  version: '1.0'\n", "{input:?}");
+        }
+        assert_eq!(
+            format_metadata("  author: \"Jane Doe\"\n  team: 'platform'\n"),
+            "  author: Jane Doe\n  team: platform\n"
+        );
+    }
+
+    #[test]
+    fn metadata_quoting_keeps_strings_that_look_like_other_types() {
+        for value in [
+            "yes",
+            "off",
+            "null",
+            "true",
+            "1.0",
+            "1.0.0",
+            "2024-01-15",
+            "10:30",
+            "1e3",
+            "~",
+            "",
+        ] {
+            let input = format!("  v: \"{value}\"\n");
+            assert_eq!(
+                format_metadata(&input),
+                format!("  v: '{value}'\n"),
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_quoting_quotes_strings_with_yaml_syntax() {
+        assert_eq!(
+            format_metadata(
+                "  note: \"a: b\"\n  ref: \"#tag\"\n  quote: \"it's\"\n  pad: \" x\"\n"
+            ),
+            "  note: 'a: b'\n  ref: '#tag'\n  quote: it's\n  pad: ' x'\n"
+        );
+        assert_eq!(
+            format_metadata("  star: \"*all\"\n  both: \"'q' \\\"d\\\"\"\n"),
+            "  star: '*all'\n  both: '''q'' \"d\"'\n"
+        );
+    }
+
+    #[test]
+    fn metadata_quoting_leaves_non_strings_and_complex_values() {
+        let entries = "  priority: 5\n  ratio: 1.5\n  draft: false\n  empty: null\n  tab: \"a\\tb\"\n  commented: \"x\" # keep\n  tags: [\"a\", 'b']\n  notes: |\n    key: \"stays\"\n  nested:\n    owner: \"team\"\n";
+        let expected = "  priority: 5\n  ratio: 1.5\n  draft: false\n  empty: null\n  tab: \"a\\tb\"\n  commented: \"x\" # keep\n  tags: [\"a\", 'b']\n  notes: |\n    key: \"stays\"\n  nested:\n    owner: team\n";
+        assert_eq!(format_metadata(entries), expected);
+    }
+
+    #[test]
+    fn metadata_quoting_never_changes_parsed_values() {
+        let input = "---\nname: my-skill\ndescription: Does things\nmetadata:\n  a: \"1.0\"\n  b: \"yes\"\n  c: \"x: y\"\n  d: 'plain'\n  e: \"it's\"\n  f: \"caf\\u00e9\"\n  g: 3\n---\nBody.\n";
+        let result = format_content(input).unwrap();
+        let parse = |content: &str| -> Value {
+            let (yaml, _) = split_frontmatter(content).unwrap();
+            serde_yaml_ng::from_str(yaml).unwrap()
+        };
+        assert_eq!(parse(input), parse(&result));
+        assert_eq!(format_content(&result).unwrap(), result, "not idempotent");
+    }
+
+    // ── format_body ──────────────────────────────────────────────────
+
+    #[test]
+    fn body_four_blank_lines_collapse_to_one() {
+        assert_eq!(format_body("One.\n\n\n\n\nTwo.\n"), "One.\n\nTwo.\n");
+    }
+
+    #[test]
+    fn body_short_blank_runs_are_kept() {
+        let body = "One.\n\nTwo.\n\n\nThree.\n";
+        assert_eq!(format_body(body), body);
+    }
+
+    #[test]
+    fn body_trailing_whitespace_is_trimmed() {
+        assert_eq!(format_body("One.  \nTwo.\t\n"), "One.\nTwo.\n");
+    }
+
+    #[test]
+    fn body_fenced_blocks_are_preserved_exactly() {
+        let body =
+            "Intro.\n\n```text\nline  \n\n\n\n\nend\n```\n\n\n\n\n~~~\na\n\n\n\nb \n~~~\nOutro.\n";
+        assert_eq!(
+            format_body(body),
+            "Intro.\n\n```text\nline  \n\n\n\n\nend\n```\n\n~~~\na\n\n\n\nb \n~~~\nOutro.\n"
+        );
+    }
+
+    #[test]
+    fn body_formatting_is_idempotent() {
+        let body = "A.\n\n\n\nB.  \n```\nx  \n\n\n\n```\n\n\n\n\nC.\n";
+        let once = format_body(body);
+        assert_eq!(format_body(&once), once);
     }
 
     #[test]
@@ -499,6 +764,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_keeps_dashes_line_inside_block_scalar() {
+        let input = "---\ndescription: |\n  Before.\n  ---\n  After.\nname: my-skill\n---\nBody.\n";
+        let result = format_content(input).unwrap();
+        assert_eq!(
+            result,
+            "---\nname: my-skill\ndescription: |\n  Before.\n  ---\n  After.\n---\nBody.\n"
+        );
+    }
+
+    #[test]
+    fn format_accepts_closing_delimiter_with_trailing_whitespace() {
+        let input = "---\nname: my-skill\ndescription: Does things\n---  \nBody.\n";
+        let result = format_content(input).unwrap();
+        assert_eq!(
+            result,
+            "---\nname: my-skill\ndescription: Does things\n---\nBody.\n"
+        );
+    }
+
     // ── Comment handling tests ───────────────────────────────────────
 
     #[test]
@@ -611,6 +896,47 @@ mod tests {
         );
     }
 
+    // ── Preamble handling ───────────────────────────────────────────
+
+    #[test]
+    fn preamble_comment_removed_by_default() {
+        let input = "<!-- editor -->\n\n---\nname: my-skill\ndescription: desc\n---\nBody.\n";
+        let result = format_content(input).unwrap();
+        assert!(result.starts_with("---\nname: my-skill"), "got: {result}");
+    }
+
+    #[test]
+    fn leading_blank_lines_removed() {
+        let input = "\n\n---\nname: my-skill\ndescription: desc\n---\nBody.\n";
+        let result = format_content(input).unwrap();
+        assert!(result.starts_with("---\n"), "got: {result}");
+    }
+
+    #[test]
+    fn preamble_comment_kept_when_configured() {
+        let input = "\n<!-- editor -->\n\n---\nname: my-skill\ndescription: desc\n---\nBody.\n";
+        let options = FormatOptions {
+            keep_preamble: true,
+        };
+        let result = format_content_with(input, &options).unwrap();
+        assert!(
+            result.starts_with("<!-- editor -->\n---\nname: my-skill"),
+            "got: {result}"
+        );
+        // Idempotent with the same options.
+        assert_eq!(format_content_with(&result, &options).unwrap(), result);
+    }
+
+    #[test]
+    fn keep_preamble_drops_blank_only_preamble() {
+        let input = "\n\n---\nname: my-skill\ndescription: desc\n---\nBody.\n";
+        let options = FormatOptions {
+            keep_preamble: true,
+        };
+        let result = format_content_with(input, &options).unwrap();
+        assert!(result.starts_with("---\n"), "got: {result}");
+    }
+
     // ── diff_skill tests ────────────────────────────────────────────
 
     #[test]
@@ -2,7 +2,12 @@
 //!
 //! These helpers use `symlink_metadata()` instead of `metadata()` to avoid
 //! following symlinks. This prevents symlink-based directory escape attacks
-//! in security-sensitive paths like skill directory traversal.
+//! in security-sensitive paths like skill directory traversal. Discovery
+//! can opt in to following directory symlinks, guarded by [`file_id`]-based
+//! cycle detection.
+//!
+//! [`on_disk_name`] and [`is_case_only_mismatch`] support name checks that
+//! must behave the same on case-sensitive and case-insensitive filesystems.
 
 use std::path::Path;
 
@@ -34,6 +39,105 @@ pub(crate) fn is_symlink(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Returns `true` if the path is a directory.
+///
+/// Symlinks to directories count only when `follow_symlinks` is set;
+/// otherwise this is [`is_regular_dir`].
+#[must_use]
+pub(crate) fn is_dir_following(path: &Path, follow_symlinks: bool) -> bool {
+    if follow_symlinks {
+        path.metadata().map(|m| m.is_dir()).unwrap_or(false)
+    } else {
+        is_regular_dir(path)
+    }
+}
+
+/// Identity of a filesystem object, stable across the paths that reach it.
+#[cfg(unix)]
+pub(crate) type FileId = (u64, u64);
+
+/// Identity of a filesystem object, stable across the paths that reach it.
+#[cfg(not(unix))]
+pub(crate) type FileId = std::path::PathBuf;
+
+/// Returns the identity of `path`, following symlinks.
+///
+/// This is `(device, inode)` on Unix and the canonical path elsewhere.
+/// Returns `None` if the path cannot be resolved.
+#[must_use]
+pub(crate) fn file_id(path: &Path) -> Option<FileId> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        path.metadata().ok().map(|m| (m.dev(), m.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        path.canonicalize().ok()
+    }
+}
+
+/// Returns the final component of `path` as spelled in its parent
+/// directory's listing.
+///
+/// On a case-insensitive filesystem (the macOS and Windows default),
+/// `skills/my-skill` opens a directory stored as `My-Skill`, so the spelling
+/// in the path says nothing about the name on disk. This lists the parent and
+/// returns the spelling of the entry that is the same filesystem object.
+/// Falls back to the spelling in `path` when the parent cannot be listed or
+/// no entry matches. Returns `None` if `path` has no UTF-8 final component.
+#[must_use]
+pub(crate) fn on_disk_name(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = std::fs::read_dir(parent) else {
+        return Some(name.to_string());
+    };
+    let mut candidates = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(entry_name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry_name == name {
+            return Some(entry_name);
+        }
+        if is_case_only_mismatch(&entry_name, name) {
+            candidates.push(entry_name);
+        }
+    }
+    let id = file_id(path);
+    candidates
+        .into_iter()
+        .find(|c| id.is_some() && file_id(&parent.join(c)) == id)
+        .or_else(|| Some(name.to_string()))
+}
+
+/// Returns `true` if `a` and `b` differ, but only in letter case.
+///
+/// Such names open the same file on a case-insensitive filesystem and
+/// different files on a case-sensitive one.
+#[must_use]
+pub(crate) fn is_case_only_mismatch(a: &str, b: &str) -> bool {
+    a != b && a.to_lowercase() == b.to_lowercase()
+}
+
+/// Returns `true` if the filesystem holding `dir` ignores letter case.
+///
+/// Probes by creating a file in `dir` and looking it up with its name
+/// upper-cased. Only used by tests, which document behavior on both kinds
+/// of filesystem.
+#[cfg(test)]
+pub(crate) fn is_case_insensitive(dir: &Path) -> bool {
+    let probe = dir.join("case-probe");
+    std::fs::write(&probe, "").unwrap();
+    let insensitive = dir.join("CASE-PROBE").exists();
+    std::fs::remove_file(probe).unwrap();
+    insensitive
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +240,69 @@ mod tests {
         let path = Path::new("/nonexistent/path/file.txt");
         assert!(!is_symlink(path));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_dir_following_respects_policy() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        assert!(!is_dir_following(&link, false));
+        assert!(is_dir_following(&link, true));
+        assert!(is_dir_following(&target, false));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_id_same_through_symlink() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        assert_eq!(file_id(&link), file_id(&target));
+        assert!(file_id(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn case_only_mismatch_ignores_identical_and_different_names() {
+        assert!(is_case_only_mismatch("My-Skill", "my-skill"));
+        assert!(is_case_only_mismatch("CAF\u{C9}", "caf\u{E9}"));
+        assert!(!is_case_only_mismatch("my-skill", "my-skill"));
+        assert!(!is_case_only_mismatch("my-skill", "your-skill"));
+    }
+
+    #[test]
+    fn on_disk_name_keeps_exact_spelling() {
+        let dir = tempdir().unwrap();
+        let skill = dir.path().join("My-Skill");
+        fs::create_dir(&skill).unwrap();
+        assert_eq!(on_disk_name(&skill).as_deref(), Some("My-Skill"));
+        assert_eq!(
+            on_disk_name(&dir.path().join("missing")).as_deref(),
+            Some("missing")
+        );
+        assert_eq!(on_disk_name(Path::new("/")), None);
+    }
+
+    /// On a case-insensitive filesystem, a path spelled `my-skill` opens the
+    /// directory stored as `My-Skill`, and `on_disk_name` reports the stored
+    /// spelling. On a case-sensitive one the path does not exist, and the
+    /// spelling in the path is all there is.
+    #[test]
+    fn on_disk_name_resolves_case_insensitive_spelling() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("My-Skill")).unwrap();
+        let typed = dir.path().join("my-skill");
+        let expected = if is_case_insensitive(dir.path()) {
+            assert!(typed.exists());
+            "My-Skill"
+        } else {
+            assert!(!typed.exists());
+            "my-skill"
+        };
+        assert_eq!(on_disk_name(&typed).as_deref(), Some(expected));
+    }
 }
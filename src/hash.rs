@@ -0,0 +1,37 @@
+//! SHA-256 content hashing.
+//!
+//! Every content fingerprint the crate records or compares (bundled shared
+//! resources, the validation cache, build manifests, and provenance) uses
+//! SHA-256, rendered as lowercase hex, so hashes of the same bytes agree
+//! across features and toolchain versions.
+
+use sha2::{Digest, Sha256};
+
+/// Lowercase hex SHA-256 digest of `bytes`.
+#[must_use]
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+/// Lowercase hex form of a finished digest.
+#[must_use]
+pub(crate) fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_hex_known_values() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Skill history: find the commit where a skill regressed.
+//!
+//! [`bisect`] walks the git history of a skill's `SKILL.md` with
+//! `git log --follow`, reads each revision with `git show <commit>:<path>`,
+//! and evaluates it in memory with [`score_content`](crate::score_content)
+//! or [`validate_content`](crate::validate_content). No worktree is checked
+//! out, and renames of the skill directory are followed.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::diagnostics::ValidationTarget;
+use crate::errors::{AigentError, Result};
+
+/// Default cap on the number of revisions [`bisect`] examines.
+pub const DEFAULT_MAX_REVISIONS: usize = 100;
+
+/// Separates commit records in the `git log` output.
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// Separates the hash from the subject in a commit record.
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Metric evaluated at each revision.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryMetric {
+    /// Quality score; a revision fails below the minimum score.
+    #[default]
+    Score,
+    /// Validation errors; a revision fails with any error.
+    Validate,
+}
+
+/// Options for [`bisect`].
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    /// Metric evaluated at each revision.
+    pub metric: HistoryMetric,
+    /// Only examine commits after this revision (`<since>..HEAD`).
+    pub since: Option<String>,
+    /// Examine at most this many of the most recent revisions.
+    pub max_revisions: usize,
+    /// Minimum passing score (score metric only; defaults to the maximum).
+    pub min: Option<u32>,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            metric: HistoryMetric::default(),
+            since: None,
+            max_revisions: DEFAULT_MAX_REVISIONS,
+            min: None,
+        }
+    }
+}
+
+/// One revision of a skill's `SKILL.md` and its metric value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Revision {
+    /// Full commit hash.
+    pub commit: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Path of `SKILL.md` at this commit, relative to the repository root.
+    pub path: String,
+    /// Score total, or the number of validation errors.
+    pub value: u32,
+    /// Whether the value meets the threshold.
+    pub passing: bool,
+}
+
+/// The first commit at which the metric crossed the failure threshold.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Regression {
+    /// Last passing revision before the regression.
+    pub before: Revision,
+    /// First failing revision.
+    pub after: Revision,
+}
+
+/// Result of [`bisect`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BisectResult {
+    /// Metric evaluated.
+    pub metric: HistoryMetric,
+    /// Minimum passing score, or the maximum number of validation errors (0).
+    pub threshold: u32,
+    /// Examined revisions, oldest first.
+    pub revisions: Vec<Revision>,
+    /// First pass-to-fail transition, if any.
+    pub regression: Option<Regression>,
+}
+
+impl BisectResult {
+    /// Whether the newest examined revision fails the threshold.
+    #[must_use]
+    pub fn is_failing(&self) -> bool {
+        self.revisions.last().is_some_and(|r| !r.passing)
+    }
+}
+
+/// Find the first commit at which `skill_dir`'s `SKILL.md` crossed the
+/// failure threshold of `opts.metric`.
+///
+/// Revisions are listed newest first by `git log --follow`, capped at
+/// `opts.max_revisions`, and evaluated oldest first. The regression is the
+/// first failing revision that follows a passing one.
+///
+/// # Errors
+///
+/// Returns an error if the directory has no `SKILL.md`, if `opts.since`
+/// starts with `-` (git would read it as an option), if `git` cannot be
+/// run or fails (for example outside a repository), or if the file has no
+/// committed history in the requested range.
+pub fn bisect(skill_dir: &Path, opts: &HistoryOptions) -> Result<BisectResult> {
+    let skill_md = crate::parser::find_skill_md(skill_dir).ok_or_else(|| AigentError::Parse {
+        message: format!("SKILL.md not found in {}", skill_dir.display()),
+    })?;
+    let file_name = skill_md
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "SKILL.md".to_string());
+
+    let mut args = vec![
+        "log".to_string(),
+        "--follow".to_string(),
+        "--diff-filter=d".to_string(),
+        format!("--max-count={}", opts.max_revisions),
+        format!("--format={RECORD_SEPARATOR}%H{FIELD_SEPARATOR}%s"),
+        "--name-only".to_string(),
+    ];
+    if let Some(since) = &opts.since {
+        if since.starts_with('-') {
+            return Err(AigentError::Parse {
+                message: format!("invalid revision: {since}"),
+            });
+        }
+        args.push(format!("{since}..HEAD"));
+    }
+    args.push("--".to_string());
+    args.push(file_name);
+    let log = git(skill_dir, &args)?;
+    let commits = parse_log(&log);
+    if commits.is_empty() {
+        return Err(AigentError::Parse {
+            message: format!("no committed history for {}", skill_md.display()),
+        });
+    }
+
+    let mut threshold = 0;
+    let mut revisions = Vec::with_capacity(commits.len());
+    for (commit, subject, path) in commits.into_iter().rev() {
+        let content = git(skill_dir, &["show".to_string(), format!("{commit}:{path}")])?;
+        let value;
+        (value, threshold) = evaluate(&content, opts.metric, opts.min);
+        let passing = match opts.metric {
+            HistoryMetric::Score => value >= threshold,
+            HistoryMetric::Validate => value <= threshold,
+        };
+        revisions.push(Revision {
+            commit,
+            subject,
+            path,
+            value,
+            passing,
+        });
+    }
+    let regression = revisions
+        .windows(2)
+        .find(|pair| pair[0].passing && !pair[1].passing)
+        .map(|pair| Regression {
+            before: pair[0].clone(),
+            after: pair[1].clone(),
+        });
+    Ok(BisectResult {
+        metric: opts.metric,
+        threshold,
+        revisions,
+        regression,
+    })
+}
+
+/// Human-readable summary of a [`BisectResult`].
+#[must_use]
+pub fn format_bisect_text(result: &BisectResult) -> String {
+    let metric = match result.metric {
+        HistoryMetric::Score => "score",
+        HistoryMetric::Validate => "validation errors",
+    };
+    let mut out = format!(
+        "Examined {} revision(s) ({metric}, threshold {})\n",
+        result.revisions.len(),
+        result.threshold
+    );
+    match (&result.regression, result.revisions.first()) {
+        (Some(reg), _) => {
+            out.push_str(&format!(
+                "Regressed in {} {}: {metric} {} -> {}\n",
+                short(&reg.after.commit),
+                reg.after.subject,
+                reg.before.value,
+                reg.after.value
+            ));
+            out.push_str(&format!(
+                "Last passing: {} {}\n",
+                short(&reg.before.commit),
+                reg.before.subject
+            ));
+        }
+        (None, Some(oldest)) if !oldest.passing => {
+            out.push_str(&format!(
+                "Already failing at the oldest examined revision {} ({metric} {})\n",
+                short(&oldest.commit),
+                oldest.value
+            ));
+        }
+        _ => out.push_str("No regression found\n"),
+    }
+    out
+}
+
+/// Value of `metric` for `content` and its threshold: `min` or the maximum
+/// score, or zero validation errors.
+fn evaluate(content: &str, metric: HistoryMetric, min: Option<u32>) -> (u32, u32) {
+    match metric {
+        HistoryMetric::Score => {
+            let result = crate::score_content(content, None);
+            (result.total, min.unwrap_or(result.max))
+        }
+        HistoryMetric::Validate => {
+            let errors = crate::validate_content(content, None, ValidationTarget::Standard)
+                .iter()
+                .filter(|d| d.is_error())
+                .count();
+            (u32::try_from(errors).unwrap_or(u32::MAX), 0)
+        }
+    }
+}
+
+/// Parse `git log --format=<RS>%H<US>%s --name-only` output into
+/// `(commit, subject, path)` records, newest first.
+fn parse_log(log: &str) -> Vec<(String, String, String)> {
+    log.split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let (commit, subject) = lines.next()?.split_once(FIELD_SEPARATOR)?;
+            let path = lines.map(str::trim).find(|l| !l.is_empty())?;
+            Some((commit.to_string(), subject.to_string(), path.to_string()))
+        })
+        .collect()
+}
+
+/// Abbreviated commit hash for display.
+fn short(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+/// Run `git` in `dir` and return its standard output.
+fn git(dir: &Path, args: &[String]) -> Result<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AigentError::Io(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args.first().map_or("", String::as_str),
+            stderr.trim()
+        ))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(commit: &str, value: u32, passing: bool) -> Revision {
+        Revision {
+            commit: commit.to_string(),
+            subject: format!("commit {commit}"),
+            path: "s/SKILL.md".to_string(),
+            value,
+            passing,
+        }
+    }
+
+    #[test]
+    fn parse_log_reads_commit_subject_and_path() {
+        let log = "\u{1e}aaa\u{1f}Rename skill\n\nnew/SKILL.md\n\u{1e}bbb\u{1f}Add: skill\n\nold/SKILL.md\n";
+        assert_eq!(
+            parse_log(log),
+            vec![
+                (
+                    "aaa".to_string(),
+                    "Rename skill".to_string(),
+                    "new/SKILL.md".to_string()
+                ),
+                (
+                    "bbb".to_string(),
+                    "Add: skill".to_string(),
+                    "old/SKILL.md".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_log_skips_records_without_a_path() {
+        assert!(parse_log("\u{1e}aaa\u{1f}Empty\n\n").is_empty());
+        assert!(parse_log("").is_empty());
+    }
+
+    #[test]
+    fn bisect_rejects_since_that_looks_like_an_option() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: my-skill\ndescription: Does things.\n---\nBody.\n",
+        )
+        .unwrap();
+        let opts = HistoryOptions {
+            since: Some("--output=/tmp/pwned".to_string()),
+            ..HistoryOptions::default()
+        };
+        let err = bisect(dir.path(), &opts).unwrap_err();
+        assert!(
+            matches!(&err, AigentError::Parse { message } if message.contains("invalid revision")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn evaluate_validate_counts_errors() {
+        let good = "---\nname: my-skill\ndescription: Does things. Use when needed.\n---\nBody.\n";
+        assert_eq!(evaluate(good, HistoryMetric::Validate, None), (0, 0));
+        let bad = "---\nname: My Skill\ndescription: Does things.\n---\nBody.\n";
+        let (errors, threshold) = evaluate(bad, HistoryMetric::Validate, None);
+        assert!(errors > 0);
+        assert_eq!(threshold, 0);
+    }
+
+    #[test]
+    fn format_reports_regression_before_and_after() {
+        let result = BisectResult {
+            metric: HistoryMetric::Score,
+            threshold: 90,
+            revisions: vec![
+                revision("1111111aaa", 95, true),
+                revision("2222222bbb", 80, false),
+            ],
+            regression: Some(Regression {
+                before: revision("1111111aaa", 95, true),
+                after: revision("2222222bbb", 80, false),
+            }),
+        };
+        assert_eq!(
+            format_bisect_text(&result),
+            "Examined 2 revision(s) (score, threshold 90)\n\
+             Regressed in 2222222 commit 2222222bbb: score 95 -> 80\n\
+             Last passing: 1111111 commit 1111111aaa\n"
+        );
+        assert!(result.is_failing());
+    }
+
+    #[test]
+    fn format_reports_failing_oldest_revision() {
+        let result = BisectResult {
+            metric: HistoryMetric::Validate,
+            threshold: 0,
+            revisions: vec![revision("1111111aaa", 2, false)],
+            regression: None,
+        };
+        assert_eq!(
+            format_bisect_text(&result),
+            "Examined 1 revision(s) (validation errors, threshold 0)\n\
+             Already failing at the oldest examined revision 1111111 (validation errors 2)\n"
+        );
+    }
+}
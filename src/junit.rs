@@ -0,0 +1,278 @@
+//! JUnit XML reports for CI systems.
+//!
+//! Serializes validation diagnostics and fixture test results into the JUnit
+//! XML format understood by common CI runners (GitHub Actions reporters,
+//! GitLab, Jenkins, Azure Pipelines). Validation maps each skill to a
+//! `<testcase>` with one `<failure>` per error; fixture tests map each skill
+//! to a `<testsuite>` and each query case to a `<testcase>`.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::diagnostics::Diagnostic;
+use crate::prompt::xml_escape;
+use crate::test_runner::TestSuiteResult;
+
+/// Render validation results as a JUnit XML report.
+///
+/// Each `(path, diagnostics)` entry becomes a `<testcase>` named after the
+/// path. Error diagnostics become `<failure>` elements (typed by their code);
+/// warnings and info diagnostics are listed in `<system-out>` so they stay
+/// visible without failing the case.
+#[must_use]
+pub fn validation_report(results: &[(PathBuf, Vec<Diagnostic>)]) -> String {
+    let tests = results.len();
+    let failures = results
+        .iter()
+        .filter(|(_, diags)| diags.iter().any(|d| d.is_error()))
+        .count();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"aigent validate\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\">"
+    );
+    let _ = writeln!(
+        out,
+        "  <testsuite name=\"aigent validate\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">"
+    );
+    for (path, diags) in results {
+        let name = xml_escape(&path.display().to_string());
+        let _ = write!(
+            out,
+            "    <testcase classname=\"aigent.validate\" name=\"{name}\""
+        );
+        if diags.is_empty() {
+            out.push_str("/>\n");
+            continue;
+        }
+        out.push_str(">\n");
+        for d in diags.iter().filter(|d| d.is_error()) {
+            let _ = writeln!(
+                out,
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                xml_escape(first_line(&d.message)),
+                d.code,
+                xml_escape(&failure_text(d)),
+            );
+        }
+        let notes: Vec<String> = diags
+            .iter()
+            .filter(|d| !d.is_error())
+            .map(|d| format!("{}: {d}", d.code))
+            .collect();
+        if !notes.is_empty() {
+            let _ = writeln!(
+                out,
+                "      <system-out>{}</system-out>",
+                xml_escape(&notes.join("\n"))
+            );
+        }
+        out.push_str("    </testcase>\n");
+    }
+    out.push_str("  </testsuite>\n");
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Render fixture test suite results as a JUnit XML report.
+///
+/// Each `(skill_dir, result)` entry becomes a `<testsuite>` named after the
+/// directory, and each executed query case a `<testcase>` named after its
+/// input. Failed cases carry a `<failure>` with the failure reason. Cases
+/// excluded by a filter are not executed and are not reported.
+#[must_use]
+pub fn test_report(results: &[(PathBuf, TestSuiteResult)]) -> String {
+    let tests: usize = results.iter().map(|(_, r)| r.passed + r.failed).sum();
+    let failures: usize = results.iter().map(|(_, r)| r.failed).sum();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"aigent test\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\">"
+    );
+    for (dir, result) in results {
+        let suite = xml_escape(&dir.display().to_string());
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{suite}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">",
+            result.passed + result.failed,
+            result.failed,
+        );
+        for case in &result.results {
+            let _ = write!(
+                out,
+                "    <testcase classname=\"{suite}\" name=\"{}\"",
+                xml_escape(&case.input)
+            );
+            if case.passed {
+                out.push_str("/>\n");
+                continue;
+            }
+            let reason = case.reason.as_deref().unwrap_or("test case failed");
+            let _ = writeln!(
+                out,
+                ">\n      <failure message=\"{}\" type=\"activation\">{}</failure>\n    </testcase>",
+                xml_escape(first_line(reason)),
+                xml_escape(&format!("{reason}\nscore: {:.2}", case.score)),
+            );
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Full failure body for an error diagnostic: message, field, and suggestion.
+fn failure_text(d: &Diagnostic) -> String {
+    let mut text = d.message.clone();
+    if let Some(field) = d.field {
+        let _ = write!(text, "\nfield: {field}");
+    }
+    if let Some(suggestion) = &d.suggestion {
+        let _ = write!(text, "\nsuggestion: {suggestion}");
+    }
+    text
+}
+
+/// First line of a possibly multi-line message, for `message` attributes.
+fn first_line(s: &str) -> &str {
+    s.lines().next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{Severity, E001, W001};
+    use crate::test_runner::TestCaseResult;
+
+    fn case(input: &str, passed: bool, reason: Option<&str>) -> TestCaseResult {
+        TestCaseResult {
+            input: input.to_string(),
+            should_match: true,
+            actual_match: passed,
+            score: if passed { 0.8 } else { 0.1 },
+            passed,
+            tags: Vec::new(),
+            expect_matches: None,
+            reason: reason.map(String::from),
+        }
+    }
+
+    // ── validation_report ────────────────────────────────────────────
+
+    #[test]
+    fn validation_report_counts_skills_and_failures() {
+        let results = vec![
+            (PathBuf::from("skills/good"), vec![]),
+            (
+                PathBuf::from("skills/bad"),
+                vec![
+                    Diagnostic::new(Severity::Error, E001, "name must not be empty")
+                        .with_field("name"),
+                    Diagnostic::new(Severity::Warning, W001, "unexpected metadata field: 'x'"),
+                ],
+            ),
+        ];
+        let xml = validation_report(&results);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains("tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<testcase classname=\"aigent.validate\" name=\"skills/good\"/>"));
+        assert!(xml.contains(
+            "<failure message=\"name must not be empty\" type=\"E001\">name must not be empty\nfield: name</failure>"
+        ));
+        assert!(xml.contains(
+            "<system-out>W001: warning: unexpected metadata field: &apos;x&apos;</system-out>"
+        ));
+    }
+
+    #[test]
+    fn validation_report_one_failure_per_error() {
+        let results = vec![(
+            PathBuf::from("s"),
+            vec![
+                Diagnostic::new(Severity::Error, E001, "first"),
+                Diagnostic::new(Severity::Error, E001, "second"),
+            ],
+        )];
+        let xml = validation_report(&results);
+        assert_eq!(xml.matches("<failure ").count(), 2);
+        assert!(xml.contains("tests=\"1\" failures=\"1\""));
+    }
+
+    #[test]
+    fn validation_report_message_attribute_uses_first_line() {
+        let results = vec![(
+            PathBuf::from("s"),
+            vec![Diagnostic::new(
+                Severity::Error,
+                E001,
+                "name contains invalid character: '<'\n    my<skill",
+            )],
+        )];
+        let xml = validation_report(&results);
+        assert!(xml.contains("message=\"name contains invalid character: &apos;&lt;&apos;\""));
+        assert!(xml.contains("\n    my&lt;skill</failure>"));
+    }
+
+    #[test]
+    fn escape_drops_invalid_xml_characters() {
+        assert_eq!(xml_escape("a\u{1}b\tc"), "ab\tc");
+        assert_eq!(xml_escape("<&>"), "&lt;&amp;&gt;");
+    }
+
+    #[test]
+    fn validation_report_parses_as_xml() {
+        let results = vec![(
+            PathBuf::from("skills/a&b <c>"),
+            vec![
+                Diagnostic::new(Severity::Error, E001, "bad \"name\" & <tag>\u{1}")
+                    .with_suggestion("Use 'x' > 'y'"),
+                Diagnostic::new(Severity::Warning, W001, "it's ]]> odd"),
+            ],
+        )];
+        let xml = validation_report(&results);
+        let doc = roxmltree::Document::parse(&xml).expect("report must parse");
+        let case = doc
+            .descendants()
+            .find(|n| n.has_tag_name("testcase"))
+            .unwrap();
+        assert_eq!(case.attribute("name"), Some("skills/a&b <c>"));
+        let failure = case.children().find(|n| n.has_tag_name("failure")).unwrap();
+        assert_eq!(failure.attribute("message"), Some("bad \"name\" & <tag>"));
+    }
+
+    // ── test_report ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_report_maps_cases_to_testcases() {
+        let result = TestSuiteResult {
+            passed: 1,
+            failed: 1,
+            filtered_out: 0,
+            filter: None,
+            tags: Vec::new(),
+            duration_ms: 0,
+            results: vec![
+                case("process pdf", true, None),
+                case("bake a cake", false, Some("expected match but got none")),
+            ],
+        };
+        let xml = test_report(&[(PathBuf::from("skills/pdf"), result)]);
+        assert!(xml.contains("<testsuites name=\"aigent test\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<testsuite name=\"skills/pdf\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<testcase classname=\"skills/pdf\" name=\"process pdf\"/>"));
+        assert!(xml.contains(
+            "<failure message=\"expected match but got none\" type=\"activation\">expected match but got none\nscore: 0.10</failure>"
+        ));
+    }
+
+    #[test]
+    fn test_report_empty_results_is_well_formed() {
+        let xml = test_report(&[]);
+        assert!(xml.contains("tests=\"0\" failures=\"0\""));
+        assert!(xml.ends_with("</testsuites>\n"));
+    }
+}
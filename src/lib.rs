@@ -25,78 +25,155 @@
 pub mod assembler;
 /// Skill builder: deterministic and LLM-enhanced skill generation.
 pub mod builder;
+/// On-disk cache of validation results, keyed by content hash.
+pub mod cache;
+/// Removal of generated artifacts: backups, caches, and saved drafts.
+pub mod clean;
 /// Cross-skill conflict detection for skill collections.
 pub mod conflict;
+/// Shared frontmatter defaults from `_defaults.yml`.
+pub mod defaults;
 /// Structured diagnostics for validation, linting, and error reporting.
 pub mod diagnostics;
+/// Skill catalog generation in markdown and JSON.
+pub mod docgen;
 /// Error types for skill operations.
 pub mod errors;
+/// C ABI for validating, linting, and scoring in-memory `SKILL.md` content.
+#[cfg(feature = "ffi")]
+pub mod ffi;
 /// Auto-fix application for fixable diagnostics.
 pub mod fixer;
 /// SKILL.md formatting: canonical key ordering and markdown cleanup.
 pub mod formatter;
 /// Symlink-safe filesystem helpers.
 pub(crate) mod fs_util;
+/// SHA-256 content hashing shared by every recorded fingerprint.
+pub(crate) mod hash;
+/// Git history of a skill: find the commit where it regressed.
+pub mod history;
+/// JUnit XML reports for validation and fixture test results.
+pub mod junit;
 /// Semantic lint checks for skill quality improvement.
 pub mod linter;
+/// Minimal Language Server for live `SKILL.md` diagnostics in editors.
+#[cfg(feature = "lsp")]
+pub mod lsp;
 /// Data model for SKILL.md frontmatter properties.
 pub mod models;
 /// SKILL.md frontmatter parser.
 pub mod parser;
+/// Deploy-time `${NAME}` placeholders in `SKILL.md` files.
+pub mod placeholders;
 /// Plugin ecosystem validation: manifest, hooks, agents, commands.
 pub mod plugin;
 /// Multi-format prompt generation for LLM injection.
 pub mod prompt;
+/// Readability metrics for skill bodies.
+pub mod readability;
+/// Terminal styling for text reports.
+pub mod reporting;
 /// Quality scoring for skill best-practices compliance.
 pub mod scorer;
 /// Directory structure validation for skill packages.
 pub mod structure;
+/// Description improvement suggestions from probe queries.
+pub mod suggest;
 /// Fixture-based skill testing: run test suites defined in `tests.yml`.
 pub mod test_runner;
 /// Skill tester and previewer for evaluation-driven development.
 pub mod tester;
+/// Trigger-phrase extraction from skill descriptions.
+pub mod triggers;
 /// Skill directory and metadata validator.
 pub mod validator;
+/// Workspace configuration (`aigent.toml`) for multi-collection repositories.
+pub mod workspace;
 
 // Re-export key types at crate root for convenience.
-pub use assembler::{assemble_plugin, AssembleOptions, AssembleResult, AssembleWarning};
-pub use conflict::{detect_conflicts, detect_conflicts_with_threshold};
+pub use assembler::{
+    assemble_plugin, AssembleOptions, AssembleResult, AssembleWarning, AssembledFile,
+    AssembledSkill,
+};
+pub use cache::{validate_cached, validation_cache_key, ValidationCache, CACHE_DIR};
+pub use clean::{clean_artifacts, Artifact, ArtifactKind, CleanOptions, CleanReport};
+pub use conflict::{
+    detect_activation_overlap, detect_conflicts, detect_conflicts_with,
+    detect_conflicts_with_threshold, ConflictOptions, ConflictScope,
+};
 #[doc(inline)]
-pub use diagnostics::{Diagnostic, Severity, ValidationTarget};
+pub use diagnostics::{Diagnostic, DiagnosticCode, Severity, ValidationTarget};
+pub use docgen::{catalog_entries, BodyStats, CatalogEntry};
 #[doc(inline)]
 pub use errors::{AigentError, Result};
-pub use fixer::apply_fixes;
-pub use formatter::{diff_skill, format_content, format_skill, FormatResult};
+pub use fixer::{
+    apply_fixes, apply_fixes_interactive, extract_reference, InteractiveFixResult,
+    ReferenceExtraction, REFERENCE_FILE,
+};
+pub use formatter::{
+    diff_skill, format_body, format_content, format_content_with, format_skill, format_skill_with,
+    FormatOptions, FormatResult,
+};
 pub use fs_util::is_regular_file;
-pub use linter::lint;
+pub use history::{bisect, BisectResult, HistoryMetric, HistoryOptions, Regression, Revision};
+pub use linter::{lint, lint_with_options, LintOptions};
 #[doc(inline)]
 pub use models::SkillProperties;
 pub use parser::{
-    find_skill_md, parse_frontmatter, parse_optional_frontmatter, read_body, read_properties,
-    CLAUDE_CODE_KEYS, KNOWN_KEYS,
+    find_skill_md, is_frontmatter_delimiter, parse_frontmatter, parse_optional_frontmatter,
+    parse_properties, read_body, read_properties, split_frontmatter, CLAUDE_CODE_KEYS, KNOWN_KEYS,
+    MAX_FILE_SIZE,
 };
+pub use placeholders::{find_placeholders, PlaceholderPolicy};
 pub use plugin::{
-    validate_agent, validate_command, validate_cross_component, validate_hooks, validate_manifest,
-    PluginManifest,
+    check_registry_collisions, declared_schema, read_plugin_registry, validate_agent,
+    validate_command, validate_command_with, validate_cross_component, validate_hooks,
+    validate_hooks_with, validate_manifest, PluginManifest, PluginSchema,
 };
 pub use prompt::{
-    collect_skills, collect_skills_verbose, estimate_tokens, format_budget, format_entries,
-    to_prompt, to_prompt_format, PromptFormat, SkillEntry,
+    collect_skills, collect_skills_labeled, collect_skills_verbose, collect_skills_verbose_with,
+    collect_skills_with, estimate_tokens, format_budget, format_entries, format_entries_for,
+    format_group_budget, format_groups, format_tool_requirements, to_prompt, to_prompt_format,
+    tool_requirements, write_entries, write_entries_for, write_groups, write_groups_for,
+    CollectOptions, PromptFormat, PromptTarget, Provenance, SkillEntry, SkillGroup,
+    ToolRequirement, DEFAULT_GROUP,
+};
+pub use readability::{body_metrics, implies_workflow, metrics_diagnostic, BodyMetrics};
+pub use reporting::{ColorChoice, Style, Styler};
+pub use scorer::{
+    assess_risk, diff_scores, score, score_content, score_diff, score_skills,
+    summarize as summarize_scores, CheckDelta, Risk, ScoreDiff, ScoreResult, ScoreSummary,
+    SkillScore,
+};
+pub use structure::{
+    validate_index, validate_structure, validate_structure_with, StructureOptions,
+};
+pub use suggest::{
+    format_suggestions, llm_revise_description, suggest_description_improvements, DescriptionDraft,
+    DraftSource, SuggestionReport, TermSuggestion,
 };
-pub use scorer::{score, ScoreResult};
-pub use structure::validate_structure;
 pub use test_runner::{
-    format_text as format_test_suite, generate_fixture, run_test_suite, MatchStrength,
-    TestSuiteResult,
+    append_recorded_case, format_summary as format_test_summary, format_text as format_test_suite,
+    generate_fixture, positive_queries, recorded_cases, run_test_suite, MatchStrength,
+    RecordedCase, SuiteTotals, TestOptions, TestSuiteResult, RECORDED_MARKER,
+};
+pub use tester::{
+    test_skill, test_skill_with, test_skill_with_weights, MatchOptions, ScoreWeights, TestResult,
+    TokenBreakdown,
 };
-pub use tester::{test_skill, TestResult};
+pub use triggers::{split_description, trigger_clauses, DescriptionParts, TriggerClause};
 pub use validator::{
-    discover_skills, discover_skills_verbose, known_keys_for, validate, validate_metadata,
-    validate_metadata_with_target, validate_with_target, DiscoveryWarning,
+    discover_skills, discover_skills_verbose, discover_skills_verbose_with, discover_skills_with,
+    is_draft, known_keys_for, validate, validate_content, validate_content_in_dir,
+    validate_content_with_defaults, validate_content_with_policy, validate_frontmatter_str,
+    validate_metadata, validate_metadata_block, validate_metadata_with_target,
+    validate_superseded_by, validate_with_policy, validate_with_target, DiscoveryOptions,
+    DiscoveryWarning, BODY_MAX_TOKENS, DRAFT_MARKER, FILE_SIZE_WARNING_PERCENT, METADATA_MAX_BYTES,
 };
+pub use workspace::{load_workspace, parse_workspace, CollectionConfig, WorkspaceConfig};
 
 #[doc(inline)]
 pub use builder::{
-    assess_clarity, build_skill, derive_name, init_skill, interactive_build, BuildResult,
-    ClarityAssessment, LlmProvider, SkillSpec, SkillTemplate,
+    assess_clarity, build_skill, derive_name, derive_name_with, init_skill, interactive_build,
+    mark_draft, BuildResult, ClarityAssessment, LlmProvider, SkillDraft, SkillSpec, SkillTemplate,
 };
@@ -2,27 +2,31 @@
 //!
 //! Lint checks produce `Severity::Info` diagnostics — they never cause
 //! validation failure. They detect patterns that deviate from Anthropic
-//! best practices for agent skill definitions.
+//! best practices for agent skill definitions. The exception is W004
+//! (leftover placeholder text), a warning because it should never ship.
+//!
+//! Each check is a rule with one stable code (see [`lint_codes`](crate::linter::lint_codes)).
+//! [`LintOptions`] selects which rules run.
 
+use std::collections::HashSet;
 use std::sync::LazyLock;
 
 use regex::Regex;
 
-use crate::diagnostics::{Diagnostic, Severity};
+use crate::conflict::{jaccard_from_sets, tokenize};
+use crate::diagnostics::{Diagnostic, DiagnosticCode, Severity};
 use crate::models::SkillProperties;
+use crate::tester::tokenize_with;
+
+// ── Info codes ─────────────────────────────────────────────────────────
+
+pub use crate::diagnostics::{
+    I001, I002, I003, I004, I005, I007, I008, I009, I010, I012, I013, I014, I015, I017,
+};
 
-// ── Info code constants ────────────────────────────────────────────────
+// ── Warning codes ──────────────────────────────────────────────────────
 
-/// Description uses first or second person.
-pub const I001: &str = "I001";
-/// Description lacks a trigger phrase ("Use when…").
-pub const I002: &str = "I002";
-/// Name does not use gerund form.
-pub const I003: &str = "I003";
-/// Name is overly generic.
-pub const I004: &str = "I004";
-/// Description is overly vague.
-pub const I005: &str = "I005";
+pub use crate::diagnostics::W004;
 
 /// Generic name segments that indicate a non-descriptive skill name.
 const GENERIC_SEGMENTS: &[&str] = &[
@@ -31,34 +35,162 @@ const GENERIC_SEGMENTS: &[&str] = &[
 
 /// Trigger phrases that indicate when a skill should be used.
 ///
-/// Shared across linter, tester, and upgrade modules to ensure consistent
-/// trigger phrase detection.
-pub const TRIGGER_PHRASES: &[&str] = &[
-    "use when",
-    "use for",
-    "use this",
-    "invoke when",
-    "activate when",
+/// Re-exported from [`crate::triggers`], which owns trigger detection.
+pub use crate::triggers::TRIGGER_PHRASES;
+
+/// Markers of unfinished template text in a skill body.
+///
+/// Word markers match case-sensitively as whole words, so "TODO" fires but
+/// "a todo list" does not. Markers starting with `<` match anywhere,
+/// ignoring ASCII case.
+pub const PLACEHOLDER_MARKERS: &[&str] = &["TODO", "FIXME", "<placeholder>"];
+
+/// Interrogative words that mark a description opening as a question.
+///
+/// "When" is deliberately absent: "When the user asks…" is declarative.
+const QUESTION_WORDS: &[&str] = &[
+    "what", "how", "why", "where", "which", "who", "whose", "need", "want", "can", "could",
+    "should", "would", "do", "does", "did", "is", "are",
+];
+
+/// Word-set Jaccard similarity at or above which a description counts as a
+/// copy of the body's opening line (I010).
+const DUPLICATE_OPENING_THRESHOLD: f64 = 0.8;
+
+/// Words that suggest a description was cut off when it ends with one
+/// (I015).
+const DANGLING_CONJUNCTIONS: &[&str] = &[
+    "and", "or", "but", "nor", "yet", "so", "because", "although", "while", "if", "unless", "when",
+    "then",
 ];
 
+/// Bodies with fewer words than this are too short to judge terminology
+/// (I017).
+const MIN_TERMINOLOGY_WORDS: usize = 20;
+
 /// Regex matching first/second person pronouns at word boundaries.
 static PERSON_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?i)\b(I|me|my|you|your)\b").expect("person pronoun regex must compile")
 });
 
+/// A lint check and the one code it reports.
+struct LintRule {
+    code: DiagnosticCode,
+    check: fn(&SkillProperties, &str) -> Vec<Diagnostic>,
+}
+
+/// Every lint rule, in reporting order.
+const RULES: &[LintRule] = &[
+    LintRule {
+        code: I001,
+        check: |p, _| lint_description_person(&p.description),
+    },
+    LintRule {
+        code: I002,
+        check: |p, _| lint_description_trigger(&p.description),
+    },
+    LintRule {
+        code: I003,
+        check: |p, _| lint_name_gerund(&p.name),
+    },
+    LintRule {
+        code: I004,
+        check: |p, _| lint_name_generic(&p.name),
+    },
+    LintRule {
+        code: I005,
+        check: |p, _| lint_description_vague(&p.description),
+    },
+    LintRule {
+        code: I008,
+        check: |p, _| lint_description_question(&p.description),
+    },
+    LintRule {
+        code: I012,
+        check: |p, _| lint_description_double_spaces(&p.description),
+    },
+    LintRule {
+        code: I013,
+        check: |p, _| lint_description_padding(&p.description),
+    },
+    LintRule {
+        code: I014,
+        check: |p, _| lint_description_unbalanced(&p.description),
+    },
+    LintRule {
+        code: I015,
+        check: |p, _| lint_description_dangling_end(&p.description),
+    },
+    LintRule {
+        code: W004,
+        check: |_, body| lint_body_placeholders(body),
+    },
+    LintRule {
+        code: I009,
+        check: |_, body| lint_body_empty(body),
+    },
+    LintRule {
+        code: I010,
+        check: |p, body| lint_description_duplicates_body(&p.description, body),
+    },
+    LintRule {
+        code: I017,
+        check: |p, body| lint_body_name_terms(&p.name, body),
+    },
+    LintRule {
+        code: I007,
+        check: |p, _| lint_deprecation_removal_date(p),
+    },
+];
+
+/// Which lint rules run.
+///
+/// The default runs every rule. `enable` restricts the run to the listed
+/// codes; `disable` then removes codes from whatever is enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintOptions {
+    /// Run only these rules, if set.
+    pub enable: Option<Vec<DiagnosticCode>>,
+    /// Skip these rules.
+    pub disable: Vec<DiagnosticCode>,
+}
+
+impl LintOptions {
+    /// Whether the rule reporting `code` runs under these options.
+    #[must_use]
+    pub fn is_enabled(&self, code: DiagnosticCode) -> bool {
+        self.enable.as_ref().is_none_or(|only| only.contains(&code))
+            && !self.disable.contains(&code)
+    }
+}
+
+/// Codes of every lint rule, in reporting order.
+#[must_use]
+pub fn lint_codes() -> Vec<DiagnosticCode> {
+    RULES.iter().map(|rule| rule.code).collect()
+}
+
 /// Run all semantic lint checks on parsed skill properties and body.
 ///
-/// Returns a list of `Severity::Info` diagnostics. These never cause
-/// validation failure — they are suggestions for improving skill quality.
+/// Returns `Severity::Info` diagnostics, which never cause validation
+/// failure, plus a W004 warning for each body line with placeholder text.
+#[must_use]
+pub fn lint(properties: &SkillProperties, body: &str) -> Vec<Diagnostic> {
+    lint_with_options(properties, body, &LintOptions::default())
+}
+
+/// Run the lint rules selected by `options`; see [`lint`].
 #[must_use]
-pub fn lint(properties: &SkillProperties, _body: &str) -> Vec<Diagnostic> {
-    let mut diags = Vec::new();
-    diags.extend(lint_description_person(&properties.description));
-    diags.extend(lint_description_trigger(&properties.description));
-    diags.extend(lint_name_gerund(&properties.name));
-    diags.extend(lint_name_generic(&properties.name));
-    diags.extend(lint_description_vague(&properties.description));
-    diags
+pub fn lint_with_options(
+    properties: &SkillProperties,
+    body: &str,
+    options: &LintOptions,
+) -> Vec<Diagnostic> {
+    RULES
+        .iter()
+        .filter(|rule| options.is_enabled(rule.code))
+        .flat_map(|rule| (rule.check)(properties, body))
+        .collect()
 }
 
 /// I001: Check if description uses first or second person.
@@ -84,9 +216,7 @@ fn lint_description_person(description: &str) -> Vec<Diagnostic> {
 /// Good descriptions include guidance on when to activate the skill,
 /// such as "Use when working with PDF files."
 fn lint_description_trigger(description: &str) -> Vec<Diagnostic> {
-    let lower = description.to_lowercase();
-    let has_trigger = TRIGGER_PHRASES.iter().any(|p| lower.contains(p));
-    if has_trigger {
+    if crate::triggers::has_trigger(description) {
         vec![]
     } else {
         vec![
@@ -152,6 +282,372 @@ fn lint_description_vague(description: &str) -> Vec<Diagnostic> {
     }
 }
 
+/// I008: Check if the description is phrased as a question.
+///
+/// Only a trailing `?` or a leading interrogative word counts, so a
+/// declarative description that quotes a question mid-text is not flagged.
+fn lint_description_question(description: &str) -> Vec<Diagnostic> {
+    let trimmed = description.trim();
+    let first_word = trimmed
+        .split_whitespace()
+        .next()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .unwrap_or_default();
+    if trimmed.ends_with('?') || QUESTION_WORDS.contains(&first_word.as_str()) {
+        vec![
+            Diagnostic::new(Severity::Info, I008, "description is phrased as a question")
+                .with_field("description")
+                .with_suggestion(
+                    "Rephrase as a statement — e.g., 'Processes PDFs. Use when…' not 'Need to process PDFs?'",
+                ),
+        ]
+    } else {
+        vec![]
+    }
+}
+
+/// W004: Check the body for leftover TODO/FIXME/placeholder text.
+///
+/// Flags every line containing a [`PLACEHOLDER_MARKERS`] entry, including
+/// lines inside code fences: shipped instructions should not contain
+/// placeholders.
+fn lint_body_placeholders(body: &str) -> Vec<Diagnostic> {
+    body.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let marker = PLACEHOLDER_MARKERS
+                .iter()
+                .find(|m| contains_marker(line, m))?;
+            Some(
+                Diagnostic::new(
+                    Severity::Warning,
+                    W004,
+                    format!(
+                        "body line {} contains placeholder marker '{marker}': {}",
+                        i + 1,
+                        line.trim()
+                    ),
+                )
+                .with_field("body")
+                .with_suggestion("Replace the placeholder with real instructions"),
+            )
+        })
+        .collect()
+}
+
+/// I009: Check that the body has content beyond headings.
+///
+/// A skill whose body is empty or only a title is usually an abandoned
+/// stub. This is the short-body counterpart of the W002 body-length warning.
+fn lint_body_empty(body: &str) -> Vec<Diagnostic> {
+    let mut lines = body.lines().map(str::trim).filter(|l| !l.is_empty());
+    let message = match lines.clone().next() {
+        None => "body is empty",
+        Some(_) if lines.all(|l| l.starts_with('#')) => "body has only headings and no content",
+        Some(_) => return vec![],
+    };
+    vec![Diagnostic::new(Severity::Info, I009, message)
+        .with_field("body")
+        .with_suggestion(
+            "Add instructions to the body, or remove the skill if it is an unused stub",
+        )]
+}
+
+/// I010: Check if the description repeats the body's opening line.
+///
+/// Compares the description to the first non-empty body line that is not a
+/// heading (a title heading usually precedes the opening sentence). The
+/// description is what an agent reads to decide whether to activate the
+/// skill, so copying the body's first sentence wastes it.
+fn lint_description_duplicates_body(description: &str, body: &str) -> Vec<Diagnostic> {
+    let Some(opening) = body
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#'))
+    else {
+        return vec![];
+    };
+    let description_words = tokenize(description);
+    let opening_words = tokenize(opening);
+    if description_words.is_empty()
+        || jaccard_from_sets(&description_words, &opening_words) < DUPLICATE_OPENING_THRESHOLD
+    {
+        return vec![];
+    }
+    vec![Diagnostic::new(
+        Severity::Info,
+        I010,
+        "description repeats the first line of the body",
+    )
+    .with_field("description")
+    .with_suggestion(
+        "Focus the description on when to use the skill (activation triggers), not what the body already says",
+    )]
+}
+
+/// I017: Check that the body mentions at least one key term of the name.
+///
+/// Key terms are the name's hyphen-separated segments, minus gerunds
+/// ("processing"), generic segments ("helper"), and stopwords. Both sides
+/// are stemmed, so "pdfs" in the name matches "PDF" in the body. Bodies
+/// under [`MIN_TERMINOLOGY_WORDS`] words are skipped.
+fn lint_body_name_terms(name: &str, body: &str) -> Vec<Diagnostic> {
+    let segments: Vec<&str> = name
+        .split('-')
+        .filter(|s| !s.ends_with("ing") && !GENERIC_SEGMENTS.contains(s))
+        .collect();
+    let terms = tokenize_with(&segments.join(" "), true);
+    let spaced: String = body
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    if terms.is_empty() || spaced.split_whitespace().count() < MIN_TERMINOLOGY_WORDS {
+        return vec![];
+    }
+    let body_terms: HashSet<String> = tokenize_with(&spaced, true).into_iter().collect();
+    if terms.iter().any(|t| body_terms.contains(t)) {
+        return vec![];
+    }
+    let key_terms: Vec<String> = segments
+        .iter()
+        .filter(|s| !tokenize_with(s, false).is_empty())
+        .map(|s| format!("'{s}'"))
+        .collect();
+    vec![Diagnostic::new(
+        Severity::Info,
+        I017,
+        format!(
+            "body never mentions the name's key term(s): {}",
+            key_terms.join(", ")
+        ),
+    )
+    .with_field("name")
+    .with_suggestion(
+        "Use the name's terminology in the body, or rename the skill to match the body",
+    )]
+}
+
+/// I012: Check the description for runs of consecutive spaces.
+///
+/// Spaces inside backtick code spans are left alone, since they may be
+/// part of a quoted command or output.
+fn lint_description_double_spaces(description: &str) -> Vec<Diagnostic> {
+    let has_run = code_span_segments(description)
+        .iter()
+        .any(|(code, text)| !code && text.contains("  "));
+    if has_run {
+        vec![Diagnostic::new(
+            Severity::Info,
+            I012,
+            "description contains consecutive spaces",
+        )
+        .with_field("description")
+        .with_suggestion("Collapse repeated spaces into one")]
+    } else {
+        vec![]
+    }
+}
+
+/// I013: Check the description for leading or trailing whitespace.
+///
+/// Only reachable through a quoted YAML value, since plain scalars are
+/// trimmed by the parser.
+fn lint_description_padding(description: &str) -> Vec<Diagnostic> {
+    if description.trim() == description || description.trim().is_empty() {
+        return vec![];
+    }
+    vec![Diagnostic::new(
+        Severity::Info,
+        I013,
+        "description has leading or trailing whitespace",
+    )
+    .with_field("description")
+    .with_suggestion("Remove the surrounding whitespace")]
+}
+
+/// I014: Check the description for unbalanced brackets and quotes.
+///
+/// Code spans are ignored. Apostrophes — a straight or curly single quote
+/// between two letters or digits, as in "don't" — are not quotes. Single
+/// quotes are only flagged when an opening quote is never closed, so a
+/// trailing possessive ("the users' files") passes.
+fn lint_description_unbalanced(description: &str) -> Vec<Diagnostic> {
+    let Some(what) = first_unbalanced(description) else {
+        return vec![];
+    };
+    vec![Diagnostic::new(
+        Severity::Info,
+        I014,
+        format!("description has unbalanced {what}"),
+    )
+    .with_field("description")
+    .with_suggestion("Close or remove the unmatched character")]
+}
+
+/// I015: Check whether the description ends mid-sentence.
+///
+/// A trailing comma or conjunction ("…for reports and") usually means the
+/// description was truncated while editing.
+fn lint_description_dangling_end(description: &str) -> Vec<Diagnostic> {
+    let trimmed = description.trim_end();
+    let dangling = if trimmed.ends_with(',') {
+        Some("a comma".to_string())
+    } else {
+        trimmed
+            .rsplit(|c: char| c.is_whitespace())
+            .next()
+            .map(str::to_lowercase)
+            .filter(|w| DANGLING_CONJUNCTIONS.contains(&w.as_str()))
+            .map(|w| format!("\"{w}\""))
+    };
+    let Some(dangling) = dangling else {
+        return vec![];
+    };
+    vec![Diagnostic::new(
+        Severity::Info,
+        I015,
+        format!("description ends with {dangling} and may be truncated"),
+    )
+    .with_field("description")
+    .with_suggestion("Finish the sentence or remove the trailing word")]
+}
+
+/// Split `text` into `(is_code, text)` segments at backtick code spans.
+///
+/// Code segments exclude their backticks. An unmatched backtick starts no
+/// span and stays in the surrounding prose.
+fn code_span_segments(text: &str) -> Vec<(bool, &str)> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('`') {
+        let Some(len) = rest[open + 1..].find('`') else {
+            break;
+        };
+        segments.push((false, &rest[..open]));
+        segments.push((true, &rest[open + 1..open + 1 + len]));
+        rest = &rest[open + 1 + len + 1..];
+    }
+    segments.push((false, rest));
+    segments
+}
+
+/// Collapse runs of spaces in `description` outside code spans (the I012
+/// fix).
+pub(crate) fn collapse_description_spaces(description: &str) -> String {
+    let mut out = String::with_capacity(description.len());
+    for (code, text) in code_span_segments(description) {
+        if code {
+            out.push('`');
+            out.push_str(text);
+            out.push('`');
+            continue;
+        }
+        let mut previous_space = false;
+        for c in text.chars() {
+            if c == ' ' && previous_space {
+                continue;
+            }
+            previous_space = c == ' ';
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Name the first unbalanced bracket or quote kind in `text`, ignoring
+/// code spans, or `None` if everything is balanced.
+fn first_unbalanced(text: &str) -> Option<&'static str> {
+    let prose: Vec<char> = code_span_segments(text)
+        .into_iter()
+        .filter(|(code, _)| !code)
+        .flat_map(|(_, t)| t.chars().chain(std::iter::once(' ')))
+        .collect();
+
+    let mut stack: Vec<char> = Vec::new();
+    for &c in &prose {
+        match c {
+            '(' | '[' | '{' => stack.push(c),
+            ')' | ']' | '}' => {
+                let open = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if stack.pop() != Some(open) {
+                    return Some(bracket_name(c));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(&open) = stack.last() {
+        return Some(bracket_name(open));
+    }
+
+    let count = |target: char| prose.iter().filter(|&&c| c == target).count();
+    if count('"') % 2 != 0 || count('\u{201C}') != count('\u{201D}') {
+        return Some("double quotes");
+    }
+
+    let (mut opened, mut closed) = (0usize, 0usize);
+    for (i, &c) in prose.iter().enumerate() {
+        if c != '\'' && c != '\u{2018}' && c != '\u{2019}' {
+            continue;
+        }
+        let before = i.checked_sub(1).map(|j| prose[j]);
+        let after = prose.get(i + 1).copied();
+        let word_before = before.is_some_and(char::is_alphanumeric);
+        let word_after = after.is_some_and(char::is_alphanumeric);
+        if word_before && word_after {
+            // Apostrophe inside a word: "don't", "it’s".
+            continue;
+        }
+        if c == '\u{2018}' || (c == '\'' && !word_before) {
+            opened += 1;
+        } else {
+            closed += 1;
+        }
+    }
+    (opened > closed).then_some("single quotes")
+}
+
+/// Human-readable name for the bracket kind of `c`.
+fn bracket_name(c: char) -> &'static str {
+    match c {
+        '(' | ')' => "parentheses",
+        '[' | ']' => "square brackets",
+        _ => "braces",
+    }
+}
+
+/// I007: Check that a deprecated skill records when it will be removed.
+fn lint_deprecation_removal_date(properties: &SkillProperties) -> Vec<Diagnostic> {
+    if properties.is_deprecated() && properties.removal_date().is_none() {
+        vec![
+            Diagnostic::new(Severity::Info, I007, "deprecated skill has no removal date")
+                .with_field("metadata")
+                .with_suggestion("Add `removal-date: YYYY-MM-DD` under `metadata`"),
+        ]
+    } else {
+        vec![]
+    }
+}
+
+/// Returns `true` if `line` contains `marker` (see [`PLACEHOLDER_MARKERS`]).
+fn contains_marker(line: &str, marker: &str) -> bool {
+    if marker.starts_with('<') {
+        return line.to_ascii_lowercase().contains(marker);
+    }
+    line.match_indices(marker).any(|(i, _)| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + marker.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +730,21 @@ mod tests {
         assert!(diags.is_empty(), "expected no I002, got: {diags:?}");
     }
 
+    #[test]
+    fn i002_quoted_trigger_phrase_does_not_count() {
+        let diags = lint_description_trigger(r#"Explains phrases like "use when" in docs."#);
+        assert!(
+            diags.iter().any(|d| d.code == I002),
+            "expected I002, got: {diags:?}"
+        );
+    }
+
+    #[test]
+    fn i002_trailing_conditional_counts() {
+        let diags = lint_description_trigger("Formats Rust code, when the user asks for fixes.");
+        assert!(diags.is_empty(), "expected no I002, got: {diags:?}");
+    }
+
     #[test]
     fn i002_use_for_trigger() {
         let diags = lint_description_trigger("Use for processing large datasets");
@@ -334,6 +845,34 @@ mod tests {
         assert!(diags.is_empty(), "expected no I005, got: {diags:?}");
     }
 
+    // ── I009: Empty body ───────────────────────────────────────────────
+
+    #[test]
+    fn i009_empty_body_fires() {
+        for body in ["", "\n\n  \n"] {
+            let diags = lint_body_empty(body);
+            assert_eq!(diags.len(), 1, "body {body:?}: {diags:?}");
+            assert_eq!(diags[0].code, I009);
+            assert_eq!(diags[0].message, "body is empty");
+        }
+    }
+
+    #[test]
+    fn i009_heading_only_body_fires() {
+        let diags = lint_body_empty("# Title\n");
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].message, "body has only headings and no content");
+
+        let diags = lint_body_empty("# Title\n\n## Usage\n\n");
+        assert_eq!(diags.len(), 1);
+    }
+
+    #[test]
+    fn i009_paragraph_is_clean() {
+        let diags = lint_body_empty("# Title\n\nRun the converter on each input file.\n");
+        assert!(diags.is_empty(), "expected no I009, got: {diags:?}");
+    }
+
     // ── Full lint pipeline ─────────────────────────────────────────────
 
     #[test]
@@ -352,7 +891,7 @@ mod tests {
             "processing-pdfs",
             "Processes PDF files and generates reports. Use when working with documents.",
         );
-        let diags = lint(&props, "");
+        let diags = lint(&props, "# PDFs\n\nExtract the text, then summarize it.\n");
         assert!(
             diags.is_empty(),
             "perfect skill should have no lint issues: {diags:?}"
@@ -400,4 +939,403 @@ mod tests {
             assert!(seen.insert(code), "duplicate lint code: {code}");
         }
     }
+
+    // ── I008: Question descriptions ────────────────────────────────────
+
+    #[test]
+    fn i008_trailing_question_mark_fires() {
+        let diags = lint_description_question("Need to process PDFs?");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I008);
+        assert!(diags[0].is_info());
+    }
+
+    #[test]
+    fn i008_leading_interrogative_fires() {
+        let diags = lint_description_question("How to convert PDFs. Use when converting.");
+        assert!(diags.iter().any(|d| d.code == I008), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i008_multi_sentence_trailing_question_fires() {
+        let diags = lint_description_question("Processes PDFs. Working with forms?");
+        assert!(diags.iter().any(|d| d.code == I008), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i008_mid_text_question_silent() {
+        let diags = lint_description_question(
+            "Answers 'what changed?' questions about diffs. Use when reviewing code.",
+        );
+        assert!(diags.is_empty(), "expected no I008, got: {diags:?}");
+    }
+
+    #[test]
+    fn i008_declarative_silent() {
+        assert!(lint_description_question("Processes PDFs. Use when handling files.").is_empty());
+        assert!(lint_description_question("When the user asks, converts files.").is_empty());
+    }
+
+    // ── I010: Description duplicates body opening ──────────────────────
+
+    #[test]
+    fn i010_copied_opening_line_fires() {
+        let diags = lint_description_duplicates_body(
+            "Extracts text and tables from PDF files.",
+            "# PDF Tools\n\nExtracts text and tables from PDF files.\n\n## Steps\n",
+        );
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I010);
+        assert!(diags[0].is_info());
+    }
+
+    #[test]
+    fn i010_near_identical_opening_fires() {
+        let diags = lint_description_duplicates_body(
+            "Extracts text and tables from PDF files",
+            "Extracts the text and tables from PDF files.",
+        );
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+    }
+
+    #[test]
+    fn i010_distinct_description_silent() {
+        let diags = lint_description_duplicates_body(
+            "Extracts text from PDF files. Use when the user uploads a PDF.",
+            "# PDF Tools\n\nRun `pdftotext` on the input file first.\n",
+        );
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i010_heading_only_body_silent() {
+        let diags = lint_description_duplicates_body("PDF Tools", "# PDF Tools\n");
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    // ── I012–I015: Description grammar ─────────────────────────────────
+
+    #[test]
+    fn i012_double_space_fires() {
+        let diags = lint_description_double_spaces("Processes  PDF files.");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I012);
+        assert!(diags[0].is_info());
+    }
+
+    #[test]
+    fn i012_spaces_in_code_span_ignored() {
+        let diags = lint_description_double_spaces("Aligns output like `a  b` columns.");
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    #[test]
+    fn collapse_spaces_keeps_code_spans() {
+        assert_eq!(
+            collapse_description_spaces("Runs   `ls  -l`  then  exits."),
+            "Runs `ls  -l` then exits."
+        );
+    }
+
+    #[test]
+    fn i013_padding_fires_only_when_present() {
+        let diags = lint_description_padding(" Processes PDF files. ");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I013);
+        assert!(lint_description_padding("Processes PDF files.").is_empty());
+    }
+
+    #[test]
+    fn i014_unclosed_parenthesis_fires() {
+        let diags = lint_description_unbalanced("Processes PDFs (and images.");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I014);
+        assert!(diags[0].message.contains("parentheses"));
+    }
+
+    #[test]
+    fn i014_mismatched_brackets_fire() {
+        let diags = lint_description_unbalanced("Reads [config) files.");
+        assert!(diags[0].message.contains("parentheses"), "got: {diags:?}");
+        let diags = lint_description_unbalanced("Reads {config files.");
+        assert!(diags[0].message.contains("braces"), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i014_parentheses_in_code_span_ignored() {
+        let diags = lint_description_unbalanced("Wraps `f(x` calls (safely).");
+        assert!(diags.is_empty(), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i014_odd_double_quotes_fire() {
+        let diags = lint_description_unbalanced("Handles \"draft files.");
+        assert!(diags[0].message.contains("double quotes"), "got: {diags:?}");
+        assert!(lint_description_unbalanced("Handles \"draft\" files.").is_empty());
+    }
+
+    #[test]
+    fn i014_smart_quotes() {
+        assert!(lint_description_unbalanced("Handles \u{201C}draft\u{201D} files.").is_empty());
+        let diags = lint_description_unbalanced("Handles \u{201C}draft files.");
+        assert!(diags[0].message.contains("double quotes"), "got: {diags:?}");
+        assert!(lint_description_unbalanced("Marks \u{2018}done\u{2019} items.").is_empty());
+        let diags = lint_description_unbalanced("Marks \u{2018}done items.");
+        assert!(diags[0].message.contains("single quotes"), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i014_contractions_and_possessives_silent() {
+        for description in [
+            "Don't use for images; it's for PDFs.",
+            "Doesn\u{2019}t touch the user\u{2019}s files.",
+            "Cleans the users' caches.",
+            "Quotes 'single' words.",
+        ] {
+            let diags = lint_description_unbalanced(description);
+            assert!(diags.is_empty(), "{description}: {diags:?}");
+        }
+    }
+
+    #[test]
+    fn i014_unclosed_single_quote_fires() {
+        let diags = lint_description_unbalanced("Sets the 'mode option.");
+        assert!(diags[0].message.contains("single quotes"), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i015_trailing_comma_and_conjunction_fire() {
+        let diags = lint_description_dangling_end("Processes PDFs,");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I015);
+        assert!(diags[0].message.contains("a comma"));
+        let diags = lint_description_dangling_end("Processes PDFs and");
+        assert!(diags[0].message.contains("\"and\""), "got: {diags:?}");
+    }
+
+    #[test]
+    fn i015_complete_sentence_silent() {
+        assert!(lint_description_dangling_end("Processes PDFs and images.").is_empty());
+        assert!(lint_description_dangling_end("Use for reports, not slides.").is_empty());
+    }
+
+    // ── I007: Deprecated without removal date ──────────────────────────
+
+    fn deprecated_props(nested: &str) -> SkillProperties {
+        let mut props = make_props("old-skill", "Processes files");
+        let mut meta = std::collections::HashMap::new();
+        meta.insert(
+            "metadata".to_string(),
+            serde_yaml_ng::from_str(nested).unwrap(),
+        );
+        props.metadata = Some(meta);
+        props
+    }
+
+    #[test]
+    fn i007_deprecated_without_removal_date_fires() {
+        let diags = lint_deprecation_removal_date(&deprecated_props("deprecated: true\n"));
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I007);
+        assert!(diags[0].is_info());
+    }
+
+    #[test]
+    fn i007_removal_date_present_silent() {
+        let props = deprecated_props("deprecated: true\nremoval-date: 2027-01-31\n");
+        assert!(lint_deprecation_removal_date(&props).is_empty());
+    }
+
+    #[test]
+    fn i007_not_deprecated_silent() {
+        let props = make_props("old-skill", "Processes files");
+        assert!(lint_deprecation_removal_date(&props).is_empty());
+    }
+
+    // ── W004: Placeholder markers ──────────────────────────────────────
+
+    #[test]
+    fn w004_todo_in_body_fires() {
+        let diags = lint_body_placeholders("# Steps\n\nTODO: fill this in\n");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, W004);
+        assert!(diags[0].is_warning());
+        assert!(diags[0].message.contains("body line 3"));
+        assert!(diags[0].message.ends_with("TODO: fill this in"));
+    }
+
+    #[test]
+    fn w004_clean_body_silent() {
+        let diags = lint_body_placeholders("# Steps\n\nRun the script on a todo list.\n");
+        assert!(diags.is_empty(), "expected no W004, got: {diags:?}");
+    }
+
+    #[test]
+    fn w004_fixme_and_placeholder_fire() {
+        let diags = lint_body_placeholders("FIXME later\nSet name to <Placeholder>\n");
+        assert_eq!(diags.len(), 2, "got: {diags:?}");
+        assert!(diags[0].message.contains("'FIXME'"));
+        assert!(diags[1].message.contains("'<placeholder>'"));
+    }
+
+    #[test]
+    fn w004_fires_inside_code_fences() {
+        let diags = lint_body_placeholders("```bash\n# TODO: add command\n```\n");
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+    }
+
+    #[test]
+    fn w004_marker_inside_word_ignored() {
+        let diags = lint_body_placeholders("See TODOS.md and AUTODOC output\n");
+        assert!(diags.is_empty(), "expected no W004, got: {diags:?}");
+    }
+
+    #[test]
+    fn lint_reports_body_placeholders() {
+        let props = make_props(
+            "processing-pdfs",
+            "Processes PDF files and extracts text. Use when working with PDFs.",
+        );
+        let diags = lint(&props, "TODO: fill this in\n");
+        assert!(diags.iter().any(|d| d.code == W004), "got: {diags:?}");
+    }
+
+    // ── I017: Name terminology missing from body ───────────────────────
+
+    /// A body of more than [`MIN_TERMINOLOGY_WORDS`] words mentioning `term`.
+    fn body_with(term: &str) -> String {
+        format!(
+            "Open each {term} and read every page. Collect the text and tables into \
+             a single summary that lists every section in its original order."
+        )
+    }
+
+    #[test]
+    fn i017_body_without_name_term_fires() {
+        let diags = lint_body_name_terms("processing-pdfs", &body_with("document"));
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, I017);
+        assert!(diags[0].is_info());
+        assert_eq!(
+            diags[0].message,
+            "body never mentions the name's key term(s): 'pdfs'"
+        );
+    }
+
+    #[test]
+    fn i017_stemmed_or_hyphenated_mention_silent() {
+        assert!(lint_body_name_terms("processing-pdfs", &body_with("PDF")).is_empty());
+        assert!(lint_body_name_terms("report-builder", &body_with("report-style file")).is_empty());
+    }
+
+    #[test]
+    fn i017_any_key_term_is_enough() {
+        assert!(lint_body_name_terms("pdf-report-export", &body_with("report")).is_empty());
+    }
+
+    #[test]
+    fn i017_skips_short_bodies_and_names_without_key_terms() {
+        assert!(lint_body_name_terms("processing-pdfs", "Read the document.").is_empty());
+        assert!(lint_body_name_terms("processing-helper", &body_with("document")).is_empty());
+    }
+
+    // ── Rule registry and options ──────────────────────────────────────
+
+    /// Properties and body on which the rule reporting `code` fires.
+    fn rule_fixture(code: DiagnosticCode) -> (SkillProperties, &'static str) {
+        let ok = "Processes PDF files and extracts text. Use when working with PDFs.";
+        match code {
+            I001 => (
+                make_props("processing-pdfs", "I process your PDF files."),
+                "Body.",
+            ),
+            I002 => (
+                make_props("processing-pdfs", "Processes PDF files"),
+                "Body.",
+            ),
+            I003 => (make_props("pdf-processor", ok), "Body."),
+            I004 => (make_props("helper-pdfs", ok), "Body."),
+            I005 => (make_props("processing-pdfs", "Does PDFs"), "Body."),
+            I007 => {
+                let mut props = make_props("processing-pdfs", ok);
+                let mut meta = std::collections::HashMap::new();
+                meta.insert(
+                    "metadata".to_string(),
+                    serde_yaml_ng::from_str("deprecated: true\n").unwrap(),
+                );
+                props.metadata = Some(meta);
+                (props, "Body.")
+            }
+            I008 => (
+                make_props("processing-pdfs", "How do I read PDFs?"),
+                "Body.",
+            ),
+            I009 => (make_props("processing-pdfs", ok), "# Title\n"),
+            I010 => (make_props("processing-pdfs", ok), ok),
+            I017 => (
+                make_props("processing-pdfs", ok),
+                "Open each document, read every page, and collect the text and tables \
+                 into a single report that lists the sections in their original order.",
+            ),
+            I012 => (
+                make_props("processing-pdfs", &ok.replace(' ', "  ")),
+                "Body.",
+            ),
+            I013 => (make_props("processing-pdfs", &format!(" {ok}")), "Body."),
+            I014 => (
+                make_props("processing-pdfs", "Processes PDFs (and images."),
+                "Body.",
+            ),
+            I015 => (make_props("processing-pdfs", "Processes PDFs and"), "Body."),
+            W004 => (make_props("processing-pdfs", ok), "TODO: write this\n"),
+            _ => panic!("lint rule {code} has no fixture in rule_fixture"),
+        }
+    }
+
+    #[test]
+    fn every_rule_has_a_unique_code_it_reports() {
+        let codes = lint_codes();
+        let mut seen = std::collections::HashSet::new();
+        for rule in RULES {
+            assert!(seen.insert(rule.code), "duplicate lint code {}", rule.code);
+            assert!(DiagnosticCode::all().contains(&rule.code));
+            let (props, body) = rule_fixture(rule.code);
+            let diags = (rule.check)(&props, body);
+            assert!(
+                !diags.is_empty(),
+                "{} did not fire on its fixture",
+                rule.code
+            );
+            assert!(
+                diags.iter().all(|d| d.code == rule.code),
+                "{} reported other codes: {diags:?}",
+                rule.code
+            );
+        }
+        assert_eq!(codes.len(), seen.len());
+    }
+
+    #[test]
+    fn options_enable_and_disable_rules() {
+        let props = make_props("helper", "Does PDFs");
+        let all = lint(&props, "TODO\n");
+        assert!(all.iter().any(|d| d.code == I004));
+        assert!(all.iter().any(|d| d.code == W004));
+
+        let options = LintOptions {
+            enable: None,
+            disable: vec![I004, W004],
+        };
+        let diags = lint_with_options(&props, "TODO\n", &options);
+        assert!(!diags.iter().any(|d| d.code == I004 || d.code == W004));
+        assert_eq!(diags.len(), all.len() - 2);
+
+        let options = LintOptions {
+            enable: Some(vec![I004, I005]),
+            disable: vec![I005],
+        };
+        let diags = lint_with_options(&props, "TODO\n", &options);
+        let codes: Vec<DiagnosticCode> = diags.iter().map(|d| d.code).collect();
+        assert_eq!(codes, [I004]);
+    }
 }
@@ -0,0 +1,443 @@
+//! Minimal Language Server Protocol server for `SKILL.md` files.
+//!
+//! Enabled with the `lsp` feature and run by `aigent lsp`. The server speaks
+//! JSON-RPC over any reader/writer pair (stdio for the CLI) and supports
+//! only what live validation needs:
+//!
+//! - `initialize`, `shutdown`, and `exit`;
+//! - `textDocument/didOpen`, `didChange` (full-document sync), and
+//!   `didClose`.
+//!
+//! Each open or change runs [`validate_content`](crate::validate_content)
+//! and [`lint`](crate::lint) on the buffer and sends the results as
+//! `textDocument/publishDiagnostics`. Diagnostics are placed on the line of
+//! the frontmatter key they concern (`body` diagnostics on the first body
+//! line), or on the first line when there is none.
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use crate::diagnostics::{Diagnostic, Severity, ValidationTarget, E022};
+use crate::parser::{frontmatter_lines, tab_indented_lines};
+
+/// JSON-RPC error code for a request the server does not implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for a request sent after `shutdown`.
+const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for a message body that is not valid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// Largest `Content-Length` accepted, so a bogus header cannot make the
+/// server allocate without bound.
+const MAX_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Run the server until the client sends `exit` or closes `input`.
+///
+/// # Errors
+///
+/// Returns any I/O error from `input` or `output`, or an `InvalidData`
+/// error for a message without a `Content-Length` header.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    let mut shut_down = false;
+    while let Some(body) = read_message(&mut input)? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                write_error(&mut output, &Value::Null, PARSE_ERROR, &e.to_string())?;
+                continue;
+            }
+        };
+        let method = message["method"].as_str().unwrap_or_default();
+        let id = message.get("id");
+        let params = &message["params"];
+        match (method, id) {
+            ("exit", _) => return Ok(()),
+            (_, Some(id)) if shut_down => {
+                write_error(&mut output, id, INVALID_REQUEST, "server is shut down")?;
+            }
+            ("initialize", Some(id)) => write_result(
+                &mut output,
+                id,
+                json!({
+                    // 1 = full document sync.
+                    "capabilities": { "textDocumentSync": 1 },
+                    "serverInfo": { "name": "aigent", "version": env!("CARGO_PKG_VERSION") },
+                }),
+            )?,
+            ("shutdown", Some(id)) => {
+                shut_down = true;
+                write_result(&mut output, id, Value::Null)?;
+            }
+            ("textDocument/didOpen", None) => {
+                let document = &params["textDocument"];
+                let text = document["text"].as_str().unwrap_or_default();
+                publish(&mut output, &document["uri"], text)?;
+            }
+            ("textDocument/didChange", None) => {
+                // Full sync: the last change holds the whole document.
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes.and_then(|c| c.last()?["text"].as_str()) {
+                    publish(&mut output, &params["textDocument"]["uri"], text)?;
+                }
+            }
+            ("textDocument/didClose", None) => {
+                // Clear the closed document's diagnostics.
+                write_notification(
+                    &mut output,
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": params["textDocument"]["uri"], "diagnostics": [] }),
+                )?;
+            }
+            (_, Some(id)) => {
+                let message = format!("method not supported: {method}");
+                write_error(&mut output, id, METHOD_NOT_FOUND, &message)?;
+            }
+            // Other notifications, including `initialized`, need no reply.
+            (_, None) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Validate and lint `text`, the content of the document at `uri`.
+///
+/// A `file://` URI supplies the skill directory, so the name is checked
+/// against it as `aigent validate` would.
+#[must_use]
+pub fn document_diagnostics(uri: &str, text: &str) -> Vec<Diagnostic> {
+    let dir = uri_path(uri).and_then(|path| path.parent().map(PathBuf::from));
+    let mut diags = crate::validate_content(text, dir.as_deref(), ValidationTarget::Standard);
+    if let Ok(props) = crate::parse_properties(text) {
+        let body = crate::parse_frontmatter(text)
+            .map(|(_, body)| body)
+            .unwrap_or_default();
+        diags.extend(crate::lint(&props, &body));
+    }
+    diags
+}
+
+/// Send the diagnostics for `text` as a `publishDiagnostics` notification.
+fn publish(output: &mut impl Write, uri: &Value, text: &str) -> std::io::Result<()> {
+    let diags = document_diagnostics(uri.as_str().unwrap_or_default(), text);
+    let lines = diagnostic_lines(text, &diags);
+    let lsp_diags: Vec<Value> = diags
+        .iter()
+        .zip(lines)
+        .map(|(d, line)| {
+            let width = text
+                .lines()
+                .nth(line)
+                .map_or(0, |l| l.encode_utf16().count());
+            let mut message = d.message.clone();
+            if let Some(suggestion) = &d.suggestion {
+                message.push_str(&format!(" ({suggestion})"));
+            }
+            json!({
+                "range": {
+                    "start": { "line": line, "character": 0 },
+                    "end": { "line": line, "character": width },
+                },
+                "severity": lsp_severity(d.severity),
+                "code": d.code_str(),
+                "source": "aigent",
+                "message": message,
+            })
+        })
+        .collect();
+    write_notification(
+        output,
+        "textDocument/publishDiagnostics",
+        json!({ "uri": uri, "diagnostics": lsp_diags }),
+    )
+}
+
+/// 0-based line for each diagnostic in `diags`.
+///
+/// E022 diagnostics are emitted in file order, one per tab-indented line;
+/// other diagnostics go on their field's key line.
+fn diagnostic_lines(text: &str, diags: &[Diagnostic]) -> Vec<usize> {
+    let mut tab_lines = tab_indented_lines(text).into_iter();
+    diags
+        .iter()
+        .map(|d| {
+            let line = if d.code == E022 {
+                tab_lines.next()
+            } else {
+                d.field.and_then(|field| field_line(text, field))
+            };
+            line.unwrap_or(0)
+        })
+        .collect()
+}
+
+/// 0-based line of a top-level frontmatter key, or of the first body line
+/// for `body`.
+fn field_line(text: &str, field: &str) -> Option<usize> {
+    let (offset, yaml) = frontmatter_lines(text)?;
+    if field == "body" {
+        // The line after the closing delimiter, or after a blank separator.
+        let mut first = offset + yaml.lines().count() + 1;
+        if text.lines().nth(first).is_some_and(|l| l.trim().is_empty()) {
+            first += 1;
+        }
+        return Some(first.min(text.lines().count().saturating_sub(1)));
+    }
+    yaml.lines()
+        .position(|line| {
+            line.strip_prefix(field)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map(|i| offset + i)
+}
+
+fn lsp_severity(severity: Severity) -> u8 {
+    match severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+        Severity::Hint => 4,
+    }
+}
+
+/// Local path of a `file://` URI, with `%XX` escapes decoded.
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        let hex = encoded.get(i + 1..i + 3).and_then(|h| {
+            let h = std::str::from_utf8(h).ok()?;
+            u8::from_str_radix(h, 16).ok()
+        });
+        match (encoded[i], hex) {
+            (b'%', Some(byte)) => {
+                bytes.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                bytes.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// Read one message body, or `None` at end of input.
+fn read_message(input: &mut impl BufRead) -> std::io::Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "message without a Content-Length header",
+        )
+    })?;
+    if length > MAX_MESSAGE_LENGTH {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Content-Length {length} exceeds the {MAX_MESSAGE_LENGTH}-byte limit"),
+        ));
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> std::io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
+
+fn write_result(output: &mut impl Write, id: &Value, result: Value) -> std::io::Result<()> {
+    write_message(
+        output,
+        &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    )
+}
+
+fn write_error(
+    output: &mut impl Write,
+    id: &Value,
+    code: i64,
+    message: &str,
+) -> std::io::Result<()> {
+    write_message(
+        output,
+        &json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    )
+}
+
+fn write_notification(output: &mut impl Write, method: &str, params: Value) -> std::io::Result<()> {
+    write_message(
+        output,
+        &json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: &Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{body}", body.len())
+    }
+
+    /// Run the server over `messages` and return what it sent.
+    fn exchange(messages: &[Value]) -> Vec<Value> {
+        let input: String = messages.iter().map(frame).collect();
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+        let mut reader = output.as_slice();
+        let mut replies = Vec::new();
+        while let Some(body) = read_message(&mut reader).unwrap() {
+            replies.push(serde_json::from_slice(&body).unwrap());
+        }
+        replies
+    }
+
+    fn did_open(text: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": "file:///skills/my%20skill/SKILL.md",
+                "languageId": "markdown",
+                "version": 1,
+                "text": text,
+            }},
+        })
+    }
+
+    #[test]
+    fn oversized_content_length_is_rejected() {
+        let input = format!("Content-Length: {}\r\n\r\n{{}}", usize::MAX);
+        let err = read_message(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        let input = format!("Content-Length: {}\r\n\r\n", MAX_MESSAGE_LENGTH + 1);
+        let err = read_message(&mut input.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn initialize_advertises_full_sync() {
+        let replies = exchange(&[json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"})]);
+        assert_eq!(replies[0]["id"], 1);
+        assert_eq!(replies[0]["result"]["capabilities"]["textDocumentSync"], 1);
+    }
+
+    #[test]
+    fn did_open_publishes_diagnostics_on_field_line() {
+        let text = "---\ndescription: Does things\nname: Bad_Name\n---\nBody.\n";
+        let replies = exchange(&[did_open(text)]);
+        assert_eq!(replies[0]["method"], "textDocument/publishDiagnostics");
+        let diags = replies[0]["params"]["diagnostics"].as_array().unwrap();
+        let name = diags
+            .iter()
+            .find(|d| d["code"] == "E003")
+            .expect("name error");
+        assert_eq!(name["range"]["start"]["line"], 2);
+        assert_eq!(name["range"]["end"]["character"], 14);
+        assert_eq!(name["severity"], 1);
+    }
+
+    #[test]
+    fn did_change_uses_last_content_change() {
+        let change = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": "file:///x/SKILL.md", "version": 2 },
+                "contentChanges": [{ "text": "oops" }, { "text": "still no frontmatter" }],
+            },
+        });
+        let replies = exchange(&[change]);
+        let diags = replies[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0]["code"], "E000");
+        assert_eq!(diags[0]["range"]["end"]["character"], 20);
+    }
+
+    #[test]
+    fn did_close_clears_diagnostics() {
+        let close = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didClose",
+            "params": { "textDocument": { "uri": "file:///x/SKILL.md" } },
+        });
+        let replies = exchange(&[close]);
+        assert_eq!(replies[0]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn requests_after_shutdown_are_rejected_and_exit_stops() {
+        let replies = exchange(&[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "shutdown"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "initialize"}),
+            json!({"jsonrpc": "2.0", "method": "exit"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "initialize"}),
+        ]);
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0]["result"], Value::Null);
+        assert_eq!(replies[1]["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[test]
+    fn unknown_request_gets_method_not_found() {
+        let replies = exchange(&[
+            json!({"jsonrpc": "2.0", "id": 7, "method": "textDocument/completion"}),
+            json!({"jsonrpc": "2.0", "method": "initialized"}),
+        ]);
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn tab_indented_lines_get_their_own_line() {
+        let text = "---\nname: my-skill\ndescription: Does things\nmetadata:\n\tversion: 1\n---\n";
+        let diags = document_diagnostics("untitled:1", text);
+        let lines = diagnostic_lines(text, &diags);
+        let e022 = diags.iter().position(|d| d.code == E022).unwrap();
+        assert_eq!(lines[e022], 4);
+    }
+
+    #[test]
+    fn field_line_finds_keys_and_body() {
+        let text = "<!-- c -->\n---\nname: x\ndescription : y\n---\n\nBody.\n";
+        assert_eq!(field_line(text, "name"), Some(2));
+        assert_eq!(field_line(text, "description"), Some(3));
+        assert_eq!(field_line(text, "body"), Some(6));
+        assert_eq!(field_line("---\nname: x\n---\nBody.\n", "body"), Some(3));
+        assert_eq!(field_line(text, "metadata"), None);
+    }
+
+    #[test]
+    fn uri_path_decodes_percent_escapes() {
+        assert_eq!(
+            uri_path("file:///skills/my%20skill/SKILL.md"),
+            Some(PathBuf::from("/skills/my skill/SKILL.md"))
+        );
+        assert_eq!(uri_path("untitled:1"), None);
+    }
+}
@@ -26,6 +26,239 @@ pub struct SkillProperties {
     pub metadata: Option<HashMap<String, serde_yaml_ng::Value>>,
 }
 
+impl SkillProperties {
+    /// Look up `key` in the nested `metadata:` mapping of the frontmatter.
+    fn nested_metadata(&self, key: &str) -> Option<&serde_yaml_ng::Value> {
+        self.metadata
+            .as_ref()?
+            .get("metadata")?
+            .as_mapping()?
+            .get(key)
+    }
+
+    /// Whether the skill is marked `metadata.deprecated: true`.
+    #[must_use]
+    pub fn is_deprecated(&self) -> bool {
+        self.nested_metadata("deprecated")
+            .and_then(serde_yaml_ng::Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Prompt priority from `metadata.priority`; 0 when unset or not an
+    /// integer. Higher priorities come first with `prompt --sort priority`.
+    #[must_use]
+    pub fn priority(&self) -> i64 {
+        self.nested_metadata("priority")
+            .and_then(serde_yaml_ng::Value::as_i64)
+            .unwrap_or(0)
+    }
+
+    /// Name of the replacement skill from `metadata.superseded-by`, if set.
+    #[must_use]
+    pub fn superseded_by(&self) -> Option<&str> {
+        self.nested_metadata("superseded-by")
+            .and_then(serde_yaml_ng::Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Authors from `metadata.author` and `metadata.authors`, trimmed.
+    ///
+    /// A scalar `author` comes first, followed by the names in an `authors`
+    /// list that are not already included. Empty when neither is set.
+    #[must_use]
+    pub fn authors(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        let author = self
+            .nested_metadata("author")
+            .and_then(serde_yaml_ng::Value::as_str);
+        let listed = self
+            .nested_metadata("authors")
+            .and_then(serde_yaml_ng::Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(serde_yaml_ng::Value::as_str);
+        for name in author.into_iter().chain(listed).map(str::trim) {
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Tags from `metadata.tags`, trimmed and in declaration order.
+    ///
+    /// Accepts a YAML list of scalars or a comma-separated string. Empty
+    /// entries are dropped; empty when the key is unset.
+    #[must_use]
+    pub fn tags(&self) -> Vec<String> {
+        match self.nested_metadata("tags") {
+            Some(serde_yaml_ng::Value::Sequence(items)) => items
+                .iter()
+                .filter_map(|item| match item {
+                    serde_yaml_ng::Value::String(s) => Some(s.trim().to_string()),
+                    serde_yaml_ng::Value::Number(n) => Some(n.to_string()),
+                    serde_yaml_ng::Value::Bool(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+            Some(serde_yaml_ng::Value::String(s)) => s
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Tools listed in `allowed-tools`, trimmed and in declaration order.
+    ///
+    /// Entries are separated by commas, or by whitespace when the list has no
+    /// commas. Separators inside parentheses belong to the entry, so
+    /// `Bash(git status:*)` is one tool. Empty when the field is unset.
+    #[must_use]
+    pub fn allowed_tools_list(&self) -> Vec<&str> {
+        let Some(tools) = self.allowed_tools.as_deref() else {
+            return Vec::new();
+        };
+        let has_comma = top_level_chars(tools).any(|(_, c)| c == ',');
+        let mut list = Vec::new();
+        let mut start = 0;
+        for (i, c) in top_level_chars(tools) {
+            if c == ',' || (!has_comma && c.is_whitespace()) {
+                list.push(&tools[start..i]);
+                start = i + c.len_utf8();
+            }
+        }
+        list.push(&tools[start..]);
+        list.into_iter()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    /// The lead sentence of the description.
+    ///
+    /// Ends at the first `.`, `!`, or `?` followed by whitespace, or at the
+    /// first blank line of a block-scalar description. Periods after common
+    /// abbreviations (`e.g.`, `i.e.`, `vs.`) and initials, or followed by a
+    /// lowercase word, do not end the sentence. Returns the whole trimmed
+    /// description if no sentence end is found.
+    #[must_use]
+    pub fn description_first_sentence(&self) -> &str {
+        first_sentence(&self.description)
+    }
+
+    /// Clauses of the description that say when to use the skill.
+    ///
+    /// See [`crate::triggers`] for what counts as a trigger clause.
+    #[must_use]
+    pub fn trigger_clauses(&self) -> Vec<crate::triggers::TriggerClause> {
+        crate::triggers::trigger_clauses(&self.description)
+    }
+
+    /// Version from `metadata.version`, falling back to a top-level
+    /// `version`. Numbers are rendered as written, so `version: 1.0` gives
+    /// `"1.0"`. `None` when neither is set or the value is empty.
+    #[must_use]
+    pub fn version(&self) -> Option<String> {
+        let value = self
+            .nested_metadata("version")
+            .or_else(|| self.metadata.as_ref()?.get("version"))?;
+        let version = match value {
+            serde_yaml_ng::Value::String(s) => s.trim().to_string(),
+            serde_yaml_ng::Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        Some(version).filter(|v| !v.is_empty())
+    }
+
+    /// Planned removal date from `metadata.removal-date`, if set.
+    #[must_use]
+    pub fn removal_date(&self) -> Option<&str> {
+        self.nested_metadata("removal-date")
+            .and_then(serde_yaml_ng::Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+}
+
+/// Abbreviations whose trailing period does not end a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "approx", "cf", "dr", "eg", "etc", "fig", "ie", "incl", "mr", "mrs", "ms", "no", "st", "vs",
+];
+
+/// Characters that may follow a sentence terminator before the whitespace,
+/// such as a closing quote or parenthesis.
+const CLOSERS: &[char] = &['"', '\'', ')', ']', '\u{201D}', '\u{2019}'];
+
+/// The lead sentence of `text`; see
+/// [`SkillProperties::description_first_sentence`].
+pub(crate) fn first_sentence(text: &str) -> &str {
+    let text = text.trim();
+    for (i, c) in text.char_indices() {
+        match c {
+            '\n' if text[i + 1..]
+                .trim_start_matches([' ', '\t'])
+                .starts_with('\n') =>
+            {
+                return text[..i].trim_end();
+            }
+            '.' | '!' | '?' => {
+                let end =
+                    i + 1 + text[i + 1..].len() - text[i + 1..].trim_start_matches(CLOSERS).len();
+                let rest = &text[end..];
+                if rest.is_empty() {
+                    return text;
+                }
+                if !rest.starts_with(char::is_whitespace) {
+                    continue;
+                }
+                let next_lowercase = rest.trim_start().starts_with(char::is_lowercase);
+                if c == '.' && (next_lowercase || is_abbreviation(&text[..i])) {
+                    continue;
+                }
+                return &text[..end];
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Returns `true` if the word ending `before` is an abbreviation or initial.
+fn is_abbreviation(before: &str) -> bool {
+    let word = before
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or("")
+        .trim_start_matches(['(', '[', '"', '\'']);
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        // A single letter, as in an initial ("J. Smith").
+        (Some(first), None) => first.is_alphabetic(),
+        // Dotted abbreviations such as "e.g" and "i.e".
+        _ if word.contains('.') => true,
+        _ => ABBREVIATIONS.contains(&word.to_lowercase().as_str()),
+    }
+}
+
+/// Characters of `s` with their byte offsets, skipping those inside
+/// parentheses.
+fn top_level_chars(s: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut depth = 0usize;
+    s.char_indices().filter(move |&(_, c)| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => return depth == 0,
+        }
+        false
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,6 +290,64 @@ mod tests {
         }
     }
 
+    // ── description_first_sentence ──────────────────────────────────
+
+    fn first_of(description: &str) -> String {
+        let mut sp = minimal_props();
+        sp.description = description.to_string();
+        sp.description_first_sentence().to_string()
+    }
+
+    #[test]
+    fn first_sentence_stops_at_terminator() {
+        assert_eq!(
+            first_of("Formats code. Use when the user asks for formatting."),
+            "Formats code."
+        );
+        assert_eq!(first_of("Is it ready? Check it."), "Is it ready?");
+        assert_eq!(
+            first_of("Single sentence without a period"),
+            "Single sentence without a period"
+        );
+    }
+
+    #[test]
+    fn first_sentence_skips_abbreviations_and_decimals() {
+        assert_eq!(
+            first_of("Converts files, e.g. PDF or DOCX, to text. Use when needed."),
+            "Converts files, e.g. PDF or DOCX, to text."
+        );
+        assert_eq!(
+            first_of("Compares Python vs. Rust in v1.5 builds. More text."),
+            "Compares Python vs. Rust in v1.5 builds."
+        );
+        assert_eq!(
+            first_of("Handles images, audio, etc. and video. More."),
+            "Handles images, audio, etc. and video."
+        );
+        assert_eq!(
+            first_of("Written by J. Smith. More."),
+            "Written by J. Smith."
+        );
+    }
+
+    #[test]
+    fn first_sentence_keeps_closing_quote() {
+        assert_eq!(first_of("Says \"hello.\" Then stops."), "Says \"hello.\"");
+    }
+
+    #[test]
+    fn first_sentence_handles_block_scalars() {
+        assert_eq!(
+            first_of("Processes PDFs\nand forms. Use for\nextraction.\n"),
+            "Processes PDFs\nand forms."
+        );
+        assert_eq!(
+            first_of("  Summary line without period\n\nDetails follow here.\n"),
+            "Summary line without period"
+        );
+    }
+
     #[test]
     fn construct_with_required_fields_only() {
         let sp = minimal_props();
@@ -217,4 +508,128 @@ metadata:
         let result = serde_yaml_ng::from_str::<SkillProperties>(yaml);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn trigger_clauses_read_description() {
+        let mut sp = minimal_props();
+        sp.description = "Processes PDFs. Use when working with documents.".to_string();
+        let clauses = sp.trigger_clauses();
+        assert_eq!(clauses.len(), 1);
+        assert_eq!(clauses[0].text, "Use when working with documents");
+    }
+
+    // ── deprecation metadata ─────────────────────────────────────────
+
+    fn props_with_nested(yaml: &str) -> SkillProperties {
+        let mut sp = minimal_props();
+        let mut meta = HashMap::new();
+        meta.insert(
+            "metadata".to_string(),
+            serde_yaml_ng::from_str(yaml).unwrap(),
+        );
+        sp.metadata = Some(meta);
+        sp
+    }
+
+    #[test]
+    fn deprecation_accessors_read_nested_metadata() {
+        let sp = props_with_nested(
+            "deprecated: true\nsuperseded-by: new-skill\nremoval-date: 2027-01-31\n",
+        );
+        assert!(sp.is_deprecated());
+        assert_eq!(sp.superseded_by(), Some("new-skill"));
+        assert_eq!(sp.removal_date(), Some("2027-01-31"));
+    }
+
+    #[test]
+    fn deprecation_accessors_default_when_absent() {
+        let sp = minimal_props();
+        assert!(!sp.is_deprecated());
+        assert!(sp.superseded_by().is_none());
+        assert!(sp.removal_date().is_none());
+    }
+
+    #[test]
+    fn deprecated_requires_boolean_true() {
+        assert!(!props_with_nested("deprecated: false\n").is_deprecated());
+        assert!(!props_with_nested("deprecated: 'yes'\n").is_deprecated());
+    }
+
+    #[test]
+    fn authors_merge_scalar_author_and_list() {
+        assert_eq!(
+            props_with_nested("authors: [Jane Doe, ' John Roe ']\n").authors(),
+            ["Jane Doe", "John Roe"]
+        );
+        assert_eq!(
+            props_with_nested("author: John Roe\nauthors: [Jane Doe, John Roe]\n").authors(),
+            ["John Roe", "Jane Doe"]
+        );
+        assert_eq!(
+            props_with_nested("author: Jane Doe\n").authors(),
+            ["Jane Doe"]
+        );
+        assert!(minimal_props().authors().is_empty());
+    }
+
+    #[test]
+    fn tags_accept_list_or_comma_string() {
+        assert_eq!(
+            props_with_nested("tags: [pdf, ' forms ', 2]\n").tags(),
+            ["pdf", "forms", "2"]
+        );
+        assert_eq!(
+            props_with_nested("tags: 'pdf, , forms'\n").tags(),
+            ["pdf", "forms"]
+        );
+        assert!(props_with_nested("tags: {a: b}\n").tags().is_empty());
+        assert!(minimal_props().tags().is_empty());
+    }
+
+    #[test]
+    fn top_level_deprecated_key_is_not_the_convention() {
+        let mut sp = minimal_props();
+        let mut meta = HashMap::new();
+        meta.insert("deprecated".to_string(), serde_yaml_ng::Value::Bool(true));
+        sp.metadata = Some(meta);
+        assert!(!sp.is_deprecated());
+    }
+
+    #[test]
+    fn version_prefers_nested_metadata_over_top_level() {
+        assert_eq!(
+            props_with_nested("version: 1.0\n").version().as_deref(),
+            Some("1.0")
+        );
+        let mut sp = props_with_nested("version: '2.1.0'\n");
+        sp.metadata.as_mut().unwrap().insert(
+            "version".to_string(),
+            serde_yaml_ng::Value::String("9.9.9".to_string()),
+        );
+        assert_eq!(sp.version().as_deref(), Some("2.1.0"));
+        sp.metadata.as_mut().unwrap().remove("metadata");
+        assert_eq!(sp.version().as_deref(), Some("9.9.9"));
+        assert!(minimal_props().version().is_none());
+    }
+
+    #[test]
+    fn allowed_tools_list_splits_on_commas_or_whitespace() {
+        let mut sp = SkillProperties {
+            allowed_tools: Some(" Bash(git add:*), Read ,, Write ".to_string()),
+            ..minimal_props()
+        };
+        assert_eq!(
+            sp.allowed_tools_list(),
+            ["Bash(git add:*)", "Read", "Write"]
+        );
+        sp.allowed_tools = Some("Bash(git status:*)  Read\tGrep".to_string());
+        assert_eq!(
+            sp.allowed_tools_list(),
+            ["Bash(git status:*)", "Read", "Grep"]
+        );
+        sp.allowed_tools = Some("Bash(a, b) Read".to_string());
+        assert_eq!(sp.allowed_tools_list(), ["Bash(a, b)", "Read"]);
+        sp.allowed_tools = None;
+        assert!(sp.allowed_tools_list().is_empty());
+    }
 }
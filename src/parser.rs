@@ -8,7 +8,11 @@ use crate::fs_util::is_regular_file;
 use crate::models::SkillProperties;
 
 /// Maximum file size for SKILL.md and related files (1 MiB).
-const MAX_FILE_SIZE: u64 = 1_048_576;
+///
+/// Larger files are rejected when read; validation warns (W013) above
+/// [`FILE_SIZE_WARNING_PERCENT`](crate::validator::FILE_SIZE_WARNING_PERCENT)
+/// of this size.
+pub const MAX_FILE_SIZE: u64 = 1_048_576;
 
 /// Reads a file with a size check, returning an error if the file exceeds 1 MiB.
 ///
@@ -27,9 +31,52 @@ pub(crate) fn read_file_checked(path: &Path) -> Result<String> {
     })
 }
 
+/// Which exact-case skill file names are present in a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct SkillMdNames {
+    /// A regular file named exactly `SKILL.md` is listed.
+    pub uppercase: bool,
+    /// A regular file named exactly `skill.md` is listed.
+    pub lowercase: bool,
+}
+
+/// List which of `SKILL.md` and `skill.md` exist in `dir`, by exact name.
+///
+/// This reads the directory listing rather than probing paths: on a
+/// case-insensitive filesystem `dir.join("SKILL.md")` also resolves a file
+/// stored as `skill.md`, so only the listing tells the two apart. Symlinks
+/// are not counted. Returns `None` if the directory cannot be listed.
+pub(crate) fn skill_md_names(dir: &Path) -> Option<SkillMdNames> {
+    let mut names = SkillMdNames::default();
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_file()) {
+            continue;
+        }
+        match entry.file_name().to_str() {
+            Some("SKILL.md") => names.uppercase = true,
+            Some("skill.md") => names.lowercase = true,
+            _ => {}
+        }
+    }
+    Some(names)
+}
+
 /// Locate SKILL.md in a directory (prefer uppercase over lowercase).
+///
+/// When both names are listed, `SKILL.md` always wins. If neither exact
+/// name is listed (or the directory cannot be listed), falls back to
+/// probing the paths, which on a case-insensitive filesystem also finds
+/// other casings such as `Skill.md`.
 #[must_use]
 pub fn find_skill_md(dir: &Path) -> Option<PathBuf> {
+    if let Some(names) = skill_md_names(dir) {
+        if names.uppercase {
+            return Some(dir.join("SKILL.md"));
+        }
+        if names.lowercase {
+            return Some(dir.join("skill.md"));
+        }
+    }
     let uppercase = dir.join("SKILL.md");
     if is_regular_file(&uppercase) {
         return Some(uppercase);
@@ -41,30 +88,168 @@ pub fn find_skill_md(dir: &Path) -> Option<PathBuf> {
     None
 }
 
+/// Split an editor-inserted preamble off the start of `content`.
+///
+/// The preamble is any leading blank lines plus at most one HTML comment
+/// (`<!-- ... -->`, possibly spanning several lines) and the blank lines
+/// after it. Returns `(preamble, rest)`; `preamble` is empty when there is
+/// nothing to skip. A comment is only treated as preamble if nothing but
+/// whitespace follows the closing `-->` on its line.
+#[must_use]
+pub fn split_preamble(content: &str) -> (&str, &str) {
+    let mut pos = skip_blank_lines(content, 0);
+
+    let rest = &content[pos..];
+    if rest.trim_start_matches([' ', '\t']).starts_with("<!--") {
+        if let Some(close) = rest.find("-->") {
+            let after = close + 3;
+            let line_end = rest[after..]
+                .find('\n')
+                .map_or(rest.len(), |i| after + i + 1);
+            if rest[after..line_end].trim().is_empty() {
+                pos = skip_blank_lines(content, pos + line_end);
+            }
+        }
+    }
+
+    content.split_at(pos)
+}
+
+/// Advance `pos` past any whitespace-only lines in `content`.
+fn skip_blank_lines(content: &str, mut pos: usize) -> usize {
+    while pos < content.len() {
+        let rest = &content[pos..];
+        let line_end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        if !rest[..line_end].trim().is_empty() {
+            break;
+        }
+        pos += line_end;
+    }
+    pos
+}
+
+/// Returns `true` if `line` is a frontmatter delimiter.
+///
+/// Only a `---` at column 0 is a delimiter; trailing whitespace is tolerated
+/// (e.g., `"---  "` or `"---\t"`), leading whitespace is **not**. YAML
+/// requires block scalar content under a top-level key to be indented, so a
+/// `---` line inside a multi-line `description: |` or `>` value is never
+/// mistaken for the closing delimiter, while a `---` at column 0 always
+/// ends the frontmatter.
+#[must_use]
+pub fn is_frontmatter_delimiter(line: &str) -> bool {
+    line.trim_end() == "---"
+}
+
+/// Split content (without a preamble) into raw frontmatter YAML and body.
+///
+/// Returns `(yaml, body)` as slices of `content`: `yaml` is the text between
+/// the delimiter lines (without its final newline) and `body` everything
+/// after the closing delimiter line. Delimiters follow
+/// [`is_frontmatter_delimiter`]. Unlike [`parse_frontmatter`], the YAML is
+/// not parsed, so callers can rewrite frontmatter text verbatim.
+///
+/// # Errors
+///
+/// `AigentError::Parse` if the first line is not a delimiter or the closing
+/// delimiter is missing.
+pub fn split_frontmatter(content: &str) -> Result<(&str, &str)> {
+    let first_end = content.find('\n').map_or(content.len(), |i| i + 1);
+    if !is_frontmatter_delimiter(content[..first_end].trim_end_matches(['\n', '\r'])) {
+        return Err(AigentError::Parse {
+            message: "content does not start with `---`".to_string(),
+        });
+    }
+
+    let mut pos = first_end;
+    while pos < content.len() {
+        let rest = &content[pos..];
+        let line_end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        if is_frontmatter_delimiter(rest[..line_end].trim_end_matches(['\n', '\r'])) {
+            let yaml = content[first_end..pos]
+                .strip_suffix('\n')
+                .map_or(&content[first_end..pos], |y| {
+                    y.strip_suffix('\r').unwrap_or(y)
+                });
+            return Ok((yaml, &content[pos + line_end..]));
+        }
+        pos += line_end;
+    }
+
+    Err(AigentError::Parse {
+        message: "closing `---` delimiter not found".to_string(),
+    })
+}
+
+/// Frontmatter lines indented with a tab, as 0-based line indices into
+/// `content`.
+///
+/// YAML forbids tabs in indentation, so these lines either fail to parse or
+/// parse differently than they look. Blank lines are skipped. Content
+/// without a complete frontmatter block has no lines to report.
+pub(crate) fn tab_indented_lines(content: &str) -> Vec<usize> {
+    let Some((offset, yaml)) = frontmatter_lines(content) else {
+        return Vec::new();
+    };
+    yaml.lines()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with('\t') && !line.trim().is_empty())
+        .map(|(i, _)| offset + i)
+        .collect()
+}
+
+/// Indentation width used by the space-indented frontmatter lines: the
+/// smallest non-zero run of leading spaces, or 2 when there is none.
+pub(crate) fn frontmatter_indent_width(content: &str) -> usize {
+    frontmatter_lines(content)
+        .and_then(|(_, yaml)| {
+            yaml.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start_matches(' ').len())
+                .filter(|&width| width > 0)
+                .min()
+        })
+        .unwrap_or(2)
+}
+
+/// The YAML between the delimiters and the 0-based index of its first line
+/// in `content`, after any preamble.
+pub(crate) fn frontmatter_lines(content: &str) -> Option<(usize, &str)> {
+    let (preamble, rest) = split_preamble(content);
+    let (yaml, _) = split_frontmatter(rest).ok()?;
+    Some((preamble.lines().count() + 1, yaml))
+}
+
 /// Extract YAML frontmatter between `---` delimiters.
 ///
 /// Returns `(metadata_map, body_text)`.
 ///
-/// Delimiter matching uses `trim_end()`, so trailing whitespace on `---` lines
-/// is tolerated (e.g., `"---  "` or `"---\t"`). Leading whitespace is **not**
-/// trimmed — `"  ---"` does not match.
+/// Leading blank lines and a single leading HTML comment before the opening
+/// `---` are skipped (see [`split_preamble`]), so editor-inserted preambles
+/// do not break parsing.
+///
+/// Delimiters are matched by [`is_frontmatter_delimiter`]: only a `---` at
+/// column 0 opens or closes the frontmatter, so an indented `---` inside a
+/// block scalar is content.
 ///
 /// All mapping keys must be strings; non-string keys (e.g., integer `42:`) are
 /// rejected as a `Parse` error.
 ///
 /// # Errors
 ///
-/// - `AigentError::Parse` if the content does not start with `---`, the closing
+/// - `AigentError::Parse` if the content (after any preamble) does not start
+///   with `---`, the closing
 ///   `---` delimiter is missing, the YAML parses to a non-mapping value, or a
 ///   mapping key is not a string.
 /// - `AigentError::Yaml` if the YAML between delimiters has syntax errors
 ///   (propagated naturally via `?` to preserve line/column info).
 pub fn parse_frontmatter(content: &str) -> Result<(HashMap<String, Value>, String)> {
+    let (_, content) = split_preamble(content);
     let mut lines = content.lines().enumerate();
 
     // Step 1: Verify content starts with `---`.
     match lines.next() {
-        Some((_, line)) if line.trim_end() == "---" => {}
+        Some((_, line)) if is_frontmatter_delimiter(line) => {}
         _ => {
             return Err(AigentError::Parse {
                 message: "content does not start with `---`".to_string(),
@@ -75,7 +260,7 @@ pub fn parse_frontmatter(content: &str) -> Result<(HashMap<String, Value>, Strin
     // Step 2: Find closing `---` delimiter.
     let mut yaml_end_line = None;
     for (i, line) in &mut lines {
-        if line.trim_end() == "---" {
+        if is_frontmatter_delimiter(line) {
             yaml_end_line = Some(i);
             break;
         }
@@ -244,31 +429,46 @@ pub fn read_properties(dir: &Path) -> Result<SkillProperties> {
     // Step 2: Read file with size check.
     let content = read_file_checked(&path)?;
 
-    // Step 3: Parse frontmatter.
-    let (mut metadata, _body) = parse_frontmatter(&content)?;
+    // Step 3: Parse properties from the content.
+    parse_properties(&content)
+}
 
-    // Step 4: Extract and validate required fields.
+/// Parse `SKILL.md` content (not a file path) into [`SkillProperties`].
+///
+/// The in-memory counterpart of [`read_properties`], for editors and other
+/// callers that hold unsaved content.
+///
+/// # Errors
+///
+/// - `AigentError::Parse` if frontmatter is malformed.
+/// - `AigentError::Yaml` if the YAML has syntax errors.
+/// - `AigentError::Validation` if required fields are missing or have wrong types.
+pub fn parse_properties(content: &str) -> Result<SkillProperties> {
+    // Step 1: Parse frontmatter.
+    let (mut metadata, _body) = parse_frontmatter(content)?;
+
+    // Step 2: Extract and validate required fields.
     let name = require_string(&metadata, "name")?;
     let description = require_string(&metadata, "description")?;
 
-    // Step 5: Extract optional string fields.
+    // Step 3: Extract optional string fields.
     let license = optional_string(&metadata, "license")?;
     let compatibility = optional_string(&metadata, "compatibility")?;
     let allowed_tools = optional_string(&metadata, "allowed-tools")?;
 
-    // Step 6: Remove known keys; remaining entries become metadata.
+    // Step 4: Remove known keys; remaining entries become metadata.
     for key in KNOWN_KEYS {
         metadata.remove(*key);
     }
 
-    // Step 7: If metadata is empty, set to None.
+    // Step 5: If metadata is empty, set to None.
     let extra = if metadata.is_empty() {
         None
     } else {
         Some(metadata)
     };
 
-    // Step 8: Construct and return.
+    // Step 6: Construct and return.
     Ok(SkillProperties {
         name,
         description,
@@ -285,6 +485,10 @@ pub fn read_properties(dir: &Path) -> Result<SkillProperties> {
 /// and returns the body portion. Returns `Ok(String::new())` when the
 /// file has valid frontmatter but no body after the closing `---`.
 ///
+/// The body is trimmed by `trim_body_separator`: if its first line is
+/// blank (empty or whitespace only), that one line is dropped. Any further
+/// blank lines, and all blank lines inside the body, are kept.
+///
 /// # Errors
 ///
 /// - `AigentError::Parse` if no SKILL.md is found in the directory.
@@ -296,7 +500,22 @@ pub fn read_body(dir: &Path) -> Result<String> {
     })?;
     let content = read_file_checked(&path)?;
     let (_, body) = parse_frontmatter(&content)?;
-    Ok(body)
+    Ok(trim_body_separator(&body).to_string())
+}
+
+/// Drop the single blank line that conventionally separates the closing
+/// `---` from the body.
+///
+/// Only the first line is considered, and only if it is empty or whitespace
+/// (a trailing `\r` included). Line counts and first-paragraph checks then
+/// agree whether or not the author left that line.
+pub(crate) fn trim_body_separator(body: &str) -> &str {
+    let (first, rest) = body.split_once('\n').unwrap_or((body, ""));
+    if first.trim().is_empty() {
+        rest
+    } else {
+        body
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +564,37 @@ mod tests {
         assert_eq!(result, Some(dir.path().join("SKILL.md")));
     }
 
+    #[test]
+    fn find_skill_md_prefers_uppercase_when_both_listed() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("SKILL.md"), "upper").unwrap();
+        // On a case-insensitive filesystem this overwrites SKILL.md.
+        fs::write(dir.path().join("skill.md"), "lower").unwrap();
+        // Either way the listing keeps the first-created name, SKILL.md.
+        assert!(skill_md_names(dir.path()).unwrap().uppercase);
+        let found = find_skill_md(dir.path()).unwrap();
+        assert_eq!(found, dir.path().join("SKILL.md"));
+        if skill_md_names(dir.path()).unwrap().lowercase {
+            assert_eq!(fs::read_to_string(found).unwrap(), "upper");
+        }
+    }
+
+    #[test]
+    fn skill_md_names_reports_exact_casing() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("skill.md"), "---\n---\n").unwrap();
+        // Holds on case-insensitive filesystems too: the listing keeps the
+        // stored name even though probing SKILL.md would succeed.
+        assert_eq!(
+            skill_md_names(dir.path()),
+            Some(SkillMdNames {
+                uppercase: false,
+                lowercase: true,
+            })
+        );
+        assert_eq!(find_skill_md(dir.path()), Some(dir.path().join("skill.md")));
+    }
+
     #[test]
     fn find_skill_md_neither_exists() {
         let dir = tempdir().unwrap();
@@ -467,6 +717,143 @@ mod tests {
         assert!(desc.contains("---"));
     }
 
+    #[test]
+    fn parse_frontmatter_dashes_line_in_literal_block_scalar() {
+        let content = "---\nname: test\ndescription: |\n  Before the rule.\n  ---\n  After the rule.\nlicense: MIT\n---\n# Body\n";
+        let (meta, body) = parse_frontmatter(content).unwrap();
+        assert_eq!(
+            meta["description"].as_str().unwrap(),
+            "Before the rule.\n---\nAfter the rule.\n"
+        );
+        assert_eq!(meta["license"], Value::String("MIT".to_string()));
+        assert_eq!(body, "# Body\n");
+    }
+
+    // ── split_frontmatter ────────────────────────────────────────────
+
+    #[test]
+    fn split_frontmatter_skips_indented_dashes() {
+        let content = "---\nname: test\ndescription: |\n  a\n  ---\n  b\n---\nBody\n---\nMore\n";
+        let (yaml, body) = split_frontmatter(content).unwrap();
+        assert_eq!(yaml, "name: test\ndescription: |\n  a\n  ---\n  b");
+        assert_eq!(body, "Body\n---\nMore\n");
+    }
+
+    #[test]
+    fn split_frontmatter_tolerates_trailing_whitespace_and_crlf() {
+        let (yaml, body) = split_frontmatter("---  \r\nname: x\r\n---\t\r\nBody\r\n").unwrap();
+        assert_eq!(yaml, "name: x");
+        assert_eq!(body, "Body\r\n");
+    }
+
+    #[test]
+    fn split_frontmatter_empty_and_unterminated() {
+        assert_eq!(split_frontmatter("---\n---\n").unwrap(), ("", ""));
+        assert_eq!(
+            split_frontmatter("---\nname: x\n---").unwrap(),
+            ("name: x", "")
+        );
+        assert!(split_frontmatter("name: x\n").is_err());
+        assert!(split_frontmatter("---\nname: x\n  ---\n").is_err());
+    }
+
+    // ── tab indentation ──────────────────────────────────────────────
+
+    #[test]
+    fn tab_indented_lines_counts_from_file_start() {
+        let content = "<!-- c -->\n---\nname: x\nmetadata:\n\tversion: 1\n\t\n---\n\tBody\n";
+        assert_eq!(tab_indented_lines(content), [4]);
+        assert!(tab_indented_lines("---\nname: x\n").is_empty());
+    }
+
+    #[test]
+    fn frontmatter_indent_width_detects_spaces() {
+        assert_eq!(
+            frontmatter_indent_width("---\nmetadata:\n    a: 1\n        b: 2\n---\n"),
+            4
+        );
+        assert_eq!(frontmatter_indent_width("---\nmetadata:\n\ta: 1\n---\n"), 2);
+    }
+
+    // ── preamble tolerance ───────────────────────────────────────────
+
+    #[test]
+    fn split_preamble_none() {
+        let content = "---\nname: x\n---\n";
+        assert_eq!(split_preamble(content), ("", content));
+    }
+
+    #[test]
+    fn split_preamble_blank_lines() {
+        let (preamble, rest) = split_preamble("\n  \n---\nname: x\n---\n");
+        assert_eq!(preamble, "\n  \n");
+        assert!(rest.starts_with("---"));
+    }
+
+    #[test]
+    fn split_preamble_multiline_comment() {
+        let (preamble, rest) =
+            split_preamble("<!-- generated\n  by editor -->\n\n---\nname: x\n---\n");
+        assert_eq!(preamble, "<!-- generated\n  by editor -->\n\n");
+        assert!(rest.starts_with("---"));
+    }
+
+    #[test]
+    fn split_preamble_only_one_comment() {
+        let content = "<!-- a -->\n<!-- b -->\n---\nname: x\n---\n";
+        let (preamble, rest) = split_preamble(content);
+        assert_eq!(preamble, "<!-- a -->\n");
+        assert!(rest.starts_with("<!-- b -->"));
+    }
+
+    #[test]
+    fn split_preamble_comment_followed_by_text_is_not_preamble() {
+        let content = "<!-- a --> text\n---\nname: x\n---\n";
+        assert_eq!(split_preamble(content), ("", content));
+    }
+
+    #[test]
+    fn split_preamble_unclosed_comment_is_not_preamble() {
+        let content = "<!-- never closed\n---\nname: x\n---\n";
+        assert_eq!(split_preamble(content), ("", content));
+    }
+
+    #[test]
+    fn parse_frontmatter_skips_leading_blank_lines() {
+        let (map, body) =
+            parse_frontmatter("\n\n---\nname: my-skill\ndescription: desc\n---\nBody\n").unwrap();
+        assert_eq!(map["name"].as_str(), Some("my-skill"));
+        assert_eq!(body, "Body\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_skips_leading_html_comment() {
+        let content =
+            "<!-- markdownlint-disable -->\n---\nname: my-skill\ndescription: desc\n---\nBody\n";
+        let (map, body) = parse_frontmatter(content).unwrap();
+        assert_eq!(map["name"].as_str(), Some("my-skill"));
+        assert_eq!(body, "Body\n");
+    }
+
+    #[test]
+    fn parse_frontmatter_rejects_other_leading_text() {
+        assert!(parse_frontmatter("#!/usr/bin/env skill\n---\nname: x\n---\n").is_err());
+    }
+
+    #[test]
+    fn read_properties_with_comment_preamble() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("my-skill");
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            "<!-- editor preamble -->\n\n---\nname: my-skill\ndescription: desc\n---\n",
+        )
+        .unwrap();
+        let props = read_properties(&dir).unwrap();
+        assert_eq!(props.name, "my-skill");
+    }
+
     // ── read_properties tests ────────────────────────────────────────
 
     #[test]
@@ -634,6 +1021,24 @@ custom-key: value
         assert!(body.contains("Hello world"));
     }
 
+    #[test]
+    fn read_body_trims_one_separator_line() {
+        let tight = write_skill_md("---\nname: test\ndescription: desc\n---\n# Body\n\nText\n");
+        let spaced = write_skill_md("---\nname: test\ndescription: desc\n---\n\n# Body\n\nText\n");
+        let tight = read_body(tight.path()).unwrap();
+        let spaced = read_body(spaced.path()).unwrap();
+        assert_eq!(spaced, "# Body\n\nText\n");
+        assert_eq!(spaced.lines().count(), tight.lines().count());
+    }
+
+    #[test]
+    fn trim_body_separator_keeps_further_blank_lines() {
+        assert_eq!(trim_body_separator("\n\nBody\n"), "\nBody\n");
+        assert_eq!(trim_body_separator("  \r\nBody\r\n"), "Body\r\n");
+        assert_eq!(trim_body_separator("Body\n\nMore\n"), "Body\n\nMore\n");
+        assert_eq!(trim_body_separator("\n"), "");
+    }
+
     #[test]
     fn read_body_no_skill_md_returns_err() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,176 @@
+//! Deploy-time `${NAME}` placeholders in `SKILL.md` files.
+//!
+//! Some teams template `SKILL.md` files with `${VAR}` tokens that a deploy
+//! pipeline substitutes later. Validated raw, such tokens fail the name
+//! character check and skew length checks. A [`PlaceholderPolicy`] tells
+//! validation how to treat them:
+//! - Substitutions are applied first, so the post-deploy shape can be
+//!   checked with concrete values.
+//! - With [`allow`](PlaceholderPolicy::allow) set, remaining placeholders
+//!   are masked with a neutral stand-in of
+//!   [`nominal_length`](PlaceholderPolicy::nominal_length) characters before
+//!   the character and length checks, and reported in one I011 note so the
+//!   pipeline can confirm it provides each variable.
+//!
+//! The default policy changes nothing: placeholders are validated as
+//! written.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::diagnostics::{Diagnostic, Severity, I011};
+
+/// Characters counted for each placeholder by default.
+pub const DEFAULT_NOMINAL_LENGTH: usize = 8;
+
+/// Regex for a `${NAME}` placeholder. Captures the variable name in `name`.
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{(?P<name>[A-Za-z_][A-Za-z0-9_]*)\}").expect("placeholder regex must compile")
+});
+
+/// How validation treats `${NAME}` placeholders.
+#[derive(Debug, Clone)]
+pub struct PlaceholderPolicy {
+    /// Recognize placeholders: mask them for validation and list them in
+    /// an I011 note. When `false`, placeholders left after substitution are
+    /// validated as written.
+    pub allow: bool,
+    /// Characters counted for each masked placeholder in length checks.
+    pub nominal_length: usize,
+    /// Values substituted for placeholders before validation, by variable
+    /// name. Variables not listed here are left in place.
+    pub substitutions: HashMap<String, String>,
+}
+
+impl Default for PlaceholderPolicy {
+    fn default() -> Self {
+        Self {
+            allow: false,
+            nominal_length: DEFAULT_NOMINAL_LENGTH,
+            substitutions: HashMap::new(),
+        }
+    }
+}
+
+impl PlaceholderPolicy {
+    /// Returns `true` if this policy leaves content unchanged.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        !self.allow && self.substitutions.is_empty()
+    }
+}
+
+/// Variable names of the placeholders in `text`, in order of first
+/// appearance, without duplicates.
+#[must_use]
+pub fn find_placeholders(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for cap in PLACEHOLDER_RE.captures_iter(text) {
+        let name = &cap["name"];
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Replace each placeholder whose variable is in `values` with its value.
+///
+/// Placeholders for other variables are left as written.
+#[must_use]
+pub fn substitute_placeholders(text: &str, values: &HashMap<String, String>) -> String {
+    if values.is_empty() {
+        return text.to_string();
+    }
+    PLACEHOLDER_RE
+        .replace_all(text, |cap: &regex::Captures<'_>| {
+            values
+                .get(&cap["name"])
+                .cloned()
+                .unwrap_or_else(|| cap[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Replace every placeholder with `length` lowercase `x` characters.
+///
+/// The stand-in is valid in a skill name and free of markup, so masked
+/// placeholders only count toward length checks.
+#[must_use]
+pub fn mask_placeholders(text: &str, length: usize) -> String {
+    PLACEHOLDER_RE
+        .replace_all(text, "x".repeat(length).as_str())
+        .into_owned()
+}
+
+/// Returns `true` if `text` contains a placeholder.
+#[must_use]
+pub fn has_placeholders(text: &str) -> bool {
+    PLACEHOLDER_RE.is_match(text)
+}
+
+/// I011: List the placeholders a deploy pipeline must provide.
+///
+/// Returns `None` when `names` is empty.
+#[must_use]
+pub fn placeholder_note(names: &[String]) -> Option<Diagnostic> {
+    if names.is_empty() {
+        return None;
+    }
+    let listed = names
+        .iter()
+        .map(|n| format!("${{{n}}}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(
+        Diagnostic::new(
+            Severity::Info,
+            I011,
+            format!("placeholders to provide at deploy time: {listed}"),
+        )
+        .with_suggestion("Make sure the deploy pipeline sets each of these variables"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_unique_placeholders_in_order() {
+        assert_eq!(
+            find_placeholders("${TEAM}-${TOOL} for ${TEAM}, not $TEAM or ${1X}"),
+            vec!["TEAM", "TOOL"]
+        );
+        assert!(find_placeholders("no placeholders").is_empty());
+    }
+
+    #[test]
+    fn substitutes_known_variables_only() {
+        let values = HashMap::from([("TEAM".to_string(), "data".to_string())]);
+        assert_eq!(
+            substitute_placeholders("${TEAM}-${TOOL}", &values),
+            "data-${TOOL}"
+        );
+    }
+
+    #[test]
+    fn masks_with_nominal_length() {
+        assert_eq!(mask_placeholders("${TEAM}-tool", 3), "xxx-tool");
+        assert!(has_placeholders("${A}"));
+        assert!(!has_placeholders("$A"));
+    }
+
+    #[test]
+    fn note_lists_placeholders() {
+        let note = placeholder_note(&["TEAM".to_string(), "TOOL".to_string()]).unwrap();
+        assert_eq!(note.code, I011);
+        assert_eq!(
+            note.message,
+            "placeholders to provide at deploy time: ${TEAM}, ${TOOL}"
+        );
+        assert!(placeholder_note(&[]).is_none());
+    }
+}
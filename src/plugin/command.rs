@@ -2,7 +2,8 @@
 
 use std::path::Path;
 
-use crate::diagnostics::{Diagnostic, Severity, K001, K002, K003, K004, K005, K006, K007};
+use super::schema::PluginSchema;
+use crate::diagnostics::{Diagnostic, Severity, K001, K002, K003, K004, K005, K006, K007, K008};
 
 /// Valid model values for commands (no `inherit`).
 const VALID_MODELS: &[&str] = &["sonnet", "opus", "haiku"];
@@ -115,9 +116,20 @@ const COMMON_VERBS: &[&str] = &[
 /// Validate a command `.md` file at the given path.
 ///
 /// Commands have optional frontmatter. Returns a list of diagnostics.
-/// Never panics — parse errors are reported as diagnostics.
+/// Never panics — parse errors are reported as diagnostics. Frontmatter
+/// fields are checked against the latest known schema; see
+/// [`validate_command_with`].
 #[must_use]
 pub fn validate_command(path: &Path) -> Vec<Diagnostic> {
+    validate_command_with(path, &PluginSchema::Latest)
+}
+
+/// Validate a command `.md` file against a specific plugin schema.
+///
+/// Unknown frontmatter fields (K008) are errors under a strict schema and
+/// warnings under a lenient one.
+#[must_use]
+pub fn validate_command_with(path: &Path, schema: &PluginSchema) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
     let content = match crate::parser::read_file_checked(path) {
@@ -149,6 +161,24 @@ pub fn validate_command(path: &Path) -> Vec<Diagnostic> {
 
     // Only validate frontmatter fields that are present
     if has_frontmatter {
+        // K008: Unknown frontmatter field (sorted for stable output)
+        let known_fields = schema.table().command_fields;
+        let mut unknown: Vec<&String> = metadata
+            .keys()
+            .filter(|k| !known_fields.contains(&k.as_str()))
+            .collect();
+        unknown.sort();
+        for field in unknown {
+            diags.push(
+                Diagnostic::new(
+                    schema.unknown_severity(),
+                    K008,
+                    format!("unknown field `{field}`{}", schema.unknown_note()),
+                )
+                .with_suggestion(format!("Valid fields: {}", known_fields.join(", "))),
+            );
+        }
+
         // K002: Description exceeds 60 chars
         if let Some(desc_val) = metadata.get("description") {
             if let Some(desc) = desc_val.as_str() {
@@ -407,4 +437,48 @@ mod tests {
         let diags = validate_command(Path::new("/nonexistent/command.md"));
         assert!(diags.iter().any(|d| d.code == K001));
     }
+
+    // ── Schema versions ──
+
+    const MIXED_FIELDS: &str =
+        "---\ndescription: Run tests\nfuture-field: yes\n---\nRun the test suite.\n";
+
+    #[test]
+    fn known_fields_no_k008() {
+        let (_dir, path) = write_command(
+            "---\ndescription: Run tests\nargument-hint: \"[file]\"\nallowed-tools: Bash\n\
+             model: haiku\ndisable-model-invocation: true\n---\nRun the test suite.\n",
+        );
+        let diags = validate_command(&path);
+        assert!(!diags.iter().any(|d| d.code == K008), "{diags:?}");
+    }
+
+    #[test]
+    fn unknown_field_is_error_under_latest_schema() {
+        let (_dir, path) = write_command(MIXED_FIELDS);
+        let diags = validate_command_with(&path, &PluginSchema::Latest);
+        let unknown: Vec<_> = diags.iter().filter(|d| d.code == K008).collect();
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_error());
+        assert!(unknown[0].message.contains("`future-field`"));
+    }
+
+    #[test]
+    fn unknown_field_is_warning_under_permissive_schema() {
+        let (_dir, path) = write_command(MIXED_FIELDS);
+        let diags = validate_command_with(&path, &PluginSchema::Permissive);
+        let unknown: Vec<_> = diags.iter().filter(|d| d.code == K008).collect();
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_warning());
+        assert!(unknown[0].message.contains("possibly from a newer schema"));
+    }
+
+    #[test]
+    fn unknown_field_is_error_under_version_without_a_table() {
+        let (_dir, path) = write_command(MIXED_FIELDS);
+        let diags = validate_command_with(&path, &PluginSchema::Version(99, 0, 0));
+        let unknown: Vec<_> = diags.iter().filter(|d| d.code == K008).collect();
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_error());
+    }
 }
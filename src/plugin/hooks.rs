@@ -4,23 +4,11 @@ use std::path::Path;
 
 use serde::Deserialize;
 
+use super::schema::PluginSchema;
 use crate::diagnostics::{
     Diagnostic, Severity, H001, H002, H003, H004, H005, H006, H007, H008, H009, H010, H011,
 };
 
-/// Valid Claude Code hook event names.
-const VALID_EVENTS: &[&str] = &[
-    "PreToolUse",
-    "PostToolUse",
-    "Stop",
-    "SubagentStop",
-    "SessionStart",
-    "SessionEnd",
-    "UserPromptSubmit",
-    "PreCompact",
-    "Notification",
-];
-
 /// Valid hook types.
 const VALID_HOOK_TYPES: &[&str] = &["command", "prompt"];
 
@@ -53,10 +41,21 @@ pub struct HookEntry {
 /// Validate a `hooks.json` file at the given path.
 ///
 /// Returns a list of diagnostics. Never panics — parse errors are reported
-/// as H001/H002 diagnostics.
+/// as H001/H002 diagnostics. Event names are checked against the latest
+/// known schema; see [`validate_hooks_with`].
 #[must_use]
 pub fn validate_hooks(path: &Path) -> Vec<Diagnostic> {
+    validate_hooks_with(path, &PluginSchema::Latest)
+}
+
+/// Validate a `hooks.json` file against a specific plugin schema.
+///
+/// Unknown event names (H003) are errors under a strict schema and warnings
+/// under a lenient one.
+#[must_use]
+pub fn validate_hooks_with(path: &Path, schema: &PluginSchema) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
+    let valid_events = schema.table().hook_events;
 
     // Read file
     let content = match crate::parser::read_file_checked(path) {
@@ -100,14 +99,17 @@ pub fn validate_hooks(path: &Path) -> Vec<Diagnostic> {
 
     for (event_name, entries) in &events {
         // H003: Unknown event name
-        if !VALID_EVENTS.contains(&event_name.as_str()) {
+        if !valid_events.contains(&event_name.as_str()) {
             diags.push(
                 Diagnostic::new(
-                    Severity::Error,
+                    schema.unknown_severity(),
                     H003,
-                    format!("unknown event name: \"{event_name}\""),
+                    format!(
+                        "unknown event name: \"{event_name}\"{}",
+                        schema.unknown_note()
+                    ),
                 )
-                .with_suggestion(format!("Valid events: {}", VALID_EVENTS.join(", "))),
+                .with_suggestion(format!("Valid events: {}", valid_events.join(", "))),
             );
         }
 
@@ -383,7 +385,7 @@ mod tests {
 
     #[test]
     fn all_valid_events_accepted() {
-        for event in VALID_EVENTS {
+        for event in PluginSchema::Latest.table().hook_events {
             let json = format!(
                 r#"{{ "{event}": [{{ "hooks": [{{ "type": "command", "command": "echo" }}] }}] }}"#
             );
@@ -395,4 +397,55 @@ mod tests {
             );
         }
     }
+
+    // ── Schema versions ──
+
+    const MIXED_EVENTS: &str = r#"{
+        "PreToolUse": [{ "hooks": [{ "type": "command", "command": "echo" }] }],
+        "FutureEvent": [{ "hooks": [{ "type": "command", "command": "echo" }] }]
+    }"#;
+
+    fn h003(diags: &[Diagnostic]) -> Vec<&Diagnostic> {
+        diags.iter().filter(|d| d.code == H003).collect()
+    }
+
+    #[test]
+    fn unknown_event_is_error_under_latest_schema() {
+        let (_dir, path) = write_hooks(MIXED_EVENTS);
+        let diags = validate_hooks_with(&path, &PluginSchema::Latest);
+        let unknown = h003(&diags);
+        assert_eq!(unknown.len(), 1, "only FutureEvent is unknown: {diags:?}");
+        assert!(unknown[0].is_error());
+        assert!(!unknown[0].message.contains("newer schema"));
+    }
+
+    #[test]
+    fn unknown_event_is_error_under_known_version() {
+        let (_dir, path) = write_hooks(MIXED_EVENTS);
+        let diags = validate_hooks_with(&path, &PluginSchema::Version(0, 0, 0));
+        let unknown = h003(&diags);
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_error());
+    }
+
+    #[test]
+    fn unknown_event_is_warning_under_permissive_schema() {
+        let (_dir, path) = write_hooks(MIXED_EVENTS);
+        let diags = validate_hooks_with(&path, &PluginSchema::Permissive);
+        let unknown = h003(&diags);
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_warning());
+        assert!(unknown[0].message.contains("possibly from a newer schema"));
+        assert!(!diags.iter().any(Diagnostic::is_error));
+    }
+
+    #[test]
+    fn unknown_event_is_error_under_version_without_a_table() {
+        let (_dir, path) = write_hooks(MIXED_EVENTS);
+        let diags = validate_hooks_with(&path, &PluginSchema::Version(99, 0, 0));
+        let unknown = h003(&diags);
+        assert_eq!(unknown.len(), 1, "{diags:?}");
+        assert!(unknown[0].is_error());
+        assert!(!unknown[0].message.contains("newer schema"));
+    }
 }
@@ -6,9 +6,12 @@ use std::sync::LazyLock;
 use regex::Regex;
 use serde::Deserialize;
 
+use super::schema::PluginSchema;
 use crate::diagnostics::{
-    Diagnostic, Severity, P001, P002, P003, P004, P005, P006, P007, P008, P009, P010, P011,
+    Diagnostic, Severity, P001, P002, P003, P004, P005, P006, P007, P008, P009, P010, P011, P012,
+    P013,
 };
+use crate::errors::Result;
 
 /// Regex for valid kebab-case names: lowercase letters, digits, hyphens.
 static KEBAB_CASE_RE: LazyLock<Regex> =
@@ -82,9 +85,22 @@ pub struct PluginManifest {
     /// LSP server configuration path.
     #[serde(rename = "lspServers")]
     pub lsp_servers: Option<String>,
+    /// Minimum Claude Code version the plugin targets.
+    #[serde(rename = "minClaudeCodeVersion")]
+    pub min_claude_code_version: Option<String>,
 }
 
 impl PluginManifest {
+    /// Plugin schema selected by `minClaudeCodeVersion`, if it is set and valid.
+    #[must_use]
+    pub fn schema(&self) -> Option<PluginSchema> {
+        let declared = self.min_claude_code_version.as_deref()?;
+        match declared.parse() {
+            Ok(schema @ PluginSchema::Version(..)) => Some(schema),
+            _ => None,
+        }
+    }
+
     /// Return path overrides as (field_name, path_value) pairs.
     fn path_overrides(&self) -> Vec<(&'static str, &str)> {
         let string_fields: [(&str, &Option<String>); 6] = [
@@ -209,6 +225,21 @@ pub fn validate_manifest(path: &Path) -> Vec<Diagnostic> {
         }
     }
 
+    // P012: minClaudeCodeVersion not a version
+    if let Some(declared) = &manifest.min_claude_code_version {
+        if manifest.schema().is_none() {
+            diags.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    P012,
+                    format!("`minClaudeCodeVersion` is not a valid version: \"{declared}\""),
+                )
+                .with_field("minClaudeCodeVersion")
+                .with_suggestion("Use x.y.z format (e.g., \"2.0.0\")"),
+            );
+        }
+    }
+
     // P005: description empty or missing
     match &manifest.description {
         Some(d) if d.trim().is_empty() => {
@@ -314,6 +345,70 @@ pub fn validate_manifest(path: &Path) -> Vec<Diagnostic> {
     diags
 }
 
+/// Plugin schema declared by the `plugin.json` at the given path.
+///
+/// Returns `None` if the file cannot be read or parsed, or does not set a
+/// valid `minClaudeCodeVersion`.
+#[must_use]
+pub fn declared_schema(path: &Path) -> Option<PluginSchema> {
+    let content = crate::parser::read_file_checked(path).ok()?;
+    let manifest: PluginManifest = serde_json::from_str(&content).ok()?;
+    manifest.schema()
+}
+
+/// Read a registry of known plugin names.
+///
+/// The registry is a plain text file with one plugin name per line. Blank
+/// lines and lines starting with `#` are ignored; surrounding whitespace is
+/// trimmed.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn read_plugin_registry(path: &Path) -> Result<Vec<String>> {
+    let content = crate::parser::read_file_checked(path)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Check the `plugin.json` name at `path` against a registry of known
+/// plugin names (see [`read_plugin_registry`]).
+///
+/// Reports P013 (warning) when the name matches a registry entry, ignoring
+/// ASCII case. The check is advisory: a shared name confuses installs from
+/// a marketplace but does not break the plugin. Returns no diagnostics if
+/// the manifest cannot be read or has no name; [`validate_manifest`]
+/// reports those problems.
+#[must_use]
+pub fn check_registry_collisions(path: &Path, registry: &[String]) -> Vec<Diagnostic> {
+    let Ok(content) = crate::parser::read_file_checked(path) else {
+        return vec![];
+    };
+    let Some(name) = serde_json::from_str::<PluginManifest>(&content)
+        .ok()
+        .and_then(|m| m.name)
+    else {
+        return vec![];
+    };
+    if !registry
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(&name))
+    {
+        return vec![];
+    }
+    vec![Diagnostic::new(
+        Severity::Warning,
+        P013,
+        format!("plugin name `{name}` collides with a known plugin in the registry"),
+    )
+    .with_field("name")
+    .with_suggestion("Choose a distinct name so users do not install the wrong plugin")]
+}
+
 /// Recursively scan all string values in a JSON tree for credential patterns.
 ///
 /// Tracks the JSON path for actionable diagnostic messages.
@@ -323,24 +418,22 @@ fn scan_credentials(
     path: &mut Vec<String>,
 ) {
     match value {
-        serde_json::Value::String(s) => {
-            if CREDENTIAL_RE.is_match(s) {
-                let location = if path.is_empty() {
-                    String::new()
-                } else {
-                    format!(" at `{}`", path.join(""))
-                };
-                diags.push(
-                    Diagnostic::new(
-                        Severity::Error,
-                        P008,
-                        format!("possible hardcoded credential detected{location}"),
-                    )
-                    .with_suggestion(
-                        "Use environment variables or a secrets manager instead of inline credentials",
-                    ),
-                );
-            }
+        serde_json::Value::String(s) if CREDENTIAL_RE.is_match(s) => {
+            let location = if path.is_empty() {
+                String::new()
+            } else {
+                format!(" at `{}`", path.join(""))
+            };
+            diags.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    P008,
+                    format!("possible hardcoded credential detected{location}"),
+                )
+                .with_suggestion(
+                    "Use environment variables or a secrets manager instead of inline credentials",
+                ),
+            );
         }
         serde_json::Value::Object(map) => {
             for (key, v) in map {
@@ -642,4 +735,62 @@ mod tests {
         // Should NOT trigger P011 since it's caught by P006 first
         assert!(!diags.iter().any(|d| d.code == P011));
     }
+
+    // ── Declared schema ──
+
+    #[test]
+    fn declared_min_version_selects_schema() {
+        let (_dir, path) = write_manifest(
+            r#"{ "name": "my-plugin", "description": "d", "minClaudeCodeVersion": "1.0.3" }"#,
+        );
+        assert_eq!(declared_schema(&path), Some(PluginSchema::Version(1, 0, 3)));
+        assert!(!validate_manifest(&path).iter().any(|d| d.code == P012));
+    }
+
+    #[test]
+    fn absent_min_version_declares_no_schema() {
+        let (_dir, path) = write_manifest(r#"{ "name": "my-plugin", "description": "d" }"#);
+        assert_eq!(declared_schema(&path), None);
+    }
+
+    #[test]
+    fn invalid_min_version_p012() {
+        let (_dir, path) = write_manifest(
+            r#"{ "name": "my-plugin", "description": "d", "minClaudeCodeVersion": "latest" }"#,
+        );
+        assert_eq!(declared_schema(&path), None);
+        let diags = validate_manifest(&path);
+        let p012: Vec<_> = diags.iter().filter(|d| d.code == P012).collect();
+        assert_eq!(p012.len(), 1, "{diags:?}");
+        assert!(p012[0].is_warning());
+    }
+
+    // ── P013: registry collisions ─────────────────────────────────────
+
+    #[test]
+    fn read_plugin_registry_skips_blanks_and_comments() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("registry.txt");
+        fs::write(&path, "# known plugins\n\n  alpha  \nbeta\n").unwrap();
+        assert_eq!(read_plugin_registry(&path).unwrap(), vec!["alpha", "beta"]);
+        assert!(read_plugin_registry(&dir.path().join("missing.txt")).is_err());
+    }
+
+    #[test]
+    fn registry_collision_p013() {
+        let (_dir, path) = write_manifest(r#"{ "name": "my-plugin" }"#);
+        let registry = vec!["other".to_string(), "My-Plugin".to_string()];
+        let diags = check_registry_collisions(&path, &registry);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, P013);
+        assert!(diags[0].is_warning());
+    }
+
+    #[test]
+    fn registry_without_name_is_clean() {
+        let (_dir, path) = write_manifest(r#"{ "name": "my-plugin" }"#);
+        assert!(check_registry_collisions(&path, &["other".to_string()]).is_empty());
+        let (_dir, path) = write_manifest(r#"{ "description": "no name" }"#);
+        assert!(check_registry_collisions(&path, &["my-plugin".to_string()]).is_empty());
+    }
 }
@@ -6,9 +6,14 @@ pub mod command;
 pub mod cross;
 pub mod hooks;
 pub mod manifest;
+pub mod schema;
 
 pub use agent::validate_agent;
-pub use command::validate_command;
+pub use command::{validate_command, validate_command_with};
 pub use cross::validate_cross_component;
-pub use hooks::validate_hooks;
-pub use manifest::{validate_manifest, PluginManifest};
+pub use hooks::{validate_hooks, validate_hooks_with};
+pub use manifest::{
+    check_registry_collisions, declared_schema, read_plugin_registry, validate_manifest,
+    PluginManifest,
+};
+pub use schema::{PluginSchema, SchemaTable, BASELINE_SCHEMA, LATEST_SCHEMA, SCHEMA_TABLES};
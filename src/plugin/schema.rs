@@ -0,0 +1,218 @@
+//! Claude Code plugin schema versions.
+//!
+//! The set of `hooks.json` event names and command frontmatter fields grows
+//! with Claude Code releases. Each [`SchemaTable`] lists what one release
+//! accepts; [`PluginSchema`] selects the table the hook and command
+//! validators check against, and whether names missing from it are errors
+//! or only warnings.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostics::Severity;
+use crate::errors::AigentError;
+
+/// Hook events and command fields known as of one Claude Code release.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SchemaTable {
+    /// First Claude Code release this table applies to.
+    pub version: (u32, u32, u32),
+    /// Valid `hooks.json` event names.
+    pub hook_events: &'static [&'static str],
+    /// Valid command frontmatter fields.
+    pub command_fields: &'static [&'static str],
+}
+
+/// The event names and command fields aigent validated before schema
+/// versions existed, applying from the first release.
+///
+/// No release boundaries for these names are recorded yet, so this is the
+/// only table and every declared Claude Code version is checked strictly
+/// against it (see [`PluginSchema::is_lenient`]). A table for a later
+/// release belongs in [`SCHEMA_TABLES`] only with the release notes that
+/// introduced its names.
+pub const BASELINE_SCHEMA: SchemaTable = SchemaTable {
+    version: (0, 0, 0),
+    hook_events: &[
+        "PreToolUse",
+        "PostToolUse",
+        "Stop",
+        "SubagentStop",
+        "SessionStart",
+        "SessionEnd",
+        "UserPromptSubmit",
+        "PreCompact",
+        "Notification",
+    ],
+    command_fields: &[
+        "description",
+        "argument-hint",
+        "allowed-tools",
+        "model",
+        "disable-model-invocation",
+    ],
+};
+
+/// The newest known table, used by [`PluginSchema::Latest`] and
+/// [`PluginSchema::Permissive`].
+pub const LATEST_SCHEMA: &SchemaTable = &BASELINE_SCHEMA;
+
+/// Known schema tables, oldest first; the last is [`LATEST_SCHEMA`].
+pub const SCHEMA_TABLES: &[SchemaTable] = &[BASELINE_SCHEMA];
+
+/// Which schema the plugin validators check against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PluginSchema {
+    /// The newest known table; unknown names are errors.
+    #[default]
+    Latest,
+    /// The newest known table; unknown names are warnings.
+    Permissive,
+    /// The newest known table at or before a given Claude Code release;
+    /// unknown names are errors. A release no table matches more closely
+    /// than the newest one is checked strictly against it, not guessed at.
+    Version(u32, u32, u32),
+}
+
+impl PluginSchema {
+    /// The table to check against.
+    #[must_use]
+    pub fn table(&self) -> &'static SchemaTable {
+        match *self {
+            Self::Latest | Self::Permissive => LATEST_SCHEMA,
+            Self::Version(major, minor, patch) => SCHEMA_TABLES
+                .iter()
+                .rev()
+                .find(|t| t.version <= (major, minor, patch))
+                .unwrap_or(LATEST_SCHEMA),
+        }
+    }
+
+    /// Returns `true` if names missing from the table are only warnings.
+    #[must_use]
+    pub fn is_lenient(&self) -> bool {
+        match *self {
+            Self::Latest | Self::Version(..) => false,
+            Self::Permissive => true,
+        }
+    }
+
+    /// Severity for an event name or field missing from the table.
+    pub(crate) fn unknown_severity(&self) -> Severity {
+        if self.is_lenient() {
+            Severity::Warning
+        } else {
+            Severity::Error
+        }
+    }
+
+    /// Message suffix for an unknown name: empty in strict mode, a
+    /// forward-compatibility note otherwise.
+    pub(crate) fn unknown_note(&self) -> String {
+        if self.is_lenient() {
+            let (major, minor, patch) = self.table().version;
+            format!(" (possibly from a newer schema than {major}.{minor}.{patch})")
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl FromStr for PluginSchema {
+    type Err = AigentError;
+
+    /// Parse `latest`, `permissive`, or a version such as `2`, `2.1`, or `2.1.0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "latest" => return Ok(Self::Latest),
+            "permissive" => return Ok(Self::Permissive),
+            _ => {}
+        }
+        let invalid = || AigentError::Parse {
+            message: format!(
+                "invalid plugin schema \"{s}\" (expected a version, `latest`, or `permissive`)"
+            ),
+        };
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(invalid());
+        }
+        let mut numbers = [0u32; 3];
+        for (slot, part) in numbers.iter_mut().zip(&parts) {
+            *slot = part.parse().map_err(|_| invalid())?;
+        }
+        Ok(Self::Version(numbers[0], numbers[1], numbers[2]))
+    }
+}
+
+impl fmt::Display for PluginSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latest => write!(f, "latest"),
+            Self::Permissive => write!(f, "permissive"),
+            Self::Version(major, minor, patch) => write!(f, "{major}.{minor}.{patch}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_are_ordered_oldest_first() {
+        for pair in SCHEMA_TABLES.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+        assert_eq!(SCHEMA_TABLES.last(), Some(LATEST_SCHEMA));
+    }
+
+    #[test]
+    fn parses_keywords_and_versions() {
+        assert_eq!(
+            "latest".parse::<PluginSchema>().unwrap(),
+            PluginSchema::Latest
+        );
+        assert_eq!(
+            "permissive".parse::<PluginSchema>().unwrap(),
+            PluginSchema::Permissive
+        );
+        assert_eq!(
+            "2".parse::<PluginSchema>().unwrap(),
+            PluginSchema::Version(2, 0, 0)
+        );
+        assert_eq!(
+            "1.4.2".parse::<PluginSchema>().unwrap(),
+            PluginSchema::Version(1, 4, 2)
+        );
+        for bad in ["", "v2", "1.2.3.4", "1.x", "newest"] {
+            assert!(bad.parse::<PluginSchema>().is_err(), "{bad:?} should fail");
+        }
+    }
+
+    #[test]
+    fn version_selects_newest_table_not_after_it() {
+        assert_eq!(PluginSchema::Version(0, 0, 0).table(), &BASELINE_SCHEMA);
+        assert_eq!(PluginSchema::Version(2, 1, 0).table(), &BASELINE_SCHEMA);
+        assert!(!PluginSchema::Version(0, 0, 0).is_lenient());
+    }
+
+    #[test]
+    fn version_newer_than_known_tables_falls_back_to_strict() {
+        let schema = PluginSchema::Version(99, 0, 0);
+        assert!(!schema.is_lenient());
+        assert_eq!(schema.table(), LATEST_SCHEMA);
+        assert_eq!(schema.unknown_severity(), Severity::Error);
+        assert!(schema.unknown_note().is_empty());
+    }
+
+    #[test]
+    fn latest_is_strict_and_permissive_is_lenient() {
+        assert_eq!(PluginSchema::Latest.unknown_severity(), Severity::Error);
+        assert!(PluginSchema::Latest.unknown_note().is_empty());
+        assert_eq!(
+            PluginSchema::Permissive.unknown_severity(),
+            Severity::Warning
+        );
+    }
+}
@@ -1,5 +1,9 @@
+use std::io::Write;
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
 use crate::parser::{find_skill_md, read_properties};
 use crate::validator::DiscoveryWarning;
 
@@ -16,8 +20,71 @@ pub struct SkillEntry {
     pub description: String,
     /// Absolute path to the SKILL.md file.
     pub location: String,
+    /// Prompt priority from `metadata.priority` (0 when unset).
+    pub priority: i64,
+    /// Tools from `allowed-tools`, in declaration order.
+    pub allowed_tools: Vec<String>,
+    /// Revision of the SKILL.md file, when collected with
+    /// [`CollectOptions::provenance`].
+    pub provenance: Option<Provenance>,
+}
+
+/// The exact `SKILL.md` revision a skill entry was collected from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Lowercase hex SHA-256 of the file's bytes.
+    pub sha256: String,
+    /// Last-modified time in seconds since the Unix epoch, if the platform
+    /// reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<u64>,
+}
+
+impl Provenance {
+    /// Hash the file at `path` and read its modification time.
+    ///
+    /// The file is streamed through the hasher rather than loaded whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be opened or read.
+    pub fn of_file(path: &Path) -> std::io::Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        let mtime = file
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        let sha256 = crate::hash::to_hex(&hasher.finalize());
+        Ok(Self { sha256, mtime })
+    }
+
+    /// Compact `sha256:<hex>/mtime:<secs>` form, used in markdown comments.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self.mtime {
+            Some(mtime) => format!("sha256:{}/mtime:{mtime}", self.sha256),
+            None => format!("sha256:{}", self.sha256),
+        }
+    }
+}
+
+/// Skill entries from one labeled source, rendered as a section of a
+/// grouped prompt.
+#[derive(Debug, Clone)]
+pub struct SkillGroup {
+    /// Source label, such as `plugin` or `org-library`.
+    pub label: String,
+    /// Entries collected from the source's directories.
+    pub entries: Vec<SkillEntry>,
 }
 
+/// Label of the group holding directories given without a label.
+pub const DEFAULT_GROUP: &str = "default";
+
 /// Output format for prompt generation.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum PromptFormat {
@@ -32,26 +99,147 @@ pub enum PromptFormat {
     Markdown,
 }
 
+/// Agent environment a prompt is shaped for.
+///
+/// A target adds instructions telling the model how to use the listed
+/// skills, since the bare API and Claude Code invoke skills differently.
+/// Prompts written without a target (as by [`write_entries`]) list the
+/// skills only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptTarget {
+    /// The bare API: the model reads a skill's `SKILL.md` itself.
+    Standard,
+    /// Claude Code: skills are invoked with the Skill tool, or by the user
+    /// as `/<name>` slash commands. Each entry gets an `invocation` hint.
+    ClaudeCode,
+}
+
+impl PromptTarget {
+    /// Identifier of the target (`standard` or `claude-code`).
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::ClaudeCode => "claude-code",
+        }
+    }
+
+    /// Instructions on how to use the listed skills.
+    #[must_use]
+    pub fn instructions(self) -> &'static str {
+        match self {
+            Self::Standard => {
+                "When a task matches a skill's description, read the SKILL.md file at the \
+                 skill's location and follow its instructions. Read other files the skill \
+                 references only when the task needs them."
+            }
+            Self::ClaudeCode => {
+                "When a task matches a skill's description, invoke the skill with the Skill \
+                 tool. Users can also run a skill as a slash command, given as its \
+                 invocation. Each skill's instructions are in the SKILL.md file at its \
+                 location."
+            }
+        }
+    }
+
+    /// How a skill named `name` is invoked, if the target has a convention
+    /// for it.
+    #[must_use]
+    pub fn invocation(self, name: &str) -> Option<String> {
+        match self {
+            Self::Standard => None,
+            Self::ClaudeCode => Some(format!("/{name}")),
+        }
+    }
+}
+
 /// Escape all five XML predefined entities: `& < > " '`.
 ///
-/// Ampersand is escaped first to prevent double-escaping of other
-/// entity references.
+/// Characters that XML 1.0 does not allow at all (control characters other
+/// than tab, newline, and carriage return, and the noncharacters U+FFFE and
+/// U+FFFF) are dropped, since no escape makes them valid. The result is safe
+/// in both text content and quoted attribute values.
 #[must_use]
 pub fn xml_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Order of entries in a generated prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PromptSort {
+    /// The order the skill directories were given in (default).
+    #[default]
+    Input,
+    /// Alphabetical by skill name.
+    Name,
+    /// Highest `metadata.priority` first, ties broken by name.
+    Priority,
+}
+
+/// Reorder `entries` for prompt output.
+///
+/// Sorting is stable, so entries that compare equal keep their input order.
+pub fn sort_entries(entries: &mut [SkillEntry], sort: PromptSort) {
+    match sort {
+        PromptSort::Input => {}
+        PromptSort::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
+        PromptSort::Priority => entries.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.name.cmp(&b.name))
+        }),
+    }
+}
+
+/// Remove entries whose name is in `names`.
+///
+/// Returns the names that matched at least one entry, in `names` order, so
+/// callers can report the ones that matched nothing.
+pub fn exclude_entries<'a>(entries: &mut Vec<SkillEntry>, names: &'a [String]) -> Vec<&'a str> {
+    let matched: Vec<&str> = names
+        .iter()
+        .filter(|name| entries.iter().any(|e| &e.name == *name))
+        .map(String::as_str)
+        .collect();
+    entries.retain(|e| !names.contains(&e.name));
+    matched
+}
+
+/// Options for collecting skill entries.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollectOptions {
+    /// Keep skills marked `metadata.deprecated: true` (skipped by default).
+    pub include_deprecated: bool,
+    /// Record each entry's [`Provenance`] (off by default).
+    pub provenance: bool,
 }
 
 /// Collect skill entries from a list of directories.
 ///
 /// Each directory is canonicalized to an absolute path, then its SKILL.md
 /// is read and parsed. Directories that cannot be canonicalized or parsed
-/// are silently skipped.
+/// are silently skipped, as are deprecated skills.
 #[must_use]
 pub fn collect_skills(dirs: &[&Path]) -> Vec<SkillEntry> {
+    collect_skills_with(dirs, &CollectOptions::default())
+}
+
+/// Collect skill entries from a list of directories with explicit options.
+#[must_use]
+pub fn collect_skills_with(dirs: &[&Path], opts: &CollectOptions) -> Vec<SkillEntry> {
     let mut entries = Vec::new();
 
     for dir in dirs {
@@ -64,16 +252,33 @@ pub fn collect_skills(dirs: &[&Path]) -> Vec<SkillEntry> {
             Ok(p) => p,
             Err(_) => continue,
         };
+        if props.is_deprecated() && !opts.include_deprecated {
+            continue;
+        }
 
-        let location = match find_skill_md(&canonical) {
-            Some(p) => p.to_string_lossy().to_string(),
-            None => continue,
+        let Some(skill_md) = find_skill_md(&canonical) else {
+            continue;
+        };
+        let provenance = if opts.provenance {
+            match Provenance::of_file(&skill_md) {
+                Ok(p) => Some(p),
+                Err(_) => continue,
+            }
+        } else {
+            None
         };
 
         entries.push(SkillEntry {
+            priority: props.priority(),
+            allowed_tools: props
+                .allowed_tools_list()
+                .into_iter()
+                .map(String::from)
+                .collect(),
             name: props.name,
             description: props.description,
-            location,
+            location: skill_md.to_string_lossy().to_string(),
+            provenance,
         });
     }
 
@@ -83,11 +288,22 @@ pub fn collect_skills(dirs: &[&Path]) -> Vec<SkillEntry> {
 /// Collect skill entries from directories, collecting warnings for skills that could not be parsed.
 ///
 /// Returns `(entries, warnings)`. The original [`collect_skills()`] function
-/// remains unchanged for backward compatibility.
+/// remains unchanged for backward compatibility. Deprecated skills are
+/// skipped and reported in a single warning with their count.
 #[must_use]
 pub fn collect_skills_verbose(dirs: &[&Path]) -> (Vec<SkillEntry>, Vec<DiscoveryWarning>) {
+    collect_skills_verbose_with(dirs, &CollectOptions::default())
+}
+
+/// Collect skill entries and warnings from directories with explicit options.
+#[must_use]
+pub fn collect_skills_verbose_with(
+    dirs: &[&Path],
+    opts: &CollectOptions,
+) -> (Vec<SkillEntry>, Vec<DiscoveryWarning>) {
     let mut entries = Vec::new();
     let mut warnings = Vec::new();
+    let mut deprecated: Vec<(std::path::PathBuf, String)> = Vec::new();
 
     for dir in dirs {
         let canonical = match std::fs::canonicalize(dir) {
@@ -112,22 +328,86 @@ pub fn collect_skills_verbose(dirs: &[&Path]) -> (Vec<SkillEntry>, Vec<Discovery
             }
         };
 
+        if props.is_deprecated() && !opts.include_deprecated {
+            deprecated.push((canonical, props.name));
+            continue;
+        }
+
         // find_skill_md is called again (read_properties calls it internally),
         // but we need the actual path for the location field.
-        let location = find_skill_md(&canonical)
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_else(|| canonical.join("SKILL.md").to_string_lossy().to_string());
+        let skill_md = find_skill_md(&canonical).unwrap_or_else(|| canonical.join("SKILL.md"));
+        let provenance = if opts.provenance {
+            match Provenance::of_file(&skill_md) {
+                Ok(p) => Some(p),
+                Err(e) => {
+                    warnings.push(DiscoveryWarning {
+                        path: skill_md,
+                        message: format!("cannot hash SKILL.md: {e}"),
+                    });
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+        let location = skill_md.to_string_lossy().to_string();
 
         entries.push(SkillEntry {
+            priority: props.priority(),
+            allowed_tools: props
+                .allowed_tools_list()
+                .into_iter()
+                .map(String::from)
+                .collect(),
             name: props.name,
             description: props.description,
             location,
+            provenance,
+        });
+    }
+
+    if let Some((first, _)) = deprecated.first() {
+        let names: Vec<&str> = deprecated.iter().map(|(_, n)| n.as_str()).collect();
+        let noun = if names.len() == 1 { "skill" } else { "skills" };
+        warnings.push(DiscoveryWarning {
+            path: first.parent().unwrap_or(first).to_path_buf(),
+            message: format!(
+                "skipped {} deprecated {noun}: {}",
+                names.len(),
+                names.join(", ")
+            ),
         });
     }
 
     (entries, warnings)
 }
 
+/// Collect skill entries and warnings for labeled sources.
+///
+/// Returns one [`SkillGroup`] per distinct label, in the order labels first
+/// appear; directories listed under a repeated label join its group.
+/// Entries within a group keep their directory order.
+#[must_use]
+pub fn collect_skills_labeled(
+    sources: &[(&str, &[&Path])],
+    opts: &CollectOptions,
+) -> (Vec<SkillGroup>, Vec<DiscoveryWarning>) {
+    let mut groups: Vec<SkillGroup> = Vec::new();
+    let mut warnings = Vec::new();
+    for (label, dirs) in sources {
+        let (entries, source_warnings) = collect_skills_verbose_with(dirs, opts);
+        warnings.extend(source_warnings);
+        match groups.iter_mut().find(|g| g.label == *label) {
+            Some(group) => group.entries.extend(entries),
+            None => groups.push(SkillGroup {
+                label: (*label).to_string(),
+                entries,
+            }),
+        }
+    }
+    (groups, warnings)
+}
+
 /// Generate an `<available_skills>` XML block from skill directories.
 ///
 /// Each directory is canonicalized to an absolute path, then its SKILL.md
@@ -152,7 +432,7 @@ pub fn collect_skills_verbose(dirs: &[&Path]) -> (Vec<SkillEntry>, Vec<Discovery
 #[must_use]
 pub fn to_prompt(dirs: &[&Path]) -> String {
     let entries = collect_skills(dirs);
-    format_xml(&entries)
+    format_entries(&entries, PromptFormat::Xml)
 }
 
 /// Generate prompt output in the specified format.
@@ -168,14 +448,144 @@ pub fn to_prompt_format(dirs: &[&Path], format: PromptFormat) -> String {
 /// Format pre-collected skill entries in the specified output format.
 ///
 /// Use this with [`collect_skills_verbose`] when you need access to both the
-/// formatted output and any discovery warnings.
+/// formatted output and any discovery warnings. Builds the whole prompt in
+/// memory; use [`write_entries`] to stream large collections instead.
 #[must_use]
 pub fn format_entries(entries: &[SkillEntry], format: PromptFormat) -> String {
+    let mut out = Vec::new();
+    // Writing to a Vec cannot fail, and every renderer emits UTF-8.
+    let _ = write_entries(entries, format, &mut out);
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Write skill entries in the specified output format to `out`.
+///
+/// Emits one entry at a time, so memory use does not grow with the size of
+/// the collection. The bytes written are identical to [`format_entries`].
+///
+/// # Errors
+///
+/// Returns any I/O error from `out`.
+pub fn write_entries(
+    entries: &[SkillEntry],
+    format: PromptFormat,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    write_entries_inner(entries, format, None, out)
+}
+
+/// Format skill entries with invocation instructions for `target`.
+///
+/// Builds the whole prompt in memory; use [`write_entries_for`] to stream
+/// large collections instead.
+#[must_use]
+pub fn format_entries_for(
+    entries: &[SkillEntry],
+    format: PromptFormat,
+    target: PromptTarget,
+) -> String {
+    let mut out = Vec::new();
+    // Writing to a Vec cannot fail, and every renderer emits UTF-8.
+    let _ = write_entries_for(entries, format, target, &mut out);
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Write skill entries with invocation instructions for `target` to `out`.
+///
+/// Like [`write_entries`], plus the target's
+/// [`instructions`](PromptTarget::instructions) and, where the target has
+/// one, each skill's [`invocation`](PromptTarget::invocation). JSON output
+/// becomes an object holding the target, the instructions, and the `skills`
+/// array.
+///
+/// # Errors
+///
+/// Returns any I/O error from `out`.
+pub fn write_entries_for(
+    entries: &[SkillEntry],
+    format: PromptFormat,
+    target: PromptTarget,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    write_entries_inner(entries, format, Some(target), out)
+}
+
+/// Format skill groups in the specified output format.
+///
+/// Builds the whole prompt in memory; use [`write_groups`] to stream large
+/// collections instead.
+#[must_use]
+pub fn format_groups(groups: &[SkillGroup], format: PromptFormat) -> String {
+    let mut out = Vec::new();
+    // Writing to a Vec cannot fail, and every renderer emits UTF-8.
+    let _ = write_groups(groups, format, &mut out);
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Write skill groups in the specified output format to `out`, each group
+/// as a labeled section:
+/// - XML: a `<skill_group name="...">` element per group inside
+///   `<available_skills>`;
+/// - Markdown: an H2 per group, with skills as H3;
+/// - JSON: an object mapping each label to its array of skills;
+/// - YAML: `skills:` as a mapping from each label to its list of skills.
+///
+/// Groups appear in the order given.
+///
+/// # Errors
+///
+/// Returns any I/O error from `out`.
+pub fn write_groups(
+    groups: &[SkillGroup],
+    format: PromptFormat,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    write_groups_inner(groups, format, None, out)
+}
+
+/// Write skill groups with invocation instructions for `target` to `out`.
+///
+/// Like [`write_groups`], with the additions of [`write_entries_for`]. In
+/// JSON the label mapping moves under `skills`, next to the target and
+/// instructions.
+///
+/// # Errors
+///
+/// Returns any I/O error from `out`.
+pub fn write_groups_for(
+    groups: &[SkillGroup],
+    format: PromptFormat,
+    target: PromptTarget,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    write_groups_inner(groups, format, Some(target), out)
+}
+
+fn write_groups_inner(
+    groups: &[SkillGroup],
+    format: PromptFormat,
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    match format {
+        PromptFormat::Xml => write_xml_groups(groups, target, out),
+        PromptFormat::Json => write_json_groups(groups, target, out),
+        PromptFormat::Yaml => write_yaml_groups(groups, target, out),
+        PromptFormat::Markdown => write_markdown_groups(groups, target, out),
+    }
+}
+
+fn write_entries_inner(
+    entries: &[SkillEntry],
+    format: PromptFormat,
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
     match format {
-        PromptFormat::Xml => format_xml(entries),
-        PromptFormat::Json => format_json(entries),
-        PromptFormat::Yaml => format_yaml(entries),
-        PromptFormat::Markdown => format_markdown(entries),
+        PromptFormat::Xml => write_xml(entries, target, out),
+        PromptFormat::Json => write_json(entries, target, out),
+        PromptFormat::Yaml => write_yaml(entries, target, out),
+        PromptFormat::Markdown => write_markdown(entries, target, out),
     }
 }
 
@@ -202,7 +612,80 @@ pub fn estimate_tokens(s: &str) -> usize {
 #[must_use]
 pub fn format_budget(entries: &[SkillEntry]) -> String {
     let mut out = String::from("Token budget (estimated):\n");
+    let total = push_budget_lines(&mut out, entries);
+    push_budget_total(&mut out, total);
+    out
+}
+
+/// Format a token budget report broken down by group.
+///
+/// Each group lists its skills under a `[label]` header, followed by its
+/// subtotal; the total and warning are as in [`format_budget`].
+#[must_use]
+pub fn format_group_budget(groups: &[SkillGroup]) -> String {
+    let mut out = String::from("Token budget (estimated):\n");
+    let mut total = 0usize;
+    for group in groups {
+        out.push_str(&format!("  [{}]\n", group.label));
+        let subtotal = push_budget_lines(&mut out, &group.entries);
+        out.push_str(&format!("  {:<30} ~{} tokens\n", "Subtotal:", subtotal));
+        total += subtotal;
+    }
+    push_budget_total(&mut out, total);
+    out
+}
 
+/// A tool from `allowed-tools` and the skills that list it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ToolRequirement {
+    /// Tool as written in `allowed-tools` (e.g., `Read` or `Bash(git:*)`).
+    pub tool: String,
+    /// Names of the skills that list the tool, in entry order.
+    pub skills: Vec<String>,
+}
+
+/// The deduplicated union of the entries' `allowed-tools`, sorted by tool.
+///
+/// Tools are compared exactly, so `Bash` and `Bash(git:*)` are separate
+/// requirements.
+#[must_use]
+pub fn tool_requirements<'a>(
+    entries: impl IntoIterator<Item = &'a SkillEntry>,
+) -> Vec<ToolRequirement> {
+    let mut tools: std::collections::BTreeMap<&str, Vec<String>> = Default::default();
+    for entry in entries {
+        for tool in &entry.allowed_tools {
+            let skills = tools.entry(tool).or_default();
+            if !skills.contains(&entry.name) {
+                skills.push(entry.name.clone());
+            }
+        }
+    }
+    tools
+        .into_iter()
+        .map(|(tool, skills)| ToolRequirement {
+            tool: tool.to_string(),
+            skills,
+        })
+        .collect()
+}
+
+/// Format tool requirements as a report: one line per tool with the skills
+/// that need it.
+#[must_use]
+pub fn format_tool_requirements(requirements: &[ToolRequirement]) -> String {
+    let mut out = format!("Tools required ({}):\n", requirements.len());
+    if requirements.is_empty() {
+        out.push_str("  (no skill lists allowed-tools)\n");
+    }
+    for req in requirements {
+        out.push_str(&format!("  {:<30} {}\n", req.tool, req.skills.join(", ")));
+    }
+    out
+}
+
+/// Append a line per skill with its estimated tokens; returns their sum.
+fn push_budget_lines(out: &mut String, entries: &[SkillEntry]) -> usize {
     let mut total = 0usize;
     for entry in entries {
         // Estimate tokens for the prompt representation of this skill.
@@ -211,7 +694,11 @@ pub fn format_budget(entries: &[SkillEntry]) -> String {
         total += tokens;
         out.push_str(&format!("  {:<30} ~{} tokens\n", entry.name, tokens));
     }
+    total
+}
 
+/// Append the total, context usage, and the over-budget warning.
+fn push_budget_total(out: &mut String, total: usize) {
     out.push_str(&format!("  {:<30} ---\n", ""));
     out.push_str(&format!("  {:<30} ~{} tokens\n", "Total:", total));
 
@@ -223,74 +710,313 @@ pub fn format_budget(entries: &[SkillEntry]) -> String {
             "\n  ⚠ Total exceeds 4000 tokens (~2% of context). Consider consolidating skills.\n",
         );
     }
-
-    out
 }
 
 // ── Format implementations ─────────────────────────────────────────────
 
-fn format_xml(entries: &[SkillEntry]) -> String {
-    let mut out = String::from("<available_skills>\n");
-
+fn write_xml(
+    entries: &[SkillEntry],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    if let Some(target) = target {
+        writeln!(
+            out,
+            "<skills_instructions>\n{}\n</skills_instructions>",
+            xml_escape(target.instructions())
+        )?;
+    }
+    out.write_all(b"<available_skills>\n")?;
     for entry in entries {
-        out.push_str("  <skill>\n");
-        out.push_str(&format!("    <name>{}</name>\n", xml_escape(&entry.name)));
-        out.push_str(&format!(
-            "    <description>{}</description>\n",
-            xml_escape(&entry.description)
-        ));
-        out.push_str(&format!(
-            "    <location>{}</location>\n",
-            xml_escape(&entry.location)
-        ));
-        out.push_str("  </skill>\n");
+        write_xml_skill(entry, target, "  ", out)?;
     }
+    out.write_all(b"</available_skills>")
+}
 
-    out.push_str("</available_skills>");
-    out
+fn write_xml_groups(
+    groups: &[SkillGroup],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    if let Some(target) = target {
+        writeln!(
+            out,
+            "<skills_instructions>\n{}\n</skills_instructions>",
+            xml_escape(target.instructions())
+        )?;
+    }
+    out.write_all(b"<available_skills>\n")?;
+    for group in groups {
+        writeln!(out, "  <skill_group name=\"{}\">", xml_escape(&group.label))?;
+        for entry in &group.entries {
+            write_xml_skill(entry, target, "    ", out)?;
+        }
+        out.write_all(b"  </skill_group>\n")?;
+    }
+    out.write_all(b"</available_skills>")
 }
 
-fn format_json(entries: &[SkillEntry]) -> String {
-    let items: Vec<serde_json::Value> = entries
-        .iter()
-        .map(|e| {
-            serde_json::json!({
-                "name": e.name,
-                "description": e.description,
-                "location": e.location,
-            })
-        })
-        .collect();
+/// Write one `<skill>` element, indented by `indent`.
+fn write_xml_skill(
+    entry: &SkillEntry,
+    target: Option<PromptTarget>,
+    indent: &str,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    match &entry.provenance {
+        Some(p) => {
+            write!(out, "{indent}<skill sha256=\"{}\"", p.sha256)?;
+            if let Some(mtime) = p.mtime {
+                write!(out, " mtime=\"{mtime}\"")?;
+            }
+            writeln!(out, ">")?;
+        }
+        None => writeln!(out, "{indent}<skill>")?,
+    }
+    writeln!(out, "{indent}  <name>{}</name>", xml_escape(&entry.name))?;
+    writeln!(
+        out,
+        "{indent}  <description>{}</description>",
+        xml_escape(&entry.description)
+    )?;
+    writeln!(
+        out,
+        "{indent}  <location>{}</location>",
+        xml_escape(&entry.location)
+    )?;
+    if let Some(invocation) = target.and_then(|t| t.invocation(&entry.name)) {
+        writeln!(
+            out,
+            "{indent}  <invocation>{}</invocation>",
+            xml_escape(&invocation)
+        )?;
+    }
+    writeln!(out, "{indent}</skill>")
+}
+
+/// Write pretty-printed JSON: the array of entries, or with a target, an
+/// object holding the target, its instructions, and the array.
+fn write_json(
+    entries: &[SkillEntry],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    let Some(target) = target else {
+        return write_json_array(entries, None, "", out);
+    };
+    let instructions =
+        serde_json::to_string(target.instructions()).map_err(std::io::Error::other)?;
+    write!(
+        out,
+        "{{\n  \"target\": \"{}\",\n  \"instructions\": {instructions},\n  \"skills\": ",
+        target.as_str()
+    )?;
+    write_json_array(entries, Some(target), "  ", out)?;
+    out.write_all(b"\n}")
+}
 
-    serde_json::to_string_pretty(&items).unwrap_or_else(|_| "[]".to_string())
+/// Write grouped JSON: an object mapping each label to its array of
+/// entries, or with a target, that object under `skills` next to the target
+/// and its instructions.
+fn write_json_groups(
+    groups: &[SkillGroup],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    let indent = match target {
+        Some(target) => {
+            let instructions =
+                serde_json::to_string(target.instructions()).map_err(std::io::Error::other)?;
+            write!(
+                out,
+                "{{\n  \"target\": \"{}\",\n  \"instructions\": {instructions},\n  \"skills\": ",
+                target.as_str()
+            )?;
+            "  "
+        }
+        None => "",
+    };
+    if groups.is_empty() {
+        out.write_all(b"{}")?;
+    } else {
+        out.write_all(b"{")?;
+        for (i, group) in groups.iter().enumerate() {
+            let label = serde_json::to_string(&group.label).map_err(std::io::Error::other)?;
+            out.write_all(if i == 0 { b"\n" } else { b",\n" })?;
+            write!(out, "{indent}  {label}: ")?;
+            write_json_array(&group.entries, target, &format!("{indent}  "), out)?;
+        }
+        write!(out, "\n{indent}}}")?;
+    }
+    if target.is_some() {
+        out.write_all(b"\n}")?;
+    }
+    Ok(())
 }
 
-fn format_yaml(entries: &[SkillEntry]) -> String {
-    let mut out = String::from("skills:\n");
+/// Write a pretty-printed JSON array, one object at a time, with every line
+/// after the first prefixed by `indent`.
+///
+/// The output matches `serde_json::to_string_pretty` of the whole array:
+/// each object is pretty-printed on its own and indented one level.
+fn write_json_array(
+    entries: &[SkillEntry],
+    target: Option<PromptTarget>,
+    indent: &str,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    if entries.is_empty() {
+        return out.write_all(b"[]");
+    }
+    out.write_all(b"[")?;
+    for (i, e) in entries.iter().enumerate() {
+        let mut item = serde_json::json!({
+            "name": e.name,
+            "description": e.description,
+            "location": e.location,
+        });
+        if let Some(invocation) = target.and_then(|t| t.invocation(&e.name)) {
+            item["invocation"] = serde_json::Value::String(invocation);
+        }
+        if let Some(p) = &e.provenance {
+            item["sha256"] = serde_json::Value::String(p.sha256.clone());
+            if let Some(mtime) = p.mtime {
+                item["mtime"] = serde_json::Value::from(mtime);
+            }
+        }
+        let pretty = serde_json::to_string_pretty(&item).map_err(std::io::Error::other)?;
+        out.write_all(if i == 0 { b"\n" } else { b",\n" })?;
+        // Pretty JSON escapes newlines inside strings, so every line break
+        // here is structural and safe to indent.
+        for (j, line) in pretty.lines().enumerate() {
+            if j > 0 {
+                out.write_all(b"\n")?;
+            }
+            write!(out, "{indent}  {line}")?;
+        }
+    }
+    write!(out, "\n{indent}]")
+}
 
+fn write_yaml(
+    entries: &[SkillEntry],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    if let Some(target) = target {
+        writeln!(out, "target: {}", target.as_str())?;
+        writeln!(out, "instructions: {}", yaml_quote(target.instructions()))?;
+    }
+    out.write_all(b"skills:\n")?;
     for entry in entries {
-        out.push_str(&format!("  - name: {}\n", yaml_quote(&entry.name)));
-        out.push_str(&format!(
-            "    description: {}\n",
-            yaml_quote(&entry.description)
-        ));
-        out.push_str(&format!("    location: {}\n", yaml_quote(&entry.location)));
+        write_yaml_skill(entry, target, "  ", out)?;
     }
+    Ok(())
+}
 
-    out
+fn write_yaml_groups(
+    groups: &[SkillGroup],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    if let Some(target) = target {
+        writeln!(out, "target: {}", target.as_str())?;
+        writeln!(out, "instructions: {}", yaml_quote(target.instructions()))?;
+    }
+    if groups.is_empty() {
+        return out.write_all(b"skills: {}\n");
+    }
+    out.write_all(b"skills:\n")?;
+    for group in groups {
+        let label = yaml_quote(&group.label);
+        if group.entries.is_empty() {
+            writeln!(out, "  {label}: []")?;
+            continue;
+        }
+        writeln!(out, "  {label}:")?;
+        for entry in &group.entries {
+            write_yaml_skill(entry, target, "    ", out)?;
+        }
+    }
+    Ok(())
 }
 
-fn format_markdown(entries: &[SkillEntry]) -> String {
-    let mut out = String::from("# Available Skills\n\n");
+/// Write one skill as a YAML list item, indented by `indent`.
+fn write_yaml_skill(
+    entry: &SkillEntry,
+    target: Option<PromptTarget>,
+    indent: &str,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    writeln!(out, "{indent}- name: {}", yaml_quote(&entry.name))?;
+    writeln!(
+        out,
+        "{indent}  description: {}",
+        yaml_quote(&entry.description)
+    )?;
+    writeln!(out, "{indent}  location: {}", yaml_quote(&entry.location))?;
+    if let Some(invocation) = target.and_then(|t| t.invocation(&entry.name)) {
+        writeln!(out, "{indent}  invocation: {}", yaml_quote(&invocation))?;
+    }
+    if let Some(p) = &entry.provenance {
+        writeln!(out, "{indent}  sha256: {}", p.sha256)?;
+        if let Some(mtime) = p.mtime {
+            writeln!(out, "{indent}  mtime: {mtime}")?;
+        }
+    }
+    Ok(())
+}
 
+fn write_markdown(
+    entries: &[SkillEntry],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    out.write_all(b"# Available Skills\n\n")?;
+    if let Some(target) = target {
+        write!(out, "{}\n\n", target.instructions())?;
+    }
     for entry in entries {
-        out.push_str(&format!("## {}\n\n", entry.name));
-        out.push_str(&format!("> {}\n\n", entry.description));
-        out.push_str(&format!("**Location**: `{}`\n\n", entry.location));
-        out.push_str("---\n\n");
+        write_markdown_skill(entry, target, "##", out)?;
     }
+    Ok(())
+}
 
-    out
+fn write_markdown_groups(
+    groups: &[SkillGroup],
+    target: Option<PromptTarget>,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    out.write_all(b"# Available Skills\n\n")?;
+    if let Some(target) = target {
+        write!(out, "{}\n\n", target.instructions())?;
+    }
+    for group in groups {
+        write!(out, "## {}\n\n", group.label)?;
+        for entry in &group.entries {
+            write_markdown_skill(entry, target, "###", out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write one skill section under a `heading` of the given level.
+fn write_markdown_skill(
+    entry: &SkillEntry,
+    target: Option<PromptTarget>,
+    heading: &str,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    write!(out, "{heading} {}\n\n", entry.name)?;
+    write!(out, "> {}\n\n", entry.description)?;
+    write!(out, "**Location**: `{}`\n\n", entry.location)?;
+    if let Some(invocation) = target.and_then(|t| t.invocation(&entry.name)) {
+        write!(out, "**Invocation**: `{invocation}`\n\n")?;
+    }
+    if let Some(p) = &entry.provenance {
+        write!(out, "<!-- {} -->\n\n", p.label())?;
+    }
+    out.write_all(b"---\n\n")
 }
 
 /// Quote a YAML string value if it contains special characters.
@@ -368,6 +1094,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn xml_escape_drops_invalid_xml_characters() {
+        assert_eq!(xml_escape("a\u{1}b\tc\u{FFFF}"), "ab\tc");
+        assert_eq!(xml_escape("line\r\nnext"), "line\r\nnext");
+    }
+
     #[test]
     fn xml_escape_ampersand_first_no_double_escape() {
         // Input contains literal "&lt;" (ampersand + "lt;").
@@ -599,6 +1331,9 @@ mod tests {
             name: "my-skill".to_string(),
             description: "Does things".to_string(),
             location: "/path/to/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
         }];
         let budget = format_budget(&entries);
         assert!(budget.contains("my-skill"));
@@ -614,6 +1349,9 @@ mod tests {
             name: "big-skill".to_string(),
             description: big_desc,
             location: "/path/to/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
         }];
         let budget = format_budget(&entries);
         assert!(
@@ -628,6 +1366,9 @@ mod tests {
             name: "small-skill".to_string(),
             description: "Short".to_string(),
             location: "/path/to/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
         }];
         let budget = format_budget(&entries);
         assert!(
@@ -728,6 +1469,137 @@ mod tests {
         assert_eq!(original[0].description, verbose[0].description);
     }
 
+    // ── sorting ──────────────────────────────────────────────────────
+
+    #[test]
+    fn sort_by_priority_puts_highest_first_and_unset_last() {
+        let parent = tempdir().unwrap();
+        let mut dirs = Vec::new();
+        for (name, metadata) in [
+            ("alpha-skill", ""),
+            ("beta-skill", "metadata:\n  priority: 5\n"),
+            ("gamma-skill", "metadata:\n  priority: 10\n"),
+        ] {
+            let dir = parent.path().join(name);
+            fs::create_dir(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Does things\n{metadata}---\n"),
+            )
+            .unwrap();
+            dirs.push(dir);
+        }
+        let refs: Vec<&Path> = dirs.iter().map(std::path::PathBuf::as_path).collect();
+        let mut entries = collect_skills(&refs);
+        let priorities: Vec<i64> = entries.iter().map(|e| e.priority).collect();
+        assert_eq!(priorities, [0, 5, 10]);
+
+        sort_entries(&mut entries, PromptSort::Priority);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["gamma-skill", "beta-skill", "alpha-skill"]);
+    }
+
+    #[test]
+    fn sort_by_priority_breaks_ties_by_name() {
+        let entry = |name: &str, priority| SkillEntry {
+            name: name.to_string(),
+            description: String::new(),
+            location: String::new(),
+            priority,
+            allowed_tools: Vec::new(),
+            provenance: None,
+        };
+        let mut entries = vec![entry("b", 1), entry("c", 0), entry("a", 1)];
+        sort_entries(&mut entries, PromptSort::Priority);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+
+        sort_entries(&mut entries, PromptSort::Name);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn exclude_entries_drops_named_and_reports_matches() {
+        let entry = |name: &str| SkillEntry {
+            name: name.to_string(),
+            description: String::new(),
+            location: String::new(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
+        };
+        let mut entries = vec![entry("a"), entry("b"), entry("c")];
+        let names = ["b".to_string(), "missing".to_string()];
+        assert_eq!(exclude_entries(&mut entries, &names), ["b"]);
+        let left: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(left, ["a", "c"]);
+    }
+
+    // ── deprecated skills ────────────────────────────────────────────
+
+    /// Create a deprecated `old-skill` and its successor `new-skill` side by side.
+    fn make_deprecated_pair() -> (tempfile::TempDir, std::path::PathBuf, std::path::PathBuf) {
+        let parent = tempdir().unwrap();
+        let old = parent.path().join("old-skill");
+        let new = parent.path().join("new-skill");
+        fs::create_dir(&old).unwrap();
+        fs::create_dir(&new).unwrap();
+        fs::write(
+            old.join("SKILL.md"),
+            "---\nname: old-skill\ndescription: Old way\nmetadata:\n  deprecated: true\n  superseded-by: new-skill\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            new.join("SKILL.md"),
+            "---\nname: new-skill\ndescription: New way\n---\n",
+        )
+        .unwrap();
+        (parent, old, new)
+    }
+
+    #[test]
+    fn collect_skills_skips_deprecated_by_default() {
+        let (_parent, old, new) = make_deprecated_pair();
+        let entries = collect_skills(&[old.as_path(), new.as_path()]);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["new-skill"]);
+        assert!(!to_prompt(&[old.as_path(), new.as_path()]).contains("old-skill"));
+    }
+
+    #[test]
+    fn collect_skills_with_include_deprecated_keeps_them() {
+        let (_parent, old, new) = make_deprecated_pair();
+        let opts = CollectOptions {
+            include_deprecated: true,
+            ..CollectOptions::default()
+        };
+        let entries = collect_skills_with(&[old.as_path(), new.as_path()], &opts);
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn collect_skills_verbose_counts_skipped_deprecated() {
+        let (_parent, old, new) = make_deprecated_pair();
+        let (entries, warnings) = collect_skills_verbose(&[old.as_path(), new.as_path()]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(warnings.len(), 1, "got: {warnings:?}");
+        assert_eq!(warnings[0].message, "skipped 1 deprecated skill: old-skill");
+    }
+
+    #[test]
+    fn collect_skills_verbose_with_include_deprecated_no_warning() {
+        let (_parent, old, new) = make_deprecated_pair();
+        let opts = CollectOptions {
+            include_deprecated: true,
+            ..CollectOptions::default()
+        };
+        let (entries, warnings) =
+            collect_skills_verbose_with(&[old.as_path(), new.as_path()], &opts);
+        assert_eq!(entries.len(), 2);
+        assert!(warnings.is_empty(), "got: {warnings:?}");
+    }
+
     // ── format_entries tests ─────────────────────────────────────────
 
     #[test]
@@ -736,9 +1608,560 @@ mod tests {
             name: "test-skill".to_string(),
             description: "Does things".to_string(),
             location: "/path/to/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
         }];
         let result = format_entries(&entries, PromptFormat::Xml);
         assert!(result.contains("<name>test-skill</name>"));
         assert!(result.starts_with("<available_skills>"));
     }
+
+    #[test]
+    fn format_xml_round_trips_through_xml_parser() {
+        let nasty = [
+            "Tom & Jerry <b>bold</b> > less",
+            r#"Quotes "double" and 'single'"#,
+            "CDATA end ]]> and entity &amp; literal",
+            "Control\u{1}\u{8}chars\u{1F} and \u{FFFE}noncharacters",
+            "Unicode: café, 日本語, emoji 🚀",
+            "Tabs\tand\nnewlines",
+        ];
+        let entries: Vec<SkillEntry> = nasty
+            .iter()
+            .enumerate()
+            .map(|(i, text)| SkillEntry {
+                name: format!("skill-{i} <&>"),
+                description: (*text).to_string(),
+                location: format!("/path/with \"quotes\" & <angles>/{i}/SKILL.md"),
+                priority: 0,
+                allowed_tools: Vec::new(),
+                provenance: None,
+            })
+            .collect();
+        let xml = format_entries(&entries, PromptFormat::Xml);
+        let doc = roxmltree::Document::parse(&xml).expect("prompt XML must parse");
+
+        let root = doc.root_element();
+        assert_eq!(root.tag_name().name(), "available_skills");
+        let skills: Vec<_> = root.children().filter(|n| n.is_element()).collect();
+        assert_eq!(skills.len(), entries.len());
+
+        let strip_invalid = |s: &str| -> String {
+            s.chars()
+                .filter(|&c| {
+                    matches!(c, '\t' | '\n' | '\r')
+                        || (c >= ' ' && c != '\u{FFFE}' && c != '\u{FFFF}')
+                })
+                .collect()
+        };
+        for (skill, entry) in skills.iter().zip(&entries) {
+            let field = |tag: &str| {
+                skill
+                    .children()
+                    .find(|n| n.has_tag_name(tag))
+                    .and_then(|n| n.text())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            assert_eq!(field("name"), entry.name);
+            assert_eq!(field("description"), strip_invalid(&entry.description));
+            assert_eq!(field("location"), entry.location);
+        }
+    }
+
+    // ── write_entries tests ──────────────────────────────────────────
+
+    /// Writer that accepts at most a few bytes per call, to exercise
+    /// partial writes.
+    struct TrickleWriter(Vec<u8>);
+
+    impl Write for TrickleWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(7);
+            self.0.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn large_entry_set(count: usize) -> Vec<SkillEntry> {
+        (0..count)
+            .map(|i| SkillEntry {
+                name: format!("skill-{i}"),
+                description: format!("Does \"thing\" #{i} & more: <tags>\nline two \u{1F680}"),
+                location: format!("/skills/group-{}/skill-{i}/SKILL.md", i % 17),
+                priority: 0,
+                allowed_tools: Vec::new(),
+                provenance: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn write_entries_matches_output_from_before_streaming() {
+        // Rendered by the buffered formatters that `write_entries` replaced.
+        let expected = [
+            (
+                PromptFormat::Xml,
+                "<available_skills>\n  <skill>\n    <name>skill-0</name>\n    <description>Does &quot;thing&quot; #0 &amp; more: &lt;tags&gt;\nline two \u{1F680}</description>\n    <location>/skills/group-0/skill-0/SKILL.md</location>\n  </skill>\n  <skill>\n    <name>skill-1</name>\n    <description>Does &quot;thing&quot; #1 &amp; more: &lt;tags&gt;\nline two \u{1F680}</description>\n    <location>/skills/group-1/skill-1/SKILL.md</location>\n  </skill>\n</available_skills>",
+            ),
+            (
+                PromptFormat::Json,
+                "[\n  {\n    \"description\": \"Does \\\"thing\\\" #0 & more: <tags>\\nline two \u{1F680}\",\n    \"location\": \"/skills/group-0/skill-0/SKILL.md\",\n    \"name\": \"skill-0\"\n  },\n  {\n    \"description\": \"Does \\\"thing\\\" #1 & more: <tags>\\nline two \u{1F680}\",\n    \"location\": \"/skills/group-1/skill-1/SKILL.md\",\n    \"name\": \"skill-1\"\n  }\n]",
+            ),
+            (
+                PromptFormat::Yaml,
+                "skills:\n  - name: skill-0\n    description: \"Does \\\"thing\\\" #0 & more: <tags>\nline two \u{1F680}\"\n    location: /skills/group-0/skill-0/SKILL.md\n  - name: skill-1\n    description: \"Does \\\"thing\\\" #1 & more: <tags>\nline two \u{1F680}\"\n    location: /skills/group-1/skill-1/SKILL.md\n",
+            ),
+            (
+                PromptFormat::Markdown,
+                "# Available Skills\n\n## skill-0\n\n> Does \"thing\" #0 & more: <tags>\nline two \u{1F680}\n\n**Location**: `/skills/group-0/skill-0/SKILL.md`\n\n---\n\n## skill-1\n\n> Does \"thing\" #1 & more: <tags>\nline two \u{1F680}\n\n**Location**: `/skills/group-1/skill-1/SKILL.md`\n\n---\n\n",
+            ),
+        ];
+        let entries = large_entry_set(2);
+        for (format, expected) in expected {
+            let mut streamed = TrickleWriter(Vec::new());
+            write_entries(&entries, format, &mut streamed).unwrap();
+            assert_eq!(
+                String::from_utf8(streamed.0).unwrap(),
+                expected,
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn write_entries_output_does_not_depend_on_write_sizes() {
+        let entries = large_entry_set(5_000);
+        for format in [
+            PromptFormat::Xml,
+            PromptFormat::Json,
+            PromptFormat::Yaml,
+            PromptFormat::Markdown,
+        ] {
+            let mut trickled = TrickleWriter(Vec::new());
+            write_entries(&entries, format, &mut trickled).unwrap();
+            let mut whole = Vec::new();
+            write_entries(&entries, format, &mut whole).unwrap();
+            assert!(
+                trickled.0 == whole,
+                "{format:?} output depends on write sizes"
+            );
+        }
+    }
+
+    #[test]
+    fn write_entries_json_matches_whole_array_serialization() {
+        for count in [0, 1, 2, 1_000] {
+            let entries = large_entry_set(count);
+            let items: Vec<serde_json::Value> = entries
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "name": e.name,
+                        "description": e.description,
+                        "location": e.location,
+                    })
+                })
+                .collect();
+            let expected = serde_json::to_string_pretty(&items).unwrap();
+            let mut streamed = Vec::new();
+            write_entries(&entries, PromptFormat::Json, &mut streamed).unwrap();
+            assert_eq!(
+                String::from_utf8(streamed).unwrap(),
+                expected,
+                "{count} entries"
+            );
+        }
+    }
+
+    // ── target snapshots ─────────────────────────────────────────────
+
+    fn target_entries() -> Vec<SkillEntry> {
+        vec![SkillEntry {
+            name: "pdf-tools".to_string(),
+            description: "Extracts text from PDF files. Use when reading PDFs.".to_string(),
+            location: "/skills/pdf-tools/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: None,
+        }]
+    }
+
+    #[test]
+    fn xml_standard_target_snapshot() {
+        assert_eq!(
+            format_entries_for(&target_entries(), PromptFormat::Xml, PromptTarget::Standard),
+            "<skills_instructions>\n\
+             When a task matches a skill&apos;s description, read the SKILL.md file at the \
+             skill&apos;s location and follow its instructions. Read other files the skill \
+             references only when the task needs them.\n\
+             </skills_instructions>\n\
+             <available_skills>\n  <skill>\n    <name>pdf-tools</name>\n    \
+             <description>Extracts text from PDF files. Use when reading PDFs.</description>\n    \
+             <location>/skills/pdf-tools/SKILL.md</location>\n  </skill>\n</available_skills>"
+        );
+    }
+
+    #[test]
+    fn xml_claude_code_target_snapshot() {
+        assert_eq!(
+            format_entries_for(
+                &target_entries(),
+                PromptFormat::Xml,
+                PromptTarget::ClaudeCode
+            ),
+            "<skills_instructions>\n\
+             When a task matches a skill&apos;s description, invoke the skill with the Skill \
+             tool. Users can also run a skill as a slash command, given as its invocation. \
+             Each skill&apos;s instructions are in the SKILL.md file at its location.\n\
+             </skills_instructions>\n\
+             <available_skills>\n  <skill>\n    <name>pdf-tools</name>\n    \
+             <description>Extracts text from PDF files. Use when reading PDFs.</description>\n    \
+             <location>/skills/pdf-tools/SKILL.md</location>\n    \
+             <invocation>/pdf-tools</invocation>\n  </skill>\n</available_skills>"
+        );
+    }
+
+    #[test]
+    fn json_claude_code_target_snapshot() {
+        assert_eq!(
+            format_entries_for(
+                &target_entries(),
+                PromptFormat::Json,
+                PromptTarget::ClaudeCode
+            ),
+            format!(
+                "{{\n  \"target\": \"claude-code\",\n  \"instructions\": {},\n  \"skills\": [\n    \
+                 {{\n      \"description\": \"Extracts text from PDF files. Use when reading PDFs.\",\n      \
+                 \"invocation\": \"/pdf-tools\",\n      \
+                 \"location\": \"/skills/pdf-tools/SKILL.md\",\n      \
+                 \"name\": \"pdf-tools\"\n    }}\n  ]\n}}",
+                serde_json::to_string(PromptTarget::ClaudeCode.instructions()).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn json_standard_target_is_object_without_invocation() {
+        let out = format_entries_for(
+            &target_entries(),
+            PromptFormat::Json,
+            PromptTarget::Standard,
+        );
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json["target"], "standard");
+        assert_eq!(json["skills"][0]["name"], "pdf-tools");
+        assert!(json["skills"][0].get("invocation").is_none());
+        let empty = format_entries_for(&[], PromptFormat::Json, PromptTarget::Standard);
+        assert!(empty.ends_with("\"skills\": []\n}"), "{empty}");
+    }
+
+    #[test]
+    fn yaml_claude_code_target_snapshot() {
+        assert_eq!(
+            format_entries_for(
+                &target_entries(),
+                PromptFormat::Yaml,
+                PromptTarget::ClaudeCode
+            ),
+            format!(
+                "target: claude-code\ninstructions: \"{}\"\nskills:\n  - name: pdf-tools\n    \
+                 description: Extracts text from PDF files. Use when reading PDFs.\n    \
+                 location: /skills/pdf-tools/SKILL.md\n    invocation: /pdf-tools\n",
+                PromptTarget::ClaudeCode.instructions()
+            )
+        );
+    }
+
+    #[test]
+    fn markdown_claude_code_target_snapshot() {
+        assert_eq!(
+            format_entries_for(
+                &target_entries(),
+                PromptFormat::Markdown,
+                PromptTarget::ClaudeCode
+            ),
+            format!(
+                "# Available Skills\n\n{}\n\n## pdf-tools\n\n\
+                 > Extracts text from PDF files. Use when reading PDFs.\n\n\
+                 **Location**: `/skills/pdf-tools/SKILL.md`\n\n\
+                 **Invocation**: `/pdf-tools`\n\n---\n\n",
+                PromptTarget::ClaudeCode.instructions()
+            )
+        );
+    }
+
+    #[test]
+    fn targeted_output_parses_in_every_format() {
+        let entries = large_entry_set(3);
+        for target in [PromptTarget::Standard, PromptTarget::ClaudeCode] {
+            let xml = format_entries_for(&entries, PromptFormat::Xml, target);
+            roxmltree::Document::parse(&format!("<prompt>{xml}</prompt>")).unwrap();
+            let json = format_entries_for(&entries, PromptFormat::Json, target);
+            let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(json["skills"].as_array().unwrap().len(), 3);
+            let yaml = format_entries_for(&entries, PromptFormat::Yaml, target);
+            let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&yaml).unwrap();
+            assert_eq!(yaml["instructions"].as_str(), Some(target.instructions()));
+        }
+    }
+
+    // ── grouped prompts ──────────────────────────────────────────────
+
+    fn sample_groups() -> Vec<SkillGroup> {
+        let mut second = target_entries();
+        second[0].name = "lint-rules".to_string();
+        second[0].location = "/org/lint-rules/SKILL.md".to_string();
+        vec![
+            SkillGroup {
+                label: "plugin".to_string(),
+                entries: target_entries(),
+            },
+            SkillGroup {
+                label: "org-library".to_string(),
+                entries: second,
+            },
+        ]
+    }
+
+    #[test]
+    fn collect_skills_labeled_follows_label_order_and_merges_repeats() {
+        let (_p1, a) = make_skill_dir("skill-a", "---\nname: skill-a\ndescription: A\n---\n");
+        let (_p2, b) = make_skill_dir("skill-b", "---\nname: skill-b\ndescription: B\n---\n");
+        let (_p3, c) = make_skill_dir("skill-c", "---\nname: skill-c\ndescription: C\n---\n");
+        let (groups, warnings) = collect_skills_labeled(
+            &[
+                ("zeta", &[b.as_path()]),
+                ("alpha", &[a.as_path()]),
+                ("zeta", &[c.as_path()]),
+            ],
+            &CollectOptions::default(),
+        );
+        assert!(warnings.is_empty());
+        let labels: Vec<&str> = groups.iter().map(|g| g.label.as_str()).collect();
+        assert_eq!(labels, ["zeta", "alpha"]);
+        let zeta: Vec<&str> = groups[0].entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(zeta, ["skill-b", "skill-c"]);
+        assert_eq!(groups[1].entries[0].name, "skill-a");
+    }
+
+    #[test]
+    fn xml_groups_snapshot() {
+        assert_eq!(
+            format_groups(&sample_groups(), PromptFormat::Xml),
+            "<available_skills>\n  <skill_group name=\"plugin\">\n    <skill>\n      \
+             <name>pdf-tools</name>\n      \
+             <description>Extracts text from PDF files. Use when reading PDFs.</description>\n      \
+             <location>/skills/pdf-tools/SKILL.md</location>\n    </skill>\n  \
+             </skill_group>\n  <skill_group name=\"org-library\">\n    <skill>\n      \
+             <name>lint-rules</name>\n      \
+             <description>Extracts text from PDF files. Use when reading PDFs.</description>\n      \
+             <location>/org/lint-rules/SKILL.md</location>\n    </skill>\n  \
+             </skill_group>\n</available_skills>"
+        );
+    }
+
+    #[test]
+    fn markdown_groups_use_h2_per_group() {
+        let out = format_groups(&sample_groups(), PromptFormat::Markdown);
+        assert!(out.starts_with(
+            "# Available Skills\n\n## plugin\n\n### pdf-tools\n\n\
+             > Extracts text from PDF files. Use when reading PDFs.\n\n"
+        ));
+        let plugin = out.find("## plugin").unwrap();
+        let org = out.find("## org-library\n\n### lint-rules").unwrap();
+        assert!(plugin < org);
+    }
+
+    #[test]
+    fn json_groups_map_labels_in_order() {
+        let out = format_groups(&sample_groups(), PromptFormat::Json);
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(json["plugin"][0]["name"], "pdf-tools");
+        assert_eq!(json["org-library"][0]["name"], "lint-rules");
+        assert!(out.find("\"plugin\"").unwrap() < out.find("\"org-library\"").unwrap());
+        assert_eq!(format_groups(&[], PromptFormat::Json), "{}");
+    }
+
+    #[test]
+    fn yaml_groups_map_labels_under_skills() {
+        let mut groups = sample_groups();
+        groups.push(SkillGroup {
+            label: "empty".to_string(),
+            entries: Vec::new(),
+        });
+        let out = format_groups(&groups, PromptFormat::Yaml);
+        assert!(out.starts_with("skills:\n  plugin:\n    - name: pdf-tools\n"));
+        let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_str(&out).unwrap();
+        assert_eq!(
+            yaml["skills"]["org-library"][0]["name"].as_str(),
+            Some("lint-rules")
+        );
+        assert_eq!(yaml["skills"]["empty"].as_sequence().map(Vec::len), Some(0));
+    }
+
+    #[test]
+    fn targeted_groups_parse_in_every_format() {
+        let groups = sample_groups();
+        for target in [PromptTarget::Standard, PromptTarget::ClaudeCode] {
+            let mut xml = Vec::new();
+            write_groups_for(&groups, PromptFormat::Xml, target, &mut xml).unwrap();
+            let xml = String::from_utf8(xml).unwrap();
+            roxmltree::Document::parse(&format!("<prompt>{xml}</prompt>")).unwrap();
+            let mut json = Vec::new();
+            write_groups_for(&groups, PromptFormat::Json, target, &mut json).unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+            assert_eq!(json["target"], target.as_str());
+            assert_eq!(json["skills"]["org-library"][0]["name"], "lint-rules");
+            let mut yaml = Vec::new();
+            write_groups_for(&groups, PromptFormat::Yaml, target, &mut yaml).unwrap();
+            let yaml: serde_yaml_ng::Value = serde_yaml_ng::from_slice(&yaml).unwrap();
+            assert_eq!(
+                yaml["skills"]["plugin"][0]["name"].as_str(),
+                Some("pdf-tools")
+            );
+        }
+    }
+
+    #[test]
+    fn group_budget_reports_subtotals() {
+        let groups = sample_groups();
+        let out = format_group_budget(&groups);
+        let plugin = out.find("  [plugin]\n  pdf-tools").unwrap();
+        let org = out.find("  [org-library]\n  lint-rules").unwrap();
+        assert!(plugin < org);
+        assert_eq!(out.matches("Subtotal:").count(), 2);
+        let all: Vec<SkillEntry> = groups.into_iter().flat_map(|g| g.entries).collect();
+        let flat = format_budget(&all);
+        let total = |s: &str| {
+            s.lines()
+                .find(|l| l.contains("Total:"))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(total(&out), total(&flat));
+    }
+
+    // ── tool requirements ────────────────────────────────────────────
+
+    fn tool_entry(name: &str, tools: &[&str]) -> SkillEntry {
+        SkillEntry {
+            name: name.to_string(),
+            description: String::new(),
+            location: String::new(),
+            priority: 0,
+            allowed_tools: tools.iter().map(|t| (*t).to_string()).collect(),
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn tool_requirements_dedupes_and_sorts() {
+        let entries = [
+            tool_entry("pdf-tools", &["Read", "Bash(pdftotext:*)"]),
+            tool_entry("deploy", &["Bash", "Read", "Read"]),
+            tool_entry("notes", &[]),
+        ];
+        let reqs = tool_requirements(&entries);
+        let tools: Vec<&str> = reqs.iter().map(|r| r.tool.as_str()).collect();
+        assert_eq!(tools, ["Bash", "Bash(pdftotext:*)", "Read"]);
+        assert_eq!(reqs[2].skills, ["pdf-tools", "deploy"]);
+        let report = format_tool_requirements(&reqs);
+        assert!(
+            report.starts_with("Tools required (3):\n  Bash "),
+            "{report}"
+        );
+        assert!(report.ends_with(" pdf-tools, deploy\n"), "{report}");
+    }
+
+    #[test]
+    fn collect_skills_reads_allowed_tools() {
+        let (_parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: A test skill\nallowed-tools: Bash(git status:*), Read\n---\n",
+        );
+        let entries = collect_skills(&[dir.as_path()]);
+        assert_eq!(entries[0].allowed_tools, ["Bash(git status:*)", "Read"]);
+        assert_eq!(
+            format_tool_requirements(&[]),
+            "Tools required (0):\n  (no skill lists allowed-tools)\n"
+        );
+    }
+
+    // ── provenance ───────────────────────────────────────────────────
+
+    fn collect_with_provenance(dir: &Path) -> SkillEntry {
+        let opts = CollectOptions {
+            provenance: true,
+            ..CollectOptions::default()
+        };
+        let (mut entries, warnings) = collect_skills_verbose_with(&[dir], &opts);
+        assert!(warnings.is_empty(), "got: {warnings:?}");
+        entries.remove(0)
+    }
+
+    #[test]
+    fn provenance_hash_is_stable_and_tracks_content() {
+        let content = "---\nname: my-skill\ndescription: A test skill\n---\n";
+        let (_parent, dir) = make_skill_dir("my-skill", content);
+        let first = collect_with_provenance(&dir).provenance.unwrap();
+        let second = collect_with_provenance(&dir).provenance.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.sha256, crate::hash::sha256_hex(content.as_bytes()));
+        assert!(first.mtime.is_some());
+
+        fs::write(dir.join("SKILL.md"), content.replace("A test", "An edited")).unwrap();
+        let edited = collect_with_provenance(&dir).provenance.unwrap();
+        assert_ne!(edited.sha256, first.sha256);
+    }
+
+    #[test]
+    fn provenance_is_off_by_default() {
+        let (_parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: A test skill\n---\n",
+        );
+        assert!(collect_skills(&[dir.as_path()])[0].provenance.is_none());
+        let (entries, _) = collect_skills_verbose(&[dir.as_path()]);
+        assert!(entries[0].provenance.is_none());
+    }
+
+    #[test]
+    fn provenance_renders_in_every_format() {
+        let entries = vec![SkillEntry {
+            name: "my-skill".to_string(),
+            description: "Does things".to_string(),
+            location: "/s/SKILL.md".to_string(),
+            priority: 0,
+            allowed_tools: Vec::new(),
+            provenance: Some(Provenance {
+                sha256: "ab12".to_string(),
+                mtime: Some(1_700_000_000),
+            }),
+        }];
+        let xml = format_entries(&entries, PromptFormat::Xml);
+        assert!(
+            xml.contains("  <skill sha256=\"ab12\" mtime=\"1700000000\">\n"),
+            "got: {xml}"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&format_entries(&entries, PromptFormat::Json)).unwrap();
+        assert_eq!(json[0]["sha256"], "ab12");
+        assert_eq!(json[0]["mtime"], 1_700_000_000);
+        let yaml = format_entries(&entries, PromptFormat::Yaml);
+        assert!(
+            yaml.contains("    sha256: ab12\n    mtime: 1700000000\n"),
+            "got: {yaml}"
+        );
+        let markdown = format_entries(&entries, PromptFormat::Markdown);
+        assert!(
+            markdown.contains("<!-- sha256:ab12/mtime:1700000000 -->"),
+            "got: {markdown}"
+        );
+    }
 }
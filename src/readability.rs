@@ -0,0 +1,289 @@
+//! Readability metrics for skill bodies.
+//!
+//! [`body_metrics`] measures how skimmable a markdown body is: sentence
+//! length in narrative prose, the share of lines that are code, instruction
+//! steps versus narrative paragraphs, and numbered procedures. Fenced code
+//! blocks, tables, headings, and list items are never counted as sentences.
+//!
+//! The metrics feed the scorer's workflow check, `check --metrics` (I016),
+//! and the `doc` catalog.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::{Diagnostic, Severity, I016};
+
+/// List item: indentation, marker (`-`, `*`, `+`, `1.`, or `1)`), and text.
+static LIST_ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s*)([-*+]|\d+[.)])\s+(.*)$").expect("list item regex must compile")
+});
+
+/// Description words that imply the skill walks through a workflow.
+static WORKFLOW_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(workflows?|step-by-step|steps|procedures?|pipelines?|checklists?)\b")
+        .expect("workflow regex must compile")
+});
+
+/// Leading words that mark a list item as narrative rather than an
+/// instruction.
+const NON_IMPERATIVE: &[&str] = &[
+    "a", "all", "an", "and", "but", "each", "every", "for", "if", "in", "it", "its", "no", "not",
+    "on", "or", "some", "that", "the", "these", "this", "those", "when", "with",
+];
+
+/// Readability metrics of a markdown body.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BodyMetrics {
+    /// Sentences in narrative paragraphs.
+    pub sentences: usize,
+    /// Words in narrative paragraphs.
+    pub prose_words: usize,
+    /// Mean words per sentence (0 without sentences).
+    pub avg_sentence_words: f64,
+    /// Non-blank lines of paragraphs and list items.
+    pub prose_lines: usize,
+    /// Lines inside fenced code blocks, fences excluded.
+    pub code_lines: usize,
+    /// Fenced code blocks.
+    pub code_blocks: usize,
+    /// Share of prose and code lines that are code (0.0–1.0).
+    pub code_ratio: f64,
+    /// List items that read as instructions (see [`body_metrics`]).
+    pub imperative_steps: usize,
+    /// Narrative paragraphs: runs of prose lines outside lists and tables.
+    pub paragraphs: usize,
+    /// Numbered lists of two or more items.
+    pub numbered_procedures: usize,
+}
+
+/// Compute readability metrics for `body`.
+///
+/// A list item is an instruction step when it is numbered, or when its first
+/// word looks like a bare verb: not a determiner or conjunction, and not
+/// ending in "-ing", "-ed", or a plural "-s". Lines following a list item
+/// without a blank line continue that item. A numbered procedure survives
+/// blank lines, nested items, and code blocks between its steps; a heading,
+/// table, or paragraph ends it.
+#[must_use]
+pub fn body_metrics(body: &str) -> BodyMetrics {
+    let mut m = BodyMetrics::default();
+    let mut in_fence = false;
+    let mut in_list_item = false;
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut numbered_run = 0usize;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if !in_fence {
+                flush(&mut paragraph, &mut m);
+                m.code_blocks += 1;
+                in_list_item = false;
+            }
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            m.code_lines += 1;
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut m);
+            in_list_item = false;
+            continue;
+        }
+        if trimmed.starts_with('#') || trimmed.starts_with('|') {
+            flush(&mut paragraph, &mut m);
+            in_list_item = false;
+            numbered_run = 0;
+            continue;
+        }
+        if let Some(caps) = LIST_ITEM_RE.captures(line) {
+            flush(&mut paragraph, &mut m);
+            in_list_item = true;
+            m.prose_lines += 1;
+            let numbered = caps[2].starts_with(|c: char| c.is_ascii_digit());
+            let nested = !caps[1].is_empty();
+            if numbered || is_imperative(&caps[3]) {
+                m.imperative_steps += 1;
+            }
+            if numbered && !nested {
+                numbered_run += 1;
+                if numbered_run == 2 {
+                    m.numbered_procedures += 1;
+                }
+            } else if !nested {
+                numbered_run = 0;
+            }
+            continue;
+        }
+        m.prose_lines += 1;
+        if in_list_item {
+            continue;
+        }
+        if paragraph.is_empty() && !line.starts_with(char::is_whitespace) {
+            numbered_run = 0;
+        }
+        paragraph.push(trimmed);
+    }
+    flush(&mut paragraph, &mut m);
+
+    if m.sentences > 0 {
+        m.avg_sentence_words = m.prose_words as f64 / m.sentences as f64;
+    }
+    let content_lines = m.prose_lines + m.code_lines;
+    if content_lines > 0 {
+        m.code_ratio = m.code_lines as f64 / content_lines as f64;
+    }
+    m
+}
+
+/// Whether `description` says the skill walks through a workflow, so its
+/// body should carry a numbered procedure.
+#[must_use]
+pub fn implies_workflow(description: &str) -> bool {
+    WORKFLOW_RE.is_match(description)
+}
+
+/// Info diagnostic (I016) summarizing selected metrics, for `check --metrics`.
+#[must_use]
+pub fn metrics_diagnostic(m: &BodyMetrics) -> Diagnostic {
+    Diagnostic::new(
+        Severity::Info,
+        I016,
+        format!(
+            "body readability: {:.1} words per sentence, {:.0}% code, {} step(s) vs {} paragraph(s), {} numbered procedure(s)",
+            m.avg_sentence_words,
+            m.code_ratio * 100.0,
+            m.imperative_steps,
+            m.paragraphs,
+            m.numbered_procedures
+        ),
+    )
+}
+
+/// Count the buffered paragraph into `m` and clear the buffer.
+fn flush(paragraph: &mut Vec<&str>, m: &mut BodyMetrics) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let text = paragraph.join(" ");
+    m.paragraphs += 1;
+    m.prose_words += text.split_whitespace().count();
+    m.sentences += sentence_count(&text);
+    paragraph.clear();
+}
+
+/// Number of sentences with at least one word in `text`.
+fn sentence_count(text: &str) -> usize {
+    let mut count = 0;
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let sentence = crate::models::first_sentence(rest);
+        if sentence.chars().any(char::is_alphanumeric) {
+            count += 1;
+        }
+        rest = rest[sentence.len()..].trim_start();
+    }
+    count
+}
+
+/// Whether list item `text` starts like an instruction.
+fn is_imperative(text: &str) -> bool {
+    let word: String = text
+        .trim_start_matches(['*', '_', '`', '['])
+        .chars()
+        .take_while(|c| c.is_alphabetic())
+        .collect::<String>()
+        .to_lowercase();
+    !word.is_empty()
+        && !NON_IMPERATIVE.contains(&word.as_str())
+        && !word.ends_with("ing")
+        && !word.ends_with("ed")
+        && (!word.ends_with('s') || word.ends_with("ss"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prose_sentences_and_average_length() {
+        let m = body_metrics("One two three. Four five six seven eight.\n\nNine ten!\n");
+        assert_eq!(m.paragraphs, 2);
+        assert_eq!(m.sentences, 3);
+        assert_eq!(m.prose_words, 10);
+        assert!((m.avg_sentence_words - 10.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn abbreviations_do_not_split_sentences() {
+        let m = body_metrics("Use a converter, e.g. pandoc, for this. Then stop.\n");
+        assert_eq!(m.sentences, 2);
+    }
+
+    #[test]
+    fn code_blocks_tables_and_headings_are_not_sentences() {
+        let body = "# Title\n\nRun it.\n\n```bash\necho one. two.\necho three.\n```\n\n| a. | b. |\n|----|----|\n| c. | d. |\n";
+        let m = body_metrics(body);
+        assert_eq!(m.sentences, 1);
+        assert_eq!(m.paragraphs, 1);
+        assert_eq!(m.code_blocks, 1);
+        assert_eq!(m.code_lines, 2);
+        assert_eq!(m.prose_lines, 1);
+        assert!((m.code_ratio - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn list_items_are_steps_not_sentences() {
+        let body = "- Open the file.\n- The file is large.\n- Checking is optional.\n  Continue here.\n* Save it.\n";
+        let m = body_metrics(body);
+        assert_eq!(m.sentences, 0);
+        assert_eq!(m.paragraphs, 0);
+        assert_eq!(m.imperative_steps, 2);
+        assert_eq!(m.prose_lines, 5);
+    }
+
+    #[test]
+    fn numbered_procedure_survives_code_and_nested_items() {
+        let body = "1. Install it:\n\n   ```bash\n   cargo install aigent\n   ```\n\n2. Run it.\n   - with a nested note\n3. Check the output.\n";
+        let m = body_metrics(body);
+        assert_eq!(m.numbered_procedures, 1);
+        assert_eq!(m.imperative_steps, 3);
+    }
+
+    #[test]
+    fn paragraph_or_heading_ends_a_numbered_run() {
+        let m = body_metrics("1. One.\n\nSome prose.\n\n2. Two.\n\n## Next\n\n3. Three.\n");
+        assert_eq!(m.numbered_procedures, 0);
+        let m = body_metrics("1. One.\n2. Two.\n\n# Again\n\n1. One.\n2. Two.\n");
+        assert_eq!(m.numbered_procedures, 2);
+    }
+
+    #[test]
+    fn empty_body_has_zero_metrics() {
+        assert_eq!(body_metrics(""), BodyMetrics::default());
+    }
+
+    #[test]
+    fn workflow_words_in_description() {
+        assert!(implies_workflow("Guides the release workflow."));
+        assert!(implies_workflow("Walks through deployment steps"));
+        assert!(!implies_workflow(
+            "Processes PDF files. Use when working with documents."
+        ));
+    }
+
+    #[test]
+    fn metrics_diagnostic_is_info_i016() {
+        let d = metrics_diagnostic(&body_metrics("Run it. Stop now.\n\n1. One.\n2. Two.\n"));
+        assert_eq!(d.severity, Severity::Info);
+        assert_eq!(d.code, I016);
+        assert_eq!(
+            d.message,
+            "body readability: 2.0 words per sentence, 0% code, 2 step(s) vs 1 paragraph(s), 1 numbered procedure(s)"
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Terminal styling for text reports.
+//!
+//! Text output colors diagnostics by severity: errors red, warnings yellow,
+//! info and hints dim, suggestions cyan. A [`Styler`] wraps the existing
+//! `Display` output in ANSI escape codes, or leaves it untouched when color
+//! is off, so a report renders the same text either way and only the escape
+//! codes differ.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::errors::AigentError;
+
+/// When to color text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color only when writing to a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    /// Always color, even when `NO_COLOR` is set or output is redirected.
+    Always,
+    /// Never color.
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether to color, given whether the output stream is a
+    /// terminal and whether `NO_COLOR` is set to a non-empty value.
+    #[must_use]
+    pub fn resolve(self, is_terminal: bool, no_color: bool) -> bool {
+        match self {
+            Self::Auto => is_terminal && !no_color,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+
+    /// Decide whether to color a stream, reading `NO_COLOR` from the
+    /// environment.
+    #[must_use]
+    pub fn for_stream(self, is_terminal: bool) -> bool {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        self.resolve(is_terminal, no_color)
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = AigentError;
+
+    /// Parse `auto`, `always`, or `never`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(AigentError::Parse {
+                message: format!("invalid color choice \"{s}\" (expected auto, always, or never)"),
+            }),
+        }
+    }
+}
+
+/// A text style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Errors and failures (red).
+    Error,
+    /// Warnings (yellow).
+    Warning,
+    /// Info and hints (dim).
+    Info,
+    /// Suggested fixes and next steps (cyan).
+    Suggestion,
+    /// Passing checks (green).
+    Success,
+}
+
+impl Style {
+    /// The ANSI escape sequence that starts this style.
+    fn code(self) -> &'static str {
+        match self {
+            Self::Error => "\x1b[31m",
+            Self::Warning => "\x1b[33m",
+            Self::Info => "\x1b[2m",
+            Self::Suggestion => "\x1b[36m",
+            Self::Success => "\x1b[32m",
+        }
+    }
+
+    /// The style for diagnostics of a given severity.
+    #[must_use]
+    pub fn for_severity(severity: Severity) -> Self {
+        match severity {
+            Severity::Error => Self::Error,
+            Severity::Warning => Self::Warning,
+            Severity::Info | Severity::Hint => Self::Info,
+        }
+    }
+}
+
+/// ANSI sequence that resets all styles.
+const RESET: &str = "\x1b[0m";
+
+/// Applies [`Style`]s to text, or passes text through when disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Styler {
+    enabled: bool,
+}
+
+impl Styler {
+    /// A styler that never emits escape codes.
+    pub const PLAIN: Self = Self { enabled: false };
+
+    /// A styler that colors when `enabled` is `true`.
+    #[must_use]
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Returns `true` if this styler emits escape codes.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Wrap `value`'s `Display` output in `style`.
+    #[must_use]
+    pub fn paint<T: fmt::Display>(&self, style: Style, value: T) -> Painted<T> {
+        Painted {
+            style: self.enabled.then_some(style),
+            value,
+        }
+    }
+
+    /// Render a diagnostic in the color of its severity.
+    #[must_use]
+    pub fn diagnostic<'a>(&self, diagnostic: &'a Diagnostic) -> Painted<&'a Diagnostic> {
+        self.paint(Style::for_severity(diagnostic.severity), diagnostic)
+    }
+
+    /// Render `[PASS]` or `[FAIL]`.
+    #[must_use]
+    pub fn status(&self, passed: bool) -> Painted<&'static str> {
+        if passed {
+            self.paint(Style::Success, "PASS")
+        } else {
+            self.paint(Style::Error, "FAIL")
+        }
+    }
+
+    /// Render the multi-skill summary line
+    /// (`"N skills: A ok, B errors, C warnings only"`), coloring non-zero
+    /// error and warning counts.
+    #[must_use]
+    pub fn summary(&self, total: usize, ok: usize, errors: usize, warnings: usize) -> String {
+        let errors = if errors > 0 {
+            self.paint(Style::Error, format!("{errors} errors"))
+                .to_string()
+        } else {
+            format!("{errors} errors")
+        };
+        let warnings = if warnings > 0 {
+            self.paint(Style::Warning, format!("{warnings} warnings only"))
+                .to_string()
+        } else {
+            format!("{warnings} warnings only")
+        };
+        format!("{total} skills: {ok} ok, {errors}, {warnings}")
+    }
+
+    /// Color a unified diff: removed lines red, added lines green, hunk
+    /// headers cyan. File headers (`---`/`+++`) are left plain.
+    #[must_use]
+    pub fn diff(&self, diff: &str) -> String {
+        if !self.enabled {
+            return diff.to_string();
+        }
+        let mut out = String::with_capacity(diff.len());
+        for line in diff.split_inclusive('\n') {
+            let (text, newline) = match line.strip_suffix('\n') {
+                Some(text) => (text, "\n"),
+                None => (line, ""),
+            };
+            let style = if text.starts_with("+++") || text.starts_with("---") {
+                None
+            } else if text.starts_with('+') {
+                Some(Style::Success)
+            } else if text.starts_with('-') {
+                Some(Style::Error)
+            } else if text.starts_with("@@") {
+                Some(Style::Suggestion)
+            } else {
+                None
+            };
+            match style {
+                Some(style) => out.push_str(&self.paint(style, text).to_string()),
+                None => out.push_str(text),
+            }
+            out.push_str(newline);
+        }
+        out
+    }
+}
+
+/// A value whose `Display` output is wrapped in a style.
+///
+/// Created by [`Styler::paint`]; displays as the plain value when the styler
+/// is disabled.
+#[derive(Debug, Clone, Copy)]
+pub struct Painted<T> {
+    style: Option<Style>,
+    value: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Some(style) => write!(f, "{}{}{RESET}", style.code(), self.value),
+            None => self.value.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{E001, I001, W001};
+
+    const COLOR: Styler = Styler { enabled: true };
+
+    #[test]
+    fn plain_styler_leaves_display_unchanged() {
+        let d = Diagnostic::new(Severity::Warning, W001, "unexpected field");
+        assert_eq!(
+            Styler::PLAIN.diagnostic(&d).to_string(),
+            "warning: unexpected field"
+        );
+        assert_eq!(Styler::PLAIN.status(false).to_string(), "FAIL");
+    }
+
+    #[test]
+    fn diagnostics_are_colored_by_severity() {
+        let error = Diagnostic::new(Severity::Error, E001, "name must not be empty");
+        let warning = Diagnostic::new(Severity::Warning, W001, "unexpected field");
+        let info = Diagnostic::new(Severity::Info, I001, "consider a shorter name");
+        assert_eq!(
+            COLOR.diagnostic(&error).to_string(),
+            "\x1b[31mname must not be empty\x1b[0m"
+        );
+        assert_eq!(
+            COLOR.diagnostic(&warning).to_string(),
+            "\x1b[33mwarning: unexpected field\x1b[0m"
+        );
+        assert_eq!(
+            COLOR.diagnostic(&info).to_string(),
+            "\x1b[2minfo: consider a shorter name\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn suggestions_are_cyan() {
+        assert_eq!(
+            COLOR
+                .paint(Style::Suggestion, "Run with --apply")
+                .to_string(),
+            "\x1b[36mRun with --apply\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn summary_colors_only_nonzero_counts() {
+        assert_eq!(
+            Styler::PLAIN.summary(3, 1, 1, 1),
+            "3 skills: 1 ok, 1 errors, 1 warnings only"
+        );
+        assert_eq!(
+            COLOR.summary(3, 1, 1, 1),
+            "3 skills: 1 ok, \x1b[31m1 errors\x1b[0m, \x1b[33m1 warnings only\x1b[0m"
+        );
+        assert_eq!(
+            COLOR.summary(2, 2, 0, 0),
+            "2 skills: 2 ok, 0 errors, 0 warnings only"
+        );
+    }
+
+    #[test]
+    fn diff_colors_changed_lines_only() {
+        let diff = "--- a/SKILL.md\n+++ b/SKILL.md\n@@ -1 +1 @@\n-old\n+new\n same\n";
+        assert_eq!(Styler::PLAIN.diff(diff), diff);
+        assert_eq!(
+            COLOR.diff(diff),
+            "--- a/SKILL.md\n+++ b/SKILL.md\n\x1b[36m@@ -1 +1 @@\x1b[0m\n\x1b[31m-old\x1b[0m\n\x1b[32m+new\x1b[0m\n same\n"
+        );
+    }
+
+    #[test]
+    fn color_choice_resolution() {
+        assert!(ColorChoice::Auto.resolve(true, false));
+        assert!(!ColorChoice::Auto.resolve(false, false));
+        assert!(!ColorChoice::Auto.resolve(true, true));
+        assert!(ColorChoice::Always.resolve(false, true));
+        assert!(!ColorChoice::Never.resolve(true, false));
+    }
+
+    #[test]
+    fn color_choice_parses() {
+        assert_eq!(
+            "always".parse::<ColorChoice>().unwrap(),
+            ColorChoice::Always
+        );
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+}
@@ -5,29 +5,101 @@
 //!
 //! Scoring weights:
 //! - Structural checks (validation): 60 points base
-//! - Quality checks (lint): 8 points per check (5 checks × 8 = 40 max)
+//! - Quality checks: 40 points — 6 per lint check (5 checks × 6 = 30),
+//!   6 for a semver `metadata.version` (3 if present but not semver), and 4
+//!   for a numbered procedure when the description implies a workflow
 //!
 //! A perfect skill with no validation errors and no lint issues scores 100.
+//!
+//! When scoring many skills at once, [`score_skills`] and [`summarize`]
+//! produce per-skill results plus a distribution summary. [`score_diff`]
+//! compares two revisions of one skill check by check.
+//!
+//! Each result also carries a [`Risk`] level for triage. It does not change
+//! the score: it weighs the points a skill is missing by the size of its
+//! body, since a large skill costs more context when it loads, so the same
+//! shortfall matters more (see [`assess_risk`]).
+//!
+//! [`summarize`]: crate::scorer::summarize
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
+use regex::Regex;
 use serde::Serialize;
 
-use crate::diagnostics::Diagnostic;
+use crate::diagnostics::{
+    Diagnostic, E000, E001, E002, E003, E004, E005, E006, E007, E008, E009, E010, E011, E012, E014,
+    E015, E016, E017, E018, E023, W001, W002,
+};
 use crate::linter;
+use crate::models::SkillProperties;
+use crate::readability::{body_metrics, implies_workflow, BodyMetrics};
+use crate::reporting::{Style, Styler};
 use crate::validator;
 
 /// Points per passing structural check.
 const STRUCTURAL_POINTS_PER_CHECK: u32 = 10;
 
 /// Points per passing lint check.
-const LINT_POINTS_PER_CHECK: u32 = 8;
+const LINT_POINTS_PER_CHECK: u32 = 6;
 
 /// Total number of lint checks.
 const LINT_CHECK_COUNT: u32 = 5;
 
-/// Maximum points for quality (lint) checks.
-const QUALITY_MAX: u32 = LINT_POINTS_PER_CHECK * LINT_CHECK_COUNT;
+/// Points for a version that is valid semver.
+const VERSION_POINTS: u32 = 6;
+
+/// Points for a version that is present but not semver.
+const VERSION_PARTIAL_POINTS: u32 = 3;
+
+/// Points for the numbered-procedure check.
+const PROCEDURE_POINTS: u32 = 4;
+
+/// Semantic version: `MAJOR.MINOR.PATCH` with optional pre-release and build
+/// metadata.
+static SEMVER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$")
+        .expect("semver regex must compile")
+});
+
+/// Maximum number of skills listed in [`ScoreSummary::lowest`].
+const LOWEST_COUNT: usize = 5;
+
+/// Body size bands for [`risk_multiplier`]: (largest estimated token count
+/// in the band, multiplier).
+const RISK_SIZE_BANDS: &[(usize, u32)] = &[(1000, 1), (3000, 2)];
+
+/// Multiplier for bodies larger than every band in [`RISK_SIZE_BANDS`].
+const RISK_MAX_MULTIPLIER: u32 = 3;
+
+/// Weighted shortfall at which risk becomes [`Risk::Medium`].
+const RISK_MEDIUM_AT: u32 = 20;
+
+/// Weighted shortfall at which risk becomes [`Risk::High`].
+const RISK_HIGH_AT: u32 = 45;
+
+/// Triage risk of a skill: its missing points weighted by body size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Risk {
+    /// Small shortfall, or a small skill.
+    Low,
+    /// Moderate shortfall for its size.
+    Medium,
+    /// Large shortfall in a large skill.
+    High,
+}
+
+impl std::fmt::Display for Risk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        })
+    }
+}
 
 /// Result of scoring a skill directory.
 #[derive(Debug, Clone, Serialize)]
@@ -40,6 +112,14 @@ pub struct ScoreResult {
     pub structural: CategoryResult,
     /// Quality (lint) check breakdown.
     pub quality: CategoryResult,
+    /// Estimated token count of the body (0 if unreadable).
+    pub body_tokens: usize,
+    /// Readability metrics of the body.
+    pub body_metrics: BodyMetrics,
+    /// Size multiplier applied to the shortfall (see [`risk_multiplier`]).
+    pub risk_multiplier: u32,
+    /// Triage risk; does not affect `total`.
+    pub risk: Risk,
 }
 
 /// Breakdown for a scoring category (structural or quality).
@@ -67,6 +147,10 @@ pub struct CheckResult {
     pub fail_label: Option<String>,
     /// Whether this check passed.
     pub passed: bool,
+    /// Points earned by this check.
+    pub points: u32,
+    /// Points this check is worth when it passes.
+    pub max_points: u32,
     /// Diagnostic message if the check failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
@@ -87,6 +171,50 @@ impl CheckResult {
     }
 }
 
+/// Score of a single skill within a multi-skill run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillScore {
+    /// Skill name (from frontmatter, or the directory name if unparseable).
+    pub name: String,
+    /// Path to the skill directory.
+    pub path: PathBuf,
+    /// Full score result for this skill.
+    #[serde(flatten)]
+    pub result: ScoreResult,
+}
+
+/// A skill name paired with its total score.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NamedTotal {
+    /// Skill name.
+    pub name: String,
+    /// Overall quality score (0–100).
+    pub total: u32,
+}
+
+/// Distribution summary over a set of skill scores.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreSummary {
+    /// Number of skills scored.
+    pub count: usize,
+    /// Lowest total score.
+    pub min: u32,
+    /// Median total score (mean of the two middle scores for even counts).
+    pub median: f64,
+    /// Highest total score.
+    pub max: u32,
+    /// Mean total score.
+    pub mean: f64,
+    /// Minimum acceptable score used for the gate.
+    pub gate: u32,
+    /// Number of skills scoring below `gate`.
+    pub below_gate: usize,
+    /// Lowest-scoring imperfect skills, worst first (at most 5).
+    pub lowest: Vec<NamedTotal>,
+    /// Every skill rated [`Risk::High`], worst first.
+    pub high_risk: Vec<NamedTotal>,
+}
+
 /// Score a skill directory against Anthropic best-practices.
 ///
 /// Runs both structural validation and semantic lint checks, then
@@ -102,20 +230,46 @@ impl CheckResult {
 #[must_use]
 pub fn score(dir: &Path) -> ScoreResult {
     let validation_diags = validator::validate(dir);
-    let structural = score_structural(&validation_diags);
-
-    // Only run lint checks if the skill is parseable (no infrastructure errors).
-    let quality = match crate::parser::read_properties(dir) {
-        Ok(props) => {
-            let body = crate::parser::read_body(dir).unwrap_or_default();
-            let lint_diags = linter::lint(&props, &body);
-            score_quality(&lint_diags)
-        }
-        Err(_) => {
-            // If we can't parse the skill, all lint checks fail.
-            all_quality_checks_failed()
-        }
+    let body = crate::parser::read_body(dir).unwrap_or_default();
+
+    // Only run quality checks if the skill is parseable (no infrastructure errors).
+    let props = crate::parser::read_properties(dir).ok();
+    combine(&validation_diags, props.as_ref(), &body)
+}
+
+/// Score `SKILL.md` content held in memory.
+///
+/// Same checks as [`score`], without reading from disk. `dir` is only used
+/// to check that the name matches the directory name.
+#[must_use]
+pub fn score_content(content: &str, dir: Option<&Path>) -> ScoreResult {
+    let validation_diags =
+        validator::validate_content(content, dir, crate::diagnostics::ValidationTarget::Standard);
+    let body = crate::parser::parse_frontmatter(content)
+        .map(|(_, body)| body)
+        .unwrap_or_default();
+    let props = crate::parser::parse_properties(content).ok();
+    combine(&validation_diags, props.as_ref(), &body)
+}
+
+/// Combine validation diagnostics and quality checks into a score. `props`
+/// is `None` when the skill could not be parsed, failing every quality check.
+fn combine(
+    validation_diags: &[Diagnostic],
+    props: Option<&SkillProperties>,
+    body: &str,
+) -> ScoreResult {
+    let structural = score_structural(validation_diags);
+    let metrics = body_metrics(body);
+    let quality = match props {
+        Some(props) => score_quality(
+            &linter::lint(props, body),
+            props.version().as_deref(),
+            score_procedure(&props.description, &metrics),
+        ),
+        None => all_quality_checks_failed(),
     };
+    let body_tokens = crate::prompt::estimate_tokens(body);
 
     let total = structural.score + quality.score;
     let max = structural.max + quality.max;
@@ -124,6 +278,270 @@ pub fn score(dir: &Path) -> ScoreResult {
         max,
         structural,
         quality,
+        body_tokens,
+        body_metrics: metrics,
+        risk_multiplier: risk_multiplier(body_tokens),
+        risk: assess_risk(total, max, body_tokens),
+    }
+}
+
+/// Size multiplier for a body of `body_tokens` estimated tokens: 1 up to
+/// 1000 tokens, 2 up to 3000, and 3 beyond.
+#[must_use]
+pub fn risk_multiplier(body_tokens: usize) -> u32 {
+    RISK_SIZE_BANDS
+        .iter()
+        .find(|&&(limit, _)| body_tokens <= limit)
+        .map_or(RISK_MAX_MULTIPLIER, |&(_, multiplier)| multiplier)
+}
+
+/// Assess triage risk from a score and the body size.
+///
+/// The shortfall (`max - total`) is multiplied by [`risk_multiplier`]: a
+/// weighted shortfall under 20 is low risk, under 45 medium, otherwise high.
+/// So a small skill at 70/100 is medium risk and a large one high.
+#[must_use]
+pub fn assess_risk(total: u32, max: u32, body_tokens: usize) -> Risk {
+    let weighted = max.saturating_sub(total) * risk_multiplier(body_tokens);
+    if weighted >= RISK_HIGH_AT {
+        Risk::High
+    } else if weighted >= RISK_MEDIUM_AT {
+        Risk::Medium
+    } else {
+        Risk::Low
+    }
+}
+
+/// Score several skill directories, pairing each result with its skill name.
+///
+/// The name is taken from the frontmatter when the skill parses; otherwise
+/// the directory name is used so unparseable skills still appear by name.
+#[must_use]
+pub fn score_skills(dirs: &[&Path]) -> Vec<SkillScore> {
+    dirs.iter()
+        .map(|dir| {
+            let name = crate::parser::read_properties(dir)
+                .map(|props| props.name)
+                .unwrap_or_else(|_| {
+                    dir.file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| dir.display().to_string())
+                });
+            SkillScore {
+                name,
+                path: dir.to_path_buf(),
+                result: score(dir),
+            }
+        })
+        .collect()
+}
+
+/// Summarize the distribution of `scores` against a minimum `gate`.
+///
+/// Skills with a total below `gate` are counted in `below_gate`. The
+/// `lowest` list holds up to five imperfect skills, worst first, with ties
+/// broken by name. An empty input yields an all-zero summary.
+#[must_use]
+pub fn summarize(scores: &[SkillScore], gate: u32) -> ScoreSummary {
+    let mut totals: Vec<u32> = scores.iter().map(|s| s.result.total).collect();
+    totals.sort_unstable();
+
+    let count = totals.len();
+    let median = match count {
+        0 => 0.0,
+        n if n % 2 == 1 => f64::from(totals[n / 2]),
+        n => (f64::from(totals[n / 2 - 1]) + f64::from(totals[n / 2])) / 2.0,
+    };
+    let mean = if count == 0 {
+        0.0
+    } else {
+        totals.iter().map(|&t| f64::from(t)).sum::<f64>() / count as f64
+    };
+
+    let mut lowest: Vec<NamedTotal> = scores
+        .iter()
+        .filter(|s| s.result.total < s.result.max)
+        .map(|s| NamedTotal {
+            name: s.name.clone(),
+            total: s.result.total,
+        })
+        .collect();
+    lowest.sort_by(|a, b| a.total.cmp(&b.total).then_with(|| a.name.cmp(&b.name)));
+    lowest.truncate(LOWEST_COUNT);
+
+    let mut high_risk: Vec<NamedTotal> = scores
+        .iter()
+        .filter(|s| s.result.risk == Risk::High)
+        .map(|s| NamedTotal {
+            name: s.name.clone(),
+            total: s.result.total,
+        })
+        .collect();
+    high_risk.sort_by(|a, b| a.total.cmp(&b.total).then_with(|| a.name.cmp(&b.name)));
+
+    ScoreSummary {
+        count,
+        min: totals.first().copied().unwrap_or(0),
+        median,
+        max: totals.last().copied().unwrap_or(0),
+        mean,
+        gate,
+        below_gate: totals.iter().filter(|&&t| t < gate).count(),
+        lowest,
+        high_risk,
+    }
+}
+
+/// Change in one checklist item between two revisions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckDelta {
+    /// Category the check belongs to (`structural` or `quality`).
+    pub category: String,
+    /// Check label (the pass label, so it is the same in both revisions).
+    pub label: String,
+    /// Whether the check passes in the new revision.
+    pub passed: bool,
+    /// Points gained (positive), lost (negative), or 0 if unchanged.
+    pub points: i64,
+}
+
+/// Check-by-check comparison of two scores of the same skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScoreDiff {
+    /// Total score of the old revision.
+    pub old_total: u32,
+    /// Total score of the new revision.
+    pub new_total: u32,
+    /// Maximum possible score.
+    pub max: u32,
+    /// `new_total - old_total`.
+    pub delta: i64,
+    /// Checks that earn more points in the new revision than in the old.
+    pub gained: Vec<CheckDelta>,
+    /// Checks that earn fewer points in the new revision than in the old.
+    pub lost: Vec<CheckDelta>,
+    /// Checks with the same points in both revisions.
+    pub unchanged: Vec<CheckDelta>,
+    /// Explanation when a revision could not be scored normally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Compare the scores of two revisions of a skill directory.
+///
+/// If the old revision cannot be parsed at all (including when it does not
+/// exist), it is scored as 0 with every check failed, and the diff carries
+/// a note saying so.
+#[must_use]
+pub fn score_diff(old_dir: &Path, new_dir: &Path) -> ScoreDiff {
+    let new = score(new_dir);
+    match crate::parser::read_properties(old_dir) {
+        Ok(_) => diff_scores(&score(old_dir), &new),
+        Err(e) => {
+            let mut diff = diff_scores(&zero_score(&new), &new);
+            diff.note = Some(format!(
+                "old revision could not be parsed ({e}); scored as 0"
+            ));
+            diff
+        }
+    }
+}
+
+/// Compare two pre-computed scores check by check.
+///
+/// Checks are paired by category and pass label. A check present in only
+/// one of the results counts as earning no points in the other.
+#[must_use]
+pub fn diff_scores(old: &ScoreResult, new: &ScoreResult) -> ScoreDiff {
+    let mut diff = ScoreDiff {
+        old_total: old.total,
+        new_total: new.total,
+        max: new.max,
+        delta: i64::from(new.total) - i64::from(old.total),
+        gained: Vec::new(),
+        lost: Vec::new(),
+        unchanged: Vec::new(),
+        note: None,
+    };
+
+    for (category, old_cat, new_cat) in [
+        ("structural", &old.structural, &new.structural),
+        ("quality", &old.quality, &new.quality),
+    ] {
+        for check in &new_cat.checks {
+            let old_points = old_cat
+                .checks
+                .iter()
+                .find(|c| c.label == check.label)
+                .map_or(0, |c| c.points);
+            let item = CheckDelta {
+                category: category.to_string(),
+                label: check.label.clone(),
+                passed: check.passed,
+                points: i64::from(check.points) - i64::from(old_points),
+            };
+            match item.points {
+                p if p > 0 => diff.gained.push(item),
+                p if p < 0 => diff.lost.push(item),
+                _ => diff.unchanged.push(item),
+            }
+        }
+    }
+    diff
+}
+
+/// A score with every check of `template` failed and no points.
+fn zero_score(template: &ScoreResult) -> ScoreResult {
+    let fail_all = |cat: &CategoryResult| CategoryResult {
+        score: 0,
+        max: cat.max,
+        checks: cat
+            .checks
+            .iter()
+            .map(|c| CheckResult {
+                passed: false,
+                points: 0,
+                message: None,
+                ..c.clone()
+            })
+            .collect(),
+    };
+    ScoreResult {
+        total: 0,
+        max: template.max,
+        structural: fail_all(&template.structural),
+        quality: fail_all(&template.quality),
+        body_tokens: template.body_tokens,
+        body_metrics: template.body_metrics,
+        risk_multiplier: template.risk_multiplier,
+        risk: assess_risk(0, template.max, template.body_tokens),
+    }
+}
+
+/// A pass/fail check worth `points`. It fails when `failure` holds a
+/// diagnostic, whose message is reported.
+fn binary_check(
+    label: &str,
+    fail_label: &str,
+    failure: Option<&Diagnostic>,
+    points: u32,
+) -> CheckResult {
+    CheckResult {
+        label: label.to_string(),
+        fail_label: Some(fail_label.to_string()),
+        passed: failure.is_none(),
+        points: if failure.is_none() { points } else { 0 },
+        max_points: points,
+        message: failure.map(|d| d.message.clone()),
+    }
+}
+
+/// Build a category from its checks, summing earned and possible points.
+fn category(checks: Vec<CheckResult>) -> CategoryResult {
+    CategoryResult {
+        score: checks.iter().map(|c| c.points).sum(),
+        max: checks.iter().map(|c| c.max_points).sum(),
+        checks,
     }
 }
 
@@ -132,234 +550,383 @@ pub fn score(dir: &Path) -> ScoreResult {
 /// Each of the 6 checks independently earns 10 points (60 max).
 /// Individual checks are derived from validation diagnostic codes.
 fn score_structural(diags: &[Diagnostic]) -> CategoryResult {
-    let checks = vec![
-        CheckResult {
-            label: "SKILL.md exists and is parseable".to_string(),
-            fail_label: Some("SKILL.md missing or unparseable".to_string()),
-            passed: !diags.iter().any(|d| d.code == "E000"),
-            message: diags
-                .iter()
-                .find(|d| d.code == "E000")
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Name format valid".to_string(),
-            fail_label: Some("Name format invalid".to_string()),
-            passed: !diags.iter().any(|d| {
+    category(vec![
+        binary_check(
+            "SKILL.md exists and is parseable",
+            "SKILL.md missing or unparseable",
+            diags.iter().find(|d| d.code == E000),
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+        binary_check(
+            "Name format valid",
+            "Name format invalid",
+            diags.iter().find(|d| {
                 matches!(
                     d.code,
-                    "E001" | "E002" | "E003" | "E004" | "E005" | "E006" | "E007" | "E008" | "E009"
+                    E001 | E002 | E003 | E004 | E005 | E006 | E007 | E008 | E009 | E023
                 )
             }),
-            message: diags
-                .iter()
-                .find(|d| {
-                    matches!(
-                        d.code,
-                        "E001"
-                            | "E002"
-                            | "E003"
-                            | "E004"
-                            | "E005"
-                            | "E006"
-                            | "E007"
-                            | "E008"
-                            | "E009"
-                    )
-                })
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Description valid".to_string(),
-            fail_label: Some("Description invalid".to_string()),
-            passed: !diags
-                .iter()
-                .any(|d| matches!(d.code, "E010" | "E011" | "E012")),
-            message: diags
-                .iter()
-                .find(|d| matches!(d.code, "E010" | "E011" | "E012"))
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Required fields present".to_string(),
-            fail_label: Some("Required fields missing".to_string()),
-            passed: !diags
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+        binary_check(
+            "Description valid",
+            "Description invalid",
+            diags.iter().find(|d| matches!(d.code, E010 | E011 | E012)),
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+        binary_check(
+            "Required fields present",
+            "Required fields missing",
+            diags
                 .iter()
-                .any(|d| matches!(d.code, "E014" | "E015" | "E016" | "E017" | "E018")),
-            message: diags
-                .iter()
-                .find(|d| matches!(d.code, "E014" | "E015" | "E016" | "E017" | "E018"))
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "No unknown fields".to_string(),
-            fail_label: Some("Unknown fields found".to_string()),
-            passed: !diags.iter().any(|d| d.code == "W001"),
-            message: diags
-                .iter()
-                .find(|d| d.code == "W001")
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Body within size limits".to_string(),
-            fail_label: Some("Body exceeds size limits".to_string()),
-            passed: !diags.iter().any(|d| d.code == "W002"),
-            message: diags
-                .iter()
-                .find(|d| d.code == "W002")
-                .map(|d| d.message.clone()),
-        },
-    ];
+                .find(|d| matches!(d.code, E014 | E015 | E016 | E017 | E018)),
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+        binary_check(
+            "No unknown fields",
+            "Unknown fields found",
+            diags.iter().find(|d| d.code == W001),
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+        binary_check(
+            "Body within size limits",
+            "Body exceeds size limits",
+            diags.iter().find(|d| d.code == W002),
+            STRUCTURAL_POINTS_PER_CHECK,
+        ),
+    ])
+}
 
-    let passing = checks.iter().filter(|c| c.passed).count() as u32;
-    let points = passing * STRUCTURAL_POINTS_PER_CHECK;
-    let max = checks.len() as u32 * STRUCTURAL_POINTS_PER_CHECK;
+/// Labels of the lint checks: (pass label, fail label, lint code).
+const LINT_CHECKS: [(&str, &str, crate::diagnostics::DiagnosticCode); LINT_CHECK_COUNT as usize] = [
+    (
+        "Third-person description",
+        "Not third-person description",
+        linter::I001,
+    ),
+    (
+        "Trigger phrase present",
+        "Trigger phrase missing",
+        linter::I002,
+    ),
+    ("Gerund name form", "Non-gerund name form", linter::I003),
+    ("Specific name", "Generic name", linter::I004),
+    (
+        "Detailed description",
+        "Description too short",
+        linter::I005,
+    ),
+];
 
-    CategoryResult {
-        score: points,
-        max,
-        checks,
-    }
-}
+/// Pass label of the version check.
+const VERSION_LABEL: &str = "Semver version";
+
+/// Fail label of the version check.
+const VERSION_FAIL_LABEL: &str = "Version missing or not semver";
+
+/// Pass label of the numbered-procedure check.
+const PROCEDURE_LABEL: &str = "Numbered procedure for workflow";
 
-/// Score the quality (lint) category.
+/// Fail label of the numbered-procedure check.
+const PROCEDURE_FAIL_LABEL: &str = "Workflow without numbered procedure";
+
+/// Score the quality category.
 ///
-/// 8 points per passing lint check (5 checks × 8 = 40 max).
-fn score_quality(lint_diags: &[Diagnostic]) -> CategoryResult {
-    let checks = vec![
-        CheckResult {
-            label: "Third-person description".to_string(),
-            fail_label: Some("Not third-person description".to_string()),
-            passed: !lint_diags.iter().any(|d| d.code == linter::I001),
-            message: lint_diags
-                .iter()
-                .find(|d| d.code == linter::I001)
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Trigger phrase present".to_string(),
-            fail_label: Some("Trigger phrase missing".to_string()),
-            passed: !lint_diags.iter().any(|d| d.code == linter::I002),
-            message: lint_diags
-                .iter()
-                .find(|d| d.code == linter::I002)
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Gerund name form".to_string(),
-            fail_label: Some("Non-gerund name form".to_string()),
-            passed: !lint_diags.iter().any(|d| d.code == linter::I003),
-            message: lint_diags
-                .iter()
-                .find(|d| d.code == linter::I003)
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Specific name".to_string(),
-            fail_label: Some("Generic name".to_string()),
-            passed: !lint_diags.iter().any(|d| d.code == linter::I004),
-            message: lint_diags
-                .iter()
-                .find(|d| d.code == linter::I004)
-                .map(|d| d.message.clone()),
-        },
-        CheckResult {
-            label: "Detailed description".to_string(),
-            fail_label: Some("Description too short".to_string()),
-            passed: !lint_diags.iter().any(|d| d.code == linter::I005),
-            message: lint_diags
-                .iter()
-                .find(|d| d.code == linter::I005)
-                .map(|d| d.message.clone()),
-        },
-    ];
+/// 6 points per passing lint check (5 checks × 6 = 30), plus the version
+/// check (see [`score_version`]) and `procedure`, for 40 max.
+fn score_quality(
+    lint_diags: &[Diagnostic],
+    version: Option<&str>,
+    procedure: CheckResult,
+) -> CategoryResult {
+    let mut checks: Vec<CheckResult> = LINT_CHECKS
+        .iter()
+        .map(|&(label, fail_label, code)| {
+            binary_check(
+                label,
+                fail_label,
+                lint_diags.iter().find(|d| d.code == code),
+                LINT_POINTS_PER_CHECK,
+            )
+        })
+        .collect();
+    checks.push(score_version(version));
+    checks.push(procedure);
+    category(checks)
+}
 
-    let passing = checks.iter().filter(|c| c.passed).count() as u32;
-    let points = passing * LINT_POINTS_PER_CHECK;
+/// Score `metadata.version` (or a top-level `version`): full points for
+/// valid semver, partial points when present but not semver, none when
+/// absent.
+fn score_version(version: Option<&str>) -> CheckResult {
+    let (points, message) = match version {
+        Some(v) if SEMVER_RE.is_match(v) => (VERSION_POINTS, None),
+        Some(v) => (
+            VERSION_PARTIAL_POINTS,
+            Some(format!(
+                "version \"{v}\" is not semver (MAJOR.MINOR.PATCH); partial credit"
+            )),
+        ),
+        None => (0, Some("no `metadata.version` set".to_string())),
+    };
+    CheckResult {
+        label: VERSION_LABEL.to_string(),
+        fail_label: Some(VERSION_FAIL_LABEL.to_string()),
+        passed: points == VERSION_POINTS,
+        points,
+        max_points: VERSION_POINTS,
+        message,
+    }
+}
 
-    CategoryResult {
-        score: points,
-        max: QUALITY_MAX,
-        checks,
+/// Score the body's structure against the description: a description that
+/// implies a workflow (see [`implies_workflow`]) needs a numbered procedure
+/// in the body. Other skills pass.
+fn score_procedure(description: &str, metrics: &BodyMetrics) -> CheckResult {
+    let missing = implies_workflow(description) && metrics.numbered_procedures == 0;
+    CheckResult {
+        label: PROCEDURE_LABEL.to_string(),
+        fail_label: Some(PROCEDURE_FAIL_LABEL.to_string()),
+        passed: !missing,
+        points: if missing { 0 } else { PROCEDURE_POINTS },
+        max_points: PROCEDURE_POINTS,
+        message: missing.then(|| {
+            "description implies a workflow, but the body has no numbered list of steps".to_string()
+        }),
     }
 }
 
 /// All quality checks failed (used when skill can't be parsed).
 fn all_quality_checks_failed() -> CategoryResult {
-    CategoryResult {
-        score: 0,
-        max: QUALITY_MAX,
-        checks: vec![
-            CheckResult {
-                label: "Third-person description".to_string(),
-                fail_label: Some("Not third-person description".to_string()),
-                passed: false,
-                message: Some("Skill could not be parsed".to_string()),
-            },
-            CheckResult {
-                label: "Trigger phrase present".to_string(),
-                fail_label: Some("Trigger phrase missing".to_string()),
-                passed: false,
-                message: Some("Skill could not be parsed".to_string()),
-            },
-            CheckResult {
-                label: "Gerund name form".to_string(),
-                fail_label: Some("Non-gerund name form".to_string()),
-                passed: false,
-                message: Some("Skill could not be parsed".to_string()),
-            },
-            CheckResult {
-                label: "Specific name".to_string(),
-                fail_label: Some("Generic name".to_string()),
-                passed: false,
-                message: Some("Skill could not be parsed".to_string()),
-            },
-            CheckResult {
-                label: "Detailed description".to_string(),
-                fail_label: Some("Description too short".to_string()),
-                passed: false,
-                message: Some("Skill could not be parsed".to_string()),
-            },
-        ],
-    }
+    let unparsed = |label: &str, fail_label: &str, max_points: u32| CheckResult {
+        label: label.to_string(),
+        fail_label: Some(fail_label.to_string()),
+        passed: false,
+        points: 0,
+        max_points,
+        message: Some("Skill could not be parsed".to_string()),
+    };
+    let mut checks: Vec<CheckResult> = LINT_CHECKS
+        .iter()
+        .map(|&(label, fail_label, _)| unparsed(label, fail_label, LINT_POINTS_PER_CHECK))
+        .collect();
+    checks.push(unparsed(VERSION_LABEL, VERSION_FAIL_LABEL, VERSION_POINTS));
+    checks.push(unparsed(
+        PROCEDURE_LABEL,
+        PROCEDURE_FAIL_LABEL,
+        PROCEDURE_POINTS,
+    ));
+    category(checks)
 }
 
 /// Format a `ScoreResult` as human-readable text.
 #[must_use]
 pub fn format_text(result: &ScoreResult) -> String {
+    format_text_styled(result, &Styler::PLAIN)
+}
+
+/// Format a `ScoreResult` as human-readable text, coloring `PASS`/`FAIL`
+/// markers and failure messages with `styler`.
+#[must_use]
+pub fn format_text_styled(result: &ScoreResult, styler: &Styler) -> String {
     let mut out = String::new();
 
     out.push_str(&format!("Score: {}/{}\n", result.total, result.max));
-
+    let risk_style = match result.risk {
+        Risk::Low => Style::Success,
+        Risk::Medium => Style::Warning,
+        Risk::High => Style::Error,
+    };
     out.push_str(&format!(
-        "\nStructural ({}/{}):\n",
-        result.structural.score, result.structural.max
+        "Risk: {} (~{} body tokens, x{})\n",
+        styler.paint(risk_style, result.risk),
+        result.body_tokens,
+        result.risk_multiplier
     ));
-    for check in &result.structural.checks {
-        let status = if check.passed { "PASS" } else { "FAIL" };
-        let display_label = check.display_label();
-        out.push_str(&format!("  [{status}] {display_label}\n"));
-        if let Some(msg) = &check.message {
-            out.push_str(&format!("         {msg}\n"));
+
+    for (category, category_result) in [
+        ("Structural", &result.structural),
+        ("Quality", &result.quality),
+    ] {
+        out.push_str(&format!(
+            "\n{category} ({}/{}):\n",
+            category_result.score, category_result.max
+        ));
+        for check in &category_result.checks {
+            let status = styler.status(check.passed);
+            let display_label = check.display_label();
+            out.push_str(&format!("  [{status}] {display_label}\n"));
+            if let Some(msg) = &check.message {
+                out.push_str(&format!(
+                    "         {}\n",
+                    styler.paint(Style::Suggestion, msg)
+                ));
+            }
         }
     }
 
+    out
+}
+
+/// Format per-skill totals and a [`ScoreSummary`] as human-readable text.
+#[must_use]
+pub fn format_summary_text(scores: &[SkillScore], summary: &ScoreSummary) -> String {
+    let mut out = String::new();
+
+    for s in scores {
+        out.push_str(&format!(
+            "{:>3}/{}  {}",
+            s.result.total, s.result.max, s.name
+        ));
+        if s.result.risk != Risk::Low {
+            out.push_str(&format!("  (risk: {})", s.result.risk));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("\nSkills: {}\n", summary.count));
     out.push_str(&format!(
-        "\nQuality ({}/{}):\n",
-        result.quality.score, result.quality.max
+        "Scores: min {}, median {:.1}, max {}, mean {:.1}\n",
+        summary.min, summary.median, summary.max, summary.mean
     ));
-    for check in &result.quality.checks {
-        let status = if check.passed { "PASS" } else { "FAIL" };
-        let display_label = check.display_label();
-        out.push_str(&format!("  [{status}] {display_label}\n"));
-        if let Some(msg) = &check.message {
-            out.push_str(&format!("         {msg}\n"));
+    out.push_str(&format!("Below {}: {}\n", summary.gate, summary.below_gate));
+
+    if !summary.lowest.is_empty() {
+        out.push_str("\nLowest:\n");
+        for entry in &summary.lowest {
+            out.push_str(&format!("  {:>3}  {}\n", entry.total, entry.name));
+        }
+    }
+
+    if !summary.high_risk.is_empty() {
+        out.push_str("\nHigh risk:\n");
+        for entry in &summary.high_risk {
+            out.push_str(&format!("  {:>3}  {}\n", entry.total, entry.name));
         }
     }
 
     out
 }
 
+/// Format a `ScoreResult` as a markdown table for PR comments.
+///
+/// Lists each check with its category, earned/possible points, and status,
+/// followed by a bold total line.
+#[must_use]
+pub fn format_markdown(result: &ScoreResult) -> String {
+    let mut out = String::from("| Category | Check | Points | Status |\n");
+    out.push_str("|----------|-------|--------|--------|\n");
+    for (category, result) in [
+        ("Structural", &result.structural),
+        ("Quality", &result.quality),
+    ] {
+        for check in &result.checks {
+            let status = match check.points {
+                _ if check.passed => "PASS",
+                0 => "FAIL",
+                _ => "PARTIAL",
+            };
+            out.push_str(&format!(
+                "| {category} | {} | {}/{} | {status} |\n",
+                markdown_cell(check.display_label()),
+                check.points,
+                check.max_points
+            ));
+        }
+    }
+    out.push_str(&format!("\n**Total: {}/{}**\n", result.total, result.max));
+    if result.risk != Risk::Low {
+        out.push_str(&format!(
+            "\nRisk: {} (~{} body tokens)\n",
+            result.risk, result.body_tokens
+        ));
+    }
+    out
+}
+
+/// Format per-skill tables and a [`ScoreSummary`] as markdown.
+///
+/// Renders one section per skill (see [`format_markdown`]) and a summary
+/// section with the score distribution.
+#[must_use]
+pub fn format_summary_markdown(scores: &[SkillScore], summary: &ScoreSummary) -> String {
+    let mut out = String::new();
+
+    for s in scores {
+        out.push_str(&format!(
+            "## {} ({}/{})\n\n",
+            markdown_cell(&s.name),
+            s.result.total,
+            s.result.max
+        ));
+        out.push_str(&format_markdown(&s.result));
+        out.push('\n');
+    }
+
+    out.push_str("## Summary\n\n");
+    out.push_str(&format!("- Skills: {}\n", summary.count));
+    out.push_str(&format!(
+        "- Scores: min {}, median {:.1}, max {}, mean {:.1}\n",
+        summary.min, summary.median, summary.max, summary.mean
+    ));
+    out.push_str(&format!(
+        "- Below {}: {}\n",
+        summary.gate, summary.below_gate
+    ));
+    if !summary.high_risk.is_empty() {
+        let names: Vec<String> = summary
+            .high_risk
+            .iter()
+            .map(|e| format!("{} ({})", markdown_cell(&e.name), e.total))
+            .collect();
+        out.push_str(&format!("- High risk: {}\n", names.join(", ")));
+    }
+
+    out
+}
+
+/// Format a [`ScoreDiff`] as markdown for a PR comment.
+///
+/// A headline with the old and new totals is followed by bullet lists of
+/// gained and lost checks with their point deltas. Unchanged checks are
+/// only counted.
+#[must_use]
+pub fn format_diff_markdown(diff: &ScoreDiff) -> String {
+    let mut out = if diff.delta == 0 {
+        format!("**Score unchanged: {}/{}**\n", diff.new_total, diff.max)
+    } else {
+        format!(
+            "**Score changed {} → {} ({:+})**\n",
+            diff.old_total, diff.new_total, diff.delta
+        )
+    };
+    if let Some(note) = &diff.note {
+        out.push_str(&format!("\n> Note: {note}\n"));
+    }
+    for (heading, items) in [("Gained", &diff.gained), ("Lost", &diff.lost)] {
+        if items.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n{heading}:\n"));
+        for item in items {
+            out.push_str(&format!(
+                "- {} ({:+})\n",
+                markdown_cell(&item.label),
+                item.points
+            ));
+        }
+    }
+    if !diff.unchanged.is_empty() {
+        out.push_str(&format!("\nUnchanged: {} check(s)\n", diff.unchanged.len()));
+    }
+    out
+}
+
+/// Escape text for a markdown table cell.
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,6 +936,11 @@ mod tests {
     /// Expected structural max (6 checks × 10 points).
     const STRUCTURAL_MAX: u32 = 6 * STRUCTURAL_POINTS_PER_CHECK;
 
+    /// Expected quality max (5 lint checks × 6 points plus the version and
+    /// procedure checks).
+    const QUALITY_MAX: u32 =
+        LINT_POINTS_PER_CHECK * LINT_CHECK_COUNT + VERSION_POINTS + PROCEDURE_POINTS;
+
     /// Create a skill directory with given frontmatter content.
     fn make_skill(name: &str, frontmatter: &str) -> (tempfile::TempDir, std::path::PathBuf) {
         let parent = tempdir().unwrap();
@@ -390,7 +962,7 @@ mod tests {
         // A perfect skill should earn all structural points.
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
         assert_eq!(
@@ -401,7 +973,7 @@ mod tests {
     }
 
     #[test]
-    fn quality_max_is_5_times_8() {
+    fn quality_max_is_40() {
         assert_eq!(QUALITY_MAX, 40);
     }
 
@@ -411,7 +983,7 @@ mod tests {
     fn perfect_skill_scores_100() {
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports for analysis.\n  Use when working with document conversion tasks.\n---\n# Body\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports for analysis.\n  Use when working with document conversion tasks.\nmetadata:\n  version: 1.0.0\n---\n# Body\n",
         );
         let result = score(&dir);
         assert_eq!(
@@ -430,7 +1002,7 @@ mod tests {
 
     #[test]
     fn lint_issues_reduce_quality_score() {
-        // "pdf-processor" is not gerund, no trigger phrase → loses I002 + I003 = -16
+        // "pdf-processor" is not gerund, no trigger phrase → loses I002 + I003 = -12
         let (_parent, dir) = make_skill(
             "pdf-processor",
             "---\nname: pdf-processor\ndescription: Processes PDF files and generates detailed reports for analysis\n---\n",
@@ -506,7 +1078,7 @@ mod tests {
     fn perfect_skill_all_checks_pass() {
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
         assert!(
@@ -538,7 +1110,7 @@ mod tests {
     fn format_text_includes_score_line() {
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
         let text = format_text(&result);
@@ -563,7 +1135,7 @@ mod tests {
     fn score_result_serializes_to_json() {
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
         let json = serde_json::to_value(&result).unwrap();
@@ -577,7 +1149,7 @@ mod tests {
     fn json_omits_message_when_passed() {
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\n",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
         let json = serde_json::to_value(&result).unwrap();
@@ -593,19 +1165,478 @@ mod tests {
     // ── Scoring granularity ──────────────────────────────────────────
 
     #[test]
-    fn each_lint_failure_costs_8_points() {
+    fn each_lint_failure_costs_6_points() {
         // "processing-pdfs" with only I002 missing (no trigger phrase)
         let (_parent, dir) = make_skill(
             "processing-pdfs",
-            "---\nname: processing-pdfs\ndescription: Processes PDF files and generates detailed reports for analysis\n---\n",
+            "---\nname: processing-pdfs\ndescription: Processes PDF files and generates detailed reports for analysis\nmetadata:\n  version: 1.0.0\n---\n",
         );
         let result = score(&dir);
-        // Should lose exactly I002 (no trigger) → quality = 32
-        let expected_quality = QUALITY_MAX - LINT_POINTS_PER_CHECK; // 40 - 8 = 32
+        // Should lose exactly I002 (no trigger) → quality = 34
+        let expected_quality = QUALITY_MAX - LINT_POINTS_PER_CHECK; // 40 - 6 = 34
         assert_eq!(
             result.quality.score, expected_quality,
             "missing one lint check should cost {} points, got quality={}",
             LINT_POINTS_PER_CHECK, result.quality.score,
         );
     }
+
+    // ── Version check ────────────────────────────────────────────────
+
+    /// The version check of `content`, scored from memory.
+    fn version_check(content: &str) -> CheckResult {
+        let result = score_content(content, None);
+        result
+            .quality
+            .checks
+            .into_iter()
+            .find(|c| c.label == VERSION_LABEL)
+            .unwrap()
+    }
+
+    #[test]
+    fn semver_version_earns_full_points() {
+        let check = version_check(
+            "---\nname: processing-pdfs\ndescription: Processes PDFs\nmetadata:\n  version: 1.2.3\n---\n",
+        );
+        assert!(check.passed);
+        assert_eq!((check.points, check.max_points), (6, 6));
+        assert!(check.message.is_none());
+    }
+
+    #[test]
+    fn non_semver_version_earns_partial_points() {
+        let check = version_check(
+            "---\nname: processing-pdfs\ndescription: Processes PDFs\nmetadata:\n  version: 1.0\n---\n",
+        );
+        assert!(!check.passed);
+        assert_eq!(check.points, VERSION_PARTIAL_POINTS);
+        assert!(check.message.unwrap().contains("\"1.0\" is not semver"));
+    }
+
+    #[test]
+    fn absent_version_earns_no_points() {
+        let check = version_check("---\nname: processing-pdfs\ndescription: Processes PDFs\n---\n");
+        assert!(!check.passed);
+        assert_eq!(check.points, 0);
+        assert!(check.message.is_some());
+    }
+
+    #[test]
+    fn top_level_version_is_scored() {
+        let check = version_check(
+            "---\nname: processing-pdfs\ndescription: Processes PDFs\nversion: 2.0.0-rc.1\n---\n",
+        );
+        assert!(check.passed);
+    }
+
+    #[test]
+    fn partial_version_shows_in_markdown_and_diff() {
+        let (_old_parent, old) = make_skill(
+            "processing-pdfs",
+            &DIFF_PERFECT.replace("version: 1.0.0", "version: '1.0'"),
+        );
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let md = format_markdown(&score(&old));
+        assert!(
+            md.contains("| Quality | Version missing or not semver | 3/6 | PARTIAL |"),
+            "md: {md}"
+        );
+        let diff = score_diff(&old, &new);
+        assert_eq!(labels(&diff.gained), [(VERSION_LABEL, 3)]);
+    }
+
+    // ── Procedure check ──────────────────────────────────────────────
+
+    /// The procedure check of `content`, scored from memory.
+    fn procedure_check(content: &str) -> CheckResult {
+        let result = score_content(content, None);
+        result
+            .quality
+            .checks
+            .into_iter()
+            .find(|c| c.label == PROCEDURE_LABEL)
+            .unwrap()
+    }
+
+    #[test]
+    fn workflow_without_numbered_steps_loses_procedure_points() {
+        let check = procedure_check(
+            "---\nname: releasing-crates\ndescription: Guides the release workflow\n---\nRun the release.\n",
+        );
+        assert!(!check.passed);
+        assert_eq!((check.points, check.max_points), (0, PROCEDURE_POINTS));
+        assert!(check.message.is_some());
+    }
+
+    #[test]
+    fn workflow_with_numbered_steps_earns_procedure_points() {
+        let check = procedure_check(
+            "---\nname: releasing-crates\ndescription: Guides the release workflow\n---\n1. Bump the version.\n2. Publish.\n",
+        );
+        assert!(check.passed);
+        assert_eq!(check.points, PROCEDURE_POINTS);
+    }
+
+    #[test]
+    fn non_workflow_description_passes_procedure_check() {
+        let check = procedure_check(
+            "---\nname: processing-pdfs\ndescription: Processes PDFs\n---\nPlain prose.\n",
+        );
+        assert!(check.passed);
+    }
+
+    #[test]
+    fn score_result_carries_body_metrics() {
+        let result = score_content(
+            "---\nname: processing-pdfs\ndescription: Processes PDFs\n---\nOne. Two.\n",
+            None,
+        );
+        assert_eq!(result.body_metrics.sentences, 2);
+    }
+
+    // ── Multi-skill summary ──────────────────────────────────────────
+
+    /// Build a `SkillScore` with the given name and total (breakdown unused).
+    fn fake_score(name: &str, total: u32) -> SkillScore {
+        let empty = CategoryResult {
+            score: 0,
+            max: 0,
+            checks: Vec::new(),
+        };
+        SkillScore {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            result: ScoreResult {
+                total,
+                max: 100,
+                structural: empty.clone(),
+                quality: empty,
+                body_tokens: 0,
+                body_metrics: BodyMetrics::default(),
+                risk_multiplier: 1,
+                risk: assess_risk(total, 100, 0),
+            },
+        }
+    }
+
+    /// Like [`fake_score`], with a body of `body_tokens` estimated tokens.
+    fn fake_sized_score(name: &str, total: u32, body_tokens: usize) -> SkillScore {
+        let mut score = fake_score(name, total);
+        score.result.body_tokens = body_tokens;
+        score.result.risk_multiplier = risk_multiplier(body_tokens);
+        score.result.risk = assess_risk(total, 100, body_tokens);
+        score
+    }
+
+    #[test]
+    fn summarize_odd_count_distribution() {
+        let scores = vec![
+            fake_score("a", 100),
+            fake_score("b", 60),
+            fake_score("c", 84),
+        ];
+        let summary = summarize(&scores, 100);
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.min, 60);
+        assert_eq!(summary.max, 100);
+        assert!((summary.median - 84.0).abs() < f64::EPSILON);
+        assert!((summary.mean - 81.333).abs() < 0.01);
+    }
+
+    #[test]
+    fn summarize_even_count_median_is_midpoint() {
+        let scores = vec![
+            fake_score("a", 60),
+            fake_score("b", 70),
+            fake_score("c", 90),
+            fake_score("d", 100),
+        ];
+        let summary = summarize(&scores, 100);
+        assert!((summary.median - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn summarize_counts_below_gate() {
+        let scores = vec![
+            fake_score("a", 60),
+            fake_score("b", 80),
+            fake_score("c", 100),
+        ];
+        assert_eq!(summarize(&scores, 80).below_gate, 1);
+        assert_eq!(summarize(&scores, 100).below_gate, 2);
+        assert_eq!(summarize(&scores, 0).below_gate, 0);
+    }
+
+    #[test]
+    fn summarize_lowest_excludes_perfect_and_sorts_worst_first() {
+        let scores = vec![
+            fake_score("perfect", 100),
+            fake_score("zeta", 68),
+            fake_score("alpha", 68),
+            fake_score("worst", 40),
+        ];
+        let summary = summarize(&scores, 100);
+        let names: Vec<&str> = summary.lowest.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["worst", "alpha", "zeta"]);
+    }
+
+    #[test]
+    fn summarize_lowest_is_capped() {
+        let scores: Vec<SkillScore> = (0..10)
+            .map(|i| fake_score(&format!("s{i}"), 50 + i))
+            .collect();
+        let summary = summarize(&scores, 100);
+        assert_eq!(summary.lowest.len(), LOWEST_COUNT);
+        assert_eq!(summary.lowest[0].total, 50);
+    }
+
+    #[test]
+    fn summarize_empty_input() {
+        let summary = summarize(&[], 100);
+        assert_eq!(summary.count, 0);
+        assert_eq!(summary.below_gate, 0);
+        assert!(summary.lowest.is_empty());
+    }
+
+    #[test]
+    fn score_content_matches_score() {
+        let content = "---\nname: processing-pdfs\ndescription: Processes PDFs. Use when working with documents.\n---\nBody.\n";
+        let (_parent, dir) = make_skill("processing-pdfs", content);
+        let from_dir = score(&dir);
+        let from_content = score_content(content, Some(&dir));
+        assert_eq!(from_content.total, from_dir.total);
+        assert_eq!(from_content.max, from_dir.max);
+    }
+
+    #[test]
+    fn score_content_unparseable_fails_quality_checks() {
+        let result = score_content("no frontmatter", None);
+        assert_eq!(result.quality.score, 0);
+    }
+
+    #[test]
+    fn score_skills_uses_frontmatter_or_dir_name() {
+        let (_p1, good) = make_skill(
+            "processing-pdfs",
+            "---\nname: processing-pdfs\ndescription: Processes PDFs. Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
+        );
+        let (_p2, bad) = make_skill("broken-skill", "no frontmatter here");
+        let scores = score_skills(&[good.as_path(), bad.as_path()]);
+        assert_eq!(scores[0].name, "processing-pdfs");
+        assert_eq!(scores[1].name, "broken-skill");
+    }
+
+    // ── Risk ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn risk_multiplier_grows_with_body_size() {
+        assert_eq!(risk_multiplier(0), 1);
+        assert_eq!(risk_multiplier(1000), 1);
+        assert_eq!(risk_multiplier(1001), 2);
+        assert_eq!(risk_multiplier(3000), 2);
+        assert_eq!(risk_multiplier(3001), 3);
+    }
+
+    #[test]
+    fn same_score_is_riskier_in_a_larger_skill() {
+        assert_eq!(assess_risk(70, 100, 200), Risk::Medium);
+        assert_eq!(assess_risk(70, 100, 2000), Risk::High);
+        assert_eq!(assess_risk(90, 100, 200), Risk::Low);
+        assert_eq!(assess_risk(90, 100, 2000), Risk::Medium);
+        assert_eq!(assess_risk(100, 100, 10_000), Risk::Low);
+    }
+
+    #[test]
+    fn risk_does_not_change_total() {
+        let (_parent, dir) = make_skill(
+            "processing-pdfs",
+            "---\nname: processing-pdfs\ndescription: Processes PDF files and generates reports. Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n# PDF Processing\n",
+        );
+        let result = score(&dir);
+        assert_eq!(result.total, 100);
+        assert_eq!(result.risk, Risk::Low);
+        assert!(result.body_tokens > 0);
+        assert_eq!(result.risk_multiplier, 1);
+    }
+
+    #[test]
+    fn risk_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&Risk::High).unwrap(), "\"high\"");
+    }
+
+    #[test]
+    fn summarize_lists_high_risk_skills_worst_first() {
+        let scores = vec![
+            fake_sized_score("small", 50, 100),
+            fake_sized_score("large", 80, 5000),
+            fake_sized_score("larger", 70, 5000),
+            fake_sized_score("fine", 100, 5000),
+        ];
+        let summary = summarize(&scores, 80);
+        let names: Vec<&str> = summary.high_risk.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["small", "larger", "large"]);
+    }
+
+    #[test]
+    fn format_summary_text_marks_risky_skills() {
+        let scores = vec![
+            fake_sized_score("tidy", 95, 100),
+            fake_sized_score("bulky", 85, 5000),
+        ];
+        let summary = summarize(&scores, 80);
+        let text = format_summary_text(&scores, &summary);
+        assert!(text.contains(" 95/100  tidy\n"), "text: {text}");
+        assert!(
+            text.contains(" 85/100  bulky  (risk: high)"),
+            "text: {text}"
+        );
+        assert!(text.contains("High risk:\n   85  bulky"), "text: {text}");
+    }
+
+    #[test]
+    fn format_summary_text_lists_scores_and_lowest() {
+        let scores = vec![fake_score("good", 100), fake_score("weak", 52)];
+        let summary = summarize(&scores, 80);
+        let text = format_summary_text(&scores, &summary);
+        assert!(text.contains("100/100  good"), "text: {text}");
+        assert!(text.contains(" 52/100  weak"), "text: {text}");
+        assert!(text.contains("min 52, median 76.0, max 100, mean 76.0"));
+        assert!(text.contains("Below 80: 1"));
+        assert!(text.contains("Lowest:\n   52  weak"));
+    }
+
+    #[test]
+    fn format_markdown_renders_table_and_total() {
+        let (_parent, dir) = make_skill(
+            "processing-pdfs",
+            "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\n",
+        );
+        let result = score(&dir);
+        let md = format_markdown(&result);
+        assert!(md.starts_with("| Category | Check | Points | Status |\n|---"));
+        assert!(
+            md.contains("| Structural | Name format valid | 10/10 | PASS |"),
+            "md: {md}"
+        );
+        assert!(md.contains("| Quality |"), "md: {md}");
+        assert!(md.ends_with("**Total: 100/100**\n"), "md: {md}");
+    }
+
+    #[test]
+    fn format_markdown_shows_failed_checks() {
+        let (_parent, dir) = make_skill("helper", "---\nname: helper\ndescription: Helps\n---\n");
+        let md = format_markdown(&score(&dir));
+        assert!(
+            md.contains("| Quality | Generic name | 0/6 | FAIL |"),
+            "md: {md}"
+        );
+    }
+
+    #[test]
+    fn format_summary_markdown_has_section_per_skill() {
+        let scores = vec![fake_score("good", 100), fake_score("weak", 52)];
+        let summary = summarize(&scores, 80);
+        let md = format_summary_markdown(&scores, &summary);
+        assert!(md.contains("## good (100/100)\n\n| Category |"), "md: {md}");
+        assert!(md.contains("## weak (52/100)"), "md: {md}");
+        assert!(md.contains("## Summary\n\n- Skills: 2\n"));
+        assert!(md.contains("- Below 80: 1"));
+    }
+
+    #[test]
+    fn markdown_cell_escapes_pipes() {
+        assert_eq!(markdown_cell("a|b\nc"), "a\\|b c");
+    }
+
+    #[test]
+    fn skill_score_json_flattens_result() {
+        let json = serde_json::to_value(fake_score("a", 90)).unwrap();
+        assert_eq!(json["name"], "a");
+        assert_eq!(json["total"], 90);
+        assert!(json.get("result").is_none());
+    }
+
+    // ── Score diffs ──────────────────────────────────────────────────
+
+    const DIFF_PERFECT: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports for analysis.\n  Use when working with document conversion tasks.\nmetadata:\n  version: 1.0.0\n---\n# Body\n";
+    const DIFF_NO_TRIGGER: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports for analysis\n  of document conversion tasks.\nmetadata:\n  version: 1.0.0\n---\n# Body\n";
+    const DIFF_UNKNOWN_FIELD: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports for analysis.\n  Use when working with document conversion tasks.\nflavor: extra\nmetadata:\n  version: 1.0.0\n---\n# Body\n";
+
+    fn labels(items: &[CheckDelta]) -> Vec<(&str, i64)> {
+        items.iter().map(|i| (i.label.as_str(), i.points)).collect()
+    }
+
+    #[test]
+    fn score_diff_gained_only() {
+        let (_old_parent, old) = make_skill("processing-pdfs", DIFF_NO_TRIGGER);
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let diff = score_diff(&old, &new);
+        assert_eq!((diff.old_total, diff.new_total, diff.delta), (94, 100, 6));
+        assert_eq!(labels(&diff.gained), [("Trigger phrase present", 6)]);
+        assert!(diff.lost.is_empty());
+        assert_eq!(diff.unchanged.len(), 12);
+        assert_eq!(diff.note, None);
+    }
+
+    #[test]
+    fn score_diff_mixed() {
+        let (_old_parent, old) = make_skill("processing-pdfs", DIFF_NO_TRIGGER);
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_UNKNOWN_FIELD);
+        let diff = score_diff(&old, &new);
+        assert_eq!(diff.delta, -4);
+        assert_eq!(labels(&diff.gained), [("Trigger phrase present", 6)]);
+        assert_eq!(labels(&diff.lost), [("No unknown fields", -10)]);
+        assert_eq!(diff.lost[0].category, "structural");
+        assert!(!diff.lost[0].passed);
+    }
+
+    #[test]
+    fn score_diff_identical() {
+        let (_old_parent, old) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let diff = score_diff(&old, &new);
+        assert_eq!(diff.delta, 0);
+        assert!(diff.gained.is_empty() && diff.lost.is_empty());
+        assert_eq!(diff.unchanged.len(), 13);
+        assert!(diff.unchanged.iter().all(|i| i.points == 0));
+    }
+
+    #[test]
+    fn score_diff_unparseable_old_scores_zero() {
+        let (_old_parent, old) = make_skill("processing-pdfs", "no frontmatter here\n");
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let diff = score_diff(&old, &new);
+        assert_eq!((diff.old_total, diff.new_total, diff.delta), (0, 100, 100));
+        assert_eq!(diff.gained.len(), 13);
+        assert_eq!(diff.gained.iter().map(|i| i.points).sum::<i64>(), 100);
+        assert!(diff.note.as_deref().unwrap().contains("scored as 0"));
+    }
+
+    #[test]
+    fn format_diff_markdown_lists_changes() {
+        let (_old_parent, old) = make_skill("processing-pdfs", DIFF_NO_TRIGGER);
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_UNKNOWN_FIELD);
+        let md = format_diff_markdown(&score_diff(&old, &new));
+        assert_eq!(
+            md,
+            "**Score changed 94 → 90 (-4)**\n\nGained:\n- Trigger phrase present (+6)\n\
+             \nLost:\n- No unknown fields (-10)\n\nUnchanged: 11 check(s)\n"
+        );
+    }
+
+    #[test]
+    fn format_diff_markdown_unchanged_and_note() {
+        let (_old_parent, old) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let (_new_parent, new) = make_skill("processing-pdfs", DIFF_PERFECT);
+        let md = format_diff_markdown(&score_diff(&old, &new));
+        assert!(md.starts_with("**Score unchanged: 100/100**\n"), "{md}");
+
+        let missing = tempdir().unwrap();
+        let md = format_diff_markdown(&score_diff(&missing.path().join("gone"), &new));
+        assert!(
+            md.contains("\n> Note: old revision could not be parsed"),
+            "{md}"
+        );
+    }
 }
@@ -4,22 +4,31 @@
 //! structure: file references in the markdown body, script permissions,
 //! reference depth, and nesting depth.
 //!
-//! Structure diagnostics use codes S001–S005. Most are `Severity::Warning`
+//! Structure diagnostics use codes S001–S014. Most are `Severity::Warning`
 //! unless the issue would cause a broken skill at runtime. S005 (symlink
-//! detected) uses `Severity::Info`.
+//! detected), S010 (inconsistent reference paths), and S014 (unreferenced
+//! example) use `Severity::Info`.
+//!
+//! Checks on the `examples/` subdirectory (S011–S014) are opt-in through
+//! [`StructureOptions::check_examples`].
+//!
+//! [`validate_index`] checks a collection root: skills on disk against the
+//! entries listed in a top-level `INDEX.md` (S007–S009).
 
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 use regex::Regex;
 
-use crate::diagnostics::{Diagnostic, Severity, S001, S003, S004, S005, S006};
-use crate::fs_util::{is_regular_dir, is_symlink};
+use crate::diagnostics::{
+    Diagnostic, Severity, S001, S003, S004, S005, S006, S007, S008, S009, S010, S011, S012, S013,
+    S014,
+};
+use crate::fs_util::{is_regular_dir, is_regular_file, is_symlink};
 
 #[cfg(unix)]
 use crate::diagnostics::S002;
-#[cfg(unix)]
-use crate::fs_util::is_regular_file;
 
 /// Maximum allowed nesting depth for files referenced from SKILL.md.
 const MAX_REFERENCE_DEPTH: usize = 1;
@@ -31,10 +40,24 @@ const MAX_NESTING_DEPTH: usize = 2;
 ///
 /// Captures the path in group 1. Excludes URLs (http:// or https://) and
 /// anchors (#fragment).
-static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+pub(crate) static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"!?\[(?:[^\]]*)\]\((?P<path>[^)]+)\)").expect("link regex must compile")
 });
 
+/// Name of the skill subdirectory holding example files.
+const EXAMPLES_DIR: &str = "examples";
+
+/// Options controlling which structure checks run.
+#[derive(Debug, Clone, Default)]
+pub struct StructureOptions {
+    /// Check the files under `examples/` (S011–S014).
+    ///
+    /// Off by default: `.json` and `.yml`/`.yaml` files must parse, `.sh`
+    /// files must start with a shebang, and every example must be
+    /// referenced from SKILL.md or a document SKILL.md links to.
+    pub check_examples: bool,
+}
+
 /// Validate the directory structure of a skill package.
 ///
 /// Checks:
@@ -44,6 +67,7 @@ static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
 /// - S004: Excessive directory nesting depth
 /// - S005: Symlink detected in skill directory (Info)
 /// - S006: Path traversal in reference link
+/// - S010: Same file referenced via differently-spelled paths (Info)
 ///
 /// # Arguments
 ///
@@ -54,12 +78,26 @@ static LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
 /// A list of diagnostics. Empty means the structure is valid.
 #[must_use]
 pub fn validate_structure(dir: &Path) -> Vec<Diagnostic> {
+    validate_structure_with(dir, &StructureOptions::default())
+}
+
+/// Validate the directory structure of a skill package with explicit
+/// [`StructureOptions`].
+///
+/// Runs the [`validate_structure`] checks, plus, when
+/// [`check_examples`](StructureOptions::check_examples) is set:
+/// - S011: `.json` example does not parse as JSON
+/// - S012: `.yml`/`.yaml` example does not parse as YAML
+/// - S013: `.sh` example has no shebang line
+/// - S014: Example file not referenced from SKILL.md or a linked document (Info)
+#[must_use]
+pub fn validate_structure_with(dir: &Path, options: &StructureOptions) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
     // Read the SKILL.md body for reference checking.
     let body = crate::parser::read_body(dir).unwrap_or_default();
 
-    // S001 + S003 + S006: Check file references in the body.
+    // S001 + S003 + S006 + S010: Check file references in the body.
     diags.extend(check_references(dir, &body));
 
     // S002: Check script permissions.
@@ -71,9 +109,138 @@ pub fn validate_structure(dir: &Path) -> Vec<Diagnostic> {
     // S005: Check for symlinks.
     diags.extend(check_symlinks(dir));
 
+    // S011–S014: Check example files.
+    if options.check_examples {
+        diags.extend(check_examples(dir, &body));
+    }
+
+    diags
+}
+
+/// File name of the collection index checked by [`validate_index`].
+const INDEX_FILE: &str = "INDEX.md";
+
+/// Validate a collection root's `INDEX.md` against the skills on disk.
+///
+/// Skills are discovered recursively under `root`. Index entries are the
+/// markdown links in `INDEX.md` that point at a skill: a `SKILL.md` file,
+/// a path ending in `/`, or an extensionless path. URLs, anchors, and links
+/// to other files (e.g., `CONTRIBUTING.md`) are ignored.
+///
+/// Checks:
+/// - S007: Skill present on disk but missing from the index
+/// - S008: Index entry points to a nonexistent skill
+/// - S009: `INDEX.md` not found at the root
+///
+/// # Arguments
+///
+/// * `root` - Path to the collection root containing `INDEX.md`
+///
+/// # Returns
+///
+/// A list of diagnostics. Empty means the index and the skills agree.
+#[must_use]
+pub fn validate_index(root: &Path) -> Vec<Diagnostic> {
+    let index_path = root.join(INDEX_FILE);
+    let content = match crate::parser::read_file_checked(&index_path) {
+        Ok(c) => c,
+        Err(e) => {
+            let message = if index_path.exists() {
+                format!("cannot read {INDEX_FILE}: {e}")
+            } else {
+                format!("{INDEX_FILE} not found in {}", root.display())
+            };
+            return vec![
+                Diagnostic::new(Severity::Warning, S009, message).with_suggestion(format!(
+                    "Create {INDEX_FILE} with a link to each skill's SKILL.md"
+                )),
+            ];
+        }
+    };
+
+    let mut diags = Vec::new();
+    let mut indexed: HashSet<PathBuf> = HashSet::new();
+
+    for cap in LINK_RE.captures_iter(&content) {
+        let Some(target) = index_link_target(cap["path"].trim()) else {
+            continue;
+        };
+        let skill_dir = root.join(&target);
+        match skill_dir.canonicalize() {
+            Ok(canonical) if crate::parser::find_skill_md(&skill_dir).is_some() => {
+                indexed.insert(canonical);
+            }
+            _ => {
+                diags.push(
+                    Diagnostic::new(
+                        Severity::Warning,
+                        S008,
+                        format!("{INDEX_FILE} entry points to nonexistent skill: '{target}'"),
+                    )
+                    .with_suggestion("Remove the entry or restore the skill directory"),
+                );
+            }
+        }
+    }
+
+    let root_canonical = root.canonicalize().ok();
+    for dir in crate::validator::discover_skills(root) {
+        let Ok(canonical) = dir.canonicalize() else {
+            continue;
+        };
+        // A SKILL.md at the root itself is not an index entry.
+        if Some(&canonical) == root_canonical.as_ref() || indexed.contains(&canonical) {
+            continue;
+        }
+        let rel = dir.strip_prefix(root).unwrap_or(&dir);
+        diags.push(
+            Diagnostic::new(
+                Severity::Warning,
+                S007,
+                format!("skill missing from {INDEX_FILE}: '{}'", rel.display()),
+            )
+            .with_suggestion(format!(
+                "Add a link to '{}/SKILL.md' in {INDEX_FILE}",
+                rel.display()
+            )),
+        );
+    }
+
     diags
 }
 
+/// Extract the skill directory referenced by an `INDEX.md` link target.
+///
+/// Returns `None` for URLs, anchors, and links to non-skill files.
+fn index_link_target(link: &str) -> Option<String> {
+    if link.starts_with("http://") || link.starts_with("https://") || link.starts_with('#') {
+        return None;
+    }
+    let clean = link.split('#').next().unwrap_or(link).trim();
+    if clean.is_empty() {
+        return None;
+    }
+
+    let path = Path::new(clean);
+    let is_skill_file = matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("SKILL.md" | "skill.md")
+    );
+    let dir = if is_skill_file {
+        path.parent().unwrap_or(Path::new(""))
+    } else if clean.ends_with('/') || path.extension().is_none() {
+        path
+    } else {
+        return None;
+    };
+
+    let target = dir.to_string_lossy().trim_end_matches('/').to_string();
+    if target.is_empty() || target == "." {
+        return None;
+    }
+    Some(target)
+}
+
 /// Returns `true` if the given path string contains `..` (parent directory) components.
 fn contains_path_traversal(path: &str) -> bool {
     Path::new(path)
@@ -81,17 +248,42 @@ fn contains_path_traversal(path: &str) -> bool {
         .any(|c| c == std::path::Component::ParentDir)
 }
 
-/// S001 + S003 + S006: Check file references in the markdown body.
+/// Normalize a reference path for comparison.
+///
+/// Drops `.` components and the empty components left by repeated or
+/// trailing slashes, so `./guide.md`, `guide.md`, and `docs//a.md` compare
+/// by the file they name. A leading `/` is kept. `..` is left alone; such
+/// paths are rejected as traversal (S006) before they are compared.
+fn normalize_reference(path: &str) -> String {
+    let joined = path
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect::<Vec<_>>()
+        .join("/");
+    if path.starts_with('/') {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+/// S001 + S003 + S006 + S010: Check file references in the markdown body.
 ///
 /// Extracts `[text](path)` and `![alt](path)` patterns, skipping URLs
 /// and anchors. Reports S006 if the path contains `..` traversal components,
 /// S003 if the reference path exceeds `MAX_REFERENCE_DEPTH` levels, and
-/// S001 if the referenced file doesn't exist.
+/// S001 if the referenced file doesn't exist. Paths are normalized first,
+/// so each file is checked once however many times it is linked, and S010
+/// notes a file linked under more than one spelling.
 fn check_references(dir: &Path, body: &str) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
+    // Normalized path -> distinct spellings, in order of first appearance.
+    let mut spellings: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
 
     for cap in LINK_RE.captures_iter(body) {
-        let path_str = &cap["path"];
+        // Trim stray `\r` left by CRLF line endings.
+        let path_str = cap.name("path").map_or("", |m| m.as_str()).trim();
 
         // Skip URLs and anchors.
         if path_str.starts_with("http://")
@@ -120,8 +312,19 @@ fn check_references(dir: &Path, body: &str) -> Vec<Diagnostic> {
             continue;
         }
 
+        // Check each file once, recording how it was spelled.
+        let normalized = normalize_reference(clean_path);
+        if let Some(&i) = seen.get(&normalized) {
+            if !spellings[i].1.contains(&clean_path) {
+                spellings[i].1.push(clean_path);
+            }
+            continue;
+        }
+        seen.insert(normalized.clone(), spellings.len());
+        spellings.push((normalized.clone(), vec![clean_path]));
+
         // Check reference depth (S003).
-        let depth = clean_path.matches('/').count();
+        let depth = normalized.trim_start_matches('/').matches('/').count();
         if depth > MAX_REFERENCE_DEPTH {
             diags.push(
                 Diagnostic::new(
@@ -154,6 +357,26 @@ fn check_references(dir: &Path, body: &str) -> Vec<Diagnostic> {
         }
     }
 
+    // Note files linked under more than one spelling (S010).
+    for (normalized, paths) in &spellings {
+        if paths.len() > 1 {
+            let listed = paths
+                .iter()
+                .map(|p| format!("'{p}'"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            diags.push(
+                Diagnostic::new(
+                    Severity::Info,
+                    S010,
+                    format!("'{normalized}' is referenced via different paths: {listed}"),
+                )
+                .with_field("body")
+                .with_suggestion(format!("Use one spelling for every link: '{normalized}'")),
+            );
+        }
+    }
+
     diags
 }
 
@@ -322,6 +545,191 @@ fn check_symlinks_recursive(
     }
 }
 
+/// S011–S014: Check the files under `examples/`.
+///
+/// Each example is checked by extension: `.json` must parse as JSON,
+/// `.yml`/`.yaml` as YAML (multi-document streams allowed), and `.sh` must
+/// start with `#!`. Every example, whatever its type, must also be
+/// referenced from the SKILL.md body or from a document the body links to
+/// (see [`reference_texts`]). Symlinks are skipped; S005 reports them.
+fn check_examples(dir: &Path, body: &str) -> Vec<Diagnostic> {
+    let examples = dir.join(EXAMPLES_DIR);
+    if !is_regular_dir(&examples) {
+        return vec![];
+    }
+    let mut files = Vec::new();
+    collect_example_files(&examples, &mut files);
+    files.sort();
+
+    let texts = reference_texts(dir, body);
+    let mut diags = Vec::new();
+    for file in &files {
+        let rel = file
+            .strip_prefix(dir)
+            .unwrap_or(file)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let extension = file
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => {
+                if let Some(error) = example_parse_error(file, |text| {
+                    serde_json::from_str::<serde_json::Value>(text)
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                }) {
+                    diags.push(
+                        Diagnostic::new(
+                            Severity::Warning,
+                            S011,
+                            format!("example '{rel}' is not valid JSON: {error}"),
+                        )
+                        .with_suggestion("Fix the JSON syntax or remove the stale example"),
+                    );
+                }
+            }
+            Some("yml" | "yaml") => {
+                if let Some(error) = example_parse_error(file, parse_yaml_documents) {
+                    diags.push(
+                        Diagnostic::new(
+                            Severity::Warning,
+                            S012,
+                            format!("example '{rel}' is not valid YAML: {error}"),
+                        )
+                        .with_suggestion("Fix the YAML syntax or remove the stale example"),
+                    );
+                }
+            }
+            Some("sh") => {
+                let has_shebang = std::fs::read(file).is_ok_and(|bytes| bytes.starts_with(b"#!"));
+                if !has_shebang {
+                    diags.push(
+                        Diagnostic::new(
+                            Severity::Warning,
+                            S013,
+                            format!("example script '{rel}' has no shebang line"),
+                        )
+                        .with_suggestion("Start the script with a line like '#!/usr/bin/env bash'"),
+                    );
+                }
+            }
+            _ => {}
+        }
+        if !texts.iter().any(|text| text.contains(&rel)) {
+            diags.push(
+                Diagnostic::new(
+                    Severity::Info,
+                    S014,
+                    format!("example '{rel}' is not referenced from SKILL.md"),
+                )
+                .with_suggestion(format!(
+                    "Link '{rel}' from SKILL.md or a referenced document, or remove it"
+                )),
+            );
+        }
+    }
+    diags
+}
+
+/// Recursively collect the regular files under `current`.
+fn collect_example_files(current: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(current) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_regular_file(&path) {
+            files.push(path);
+        } else if is_regular_dir(&path) {
+            collect_example_files(&path, files);
+        }
+    }
+}
+
+/// Read an example and run `parse` on it, returning the first line of any
+/// error (or of the read failure).
+fn example_parse_error(
+    path: &Path,
+    parse: impl Fn(&str) -> std::result::Result<(), String>,
+) -> Option<String> {
+    let error = match std::fs::read_to_string(path) {
+        Ok(text) => parse(&text).err()?,
+        Err(e) => e.to_string(),
+    };
+    Some(error.lines().next().unwrap_or_default().to_string())
+}
+
+/// Parse every document in a YAML stream.
+fn parse_yaml_documents(text: &str) -> std::result::Result<(), String> {
+    use serde::Deserialize;
+
+    for document in serde_yaml_ng::Deserializer::from_str(text) {
+        serde_yaml_ng::Value::deserialize(document).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// The texts an example may be referenced from: the SKILL.md body and each
+/// markdown document the body links to (one level, matching
+/// `MAX_REFERENCE_DEPTH`).
+///
+/// An example counts as referenced when one of these texts mentions its
+/// path relative to the skill directory (e.g., `examples/request.json`),
+/// whether in a link, inline code, or prose. Links from a linked document
+/// are resolved against that document's directory first, so
+/// `[request](request.json)` inside `examples/README.md` counts too.
+fn reference_texts(dir: &Path, body: &str) -> Vec<String> {
+    let mut texts = vec![body.to_string()];
+    for cap in LINK_RE.captures_iter(body) {
+        let link = cap["path"].trim();
+        let clean = link.split('#').next().unwrap_or(link);
+        if link.starts_with("http://")
+            || link.starts_with("https://")
+            || !clean.ends_with(".md")
+            || contains_path_traversal(clean)
+        {
+            continue;
+        }
+        let doc = normalize_reference(clean);
+        let Ok(text) = crate::parser::read_file_checked(&dir.join(&doc)) else {
+            continue;
+        };
+        // Resolve the document's own links to skill-relative paths.
+        let base = doc.rsplit_once('/').map_or("", |(parent, _)| parent);
+        let resolved: Vec<String> = LINK_RE
+            .captures_iter(&text)
+            .filter_map(|c| resolve_link(base, c["path"].trim()))
+            .collect();
+        texts.push(text);
+        texts.push(resolved.join("\n"));
+    }
+    texts
+}
+
+/// Resolve `link`, found in a document under `base`, to a path relative to
+/// the skill directory. Returns `None` for URLs and links that escape it.
+fn resolve_link(base: &str, link: &str) -> Option<String> {
+    if link.starts_with("http://") || link.starts_with("https://") || link.starts_with('#') {
+        return None;
+    }
+    let clean = link.split('#').next().unwrap_or(link);
+    let mut parts: Vec<&str> = base.split('/').filter(|c| !c.is_empty()).collect();
+    for component in clean.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            other => parts.push(other),
+        }
+    }
+    Some(parts.join("/"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +813,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn s001_crlf_reference_resolves() {
+        let (_parent, dir) = make_skill(
+            "my-skill",
+            "---\r\nname: my-skill\r\ndescription: desc\r\n---\r\n\r\nSee [guide](guide.md)\r\n",
+        );
+        fs::write(dir.join("guide.md"), "# Guide").unwrap();
+        let diags = validate_structure(&dir);
+        assert!(
+            !diags.iter().any(|d| d.code == S001),
+            "CRLF body should not break references, got: {diags:?}",
+        );
+    }
+
+    #[test]
+    fn s001_reference_path_trims_stray_carriage_return() {
+        let (_parent, dir) =
+            make_skill("my-skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        fs::write(dir.join("guide.md"), "# Guide").unwrap();
+        let diags = check_references(&dir, "See [guide](guide.md\r\n) for details.\r\n");
+        assert!(diags.is_empty(), "expected no diagnostics, got: {diags:?}");
+    }
+
     // ── S002: Script missing execute permission ──────────────────────
 
     #[cfg(unix)]
@@ -741,4 +1172,292 @@ mod tests {
             "traversal path should not also trigger S001, got: {diags:?}",
         );
     }
+
+    // ── S010: Inconsistent reference paths ───────────────────────────
+
+    #[test]
+    fn normalize_reference_drops_dot_and_empty_components() {
+        assert_eq!(normalize_reference("./guide.md"), "guide.md");
+        assert_eq!(normalize_reference("docs//./a.md"), "docs/a.md");
+        assert_eq!(normalize_reference("guide.md"), "guide.md");
+        assert_eq!(normalize_reference("/abs/./x.md"), "/abs/x.md");
+    }
+
+    #[test]
+    fn s010_same_file_with_different_spellings() {
+        let (_parent, dir) =
+            make_skill("my-skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        fs::write(dir.join("guide.md"), "# Guide").unwrap();
+        let diags = check_references(
+            &dir,
+            "See [guide](guide.md), [again](./guide.md), and [once more](guide.md#setup).\n",
+        );
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, S010);
+        assert_eq!(diags[0].severity, Severity::Info);
+        assert_eq!(
+            diags[0].message,
+            "'guide.md' is referenced via different paths: 'guide.md', './guide.md'"
+        );
+        assert!(diags[0]
+            .suggestion
+            .as_deref()
+            .unwrap()
+            .contains("'guide.md'"));
+    }
+
+    #[test]
+    fn s010_missing_file_reported_once_across_spellings() {
+        let (_parent, dir) =
+            make_skill("my-skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        let diags = check_references(&dir, "[a](guide.md) [b](./guide.md) [c](guide.md)\n");
+        assert_eq!(diags.iter().filter(|d| d.code == S001).count(), 1);
+        assert_eq!(diags.iter().filter(|d| d.code == S010).count(), 1);
+    }
+
+    #[test]
+    fn s010_not_reported_for_consistent_paths() {
+        let (_parent, dir) =
+            make_skill("my-skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        fs::write(dir.join("guide.md"), "# Guide").unwrap();
+        let diags = check_references(&dir, "[a](guide.md) and [b](guide.md)\n");
+        assert!(diags.is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn s003_dot_prefix_does_not_add_depth() {
+        let (_parent, dir) =
+            make_skill("my-skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        fs::create_dir(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/a.md"), "# A").unwrap();
+        let diags = check_references(&dir, "[a](./docs/a.md)\n");
+        assert!(diags.is_empty(), "{diags:?}");
+    }
+
+    // ── S007–S009: Collection index ──────────────────────────────────
+
+    /// Create a collection root with the given skills and optional INDEX.md.
+    fn make_collection(skills: &[&str], index: Option<&str>) -> tempfile::TempDir {
+        let root = tempdir().unwrap();
+        for name in skills {
+            let dir = root.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Does things\n---\nBody.\n"),
+            )
+            .unwrap();
+        }
+        if let Some(content) = index {
+            fs::write(root.path().join("INDEX.md"), content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn index_in_sync_has_no_diagnostics() {
+        let root = make_collection(
+            &["alpha", "beta"],
+            Some("# Skills\n\n- [Alpha](alpha/SKILL.md)\n- [Beta](beta/)\n"),
+        );
+        let diags = validate_index(root.path());
+        assert!(diags.is_empty(), "expected no diagnostics, got {diags:?}");
+    }
+
+    #[test]
+    fn s007_skill_missing_from_index() {
+        let root = make_collection(
+            &["alpha", "beta"],
+            Some("# Skills\n\n- [Alpha](alpha/SKILL.md)\n"),
+        );
+        let diags = validate_index(root.path());
+        assert_eq!(diags.len(), 1, "got {diags:?}");
+        assert_eq!(diags[0].code, S007);
+        assert!(diags[0].is_warning());
+        assert!(diags[0].message.contains("beta"));
+    }
+
+    #[test]
+    fn s008_index_entry_for_nonexistent_skill() {
+        let root = make_collection(
+            &["alpha"],
+            Some("- [Alpha](alpha/SKILL.md)\n- [Gone](gone/SKILL.md)\n"),
+        );
+        let diags = validate_index(root.path());
+        assert_eq!(diags.len(), 1, "got {diags:?}");
+        assert_eq!(diags[0].code, S008);
+        assert!(diags[0].message.contains("gone"));
+    }
+
+    #[test]
+    fn s009_missing_index_file() {
+        let root = make_collection(&["alpha"], None);
+        let diags = validate_index(root.path());
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, S009);
+    }
+
+    #[test]
+    fn index_ignores_urls_anchors_and_other_files() {
+        let root = make_collection(
+            &["alpha"],
+            Some(
+                "- [Alpha](alpha)\n- [Site](https://example.com)\n- [Top](#top)\n- [Guide](CONTRIBUTING.md)\n",
+            ),
+        );
+        let diags = validate_index(root.path());
+        assert!(diags.is_empty(), "expected no diagnostics, got {diags:?}");
+    }
+
+    #[test]
+    fn index_finds_nested_skills() {
+        let root = make_collection(&["group/alpha"], Some("- [Alpha](group/alpha/SKILL.md)\n"));
+        let diags = validate_index(root.path());
+        assert!(diags.is_empty(), "expected no diagnostics, got {diags:?}");
+    }
+
+    // ── S011–S014: Examples directory ────────────────────────────────
+
+    const EXAMPLES_OPTS: StructureOptions = StructureOptions {
+        check_examples: true,
+    };
+
+    /// Create a skill whose body references each of `files` under
+    /// `examples/`, and write the files with the given content.
+    fn make_skill_with_examples(files: &[(&str, &str)]) -> (tempfile::TempDir, PathBuf) {
+        let links: String = files
+            .iter()
+            .map(|(name, _)| format!("- [{name}](examples/{name})\n"))
+            .collect();
+        let (parent, dir) = make_skill(
+            "my-skill",
+            &format!("---\nname: my-skill\ndescription: desc\n---\n\n{links}"),
+        );
+        fs::create_dir(dir.join("examples")).unwrap();
+        for (name, content) in files {
+            fs::write(dir.join("examples").join(name), content).unwrap();
+        }
+        (parent, dir)
+    }
+
+    fn example_codes(diags: &[Diagnostic]) -> Vec<crate::diagnostics::DiagnosticCode> {
+        diags
+            .iter()
+            .map(|d| d.code)
+            .filter(|c| [S011, S012, S013, S014].contains(c))
+            .collect()
+    }
+
+    #[test]
+    fn examples_valid_files_are_silent() {
+        let (_parent, dir) = make_skill_with_examples(&[
+            ("request.json", "{\"query\": \"hello\"}\n"),
+            ("config.yml", "name: demo\nsteps:\n  - run\n"),
+            ("stream.yaml", "a: 1\n---\nb: 2\n"),
+            ("run.sh", "#!/usr/bin/env bash\necho hi\n"),
+        ]);
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert!(example_codes(&diags).is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn examples_not_checked_by_default() {
+        let (_parent, dir) = make_skill_with_examples(&[("bad.json", "{oops")]);
+        let diags = validate_structure(&dir);
+        assert!(example_codes(&diags).is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn s011_invalid_json_example() {
+        let (_parent, dir) = make_skill_with_examples(&[("bad.json", "{\"a\": 1,}\n")]);
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert_eq!(example_codes(&diags), vec![S011], "{diags:?}");
+        let d = diags.iter().find(|d| d.code == S011).unwrap();
+        assert!(d.is_warning());
+        assert!(d
+            .message
+            .starts_with("example 'examples/bad.json' is not valid JSON: "));
+        assert!(d.message.contains("line 1"), "{}", d.message);
+    }
+
+    #[test]
+    fn s012_invalid_yaml_example() {
+        let (_parent, dir) =
+            make_skill_with_examples(&[("bad.yaml", "key: [unclosed\n"), ("ok.yml", "a: 1\n")]);
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert_eq!(example_codes(&diags), vec![S012], "{diags:?}");
+        let d = diags.iter().find(|d| d.code == S012).unwrap();
+        assert!(d.message.contains("'examples/bad.yaml'"));
+        assert!(!d.message.contains('\n'));
+    }
+
+    #[test]
+    fn s013_script_without_shebang() {
+        let (_parent, dir) = make_skill_with_examples(&[
+            ("no-shebang.sh", "echo hi\n"),
+            ("ok.sh", "#!/bin/sh\necho hi\n"),
+        ]);
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert_eq!(example_codes(&diags), vec![S013], "{diags:?}");
+        assert!(diags
+            .iter()
+            .any(|d| d.message == "example script 'examples/no-shebang.sh' has no shebang line"));
+    }
+
+    #[test]
+    fn s014_unreferenced_example() {
+        let (_parent, dir) = make_skill_with_examples(&[("used.json", "{}")]);
+        fs::write(dir.join("examples/orphan.txt"), "left over").unwrap();
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert_eq!(example_codes(&diags), vec![S014], "{diags:?}");
+        let d = diags.iter().find(|d| d.code == S014).unwrap();
+        assert_eq!(d.severity, Severity::Info);
+        assert!(d.message.contains("'examples/orphan.txt'"));
+    }
+
+    #[test]
+    fn s014_reference_from_linked_document_counts() {
+        let (_parent, dir) = make_skill(
+            "my-skill",
+            "---\nname: my-skill\ndescription: desc\n---\n\nSee [usage](docs/usage.md) and [examples](examples/README.md).\n",
+        );
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::write(
+            dir.join("docs/usage.md"),
+            "Send [a request](../examples/request.json).\n",
+        )
+        .unwrap();
+        fs::write(dir.join("examples/README.md"), "Run [this](run.sh).\n").unwrap();
+        fs::write(dir.join("examples/request.json"), "{}").unwrap();
+        fs::write(dir.join("examples/run.sh"), "#!/bin/sh\n").unwrap();
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert!(example_codes(&diags).is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn s014_mention_in_inline_code_counts() {
+        let (_parent, dir) = make_skill(
+            "my-skill",
+            "---\nname: my-skill\ndescription: desc\n---\n\nRun `bash examples/run.sh`.\n",
+        );
+        fs::create_dir(dir.join("examples")).unwrap();
+        fs::write(dir.join("examples/run.sh"), "#!/bin/sh\n").unwrap();
+        let diags = validate_structure_with(&dir, &EXAMPLES_OPTS);
+        assert!(example_codes(&diags).is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn resolve_link_joins_and_normalizes() {
+        assert_eq!(
+            resolve_link("docs", "../examples/a.json").as_deref(),
+            Some("examples/a.json")
+        );
+        assert_eq!(
+            resolve_link("examples", "./run.sh#top").as_deref(),
+            Some("examples/run.sh")
+        );
+        assert_eq!(resolve_link("", "../outside.md"), None);
+        assert_eq!(resolve_link("docs", "https://example.com"), None);
+    }
 }
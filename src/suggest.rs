@@ -0,0 +1,447 @@
+//! Description improvement suggestions from probe queries.
+//!
+//! After probing a skill with several phrasings, the queries that fail to
+//! match strongly point at words the description is missing. A
+//! [`SuggestionReport`] ranks those words by how many weak or missed
+//! queries use them, and drafts a revised description that splices the top
+//! terms into the trigger clause.
+//!
+//! The draft is deterministic: the same description and queries always give
+//! the same report. [`llm_revise_description`] asks an LLM for a draft
+//! instead; its result is marked [`DraftSource::Llm`] and must keep the
+//! original trigger clauses.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::builder::LlmProvider;
+use crate::errors::{AigentError, Result};
+use crate::models::SkillProperties;
+use crate::tester::{compute_query_match_with, expand_synonyms, tokenize_with, QueryMatch};
+use crate::triggers::trigger_clauses;
+
+/// Maximum description length allowed by the specification, in characters.
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Most missing terms spliced into a draft description.
+pub const MAX_DRAFT_TERMS: usize = 5;
+
+/// Function words never suggested, on top of the probe's own stopwords.
+///
+/// They carry no topic, so adding them to a description would not help it
+/// match anything.
+const FUNCTION_WORDS: &[&str] = &[
+    "about", "all", "as", "at", "be", "by", "can", "could", "do", "does", "from", "help", "how",
+    "i", "into", "me", "my", "need", "please", "some", "what", "want", "you", "your",
+];
+
+/// A query term the description does not cover.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TermSuggestion {
+    /// The term as written in the queries (lowercased; the most common
+    /// spelling when queries inflect it differently).
+    pub term: String,
+    /// Number of weak or missed queries that use the term.
+    pub missed: usize,
+}
+
+/// Where a revised description draft came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DraftSource {
+    /// Missing terms spliced into the trigger clause.
+    Deterministic,
+    /// Written by an LLM provider; review before use.
+    Llm,
+}
+
+/// A revised description proposed by a [`SuggestionReport`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DescriptionDraft {
+    /// The revised description.
+    pub text: String,
+    /// How the draft was produced.
+    pub source: DraftSource,
+    /// Number of queries that match the draft strongly.
+    pub matched: usize,
+}
+
+/// Analysis of which query terms a description misses.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SuggestionReport {
+    /// Skill name.
+    pub name: String,
+    /// The current description.
+    pub description: String,
+    /// Number of queries analyzed.
+    pub queries: usize,
+    /// Number of queries that match the current description strongly.
+    pub matched: usize,
+    /// Missing terms, most often missed first (ties by term).
+    pub terms: Vec<TermSuggestion>,
+    /// Revised description, or `None` if no term is missing or none fits
+    /// within the 1024-character limit.
+    pub draft: Option<DescriptionDraft>,
+}
+
+/// Find the query terms a skill's description misses and draft a fix.
+///
+/// Queries that match strongly are taken as covered. Every other query
+/// contributes its terms that appear neither in the description (after
+/// stemming and synonym expansion, as in `probe`) nor in the name. The
+/// draft splices up to [`MAX_DRAFT_TERMS`] of the top terms into the last
+/// trigger clause — or adds a "Use when…" sentence if there is none —
+/// leaving the existing trigger text intact, and drops terms as needed to
+/// stay within 1024 characters.
+#[must_use]
+pub fn suggest_description_improvements(
+    props: &SkillProperties,
+    queries: &[&str],
+) -> SuggestionReport {
+    let name = &props.name;
+    let description = &props.description;
+    let covered: HashSet<String> = tokenize_with(description, true).into_iter().collect();
+    let name_lower = name.to_lowercase();
+
+    // Stem → (spellings with counts, weak or missed queries using it).
+    let mut missing: BTreeMap<String, (HashMap<String, usize>, usize)> = BTreeMap::new();
+    for query in queries {
+        if is_strong(query, name, description) {
+            continue;
+        }
+        let mut seen: HashSet<String> = HashSet::new();
+        for word in query.split_whitespace() {
+            let (Some(spelling), Some(stem)) = (
+                tokenize_with(word, false).pop(),
+                tokenize_with(word, true).pop(),
+            ) else {
+                continue;
+            };
+            if FUNCTION_WORDS.contains(&spelling.as_str()) {
+                continue;
+            }
+            let synonyms = expand_synonyms(std::slice::from_ref(&stem));
+            if synonyms.iter().any(|s| covered.contains(s)) || name_lower.contains(&stem) {
+                continue;
+            }
+            let entry = missing.entry(stem.clone()).or_default();
+            *entry.0.entry(spelling).or_default() += 1;
+            if seen.insert(stem) {
+                entry.1 += 1;
+            }
+        }
+    }
+
+    let mut terms: Vec<TermSuggestion> = missing
+        .into_values()
+        .map(|(spellings, missed)| TermSuggestion {
+            term: most_common(spellings),
+            missed,
+        })
+        .collect();
+    terms.sort_by(|a, b| b.missed.cmp(&a.missed).then_with(|| a.term.cmp(&b.term)));
+
+    let draft = (1..=terms.len().min(MAX_DRAFT_TERMS))
+        .rev()
+        .map(|n| splice_terms(description, &terms[..n]))
+        .find(|text| text.chars().count() <= MAX_DESCRIPTION_LENGTH)
+        .map(|text| DescriptionDraft {
+            matched: count_strong(queries, name, &text),
+            text,
+            source: DraftSource::Deterministic,
+        });
+
+    SuggestionReport {
+        name: name.clone(),
+        description: description.clone(),
+        queries: queries.len(),
+        matched: count_strong(queries, name, description),
+        terms,
+        draft,
+    }
+}
+
+/// Ask an LLM to revise the description in `report` to cover its missing
+/// terms.
+///
+/// The result is checked like a deterministic draft: it must be non-empty,
+/// within 1024 characters, and contain every original trigger clause
+/// (compared case-insensitively). Otherwise an error is returned so the
+/// caller can keep the deterministic draft.
+///
+/// # Errors
+///
+/// Returns the provider's error, or `AigentError::Build` if the response
+/// fails the checks above.
+pub fn llm_revise_description(
+    provider: &dyn LlmProvider,
+    report: &SuggestionReport,
+    queries: &[&str],
+) -> Result<DescriptionDraft> {
+    let system = "You revise AI agent skill descriptions so they match more user \
+        requests. Keep the description in third person, keep every existing \
+        'Use when' trigger clause word for word, and work the missing terms in \
+        naturally. Reply with ONLY the revised description, at most 1024 characters.";
+    let terms: Vec<&str> = report.terms.iter().map(|t| t.term.as_str()).collect();
+    let user_msg = format!(
+        "Skill name: {}\nDescription: {}\nMissing terms: {}",
+        report.name,
+        report.description,
+        terms.join(", ")
+    );
+
+    let text = provider.generate(system, &user_msg)?.trim().to_string();
+    if text.is_empty() || text.chars().count() > MAX_DESCRIPTION_LENGTH {
+        return Err(AigentError::Build {
+            message: "LLM returned an empty or overlong description".to_string(),
+        });
+    }
+    let lower = text.to_lowercase();
+    if trigger_clauses(&report.description)
+        .iter()
+        .any(|c| !lower.contains(&c.text.to_lowercase()))
+    {
+        return Err(AigentError::Build {
+            message: "LLM description dropped the existing trigger phrase".to_string(),
+        });
+    }
+    Ok(DescriptionDraft {
+        matched: count_strong(queries, &report.name, &text),
+        text,
+        source: DraftSource::Llm,
+    })
+}
+
+/// Format a suggestion report as human-readable text.
+#[must_use]
+pub fn format_suggestions(report: &SuggestionReport) -> String {
+    let mut out = format!(
+        "{}: {} of {} queries match strongly\n",
+        report.name, report.matched, report.queries
+    );
+    if report.terms.is_empty() {
+        out.push_str("\nNo missing terms: every weak or missed query is covered.\n");
+        return out;
+    }
+    out.push_str("\nMissing terms (weak or missed queries using each):\n");
+    let width = report
+        .terms
+        .iter()
+        .map(|t| t.term.chars().count())
+        .max()
+        .unwrap_or(0);
+    for t in &report.terms {
+        out.push_str(&format!("  {:<width$}  {}\n", t.term, t.missed));
+    }
+    if let Some(draft) = &report.draft {
+        let source = match draft.source {
+            DraftSource::Deterministic => "deterministic",
+            DraftSource::Llm => "LLM-generated, review before use",
+        };
+        out.push_str(&format!(
+            "\nSuggested description ({source}; {} of {} queries match strongly):\n  {}\n",
+            draft.matched, report.queries, draft.text
+        ));
+    }
+    out
+}
+
+/// Returns `true` if `query` matches the skill strongly.
+fn is_strong(query: &str, name: &str, description: &str) -> bool {
+    compute_query_match_with(query, name, description, true).0 == QueryMatch::Strong
+}
+
+/// Number of `queries` that match the skill strongly.
+fn count_strong(queries: &[&str], name: &str, description: &str) -> usize {
+    queries
+        .iter()
+        .filter(|q| is_strong(q, name, description))
+        .count()
+}
+
+/// The most frequent spelling, preferring the alphabetically first on ties.
+fn most_common(spellings: HashMap<String, usize>) -> String {
+    spellings
+        .into_iter()
+        .max_by(|(a, ca), (b, cb)| ca.cmp(cb).then_with(|| b.cmp(a)))
+        .map(|(s, _)| s)
+        .unwrap_or_default()
+}
+
+/// Splice `terms` into the last trigger clause of `description`, or append
+/// a new trigger sentence if it has none.
+fn splice_terms(description: &str, terms: &[TermSuggestion]) -> String {
+    let list = join_terms(terms);
+    if let Some(clause) = trigger_clauses(description).last() {
+        let end = clause.span.end;
+        format!(
+            "{}, or when the request mentions {list}{}",
+            &description[..end],
+            &description[end..]
+        )
+    } else {
+        let trimmed = description.trim_end();
+        let stop = if trimmed.ends_with(['.', '!', '?']) {
+            ""
+        } else {
+            "."
+        };
+        format!("{trimmed}{stop} Use when the request mentions {list}.")
+    }
+}
+
+/// Join terms as "a", "a or b", or "a, b, or c".
+fn join_terms(terms: &[TermSuggestion]) -> String {
+    let words: Vec<&str> = terms.iter().map(|t| t.term.as_str()).collect();
+    match words.as_slice() {
+        [] => String::new(),
+        [one] => (*one).to_string(),
+        [a, b] => format!("{a} or {b}"),
+        [rest @ .., last] => format!("{}, or {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::providers::mock::MockProvider;
+
+    fn props(name: &str, description: &str) -> SkillProperties {
+        SkillProperties {
+            name: name.to_string(),
+            description: description.to_string(),
+            license: None,
+            compatibility: None,
+            allowed_tools: None,
+            metadata: None,
+        }
+    }
+
+    const QUERIES: &[&str] = &[
+        "extract text from a pdf",
+        "read the invoice totals",
+        "parse invoice line items",
+        "pull data from a receipt",
+        "scan a receipt and an invoice",
+    ];
+
+    #[test]
+    fn ranks_terms_missed_by_weak_queries() {
+        let p = props(
+            "pdf-extractor",
+            "Extracts text from PDF files. Use when working with PDF documents.",
+        );
+        let report = suggest_description_improvements(&p, QUERIES);
+        assert_eq!(report.queries, 5);
+        assert_eq!(report.matched, 1);
+        let terms: Vec<(&str, usize)> = report
+            .terms
+            .iter()
+            .map(|t| (t.term.as_str(), t.missed))
+            .collect();
+        assert_eq!(
+            terms,
+            vec![
+                ("invoice", 3),
+                ("receipt", 2),
+                ("data", 1),
+                ("items", 1),
+                ("line", 1),
+                ("pull", 1),
+                ("scan", 1),
+                ("totals", 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn draft_splices_terms_into_trigger_clause() {
+        let p = props(
+            "pdf-extractor",
+            "Extracts text from PDF files. Use when working with PDF documents.",
+        );
+        let report = suggest_description_improvements(&p, QUERIES);
+        let draft = report.draft.unwrap();
+        assert_eq!(draft.source, DraftSource::Deterministic);
+        assert_eq!(
+            draft.text,
+            "Extracts text from PDF files. Use when working with PDF documents, \
+             or when the request mentions invoice, receipt, data, items, or line."
+        );
+        assert!(draft.matched > report.matched, "draft should match more");
+    }
+
+    #[test]
+    fn draft_adds_trigger_when_missing() {
+        let p = props("pdf-extractor", "Extracts text from PDF files");
+        let report = suggest_description_improvements(&p, &["summarize an invoice"]);
+        assert_eq!(
+            report.draft.unwrap().text,
+            "Extracts text from PDF files. Use when the request mentions invoice or summarize."
+        );
+    }
+
+    #[test]
+    fn output_is_stable_across_runs() {
+        let p = props("pdf-extractor", "Extracts text from PDF files.");
+        let first = suggest_description_improvements(&p, QUERIES);
+        for _ in 0..5 {
+            assert_eq!(suggest_description_improvements(&p, QUERIES), first);
+        }
+    }
+
+    #[test]
+    fn covered_queries_give_no_suggestions() {
+        let p = props(
+            "pdf-extractor",
+            "Extracts text from PDF files. Use when working with PDF documents.",
+        );
+        let report = suggest_description_improvements(&p, &["extract text from a pdf"]);
+        assert!(report.terms.is_empty());
+        assert!(report.draft.is_none());
+        assert!(format_suggestions(&report).contains("No missing terms"));
+    }
+
+    #[test]
+    fn draft_stays_within_length_limit() {
+        // 974 characters: room for two spliced terms but not three.
+        let long = format!(
+            "{}. Use when working with PDF documents.",
+            "Extracts".repeat(117)
+        );
+        let p = props("pdf-extractor", &long);
+        let report = suggest_description_improvements(&p, QUERIES);
+        let draft = report.draft.unwrap();
+        assert!(draft.text.chars().count() <= MAX_DESCRIPTION_LENGTH);
+        assert!(draft.text.contains("Use when working with PDF documents"));
+        assert!(
+            draft.text.ends_with("mentions invoice or receipt."),
+            "{}",
+            draft.text
+        );
+    }
+
+    #[test]
+    fn llm_draft_must_keep_trigger() {
+        let p = props(
+            "pdf-extractor",
+            "Extracts text from PDF files. Use when working with PDF documents.",
+        );
+        let report = suggest_description_improvements(&p, QUERIES);
+
+        let good = MockProvider::new(
+            "Extracts text from PDFs, invoices, and receipts. Use when working with PDF documents.",
+        );
+        let draft = llm_revise_description(&good, &report, QUERIES).unwrap();
+        assert_eq!(draft.source, DraftSource::Llm);
+        assert!(format_suggestions(&SuggestionReport {
+            draft: Some(draft),
+            ..report.clone()
+        })
+        .contains("LLM-generated, review before use"));
+
+        let bad = MockProvider::new("Extracts text from invoices and receipts.");
+        assert!(llm_revise_description(&bad, &report, QUERIES).is_err());
+    }
+}
@@ -4,7 +4,10 @@
 //! outcomes. The test runner evaluates each query using the probe infrastructure
 //! and compares against expectations.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use regex::Regex;
 
 use crate::errors::{AigentError, Result};
 use crate::parser::read_file_checked;
@@ -17,8 +20,82 @@ pub struct TestSuiteResult {
     pub passed: usize,
     /// Number of failing test cases.
     pub failed: usize,
+    /// Number of fixture cases skipped because they did not match the
+    /// filter or tags.
+    pub filtered_out: usize,
+    /// The input filter applied to this run, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    /// The tags selecting cases for this run, if any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     /// Individual test case results.
     pub results: Vec<TestCaseResult>,
+    /// Wall-clock time spent running the suite, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Aggregate counts over several test suite runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SuiteTotals {
+    /// Number of suites run.
+    pub suites: usize,
+    /// Passing cases across all suites.
+    pub passed: usize,
+    /// Failing cases across all suites.
+    pub failed: usize,
+    /// Cases skipped by the filter or tags across all suites.
+    pub filtered_out: usize,
+    /// Summed suite durations, in milliseconds.
+    pub duration_ms: u64,
+}
+
+impl SuiteTotals {
+    /// Sum the counts of each `(skill_dir, result)` entry.
+    #[must_use]
+    pub fn from_results(results: &[(PathBuf, TestSuiteResult)]) -> Self {
+        results.iter().fold(Self::default(), |totals, (_, r)| Self {
+            suites: totals.suites + 1,
+            passed: totals.passed + r.passed,
+            failed: totals.failed + r.failed,
+            filtered_out: totals.filtered_out + r.filtered_out,
+            duration_ms: totals.duration_ms + r.duration_ms,
+        })
+    }
+}
+
+/// Options controlling which fixture cases a test suite run executes.
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+    /// Only run cases whose input contains this substring (case-insensitive).
+    ///
+    /// Cases that do not match are skipped and counted in
+    /// [`TestSuiteResult::filtered_out`]; they do not affect the pass/fail
+    /// counts. A filter that matches nothing yields an empty (passing) result,
+    /// so callers that want to treat this as a mistake must check
+    /// `passed + failed == 0` themselves.
+    pub filter: Option<String>,
+    /// Only run cases carrying at least one of these `tags`.
+    ///
+    /// Empty runs every case. Tags are compared exactly. Skipped cases are
+    /// counted in [`TestSuiteResult::filtered_out`], like the input filter.
+    pub tags: Vec<String>,
+    /// With `tags`, also run cases that have no tags at all.
+    pub include_untagged: bool,
+}
+
+impl TestOptions {
+    /// Returns `true` if the given query case should be executed.
+    fn matches(&self, query: &TestQuery) -> bool {
+        let input_matches = match &self.filter {
+            Some(f) => query.input.to_lowercase().contains(&f.to_lowercase()),
+            None => true,
+        };
+        let tags_match = self.tags.is_empty()
+            || (query.tags.is_empty() && self.include_untagged)
+            || query.tags.iter().any(|t| self.tags.contains(t));
+        input_matches && tags_match
+    }
 }
 
 /// Result of a single test case.
@@ -34,6 +111,12 @@ pub struct TestCaseResult {
     pub score: f64,
     /// Whether the test case passed.
     pub passed: bool,
+    /// The case's tags from `tests.yml`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The `expect_matches` pattern checked for this case, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expect_matches: Option<String>,
     /// Optional failure reason.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
@@ -71,7 +154,7 @@ impl MatchStrength {
 
 /// A test fixture parsed from tests.yml.
 #[derive(Debug, serde::Deserialize)]
-struct TestFixture {
+pub(crate) struct TestFixture {
     /// The list of test queries.
     queries: Vec<TestQuery>,
 }
@@ -89,18 +172,99 @@ struct TestQuery {
     /// Optional expected match strength (human-friendly alternative to `min_score`).
     #[serde(default)]
     strength: Option<MatchStrength>,
+    /// Optional regex the text selected by `expect_matches_in` must match.
+    /// Compiled when the fixture is parsed.
+    #[serde(default, deserialize_with = "deserialize_regex")]
+    expect_matches: Option<Regex>,
+    /// Which text `expect_matches` is checked against.
+    #[serde(default)]
+    expect_matches_in: ExpectTarget,
+    /// Labels for selecting the case with `--tag`.
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Text that a query's `expect_matches` regex is checked against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ExpectTarget {
+    /// The skill's frontmatter description.
+    #[default]
+    Description,
+    /// The skill's `<available_skills>` XML prompt entry.
+    Prompt,
+}
+
+impl ExpectTarget {
+    /// Name used in failure messages.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Description => "description",
+            Self::Prompt => "prompt",
+        }
+    }
+}
+
+/// Deserialize an optional regex, rejecting patterns that do not compile.
+fn deserialize_regex<'de, D>(deserializer: D) -> std::result::Result<Option<Regex>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(pattern) = <Option<String> as serde::Deserialize>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    Regex::new(&pattern)
+        .map(Some)
+        .map_err(|e| serde::de::Error::custom(format!("invalid expect_matches regex: {e}")))
+}
+
+/// Parse `tests.yml` content into a fixture.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if the content is not a valid fixture.
+pub(crate) fn parse_fixture(content: &str) -> Result<TestFixture> {
+    serde_yaml_ng::from_str(content).map_err(|e| AigentError::Parse {
+        message: format!("invalid tests.yml: {e}"),
+    })
+}
+
+/// Queries in a skill's `tests.yml` that should activate it, in file order.
+///
+/// Used as the default query set for `probe --suggest`.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if `tests.yml` is missing or invalid, or
+/// `AigentError::Io` if it cannot be read.
+pub fn positive_queries(skill_dir: &Path) -> Result<Vec<String>> {
+    let fixture_path = skill_dir.join("tests.yml");
+    if !fixture_path.exists() {
+        return Err(AigentError::Parse {
+            message: format!("no tests.yml found in {}", skill_dir.display()),
+        });
+    }
+    let fixture = parse_fixture(&read_file_checked(&fixture_path)?)?;
+    Ok(fixture
+        .queries
+        .into_iter()
+        .filter(|q| q.should_match)
+        .map(|q| q.input)
+        .collect())
 }
 
 /// Run a test suite for a skill directory.
 ///
 /// Reads `tests.yml` from the skill directory and runs each query through
 /// the probe infrastructure, comparing results against expectations.
+/// Only queries accepted by `options` are executed.
 ///
 /// # Errors
 ///
 /// Returns an error if `tests.yml` cannot be found or parsed, or if the
 /// skill directory is invalid.
-pub fn run_test_suite(skill_dir: &Path) -> Result<TestSuiteResult> {
+pub fn run_test_suite(skill_dir: &Path, options: &TestOptions) -> Result<TestSuiteResult> {
+    let start = Instant::now();
     let fixture_path = skill_dir.join("tests.yml");
     if !fixture_path.exists() {
         return Err(AigentError::Parse {
@@ -112,16 +276,21 @@ pub fn run_test_suite(skill_dir: &Path) -> Result<TestSuiteResult> {
     }
 
     let content = read_file_checked(&fixture_path)?;
-    let fixture: TestFixture =
-        serde_yaml_ng::from_str(&content).map_err(|e| AigentError::Parse {
-            message: format!("invalid tests.yml: {e}"),
-        })?;
+    let fixture = parse_fixture(&content)?;
 
     let mut results = Vec::new();
     let mut passed = 0;
     let mut failed = 0;
+    let mut filtered_out = 0;
+    // The prompt entry is rendered once, on first use.
+    let mut prompt_text: Option<String> = None;
 
     for query in &fixture.queries {
+        if !options.matches(query) {
+            filtered_out += 1;
+            continue;
+        }
+
         let probe_result = tester::test_skill(skill_dir, &query.input)?;
 
         let actual_match = !matches!(probe_result.query_match, tester::QueryMatch::None);
@@ -151,6 +320,24 @@ pub fn run_test_suite(skill_dir: &Path) -> Result<TestSuiteResult> {
             ));
         }
 
+        // Check the text assertion once activation expectations hold.
+        if let (true, Some(re)) = (case_passed, &query.expect_matches) {
+            let text = match query.expect_matches_in {
+                ExpectTarget::Description => probe_result.description.as_str(),
+                ExpectTarget::Prompt => prompt_text
+                    .get_or_insert_with(|| crate::prompt::to_prompt(&[skill_dir]))
+                    .as_str(),
+            };
+            if !re.is_match(text) {
+                case_passed = false;
+                reason = Some(format!(
+                    "{} does not match /{}/: {text:?}",
+                    query.expect_matches_in.label(),
+                    re.as_str()
+                ));
+            }
+        }
+
         if case_passed {
             passed += 1;
         } else {
@@ -163,6 +350,11 @@ pub fn run_test_suite(skill_dir: &Path) -> Result<TestSuiteResult> {
             actual_match,
             score,
             passed: case_passed,
+            tags: query.tags.clone(),
+            expect_matches: query
+                .expect_matches
+                .as_ref()
+                .map(|re| re.as_str().to_string()),
             reason,
         });
     }
@@ -170,7 +362,11 @@ pub fn run_test_suite(skill_dir: &Path) -> Result<TestSuiteResult> {
     Ok(TestSuiteResult {
         passed,
         failed,
+        filtered_out,
+        filter: options.filter.clone(),
+        tags: options.tags.clone(),
         results,
+        duration_ms: u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
     })
 }
 
@@ -229,10 +425,187 @@ pub fn generate_fixture(skill_dir: &Path) -> Result<String> {
         message: format!("failed to generate tests.yml: {e}"),
     })?;
 
-    Ok(format!(
-        "# Test fixture for {name}\n# Run with: aigent test {name}/\n{yaml}",
-        name = props.name,
-    ))
+    Ok(format!("{}{yaml}", fixture_header(&props.name)))
+}
+
+/// Comment lines opening a generated `tests.yml`.
+fn fixture_header(name: &str) -> String {
+    format!("# Test fixture for {name}\n# Run with: aigent test {name}/\n")
+}
+
+/// Comment placed above each case recorded by `aigent probe --record`.
+///
+/// Deleting it marks the case as reviewed.
+pub const RECORDED_MARKER: &str = "# recorded by aigent probe, pending review";
+
+/// A `tests.yml` case still marked with [`RECORDED_MARKER`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RecordedCase {
+    /// The recorded query.
+    pub input: String,
+    /// Whether the query activated the skill when it was recorded.
+    pub should_match: bool,
+}
+
+/// Where the `queries:` list sits in `tests.yml` text, by 0-based line.
+struct QueriesLayout {
+    /// Line of the `queries:` key.
+    key: usize,
+    /// Whether the key holds an empty flow list (`queries: []`).
+    empty_flow: bool,
+    /// Indentation of the list items.
+    indent: String,
+    /// First line of each list item.
+    items: Vec<usize>,
+    /// Line after the last non-comment line of the list.
+    end: usize,
+}
+
+/// Locate the `queries:` list in `lines`, or `None` if there is no
+/// top-level `queries` key.
+fn queries_layout(lines: &[&str]) -> Option<QueriesLayout> {
+    let key = lines.iter().position(|l| l.starts_with("queries:"))?;
+    let empty_flow = lines[key]["queries:".len()..].trim() == "[]";
+    let mut indent = None;
+    let mut items = Vec::new();
+    let mut end = key + 1;
+    for (i, line) in lines.iter().enumerate().skip(key + 1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let width = line.len() - line.trim_start().len();
+        if width == 0 && !trimmed.starts_with('-') {
+            break;
+        }
+        if trimmed == "-" || trimmed.starts_with("- ") {
+            let indent = indent.get_or_insert(width);
+            if *indent == width {
+                items.push(i);
+            }
+        }
+        end = i + 1;
+    }
+    Some(QueriesLayout {
+        key,
+        empty_flow,
+        indent: " ".repeat(indent.unwrap_or(0)),
+        items,
+        end,
+    })
+}
+
+/// Append `query` to the `tests.yml` at `path` as a recorded case, with
+/// `should_match` set to `matched` and [`RECORDED_MARKER`] above it.
+///
+/// The case is inserted as text at the end of the `queries` list, so
+/// existing cases, comments, and formatting are left as they are. A missing
+/// file is created with the header [`generate_fixture`] uses. Returns
+/// `false` without writing if a case with the same input already exists.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if the existing file is not a valid fixture
+/// or its `queries` list cannot be appended to, or `AigentError::Io` if the
+/// file cannot be written.
+pub fn append_recorded_case(path: &Path, query: &str, matched: bool) -> Result<bool> {
+    let content = if path.exists() {
+        let content = read_file_checked(path)?;
+        if parse_fixture(&content)?
+            .queries
+            .iter()
+            .any(|q| q.input == query)
+        {
+            return Ok(false);
+        }
+        content
+    } else {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let name = crate::read_properties(dir).map_or_else(
+            |_| {
+                dir.file_name()
+                    .map_or_else(|| "skill".into(), |n| n.to_string_lossy().into_owned())
+            },
+            |p| p.name,
+        );
+        format!("{}queries: []\n", fixture_header(&name))
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let cannot_append = || AigentError::Parse {
+        message: format!(
+            "cannot append to {}: no top-level queries list",
+            path.display()
+        ),
+    };
+    let layout = queries_layout(&lines).ok_or_else(cannot_append)?;
+    // YAML double-quoted scalars accept JSON string syntax.
+    let input = serde_json::to_string(query).map_err(|e| AigentError::Parse {
+        message: format!("cannot record query: {e}"),
+    })?;
+    let indent = &layout.indent;
+    let case = [
+        format!("{indent}{RECORDED_MARKER}"),
+        format!("{indent}- input: {input}"),
+        format!("{indent}  should_match: {matched}"),
+    ];
+
+    let mut out: Vec<String> = Vec::with_capacity(lines.len() + case.len());
+    for (i, line) in lines.iter().enumerate() {
+        if i == layout.end {
+            out.extend(case.iter().cloned());
+        }
+        if i == layout.key && layout.empty_flow {
+            out.push("queries:".to_string());
+        } else {
+            out.push((*line).to_string());
+        }
+    }
+    if layout.end >= lines.len() {
+        out.extend(case.iter().cloned());
+    }
+    let updated = out.join("\n") + "\n";
+
+    // Guard against layouts the line scan misreads.
+    let appended = parse_fixture(&updated)?;
+    if appended.queries.last().map(|q| q.input.as_str()) != Some(query) {
+        return Err(cannot_append());
+    }
+    std::fs::write(path, updated)?;
+    Ok(true)
+}
+
+/// Cases in the `tests.yml` at `path` still marked with
+/// [`RECORDED_MARKER`], in file order.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if the file is missing or not a valid
+/// fixture.
+pub fn recorded_cases(path: &Path) -> Result<Vec<RecordedCase>> {
+    let content = read_file_checked(path)?;
+    let fixture = parse_fixture(&content)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(layout) = queries_layout(&lines) else {
+        return Ok(Vec::new());
+    };
+    let marked = layout.items.iter().map(|&item| {
+        lines[..item]
+            .iter()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .is_some_and(|l| l.trim() == RECORDED_MARKER)
+    });
+    Ok(fixture
+        .queries
+        .into_iter()
+        .zip(marked)
+        .filter(|(_, marked)| *marked)
+        .map(|(q, _)| RecordedCase {
+            input: q.input,
+            should_match: q.should_match,
+        })
+        .collect())
 }
 
 /// Format test suite results as human-readable text.
@@ -253,15 +626,69 @@ pub fn format_text(result: &TestSuiteResult) -> String {
     }
 
     out.push_str(&format!(
-        "\n{passed} passed, {failed} failed, {total} total\n",
+        "\n{passed} passed, {failed} failed, {total} total",
         passed = result.passed,
         failed = result.failed,
         total = result.passed + result.failed,
     ));
+    if result.filtered_out > 0 {
+        out.push_str(&format!(" ({} filtered out)", result.filtered_out));
+    }
+    out.push('\n');
 
     out
 }
 
+/// Format a per-suite table and the aggregate totals for several suites.
+///
+/// Rows (skill, passed, failed, skipped, duration) are sorted by failures,
+/// most first, then by path, so failing suites lead the table. Skipped
+/// counts are cases excluded by the filter or tags.
+#[must_use]
+pub fn format_summary(results: &[(PathBuf, TestSuiteResult)]) -> String {
+    let mut rows: Vec<(String, &TestSuiteResult)> = results
+        .iter()
+        .map(|(dir, r)| (dir.display().to_string(), r))
+        .collect();
+    rows.sort_by(|a, b| b.1.failed.cmp(&a.1.failed).then_with(|| a.0.cmp(&b.0)));
+
+    let width = rows
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Skill".len());
+    let mut out = format!(
+        "{:<width$}  {:>6}  {:>6}  {:>7}  {:>8}\n",
+        "Skill", "Passed", "Failed", "Skipped", "Duration"
+    );
+    for (name, r) in &rows {
+        out.push_str(&format!(
+            "{name:<width$}  {:>6}  {:>6}  {:>7}  {:>8}\n",
+            r.passed,
+            r.failed,
+            r.filtered_out,
+            format!("{}ms", r.duration_ms)
+        ));
+    }
+
+    let totals = SuiteTotals::from_results(results);
+    out.push_str(&format!(
+        "\nTotal: {} passed, {} failed, {} total",
+        totals.passed,
+        totals.failed,
+        totals.passed + totals.failed
+    ));
+    if totals.filtered_out > 0 {
+        out.push_str(&format!(" ({} filtered out)", totals.filtered_out));
+    }
+    out.push_str(&format!(
+        " across {} suites in {}ms\n",
+        totals.suites, totals.duration_ms
+    ));
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -288,7 +715,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"deploy kubernetes\"\n    should_match: false\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         assert_eq!(result.passed, 2);
         assert_eq!(result.failed, 0);
     }
@@ -300,7 +727,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: false\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         assert_eq!(result.failed, 1);
         assert!(result.results[0].reason.is_some());
     }
@@ -312,7 +739,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    min_score: 0.99\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         // Score likely below 0.99, so should fail.
         assert_eq!(result.failed, 1);
         assert!(result.results[0]
@@ -322,6 +749,79 @@ mod tests {
             .contains("below minimum"));
     }
 
+    #[test]
+    fn positive_queries_keeps_should_match_inputs() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            "---\nname: my-skill\ndescription: Processes PDF files.\n---\nBody.\n",
+            "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"deploy kubernetes\"\n    should_match: false\n  - input: \"read a PDF\"\n    should_match: true\n",
+        );
+        assert_eq!(
+            positive_queries(&dir).unwrap(),
+            vec!["process PDF files", "read a PDF"]
+        );
+        assert!(positive_queries(dir.parent().unwrap()).is_err());
+    }
+
+    // ── expect_matches ───────────────────────────────────────────────
+
+    #[test]
+    fn expect_matches_passes_against_description() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    expect_matches: \"(?i)pdf files\"\n",
+        );
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        assert_eq!(result.passed, 1, "{:?}", result.results);
+        assert_eq!(
+            result.results[0].expect_matches.as_deref(),
+            Some("(?i)pdf files")
+        );
+    }
+
+    #[test]
+    fn expect_matches_failure_reports_regex_and_text() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    expect_matches: \"spreadsheet\"\n",
+        );
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        assert_eq!(result.failed, 1);
+        assert_eq!(
+            result.results[0].reason.as_deref(),
+            Some(
+                "description does not match /spreadsheet/: \"Processes PDF files and generates reports. Use when working with documents.\""
+            )
+        );
+    }
+
+    #[test]
+    fn expect_matches_in_prompt_checks_xml_entry() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n  - input: \"deploy kubernetes\"\n    should_match: false\n    expect_matches: \"<name>my-skill</name>\"\n    expect_matches_in: prompt\n",
+        );
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        assert_eq!(result.passed, 1, "{:?}", result.results);
+    }
+
+    #[test]
+    fn invalid_expect_matches_regex_is_suite_error() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    expect_matches: \"(unclosed\"\n",
+        );
+        let err = run_test_suite(&dir, &TestOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid tests.yml"), "{err}");
+        assert!(err.contains("invalid expect_matches regex"), "{err}");
+    }
+
     #[test]
     fn missing_tests_yml_returns_error() {
         let parent = tempdir().unwrap();
@@ -332,7 +832,7 @@ mod tests {
             "---\nname: no-tests\ndescription: Does things\n---\nBody.\n",
         )
         .unwrap();
-        let result = run_test_suite(&dir);
+        let result = run_test_suite(&dir, &TestOptions::default());
         assert!(result.is_err());
     }
 
@@ -379,7 +879,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    strength: strong\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         // The probe likely scores below 0.6 for this query, so strength: strong should fail.
         if result.failed > 0 {
             assert!(result.results[0]
@@ -398,7 +898,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    strength: weak\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         // "process PDF files" against a PDF skill should score >= 0.3.
         assert_eq!(
             result.passed, 1,
@@ -413,7 +913,7 @@ mod tests {
             "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
             "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    min_score: 0.99\n    strength: weak\n",
         );
-        let result = run_test_suite(&dir).unwrap();
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
         // min_score: 0.99 should take precedence and cause failure.
         assert_eq!(result.failed, 1);
         assert!(result.results[0].reason.as_ref().unwrap().contains("0.99"));
@@ -454,6 +954,10 @@ mod tests {
         let result = TestSuiteResult {
             passed: 1,
             failed: 1,
+            filtered_out: 0,
+            filter: None,
+            tags: Vec::new(),
+            duration_ms: 0,
             results: vec![
                 TestCaseResult {
                     input: "query one".into(),
@@ -461,6 +965,8 @@ mod tests {
                     actual_match: true,
                     score: 0.75,
                     passed: true,
+                    tags: Vec::new(),
+                    expect_matches: None,
                     reason: None,
                 },
                 TestCaseResult {
@@ -469,6 +975,8 @@ mod tests {
                     actual_match: false,
                     score: 0.1,
                     passed: false,
+                    tags: Vec::new(),
+                    expect_matches: None,
                     reason: Some("expected a match, got no match".into()),
                 },
             ],
@@ -477,5 +985,290 @@ mod tests {
         assert!(text.contains("[PASS]"));
         assert!(text.contains("[FAIL]"));
         assert!(text.contains("1 passed, 1 failed"));
+        assert!(!text.contains("filtered out"));
+    }
+
+    // ── filter ────────────────────────────────────────────────────────
+
+    // ── format_summary ───────────────────────────────────────────────
+
+    fn suite(passed: usize, failed: usize, filtered_out: usize) -> TestSuiteResult {
+        TestSuiteResult {
+            passed,
+            failed,
+            filtered_out,
+            filter: None,
+            tags: Vec::new(),
+            results: Vec::new(),
+            duration_ms: 5,
+        }
+    }
+
+    #[test]
+    fn suite_totals_sum_each_suite() {
+        let results = vec![
+            (PathBuf::from("a"), suite(2, 1, 0)),
+            (PathBuf::from("b"), suite(3, 0, 2)),
+        ];
+        assert_eq!(
+            SuiteTotals::from_results(&results),
+            SuiteTotals {
+                suites: 2,
+                passed: 5,
+                failed: 1,
+                filtered_out: 2,
+                duration_ms: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn format_summary_sorts_failures_then_name() {
+        let results = vec![
+            (PathBuf::from("zeta"), suite(1, 0, 0)),
+            (PathBuf::from("beta"), suite(0, 1, 0)),
+            (PathBuf::from("alpha"), suite(1, 0, 1)),
+            (PathBuf::from("gamma"), suite(0, 2, 0)),
+        ];
+        let text = format_summary(&results);
+        let order: Vec<&str> = text
+            .lines()
+            .skip(1)
+            .take(4)
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(order, vec!["gamma", "beta", "alpha", "zeta"]);
+        assert!(
+            text.contains("alpha       1       0        1       5ms"),
+            "text: {text}"
+        );
+        assert!(text.contains(
+            "Total: 2 passed, 3 failed, 5 total (1 filtered out) across 4 suites in 20ms"
+        ));
+    }
+
+    const PDF_SKILL: &str = "---\nname: my-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n";
+
+    const THREE_QUERIES: &str = "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"Convert pdf to text\"\n    should_match: true\n  - input: \"deploy kubernetes\"\n    should_match: false\n";
+
+    fn filtered(filter: &str) -> TestOptions {
+        TestOptions {
+            filter: Some(filter.to_string()),
+            ..TestOptions::default()
+        }
+    }
+
+    #[test]
+    fn filter_runs_only_matching_cases_case_insensitive() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, THREE_QUERIES);
+        let result = run_test_suite(&dir, &filtered("PDF")).unwrap();
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.passed + result.failed, 2);
+        assert_eq!(result.filtered_out, 1);
+        assert!(result
+            .results
+            .iter()
+            .all(|r| r.input.to_lowercase().contains("pdf")));
+    }
+
+    #[test]
+    fn filter_counts_reflect_executed_subset() {
+        // The failing expectation is filtered out, so the run passes.
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"generate reports\"\n    should_match: false\n",
+        );
+        let result = run_test_suite(&dir, &filtered("process")).unwrap();
+        assert_eq!(result.passed, 1);
+        assert_eq!(result.failed, 0);
+        assert_eq!(result.filtered_out, 1);
+    }
+
+    #[test]
+    fn filter_with_no_matches_yields_empty_result() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, THREE_QUERIES);
+        let result = run_test_suite(&dir, &filtered("nothing-like-this")).unwrap();
+        assert_eq!(result.passed, 0);
+        assert_eq!(result.failed, 0);
+        assert_eq!(result.filtered_out, 3);
+        assert!(result.results.is_empty());
+    }
+
+    #[test]
+    fn filter_is_serialized_in_json() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, THREE_QUERIES);
+        let result = run_test_suite(&dir, &filtered("deploy")).unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["filter"], "deploy");
+        assert_eq!(json["filtered_out"], 2);
+
+        let unfiltered = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        let json = serde_json::to_value(&unfiltered).unwrap();
+        assert!(json.get("filter").is_none());
+        assert_eq!(json["filtered_out"], 0);
+    }
+
+    // ── tags ──────────────────────────────────────────────────────────
+
+    const TAGGED_QUERIES: &str = "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    tags: [smoke, pdf]\n  - input: \"Convert pdf to text\"\n    should_match: true\n    tags: [pdf]\n  - input: \"deploy kubernetes\"\n    should_match: false\n";
+
+    fn tagged(tags: &[&str], include_untagged: bool) -> TestOptions {
+        TestOptions {
+            tags: tags.iter().map(|t| (*t).to_string()).collect(),
+            include_untagged,
+            ..TestOptions::default()
+        }
+    }
+
+    fn inputs(result: &TestSuiteResult) -> Vec<&str> {
+        result.results.iter().map(|r| r.input.as_str()).collect()
+    }
+
+    #[test]
+    fn tag_runs_only_tagged_cases() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, TAGGED_QUERIES);
+        let result = run_test_suite(&dir, &tagged(&["smoke"], false)).unwrap();
+        assert_eq!(inputs(&result), ["process PDF files"]);
+        assert_eq!(result.filtered_out, 2);
+        assert_eq!(result.results[0].tags, ["smoke", "pdf"]);
+    }
+
+    #[test]
+    fn any_listed_tag_selects_a_case() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, TAGGED_QUERIES);
+        let result = run_test_suite(&dir, &tagged(&["smoke", "pdf"], false)).unwrap();
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.filtered_out, 1);
+    }
+
+    #[test]
+    fn untagged_cases_run_only_when_included() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, TAGGED_QUERIES);
+        let result = run_test_suite(&dir, &tagged(&["smoke"], true)).unwrap();
+        assert_eq!(inputs(&result), ["process PDF files", "deploy kubernetes"]);
+
+        let all = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        assert_eq!(all.results.len(), 3);
+        assert_eq!(all.filtered_out, 0);
+    }
+
+    #[test]
+    fn tag_and_filter_must_both_match() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, TAGGED_QUERIES);
+        let options = TestOptions {
+            filter: Some("convert".to_string()),
+            ..tagged(&["pdf"], false)
+        };
+        let result = run_test_suite(&dir, &options).unwrap();
+        assert_eq!(inputs(&result), ["Convert pdf to text"]);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["pdf"]));
+    }
+
+    #[test]
+    fn format_text_reports_filtered_out_count() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, THREE_QUERIES);
+        let result = run_test_suite(&dir, &filtered("kubernetes")).unwrap();
+        let text = format_text(&result);
+        assert!(
+            text.contains("1 passed, 0 failed, 1 total (2 filtered out)"),
+            "got: {text}"
+        );
+    }
+
+    // ── recorded cases ────────────────────────────────────────────────
+
+    const EXISTING_TESTS: &str = "# Hand-written cases\nqueries:\n  # the main use\n  - input: \"process PDF files\"\n    should_match: true   # keep strong\n    strength: strong\n";
+
+    #[test]
+    fn append_recorded_case_keeps_existing_text() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, EXISTING_TESTS);
+        let path = dir.join("tests.yml");
+        assert!(append_recorded_case(&path, "merge two \"PDFs\"", false).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            format!(
+                "{EXISTING_TESTS}  {RECORDED_MARKER}\n  - input: \"merge two \\\"PDFs\\\"\"\n    should_match: false\n"
+            )
+        );
+        let fixture = parse_fixture(&content).unwrap();
+        assert_eq!(fixture.queries[1].input, "merge two \"PDFs\"");
+        assert!(!fixture.queries[1].should_match);
+    }
+
+    #[test]
+    fn append_recorded_case_skips_duplicate_input() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, EXISTING_TESTS);
+        let path = dir.join("tests.yml");
+        assert!(!append_recorded_case(&path, "process PDF files", false).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), EXISTING_TESTS);
+        assert!(append_recorded_case(&path, "read a PDF", true).unwrap());
+        assert!(!append_recorded_case(&path, "read a PDF", true).unwrap());
+        assert_eq!(recorded_cases(&path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn append_recorded_case_creates_missing_file() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, EXISTING_TESTS);
+        let path = dir.join("tests.yml");
+        fs::remove_file(&path).unwrap();
+        assert!(append_recorded_case(&path, "process PDF files", true).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            format!(
+                "# Test fixture for my-skill\n# Run with: aigent test my-skill/\nqueries:\n\
+                 {RECORDED_MARKER}\n- input: \"process PDF files\"\n  should_match: true\n"
+            )
+        );
+        let result = run_test_suite(&dir, &TestOptions::default()).unwrap();
+        assert_eq!(result.passed, 1, "{:?}", result.results);
+    }
+
+    #[test]
+    fn append_recorded_case_inserts_before_later_keys() {
+        let (_parent, dir) = make_skill_with_tests(
+            "my-skill",
+            PDF_SKILL,
+            "queries:\n- input: a\n  should_match: true\n\n# trailing notes\nextra: 1\n",
+        );
+        let path = dir.join("tests.yml");
+        // Unknown top-level keys are ignored by the fixture parser.
+        assert!(append_recorded_case(&path, "b", false).unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(
+            content.starts_with(&format!(
+                "queries:\n- input: a\n  should_match: true\n{RECORDED_MARKER}\n- input: \"b\"\n"
+            )),
+            "{content}"
+        );
+        assert!(content.ends_with("\n# trailing notes\nextra: 1\n"));
+    }
+
+    #[test]
+    fn recorded_cases_lists_only_marked_cases() {
+        let (_parent, dir) = make_skill_with_tests("my-skill", PDF_SKILL, EXISTING_TESTS);
+        let path = dir.join("tests.yml");
+        append_recorded_case(&path, "split a PDF", true).unwrap();
+        append_recorded_case(&path, "bake bread", false).unwrap();
+        assert_eq!(
+            recorded_cases(&path).unwrap(),
+            vec![
+                RecordedCase {
+                    input: "split a PDF".into(),
+                    should_match: true,
+                },
+                RecordedCase {
+                    input: "bake bread".into(),
+                    should_match: false,
+                },
+            ]
+        );
+        // Deleting the marker marks the case as reviewed.
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replacen(RECORDED_MARKER, "# reviewed", 1)).unwrap();
+        assert_eq!(recorded_cases(&path).unwrap()[0].input, "bake bread");
     }
 }
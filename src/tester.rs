@@ -10,12 +10,13 @@ use std::path::Path;
 use std::sync::LazyLock;
 
 use rust_stemmers::{Algorithm, Stemmer};
+use serde::Serialize;
 
 use crate::diagnostics::Diagnostic;
-use crate::linter::TRIGGER_PHRASES;
 use crate::models::SkillProperties;
-use crate::parser::read_properties;
+use crate::parser::{read_body, read_properties};
 use crate::prompt::estimate_tokens;
+use crate::reporting::{Style, Styler};
 use crate::structure::validate_structure;
 use crate::validator::validate;
 use crate::Result;
@@ -35,6 +36,8 @@ pub struct TestResult {
     pub score: f64,
     /// Estimated token cost of the skill's prompt footprint.
     pub estimated_tokens: usize,
+    /// Per-component token estimates behind `estimated_tokens`, plus the body.
+    pub estimated_tokens_breakdown: TokenBreakdown,
     /// Validation diagnostics (errors + warnings).
     pub diagnostics: Vec<Diagnostic>,
     /// Structure diagnostics (missing references, etc.).
@@ -43,6 +46,76 @@ pub struct TestResult {
     pub properties: SkillProperties,
 }
 
+/// Estimated token cost of each part of a skill.
+///
+/// `name` and `description` make up the system-prompt footprint reported as
+/// [`TestResult::estimated_tokens`]; `body` is loaded only on activation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct TokenBreakdown {
+    /// Tokens in the skill name.
+    pub name: usize,
+    /// Tokens in the description.
+    pub description: usize,
+    /// Tokens in the `SKILL.md` body.
+    pub body: usize,
+}
+
+/// Options controlling how queries are matched against a skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Stem query and description tokens so morphological variants match
+    /// (e.g., "processing PDFs" matches "Processes PDF files"). Disabling
+    /// this also disables synonym expansion, which works on stems.
+    pub stemming: bool,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self { stemming: true }
+    }
+}
+
+/// Weights of the components of the query match score.
+///
+/// Each component is the fraction of query tokens found in one part of the
+/// skill; the score is their weighted mean, so it stays within 0.0–1.0 and
+/// the [`QueryMatch`] thresholds apply whatever the weights' scale. Only the
+/// ratios between weights matter. Negative weights count as zero.
+///
+/// The defaults reproduce the original formula:
+///
+/// | Field | Default | Component |
+/// |-------|---------|-----------|
+/// | `description_weight` | 0.5 | Description tokens, with synonym expansion |
+/// | `trigger_weight` | 0.3 | Tokens of the description's trigger clauses ("Use when ...") |
+/// | `name_weight` | 0.2 | Substrings of the skill name |
+/// | `body_weight` | 0.0 | Tokens of the `SKILL.md` body |
+///
+/// The body is not in the system prompt, so it does not influence real
+/// activation by default; give it a weight to model an agent that sees it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    /// Weight of the name match.
+    pub name_weight: f64,
+    /// Weight of the description match.
+    pub description_weight: f64,
+    /// Weight of the trigger clause match.
+    pub trigger_weight: f64,
+    /// Weight of the body match.
+    pub body_weight: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            name_weight: 0.2,
+            description_weight: 0.5,
+            trigger_weight: 0.3,
+            body_weight: 0.0,
+        }
+    }
+}
+
 /// Describes how well the skill description matches a test query.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueryMatch {
@@ -70,15 +143,57 @@ pub enum QueryMatch {
 ///
 /// Returns an error if the SKILL.md cannot be read or parsed.
 pub fn test_skill(dir: &Path, query: &str) -> Result<TestResult> {
+    test_skill_with(dir, query, &MatchOptions::default())
+}
+
+/// Test a skill against a sample user query with explicit matching options.
+///
+/// Same as [`test_skill`], but lets callers disable stemming (and with it,
+/// synonym expansion) when exact token matching is wanted.
+///
+/// # Errors
+///
+/// Returns an error if the SKILL.md cannot be read or parsed.
+pub fn test_skill_with(dir: &Path, query: &str, opts: &MatchOptions) -> Result<TestResult> {
+    test_skill_with_weights(dir, query, opts, &ScoreWeights::default())
+}
+
+/// Test a skill against a sample user query with explicit score weights.
+///
+/// Same as [`test_skill_with`], but scores the match with `weights`, so
+/// teams can calibrate the probe against the activation they observe (for
+/// example, weighting the description more heavily than the name).
+///
+/// # Errors
+///
+/// Returns an error if the SKILL.md cannot be read or parsed.
+pub fn test_skill_with_weights(
+    dir: &Path,
+    query: &str,
+    opts: &MatchOptions,
+    weights: &ScoreWeights,
+) -> Result<TestResult> {
     let properties = read_properties(dir)?;
+    let body = read_body(dir).unwrap_or_default();
 
     // Compute weighted match score and category.
-    let (query_match, score) =
-        compute_query_match(query, &properties.name, &properties.description);
+    let (query_match, score) = compute_query_match_weighted(
+        query,
+        &properties.name,
+        &properties.description,
+        &body,
+        opts.stemming,
+        weights,
+    );
 
     // Estimate token footprint: name + description (what goes into system prompt).
-    let estimated_tokens =
-        estimate_tokens(&properties.name) + estimate_tokens(&properties.description);
+    // The body is reported separately since it only loads on activation.
+    let estimated_tokens_breakdown = TokenBreakdown {
+        name: estimate_tokens(&properties.name),
+        description: estimate_tokens(&properties.description),
+        body: estimate_tokens(&body),
+    };
+    let estimated_tokens = estimated_tokens_breakdown.name + estimated_tokens_breakdown.description;
 
     // Run standard validation.
     let diagnostics = validate(dir);
@@ -93,6 +208,7 @@ pub fn test_skill(dir: &Path, query: &str) -> Result<TestResult> {
         query_match,
         score,
         estimated_tokens,
+        estimated_tokens_breakdown,
         diagnostics,
         structure_diagnostics,
         properties,
@@ -161,12 +277,23 @@ fn fmt_field(out: &mut String, label: &str, value: &str, col: usize, width: usiz
 /// Format a test result as human-readable text.
 #[must_use]
 pub fn format_test_result(result: &TestResult) -> String {
-    format_test_result_width(result, DEFAULT_WIDTH)
+    format_test_result_width(result, DEFAULT_WIDTH, &Styler::PLAIN)
+}
+
+/// Format a test result as human-readable text, coloring the activation
+/// verdict and diagnostics with `styler`.
+#[must_use]
+pub fn format_test_result_styled(result: &TestResult, styler: &Styler) -> String {
+    format_test_result_width(result, DEFAULT_WIDTH, styler)
 }
 
 /// Format a test result with a specific terminal width (for testing).
 #[must_use]
-pub(crate) fn format_test_result_width(result: &TestResult, width: usize) -> String {
+pub(crate) fn format_test_result_width(
+    result: &TestResult,
+    width: usize,
+    styler: &Styler,
+) -> String {
     let mut out = String::new();
 
     // Aligned label width (widest label is "Description:" at 12 chars + 1 padding).
@@ -189,13 +316,24 @@ pub(crate) fn format_test_result_width(result: &TestResult, width: usize) -> Str
         QueryMatch::Weak => "WEAK ⚠ — some overlap, but description may not trigger reliably",
         QueryMatch::None => "NONE ✗ — description does not match the test query",
     };
+    // Wrap first, then color, so escape codes do not count towards width.
+    let mut activation = String::new();
     fmt_field(
-        &mut out,
+        &mut activation,
         "Activation:",
         &format!("{match_label} (score: {:.2})", result.score),
         W,
         width,
     );
+    let style = match &result.query_match {
+        QueryMatch::Strong => Style::Success,
+        QueryMatch::Weak => Style::Warning,
+        QueryMatch::None => Style::Error,
+    };
+    out.push_str(&format!(
+        "{}\n",
+        styler.paint(style, activation.trim_end_matches('\n'))
+    ));
 
     // Token budget.
     fmt_field(
@@ -221,13 +359,13 @@ pub(crate) fn format_test_result_width(result: &TestResult, width: usize) -> Str
         if !errors.is_empty() {
             out.push_str(&format!("Validation errors ({}):\n", errors.len()));
             for d in &errors {
-                out.push_str(&format!("  {d}\n"));
+                out.push_str(&format!("  {}\n", styler.diagnostic(d)));
             }
         }
         if !warnings.is_empty() {
             out.push_str(&format!("Validation warnings ({}):\n", warnings.len()));
             for d in &warnings {
-                out.push_str(&format!("  {d}\n"));
+                out.push_str(&format!("  {}\n", styler.diagnostic(d)));
             }
         }
         if !result.structure_diagnostics.is_empty() {
@@ -236,7 +374,7 @@ pub(crate) fn format_test_result_width(result: &TestResult, width: usize) -> Str
                 result.structure_diagnostics.len()
             ));
             for d in &result.structure_diagnostics {
-                out.push_str(&format!("  {d}\n"));
+                out.push_str(&format!("  {}\n", styler.diagnostic(d)));
             }
         }
     }
@@ -276,20 +414,57 @@ const SYNONYM_GROUPS: &[&[&str]] = &[
 /// Cached Snowball English stemmer, reused across all `stem()` calls.
 static STEMMER: LazyLock<Stemmer> = LazyLock::new(|| Stemmer::create(Algorithm::English));
 
+/// Tokens shorter than this (in characters) are never stemmed, so short
+/// words and acronyms like "gas" or "ads" keep their surface form.
+const MIN_STEM_LEN: usize = 4;
+
 /// Stem a word using the Snowball English stemmer.
+///
+/// Words the Snowball stemmer leaves untouched (often acronyms without a
+/// vowel, like "pdfs") fall back to stripping a plain `-s`, `-ing`, or `-ed`
+/// suffix. Words shorter than [`MIN_STEM_LEN`] are returned lowercased but
+/// unstemmed.
 fn stem(word: &str) -> String {
-    STEMMER.stem(&word.to_lowercase()).into_owned()
+    let lower = word.to_lowercase();
+    if lower.chars().count() < MIN_STEM_LEN {
+        return lower;
+    }
+    let stemmed = STEMMER.stem(&lower);
+    if stemmed != lower {
+        return stemmed.into_owned();
+    }
+    strip_suffix_light(&lower).unwrap_or(lower)
+}
+
+/// Strip a common inflectional suffix, keeping at least three characters.
+///
+/// Leaves `-ss`, `-us`, and `-is` endings alone ("class", "status", "analysis").
+fn strip_suffix_light(word: &str) -> Option<String> {
+    let stripped = if let Some(rest) = word.strip_suffix("ing") {
+        rest
+    } else if let Some(rest) = word.strip_suffix("ed") {
+        rest
+    } else if word.ends_with("ss") || word.ends_with("us") || word.ends_with("is") {
+        return None;
+    } else {
+        word.strip_suffix('s')?
+    };
+    (stripped.chars().count() >= 3).then(|| stripped.to_string())
 }
 
-/// Tokenize a string into lowercase, stemmed words with punctuation stripped
-/// and stopwords removed.
-fn tokenize(text: &str) -> Vec<String> {
+/// Tokenize a string into lowercase words with punctuation stripped and
+/// stopwords removed, stemming each word if `stemming` is set.
+pub(crate) fn tokenize_with(text: &str, stemming: bool) -> Vec<String> {
     text.split_whitespace()
         .map(|w| {
             let cleaned = w
                 .trim_matches(|c: char| !c.is_alphanumeric())
                 .to_lowercase();
-            stem(&cleaned)
+            if stemming {
+                stem(&cleaned)
+            } else {
+                cleaned
+            }
         })
         .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
         .collect()
@@ -300,7 +475,7 @@ fn tokenize(text: &str) -> Vec<String> {
 /// For each query token that matches a word in any synonym group,
 /// all other words in that group are added to the result set.
 /// Original tokens are always preserved.
-fn expand_synonyms(tokens: &[String]) -> HashSet<String> {
+pub(crate) fn expand_synonyms(tokens: &[String]) -> HashSet<String> {
     let mut expanded: HashSet<String> = tokens.iter().cloned().collect();
     for token in tokens {
         for group in SYNONYM_GROUPS {
@@ -314,42 +489,75 @@ fn expand_synonyms(tokens: &[String]) -> HashSet<String> {
     expanded
 }
 
-/// Extract a trigger phrase from a description.
+/// Extract the trigger text from a description.
 ///
-/// Scans for lines containing any of the shared `TRIGGER_PHRASES`
-/// (case-insensitive) and returns the full line text if found.
+/// Joins the description's trigger clauses (see [`crate::triggers`]), or
+/// returns `None` if it has none.
 fn extract_trigger(description: &str) -> Option<String> {
-    for line in description.lines() {
-        let trimmed = line.trim();
-        let lower = trimmed.to_lowercase();
-        if TRIGGER_PHRASES.iter().any(|p| lower.contains(p)) {
-            return Some(trimmed.to_string());
-        }
+    let clauses = crate::triggers::trigger_clauses(description);
+    if clauses.is_empty() {
+        return None;
     }
-    None
+    let texts: Vec<&str> = clauses.iter().map(|c| c.text.as_str()).collect();
+    Some(texts.join(" "))
+}
+
+/// Compute a match score between a query and a skill with the default
+/// [`ScoreWeights`], ignoring the body.
+pub(crate) fn compute_query_match_with(
+    query: &str,
+    name: &str,
+    description: &str,
+    stemming: bool,
+) -> (QueryMatch, f64) {
+    compute_query_match_weighted(
+        query,
+        name,
+        description,
+        "",
+        stemming,
+        &ScoreWeights::default(),
+    )
 }
 
 /// Compute a weighted match score between a query and a skill.
 ///
-/// Uses a three-component weighted formula:
-/// - **0.5 × description overlap** (matches against synonym-expanded query tokens,
+/// Combines four components, weighted by `weights`:
+/// - **description overlap** (matches against synonym-expanded query tokens,
 ///   normalized by original query size so synonyms can only help, never hurt)
-/// - **0.3 × trigger score** (fraction of query tokens found in the trigger phrase)
-/// - **0.2 × name score** (fraction of query tokens found as substrings of the name)
+/// - **trigger score** (fraction of query tokens found in the trigger phrase)
+/// - **name score** (fraction of query tokens found as substrings of the name)
+/// - **body score** (fraction of query tokens found in the body; only
+///   computed when `body_weight` is positive)
+///
+/// Without `stemming`, tokens are compared by surface form and synonym
+/// expansion is skipped.
 ///
 /// Returns the [`QueryMatch`] category and the numeric score (0.0–1.0).
 /// Strong ≥ 0.4, Weak ≥ 0.15, None < 0.15.
-fn compute_query_match(query: &str, name: &str, description: &str) -> (QueryMatch, f64) {
-    let query_tokens = tokenize(query);
+pub(crate) fn compute_query_match_weighted(
+    query: &str,
+    name: &str,
+    description: &str,
+    body: &str,
+    stemming: bool,
+    weights: &ScoreWeights,
+) -> (QueryMatch, f64) {
+    let query_tokens = tokenize_with(query, stemming);
 
     if query_tokens.is_empty() {
         return (QueryMatch::None, 0.0);
     }
 
-    let desc_tokens = tokenize(description);
+    let desc_tokens = tokenize_with(description, stemming);
 
-    // Expand query tokens with synonyms for better recall.
-    let expanded_query = expand_synonyms(&query_tokens);
+    // Expand query tokens with synonyms for better recall. Synonym groups
+    // hold stems, so expansion only applies when stemming.
+    let expanded_query = if stemming {
+        expand_synonyms(&query_tokens)
+    } else {
+        query_tokens.iter().cloned().collect()
+    };
 
     // Description overlap: check expanded query tokens against description,
     // but normalize by original query size so synonyms can only help, never hurt.
@@ -367,7 +575,7 @@ fn compute_query_match(query: &str, name: &str, description: &str) -> (QueryMatc
 
     // Trigger score: fraction of query tokens found in the trigger phrase.
     let trigger_score = if let Some(trigger) = extract_trigger(description) {
-        let trigger_tokens = tokenize(&trigger);
+        let trigger_tokens = tokenize_with(&trigger, stemming);
         let trigger_set: HashSet<&str> = trigger_tokens.iter().map(|s| s.as_str()).collect();
         let matched = query_tokens
             .iter()
@@ -386,8 +594,34 @@ fn compute_query_match(query: &str, name: &str, description: &str) -> (QueryMatc
         .count();
     let name_score = matched as f64 / query_tokens.len() as f64;
 
-    // Weighted formula.
-    let score = 0.5 * desc_overlap + 0.3 * trigger_score + 0.2 * name_score;
+    let name_weight = weights.name_weight.max(0.0);
+    let description_weight = weights.description_weight.max(0.0);
+    let trigger_weight = weights.trigger_weight.max(0.0);
+    let body_weight = weights.body_weight.max(0.0);
+
+    // Body score: fraction of query tokens found in the body.
+    let body_score = if body_weight > 0.0 {
+        let body_tokens = tokenize_with(body, stemming);
+        let body_set: HashSet<&str> = body_tokens.iter().map(|s| s.as_str()).collect();
+        let matched = query_tokens
+            .iter()
+            .filter(|t| body_set.contains(t.as_str()))
+            .count();
+        matched as f64 / query_tokens.len() as f64
+    } else {
+        0.0
+    };
+
+    // Weighted mean of the components.
+    let total_weight = name_weight + description_weight + trigger_weight + body_weight;
+    if total_weight <= 0.0 {
+        return (QueryMatch::None, 0.0);
+    }
+    let score = (description_weight * desc_overlap
+        + trigger_weight * trigger_score
+        + name_weight * name_score
+        + body_weight * body_score)
+        / total_weight;
 
     let category = if score >= 0.4 {
         QueryMatch::Strong
@@ -406,6 +640,11 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    /// Compute a weighted match score between a query and a skill, with stemming.
+    fn compute_query_match(query: &str, name: &str, description: &str) -> (QueryMatch, f64) {
+        compute_query_match_with(query, name, description, true)
+    }
+
     /// Create a skill dir with given frontmatter + body.
     fn make_skill(
         name: &str,
@@ -539,8 +778,151 @@ mod tests {
         );
     }
 
+    // ── Stemming ─────────────────────────────────────────────────────
+
+    #[test]
+    fn stemmed_variants_match_at_least_as_strongly() {
+        let desc = "Processes PDF files";
+        let (base_match, base) = compute_query_match("process pdf", "unrelated-name", desc);
+        let (variant_match, variant) =
+            compute_query_match("processing pdfs", "unrelated-name", desc);
+        assert_eq!(base_match, QueryMatch::Strong);
+        assert_eq!(variant_match, QueryMatch::Strong);
+        assert!(variant >= base, "{variant} < {base}");
+    }
+
+    #[test]
+    fn disabling_stemming_requires_exact_tokens() {
+        let desc = "Processes PDF files";
+        let (m, score) = compute_query_match_with("processing pdfs", "unrelated-name", desc, false);
+        assert_eq!(m, QueryMatch::None);
+        assert_eq!(score, 0.0);
+        let (m, _) = compute_query_match_with("processes pdf", "unrelated-name", desc, false);
+        assert_eq!(m, QueryMatch::Strong);
+    }
+
+    #[test]
+    fn short_tokens_are_not_stemmed() {
+        assert_eq!(stem("ads"), "ads");
+        assert_eq!(stem("GAS"), "gas");
+        assert_eq!(stem("pdfs"), "pdf");
+        assert_eq!(stem("class"), "class");
+        assert_eq!(stem("status"), "status");
+    }
+
+    #[test]
+    fn test_skill_with_respects_stemming_option() {
+        let (_parent, dir) = make_skill("pdf-tool", "Processes PDF files", "Body.");
+        let exact = MatchOptions { stemming: false };
+        let stemmed = test_skill_with(&dir, "processing pdfs", &MatchOptions::default()).unwrap();
+        let unstemmed = test_skill_with(&dir, "processing pdfs", &exact).unwrap();
+        assert!(stemmed.score > unstemmed.score);
+    }
+
     // ── Weighted scoring specific tests ──────────────────────────────
 
+    // ── Score weights ────────────────────────────────────────────────
+
+    #[test]
+    fn default_weights_match_original_formula() {
+        let (_parent, dir) = make_skill(
+            "pdf-tool",
+            "Processes PDF files. Use when extracting text from PDFs.",
+            "Body.",
+        );
+        let default = test_skill(&dir, "extract pdf text").unwrap();
+        let explicit = test_skill_with_weights(
+            &dir,
+            "extract pdf text",
+            &MatchOptions::default(),
+            &ScoreWeights {
+                name_weight: 0.2,
+                description_weight: 0.5,
+                trigger_weight: 0.3,
+                body_weight: 0.0,
+            },
+        )
+        .unwrap();
+        assert_eq!(default.score, explicit.score);
+    }
+
+    #[test]
+    fn raising_description_weight_ranks_description_match_above_name_match() {
+        let query = "deploy kubernetes cluster";
+        let (_p1, name_only) = make_skill(
+            "deploy-kubernetes-cluster",
+            "Manages infrastructure rollouts.",
+            "Body.",
+        );
+        let (_p2, desc_only) = make_skill(
+            "infra-helper",
+            "Rolls out containers to a kubernetes cluster.",
+            "Body.",
+        );
+        let score = |dir: &Path, weights: &ScoreWeights| {
+            test_skill_with_weights(dir, query, &MatchOptions::default(), weights)
+                .unwrap()
+                .score
+        };
+
+        let equal = ScoreWeights {
+            name_weight: 1.0,
+            description_weight: 1.0,
+            trigger_weight: 0.0,
+            body_weight: 0.0,
+        };
+        assert!(score(&name_only, &equal) > score(&desc_only, &equal));
+
+        let description_heavy = ScoreWeights {
+            description_weight: 3.0,
+            ..equal
+        };
+        assert!(
+            score(&desc_only, &description_heavy) > score(&name_only, &description_heavy),
+            "description-only match should outrank name-only match"
+        );
+    }
+
+    #[test]
+    fn body_weight_counts_body_matches() {
+        let (_parent, dir) = make_skill("helper", "Helps with tasks.", "Rotate the api keys.");
+        let body_only = ScoreWeights {
+            name_weight: 0.0,
+            description_weight: 0.0,
+            trigger_weight: 0.0,
+            body_weight: 1.0,
+        };
+        let opts = MatchOptions::default();
+        let result = test_skill_with_weights(&dir, "rotate keys", &opts, &body_only).unwrap();
+        assert_eq!(result.score, 1.0);
+        assert_eq!(result.query_match, QueryMatch::Strong);
+        assert_eq!(test_skill(&dir, "rotate keys").unwrap().score, 0.0);
+    }
+
+    #[test]
+    fn zero_or_negative_weights_score_zero() {
+        let (_parent, dir) = make_skill("pdf-tool", "Processes PDF files", "Body.");
+        let weights = ScoreWeights {
+            name_weight: 0.0,
+            description_weight: -1.0,
+            trigger_weight: 0.0,
+            body_weight: 0.0,
+        };
+        let result =
+            test_skill_with_weights(&dir, "pdf files", &MatchOptions::default(), &weights).unwrap();
+        assert_eq!(result.score, 0.0);
+        assert_eq!(result.query_match, QueryMatch::None);
+    }
+
+    #[test]
+    fn extract_trigger_uses_only_trigger_clauses() {
+        assert_eq!(
+            extract_trigger("Processes PDFs. Use when working with documents.").as_deref(),
+            Some("Use when working with documents")
+        );
+        assert!(extract_trigger(r#"Explains the phrase "use when"."#).is_none());
+    }
+
     #[test]
     fn trigger_phrase_boosts_score() {
         // Use identical base descriptions + same extra words to isolate the trigger effect.
@@ -611,6 +993,24 @@ mod tests {
         assert!(result.estimated_tokens > 0);
     }
 
+    #[test]
+    fn test_skill_breaks_down_tokens_by_component() {
+        let (_parent, dir) = make_skill(
+            "pdf-tool",
+            "Processes PDF files and extracts text content",
+            "Body content here, long enough to outweigh the name by a clear margin.",
+        );
+        let result = test_skill(&dir, "process PDF").unwrap();
+        let b = result.estimated_tokens_breakdown;
+        assert_eq!(b.name, estimate_tokens("pdf-tool"));
+        assert_eq!(
+            b.description,
+            estimate_tokens("Processes PDF files and extracts text content")
+        );
+        assert!(b.body > b.name);
+        assert_eq!(result.estimated_tokens, b.name + b.description);
+    }
+
     #[test]
     fn test_skill_reports_validation_issues() {
         let parent = tempdir().unwrap();
@@ -757,7 +1157,7 @@ mod tests {
             the Anthropic agent skill specification and checks all fields";
         let (_parent, dir) = make_skill("wrap-test", long_desc, "Body content.");
         let result = test_skill(&dir, "validate skill").unwrap();
-        let text = format_test_result_width(&result, 60);
+        let text = format_test_result_width(&result, 60, &Styler::PLAIN);
         let desc_lines: Vec<&str> = text
             .lines()
             .skip_while(|l| !l.starts_with("Description:"))
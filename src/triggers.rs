@@ -0,0 +1,432 @@
+//! Trigger-phrase extraction from skill descriptions.
+//!
+//! A good description says what a skill does ("Processes PDF files.") and
+//! when to use it ("Use when working with documents."). This module splits a
+//! description into those two parts so the linter, scorer, `upgrade`, and
+//! `probe` all agree on what counts as a trigger.
+//!
+//! A trigger clause starts at one of [`TRIGGER_PHRASES`] (e.g., "Use when",
+//! "Use this when", "Use if") or at a trailing conditional introduced by a
+//! comma or dash (", when the user asks…"), and runs to the end of its
+//! sentence. Phrases inside quotes or backticks are examples, not triggers.
+//!
+//! [`TRIGGER_PHRASES`]: crate::triggers::TRIGGER_PHRASES
+
+use std::ops::Range;
+
+/// Trigger phrases that indicate when a skill should be used.
+///
+/// Matched case-insensitively at word boundaries. "Use this" also covers
+/// "Use this when" and "Use this skill when".
+pub const TRIGGER_PHRASES: &[&str] = &[
+    "use when",
+    "use if",
+    "use for",
+    "use this",
+    "invoke when",
+    "activate when",
+];
+
+/// Words that open a trailing conditional clause after a comma or dash.
+const CONDITIONAL_WORDS: &[&str] = &["when", "whenever", "if"];
+
+/// Punctuation that ends a sentence.
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?', ';', '\n'];
+
+/// A clause of a description that says when the skill should be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerClause {
+    /// Clause text, without trailing sentence punctuation.
+    pub text: String,
+    /// Byte range of `text` in the description.
+    pub span: Range<usize>,
+}
+
+/// A description split into what the skill does and when to use it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DescriptionParts {
+    /// Sentences (or sentence prefixes) stating what the skill does.
+    pub capabilities: Vec<String>,
+    /// Clauses stating when to use the skill, in order of appearance.
+    pub triggers: Vec<TriggerClause>,
+}
+
+/// Split a description into capability statements and trigger clauses.
+///
+/// Each sentence contributes at most one trigger clause, starting at its
+/// earliest trigger phrase or trailing conditional; the text before it (if
+/// any) is a capability statement.
+#[must_use]
+pub fn split_description(description: &str) -> DescriptionParts {
+    // ASCII lowercasing keeps byte offsets aligned with the original.
+    let lower = description.to_ascii_lowercase();
+    let quoted = quoted_ranges(description);
+    let mut parts = DescriptionParts::default();
+
+    for sentence in sentence_ranges(description, &quoted) {
+        let clause_start = find_clause_start(&lower, &sentence, &quoted);
+        let capability_end = clause_start.unwrap_or(sentence.end);
+        let capability = description[sentence.start..capability_end]
+            .trim_end_matches(|c: char| {
+                c.is_whitespace()
+                    || SENTENCE_TERMINATORS.contains(&c)
+                    || matches!(c, ',' | '—' | '-')
+            })
+            .trim_start();
+        if !capability.is_empty() {
+            parts.capabilities.push(capability.to_string());
+        }
+
+        if let Some(start) = clause_start {
+            let text = description[start..sentence.end]
+                .trim_end_matches(|c: char| c.is_whitespace() || SENTENCE_TERMINATORS.contains(&c));
+            parts.triggers.push(TriggerClause {
+                text: text.to_string(),
+                span: start..start + text.len(),
+            });
+        }
+    }
+
+    parts
+}
+
+/// Extract the trigger clauses from a description.
+#[must_use]
+pub fn trigger_clauses(description: &str) -> Vec<TriggerClause> {
+    split_description(description).triggers
+}
+
+/// Whether a description has at least one trigger clause.
+#[must_use]
+pub fn has_trigger(description: &str) -> bool {
+    !trigger_clauses(description).is_empty()
+}
+
+/// Byte ranges of sentences, split at terminators outside quotes.
+///
+/// A `.` only ends a sentence when followed by whitespace or the end of the
+/// text, so file extensions and version numbers stay intact.
+fn sentence_ranges(text: &str, quoted: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if !SENTENCE_TERMINATORS.contains(&c) || in_ranges(quoted, i) {
+            continue;
+        }
+        let next = chars.peek().map(|&(_, n)| n);
+        if c == '.' && next.is_some_and(|n| !n.is_whitespace()) {
+            continue;
+        }
+        let end = i + c.len_utf8();
+        push_sentence(&mut ranges, text, start..end);
+        start = end;
+    }
+    push_sentence(&mut ranges, text, start..text.len());
+    ranges
+}
+
+/// Push a sentence range with leading whitespace removed, skipping blanks.
+fn push_sentence(ranges: &mut Vec<Range<usize>>, text: &str, range: Range<usize>) {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    if !text[start..range.end].trim().is_empty() {
+        ranges.push(start..range.end);
+    }
+}
+
+/// Byte offset of the first trigger clause in a sentence, if any.
+fn find_clause_start(
+    lower: &str,
+    sentence: &Range<usize>,
+    quoted: &[Range<usize>],
+) -> Option<usize> {
+    let mut prev_alnum = false;
+    for (offset, c) in lower[sentence.clone()].char_indices() {
+        let at = sentence.start + offset;
+        let word_start = c.is_alphanumeric() && !prev_alnum;
+        prev_alnum = c.is_alphanumeric();
+        if !word_start || in_ranges(quoted, at) {
+            continue;
+        }
+        let rest = &lower[at..sentence.end];
+        if TRIGGER_PHRASES.iter().any(|p| starts_with_word(rest, p)) {
+            return Some(at);
+        }
+        if after_break(&lower[sentence.start..at])
+            && CONDITIONAL_WORDS.iter().any(|w| starts_with_word(rest, w))
+        {
+            return Some(at);
+        }
+    }
+    None
+}
+
+/// Whether `before` ends with a clause break: a comma, an em dash, or a
+/// spaced hyphen (" - "), so hyphenated words like "built-in" do not count.
+fn after_break(before: &str) -> bool {
+    let trimmed = before.trim_end();
+    trimmed.ends_with([',', '—']) || (trimmed.ends_with(" -") && trimmed.len() < before.len())
+}
+
+/// Whether `text` starts with `word` followed by a non-alphanumeric character.
+fn starts_with_word(text: &str, word: &str) -> bool {
+    text.starts_with(word)
+        && !text[word.len()..]
+            .chars()
+            .next()
+            .is_some_and(char::is_alphanumeric)
+}
+
+/// Byte ranges of quoted spans: `"…"`, `“…”`, `` `…` ``, and `'…'`.
+///
+/// A single quote only opens a span after a non-alphanumeric character and
+/// only closes one before a non-alphanumeric character, so apostrophes in
+/// words like "user's" are not quotes. Unclosed quotes are ignored.
+fn quoted_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut iter = text.char_indices();
+    while let Some((i, c)) = iter.next() {
+        let close = match c {
+            '"' => Some('"'),
+            '“' => Some('”'),
+            '`' => Some('`'),
+            '\'' if !prev.is_some_and(char::is_alphanumeric) => Some('\''),
+            _ => None,
+        };
+        prev = Some(c);
+        let Some(close) = close else { continue };
+
+        let after = i + c.len_utf8();
+        let end = text[after..].char_indices().find_map(|(j, d)| {
+            let at = after + j;
+            let next = text[at + d.len_utf8()..].chars().next();
+            let closes = d == close && (close != '\'' || !next.is_some_and(char::is_alphanumeric));
+            closes.then_some(at + d.len_utf8())
+        });
+        if let Some(end) = end {
+            ranges.push(i..end);
+            // Resume scanning after the closing quote.
+            for (j, d) in iter.by_ref() {
+                if j + d.len_utf8() >= end {
+                    prev = Some(d);
+                    break;
+                }
+            }
+        }
+    }
+    ranges
+}
+
+/// Whether `at` falls inside any of `ranges`.
+fn in_ranges(ranges: &[Range<usize>], at: usize) -> bool {
+    ranges.iter().any(|r| r.contains(&at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(description: &str) -> Vec<String> {
+        trigger_clauses(description)
+            .into_iter()
+            .map(|c| c.text)
+            .collect()
+    }
+
+    // ── Trigger phrases ──────────────────────────────────────────────
+
+    #[test]
+    fn use_when_clause_is_extracted_with_span() {
+        let desc = "Processes PDF files. Use when working with documents.";
+        let clauses = trigger_clauses(desc);
+        assert_eq!(clauses.len(), 1);
+        assert_eq!(clauses[0].text, "Use when working with documents");
+        assert_eq!(&desc[clauses[0].span.clone()], clauses[0].text);
+    }
+
+    #[test]
+    fn recognizes_use_this_when_and_use_if() {
+        assert_eq!(
+            texts("Formats code. Use this when editing Rust."),
+            ["Use this when editing Rust"]
+        );
+        assert_eq!(
+            texts("Formats code. Use if the user asks for style fixes."),
+            ["Use if the user asks for style fixes"]
+        );
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        assert_eq!(texts("Formats code. USE WHEN needed."), ["USE WHEN needed"]);
+    }
+
+    #[test]
+    fn phrase_must_start_a_word() {
+        assert!(texts("Helps you reuse when possible.").is_empty());
+        assert!(texts("Users use whenever they like.").is_empty());
+    }
+
+    #[test]
+    fn trigger_in_the_middle_of_a_sentence() {
+        assert_eq!(
+            texts("Processes PDF files; use for extraction tasks"),
+            ["use for extraction tasks"]
+        );
+    }
+
+    // ── Punctuation variants ─────────────────────────────────────────
+
+    #[test]
+    fn punctuation_variants_end_the_clause() {
+        assert_eq!(
+            texts("Formats code. Use when: editing."),
+            ["Use when: editing"]
+        );
+        assert_eq!(
+            texts("Formats code! Use when editing!"),
+            ["Use when editing"]
+        );
+        assert_eq!(
+            texts("Formats code. Use when editing"),
+            ["Use when editing"]
+        );
+        assert_eq!(
+            texts("Formats code.\nUse when editing\n"),
+            ["Use when editing"]
+        );
+        assert_eq!(
+            texts("Formats code. Use when editing; not for docs."),
+            ["Use when editing"]
+        );
+    }
+
+    #[test]
+    fn dots_inside_words_do_not_split_sentences() {
+        assert_eq!(
+            texts("Edits config.yml files. Use when updating v1.2 configs."),
+            ["Use when updating v1.2 configs"]
+        );
+    }
+
+    // ── Trailing conditionals ────────────────────────────────────────
+
+    #[test]
+    fn trailing_conditional_after_comma() {
+        assert_eq!(
+            texts("Formats Rust code, when the user asks for style fixes."),
+            ["when the user asks for style fixes"]
+        );
+        assert_eq!(
+            texts("Formats Rust code — if the build is failing."),
+            ["if the build is failing"]
+        );
+        assert_eq!(
+            texts("Formats Rust code - whenever CI fails."),
+            ["whenever CI fails"]
+        );
+    }
+
+    #[test]
+    fn conditional_without_break_is_not_a_trigger() {
+        assert!(texts("Runs when called by the scheduler.").is_empty());
+        assert!(texts("Checks opt-if flags.").is_empty());
+    }
+
+    // ── Multiple clauses ─────────────────────────────────────────────
+
+    #[test]
+    fn multiple_trigger_clauses_in_order() {
+        let desc = "Formats code. Use when editing Rust. Also use if CI reports lint errors.";
+        assert_eq!(
+            texts(desc),
+            ["Use when editing Rust", "use if CI reports lint errors"]
+        );
+    }
+
+    #[test]
+    fn one_clause_per_sentence() {
+        assert_eq!(
+            texts("Use when editing, use when reviewing."),
+            ["Use when editing, use when reviewing"]
+        );
+    }
+
+    // ── Quoted examples ──────────────────────────────────────────────
+
+    #[test]
+    fn quoted_phrases_do_not_count() {
+        assert!(texts(r#"Explains phrases like "use when" in docs."#).is_empty());
+        assert!(texts("Explains phrases like “use when ready” in docs.").is_empty());
+        assert!(texts("Explains the `use when` idiom.").is_empty());
+        assert!(texts("Explains phrases like 'use when ready' in docs.").is_empty());
+    }
+
+    #[test]
+    fn quoted_example_then_real_trigger() {
+        assert_eq!(
+            texts(r#"Rewrites "use when" phrasing. Use when editing skills."#),
+            ["Use when editing skills"]
+        );
+    }
+
+    #[test]
+    fn quoted_terminator_does_not_split_sentence() {
+        assert_eq!(
+            texts(r#"Use when the user says "stop. now" loudly."#),
+            [r#"Use when the user says "stop. now" loudly"#]
+        );
+    }
+
+    #[test]
+    fn apostrophes_are_not_quotes() {
+        assert_eq!(
+            texts("Reads the user's files. Use when they're editing."),
+            ["Use when they're editing"]
+        );
+    }
+
+    #[test]
+    fn unclosed_quote_is_ignored() {
+        assert_eq!(
+            texts(r#"Takes "raw input. Use when parsing."#),
+            ["Use when parsing"]
+        );
+    }
+
+    // ── Capability statements ────────────────────────────────────────
+
+    #[test]
+    fn capabilities_exclude_trigger_clauses() {
+        let parts = split_description(
+            "Processes PDF files and generates reports. Use when working with documents.",
+        );
+        assert_eq!(
+            parts.capabilities,
+            ["Processes PDF files and generates reports"]
+        );
+        assert_eq!(parts.triggers.len(), 1);
+    }
+
+    #[test]
+    fn capability_prefix_before_trailing_conditional() {
+        let parts = split_description("Formats Rust code, when asked.");
+        assert_eq!(parts.capabilities, ["Formats Rust code"]);
+        assert_eq!(parts.triggers[0].text, "when asked");
+    }
+
+    #[test]
+    fn no_trigger_keeps_all_sentences_as_capabilities() {
+        let parts = split_description("Processes files. Generates reports.");
+        assert_eq!(parts.capabilities, ["Processes files", "Generates reports"]);
+        assert!(parts.triggers.is_empty());
+        assert!(!has_trigger("Processes files. Generates reports."));
+    }
+
+    #[test]
+    fn empty_description_has_no_parts() {
+        assert_eq!(split_description(""), DescriptionParts::default());
+    }
+}
@@ -1,19 +1,27 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::LazyLock;
 
 use regex::Regex;
 use serde_yaml_ng::Value;
-use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::{is_nfkc_quick, IsNormalized, UnicodeNormalization};
 
 use crate::diagnostics::{
-    Diagnostic, Severity, ValidationTarget, E000, E001, E002, E003, E004, E005, E006, E007, E009,
-    E010, E011, E012, E013, E014, E015, E016, E017, E018, W001, W002,
+    caret_snippet, escape_char, length_exceeded, Diagnostic, Severity, ValidationTarget, E000,
+    E001, E002, E003, E004, E005, E006, E007, E009, E010, E011, E012, E013, E014, E015, E016, E017,
+    E018, E019, E020, E021, E022, E023, I006, SNIPPET_CONTEXT, W001, W002, W003, W005, W006, W007,
+    W008, W009, W010, W011, W012, W013, W014, W015,
 };
-use crate::fs_util::{is_regular_dir, is_regular_file};
+use crate::fs_util::{
+    file_id, is_case_only_mismatch, is_dir_following, is_regular_file, on_disk_name, FileId,
+};
+use crate::models::SkillProperties;
 use crate::parser::{
-    find_skill_md, parse_frontmatter, read_file_checked, CLAUDE_CODE_KEYS, KNOWN_KEYS,
+    find_skill_md, parse_frontmatter, read_file_checked, skill_md_names, CLAUDE_CODE_KEYS,
+    KNOWN_KEYS, MAX_FILE_SIZE,
 };
+use crate::placeholders::{self, PlaceholderPolicy};
 
 /// A warning collected during skill discovery when a path cannot be read or parsed.
 #[derive(Debug, Clone)]
@@ -25,7 +33,7 @@ pub struct DiscoveryWarning {
 }
 
 /// Reserved words that must not appear as hyphen-delimited segments in a skill name.
-const RESERVED_WORDS: &[&str] = &["anthropic", "claude"];
+pub(crate) const RESERVED_WORDS: &[&str] = &["anthropic", "claude"];
 
 /// Regex for detecting XML/HTML tags in strings.
 ///
@@ -71,10 +79,24 @@ fn collapse_hyphens(s: &str) -> String {
     result
 }
 
+/// NFKC form of `s`, borrowed when `s` is already normalized.
+///
+/// The quick check settles ASCII and most other names without allocating.
+fn nfkc(s: &str) -> Cow<'_, str> {
+    match is_nfkc_quick(s.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(s),
+        IsNormalized::No | IsNormalized::Maybe => Cow::Owned(s.nfkc().collect()),
+    }
+}
+
 /// Validate a skill name after NFKC normalization.
+///
+/// The name is normalized once and every check reads that one string.
+/// Suggestions are only built for diagnostics that are emitted.
 fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    let normalized: String = name.nfkc().collect();
+    let normalized = nfkc(name);
+    let normalized = normalized.as_ref();
 
     // 1. Non-empty.
     if normalized.is_empty() {
@@ -84,8 +106,29 @@ fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
         return diags;
     }
 
+    // One pass over the characters: length, uppercase, and invalid
+    // characters. Invalid characters get one E003 each; uppercase gets a
+    // single E003 for the whole name.
+    let mut name_len = 0;
+    let mut invalid_chars: Vec<(usize, char)> = Vec::new();
+    let mut has_uppercase = false;
+    for (i, c) in normalized.chars().enumerate() {
+        name_len += 1;
+        if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+            continue;
+        }
+        if c.is_alphabetic() && !c.is_uppercase() {
+            continue;
+        }
+        if c.is_uppercase() {
+            has_uppercase = true;
+        } else {
+            invalid_chars.push((i, c));
+        }
+    }
+
     // 2. Max length.
-    if normalized.chars().count() > 64 {
+    if name_len > 64 {
         // Find the last hyphen at or before position 64 to truncate cleanly.
         let truncated: String = {
             let s: String = normalized.chars().take(64).collect();
@@ -96,31 +139,13 @@ fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
             }
         };
         diags.push(
-            Diagnostic::new(Severity::Error, E002, "name exceeds 64 characters")
+            Diagnostic::new(Severity::Error, E002, length_exceeded("name", name_len, 64))
                 .with_field("name")
                 .with_suggestion(format!("Truncate to: '{truncated}'")),
         );
     }
 
     // 3. Character validation: a-z, 0-9, hyphen, or alphabetic non-uppercase.
-    //    Collect invalid chars and uppercase chars separately so we can emit
-    //    one E003 diagnostic per category rather than one per character.
-    let mut invalid_chars: Vec<char> = Vec::new();
-    let mut has_uppercase = false;
-    for c in normalized.chars() {
-        if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
-            continue;
-        }
-        if c.is_alphabetic() && !c.is_uppercase() {
-            continue;
-        }
-        if c.is_uppercase() {
-            has_uppercase = true;
-        } else {
-            invalid_chars.push(c);
-        }
-    }
-    // Emit one diagnostic for uppercase characters.
     if has_uppercase {
         let lowered = normalized.to_lowercase();
         diags.push(
@@ -129,15 +154,17 @@ fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
                 .with_suggestion(format!("Use lowercase: '{lowered}'")),
         );
     }
-    // Emit one diagnostic per truly invalid (non-uppercase) character.
-    for c in invalid_chars {
+    // Emit one diagnostic per truly invalid (non-uppercase) character,
+    // with a snippet pointing at it.
+    for (i, c) in invalid_chars {
         diags.push(
             Diagnostic::new(
                 Severity::Error,
                 E003,
-                format!("name contains invalid character: '{c}'"),
+                format!("name contains invalid character: '{}'", escape_char(c)),
             )
-            .with_field("name"),
+            .with_field("name")
+            .with_snippet(caret_snippet(normalized, i, SNIPPET_CONTEXT)),
         );
     }
 
@@ -159,7 +186,7 @@ fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
 
     // 6. No consecutive hyphens.
     if normalized.contains("--") {
-        let collapsed = collapse_hyphens(&normalized);
+        let collapsed = collapse_hyphens(normalized);
         diags.push(
             Diagnostic::new(Severity::Error, E006, "name contains consecutive hyphens")
                 .with_field("name")
@@ -182,27 +209,80 @@ fn validate_name(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
     }
 
     // 8. Directory name match.
-    if let Some(dir) = dir {
-        if let Some(dir_name) = dir.file_name().and_then(|n| n.to_str()) {
-            let dir_normalized: String = dir_name.nfkc().collect();
-            if normalized != dir_normalized {
-                diags.push(
-                    Diagnostic::new(
-                        Severity::Error,
-                        E009,
-                        format!(
-                            "name '{normalized}' does not match directory name '{dir_normalized}'"
-                        ),
-                    )
-                    .with_field("name"),
-                );
-            }
-        }
+    if let Some(d) = dir.and_then(|dir| dir_name_mismatch(normalized, dir)) {
+        diags.push(d);
     }
 
     diags
 }
 
+/// Compare a normalized skill name with the name of `dir` as stored on disk.
+///
+/// The on-disk spelling is used because a case-insensitive filesystem opens
+/// `My-Skill` through the path `my-skill`, so the path alone would let a
+/// case-only mismatch pass there and fail on a case-sensitive filesystem.
+/// A case-only difference gets E023, any other difference E009.
+fn dir_name_mismatch(normalized: &str, dir: &Path) -> Option<Diagnostic> {
+    let dir_name = on_disk_name(dir)?;
+    let dir_normalized = nfkc(&dir_name);
+    if normalized == dir_normalized {
+        return None;
+    }
+    let diag = if is_case_only_mismatch(normalized, &dir_normalized) {
+        Diagnostic::new(
+            Severity::Error,
+            E023,
+            format!(
+                "name '{normalized}' differs from directory name '{dir_normalized}' only in letter case"
+            ),
+        )
+        .with_suggestion(format!(
+            "Rename the directory to '{normalized}' or set the name to match it exactly; case-insensitive filesystems hide this mismatch"
+        ))
+    } else {
+        Diagnostic::new(
+            Severity::Error,
+            E009,
+            format!("name '{normalized}' does not match directory name '{dir_normalized}'"),
+        )
+    };
+    Some(diag.with_field("name"))
+}
+
+/// Warn (W014) when `description` only restates `name`.
+///
+/// Both are compared as lowercase words, so `processing-pdfs`,
+/// `Processing PDFs.`, and `processing pdfs skill` all echo the name
+/// `processing-pdfs`. Such a description gives an agent nothing to decide
+/// when to activate the skill.
+fn validate_description_echo(name: &str, description: &str) -> Option<Diagnostic> {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let name_words = words(name);
+    let mut desc_words = words(description);
+    if desc_words.last().is_some_and(|w| w == "skill") && name_words.last() != desc_words.last() {
+        desc_words.pop();
+    }
+    if name_words.is_empty() || name_words != desc_words {
+        return None;
+    }
+    Some(
+        Diagnostic::new(
+            Severity::Warning,
+            W014,
+            format!("description only restates the name '{name}'"),
+        )
+        .with_field("description")
+        .with_suggestion(
+            "Describe what the skill does and when to use it, e.g. \"Extracts text from PDF files. Use when ...\"",
+        ),
+    )
+}
+
 /// Validate a skill description.
 fn validate_description(description: &str) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
@@ -215,10 +295,15 @@ fn validate_description(description: &str) -> Vec<Diagnostic> {
         return diags;
     }
 
-    if description.chars().count() > 1024 {
+    let description_len = description.chars().count();
+    if description_len > 1024 {
         diags.push(
-            Diagnostic::new(Severity::Error, E011, "description exceeds 1024 characters")
-                .with_field("description"),
+            Diagnostic::new(
+                Severity::Error,
+                E011,
+                length_exceeded("description", description_len, 1024),
+            )
+            .with_field("description"),
         );
     }
 
@@ -237,12 +322,13 @@ fn validate_description(description: &str) -> Vec<Diagnostic> {
 fn validate_compatibility(compatibility: &str) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
-    if compatibility.chars().count() > 500 {
+    let compatibility_len = compatibility.chars().count();
+    if compatibility_len > 500 {
         diags.push(
             Diagnostic::new(
                 Severity::Error,
                 E013,
-                "compatibility exceeds 500 characters",
+                length_exceeded("compatibility", compatibility_len, 500),
             )
             .with_field("compatibility"),
         );
@@ -251,6 +337,232 @@ fn validate_compatibility(compatibility: &str) -> Vec<Diagnostic> {
     diags
 }
 
+/// Substrings (matched case-insensitively) that name Claude Code as a target.
+const CLAUDE_CODE_MARKERS: &[&str] = &["claude-code", "claude code", "claudecode"];
+
+/// Substrings (matched case-insensitively) that name a target other than
+/// Claude Code: other Claude surfaces and model-only requirements.
+const OTHER_TARGET_MARKERS: &[&str] = &[
+    "claude.ai",
+    "claude desktop",
+    "claude-desktop",
+    "claude api",
+    "anthropic api",
+    "claude 3",
+    "claude-3",
+];
+
+/// Check `compatibility` against Claude Code extension usage and the target.
+///
+/// A conservative keyword heuristic: the string is only considered to
+/// contradict extension fields when it names some target from
+/// [`OTHER_TARGET_MARKERS`] and none from [`CLAUDE_CODE_MARKERS`]. Strings
+/// that name no known target (e.g., "Requires Python 3.10") never match.
+fn validate_compatibility_consistency(
+    compatibility: &str,
+    metadata: &HashMap<String, Value>,
+    target: ValidationTarget,
+) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    let lower = compatibility.to_lowercase();
+    let names_claude_code = CLAUDE_CODE_MARKERS.iter().any(|m| lower.contains(m));
+    let names_other = OTHER_TARGET_MARKERS.iter().any(|m| lower.contains(m));
+
+    let mut extension_keys: Vec<&str> = CLAUDE_CODE_KEYS
+        .iter()
+        .copied()
+        .filter(|k| metadata.contains_key(*k))
+        .collect();
+    extension_keys.sort_unstable();
+
+    if names_other && !names_claude_code && !extension_keys.is_empty() {
+        diags.push(
+            Diagnostic::new(
+                Severity::Warning,
+                W003,
+                format!(
+                    "compatibility does not mention Claude Code, but Claude Code extension fields are used: {}",
+                    extension_keys.join(", ")
+                ),
+            )
+            .with_field("compatibility")
+            .with_suggestion("Add Claude Code to `compatibility` or remove the extension fields"),
+        );
+    }
+
+    if names_claude_code && target == ValidationTarget::Standard {
+        diags.push(
+            Diagnostic::new(
+                Severity::Info,
+                I006,
+                "compatibility names Claude Code, but validation uses the standard target",
+            )
+            .with_field("compatibility")
+            .with_suggestion("Validate with `--target claude-code`"),
+        );
+    }
+
+    diags
+}
+
+/// Default size cap for the serialized `metadata:` block, in bytes.
+pub const METADATA_MAX_BYTES: usize = 2048;
+
+/// Deepest nesting allowed for a `metadata:` value before W008.
+const METADATA_MAX_DEPTH: usize = 2;
+
+/// Check the shape of the free-form `metadata:` frontmatter block.
+///
+/// The block must be a mapping (E019) with string keys (E020). Values nested
+/// more than 2 levels deep (W008) and a block larger than `max_bytes` when
+/// serialized as YAML (W009) are warnings: harnesses that inject frontmatter
+/// into context pay for every byte. A `priority` that is not an integer is
+/// also a warning (W012); it is treated as 0. So is an `authors` value that
+/// is not a list of names, or one that leaves out a scalar `author` (W015).
+/// [`validate`] uses
+/// [`METADATA_MAX_BYTES`]; call this directly to apply a different cap.
+#[must_use]
+pub fn validate_metadata_block(block: &Value, max_bytes: usize) -> Vec<Diagnostic> {
+    let Value::Mapping(mapping) = block else {
+        return vec![Diagnostic::new(
+            Severity::Error,
+            E019,
+            format!("`metadata` must be a mapping, not {}", yaml_kind(block)),
+        )
+        .with_field("metadata")
+        .with_suggestion("Nest values under `metadata:` as `key: value` pairs")];
+    };
+
+    let mut diags = Vec::new();
+    for (key, value) in mapping {
+        let Some(key) = key.as_str() else {
+            let rendered = serde_yaml_ng::to_string(key).unwrap_or_default();
+            diags.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    E020,
+                    format!(
+                        "`metadata` key `{}` must be a string, not {}",
+                        rendered.trim_end(),
+                        yaml_kind(key)
+                    ),
+                )
+                .with_field("metadata"),
+            );
+            continue;
+        };
+        if key == "priority" && value.as_i64().is_none() {
+            let kind = if value.is_number() {
+                "a non-integer number"
+            } else {
+                yaml_kind(value)
+            };
+            diags.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    W012,
+                    format!("`metadata.priority` must be an integer, not {kind}"),
+                )
+                .with_field("metadata")
+                .with_suggestion("Use a whole number, such as `priority: 10`"),
+            );
+        }
+        let depth = nesting_depth(value);
+        if depth > METADATA_MAX_DEPTH {
+            diags.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    W008,
+                    format!(
+                        "`metadata.{key}` is nested {depth} levels deep \
+                         (max {METADATA_MAX_DEPTH})"
+                    ),
+                )
+                .with_field("metadata")
+                .with_suggestion("Flatten the value or move it into a reference file"),
+            );
+        }
+    }
+
+    diags.extend(validate_authors(mapping));
+
+    let size = serde_yaml_ng::to_string(block).map_or(0, |s| s.len());
+    if size > max_bytes {
+        diags.push(
+            Diagnostic::new(
+                Severity::Warning,
+                W009,
+                format!("`metadata` is {size} bytes when serialized (max {max_bytes})"),
+            )
+            .with_field("metadata")
+            .with_suggestion("Move bulky metadata into a reference file"),
+        );
+    }
+    diags
+}
+
+/// Warn (W015) when `metadata.authors` is not a list of names, or when a
+/// scalar `metadata.author` is not one of them.
+fn validate_authors(mapping: &serde_yaml_ng::Mapping) -> Vec<Diagnostic> {
+    let Some(authors) = mapping.get("authors") else {
+        return vec![];
+    };
+    let names: Option<Vec<&str>> = authors
+        .as_sequence()
+        .and_then(|items| items.iter().map(|v| v.as_str().map(str::trim)).collect());
+    let Some(names) = names else {
+        let kind = if authors.is_sequence() {
+            "a list with non-string entries"
+        } else {
+            yaml_kind(authors)
+        };
+        return vec![Diagnostic::new(
+            Severity::Warning,
+            W015,
+            format!("`metadata.authors` must be a list of names, not {kind}"),
+        )
+        .with_field("metadata")
+        .with_suggestion("Use a YAML list, such as `authors: [Jane Doe, John Roe]`")];
+    };
+    match mapping.get("author").and_then(Value::as_str).map(str::trim) {
+        Some(author) if !author.is_empty() && !names.contains(&author) => {
+            vec![Diagnostic::new(
+                Severity::Warning,
+                W015,
+                format!("`metadata.author` '{author}' is not listed in `metadata.authors`"),
+            )
+            .with_field("metadata")
+            .with_suggestion(format!(
+                "Add '{author}' to `metadata.authors`, or remove `metadata.author`"
+            ))]
+        }
+        _ => vec![],
+    }
+}
+
+/// Levels of sequence or mapping nesting in a YAML value (0 for scalars).
+fn nesting_depth(value: &Value) -> usize {
+    match value {
+        Value::Sequence(items) => 1 + items.iter().map(nesting_depth).max().unwrap_or(0),
+        Value::Mapping(mapping) => 1 + mapping.values().map(nesting_depth).max().unwrap_or(0),
+        Value::Tagged(tagged) => nesting_depth(&tagged.value),
+        _ => 0,
+    }
+}
+
+/// Article and noun describing the kind of a YAML value, for messages.
+fn yaml_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Sequence(_) => "a list",
+        Value::Mapping(_) => "a mapping",
+        Value::Tagged(_) => "a tagged value",
+    }
+}
+
 /// Validate skill metadata against the Anthropic specification.
 ///
 /// Expects raw `parse_frontmatter` output — the full `HashMap` before
@@ -310,10 +622,20 @@ pub fn validate_metadata_with_target(
         ),
     }
 
+    // Flag a description that only echoes the name.
+    if let (Some(Value::String(name)), Some(Value::String(desc))) =
+        (metadata.get("name"), metadata.get("description"))
+    {
+        diags.extend(validate_description_echo(name, desc));
+    }
+
     // 3. Validate compatibility if present.
     if let Some(val) = metadata.get("compatibility") {
         match val {
-            Value::String(s) => diags.extend(validate_compatibility(s)),
+            Value::String(s) => {
+                diags.extend(validate_compatibility(s));
+                diags.extend(validate_compatibility_consistency(s, metadata, target));
+            }
             _ => diags.push(
                 Diagnostic::new(Severity::Error, E016, "`compatibility` must be a string")
                     .with_field("compatibility"),
@@ -321,12 +643,23 @@ pub fn validate_metadata_with_target(
         }
     }
 
-    // 4. Warn about unexpected metadata keys (sorted for deterministic output).
+    // 4. Validate the free-form `metadata:` block if present.
+    if let Some(block) = metadata.get("metadata") {
+        diags.extend(validate_metadata_block(block, METADATA_MAX_BYTES));
+    }
+
+    // 5. Warn about unexpected metadata keys (sorted for deterministic output).
     if target != ValidationTarget::Permissive {
         let known = known_keys_for(target);
         let mut keys: Vec<_> = metadata.keys().collect();
         keys.sort();
         for key in keys {
+            // A `metadata:` mapping is the spec's free-form extension point;
+            // conventions such as `metadata.deprecated` live inside it. Its
+            // shape is checked above.
+            if key == "metadata" {
+                continue;
+            }
             if !known.contains(&key.as_str()) {
                 diags.push(
                     Diagnostic::new(
@@ -356,6 +689,19 @@ pub fn validate(dir: &Path) -> Vec<Diagnostic> {
 /// Returns a list of diagnostics (empty = valid).
 #[must_use]
 pub fn validate_with_target(dir: &Path, target: ValidationTarget) -> Vec<Diagnostic> {
+    validate_with_policy(dir, target, &PlaceholderPolicy::default())
+}
+
+/// Validate a skill directory, treating `${NAME}` placeholders according to
+/// `policy` (see [`crate::placeholders`]).
+///
+/// Returns a list of diagnostics (empty = valid).
+#[must_use]
+pub fn validate_with_policy(
+    dir: &Path,
+    target: ValidationTarget,
+    policy: &PlaceholderPolicy,
+) -> Vec<Diagnostic> {
     // 1. Find SKILL.md.
     let path = match find_skill_md(dir) {
         Some(p) => p,
@@ -368,55 +714,389 @@ pub fn validate_with_target(dir: &Path, target: ValidationTarget) -> Vec<Diagnos
         Err(e) => return vec![Diagnostic::new(Severity::Error, E000, e.to_string())],
     };
 
-    // 3. Parse frontmatter.
-    let (metadata, body) = match parse_frontmatter(&content) {
+    // 3. Check the file name's casing, then validate the content in place.
+    let mut diags: Vec<Diagnostic> = validate_skill_md_casing(dir).into_iter().collect();
+    diags.extend(validate_content_in_dir(&content, Some(dir), target, policy));
+    diags
+}
+
+/// Validate `content` as the `SKILL.md` of `dir`, the way
+/// [`validate_with_policy`] validates the file on disk.
+///
+/// Checks the content's size and layers shared defaults from the nearest
+/// `_defaults.yml` above `dir`. Used for content that does not come from
+/// the file itself, such as `validate --stdin --stdin-path`. Without `dir`,
+/// no defaults apply.
+#[must_use]
+pub fn validate_content_in_dir(
+    content: &str,
+    dir: Option<&Path>,
+    target: ValidationTarget,
+    policy: &PlaceholderPolicy,
+) -> Vec<Diagnostic> {
+    // 1. Check the size.
+    let mut diags: Vec<Diagnostic> = validate_file_size(content.len() as u64)
+        .into_iter()
+        .collect();
+
+    // 2. Layer shared defaults from the nearest `_defaults.yml`.
+    let defaults = match dir.and_then(crate::defaults::find_defaults) {
+        Some(path) => crate::defaults::load_defaults(&path).unwrap_or_else(|e| {
+            diags.push(
+                Diagnostic::new(
+                    Severity::Warning,
+                    W007,
+                    format!("ignoring {}: {e}", path.display()),
+                )
+                .with_suggestion("Make it a YAML mapping of frontmatter fields"),
+            );
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+
+    // 3. Validate the content.
+    diags.extend(validate_content_with_policy(
+        content, dir, target, &defaults, policy,
+    ));
+    diags
+}
+
+/// Percentage of [`MAX_FILE_SIZE`] above which a `SKILL.md` gets W013.
+///
+/// Gives authors notice to split the file before it grows past the limit
+/// and fails to load at all.
+pub const FILE_SIZE_WARNING_PERCENT: u64 = 80;
+
+/// Warn (W013) when a `SKILL.md` of `size` bytes is close to the size limit.
+fn validate_file_size(size: u64) -> Option<Diagnostic> {
+    if size * 100 <= MAX_FILE_SIZE * FILE_SIZE_WARNING_PERCENT {
+        return None;
+    }
+    let percent = size * 100 / MAX_FILE_SIZE;
+    Some(
+        Diagnostic::new(
+            Severity::Warning,
+            W013,
+            format!("SKILL.md is {size} bytes, {percent}% of the {MAX_FILE_SIZE}-byte size limit"),
+        )
+        .with_suggestion(
+            "Split detailed content into referenced files before it reaches the limit",
+        ),
+    )
+}
+
+/// Check that the skill file is named `SKILL.md`.
+///
+/// Returns W010 for a lone `skill.md` and E021 when both files exist. Uses
+/// the directory listing, so a `skill.md` is caught on case-insensitive
+/// filesystems too, where it would otherwise pass locally and break on
+/// case-sensitive CI.
+fn validate_skill_md_casing(dir: &Path) -> Option<Diagnostic> {
+    let names = skill_md_names(dir)?;
+    if names.uppercase && names.lowercase {
+        Some(
+            Diagnostic::new(
+                Severity::Error,
+                E021,
+                "both SKILL.md and skill.md exist; only SKILL.md is read",
+            )
+            .with_suggestion("Merge skill.md into SKILL.md and delete skill.md"),
+        )
+    } else if names.lowercase {
+        Some(
+            Diagnostic::new(
+                Severity::Warning,
+                W010,
+                "skill file is named skill.md; tools expect SKILL.md",
+            )
+            .with_suggestion("Rename skill.md to SKILL.md"),
+        )
+    } else {
+        None
+    }
+}
+
+/// Validate `SKILL.md` content held in memory.
+///
+/// Runs the same checks as [`validate_with_target`] without touching the
+/// filesystem. `dir` is only used to check that the name matches the
+/// directory name; pass `None` to skip that check.
+#[must_use]
+pub fn validate_content(
+    content: &str,
+    dir: Option<&Path>,
+    target: ValidationTarget,
+) -> Vec<Diagnostic> {
+    validate_content_with_defaults(content, dir, target, &HashMap::new())
+}
+
+/// Validate frontmatter text held in memory, such as an editor buffer.
+///
+/// `content` is either a whole `SKILL.md` or just the YAML between the `---`
+/// delimiters. Nothing is read from disk and there is no directory, so the
+/// name/directory check (E009) is skipped; a parse failure is still E000.
+#[must_use]
+pub fn validate_frontmatter_str(content: &str, target: ValidationTarget) -> Vec<Diagnostic> {
+    let bare = content.trim_start_matches('\u{feff}');
+    if bare.trim().is_empty() || bare.starts_with("---") {
+        return validate_content(content, None, target);
+    }
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let sep = if content.ends_with('\n') { "" } else { eol };
+    validate_content(&format!("---{eol}{content}{sep}---{eol}"), None, target)
+}
+
+/// Body line count above which a body gets W002.
+pub const MAX_BODY_LINES: usize = 500;
+
+/// Number of body lines as W002 counts them: after the blank line that
+/// usually follows the closing `---` is trimmed.
+pub(crate) fn body_line_count(body: &str) -> usize {
+    crate::parser::trim_body_separator(body).lines().count()
+}
+
+/// Estimated token count above which a body gets W011.
+///
+/// Complements the [`MAX_BODY_LINES`] check (W002): dense lines can make a body
+/// expensive to load well before it is long.
+pub const BODY_MAX_TOKENS: usize = 5000;
+
+/// Validate `SKILL.md` content with shared frontmatter defaults layered in.
+///
+/// Keys in `defaults` fill in frontmatter fields the content leaves out
+/// before the metadata is checked (see [`crate::defaults`]). The content
+/// itself is not changed.
+#[must_use]
+pub fn validate_content_with_defaults(
+    content: &str,
+    dir: Option<&Path>,
+    target: ValidationTarget,
+    defaults: &HashMap<String, Value>,
+) -> Vec<Diagnostic> {
+    // 1. Reject empty content, then parse frontmatter and layer defaults.
+    //    Both an empty file and tab indentation are reported instead of the
+    //    parse error, which explains them poorly.
+    if content.trim().is_empty() {
+        return vec![Diagnostic::new(Severity::Error, E000, "SKILL.md is empty")
+            .with_suggestion("Add frontmatter with `name` and `description` between `---` lines")];
+    }
+    let tab_diags = check_tab_indentation(content);
+    let (mut metadata, body) = match parse_frontmatter(content) {
         Ok(result) => result,
+        Err(_) if !tab_diags.is_empty() => return tab_diags,
         Err(e) => return vec![Diagnostic::new(Severity::Error, E000, e.to_string())],
     };
+    crate::defaults::apply_defaults(&mut metadata, defaults);
 
-    // 4. Validate metadata.
-    let mut diags = validate_metadata_with_target(&metadata, Some(dir), target);
+    // 2. Validate metadata.
+    let mut diags = tab_diags;
+    diags.extend(validate_metadata_with_target(&metadata, dir, target));
 
-    // 5. Body-length warning.
-    let line_count = body.lines().count();
-    if line_count > 500 {
+    // 3. Body-length warnings.
+    let line_count = body_line_count(&body);
+    if line_count > MAX_BODY_LINES {
         diags.push(
             Diagnostic::new(
                 Severity::Warning,
                 W002,
-                format!("body exceeds 500 lines ({line_count} lines)"),
+                format!("body exceeds {MAX_BODY_LINES} lines ({line_count} lines)"),
             )
             .with_field("body"),
         );
     }
+    let tokens = crate::prompt::estimate_tokens(&body);
+    if tokens > BODY_MAX_TOKENS {
+        diags.push(
+            Diagnostic::new(
+                Severity::Warning,
+                W011,
+                format!("body exceeds {BODY_MAX_TOKENS} estimated tokens (~{tokens} tokens)"),
+            )
+            .with_field("body")
+            .with_suggestion("Move detailed material into referenced files"),
+        );
+    }
+
+    // 4. Line endings.
+    diags.extend(check_line_endings(content));
+
+    diags
+}
 
+/// Validate `SKILL.md` content with shared defaults, treating `${NAME}`
+/// placeholders according to `policy`.
+///
+/// Substitutions are applied to the whole content first. When the policy
+/// allows placeholders, the remaining ones are masked before validation
+/// (see [`crate::placeholders::mask_placeholders`]) and listed in an I011
+/// note; a name containing a placeholder skips the directory-name check
+/// (E009), since the deployed name is not known yet.
+#[must_use]
+pub fn validate_content_with_policy(
+    content: &str,
+    dir: Option<&Path>,
+    target: ValidationTarget,
+    defaults: &HashMap<String, Value>,
+    policy: &PlaceholderPolicy,
+) -> Vec<Diagnostic> {
+    if policy.is_noop() {
+        return validate_content_with_defaults(content, dir, target, defaults);
+    }
+    let content = placeholders::substitute_placeholders(content, &policy.substitutions);
+    let found = placeholders::find_placeholders(&content);
+    if !policy.allow || found.is_empty() {
+        return validate_content_with_defaults(&content, dir, target, defaults);
+    }
+
+    let templated_name = parse_frontmatter(&content).is_ok_and(|(metadata, _)| {
+        matches!(metadata.get("name"), Some(Value::String(name)) if placeholders::has_placeholders(name))
+    });
+    let masked = placeholders::mask_placeholders(&content, policy.nominal_length);
+    let dir = if templated_name { None } else { dir };
+    let mut diags = validate_content_with_defaults(&masked, dir, target, defaults);
+    diags.extend(placeholders::placeholder_note(&found));
     diags
 }
 
+/// Report frontmatter lines indented with tabs (E022), one per line.
+///
+/// YAML forbids tab indentation; the fix replaces each leading tab with the
+/// indent width the rest of the frontmatter uses.
+fn check_tab_indentation(content: &str) -> Vec<Diagnostic> {
+    let lines = crate::parser::tab_indented_lines(content);
+    if lines.is_empty() {
+        return vec![];
+    }
+    let width = crate::parser::frontmatter_indent_width(content);
+    lines
+        .into_iter()
+        .map(|i| {
+            Diagnostic::new(
+                Severity::Error,
+                E022,
+                format!(
+                    "frontmatter line {} is indented with a tab (YAML requires spaces)",
+                    i + 1
+                ),
+            )
+            .with_suggestion(format!("Indent with {width} spaces per tab"))
+        })
+        .collect()
+}
+
+/// Warn when content uses CRLF or mixed line endings (W006).
+///
+/// Parsing tolerates `\r\n`, but editors and diff tools treat mixed endings
+/// inconsistently, so the canonical form is LF only.
+fn check_line_endings(content: &str) -> Vec<Diagnostic> {
+    let crlf = content.matches("\r\n").count();
+    if crlf == 0 {
+        return vec![];
+    }
+    let lf = content.matches('\n').count() - crlf;
+    let message = if lf == 0 {
+        "SKILL.md uses CRLF line endings".to_string()
+    } else {
+        format!("SKILL.md has mixed line endings ({crlf} CRLF, {lf} LF)")
+    };
+    vec![Diagnostic::new(Severity::Warning, W006, message)
+        .with_suggestion("Normalize line endings to LF")]
+}
+
+/// Check that a deprecated skill's `metadata.superseded-by` names a known skill.
+///
+/// `known_names` are the names of the other skills validated in the same
+/// run. Returns a W005 warning when the successor is not among them; skills
+/// without `superseded-by` produce no diagnostics.
+#[must_use]
+pub fn validate_superseded_by(
+    properties: &SkillProperties,
+    known_names: &[&str],
+) -> Vec<Diagnostic> {
+    match properties.superseded_by() {
+        Some(successor) if !known_names.contains(&successor) => vec![Diagnostic::new(
+            Severity::Warning,
+            W005,
+            format!("superseded-by names unknown skill '{successor}'"),
+        )
+        .with_field("metadata")
+        .with_suggestion("Point superseded-by at a skill in this collection")],
+        _ => vec![],
+    }
+}
+
 /// Maximum recursion depth for skill discovery.
 const MAX_DISCOVERY_DEPTH: usize = 10;
 
+/// Name of the marker file that flags a skill directory as a draft.
+///
+/// Recursive discovery skips draft skills unless
+/// [`DiscoveryOptions::include_drafts`] is set. Being a hidden file, the
+/// marker is not copied into assembled plugins.
+pub const DRAFT_MARKER: &str = ".aigent-draft";
+
+/// Options for recursive skill discovery.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiscoveryOptions {
+    /// Include skills marked as drafts (see [`DRAFT_MARKER`]).
+    pub include_drafts: bool,
+    /// Descend into symlinked directories. Each directory is visited at
+    /// most once, so symlink cycles terminate.
+    pub follow_symlinks: bool,
+}
+
+/// Returns `true` if `dir` contains a [`DRAFT_MARKER`] file.
+#[must_use]
+pub fn is_draft(dir: &Path) -> bool {
+    is_regular_file(&dir.join(DRAFT_MARKER))
+}
+
 /// Discover all skill directories under a root path.
 ///
 /// Walks the directory tree recursively, finding all `SKILL.md` or
 /// `skill.md` files. Returns the parent directory of each found file.
-/// Skips hidden directories (names starting with `.`) and stops
-/// recursing beyond 10 levels of nesting.
+/// Skips hidden directories (names starting with `.`) and draft skills,
+/// and stops recursing beyond 10 levels of nesting.
 #[must_use]
 pub fn discover_skills(root: &Path) -> Vec<std::path::PathBuf> {
+    discover_skills_with(root, &DiscoveryOptions::default())
+}
+
+/// Discover skill directories with explicit options.
+///
+/// Same as [`discover_skills`], but drafts are included when
+/// `opts.include_drafts` is set.
+#[must_use]
+pub fn discover_skills_with(root: &Path, opts: &DiscoveryOptions) -> Vec<std::path::PathBuf> {
     let mut dirs = Vec::new();
-    discover_skills_recursive(root, &mut dirs, 0);
+    let mut visited = HashSet::new();
+    discover_skills_recursive(root, opts, &mut dirs, &mut visited, 0);
     dirs.sort();
     dirs
 }
 
 /// Recursive helper for `discover_skills`.
 ///
-/// Stops recursing when `depth` exceeds [`MAX_DISCOVERY_DEPTH`].
-fn discover_skills_recursive(dir: &Path, results: &mut Vec<std::path::PathBuf>, depth: usize) {
+/// Stops recursing when `depth` exceeds [`MAX_DISCOVERY_DEPTH`] or `dir`
+/// was already visited (a symlink cycle or a second link to it).
+fn discover_skills_recursive(
+    dir: &Path,
+    opts: &DiscoveryOptions,
+    results: &mut Vec<std::path::PathBuf>,
+    visited: &mut HashSet<FileId>,
+    depth: usize,
+) {
     if depth > MAX_DISCOVERY_DEPTH {
         return;
     }
+    if file_id(dir).is_some_and(|id| !visited.insert(id)) {
+        return;
+    }
 
     let entries = match std::fs::read_dir(dir) {
         Ok(e) => e,
@@ -432,30 +1112,43 @@ fn discover_skills_recursive(dir: &Path, results: &mut Vec<std::path::PathBuf>,
             if is_regular_file(&path) && (name == "SKILL.md" || name == "skill.md") {
                 has_skill_md = true;
             }
-            if is_regular_dir(&path) && !name.starts_with('.') {
+            if is_dir_following(&path, opts.follow_symlinks) && !name.starts_with('.') {
                 subdirs.push(path);
             }
         }
     }
 
-    if has_skill_md {
+    if has_skill_md && (opts.include_drafts || !is_draft(dir)) {
         results.push(dir.to_path_buf());
     }
 
     for subdir in subdirs {
-        discover_skills_recursive(&subdir, results, depth + 1);
+        discover_skills_recursive(&subdir, opts, results, visited, depth + 1);
     }
 }
 
 /// Discover skill directories, collecting warnings for paths that could not be read.
 ///
 /// Returns `(skill_paths, warnings)`. The original [`discover_skills()`] function
-/// remains unchanged for backward compatibility.
+/// remains unchanged for backward compatibility. Draft skills are skipped.
 #[must_use]
 pub fn discover_skills_verbose(root: &Path) -> (Vec<std::path::PathBuf>, Vec<DiscoveryWarning>) {
+    discover_skills_verbose_with(root, &DiscoveryOptions::default())
+}
+
+/// Discover skill directories with warnings and explicit options.
+///
+/// Same as [`discover_skills_verbose`], but drafts are included when
+/// `opts.include_drafts` is set.
+#[must_use]
+pub fn discover_skills_verbose_with(
+    root: &Path,
+    opts: &DiscoveryOptions,
+) -> (Vec<std::path::PathBuf>, Vec<DiscoveryWarning>) {
     let mut skills = Vec::new();
     let mut warnings = Vec::new();
-    discover_skills_recursive_verbose(root, &mut skills, &mut warnings, 0);
+    let mut visited = HashSet::new();
+    discover_skills_recursive_verbose(root, opts, &mut skills, &mut warnings, &mut visited, 0);
     skills.sort();
     (skills, warnings)
 }
@@ -463,8 +1156,10 @@ pub fn discover_skills_verbose(root: &Path) -> (Vec<std::path::PathBuf>, Vec<Dis
 /// Recursive helper for `discover_skills_verbose`.
 fn discover_skills_recursive_verbose(
     dir: &Path,
+    opts: &DiscoveryOptions,
     results: &mut Vec<std::path::PathBuf>,
     warnings: &mut Vec<DiscoveryWarning>,
+    visited: &mut HashSet<FileId>,
     depth: usize,
 ) {
     if depth > MAX_DISCOVERY_DEPTH {
@@ -474,6 +1169,13 @@ fn discover_skills_recursive_verbose(
         });
         return;
     }
+    if file_id(dir).is_some_and(|id| !visited.insert(id)) {
+        warnings.push(DiscoveryWarning {
+            path: dir.to_path_buf(),
+            message: "directory already visited through another path (symlink cycle?)".to_string(),
+        });
+        return;
+    }
     let entries = match std::fs::read_dir(dir) {
         Ok(e) => e,
         Err(e) => {
@@ -496,7 +1198,7 @@ fn discover_skills_recursive_verbose(
                     if is_regular_file(&path) && (name == "SKILL.md" || name == "skill.md") {
                         has_skill_md = true;
                     }
-                    if is_regular_dir(&path) && !name.starts_with('.') {
+                    if is_dir_following(&path, opts.follow_symlinks) && !name.starts_with('.') {
                         subdirs.push(path);
                     }
                 }
@@ -510,19 +1212,22 @@ fn discover_skills_recursive_verbose(
         }
     }
 
-    if has_skill_md {
+    if has_skill_md && (opts.include_drafts || !is_draft(dir)) {
         results.push(dir.to_path_buf());
     }
 
     for subdir in subdirs {
-        discover_skills_recursive_verbose(&subdir, results, warnings, depth + 1);
+        discover_skills_recursive_verbose(&subdir, opts, results, warnings, visited, depth + 1);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::DiagnosticCode;
+    use proptest::prelude::*;
     use std::fs;
+    use std::path::PathBuf;
     use tempfile::tempdir;
 
     /// Build a metadata HashMap from key-value string pairs.
@@ -656,12 +1361,52 @@ mod tests {
     }
 
     #[test]
-    fn name_contains_reserved_anthropic() {
-        let meta = make_metadata(&[("name", "my-anthropic-skill"), ("description", "desc")]);
+    fn invalid_character_carries_caret_snippet_outside_message() {
+        let meta = make_metadata(&[("name", "my_skill"), ("description", "desc")]);
         let diags = validate_metadata(&meta, None);
-        assert!(diags
+        let d = diags
             .iter()
-            .any(|d| d.message.contains("reserved word: 'anthropic'")));
+            .find(|d| d.code == E003)
+            .expect("should have E003");
+        assert_eq!(d.message, "name contains invalid character: '_'");
+        assert_eq!(d.snippet.as_deref(), Some("my_skill\n  ^"));
+        assert_eq!(
+            d.to_string(),
+            "name contains invalid character: '_'\n    my_skill\n      ^"
+        );
+    }
+
+    #[test]
+    fn invalid_character_snippet_aligns_after_wide_chars() {
+        let meta = make_metadata(&[("name", "日本語_スキル"), ("description", "desc")]);
+        let diags = validate_metadata(&meta, None);
+        let d = diags
+            .iter()
+            .find(|d| d.code == E003)
+            .expect("should have E003");
+        // Each CJK character is two columns wide.
+        assert_eq!(d.snippet.as_deref(), Some("日本語_スキル\n      ^"));
+    }
+
+    #[test]
+    fn invalid_control_character_is_escaped() {
+        let meta = make_metadata(&[("name", "my\tskill"), ("description", "desc")]);
+        let diags = validate_metadata(&meta, None);
+        assert!(
+            diags.iter().any(|d| d
+                .message
+                .starts_with("name contains invalid character: '\\t'")),
+            "expected escaped tab, got: {diags:?}"
+        );
+    }
+
+    #[test]
+    fn name_contains_reserved_anthropic() {
+        let meta = make_metadata(&[("name", "my-anthropic-skill"), ("description", "desc")]);
+        let diags = validate_metadata(&meta, None);
+        assert!(diags
+            .iter()
+            .any(|d| d.message.contains("reserved word: 'anthropic'")));
     }
 
     #[test]
@@ -686,6 +1431,45 @@ mod tests {
             .any(|d| d.message.contains("does not match directory")));
     }
 
+    #[test]
+    fn name_differs_from_directory_only_in_case() {
+        let (_parent, dir) =
+            make_skill_dir("My-Skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        let meta = make_metadata(&[("name", "my-skill"), ("description", "desc")]);
+        let diags = validate_metadata(&meta, Some(&dir));
+        let d = diags.iter().find(|d| d.code == E023).unwrap();
+        assert_eq!(
+            d.message,
+            "name 'my-skill' differs from directory name 'My-Skill' only in letter case"
+        );
+        assert!(d
+            .suggestion
+            .as_deref()
+            .unwrap()
+            .contains("Rename the directory"));
+        assert!(!diags.iter().any(|d| d.code == E009));
+    }
+
+    /// The directory is named `My-Skill` but reached through `my-skill`. On a
+    /// case-insensitive filesystem that path opens it, and the stored name
+    /// still yields E023. On a case-sensitive one the path names a different,
+    /// missing directory, which matches the skill name.
+    #[test]
+    fn case_only_mismatch_is_found_through_matching_path() {
+        let (parent, _dir) =
+            make_skill_dir("My-Skill", "---\nname: my-skill\ndescription: desc\n---\n");
+        let typed = parent.path().join("my-skill");
+        let meta = make_metadata(&[("name", "my-skill"), ("description", "desc")]);
+        let diags = validate_metadata(&meta, Some(&typed));
+        let found = diags.iter().any(|d| d.code == E023);
+        assert_eq!(
+            found,
+            crate::fs_util::is_case_insensitive(parent.path()),
+            "{diags:?}"
+        );
+        assert!(!diags.iter().any(|d| d.code == E009));
+    }
+
     #[test]
     fn name_matches_directory() {
         let (_parent, dir) =
@@ -696,6 +1480,37 @@ mod tests {
         assert!(errors.is_empty(), "expected no errors, got: {errors:?}");
     }
 
+    #[test]
+    fn description_echoing_name_warns() {
+        for desc in [
+            "processing pdfs",
+            "Processing-PDFs.",
+            "processing pdfs skill",
+        ] {
+            let meta = make_metadata(&[("name", "processing-pdfs"), ("description", desc)]);
+            let diags = validate_metadata(&meta, None);
+            let d = diags.iter().find(|d| d.code == W014).expect(desc);
+            assert_eq!(d.severity, Severity::Warning);
+            assert_eq!(
+                d.message,
+                "description only restates the name 'processing-pdfs'"
+            );
+            assert!(d.suggestion.is_some());
+        }
+    }
+
+    #[test]
+    fn genuine_description_does_not_echo_name() {
+        for desc in [
+            "Processes PDF files. Use when working with documents.",
+            "processing pdfs and forms",
+        ] {
+            let meta = make_metadata(&[("name", "processing-pdfs"), ("description", desc)]);
+            let diags = validate_metadata(&meta, None);
+            assert!(!diags.iter().any(|d| d.code == W014), "{desc}: {diags:?}");
+        }
+    }
+
     #[test]
     fn description_too_long() {
         let long_desc: String = "a".repeat(1025);
@@ -706,6 +1521,24 @@ mod tests {
             .any(|d| d.message.contains("description exceeds 1024")));
     }
 
+    #[test]
+    fn length_messages_include_actual_count() {
+        let long_desc: String = "é".repeat(1187);
+        let meta = make_metadata(&[("name", "test"), ("description", &long_desc)]);
+        let diags = validate_metadata(&meta, None);
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.message == "description exceeds 1024 characters (1187 of 1024)"),
+            "got: {diags:?}"
+        );
+
+        let long_name: String = "a".repeat(70);
+        let meta = make_metadata(&[("name", &long_name), ("description", "desc")]);
+        let diags = validate_metadata(&meta, None);
+        assert!(diags.iter().any(|d| d.message.contains("(70 of 64)")));
+    }
+
     #[test]
     fn description_exactly_1024_chars() {
         let desc: String = "a".repeat(1024);
@@ -766,6 +1599,84 @@ mod tests {
         assert!(errors.is_empty(), "expected no errors, got: {errors:?}");
     }
 
+    // ── compatibility consistency (W003, I006) ───────────────────────
+
+    /// Codes from the compatibility consistency rule for the given
+    /// compatibility string, extension-key presence, and target.
+    fn consistency_codes(
+        compat: Option<&str>,
+        with_extensions: bool,
+        target: ValidationTarget,
+    ) -> Vec<DiagnosticCode> {
+        let mut pairs = vec![("name", "test"), ("description", "desc")];
+        if let Some(c) = compat {
+            pairs.push(("compatibility", c));
+        }
+        if with_extensions {
+            pairs.push(("user-invocable", "true"));
+            pairs.push(("argument-hint", "[file]"));
+        }
+        validate_metadata_with_target(&make_metadata(&pairs), None, target)
+            .into_iter()
+            .map(|d| d.code)
+            .filter(|c| matches!(c, W003 | I006))
+            .collect()
+    }
+
+    #[test]
+    fn compatibility_consistency_matrix() {
+        use ValidationTarget::{ClaudeCode, Standard};
+        #[rustfmt::skip]
+        let cases: &[(Option<&str>, bool, ValidationTarget, &[DiagnosticCode])] = &[
+            // Absent compatibility never fires.
+            (None, true, ClaudeCode, &[]),
+            (None, false, Standard, &[]),
+            // Only non-Claude-Code targets.
+            (Some("Claude 3 only"), true, ClaudeCode, &[W003]),
+            (Some("Claude 3 only"), false, ClaudeCode, &[]),
+            (Some("claude.ai and Claude Desktop"), true, ClaudeCode, &[W003]),
+            (Some("Anthropic API"), true, Standard, &[W003]),
+            (Some("claude-3-5-sonnet"), true, ClaudeCode, &[W003]),
+            // Claude Code named: no contradiction; info on the standard target.
+            (Some("claude-code"), true, ClaudeCode, &[]),
+            (Some("claude-code"), false, Standard, &[I006]),
+            (Some("Claude Code or claude.ai"), true, ClaudeCode, &[]),
+            (Some("Claude Code or claude.ai"), true, Standard, &[I006]),
+            (Some("ClaudeCode"), false, ValidationTarget::Permissive, &[]),
+            // No known target named.
+            (Some("Requires Python 3.10"), true, ClaudeCode, &[]),
+            (Some("Requires Python 3.10"), false, Standard, &[]),
+            (Some("claude"), true, ClaudeCode, &[]),
+        ];
+        for (compat, with_ext, target, expected) in cases {
+            assert_eq!(
+                consistency_codes(*compat, *with_ext, *target),
+                *expected,
+                "compatibility={compat:?} extensions={with_ext} target={target:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn compatibility_contradiction_lists_extension_keys() {
+        let meta = make_metadata(&[
+            ("name", "test"),
+            ("description", "desc"),
+            ("compatibility", "Claude 3 only"),
+            ("user-invocable", "true"),
+            ("argument-hint", "[file]"),
+        ]);
+        let diags = validate_metadata_with_target(&meta, None, ValidationTarget::ClaudeCode);
+        let w = diags.iter().find(|d| d.code == W003).unwrap();
+        assert!(w.is_warning());
+        assert_eq!(w.field, Some("compatibility"));
+        assert!(
+            w.message.ends_with("argument-hint, user-invocable"),
+            "{}",
+            w.message
+        );
+    }
+
     #[test]
     fn unexpected_metadata_field_warning() {
         let meta = make_metadata(&[
@@ -833,6 +1744,240 @@ mod tests {
         );
     }
 
+    // ── validate_name equivalence ────────────────────────────────────
+
+    /// `validate_name` as it was before normalization was shared between
+    /// checks: the reference the optimized version must agree with.
+    fn validate_name_reference(name: &str, dir: Option<&Path>) -> Vec<Diagnostic> {
+        let mut diags = Vec::new();
+        let normalized: String = name.nfkc().collect();
+
+        // 1. Non-empty.
+        if normalized.is_empty() {
+            diags.push(
+                Diagnostic::new(Severity::Error, E001, "name must not be empty").with_field("name"),
+            );
+            return diags;
+        }
+
+        // 2. Max length.
+        let name_len = normalized.chars().count();
+        if name_len > 64 {
+            // Find the last hyphen at or before position 64 to truncate cleanly.
+            let truncated: String = {
+                let s: String = normalized.chars().take(64).collect();
+                if let Some(pos) = s.rfind('-') {
+                    s[..pos].to_string()
+                } else {
+                    s
+                }
+            };
+            diags.push(
+                Diagnostic::new(Severity::Error, E002, length_exceeded("name", name_len, 64))
+                    .with_field("name")
+                    .with_suggestion(format!("Truncate to: '{truncated}'")),
+            );
+        }
+
+        // 3. Character validation: a-z, 0-9, hyphen, or alphabetic non-uppercase.
+        //    Collect invalid chars and uppercase chars separately so we can emit
+        //    one E003 diagnostic per category rather than one per character.
+        let mut invalid_chars: Vec<(usize, char)> = Vec::new();
+        let mut has_uppercase = false;
+        for (i, c) in normalized.chars().enumerate() {
+            if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+                continue;
+            }
+            if c.is_alphabetic() && !c.is_uppercase() {
+                continue;
+            }
+            if c.is_uppercase() {
+                has_uppercase = true;
+            } else {
+                invalid_chars.push((i, c));
+            }
+        }
+        // Emit one diagnostic for uppercase characters.
+        if has_uppercase {
+            let lowered = normalized.to_lowercase();
+            diags.push(
+                Diagnostic::new(Severity::Error, E003, "name contains uppercase characters")
+                    .with_field("name")
+                    .with_suggestion(format!("Use lowercase: '{lowered}'")),
+            );
+        }
+        // Emit one diagnostic per truly invalid (non-uppercase) character,
+        // with a snippet pointing at it.
+        for (i, c) in invalid_chars {
+            diags.push(
+                Diagnostic::new(
+                    Severity::Error,
+                    E003,
+                    format!("name contains invalid character: '{}'", escape_char(c)),
+                )
+                .with_field("name")
+                .with_snippet(caret_snippet(&normalized, i, SNIPPET_CONTEXT)),
+            );
+        }
+
+        // 4. No leading hyphen.
+        if normalized.starts_with('-') {
+            diags.push(
+                Diagnostic::new(Severity::Error, E004, "name must not start with a hyphen")
+                    .with_field("name"),
+            );
+        }
+
+        // 5. No trailing hyphen.
+        if normalized.ends_with('-') {
+            diags.push(
+                Diagnostic::new(Severity::Error, E005, "name must not end with a hyphen")
+                    .with_field("name"),
+            );
+        }
+
+        // 6. No consecutive hyphens.
+        if normalized.contains("--") {
+            let collapsed = collapse_hyphens(&normalized);
+            diags.push(
+                Diagnostic::new(Severity::Error, E006, "name contains consecutive hyphens")
+                    .with_field("name")
+                    .with_suggestion(format!("Remove consecutive hyphens: '{collapsed}'")),
+            );
+        }
+
+        // 7. Reserved words — checked as hyphen-delimited segments.
+        for word in RESERVED_WORDS {
+            if normalized.split('-').any(|seg| seg == *word) {
+                diags.push(
+                    Diagnostic::new(
+                        Severity::Error,
+                        E007,
+                        format!("name contains reserved word: '{word}'"),
+                    )
+                    .with_field("name"),
+                );
+            }
+        }
+
+        // 8. Directory name match.
+        if let Some(d) = dir.and_then(|dir| dir_name_mismatch(&normalized, dir)) {
+            diags.push(d);
+        }
+
+        diags
+    }
+
+    /// Names biased towards what `validate_name` distinguishes: case,
+    /// hyphen runs, reserved words, compatibility characters, and length.
+    fn name_like() -> impl Strategy<Value = String> {
+        let piece = prop_oneof![
+            "[a-z0-9]{1,6}",
+            "[A-Z]{1,3}",
+            Just("-".to_string()),
+            Just("--".to_string()),
+            Just("claude".to_string()),
+            Just("anthropic".to_string()),
+            Just("\u{FB01}".to_string()),
+            Just("\u{FF21}".to_string()),
+            Just("e\u{301}".to_string()),
+            Just("\u{130}".to_string()),
+            Just("ß".to_string()),
+            Just("名".to_string()),
+            Just("_ .\n\t".to_string()),
+            any::<char>().prop_map(String::from),
+        ];
+        prop::collection::vec(piece, 0..24).prop_map(|parts| parts.concat())
+    }
+
+    fn render_all(diags: &[Diagnostic]) -> String {
+        format!("{}\n{diags:?}", serde_json::to_string(diags).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn validate_name_matches_reference(
+            name in name_like(),
+            other in name_like(),
+            same_dir in any::<bool>(),
+        ) {
+            let dir_name = if same_dir { name.clone() } else { other };
+            let dir = (!dir_name.is_empty() && !dir_name.contains(['/', '\0']))
+                .then(|| PathBuf::from("skills").join(&dir_name));
+            for dir in [None, dir.as_deref()] {
+                prop_assert_eq!(
+                    render_all(&validate_name(&name, dir)),
+                    render_all(&validate_name_reference(&name, dir))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_name_matches_reference_on_corpus() {
+        let long_segments = "segment-".repeat(12);
+        let long_run = "x".repeat(80);
+        let names = [
+            "my-skill",
+            "",
+            "My-Skill",
+            "-lead",
+            "trail-",
+            "two--hyphens",
+            "claude-helper",
+            "anthropic",
+            "s\u{FB01}le",
+            "\u{FF21}bc",
+            "caf\u{E9}",
+            "cafe\u{301}",
+            "na\u{EF}ve_tool",
+            "tab\tname",
+            &long_segments,
+            &long_run,
+        ];
+        for name in names {
+            for dir in [
+                None,
+                Some(Path::new("skills/my-skill")),
+                Some(Path::new(name)),
+            ] {
+                assert_eq!(
+                    render_all(&validate_name(name, dir)),
+                    render_all(&validate_name_reference(name, dir)),
+                    "{name:?} in {dir:?}"
+                );
+            }
+        }
+    }
+
+    /// Timing comparison over 5,000 names; run with `--ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn validate_name_timing() {
+        let names: Vec<String> = (0..5000)
+            .map(|i| match i % 4 {
+                0 => format!("skill-{i}"),
+                1 => format!("pdf-tools-{i}"),
+                2 => format!("Data-Tool-{i}"),
+                _ => format!("caf\u{E9}-{i}"),
+            })
+            .collect();
+        let dir = Path::new("skills/skill-0");
+        let time = |f: fn(&str, Option<&Path>) -> Vec<Diagnostic>| {
+            let start = std::time::Instant::now();
+            for _ in 0..20 {
+                for name in &names {
+                    std::hint::black_box(f(name, Some(dir)));
+                }
+            }
+            start.elapsed()
+        };
+        let reference = time(validate_name_reference);
+        let current = time(validate_name);
+        eprintln!("validate_name: reference {reference:?}, current {current:?}");
+        assert!(current <= reference, "{current:?} > {reference:?}");
+    }
+
     // ── validate (full pipeline) tests ───────────────────────────────
 
     #[test]
@@ -845,6 +1990,111 @@ mod tests {
         assert!(diags.is_empty(), "expected no diagnostics, got: {diags:?}");
     }
 
+    #[test]
+    fn validate_content_matches_directory_validation() {
+        let content = "---\nname: my-skill\ndescription: A valid skill\nfoo: bar\n---\n# Body\n";
+        let (_parent, dir) = make_skill_dir("other-name", content);
+        let from_dir = validate(&dir);
+        let from_content = validate_content(content, Some(&dir), ValidationTarget::Standard);
+        let codes = |diags: &[Diagnostic]| diags.iter().map(|d| d.code).collect::<Vec<_>>();
+        assert_eq!(codes(&from_dir), codes(&from_content));
+        assert!(from_content.iter().any(|d| d.code == E009));
+    }
+
+    #[test]
+    fn validate_content_without_dir_skips_name_match() {
+        let diags = validate_content(
+            "---\nname: my-skill\ndescription: A valid skill\n---\n",
+            None,
+            ValidationTarget::Standard,
+        );
+        assert!(diags.is_empty(), "expected no diagnostics, got: {diags:?}");
+    }
+
+    #[test]
+    fn validate_content_unparseable_reports_e000() {
+        let diags = validate_content("no frontmatter", None, ValidationTarget::Standard);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, E000);
+    }
+
+    #[test]
+    fn validate_frontmatter_str_accepts_bare_yaml_or_whole_file() {
+        for content in [
+            "name: my-skill\ndescription: A valid skill",
+            "---\nname: my-skill\ndescription: A valid skill\n---\n# Body\n",
+        ] {
+            let diags = validate_frontmatter_str(content, ValidationTarget::Standard);
+            assert!(diags.is_empty(), "{content:?}: {diags:?}");
+        }
+        // CRLF text gets CRLF delimiters: W006 as usual, but not "mixed".
+        let diags = validate_frontmatter_str(
+            "name: my-skill\r\ndescription: A valid skill\r\n",
+            ValidationTarget::Standard,
+        );
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].message, "SKILL.md uses CRLF line endings");
+    }
+
+    #[test]
+    fn validate_frontmatter_str_reports_field_errors() {
+        let diags = validate_frontmatter_str(
+            "name: My_Skill\ndescription: A valid skill\n",
+            ValidationTarget::Standard,
+        );
+        assert!(diags.iter().any(|d| d.code == E003), "{diags:?}");
+        assert!(!diags.iter().any(|d| d.code == E009), "{diags:?}");
+        let diags = validate_frontmatter_str("name: [unclosed\n", ValidationTarget::Standard);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, E000);
+    }
+
+    #[test]
+    fn validate_frontmatter_str_matches_validate_content() {
+        let yaml =
+            "name: my-skill\ndescription: A valid skill\nfoo: bar\ncompatibility: Claude Code\n";
+        let whole = format!("---\n{yaml}---\n");
+        let summary = |diags: Vec<Diagnostic>| {
+            diags
+                .into_iter()
+                .map(|d| (d.code, d.message))
+                .collect::<Vec<_>>()
+        };
+        for target in [ValidationTarget::Standard, ValidationTarget::ClaudeCode] {
+            let from_str = summary(validate_frontmatter_str(yaml, target));
+            assert!(!from_str.is_empty());
+            assert_eq!(from_str, summary(validate_content(&whole, None, target)));
+        }
+    }
+
+    #[test]
+    fn validate_content_crlf_warns_w006() {
+        let diags = validate_content(
+            "---\r\nname: my-skill\r\ndescription: A valid skill\r\n---\r\nBody\r\n",
+            None,
+            ValidationTarget::Standard,
+        );
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(diags[0].code, W006);
+        assert!(diags[0].is_warning());
+        assert_eq!(diags[0].message, "SKILL.md uses CRLF line endings");
+        assert!(diags[0].suggestion.is_some());
+    }
+
+    #[test]
+    fn validate_content_mixed_line_endings_warns_w006() {
+        let diags = validate_content(
+            "---\nname: my-skill\r\ndescription: A valid skill\n---\n",
+            None,
+            ValidationTarget::Standard,
+        );
+        assert_eq!(diags.len(), 1, "got: {diags:?}");
+        assert_eq!(
+            diags[0].message,
+            "SKILL.md has mixed line endings (1 CRLF, 3 LF)"
+        );
+    }
+
     #[test]
     fn validate_nonexistent_path() {
         let dir = std::path::Path::new("/nonexistent/path/that/does/not/exist");
@@ -861,6 +2111,43 @@ mod tests {
             .any(|d| d.message.contains("SKILL.md not found")));
     }
 
+    #[test]
+    fn validate_empty_skill_md() {
+        for content in ["", "  \n\t\n"] {
+            let dir = tempdir().unwrap();
+            fs::write(dir.path().join("SKILL.md"), content).unwrap();
+            let diags = validate(dir.path());
+            assert_eq!(diags.len(), 1, "{content:?}: {diags:?}");
+            assert_eq!(diags[0].code, E000);
+            assert_eq!(diags[0].message, "SKILL.md is empty");
+        }
+    }
+
+    #[test]
+    fn validate_empty_content_in_memory() {
+        let policy = PlaceholderPolicy {
+            allow: true,
+            ..PlaceholderPolicy::default()
+        };
+        for content in ["", " \r\n\n"] {
+            for diags in [
+                validate_content(content, None, ValidationTarget::Standard),
+                validate_content_with_policy(
+                    content,
+                    None,
+                    ValidationTarget::Standard,
+                    &HashMap::new(),
+                    &policy,
+                ),
+                validate_frontmatter_str(content, ValidationTarget::Standard),
+            ] {
+                assert_eq!(diags.len(), 1, "{content:?}: {diags:?}");
+                assert_eq!(diags[0].code, E000);
+                assert_eq!(diags[0].message, "SKILL.md is empty");
+            }
+        }
+    }
+
     #[test]
     fn validate_body_over_500_lines_warning() {
         let body: String = (0..501)
@@ -879,6 +2166,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_body_separator_line_not_counted_for_w002() {
+        let body = vec!["line"; 500].join("\n");
+        let content = format!("---\nname: my-skill\ndescription: desc\n---\n\n{body}\n");
+        let (_parent, dir) = make_skill_dir("my-skill", &content);
+        let diags = validate(&dir);
+        assert!(!diags.iter().any(|d| d.code == W002), "{diags:?}");
+    }
+
+    #[test]
+    fn validate_dense_body_warns_w011_not_w002() {
+        // 100 lines of 400 characters: short by line count, ~10,000 tokens.
+        let line = "word ".repeat(80);
+        let body = vec![line.as_str(); 100].join("\n");
+        let content = format!("---\nname: my-skill\ndescription: desc\n---\n{body}\n");
+        let (_parent, dir) = make_skill_dir("my-skill", &content);
+        let diags = validate(&dir);
+        assert!(diags.iter().any(|d| d.code == W011), "{diags:?}");
+        assert!(!diags.iter().any(|d| d.code == W002), "{diags:?}");
+    }
+
+    #[test]
+    fn validate_file_near_size_limit_warns_w013() {
+        let limit = usize::try_from(MAX_FILE_SIZE).unwrap();
+        let header = "---\nname: my-skill\ndescription: desc\n---\n";
+        let body = "x\n".repeat((limit * 9 / 10 - header.len()) / 2);
+        let (_parent, dir) = make_skill_dir("my-skill", &format!("{header}{body}"));
+        let diags = validate(&dir);
+        let w013 = diags.iter().find(|d| d.code == W013).expect("W013");
+        assert_eq!(w013.severity, Severity::Warning);
+        assert!(
+            w013.message.contains("89% of the 1048576-byte size limit"),
+            "{w013}"
+        );
+        assert!(w013.suggestion.as_deref().unwrap().contains("Split"));
+    }
+
+    #[test]
+    fn validate_file_below_soft_cap_no_w013() {
+        let soft_cap = MAX_FILE_SIZE * FILE_SIZE_WARNING_PERCENT / 100;
+        assert!(validate_file_size(soft_cap).is_none());
+        assert!(validate_file_size(soft_cap + 1).is_some());
+        assert!(validate_file_size(0).is_none());
+    }
+
+    #[test]
+    fn validate_body_within_token_budget_no_w011() {
+        let body = "x".repeat(BODY_MAX_TOKENS * 4);
+        let content = format!("---\nname: my-skill\ndescription: desc\n---\n{body}\n");
+        let diags = validate_content(&content, None, ValidationTarget::Standard);
+        assert!(!diags.iter().any(|d| d.code == W011), "{diags:?}");
+    }
+
     #[test]
     fn validate_body_at_500_lines_no_warning() {
         let body: String = (0..500)
@@ -941,7 +2281,7 @@ mod tests {
         let meta = make_metadata(&[("name", ""), ("description", "desc")]);
         let diags = validate_metadata(&meta, None);
         assert!(
-            diags.iter().all(|d| !d.code.is_empty()),
+            diags.iter().all(|d| !d.code_str().is_empty()),
             "all diagnostics should have error codes"
         );
     }
@@ -1060,6 +2400,280 @@ mod tests {
         assert_eq!(standard.len(), default.len());
     }
 
+    // ── deprecation metadata ─────────────────────────────────────────
+
+    const DEPRECATED_SKILL: &str = "---\nname: old-skill\ndescription: Processes files. Use when handling files.\nmetadata:\n  deprecated: true\n  superseded-by: new-skill\n---\nBody.\n";
+
+    #[test]
+    fn nested_deprecation_metadata_does_not_warn_w001() {
+        let diags = validate_content(DEPRECATED_SKILL, None, ValidationTarget::Standard);
+        assert!(
+            !diags.iter().any(|d| d.code == W001),
+            "metadata mapping should not trigger W001, got: {diags:?}"
+        );
+    }
+
+    #[test]
+    fn scalar_metadata_key_is_e019_not_w001() {
+        let content = "---\nname: s\ndescription: d\nmetadata: oops\n---\n";
+        let diags = validate_content(content, None, ValidationTarget::Standard);
+        assert!(diags.iter().any(|d| d.code == E019));
+        assert!(!diags.iter().any(|d| d.code == W001), "{diags:?}");
+    }
+
+    #[test]
+    fn superseded_by_known_skill_passes() {
+        let props = crate::parser::parse_properties(DEPRECATED_SKILL).unwrap();
+        assert!(validate_superseded_by(&props, &["old-skill", "new-skill"]).is_empty());
+    }
+
+    #[test]
+    fn superseded_by_unknown_skill_warns_w005() {
+        let props = crate::parser::parse_properties(DEPRECATED_SKILL).unwrap();
+        let diags = validate_superseded_by(&props, &["old-skill"]);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, W005);
+        assert!(diags[0].is_warning());
+        assert!(diags[0].message.contains("'new-skill'"));
+    }
+
+    #[test]
+    fn no_superseded_by_is_not_checked() {
+        let props = crate::parser::parse_properties("---\nname: s\ndescription: d\n---\n").unwrap();
+        assert!(validate_superseded_by(&props, &[]).is_empty());
+    }
+
+    // ── metadata block shape (E019, E020, W008, W009) ────────────────
+
+    /// Diagnostics for a skill whose frontmatter ends with `metadata_yaml`.
+    fn metadata_diags(metadata_yaml: &str) -> Vec<Diagnostic> {
+        let content = format!("---\nname: s\ndescription: d\n{metadata_yaml}---\n");
+        validate_content(&content, None, ValidationTarget::Standard)
+    }
+
+    #[test]
+    fn metadata_string_is_e019() {
+        let diags = metadata_diags("metadata: \"just a string\"\n");
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, E019);
+        assert!(diags[0].is_error());
+        assert_eq!(
+            diags[0].message,
+            "`metadata` must be a mapping, not a string"
+        );
+        assert_eq!(diags[0].field, Some("metadata"));
+    }
+
+    #[test]
+    fn metadata_list_is_e019() {
+        let diags = metadata_diags("metadata:\n  - a\n  - b\n");
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].message, "`metadata` must be a mapping, not a list");
+    }
+
+    #[test]
+    fn metadata_empty_is_e019() {
+        let diags = metadata_diags("metadata:\n");
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].message, "`metadata` must be a mapping, not null");
+    }
+
+    #[test]
+    fn metadata_non_string_key_is_e020() {
+        let diags = metadata_diags("metadata:\n  1: one\n  true: yes\n  ok: fine\n");
+        let mut messages: Vec<_> = diags.iter().map(|d| (d.code, d.message.as_str())).collect();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                (E020, "`metadata` key `1` must be a string, not a number"),
+                (
+                    E020,
+                    "`metadata` key `true` must be a string, not a boolean"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_integer_priority_passes() {
+        let diags = metadata_diags("metadata:\n  priority: -3\n");
+        assert!(diags.is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn metadata_non_integer_priority_is_w012() {
+        for (value, kind) in [
+            ("high", "a string"),
+            ("2.5", "a non-integer number"),
+            ("[1]", "a list"),
+        ] {
+            let diags = metadata_diags(&format!("metadata:\n  priority: {value}\n"));
+            assert_eq!(diags.len(), 1, "{diags:?}");
+            assert_eq!(diags[0].code, W012);
+            assert!(diags[0].is_warning());
+            assert_eq!(
+                diags[0].message,
+                format!("`metadata.priority` must be an integer, not {kind}")
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_author_and_authors_forms_pass() {
+        for yaml in [
+            "metadata:\n  author: Jane Doe\n",
+            "metadata:\n  authors: [Jane Doe, John Roe]\n",
+            "metadata:\n  author: John Roe\n  authors: [Jane Doe, John Roe]\n",
+        ] {
+            let diags = metadata_diags(yaml);
+            assert!(diags.is_empty(), "{yaml}: {diags:?}");
+        }
+    }
+
+    #[test]
+    fn metadata_author_missing_from_authors_is_w015() {
+        let diags = metadata_diags("metadata:\n  author: Ann Poe\n  authors: [Jane Doe]\n");
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, W015);
+        assert!(diags[0].is_warning());
+        assert_eq!(
+            diags[0].message,
+            "`metadata.author` 'Ann Poe' is not listed in `metadata.authors`"
+        );
+    }
+
+    #[test]
+    fn metadata_authors_not_a_list_of_names_is_w015() {
+        for (value, kind) in [
+            ("Jane Doe", "a string"),
+            ("[Jane Doe, 3]", "a list with non-string entries"),
+        ] {
+            let diags = metadata_diags(&format!("metadata:\n  authors: {value}\n"));
+            assert_eq!(diags.len(), 1, "{diags:?}");
+            assert_eq!(diags[0].code, W015);
+            assert_eq!(
+                diags[0].message,
+                format!("`metadata.authors` must be a list of names, not {kind}")
+            );
+        }
+    }
+
+    #[test]
+    fn metadata_two_levels_deep_passes() {
+        let diags = metadata_diags("metadata:\n  owner:\n    team: core\n    contacts: [a, b]\n");
+        assert!(diags.is_empty(), "{diags:?}");
+    }
+
+    #[test]
+    fn metadata_three_levels_deep_is_w008() {
+        let diags = metadata_diags("metadata:\n  matrix:\n    - os: [linux, macos]\n  flat: 1\n");
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, W008);
+        assert!(diags[0].is_warning());
+        assert_eq!(
+            diags[0].message,
+            "`metadata.matrix` is nested 3 levels deep (max 2)"
+        );
+    }
+
+    #[test]
+    fn metadata_over_size_cap_is_w009() {
+        let big = "x".repeat(METADATA_MAX_BYTES);
+        let diags = metadata_diags(&format!("metadata:\n  notes: {big}\n"));
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, W009);
+        assert_eq!(
+            diags[0].message,
+            format!(
+                "`metadata` is {} bytes when serialized (max {METADATA_MAX_BYTES})",
+                "notes: \n".len() + big.len()
+            )
+        );
+    }
+
+    #[test]
+    fn metadata_size_cap_is_configurable() {
+        let block: Value = serde_yaml_ng::from_str("version: '1.0'\n").unwrap();
+        assert!(validate_metadata_block(&block, METADATA_MAX_BYTES).is_empty());
+        let diags = validate_metadata_block(&block, 4);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, W009);
+    }
+
+    // ── tab indentation (E022) ───────────────────────────────────────
+
+    #[test]
+    fn tab_indentation_replaces_raw_parse_error() {
+        let content =
+            "---\nname: my-skill\ndescription: desc\nmetadata:\n\tversion: 1\n---\nBody\n";
+        let diags = validate_content(content, None, ValidationTarget::Standard);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, E022);
+        assert!(diags[0].is_error());
+        assert_eq!(
+            diags[0].message,
+            "frontmatter line 5 is indented with a tab (YAML requires spaces)"
+        );
+        assert_eq!(
+            diags[0].suggestion.as_deref(),
+            Some("Indent with 2 spaces per tab")
+        );
+    }
+
+    #[test]
+    fn tabs_in_body_or_values_are_not_e022() {
+        let content = "---\nname: my-skill\ndescription: \"a\\tb\"\n---\n\tindented body\n";
+        let diags = validate_content(content, None, ValidationTarget::Standard);
+        assert!(!diags.iter().any(|d| d.code == E022), "{diags:?}");
+    }
+
+    // ── skill file casing (W010, E021) ───────────────────────────────
+
+    #[test]
+    fn lowercase_skill_md_warns_w010() {
+        let parent = tempdir().unwrap();
+        let dir = parent.path().join("my-skill");
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("skill.md"),
+            "---\nname: my-skill\ndescription: A valid skill\n---\n",
+        )
+        .unwrap();
+        let diags = validate(&dir);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, W010);
+        assert!(diags[0].is_warning());
+        assert!(diags[0].suggestion.is_some());
+    }
+
+    #[test]
+    fn uppercase_skill_md_has_no_casing_diagnostic() {
+        let (_parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: A valid skill\n---\n",
+        );
+        assert!(validate_skill_md_casing(&dir).is_none());
+    }
+
+    #[test]
+    fn both_skill_md_casings_error_e021() {
+        let (_parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: A valid skill\n---\n",
+        );
+        fs::write(dir.join("skill.md"), "---\nname: other\n---\n").unwrap();
+        let diags = validate(&dir);
+        if skill_md_names(&dir).unwrap().lowercase {
+            // Case-sensitive filesystem: two distinct files.
+            assert!(diags.iter().any(|d| d.code == E021 && d.is_error()));
+            assert!(!diags.iter().any(|d| d.code == W010));
+        } else {
+            // Case-insensitive filesystem: the write replaced SKILL.md.
+            assert!(!diags.iter().any(|d| d.code == E021 || d.code == W010));
+        }
+    }
+
     // ── discover_skills tests ────────────────────────────────────────
 
     #[test]
@@ -1114,6 +2728,104 @@ mod tests {
         assert_eq!(dirs.len(), 2);
     }
 
+    // ── draft skills ─────────────────────────────────────────────────
+
+    /// Create `skill-a` (published) and `skill-b` (draft) under a temp dir.
+    fn make_draft_collection() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let parent = tempdir().unwrap();
+        let published = parent.path().join("skill-a");
+        let draft = parent.path().join("skill-b");
+        fs::create_dir(&published).unwrap();
+        fs::create_dir(&draft).unwrap();
+        fs::write(published.join("SKILL.md"), "---\nname: a\n---\n").unwrap();
+        fs::write(draft.join("SKILL.md"), "---\nname: b\n---\n").unwrap();
+        fs::write(draft.join(DRAFT_MARKER), "").unwrap();
+        (parent, published, draft)
+    }
+
+    #[test]
+    fn discover_skills_skips_drafts() {
+        let (parent, published, _draft) = make_draft_collection();
+        assert_eq!(discover_skills(parent.path()), vec![published.clone()]);
+        let (dirs, warnings) = discover_skills_verbose(parent.path());
+        assert_eq!(dirs, vec![published]);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn discover_skills_include_drafts() {
+        let (parent, published, draft) = make_draft_collection();
+        let opts = DiscoveryOptions {
+            include_drafts: true,
+            ..DiscoveryOptions::default()
+        };
+        assert_eq!(
+            discover_skills_with(parent.path(), &opts),
+            vec![published.clone(), draft.clone()]
+        );
+        let (dirs, _) = discover_skills_verbose_with(parent.path(), &opts);
+        assert_eq!(dirs, vec![published, draft]);
+    }
+
+    #[test]
+    fn discover_skills_draft_marker_directory_ignored() {
+        let parent = tempdir().unwrap();
+        let skill = parent.path().join("my-skill");
+        fs::create_dir_all(skill.join(DRAFT_MARKER)).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: test\n---\n").unwrap();
+        assert!(!is_draft(&skill));
+        assert_eq!(discover_skills(parent.path()), vec![skill]);
+    }
+
+    // ── symlinked skills ─────────────────────────────────────────────
+
+    #[cfg(unix)]
+    #[test]
+    fn discover_skills_follows_symlinks_only_when_enabled() {
+        let shared = tempdir().unwrap();
+        let skill = shared.path().join("shared-skill");
+        fs::create_dir(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: shared-skill\n---\n").unwrap();
+
+        let root = tempdir().unwrap();
+        let link = root.path().join("shared-skill");
+        std::os::unix::fs::symlink(&skill, &link).unwrap();
+
+        assert!(discover_skills(root.path()).is_empty());
+        let opts = DiscoveryOptions {
+            follow_symlinks: true,
+            ..DiscoveryOptions::default()
+        };
+        assert_eq!(discover_skills_with(root.path(), &opts), vec![link.clone()]);
+        let (dirs, warnings) = discover_skills_verbose_with(root.path(), &opts);
+        assert_eq!(dirs, vec![link]);
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn discover_skills_symlink_cycle_terminates() {
+        let root = tempdir().unwrap();
+        let skill = root.path().join("my-skill");
+        fs::create_dir(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: my-skill\n---\n").unwrap();
+        // my-skill/loop -> root: following it would revisit the whole tree.
+        std::os::unix::fs::symlink(root.path(), skill.join("loop")).unwrap();
+
+        let opts = DiscoveryOptions {
+            follow_symlinks: true,
+            ..DiscoveryOptions::default()
+        };
+        assert_eq!(
+            discover_skills_with(root.path(), &opts),
+            vec![skill.clone()]
+        );
+        let (dirs, warnings) = discover_skills_verbose_with(root.path(), &opts);
+        assert_eq!(dirs, vec![skill]);
+        assert_eq!(warnings.len(), 1, "got: {warnings:?}");
+        assert!(warnings[0].message.contains("already visited"));
+    }
+
     // ── discover_skills_verbose tests ─────────────────────────────────
 
     #[test]
@@ -1209,4 +2921,162 @@ mod tests {
             "skill beyond max depth should not be found, got: {dirs:?}"
         );
     }
+
+    // ── _defaults.yml layering ───────────────────────────────────────
+
+    const CC_SKILL: &str =
+        "---\nname: my-skill\ndescription: Does things\nargument-hint: \"[file]\"\n---\nBody.\n";
+
+    #[test]
+    fn defaults_fill_missing_fields() {
+        let (parent, dir) = make_skill_dir("my-skill", CC_SKILL);
+        fs::write(
+            parent.path().join(crate::defaults::DEFAULTS_FILE),
+            "compatibility: Claude Desktop\n",
+        )
+        .unwrap();
+        // The default compatibility contradicts the skill's extension field.
+        let diags = validate_with_target(&dir, ValidationTarget::ClaudeCode);
+        assert!(diags.iter().any(|d| d.code == W003), "{diags:?}");
+    }
+
+    #[test]
+    fn skill_value_wins_over_default() {
+        let (parent, dir) = make_skill_dir(
+            "my-skill",
+            "---\nname: my-skill\ndescription: Does things\ncompatibility: Claude Code\n\
+             argument-hint: x\n---\nBody.\n",
+        );
+        fs::write(
+            parent.path().join(crate::defaults::DEFAULTS_FILE),
+            "compatibility: Claude Desktop\n",
+        )
+        .unwrap();
+        let diags = validate_with_target(&dir, ValidationTarget::ClaudeCode);
+        assert!(!diags.iter().any(|d| d.code == W003), "{diags:?}");
+    }
+
+    #[test]
+    fn defaults_do_not_touch_skill_md() {
+        let (parent, dir) = make_skill_dir("my-skill", CC_SKILL);
+        fs::write(
+            parent.path().join(crate::defaults::DEFAULTS_FILE),
+            "compatibility: Claude Desktop\n",
+        )
+        .unwrap();
+        // The default applies during validation (W003) but is never written.
+        let diags = validate_with_target(&dir, ValidationTarget::ClaudeCode);
+        assert!(diags.iter().any(|d| d.code == W003), "{diags:?}");
+        assert_eq!(fs::read_to_string(dir.join("SKILL.md")).unwrap(), CC_SKILL);
+        let props = crate::parser::read_properties(&dir).unwrap();
+        assert_eq!(props.compatibility, None);
+    }
+
+    #[test]
+    fn invalid_defaults_warn_and_are_ignored() {
+        let (parent, dir) = make_skill_dir("my-skill", CC_SKILL);
+        fs::write(
+            parent.path().join(crate::defaults::DEFAULTS_FILE),
+            "- not a mapping\n",
+        )
+        .unwrap();
+        let diags = validate_with_target(&dir, ValidationTarget::ClaudeCode);
+        let w007: Vec<_> = diags.iter().filter(|d| d.code == W007).collect();
+        assert_eq!(w007.len(), 1, "{diags:?}");
+        assert!(w007[0].message.contains("_defaults.yml"));
+    }
+
+    #[test]
+    fn validate_content_ignores_defaults_on_disk() {
+        let (parent, _dir) = make_skill_dir("my-skill", CC_SKILL);
+        fs::write(
+            parent.path().join(crate::defaults::DEFAULTS_FILE),
+            "compatibility: Claude Desktop\n",
+        )
+        .unwrap();
+        let diags = validate_content(CC_SKILL, None, ValidationTarget::ClaudeCode);
+        assert!(!diags.iter().any(|d| d.code == W003), "{diags:?}");
+    }
+
+    // ── ${NAME} placeholders (I011) ──────────────────────────────────
+
+    const TEMPLATED: &str = "---\nname: ${TEAM}-reports\ndescription: Builds ${TEAM} reports. Use when asked for reports.\n---\nBody\n";
+
+    fn allow_placeholders() -> PlaceholderPolicy {
+        PlaceholderPolicy {
+            allow: true,
+            ..PlaceholderPolicy::default()
+        }
+    }
+
+    #[test]
+    fn placeholders_in_name_fail_without_policy() {
+        let diags = validate_content(TEMPLATED, None, ValidationTarget::Standard);
+        assert!(diags.iter().any(|d| d.code == E003), "{diags:?}");
+    }
+
+    #[test]
+    fn allowed_placeholders_are_masked_and_listed() {
+        let (_parent, dir) = make_skill_dir("team-reports", TEMPLATED);
+        let diags = validate_with_policy(&dir, ValidationTarget::Standard, &allow_placeholders());
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, crate::diagnostics::I011);
+        assert_eq!(
+            diags[0].message,
+            "placeholders to provide at deploy time: ${TEAM}"
+        );
+    }
+
+    #[test]
+    fn placeholder_nominal_length_counts_toward_limits() {
+        let policy = PlaceholderPolicy {
+            nominal_length: 60,
+            ..allow_placeholders()
+        };
+        let diags = validate_content_with_policy(
+            TEMPLATED,
+            None,
+            ValidationTarget::Standard,
+            &HashMap::new(),
+            &policy,
+        );
+        assert!(diags.iter().any(|d| d.code == E002), "{diags:?}");
+    }
+
+    #[test]
+    fn substitution_can_push_description_over_limit() {
+        let content = "---\nname: my-skill\ndescription: Builds reports. ${DETAILS}\n---\nBody\n";
+        let short = PlaceholderPolicy {
+            substitutions: HashMap::from([("DETAILS".to_string(), "x".repeat(100))]),
+            ..PlaceholderPolicy::default()
+        };
+        let long = PlaceholderPolicy {
+            substitutions: HashMap::from([("DETAILS".to_string(), "x".repeat(1100))]),
+            ..PlaceholderPolicy::default()
+        };
+        let validate = |policy: &PlaceholderPolicy| {
+            validate_content_with_policy(
+                content,
+                None,
+                ValidationTarget::Standard,
+                &HashMap::new(),
+                policy,
+            )
+        };
+        assert!(validate(&short).is_empty(), "{:?}", validate(&short));
+        let diags = validate(&long);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(diags[0].code, E011);
+    }
+
+    #[test]
+    fn substituted_placeholders_are_not_listed() {
+        let policy = PlaceholderPolicy {
+            substitutions: HashMap::from([("TEAM".to_string(), "data".to_string())]),
+            ..allow_placeholders()
+        };
+        let (_parent, dir) = make_skill_dir("data-reports", TEMPLATED);
+        let diags = validate_with_policy(&dir, ValidationTarget::Standard, &policy);
+        assert!(diags.is_empty(), "{diags:?}");
+    }
 }
@@ -0,0 +1,421 @@
+//! Workspace configuration for repositories with several skill collections.
+//!
+//! An `aigent.toml` at the repository root declares one `[[collection]]`
+//! entry per skill collection, each with its own validation target, check
+//! profile, score threshold, exclude globs, and output paths:
+//!
+//! ```toml
+//! [[collection]]
+//! path = "internal"
+//! target = "claude-code"
+//! profile = "strict"
+//! min_score = 80
+//! exclude = ["drafts/**"]
+//! prompt_output = "dist/internal-prompt.xml"
+//! ```
+//!
+//! Unknown keys are rejected with the list of accepted keys, so typos such
+//! as `min_scor` fail loudly instead of being silently ignored.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::diagnostics::ValidationTarget;
+use crate::errors::{AigentError, Result};
+use crate::parser::read_file_checked;
+
+/// File name of the workspace configuration at the repository root.
+pub const WORKSPACE_FILE: &str = "aigent.toml";
+
+/// Parsed `aigent.toml` workspace configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkspaceConfig {
+    /// Skill collections, in declaration order.
+    #[serde(rename = "collection", default)]
+    pub collections: Vec<CollectionConfig>,
+}
+
+/// Which checks `workspace check` runs for a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckProfile {
+    /// Specification conformance only (like `validate`).
+    Spec,
+    /// Conformance plus semantic lint checks (like `check`).
+    #[default]
+    Standard,
+    /// Conformance, lint, and structure checks; warnings also fail.
+    Strict,
+}
+
+/// A single `[[collection]]` entry in `aigent.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CollectionConfig {
+    /// Collection root, relative to the workspace root.
+    pub path: PathBuf,
+    /// Display name (default: the collection path).
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Validation target profile (default: `standard`).
+    #[serde(default)]
+    pub target: ValidationTarget,
+    /// Check profile (default: `standard`).
+    #[serde(default)]
+    pub profile: CheckProfile,
+    /// Minimum acceptable quality score (0–100); unset disables the gate.
+    #[serde(default)]
+    pub min_score: Option<u32>,
+    /// Globs of skill directories to skip, relative to the collection root.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Where `workspace prompt` writes this collection's prompt.
+    #[serde(default)]
+    pub prompt_output: Option<PathBuf>,
+    /// Where `workspace doc` writes this collection's catalog.
+    #[serde(default)]
+    pub doc_output: Option<PathBuf>,
+}
+
+impl CollectionConfig {
+    /// Returns the display name: `name` if set, otherwise the path.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    /// Returns `true` if the skill directory at `rel` (relative to the
+    /// collection root) matches one of the `exclude` globs.
+    ///
+    /// A glob also excludes everything beneath a matching directory, so
+    /// `drafts` excludes `drafts/foo`.
+    #[must_use]
+    pub fn is_excluded(&self, rel: &Path) -> bool {
+        let segments: Vec<String> = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        self.exclude.iter().any(|pattern| {
+            let pattern: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+            (1..=segments.len()).any(|n| {
+                let prefix: Vec<&str> = segments[..n].iter().map(String::as_str).collect();
+                glob_match_segments(&pattern, &prefix)
+            })
+        })
+    }
+
+    /// Discover the collection's skill directories under `root`, minus
+    /// excluded ones.
+    #[must_use]
+    pub fn skill_dirs(&self, root: &Path) -> Vec<PathBuf> {
+        let base = root.join(&self.path);
+        crate::validator::discover_skills(&base)
+            .into_iter()
+            .filter(|dir| {
+                let rel = dir.strip_prefix(&base).unwrap_or(dir);
+                !self.is_excluded(rel)
+            })
+            .collect()
+    }
+}
+
+/// Parse and check `aigent.toml` content.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if the TOML is malformed, contains unknown
+/// keys or invalid values (the message names the accepted keys or values),
+/// declares no collections, declares the same path twice, or sets
+/// `min_score` above 100.
+pub fn parse_workspace(content: &str) -> Result<WorkspaceConfig> {
+    let config: WorkspaceConfig = toml::from_str(content).map_err(|e| AigentError::Parse {
+        message: e.to_string().trim_end().to_string(),
+    })?;
+
+    if config.collections.is_empty() {
+        return Err(AigentError::Parse {
+            message: "no [[collection]] entries found".to_string(),
+        });
+    }
+
+    for (i, collection) in config.collections.iter().enumerate() {
+        if let Some(min) = collection.min_score {
+            if min > 100 {
+                return Err(AigentError::Parse {
+                    message: format!(
+                        "collection '{}': min_score must be between 0 and 100, got {min}",
+                        collection.display_name()
+                    ),
+                });
+            }
+        }
+        if config.collections[..i]
+            .iter()
+            .any(|other| other.path == collection.path)
+        {
+            return Err(AigentError::Parse {
+                message: format!(
+                    "collection path '{}' is declared more than once",
+                    collection.path.display()
+                ),
+            });
+        }
+    }
+
+    Ok(config)
+}
+
+/// Load `aigent.toml` from a workspace root.
+///
+/// # Errors
+///
+/// Returns `AigentError::Parse` if the file cannot be read, fails
+/// [`parse_workspace`], or lists a collection path that is not a directory.
+/// Messages are prefixed with the config file path.
+pub fn load_workspace(root: &Path) -> Result<WorkspaceConfig> {
+    let path = root.join(WORKSPACE_FILE);
+    let content = read_file_checked(&path)?;
+    let config = parse_workspace(&content).map_err(|e| match e {
+        AigentError::Parse { message } => AigentError::Parse {
+            message: format!("{}: {message}", path.display()),
+        },
+        other => other,
+    })?;
+
+    for collection in &config.collections {
+        if !root.join(&collection.path).is_dir() {
+            return Err(AigentError::Parse {
+                message: format!(
+                    "{}: collection path '{}' is not a directory",
+                    path.display(),
+                    collection.path.display()
+                ),
+            });
+        }
+    }
+
+    Ok(config)
+}
+
+/// Match path segments against glob segments.
+///
+/// `**` matches zero or more segments; within a segment, `*` matches any
+/// run of characters and `?` matches exactly one.
+pub(crate) fn glob_match_segments(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| glob_match_segments(rest, &segments[skip..]))
+        }
+        Some((first, rest)) => match segments.split_first() {
+            Some((segment, remaining)) => {
+                glob_match_segment(first, segment) && glob_match_segments(rest, remaining)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Match a single path segment against a glob segment (`*` and `?`).
+pub(crate) fn glob_match_segment(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    // ── Parsing ──────────────────────────────────────────────────────
+
+    #[test]
+    fn parse_minimal_collection_uses_defaults() {
+        let config = parse_workspace("[[collection]]\npath = \"skills\"\n").unwrap();
+        assert_eq!(config.collections.len(), 1);
+        let c = &config.collections[0];
+        assert_eq!(c.path, PathBuf::from("skills"));
+        assert_eq!(c.target, ValidationTarget::Standard);
+        assert_eq!(c.profile, CheckProfile::Standard);
+        assert_eq!(c.min_score, None);
+        assert!(c.exclude.is_empty());
+        assert_eq!(c.display_name(), "skills");
+    }
+
+    #[test]
+    fn parse_full_collection() {
+        let config = parse_workspace(
+            r#"
+[[collection]]
+path = "internal"
+name = "Internal"
+target = "claude-code"
+profile = "strict"
+min_score = 80
+exclude = ["drafts/**"]
+prompt_output = "dist/internal.xml"
+doc_output = "docs/internal.md"
+"#,
+        )
+        .unwrap();
+        let c = &config.collections[0];
+        assert_eq!(c.display_name(), "Internal");
+        assert_eq!(c.target, ValidationTarget::ClaudeCode);
+        assert_eq!(c.profile, CheckProfile::Strict);
+        assert_eq!(c.min_score, Some(80));
+        assert_eq!(c.prompt_output, Some(PathBuf::from("dist/internal.xml")));
+        assert_eq!(c.doc_output, Some(PathBuf::from("docs/internal.md")));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_collection_key() {
+        let err = parse_workspace("[[collection]]\npath = \"a\"\nmin_scor = 80\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("min_scor"), "error should name the key: {err}");
+        assert!(
+            err.contains("min_score"),
+            "error should list accepted keys: {err}"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_top_level_key() {
+        let err = parse_workspace("colection = 1\n").unwrap_err().to_string();
+        assert!(err.contains("colection"), "{err}");
+    }
+
+    #[test]
+    fn parse_rejects_invalid_target() {
+        let err = parse_workspace("[[collection]]\npath = \"a\"\ntarget = \"strict\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("claude-code"), "should list variants: {err}");
+    }
+
+    #[test]
+    fn parse_rejects_empty_workspace() {
+        let err = parse_workspace("").unwrap_err().to_string();
+        assert!(err.contains("no [[collection]]"), "{err}");
+    }
+
+    #[test]
+    fn parse_rejects_min_score_above_100() {
+        let err = parse_workspace("[[collection]]\npath = \"a\"\nmin_score = 101\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("between 0 and 100"), "{err}");
+    }
+
+    #[test]
+    fn parse_rejects_duplicate_paths() {
+        let err = parse_workspace("[[collection]]\npath = \"a\"\n[[collection]]\npath = \"a\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more than once"), "{err}");
+    }
+
+    // ── Loading ──────────────────────────────────────────────────────
+
+    #[test]
+    fn load_prefixes_errors_with_config_path() {
+        let root = tempdir().unwrap();
+        fs::write(
+            root.path().join(WORKSPACE_FILE),
+            "[[collection]]\nbogus = 1\n",
+        )
+        .unwrap();
+        let err = load_workspace(root.path()).unwrap_err().to_string();
+        assert!(err.contains(WORKSPACE_FILE), "{err}");
+    }
+
+    #[test]
+    fn load_rejects_missing_collection_dir() {
+        let root = tempdir().unwrap();
+        fs::write(
+            root.path().join(WORKSPACE_FILE),
+            "[[collection]]\npath = \"missing\"\n",
+        )
+        .unwrap();
+        let err = load_workspace(root.path()).unwrap_err().to_string();
+        assert!(err.contains("is not a directory"), "{err}");
+    }
+
+    #[test]
+    fn load_missing_file_is_error() {
+        let root = tempdir().unwrap();
+        assert!(load_workspace(root.path()).is_err());
+    }
+
+    // ── Excludes and discovery ───────────────────────────────────────
+
+    fn collection_with_excludes(exclude: &[&str]) -> CollectionConfig {
+        let mut config = parse_workspace("[[collection]]\npath = \"c\"\n").unwrap();
+        let mut c = config.collections.remove(0);
+        c.exclude = exclude.iter().map(|s| s.to_string()).collect();
+        c
+    }
+
+    #[test]
+    fn exclude_matches_directory_and_descendants() {
+        let c = collection_with_excludes(&["drafts"]);
+        assert!(c.is_excluded(Path::new("drafts")));
+        assert!(c.is_excluded(Path::new("drafts/foo")));
+        assert!(!c.is_excluded(Path::new("published/drafts-guide")));
+    }
+
+    #[test]
+    fn exclude_star_and_double_star() {
+        let c = collection_with_excludes(&["*-wip", "**/legacy"]);
+        assert!(c.is_excluded(Path::new("parser-wip")));
+        assert!(!c.is_excluded(Path::new("parser")));
+        assert!(c.is_excluded(Path::new("legacy")));
+        assert!(c.is_excluded(Path::new("group/legacy/old-skill")));
+    }
+
+    #[test]
+    fn glob_segment_question_mark_and_star() {
+        assert!(glob_match_segment("v?", "v1"));
+        assert!(!glob_match_segment("v?", "v10"));
+        assert!(glob_match_segment("a*b*c", "aXXbYc"));
+        assert!(!glob_match_segment("a*b", "aXXc"));
+        assert!(glob_match_segment("*", ""));
+    }
+
+    #[test]
+    fn skill_dirs_applies_excludes() {
+        let root = tempdir().unwrap();
+        for name in ["c/keep", "c/drafts/skip"] {
+            let dir = root.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("SKILL.md"), "---\nname: x\ndescription: y\n---\n").unwrap();
+        }
+        let c = collection_with_excludes(&["drafts"]);
+        let dirs = c.skill_dirs(root.path());
+        assert_eq!(dirs, vec![root.path().join("c/keep")]);
+    }
+}
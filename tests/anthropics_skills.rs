@@ -10,13 +10,14 @@
 //! Gated with `#[ignore]` — run with:
 //!   cargo test --test anthropics_skills -- --ignored
 
+use assert_cmd::cargo::cargo_bin_cmd;
 use assert_cmd::Command;
 use predicates::prelude::*;
 
 const FIXTURES: &str = "tests/fixtures/anthropics-skills";
 
 fn aigent() -> Command {
-    Command::cargo_bin("aigent").unwrap()
+    cargo_bin_cmd!("aigent")
 }
 
 /// Generate per-skill integration tests.
@@ -141,7 +142,7 @@ mod template {
         aigent()
             .args(["validate", &template_path()])
             .assert()
-            .failure()
+            .code(1)
             .stderr(predicate::str::contains("does not match directory name"));
     }
 }
@@ -154,7 +155,7 @@ fn validate_recursive_finds_all_skills() {
     aigent()
         .args(["validate", "--recursive", FIXTURES])
         .assert()
-        .failure() // template causes 1 error
+        .code(1) // template causes 1 error
         .stderr(predicate::str::contains("13 skills"))
         .stderr(predicate::str::contains("12 ok"))
         .stderr(predicate::str::contains("1 errors"));
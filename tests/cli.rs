@@ -44,6 +44,38 @@ fn version_flag() {
         .stdout(predicate::str::contains(env!("CARGO_PKG_VERSION")));
 }
 
+#[test]
+fn help_exit_codes_lists_every_code() {
+    let output = aigent().arg("--help-exit-codes").output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for code in ["0", "1", "2", "3"] {
+        assert!(stdout.contains(&format!("\n  {code}  ")), "{stdout}");
+    }
+}
+
+#[test]
+fn help_exit_codes_match_docs() {
+    // The documented table must list the same codes and meanings as the CLI.
+    let output = aigent().arg("--help-exit-codes").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let docs = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/cli.md")).unwrap();
+    for line in stdout.lines().skip(1) {
+        let (code, meaning) = line.trim().split_once("  ").unwrap();
+        let row = format!("| {code} | {meaning} |");
+        assert!(docs.contains(&row), "docs/cli.md is missing: {row}");
+    }
+}
+
+#[test]
+fn long_help_lists_exit_codes() {
+    aigent()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Exit codes:"));
+}
+
 #[test]
 fn about_flag() {
     aigent()
@@ -64,10 +96,33 @@ fn about_flag() {
 fn no_args_shows_usage() {
     aigent()
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("Usage"));
 }
 
+// ── completions ─────────────────────────────────────────────────────
+
+#[test]
+fn completions_generate_for_each_shell() {
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        aigent()
+            .args(["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("aigent"))
+            .stdout(predicate::str::contains("validate-plugin"));
+    }
+}
+
+#[test]
+fn completions_reject_unknown_shell() {
+    aigent()
+        .args(["completions", "tcsh"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value"));
+}
+
 // ── validate ────────────────────────────────────────────────────────
 
 #[test]
@@ -90,7 +145,7 @@ fn validate_missing_name() {
     aigent()
         .args(["validate", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("name"));
 }
 
@@ -102,7 +157,7 @@ fn validate_missing_skill_md() {
     aigent()
         .args(["validate", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("SKILL.md"));
 }
 
@@ -119,6 +174,41 @@ fn validate_warnings_only_exit_zero() {
         .stderr(predicate::str::contains("warning:"));
 }
 
+#[test]
+fn validate_color_always_paints_errors_red() {
+    let (_parent, dir) =
+        make_skill_dir("bad-skill", "---\ndescription: A test skill\n---\nBody.\n");
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--color", "always"])
+        .env("NO_COLOR", "1")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("\x1b[31m"));
+}
+
+#[test]
+fn validate_piped_output_is_plain_by_default() {
+    let (_parent, dir) =
+        make_skill_dir("bad-skill", "---\ndescription: A test skill\n---\nBody.\n");
+    aigent()
+        .args(["validate", dir.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn validate_no_color_overrides_color_always() {
+    let (_parent, dir) =
+        make_skill_dir("bad-skill", "---\ndescription: A test skill\n---\nBody.\n");
+    aigent()
+        .args(["--color", "always", "--no-color", "validate"])
+        .arg(dir.to_str().unwrap())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("\x1b[").not());
+}
+
 #[test]
 fn validate_skill_md_file_path() {
     let (_parent, dir) = make_skill_dir(
@@ -157,7 +247,7 @@ fn properties_invalid() {
     aigent()
         .args(["properties", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("aigent properties:"));
 }
 
@@ -314,6 +404,90 @@ fn new_with_dir_override() {
     assert!(dir.join("SKILL.md").exists());
 }
 
+#[test]
+fn new_reports_score_of_created_skill() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("processing-pdf-files");
+    aigent()
+        .args([
+            "new",
+            "Process PDF files",
+            "--no-llm",
+            "--dir",
+            dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Created skill '.+' at .+ \(score: \d+/100\)").unwrap());
+}
+
+#[test]
+fn new_invalid_output_saves_draft() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("Bad_Name");
+    aigent()
+        .args([
+            "new",
+            "Process PDF files",
+            "--no-llm",
+            "--name",
+            "Bad_Name",
+            "--dir",
+            dir.to_str().unwrap(),
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("failed validation"))
+        .stderr(predicate::str::contains("Saved the generated draft"));
+    assert!(!dir.join("SKILL.md").exists());
+    let draft = parent.path().join("Bad_Name.draft");
+    let content = fs::read_to_string(draft.join("SKILL.md")).unwrap();
+    assert!(content.contains("name: Bad_Name"));
+    assert!(draft.join(".aigent-draft").exists());
+}
+
+#[test]
+fn new_no_score_omits_score() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("processing-pdf-files");
+    aigent()
+        .args([
+            "new",
+            "Process PDF files",
+            "--no-llm",
+            "--no-score",
+            "--dir",
+            dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created skill"))
+        .stdout(predicate::str::contains("score:").not());
+}
+
+#[test]
+fn new_warns_about_unclear_purpose() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("handling-pdfs");
+    aigent()
+        .args([
+            "new",
+            "What about PDFs?",
+            "--no-llm",
+            "--name",
+            "handling-pdfs",
+            "--dir",
+            dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created skill"))
+        .stderr(predicate::str::contains(
+            "warning: purpose may be unclear: Please provide a statement",
+        ));
+    assert!(dir.join("SKILL.md").exists());
+}
+
 // ── init ───────────────────────────────────────────────────────────
 
 #[test]
@@ -328,6 +502,40 @@ fn init_in_empty_dir() {
     assert!(dir.join("SKILL.md").exists());
 }
 
+#[test]
+fn init_draft_skipped_by_recursive_discovery() {
+    let parent = tempdir().unwrap();
+    let published = parent.path().join("published-skill");
+    let draft = parent.path().join("draft-skill");
+    aigent()
+        .args(["init", published.to_str().unwrap()])
+        .assert()
+        .success();
+    aigent()
+        .args(["init", draft.to_str().unwrap(), "--draft"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Marked as draft"));
+    assert!(draft.join(".aigent-draft").exists());
+
+    aigent()
+        .args(["doc", parent.path().to_str().unwrap(), "--recursive"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("published-skill"))
+        .stdout(predicate::str::contains("draft-skill").not());
+    aigent()
+        .args([
+            "doc",
+            parent.path().to_str().unwrap(),
+            "--recursive",
+            "--include-drafts",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("draft-skill"));
+}
+
 #[test]
 fn init_where_skill_md_exists() {
     let (_parent, dir) = make_skill_dir(
@@ -337,7 +545,7 @@ fn init_where_skill_md_exists() {
     aigent()
         .args(["init", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("already exists"));
 }
 
@@ -377,6 +585,73 @@ fn new_skill_passes_validate() {
         .success();
 }
 
+// ── clean ───────────────────────────────────────────────────────────
+
+#[test]
+fn clean_removes_artifacts_and_reports_bytes() {
+    let root = tempdir().unwrap();
+    let r = root.path();
+    fs::create_dir_all(r.join("skill")).unwrap();
+    fs::write(r.join("skill/SKILL.md.bak"), "12345").unwrap();
+    fs::write(r.join("skill/.aigent-cache.json"), "{}").unwrap();
+    fs::write(r.join("skill/SKILL.md.bak.orig"), "keep").unwrap();
+    aigent()
+        .args(["clean", r.to_str().unwrap(), "--recursive"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed backup:"))
+        .stdout(predicate::str::contains("Removed cache:"))
+        .stdout(predicate::str::contains(
+            "Removed 2 artifacts, 7 bytes freed",
+        ));
+    assert!(!r.join("skill/SKILL.md.bak").exists());
+    assert!(!r.join("skill/.aigent-cache.json").exists());
+    assert!(r.join("skill/SKILL.md.bak.orig").exists());
+}
+
+#[test]
+fn clean_dry_run_keeps_files() {
+    let root = tempdir().unwrap();
+    fs::write(root.path().join("SKILL.md.bak"), "x").unwrap();
+    aigent()
+        .args(["clean", root.path().to_str().unwrap(), "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would remove backup:"))
+        .stdout(predicate::str::contains(
+            "Would remove 1 artifact, 1 bytes would be freed",
+        ));
+    assert!(root.path().join("SKILL.md.bak").exists());
+}
+
+#[test]
+fn clean_with_nothing_to_remove() {
+    let root = tempdir().unwrap();
+    aigent()
+        .args(["clean", root.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No artifacts found"));
+}
+
+#[test]
+fn clean_rejects_dist_outside_root() {
+    let root = tempdir().unwrap();
+    let outside = tempdir().unwrap();
+    aigent()
+        .args([
+            "clean",
+            root.path().to_str().unwrap(),
+            "--dist",
+            outside.path().to_str().unwrap(),
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("aigent clean:"))
+        .stderr(predicate::str::contains("must be inside"));
+    assert!(outside.path().exists());
+}
+
 // ── --format flag ──────────────────────────────────────────────────
 
 #[test]
@@ -420,7 +695,7 @@ fn validate_format_json_with_errors() {
         .args(["validate", dir.to_str().unwrap(), "--format", "json"])
         .output()
         .unwrap();
-    assert!(!output.status.success());
+    assert_eq!(output.status.code(), Some(1));
     let stdout = String::from_utf8(output.stdout).unwrap();
     let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
     let arr = json.as_array().unwrap();
@@ -540,7 +815,7 @@ fn check_no_validate_skips_conformance() {
     aigent()
         .args(["check", dir.to_str().unwrap()])
         .assert()
-        .failure();
+        .code(1);
     // With --no-validate: only semantic checks, which are info-level.
     aigent()
         .args(["check", dir.to_str().unwrap(), "--no-validate"])
@@ -549,6 +824,62 @@ fn check_no_validate_skips_conformance() {
         .stderr(predicate::str::contains("info:"));
 }
 
+#[test]
+fn check_all_adds_structure_diagnostics() {
+    let (_parent, dir) = make_skill_dir(
+        "processing-pdfs",
+        "---\nname: processing-pdfs\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nSee [guide](guide.md) for details.\n",
+    );
+    let bare = check_codes(&dir, &[]);
+    assert!(!bare.contains(&"S001".to_string()), "got: {bare:?}");
+    let all = check_codes(&dir, &["--all"]);
+    assert!(all.contains(&"S001".to_string()), "got: {all:?}");
+}
+
+/// Diagnostic codes reported by `check --format json` for one skill.
+fn check_codes(dir: &std::path::Path, extra: &[&str]) -> Vec<String> {
+    let output = aigent()
+        .args(["check", dir.to_str().unwrap(), "--format", "json"])
+        .args(extra)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json[0]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn check_lint_disable_and_only_select_rules() {
+    let (_parent, dir) = make_skill_dir(
+        "helper",
+        "---\nname: helper\ndescription: Helps\n---\nBody.\n",
+    );
+    let all = check_codes(&dir, &[]);
+    assert!(all.contains(&"I003".to_string()), "got: {all:?}");
+    assert!(all.contains(&"I005".to_string()), "got: {all:?}");
+
+    let disabled = check_codes(&dir, &["--lint-disable", "I003", "--lint-disable", "i005"]);
+    assert!(!disabled.contains(&"I003".to_string()), "got: {disabled:?}");
+    assert!(!disabled.contains(&"I005".to_string()), "got: {disabled:?}");
+    assert_eq!(disabled.len(), all.len() - 2);
+
+    let only = check_codes(&dir, &["--lint-only", "I003,I005"]);
+    assert_eq!(only, ["I003", "I005"]);
+}
+
+#[test]
+fn check_lint_disable_rejects_non_lint_codes() {
+    aigent()
+        .args(["check", ".", "--lint-disable", "E001"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("not a lint rule code"));
+}
+
 // ── lint alias (maps to check) ────────────────────────────────────
 
 #[test]
@@ -578,6 +909,93 @@ fn check_perfect_skill_no_output() {
         .stderr(predicate::str::is_match(OK_LINE).unwrap());
 }
 
+#[test]
+fn check_verbose_accepts_flag() {
+    let (_parent, dir) = make_skill_dir(
+        "processing-pdfs",
+        "---\nname: processing-pdfs\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--verbose"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_match(OK_LINE).unwrap());
+}
+
+#[test]
+fn check_metrics_adds_readability_annotation() {
+    let (_parent, dir) = make_skill_dir(
+        "processing-pdfs",
+        "---\nname: processing-pdfs\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nRead the file. Convert it.\n\n1. Open it.\n2. Save it.\n\n```bash\necho done\n```\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("I016").not());
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--metrics"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "info: body readability: 2.5 words per sentence, 25% code, 2 step(s) vs 1 paragraph(s), 1 numbered procedure(s)",
+        ));
+}
+
+/// A collection of three skills whose descriptions all lack a trigger phrase.
+fn make_untriggered_collection() -> tempfile::TempDir {
+    let root = tempdir().unwrap();
+    for name in ["parsing-csv", "parsing-json", "parsing-xml"] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Parses structured data files into tables.\n---\nBody.\n"),
+        )
+        .unwrap();
+    }
+    root
+}
+
+#[test]
+fn check_summary_report_counts_codes_across_skills() {
+    let root = make_untriggered_collection();
+    aigent()
+        .args([
+            "check",
+            root.path().to_str().unwrap(),
+            "--recursive",
+            "--summary-report",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Diagnostics by frequency:"))
+        .stderr(predicate::str::is_match(r"I002\s+3\s+3").unwrap());
+}
+
+#[test]
+fn check_summary_report_json() {
+    let root = make_untriggered_collection();
+    let output = aigent()
+        .args([
+            "check",
+            root.path().to_str().unwrap(),
+            "--recursive",
+            "--summary-report",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["skills"].as_array().unwrap().len(), 3);
+    let summary = json["summary"].as_array().unwrap();
+    let i002 = summary.iter().find(|e| e["code"] == "I002").unwrap();
+    assert_eq!(i002["count"], 3);
+    assert_eq!(i002["skills"], 3);
+}
+
 #[test]
 fn check_json_format() {
     let (_parent, dir) = make_skill_dir(
@@ -627,10 +1045,41 @@ fn validate_multiple_dirs_with_errors() {
     aigent()
         .args(["validate", d1.to_str().unwrap(), d2.to_str().unwrap()])
         .assert()
-        .failure() // any error → failure
+        .code(1) // any error → failure
         .stderr(predicate::str::contains("skills:"));
 }
 
+#[test]
+fn validate_conflict_scope_sibling_respects_plugin_boundaries() {
+    let root = tempdir().unwrap();
+    for (plugin, name) in [("docs", "pdf-extract"), ("forms", "pdf-extractor")] {
+        let dir = root.path().join(plugin).join("skills").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Extracts text from PDF files\n---\nBody.\n"),
+        )
+        .unwrap();
+    }
+    let root = root.path().to_str().unwrap();
+    aigent()
+        .args(["validate", root, "--recursive"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("description overlap"));
+    aigent()
+        .args([
+            "validate",
+            root,
+            "--recursive",
+            "--conflict-scope",
+            "sibling",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Cross-skill conflicts").not());
+}
+
 // ── --recursive flag ───────────────────────────────────────────────
 
 #[test]
@@ -664,7 +1113,7 @@ fn validate_recursive_no_skills_found() {
     aigent()
         .args(["validate", parent.path().to_str().unwrap(), "--recursive"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("No SKILL.md files found"));
 }
 
@@ -705,12 +1154,150 @@ fn validate_apply_fixes_xml_tags_in_description() {
     );
 }
 
-// ── recursive mode with file path ───────────────────────────────────
-
 #[test]
-fn validate_recursive_with_file_path_input() {
-    // Passing a SKILL.md file path with --recursive should resolve to
-    // the parent and discover skills from there.
+fn validate_apply_fixes_normalizes_crlf() {
+    let (_parent, dir) = make_skill_dir(
+        "test",
+        "---\r\nname: test\r\ndescription: A valid skill for testing\r\n---\r\nBody.\r\n",
+    );
+    aigent()
+        .args(["validate", dir.to_str().unwrap()])
+        .assert()
+        .stderr(predicate::str::contains("CRLF line endings"));
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--apply-fixes"])
+        .assert()
+        .stderr(predicate::str::contains("Applied"));
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(
+        !content.contains('\r'),
+        "CRLF should be normalized: {content:?}"
+    );
+}
+
+#[test]
+fn check_apply_fixes_fixes_description_spacing() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: \" Processes  files. Use when testing.\"\n---\nBody.\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap()])
+        .assert()
+        .stderr(predicate::str::contains("consecutive spaces"))
+        .stderr(predicate::str::contains("leading or trailing whitespace"));
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--apply-fixes"])
+        .assert()
+        .stderr(predicate::str::contains("Applied 2 fix(es)"))
+        .stderr(predicate::str::contains("consecutive spaces").not());
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(
+        content.contains("description: \"Processes files. Use when testing.\""),
+        "spacing should be fixed: {content}"
+    );
+}
+
+#[test]
+fn check_fix_interactive_applies_only_accepted_fixes() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: My-Skill\ndescription: Shows <b>bold</b> examples. Use when testing.\n---\nBody.\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--fix-interactive"])
+        .write_stdin("y\nn\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("+name: my-skill"))
+        .stderr(predicate::str::contains("1 fix(es) accepted, 1 rejected"));
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(content.contains("name: my-skill"));
+    assert!(
+        content.contains("<b>bold</b>"),
+        "rejected fix must not apply"
+    );
+}
+
+#[test]
+fn check_fix_interactive_without_input_errors_cleanly() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: My-Skill\ndescription: Does things. Use when testing.\n---\nBody.\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--fix-interactive"])
+        .write_stdin("")
+        .timeout(std::time::Duration::from_secs(10))
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("no answer on input"));
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(content.contains("name: My-Skill"));
+}
+
+#[test]
+fn check_fix_interactive_conflicts_with_apply_fixes() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .args([
+            "check",
+            dir.to_str().unwrap(),
+            "--fix-interactive",
+            "--apply-fixes",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn check_extract_reference_moves_long_body() {
+    let mut body = String::from("# Long\n\nIntro.\n\n## Usage\n\nRun it.\n\n## Details\n\n");
+    body.push_str(&"More detail here.\n".repeat(600));
+    let (_parent, dir) = make_skill_dir(
+        "long-skill",
+        &format!("---\nname: long-skill\ndescription: Does things. Use when needed.\n---\n{body}"),
+    );
+    aigent()
+        .args([
+            "check",
+            dir.to_str().unwrap(),
+            "--apply-fixes",
+            "--extract-reference",
+        ])
+        .assert()
+        .stderr(predicate::str::contains("REFERENCE.md"))
+        .stderr(predicate::str::contains("body exceeds 500 lines").not());
+    let skill = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    let reference = fs::read_to_string(dir.join("REFERENCE.md")).unwrap();
+    assert!(skill.contains("Run it."));
+    assert!(skill.contains("[REFERENCE.md](REFERENCE.md)"));
+    assert!(reference.starts_with("## Details\n"));
+    assert_eq!(reference.matches("More detail here.").count(), 600);
+}
+
+#[test]
+fn check_extract_reference_requires_apply_fixes() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .args(["check", dir.to_str().unwrap(), "--extract-reference"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--apply-fixes"));
+}
+
+// ── recursive mode with file path ───────────────────────────────────
+
+#[test]
+fn validate_recursive_with_file_path_input() {
+    // Passing a SKILL.md file path with --recursive should resolve to
+    // the parent and discover skills from there.
     let parent = tempdir().unwrap();
     let skill_a = parent.path().join("skill-a");
     let skill_b = parent.path().join("skill-b");
@@ -734,8 +1321,75 @@ fn validate_recursive_with_file_path_input() {
         .success();
 }
 
+#[test]
+fn validate_junit_reports_errors_as_failures() {
+    let (_p1, good) = make_skill_dir(
+        "junit-good",
+        "---\nname: junit-good\ndescription: Does things\n---\nBody.\n",
+    );
+    let (_p2, bad) = make_skill_dir("junit-bad", "---\nname: junit-bad\n---\nBody.\n");
+    let output = aigent()
+        .args([
+            "validate",
+            good.to_str().unwrap(),
+            bad.to_str().unwrap(),
+            "--format",
+            "junit",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert!(stdout.contains("<testsuite name=\"aigent validate\" tests=\"2\" failures=\"1\""));
+    assert!(stdout.contains(&format!("name=\"{}\"/>", good.display())));
+    assert!(stdout.contains("type=\"E018\""));
+    assert!(stdout.trim_end().ends_with("</testsuites>"));
+}
+
 // ── JSON output shape consistency ───────────────────────────────────
 
+#[test]
+fn validate_json_summary_wraps_results_with_counts() {
+    let parent = tempdir().unwrap();
+    for (name, content) in [
+        (
+            "clean-skill",
+            "---\nname: clean-skill\ndescription: Converts spreadsheets\n---\nBody.\n",
+        ),
+        (
+            "warned-skill",
+            "---\nname: warned-skill\ndescription: Summarizes meetings\nflavor: extra\n---\nBody.\n",
+        ),
+        (
+            "broken-skill",
+            "---\nname: wrong-name\ndescription: Deploys containers\n---\nBody.\n",
+        ),
+    ] {
+        let dir = parent.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+    }
+    let output = aigent()
+        .args([
+            "validate",
+            parent.path().to_str().unwrap(),
+            "--recursive",
+            "--format",
+            "json",
+            "--summary",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["skills"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        json["summary"],
+        serde_json::json!({ "total": 3, "errors": 1, "warnings": 1, "ok": 1 })
+    );
+}
+
 #[test]
 fn validate_json_shape_consistent_single_and_multi_dir() {
     // Both single-dir and multi-dir should produce the same JSON shape:
@@ -859,6 +1513,79 @@ fn to_prompt_budget_flag() {
         .stderr(predicate::str::contains("Total:"));
 }
 
+#[test]
+fn prompt_target_claude_code_adds_invocation() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\nBody.\n",
+    );
+    aigent()
+        .args(["prompt", dir.to_str().unwrap(), "--target", "claude-code"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("<skills_instructions>"))
+        .stdout(predicate::str::contains("Skill tool"))
+        .stdout(predicate::str::contains(
+            "<invocation>/my-skill</invocation>",
+        ));
+}
+
+#[test]
+fn prompt_and_doc_provenance_record_skill_md_hash() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\nBody.\n",
+    );
+    let sha256 = |args: &[&str]| {
+        let output = aigent().args(args).output().unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json[0]["sha256"].as_str().map(String::from)
+    };
+    let path = dir.to_str().unwrap();
+    assert_eq!(sha256(&["prompt", path, "--format", "json"]), None);
+    let hash = sha256(&["prompt", path, "--format", "json", "--provenance"]).unwrap();
+    assert_eq!(hash.len(), 64);
+    assert_eq!(
+        sha256(&["doc", path, "--format", "json", "--provenance"]).as_ref(),
+        Some(&hash)
+    );
+    aigent()
+        .args(["prompt", path, "--provenance"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "<skill sha256=\"{hash}\" mtime=\""
+        )));
+}
+
+#[test]
+fn prompt_target_standard_json_is_object() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\nBody.\n",
+    );
+    let output = aigent()
+        .args([
+            "prompt",
+            dir.to_str().unwrap(),
+            "--target",
+            "standard",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["target"], "standard");
+    assert!(json["instructions"]
+        .as_str()
+        .unwrap()
+        .contains("read the SKILL.md"));
+    assert_eq!(json["skills"][0]["name"], "my-skill");
+}
+
 // ── M11: to-prompt --output flag ──────────────────────────────────
 
 #[test]
@@ -877,7 +1604,7 @@ fn to_prompt_output_creates_file() {
             out_file.to_str().unwrap(),
         ])
         .assert()
-        .failure() // exit 1 = changed (file didn't exist)
+        .code(1) // exit 1 = changed (file didn't exist)
         .stderr(predicate::str::contains("Updated"));
     assert!(out_file.exists());
     let content = fs::read_to_string(&out_file).unwrap();
@@ -901,7 +1628,7 @@ fn to_prompt_output_unchanged_exit_zero() {
             out_file.to_str().unwrap(),
         ])
         .assert()
-        .failure();
+        .code(1);
     // Second run: same input, should be unchanged (exit 0).
     aigent()
         .args([
@@ -933,7 +1660,7 @@ fn to_prompt_output_with_format() {
             out_file.to_str().unwrap(),
         ])
         .assert()
-        .failure(); // exit 1 = changed
+        .code(1); // exit 1 = changed
     let content = fs::read_to_string(&out_file).unwrap();
     let json: serde_json::Value = serde_json::from_str(&content).unwrap();
     assert!(json.is_array());
@@ -983,13 +1710,57 @@ fn init_with_template_claude_code() {
     assert!(content.contains("user-invocable: true"));
 }
 
+#[test]
+fn init_with_template_mcp_server() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("issue-tracker");
+    aigent()
+        .args(["init", dir.to_str().unwrap(), "--template", "mcp-server"])
+        .assert()
+        .success();
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(content.contains("allowed-tools: mcp__issue-tracker__*"));
+    assert!(content.contains("MCP server"));
+    let stub: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.join("mcp.json")).unwrap()).unwrap();
+    assert!(stub["mcpServers"]["issue-tracker"].is_object());
+    aigent()
+        .args(["validate", dir.to_str().unwrap()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn new_with_template_mcp_server() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("issue-tracker");
+    aigent()
+        .args([
+            "new",
+            "Triage issues with the tracker server",
+            "--name",
+            "issue-tracker",
+            "--dir",
+            dir.to_str().unwrap(),
+            "--no-llm",
+            "--template",
+            "mcp-server",
+        ])
+        .assert()
+        .success();
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(content.contains("mcp__issue-tracker__*"));
+    assert!(content.contains("## Server setup"));
+    assert!(dir.join("mcp.json").exists());
+}
+
 // ── M12: score subcommand ──────────────────────────────────────────
 
 #[test]
 fn score_perfect_skill_exits_zero() {
     let (_parent, dir) = make_skill_dir(
         "processing-pdfs",
-        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\nBody.\n",
+        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n",
     );
     aigent()
         .args(["score", dir.to_str().unwrap()])
@@ -1007,7 +1778,7 @@ fn score_imperfect_skill_exits_nonzero() {
     aigent()
         .args(["score", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("Score:"));
 }
 
@@ -1015,7 +1786,7 @@ fn score_imperfect_skill_exits_nonzero() {
 fn score_json_format() {
     let (_parent, dir) = make_skill_dir(
         "processing-pdfs",
-        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\n---\nBody.\n",
+        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n",
     );
     let output = aigent()
         .args(["score", dir.to_str().unwrap(), "--format", "json"])
@@ -1034,81 +1805,608 @@ fn score_missing_skill_exits_nonzero() {
     aigent()
         .args(["score", parent.path().to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("Score:"));
 }
 
-// ── M12: --structure flag ──────────────────────────────────────────
-
 #[test]
-fn validate_structure_flag_accepted() {
+fn score_min_gate_passes_imperfect_skill() {
     let (_parent, dir) = make_skill_dir(
-        "my-skill",
-        "---\nname: my-skill\ndescription: A test skill\n---\nBody.\n",
+        "helper",
+        "---\nname: helper\ndescription: Helps\n---\nBody.\n",
     );
     aigent()
-        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .args(["score", dir.to_str().unwrap(), "--min", "0"])
         .assert()
         .success();
 }
 
+/// Create a parent directory holding one perfect and one weak skill.
+fn make_mixed_score_tree() -> tempfile::TempDir {
+    let parent = tempdir().unwrap();
+    let good = parent.path().join("processing-pdfs");
+    fs::create_dir(&good).unwrap();
+    fs::write(
+        good.join("SKILL.md"),
+        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n",
+    )
+    .unwrap();
+    let weak = parent.path().join("helper");
+    fs::create_dir(&weak).unwrap();
+    fs::write(
+        weak.join("SKILL.md"),
+        "---\nname: helper\ndescription: Helps\n---\nBody.\n",
+    )
+    .unwrap();
+    parent
+}
+
 #[test]
-fn validate_structure_detects_missing_reference() {
-    let (_parent, dir) = make_skill_dir(
-        "my-skill",
-        "---\nname: my-skill\ndescription: A test skill\n---\n\nSee [guide](guide.md) for details.\n",
-    );
+fn score_recursive_prints_summary() {
+    let parent = make_mixed_score_tree();
     aigent()
-        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .args(["score", parent.path().to_str().unwrap(), "--recursive"])
         .assert()
-        .success() // structure checks are warnings, not errors
-        .stderr(predicate::str::contains("warning:").and(predicate::str::contains("guide.md")));
+        .code(1)
+        .stderr(predicate::str::contains("Skills: 2"))
+        .stderr(predicate::str::contains("Below 100: 1"))
+        .stderr(predicate::str::contains("Lowest:"))
+        .stderr(predicate::str::contains("helper"));
 }
 
 #[test]
-fn validate_structure_clean_skill_no_warnings() {
-    let (_parent, dir) = make_skill_dir(
-        "my-skill",
-        "---\nname: my-skill\ndescription: A test skill\n---\n\nSee [guide](guide.md) for details.\n",
-    );
-    fs::write(dir.join("guide.md"), "# Guide").unwrap();
+fn score_recursive_min_gate_controls_exit() {
+    let parent = make_mixed_score_tree();
     aigent()
-        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .args([
+            "score",
+            parent.path().to_str().unwrap(),
+            "--recursive",
+            "--min",
+            "0",
+        ])
         .assert()
         .success()
-        .stderr(predicate::str::is_match(OK_LINE).unwrap());
+        .stderr(predicate::str::contains("Below 0: 0"));
 }
 
-// ── M12: doc subcommand ──────────────────────────────────────────
+#[test]
+fn score_recursive_json_has_skills_and_summary() {
+    let parent = make_mixed_score_tree();
+    let output = aigent()
+        .args([
+            "score",
+            parent.path().to_str().unwrap(),
+            "--recursive",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["skills"].as_array().unwrap().len(), 2);
+    assert_eq!(json["summary"]["count"], 2);
+    assert_eq!(json["summary"]["max"], 100);
+    assert_eq!(json["summary"]["below_gate"], 1);
+    assert_eq!(json["summary"]["lowest"][0]["name"], "helper");
+}
 
 #[test]
-fn doc_generates_markdown_catalog() {
+fn score_markdown_format_renders_table_and_total() {
     let (_parent, dir) = make_skill_dir(
-        "my-doc-skill",
-        "---\nname: my-doc-skill\ndescription: A documented skill\n---\nBody.\n",
+        "processing-pdfs",
+        "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n",
     );
     aigent()
-        .args(["doc", dir.to_str().unwrap()])
+        .args(["score", dir.to_str().unwrap(), "--format", "markdown"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("# Skill Catalog"))
-        .stdout(predicate::str::contains("## my-doc-skill"))
-        .stdout(predicate::str::contains("A documented skill"));
+        .stdout(predicate::str::contains(
+            "| Category | Check | Points | Status |\n|----------|",
+        ))
+        .stdout(predicate::str::contains("**Total: 100/100**"));
 }
 
 #[test]
-fn doc_no_args_defaults_to_current_dir() {
-    // With default_value = ".", `doc` without args uses the current directory.
-    // From a non-skill directory, it produces an empty catalog with a warning.
-    aigent()
-        .arg("doc")
-        .assert()
-        .success()
-        .stderr(predicate::str::contains("cannot read skill properties"));
+fn score_recursive_markdown_has_section_per_skill() {
+    let parent = make_mixed_score_tree();
+    let output = aigent()
+        .args([
+            "score",
+            parent.path().to_str().unwrap(),
+            "--recursive",
+            "--format",
+            "markdown",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("## processing-pdfs (100/100)"), "{stdout}");
+    assert!(stdout.contains("## helper ("), "{stdout}");
+    assert_eq!(stdout.matches("| Category | Check |").count(), 2);
+    assert!(stdout.contains("## Summary"));
 }
 
+const SCORE_PERFECT: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n";
+const SCORE_NO_TRIGGER: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports\n  about documents.\nmetadata:\n  version: 1.0.0\n---\nBody.\n";
+
 #[test]
-fn doc_output_writes_file() {
+fn score_against_reports_gained_checks() {
+    let (_old_parent, old) = make_skill_dir("processing-pdfs", SCORE_NO_TRIGGER);
+    let (_new_parent, new) = make_skill_dir("processing-pdfs", SCORE_PERFECT);
+    aigent()
+        .args([
+            "score",
+            new.to_str().unwrap(),
+            "--against",
+            old.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("**Score changed 94 → 100 (+6)**"))
+        .stderr(predicate::str::contains("- Trigger phrase present (+6)"));
+}
+
+#[test]
+fn score_against_json_has_deltas() {
+    let (_old_parent, old) = make_skill_dir("processing-pdfs", SCORE_PERFECT);
+    let (_new_parent, new) = make_skill_dir("processing-pdfs", SCORE_NO_TRIGGER);
+    let output = aigent()
+        .args([
+            "score",
+            new.to_str().unwrap(),
+            "--against",
+            old.to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "new revision is below the gate"
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["delta"], -6);
+    assert_eq!(json["lost"][0]["label"], "Trigger phrase present");
+    assert_eq!(json["lost"][0]["points"], -6);
+    assert!(json.get("note").is_none());
+}
+
+#[test]
+fn score_against_missing_old_revision_scores_zero() {
+    let (_new_parent, new) = make_skill_dir("processing-pdfs", SCORE_PERFECT);
+    let missing = tempdir().unwrap();
+    aigent()
+        .args([
+            "score",
+            new.to_str().unwrap(),
+            "--against",
+            missing.path().join("gone").to_str().unwrap(),
+            "--format",
+            "markdown",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 → 100 (+100)"))
+        .stdout(predicate::str::contains(
+            "> Note: old revision could not be parsed",
+        ));
+}
+
+#[test]
+fn score_against_conflicts_with_recursive() {
+    let (_parent, dir) = make_skill_dir("processing-pdfs", SCORE_PERFECT);
+    aigent()
+        .args([
+            "score",
+            dir.to_str().unwrap(),
+            "--recursive",
+            "--against",
+            dir.to_str().unwrap(),
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn score_min_out_of_range_rejected() {
+    aigent().args(["score", "--min", "101"]).assert().code(2);
+}
+
+// ── bisect ──────────────────────────────────────────────────────────
+
+/// Run `git` in `dir` with a fixed identity, asserting success.
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args([
+            "-c",
+            "user.name=Test",
+            "-c",
+            "user.email=test@example.com",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// A repository where the skill passes, is renamed, then breaks.
+fn make_history_repo() -> tempfile::TempDir {
+    let repo = tempdir().unwrap();
+    let root = repo.path();
+    git(root, &["init", "-q"]);
+    fs::create_dir(root.join("old-skill")).unwrap();
+    fs::write(
+        root.join("old-skill/SKILL.md"),
+        "---\nname: my-skill\ndescription: Processes files. Use when working with files.\n---\nBody.\n",
+    )
+    .unwrap();
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "Add skill"]);
+    git(root, &["mv", "old-skill", "new-skill"]);
+    git(root, &["commit", "-q", "-m", "Rename skill directory"]);
+    fs::write(
+        root.join("new-skill/SKILL.md"),
+        "---\nname: My Skill\ndescription: Processes files. Use when working with files.\n---\nBody.\n",
+    )
+    .unwrap();
+    git(root, &["commit", "-q", "-am", "Break the name"]);
+    repo
+}
+
+#[test]
+fn bisect_finds_regression_across_rename() {
+    let repo = make_history_repo();
+    let dir = repo.path().join("new-skill");
+    aigent()
+        .args(["bisect", dir.to_str().unwrap(), "--metric", "validate"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Examined 3 revision(s)"))
+        .stdout(predicate::str::contains(
+            "Break the name: validation errors 0 ->",
+        ))
+        .stdout(predicate::str::contains("Last passing:"))
+        .stdout(predicate::str::contains("Rename skill directory"));
+}
+
+#[test]
+fn bisect_json_reports_paths_before_and_after_rename() {
+    let repo = make_history_repo();
+    let dir = repo.path().join("new-skill");
+    let output = aigent()
+        .args(["bisect", dir.to_str().unwrap(), "--metric", "validate"])
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let revisions = json["revisions"].as_array().unwrap();
+    assert_eq!(revisions[0]["path"], "old-skill/SKILL.md");
+    assert_eq!(json["regression"]["before"]["path"], "new-skill/SKILL.md");
+    assert_eq!(json["regression"]["after"]["subject"], "Break the name");
+}
+
+#[test]
+fn bisect_since_and_max_revisions_limit_history() {
+    let repo = make_history_repo();
+    let dir = repo.path().join("new-skill");
+    aigent()
+        .args(["bisect", dir.to_str().unwrap(), "--metric", "validate"])
+        .args(["--since", "HEAD~1"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Examined 1 revision(s)"))
+        .stdout(predicate::str::contains("Already failing"));
+    aigent()
+        .args(["bisect", dir.to_str().unwrap(), "--metric", "validate"])
+        .args(["--max-revisions", "2"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Examined 2 revision(s)"))
+        .stdout(predicate::str::contains("Break the name"));
+}
+
+#[test]
+fn bisect_outside_git_repository_fails() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .args(["bisect", dir.to_str().unwrap()])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("aigent bisect: "));
+}
+
+// ── M12: --structure flag ──────────────────────────────────────────
+
+#[test]
+fn validate_structure_flag_accepted() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\nBody.\n",
+    );
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn validate_structure_detects_missing_reference() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\n\nSee [guide](guide.md) for details.\n",
+    );
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .assert()
+        .success() // structure checks are warnings, not errors
+        .stderr(predicate::str::contains("warning:").and(predicate::str::contains("guide.md")));
+}
+
+#[test]
+fn validate_structure_clean_skill_no_warnings() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\n\nSee [guide](guide.md) for details.\n",
+    );
+    fs::write(dir.join("guide.md"), "# Guide").unwrap();
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_match(OK_LINE).unwrap());
+}
+
+#[test]
+fn validate_check_examples_reports_broken_example() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: A test skill\n---\n\nSee [request](examples/request.json).\n",
+    );
+    fs::create_dir(dir.join("examples")).unwrap();
+    fs::write(dir.join("examples/request.json"), "{\"a\": }").unwrap();
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--structure"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_match(OK_LINE).unwrap());
+    aigent()
+        .args([
+            "validate",
+            dir.to_str().unwrap(),
+            "--structure",
+            "--check-examples",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "example 'examples/request.json' is not valid JSON",
+        ));
+}
+
+#[test]
+fn check_examples_requires_structure() {
+    aigent()
+        .args(["check", ".", "--check-examples"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--structure"));
+}
+
+// ── validate --allow-placeholders / --substitute ────────────────────
+
+const TEMPLATED_SKILL: &str = "---\nname: ${TEAM}-reports\ndescription: Builds reports for ${TEAM}. Use when asked for reports.\n---\nBody.\n";
+
+#[test]
+fn validate_placeholder_name_fails_by_default() {
+    let (_parent, dir) = make_skill_dir("team-reports", TEMPLATED_SKILL);
+    aigent()
+        .args(["validate", dir.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("invalid character"));
+}
+
+#[test]
+fn validate_allow_placeholders_lists_them() {
+    let (_parent, dir) = make_skill_dir("team-reports", TEMPLATED_SKILL);
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--allow-placeholders"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "placeholders to provide at deploy time: ${TEAM}",
+        ));
+}
+
+#[test]
+fn validate_substitute_checks_post_deploy_shape() {
+    let (_parent, dir) = make_skill_dir("team-reports", TEMPLATED_SKILL);
+    let long = format!("TEAM={}", "a".repeat(1100));
+    aigent()
+        .args(["validate", dir.to_str().unwrap(), "--substitute", &long])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "description exceeds 1024 characters",
+        ));
+    aigent()
+        .args([
+            "validate",
+            dir.to_str().unwrap(),
+            "--substitute",
+            "TEAM=team",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn validate_substitute_rejects_malformed_pair() {
+    aigent()
+        .args(["validate", ".", "--substitute", "TEAM"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("expected KEY=VALUE"));
+}
+
+// ── validate --cache ────────────────────────────────────────────────
+
+#[test]
+fn validate_cache_reuses_results_for_unchanged_skill() {
+    let (parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\nmystery: 1\n---\nBody.\n",
+    );
+    let run = || {
+        aigent()
+            .current_dir(parent.path())
+            .args([
+                "validate",
+                dir.to_str().unwrap(),
+                "--cache",
+                "--format",
+                "json",
+            ])
+            .output()
+            .unwrap()
+    };
+    let first = run();
+    let cache = parent.path().join(".aigent-cache");
+    let entries = || fs::read_dir(&cache).unwrap().count();
+    assert_eq!(entries(), 1, "first run should store one entry");
+    let second = run();
+    assert_eq!(first.stdout, second.stdout);
+    assert_eq!(entries(), 1, "unchanged skill should reuse its entry");
+    assert!(String::from_utf8_lossy(&second.stdout).contains("mystery"));
+}
+
+#[test]
+fn validate_no_cache_overrides_cache() {
+    let (parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .current_dir(parent.path())
+        .args(["validate", dir.to_str().unwrap(), "--cache", "--no-cache"])
+        .assert()
+        .success();
+    assert!(!parent.path().join(".aigent-cache").exists());
+}
+
+// ── validate --index ────────────────────────────────────────────────
+
+/// Create a collection root with `alpha` and `beta` skills and an INDEX.md.
+fn make_indexed_collection(index: &str) -> tempfile::TempDir {
+    let root = tempdir().unwrap();
+    for name in ["alpha", "beta"] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Does things\n---\nBody.\n"),
+        )
+        .unwrap();
+    }
+    fs::write(root.path().join("INDEX.md"), index).unwrap();
+    root
+}
+
+#[test]
+fn validate_index_warns_skill_missing_from_index() {
+    let root = make_indexed_collection("- [Alpha](alpha/SKILL.md)\n");
+    aigent()
+        .args(["validate", root.path().to_str().unwrap(), "--index"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "skill missing from INDEX.md: 'beta'",
+        ));
+}
+
+#[test]
+fn validate_index_json_includes_index_entry() {
+    let root =
+        make_indexed_collection("- [Alpha](alpha/SKILL.md)\n- [Beta](beta/)\n- [Gone](gone/)\n");
+    let output = aigent()
+        .args([
+            "validate",
+            root.path().to_str().unwrap(),
+            "--index",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    let index_entry = entries
+        .iter()
+        .find(|e| e["path"].as_str().unwrap().ends_with("INDEX.md"))
+        .expect("index entry present");
+    assert_eq!(index_entry["diagnostics"][0]["code"], "S008");
+}
+
+// ── M12: doc subcommand ──────────────────────────────────────────
+
+#[test]
+fn doc_generates_markdown_catalog() {
+    let (_parent, dir) = make_skill_dir(
+        "my-doc-skill",
+        "---\nname: my-doc-skill\ndescription: A documented skill\n---\nBody.\n",
+    );
+    aigent()
+        .args(["doc", dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Skill Catalog"))
+        .stdout(predicate::str::contains("## my-doc-skill"))
+        .stdout(predicate::str::contains("A documented skill"));
+}
+
+#[test]
+fn doc_compact_lists_first_sentences() {
+    let (_parent, dir) = make_skill_dir(
+        "my-doc-skill",
+        "---\nname: my-doc-skill\ndescription: A documented skill. Use when documenting.\n---\nBody.\n",
+    );
+    aigent()
+        .args(["doc", dir.to_str().unwrap(), "--compact"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- **my-doc-skill** — A documented skill.\n",
+        ))
+        .stdout(predicate::str::contains("Use when").not());
+}
+
+#[test]
+fn doc_no_args_defaults_to_current_dir() {
+    // With default_value = ".", `doc` without args uses the current directory.
+    // From a non-skill directory, it produces an empty catalog with a warning.
+    aigent()
+        .arg("doc")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("cannot read skill properties"));
+}
+
+#[test]
+fn doc_output_writes_file() {
     let (_parent, dir) = make_skill_dir(
         "doc-out-skill",
         "---\nname: doc-out-skill\ndescription: Outputs to file\n---\nBody.\n",
@@ -1148,18 +2446,139 @@ fn doc_output_unchanged_on_rerun() {
         ])
         .assert()
         .success()
-        .stderr(predicate::str::contains("Updated"));
-    // Second run: content unchanged → "Unchanged".
+        .stderr(predicate::str::contains("Updated"));
+    // Second run: content unchanged → "Unchanged".
+    aigent()
+        .args([
+            "doc",
+            dir.to_str().unwrap(),
+            "--output",
+            outfile.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unchanged"));
+}
+
+#[test]
+fn doc_format_json_lists_catalog_entries() {
+    let (_parent, dir) = make_skill_dir(
+        "json-doc-skill",
+        "---\nname: json-doc-skill\ndescription: A documented skill\nlicense: MIT\n---\nBody.\n",
+    );
+    let output = aigent()
+        .args(["doc", dir.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = json.as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["name"], "json-doc-skill");
+    assert_eq!(entries[0]["license"], "MIT");
+    assert!(entries[0].get("compatibility").is_none());
+    assert_eq!(entries[0]["body"]["lines"], 1);
+}
+
+#[test]
+fn doc_format_json_output_is_diff_aware() {
+    let (_parent, dir) = make_skill_dir(
+        "json-stable",
+        "---\nname: json-stable\ndescription: Stable\n---\nBody.\n",
+    );
+    let outdir = tempdir().unwrap();
+    let outfile = outdir.path().join("catalog.json");
+    let args = [
+        "doc",
+        dir.to_str().unwrap(),
+        "--format",
+        "json",
+        "--output",
+        outfile.to_str().unwrap(),
+    ];
+    aigent()
+        .args(args)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Updated"));
+    let content = fs::read_to_string(&outfile).unwrap();
+    assert!(serde_json::from_str::<serde_json::Value>(&content).is_ok());
+    aigent()
+        .args(args)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unchanged"));
+}
+
+#[test]
+fn doc_index_writes_json_sidecar_diff_aware() {
+    let (_parent, dir) = make_skill_dir(
+        "indexed",
+        "---\nname: indexed\ndescription: Indexed skill\nmetadata:\n  tags: [pdf, forms]\n---\nBody.\n",
+    );
+    let outdir = tempdir().unwrap();
+    let index = outdir.path().join("docs/catalog.json");
+    let args = [
+        "doc",
+        dir.to_str().unwrap(),
+        "--index",
+        index.to_str().unwrap(),
+    ];
+    aigent()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Skill Catalog"))
+        .stderr(predicate::str::contains(format!(
+            "Updated {}",
+            index.display()
+        )));
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&index).unwrap()).unwrap();
+    assert_eq!(json[0]["name"], "indexed");
+    assert_eq!(json[0]["description"], "Indexed skill");
+    assert_eq!(json[0]["tags"], serde_json::json!(["pdf", "forms"]));
+    assert!(json[0].get("body").is_none());
+    aigent()
+        .args(args)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Unchanged {}",
+            index.display()
+        )));
+}
+
+#[cfg(unix)]
+#[test]
+fn validate_recursive_follow_symlinks_discovers_linked_skill() {
+    let shared = tempdir().unwrap();
+    let skill = shared.path().join("linked-skill");
+    fs::create_dir(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: linked-skill\ndescription: Shared through a symlink\n---\nBody.\n",
+    )
+    .unwrap();
+    let root = tempdir().unwrap();
+    std::os::unix::fs::symlink(&skill, root.path().join("linked-skill")).unwrap();
+    std::os::unix::fs::symlink(root.path(), skill.join("loop")).unwrap();
+
+    aigent()
+        .args(["validate", root.path().to_str().unwrap(), "--recursive"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No SKILL.md files found"));
     aigent()
         .args([
-            "doc",
-            dir.to_str().unwrap(),
-            "--output",
-            outfile.to_str().unwrap(),
+            "validate",
+            root.path().to_str().unwrap(),
+            "--recursive",
+            "--follow-symlinks",
         ])
+        .timeout(std::time::Duration::from_secs(10))
         .assert()
-        .success()
-        .stderr(predicate::str::contains("Unchanged"));
+        .success();
 }
 
 #[test]
@@ -1246,6 +2665,59 @@ fn probe_skill_json_format() {
     assert!(json["estimated_tokens"].as_u64().unwrap() > 0);
 }
 
+#[test]
+fn probe_json_includes_token_breakdown() {
+    let (_parent, dir) = make_skill_dir(
+        "token-split",
+        "---\nname: token-split\ndescription: Processes PDF files\n---\nA body with several words in it.\n",
+    );
+    let output = aigent()
+        .args([
+            "probe",
+            dir.to_str().unwrap(),
+            "--query",
+            "process PDF",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let breakdown = &json["estimated_tokens_breakdown"];
+    let name = breakdown["name"].as_u64().unwrap();
+    let description = breakdown["description"].as_u64().unwrap();
+    assert!(breakdown["body"].as_u64().unwrap() > 0);
+    assert_eq!(
+        json["estimated_tokens"].as_u64().unwrap(),
+        name + description
+    );
+}
+
+#[test]
+fn probe_exact_disables_stemming() {
+    let (_parent, dir) = make_skill_dir(
+        "pdf-stem",
+        "---\nname: pdf-stem\ndescription: Processes PDF files\n---\nBody.\n",
+    );
+    aigent()
+        .args(["probe", dir.to_str().unwrap(), "--query", "processing pdfs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("STRONG"));
+    aigent()
+        .args([
+            "probe",
+            dir.to_str().unwrap(),
+            "--query",
+            "processing pdfs",
+            "--exact",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NONE"));
+}
+
 #[test]
 fn probe_wraps_long_description_aligned() {
     let long_desc = "Validates AI agent skill definitions against the Anthropic agent \
@@ -1280,7 +2752,165 @@ fn probe_skill_missing_dir_exits_nonzero() {
     aigent()
         .args(["probe", "/nonexistent/skill", "--query", "some query"])
         .assert()
-        .failure();
+        .code(1);
+}
+
+#[test]
+fn probe_record_builds_tests_yml_for_review() {
+    let (_parent, dir) = make_skill_dir(
+        "pdf-tools",
+        "---\nname: pdf-tools\ndescription: Extracts text from PDF files. Use when working with PDF documents.\n---\nBody\n",
+    );
+    let tests_yml = dir.join("tests.yml");
+    let probe = |query: &str| {
+        aigent()
+            .args(["probe", dir.to_str().unwrap(), "-q", query, "--record"])
+            .arg(&tests_yml)
+            .assert()
+            .success()
+    };
+    probe("extract text from a PDF file").stderr(predicate::str::contains(
+        "(should_match: true, pending review)",
+    ));
+    probe("bake sourdough bread").stderr(predicate::str::contains("should_match: false"));
+    probe("bake sourdough bread").stderr(predicate::str::contains("not recorded"));
+    let content = fs::read_to_string(&tests_yml).unwrap();
+    assert!(
+        content.starts_with("# Test fixture for pdf-tools\n"),
+        "{content}"
+    );
+    assert_eq!(content.matches("bake sourdough bread").count(), 1);
+
+    // Recorded cases match the probe results, so the suite passes.
+    aigent()
+        .args(["test", dir.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("2 passed, 0 failed"));
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--review-recorded"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tests.yml:"))
+        .stdout(predicate::str::contains(
+            "  [match] extract text from a PDF file",
+        ))
+        .stdout(predicate::str::contains(
+            "  [no match] bake sourdough bread",
+        ));
+
+    // Deleting the markers marks the cases as reviewed.
+    fs::write(
+        &tests_yml,
+        content.replace(&format!("{}\n", aigent::RECORDED_MARKER), ""),
+    )
+    .unwrap();
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--review-recorded"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("No recorded cases pending review.\n"));
+}
+
+#[test]
+fn probe_record_rejects_file_outside_probed_skills() {
+    let (_p1, a) = make_skill_dir("skill-a", "---\nname: skill-a\ndescription: A\n---\n");
+    let (_p2, b) = make_skill_dir("skill-b", "---\nname: skill-b\ndescription: B\n---\n");
+    let elsewhere = tempdir().unwrap();
+    let tests_yml = elsewhere.path().join("tests.yml");
+    aigent()
+        .args(["probe", a.to_str().unwrap(), b.to_str().unwrap(), "-q", "x"])
+        .arg("--record")
+        .arg(&tests_yml)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "not in any probed skill directory",
+        ));
+    assert!(!tests_yml.exists());
+}
+
+#[test]
+fn probe_suggest_ranks_missing_terms_from_queries_file() {
+    let (parent, dir) = make_skill_dir(
+        "pdf-tools",
+        "---\nname: pdf-tools\ndescription: Extracts text from PDF files. Use when working with PDF documents.\n---\nBody\n",
+    );
+    let queries = parent.path().join("queries.txt");
+    fs::write(
+        &queries,
+        "# observed queries\nmerge two pdf files\nmerge scanned invoices\nextract text from pdf\n",
+    )
+    .unwrap();
+    aigent()
+        .args([
+            "probe",
+            dir.to_str().unwrap(),
+            "--suggest",
+            "--no-llm",
+            "--queries",
+            queries.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 of 3 queries match strongly"))
+        .stdout(predicate::str::contains("Missing terms"))
+        .stdout(predicate::str::contains("  merge     2"))
+        .stdout(predicate::str::contains(
+            "Suggested description (deterministic",
+        ))
+        .stdout(predicate::str::contains(
+            "Use when working with PDF documents, or when the request mentions merge",
+        ));
+}
+
+#[test]
+fn probe_suggest_reads_tests_yml_and_emits_json() {
+    let (_parent, dir) = make_skill_dir(
+        "pdf-tools",
+        "---\nname: pdf-tools\ndescription: Extracts text from PDF files. Use when working with PDF documents.\n---\nBody\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"merge scanned invoices\"\n    should_match: true\n  - input: \"bake a cake\"\n    should_match: false\n",
+    )
+    .unwrap();
+    let output = aigent()
+        .args([
+            "probe",
+            dir.to_str().unwrap(),
+            "--suggest",
+            "--no-llm",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["queries"], 1);
+    assert_eq!(json["terms"][0]["term"], "invoices");
+    assert_eq!(json["draft"]["matched"], 1);
+    assert_eq!(json["draft"]["source"], "deterministic");
+}
+
+#[test]
+fn probe_queries_requires_suggest() {
+    let (_parent, dir) = make_skill_dir(
+        "pdf-tools",
+        "---\nname: pdf-tools\ndescription: Extracts text from PDF files.\n---\nBody\n",
+    );
+    aigent()
+        .args([
+            "probe",
+            dir.to_str().unwrap(),
+            "--query",
+            "merge pdfs",
+            "--queries",
+            "q.txt",
+        ])
+        .assert()
+        .code(2);
 }
 
 // ── M12: upgrade subcommand ──────────────────────────────────────
@@ -1294,7 +2924,7 @@ fn upgrade_detects_missing_compatibility() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("[fix] U001"))
         .stderr(predicate::str::contains("compatibility"));
 }
@@ -1308,7 +2938,7 @@ fn upgrade_detects_missing_trigger_phrase() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("[info] U002"))
         .stderr(predicate::str::contains("trigger phrase"));
 }
@@ -1342,6 +2972,48 @@ fn upgrade_apply_modifies_skill() {
     assert!(content.contains("compatibility"));
 }
 
+#[test]
+fn upgrade_apply_works_with_scalar_metadata() {
+    let (_parent, dir) = make_skill_dir(
+        "upgrade-scalar-meta",
+        "---\nname: upgrade-scalar-meta\ndescription: A basic skill\nmetadata: just a string\n---\nBody.\n",
+    );
+    aigent()
+        .args(["upgrade", dir.to_str().unwrap(), "--apply"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Applied"));
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert_eq!(
+        content,
+        "---\nname: upgrade-scalar-meta\ndescription: A basic skill\nmetadata: just a string\ncompatibility: claude-code\n---\nBody.\n"
+    );
+    aigent()
+        .args(["validate", dir.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "`metadata` must be a mapping, not a string",
+        ));
+}
+
+#[test]
+fn upgrade_apply_keeps_dashes_line_in_block_scalar() {
+    let (_parent, dir) = make_skill_dir(
+        "upgrade-dashes",
+        "---\nname: upgrade-dashes\ndescription: |\n  Before.\n  ---\n  After.\n---\nBody.\n",
+    );
+    aigent()
+        .args(["upgrade", dir.to_str().unwrap(), "--apply"])
+        .assert()
+        .success();
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert_eq!(
+        content,
+        "---\nname: upgrade-dashes\ndescription: |\n  Before.\n  ---\n  After.\ncompatibility: claude-code\n---\nBody.\n"
+    );
+}
+
 #[test]
 fn upgrade_full_reports_suggestions() {
     let (_parent, dir) = make_skill_dir(
@@ -1352,7 +3024,7 @@ fn upgrade_full_reports_suggestions() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap(), "--full"])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("[fix] U001"))
         .stderr(predicate::str::contains("compatibility"));
 }
@@ -1406,7 +3078,7 @@ fn upgrade_summary_counts_fixes_and_info_separately() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("1 fix(es)"))
         .stderr(predicate::str::contains("informational suggestion(s)"));
 }
@@ -1421,7 +3093,7 @@ fn upgrade_dry_run_accepted() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap(), "--dry-run"])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("[fix] U001"));
 }
 
@@ -1434,7 +3106,7 @@ fn upgrade_dry_run_conflicts_with_apply() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap(), "--dry-run", "--apply"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("cannot be used with"));
 }
 
@@ -1493,7 +3165,7 @@ fn upgrade_full_exits_failure_on_validation_errors() {
     aigent()
         .args(["upgrade", dir.to_str().unwrap(), "--full"])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("[full] error"));
 }
 
@@ -1748,7 +3420,7 @@ fn build_with_validate_rejects_invalid() {
             "--validate",
         ])
         .assert()
-        .failure();
+        .code(1);
 }
 
 #[test]
@@ -1776,6 +3448,182 @@ fn build_plugin_json_valid() {
     assert_eq!(json["version"], "0.1.0");
 }
 
+#[test]
+fn build_bundle_shared_copies_linked_files() {
+    let parent = tempdir().unwrap();
+    fs::create_dir_all(parent.path().join("shared")).unwrap();
+    fs::write(parent.path().join("shared/style.md"), "Style").unwrap();
+    let dir = parent.path().join("my-skill");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: my-skill\ndescription: Does things\n---\nSee [style](../shared/style.md).\n",
+    )
+    .unwrap();
+    let out_dir = parent.path().join("plugin");
+    aigent()
+        .args([
+            "build",
+            dir.to_str().unwrap(),
+            "--output",
+            out_dir.to_str().unwrap(),
+            "--bundle-shared",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("escapes").not());
+    assert!(out_dir.join("resources/style.md").exists());
+    let md = fs::read_to_string(out_dir.join("skills/my-skill/SKILL.md")).unwrap();
+    assert!(md.contains("(../../resources/style.md)"));
+}
+
+#[test]
+fn build_warns_on_escaping_link_without_bundle_shared() {
+    let parent = tempdir().unwrap();
+    let dir = parent.path().join("my-skill");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: my-skill\ndescription: Does things\n---\nSee [gone](../missing.md).\n",
+    )
+    .unwrap();
+    let out_dir = parent.path().join("plugin");
+    aigent()
+        .args([
+            "build",
+            dir.to_str().unwrap(),
+            "--output",
+            out_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "link '../missing.md' escapes the skill directory",
+        ));
+}
+
+#[test]
+fn build_manifest_out_lists_each_skill() {
+    let parent = tempdir().unwrap();
+    let mut dirs = Vec::new();
+    for name in ["skill-a", "skill-b"] {
+        let dir = parent.path().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Does things\n---\nBody.\n"),
+        )
+        .unwrap();
+        dirs.push(dir);
+    }
+    let out_dir = parent.path().join("plugin");
+    let manifest = parent.path().join("manifest.json");
+    aigent()
+        .args([
+            "build",
+            dirs[0].to_str().unwrap(),
+            dirs[1].to_str().unwrap(),
+            "--output",
+            out_dir.to_str().unwrap(),
+            "--name",
+            "my-plugin",
+            "--manifest-out",
+            manifest.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).unwrap()).unwrap();
+    assert_eq!(json["name"], "my-plugin");
+    assert_eq!(json["version"], "0.1.0");
+    let skills = json["skills"].as_array().unwrap();
+    let names: Vec<&str> = skills.iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert_eq!(names, ["skill-a", "skill-b"]);
+    assert_eq!(skills[0]["files"][0]["path"], "SKILL.md");
+    assert_eq!(skills[0]["files"][0]["hash"].as_str().unwrap().len(), 64);
+    assert_eq!(json["hash_algorithm"], "sha256");
+}
+
+#[test]
+fn build_exclude_leaves_out_matching_files() {
+    let (parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    fs::write(dir.join("scratch.tmp"), "x").unwrap();
+    fs::write(dir.join("EXAMPLES.md"), "# Examples\n").unwrap();
+    let out_dir = parent.path().join("plugin");
+    aigent()
+        .args([
+            "build",
+            dir.to_str().unwrap(),
+            "--output",
+            out_dir.to_str().unwrap(),
+            "--exclude",
+            "*.tmp",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Excluded 1 file(s)"));
+    assert!(!out_dir.join("skills/my-skill/scratch.tmp").exists());
+    assert!(out_dir.join("skills/my-skill/EXAMPLES.md").exists());
+}
+
+#[test]
+fn build_namespace_prefixes_skill_and_replaces_previous_output() {
+    let (parent, dir) = make_skill_dir(
+        "helper",
+        "---\nname: helper\ndescription: Does things\n---\nBody.\n",
+    );
+    let out_dir = parent.path().join("plugin");
+    let build = |extra: &[&str]| {
+        let mut args = vec!["build", dir.to_str().unwrap(), "--output"];
+        args.push(out_dir.to_str().unwrap());
+        args.extend(extra);
+        aigent().args(args).assert().success()
+    };
+    build(&[]).stderr(predicate::str::contains("Replaced").not());
+    build(&["--namespace", "acme"]).stderr(predicate::str::contains("Replaced previous output in"));
+    assert!(!out_dir.join("skills/helper").exists());
+    let skill_md = fs::read_to_string(out_dir.join("skills/acme-helper/SKILL.md")).unwrap();
+    assert!(skill_md.contains("name: acme-helper\n"), "{skill_md}");
+}
+
+#[test]
+fn build_copies_tests_yml_unless_no_tests() {
+    let (parent, dir) = make_skill_dir(
+        "my-skill",
+        "---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    fs::write(dir.join("tests.yml"), "queries: oops\n").unwrap();
+    let out_dir = parent.path().join("plugin");
+    aigent()
+        .args([
+            "build",
+            dir.to_str().unwrap(),
+            "--output",
+            out_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("invalid tests.yml"));
+    assert!(out_dir.join("skills/my-skill/tests.yml").exists());
+
+    let bare_dir = parent.path().join("bare");
+    aigent()
+        .args([
+            "build",
+            dir.to_str().unwrap(),
+            "--output",
+            bare_dir.to_str().unwrap(),
+            "--no-tests",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("tests.yml").not());
+    assert!(!bare_dir.join("skills/my-skill/tests.yml").exists());
+}
+
 // ── M13: fmt subcommand (#76) ────────────────────────────────────
 
 #[test]
@@ -1814,6 +3662,37 @@ fn fmt_reorders_keys() {
     );
 }
 
+#[test]
+fn fmt_removes_comment_preamble() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "<!-- editor -->\n\n---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .args(["fmt", dir.to_str().unwrap()])
+        .assert()
+        .success();
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(content.starts_with("---\nname: my-skill"), "got: {content}");
+}
+
+#[test]
+fn fmt_keep_preamble_preserves_comment() {
+    let (_parent, dir) = make_skill_dir(
+        "my-skill",
+        "<!-- editor -->\n\n---\nname: my-skill\ndescription: Does things\n---\nBody.\n",
+    );
+    aigent()
+        .args(["fmt", dir.to_str().unwrap(), "--keep-preamble"])
+        .assert()
+        .success();
+    let content = fs::read_to_string(dir.join("SKILL.md")).unwrap();
+    assert!(
+        content.starts_with("<!-- editor -->\n---\nname: my-skill"),
+        "got: {content}"
+    );
+}
+
 #[test]
 fn fmt_check_unformatted_exits_nonzero() {
     let (_parent, dir) = make_skill_dir(
@@ -1823,7 +3702,7 @@ fn fmt_check_unformatted_exits_nonzero() {
     aigent()
         .args(["fmt", dir.to_str().unwrap(), "--check"])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("Would reformat"))
         .stderr(predicate::str::contains("---"))
         .stderr(predicate::str::contains("+++"))
@@ -1845,7 +3724,7 @@ fn fmt_check_shows_diff_content() {
     aigent()
         .args(["fmt", dir.to_str().unwrap(), "--check"])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("-allowed-tools"))
         .stderr(predicate::str::contains("+allowed-tools"));
 }
@@ -1913,6 +3792,192 @@ fn format_alias_works() {
         .success();
 }
 
+#[test]
+fn fmt_check_shows_metadata_requoting() {
+    let (_parent, dir) = make_skill_dir(
+        "quote-test",
+        "---\nname: quote-test\ndescription: Does things\nmetadata:\n  version: \"1.0\"\n  author: 'Jane'\n---\nBody.\n",
+    );
+    aigent()
+        .args(["fmt", dir.to_str().unwrap(), "--check"])
+        .assert()
+        .code(1)
+        .stderr(
+            predicate::str::contains("-  version: \"1.0\"")
+                .and(predicate::str::contains("+  version: '1.0'"))
+                .and(predicate::str::contains("+  author: Jane")),
+        );
+}
+
+// ── --stdin ─────────────────────────────────────────────────────────
+
+const STDIN_SKILL: &str = "---\nname: stdin-skill\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n";
+
+#[test]
+fn validate_stdin_valid_content() {
+    aigent()
+        .args(["validate", "--stdin"])
+        .write_stdin(STDIN_SKILL)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("ok"));
+}
+
+#[test]
+fn validate_stdin_empty_content_reports_empty_file() {
+    aigent()
+        .args(["validate", "--stdin"])
+        .write_stdin("  \n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("SKILL.md is empty"));
+}
+
+#[test]
+fn validate_stdin_invalid_content_exits_nonzero() {
+    aigent()
+        .args(["validate", "--stdin"])
+        .write_stdin("---\nname: Bad_Name\ndescription: Does things\n---\nBody.\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Bad_Name"));
+}
+
+#[test]
+fn validate_stdin_json_uses_synthetic_path() {
+    let output = aigent()
+        .args(["validate", "--stdin", "--format", "json"])
+        .write_stdin(STDIN_SKILL)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["path"], "<stdin>");
+}
+
+#[test]
+fn validate_stdin_path_checks_directory_name() {
+    let output = aigent()
+        .args([
+            "validate",
+            "--stdin",
+            "--stdin-path",
+            "skills/other-name/SKILL.md",
+            "--format",
+            "json",
+        ])
+        .write_stdin(STDIN_SKILL)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["path"], "skills/other-name/SKILL.md");
+    assert_eq!(json[0]["diagnostics"][0]["code"], "E009");
+}
+
+#[test]
+fn stdin_path_uses_shared_defaults_like_the_file_on_disk() {
+    let parent = tempdir().unwrap();
+    let coll = parent.path().join("coll");
+    let skill = coll.join("stdin-skill");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(coll.join("_defaults.yml"), "compatibility: [a, b]\n").unwrap();
+    fs::write(skill.join("SKILL.md"), STDIN_SKILL).unwrap();
+    let skill_md = skill.join("SKILL.md");
+    let skill_md = skill_md.to_str().unwrap();
+
+    for command in ["validate", "check"] {
+        let on_disk = aigent()
+            .args([command, skill.to_str().unwrap(), "--format", "json"])
+            .output()
+            .unwrap();
+        let piped = aigent()
+            .args([
+                command,
+                "--stdin",
+                "--stdin-path",
+                skill_md,
+                "--format",
+                "json",
+            ])
+            .write_stdin(STDIN_SKILL)
+            .output()
+            .unwrap();
+        assert_eq!(on_disk.status.code(), Some(1), "{command}");
+        assert_eq!(piped.status.code(), on_disk.status.code(), "{command}");
+        let codes = |out: &[u8]| -> Vec<String> {
+            let json: serde_json::Value = serde_json::from_slice(out).unwrap();
+            json[0]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|d| d["code"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(codes(&piped.stdout), codes(&on_disk.stdout), "{command}");
+    }
+}
+
+#[test]
+fn validate_stdin_rejects_recursive() {
+    aigent()
+        .args(["validate", "--stdin", "--recursive"])
+        .write_stdin(STDIN_SKILL)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn validate_stdin_path_requires_stdin() {
+    aigent()
+        .args(["validate", "--stdin-path", "x/SKILL.md"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--stdin"));
+}
+
+#[test]
+fn check_stdin_runs_lint_checks() {
+    aigent()
+        .args(["check", "--stdin"])
+        .write_stdin("---\nname: stdin-skill\ndescription: Processes PDF files and generates reports.\n---\nBody.\n")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("description lacks trigger phrase"));
+}
+
+#[test]
+fn fmt_stdin_writes_formatted_content_to_stdout() {
+    aigent()
+        .args(["fmt", "--stdin"])
+        .write_stdin("---\ndescription: Does things\nname: stdin-skill\n---\nBody.\n")
+        .assert()
+        .success()
+        .stdout("---\nname: stdin-skill\ndescription: Does things\n---\nBody.\n");
+}
+
+#[test]
+fn fmt_stdin_check_already_formatted_exits_zero() {
+    aigent()
+        .args(["fmt", "--stdin", "--check"])
+        .write_stdin(STDIN_SKILL)
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn fmt_stdin_check_unformatted_exits_nonzero() {
+    aigent()
+        .args(["fmt", "--stdin", "--check"])
+        .write_stdin("---\ndescription: Does things\nname: stdin-skill\n---\nBody.\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Would reformat: <stdin>"))
+        .stdout("");
+}
+
 // ── M12: watch mode (no-feature build) ───────────────────────────
 
 #[test]
@@ -1924,10 +3989,93 @@ fn watch_flag_without_feature_exits_with_message() {
     aigent()
         .args(["validate", dir.to_str().unwrap(), "--watch"])
         .assert()
-        .failure()
+        .code(2)
         .stderr(predicate::str::contains("watch"));
 }
 
+// ── lsp ──────────────────────────────────────────────────────────
+
+#[cfg(not(feature = "lsp"))]
+#[test]
+fn lsp_without_feature_exits_with_message() {
+    aigent()
+        .arg("lsp")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cargo build --features lsp"));
+}
+
+/// Frame JSON-RPC messages with `Content-Length` headers.
+#[cfg(feature = "lsp")]
+fn lsp_frames(messages: &[serde_json::Value]) -> String {
+    messages
+        .iter()
+        .map(|m| {
+            let body = m.to_string();
+            format!("Content-Length: {}\r\n\r\n{body}", body.len())
+        })
+        .collect()
+}
+
+/// Split a server's output into JSON-RPC message bodies.
+#[cfg(feature = "lsp")]
+fn lsp_messages(output: &[u8]) -> Vec<serde_json::Value> {
+    let text = String::from_utf8_lossy(output);
+    let mut rest = text.as_ref();
+    let mut messages = Vec::new();
+    while let Some((header, after)) = rest.split_once("\r\n\r\n") {
+        let length: usize = header
+            .trim_start_matches("Content-Length:")
+            .trim()
+            .parse()
+            .unwrap();
+        messages.push(serde_json::from_str(&after[..length]).unwrap());
+        rest = &after[length..];
+    }
+    messages
+}
+
+#[cfg(feature = "lsp")]
+#[test]
+fn lsp_publishes_name_error_for_opened_document() {
+    let (_parent, dir) = make_skill_dir("my-skill", "");
+    let uri = format!("file://{}", dir.join("SKILL.md").display());
+    let input = lsp_frames(&[
+        serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+        serde_json::json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": {
+                "uri": uri,
+                "languageId": "markdown",
+                "version": 1,
+                "text": "---\nname: My_Skill\ndescription: Does things\n---\nBody.\n",
+            }},
+        }),
+        serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+        serde_json::json!({"jsonrpc": "2.0", "method": "exit"}),
+    ]);
+    let output = aigent().arg("lsp").write_stdin(input).output().unwrap();
+    assert!(output.status.success());
+    let messages = lsp_messages(&output.stdout);
+    assert_eq!(messages[0]["id"], 1);
+    assert!(messages[0]["result"]["capabilities"].is_object());
+    let publish = messages
+        .iter()
+        .find(|m| m["method"] == "textDocument/publishDiagnostics")
+        .expect("publishDiagnostics notification");
+    assert_eq!(publish["params"]["uri"], uri);
+    let diags = publish["params"]["diagnostics"].as_array().unwrap();
+    let name = diags
+        .iter()
+        .find(|d| d["code"] == "E003")
+        .expect("name error");
+    assert_eq!(name["range"]["start"]["line"], 1);
+    assert_eq!(name["severity"], 1);
+    assert_eq!(messages.last().unwrap()["id"], 2);
+}
+
 // ── M11: build --interactive flag ─────────────────────────────────
 
 #[test]
@@ -1948,7 +4096,7 @@ fn new_interactive_flag_accepted() {
         ])
         .write_stdin("n\n")
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("cancelled"));
 }
 
@@ -1986,7 +4134,7 @@ fn test_run_suite_with_failure() {
     aigent()
         .args(["test", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("0 passed, 1 failed"));
 }
 
@@ -1999,7 +4147,7 @@ fn test_missing_tests_yml_exits_nonzero() {
     aigent()
         .args(["test", dir.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("no tests.yml"));
 }
 
@@ -2061,6 +4209,221 @@ fn test_json_format_outputs_suite_result() {
     assert_eq!(json["failed"], 0);
 }
 
+#[test]
+fn test_junit_format_maps_cases_to_testcases() {
+    let (_parent, dir) = make_skill_dir(
+        "test-junit-suite",
+        "---\nname: test-junit-suite\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"process PDF & docs\"\n    should_match: false\n",
+    )
+    .unwrap();
+    let output = aigent()
+        .args(["test", dir.to_str().unwrap(), "--format", "junit"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("<?xml"));
+    assert!(stdout.contains("tests=\"2\" failures=\"1\""));
+    assert!(stdout.contains("name=\"process PDF files\"/>"));
+    assert!(stdout.contains("name=\"process PDF &amp; docs\">"));
+    assert_eq!(stdout.matches("<failure ").count(), 1);
+}
+
+#[test]
+fn test_filter_runs_matching_cases_only() {
+    let (_parent, dir) = make_skill_dir(
+        "test-filter",
+        "---\nname: test-filter\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"generate reports\"\n    should_match: false\n",
+    )
+    .unwrap();
+    // The second case would fail; filtering it out makes the run pass.
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--filter", "pdf"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "1 passed, 0 failed, 1 total (1 filtered out)",
+        ));
+}
+
+#[test]
+fn test_tag_runs_tagged_cases_only() {
+    let (_parent, dir) = make_skill_dir(
+        "test-tag",
+        "---\nname: test-tag\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n    tags: [smoke]\n  - input: \"generate reports\"\n    should_match: false\n",
+    )
+    .unwrap();
+    // The untagged case would fail; --tag skips it.
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--tag", "smoke"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "1 passed, 0 failed, 1 total (1 filtered out)",
+        ));
+    aigent()
+        .args([
+            "test",
+            dir.to_str().unwrap(),
+            "--tag",
+            "smoke",
+            "--include-untagged",
+        ])
+        .assert()
+        .code(1);
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--tag", "nightly"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("no test cases tagged 'nightly'"));
+}
+
+#[test]
+fn test_filter_json_includes_filter() {
+    let (_parent, dir) = make_skill_dir(
+        "test-filter-json",
+        "---\nname: test-filter-json\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n  - input: \"deploy kubernetes\"\n    should_match: false\n",
+    )
+    .unwrap();
+    let output = aigent()
+        .args([
+            "test",
+            dir.to_str().unwrap(),
+            "--filter",
+            "Kubernetes",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["filter"], "Kubernetes");
+    assert_eq!(json["filtered_out"], 1);
+    assert_eq!(json["passed"], 1);
+    assert_eq!(json["results"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_filter_no_matches_exits_nonzero() {
+    let (_parent, dir) = make_skill_dir(
+        "test-filter-none",
+        "---\nname: test-filter-none\ndescription: Processes PDF files. Use when working with documents.\n---\nBody.\n",
+    );
+    fs::write(
+        dir.join("tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n",
+    )
+    .unwrap();
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--filter", "nonexistent"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "no test cases match filter 'nonexistent'",
+        ));
+}
+
+/// Build a collection with a passing and a failing fixture suite.
+fn make_test_collection() -> tempfile::TempDir {
+    let root = tempdir().unwrap();
+    for (name, should_match) in [("passing-suite", "true"), ("failing-suite", "false")] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Processes PDF files and generates reports. Use when working with documents.\n---\nBody.\n"),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tests.yml"),
+            format!("queries:\n  - input: \"process PDF files\"\n    should_match: {should_match}\n  - input: \"deploy kubernetes\"\n    should_match: false\n"),
+        )
+        .unwrap();
+    }
+    root
+}
+
+#[test]
+fn test_recursive_json_is_one_document_with_totals() {
+    let root = make_test_collection();
+    let output = aigent()
+        .args([
+            "test",
+            root.path().to_str().unwrap(),
+            "--recursive",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let suites = json["suites"].as_array().unwrap();
+    assert_eq!(suites.len(), 2);
+    assert!(suites.iter().all(|s| s["path"].is_string()));
+    let sum = |key: &str| -> u64 {
+        suites
+            .iter()
+            .map(|s| s["result"][key].as_u64().unwrap())
+            .sum()
+    };
+    assert_eq!(json["totals"]["suites"], 2);
+    assert_eq!(json["totals"]["passed"], sum("passed"));
+    assert_eq!(json["totals"]["failed"], sum("failed"));
+    assert_eq!(json["totals"]["passed"], 3);
+    assert_eq!(json["totals"]["failed"], 1);
+}
+
+#[test]
+fn test_recursive_text_prints_summary_table_failures_first() {
+    let root = make_test_collection();
+    let output = aigent()
+        .args(["test", root.path().to_str().unwrap(), "--recursive"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let table = &stderr[stderr.find("Skill  ").expect("summary table")..];
+    assert!(table.contains("Passed  Failed  Skipped  Duration"));
+    let failing = table.find("failing-suite").unwrap();
+    let passing = table.find("passing-suite").unwrap();
+    assert!(failing < passing, "table: {table}");
+    assert!(table.contains("Total: 3 passed, 1 failed, 4 total across 2 suites"));
+}
+
+#[cfg(not(feature = "watch"))]
+#[test]
+fn test_watch_without_feature_exits_with_message() {
+    let (_parent, dir) = make_skill_dir(
+        "test-watch",
+        "---\nname: test-watch\ndescription: Testing watch\n---\nBody.\n",
+    );
+    aigent()
+        .args(["test", dir.to_str().unwrap(), "--watch"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("watch"));
+}
+
 // ── Default directory (#116) ────────────────────────────────────────
 
 #[test]
@@ -2168,7 +4531,7 @@ fn probe_no_skill_in_cwd_errors_gracefully() {
         .args(["probe", "--query", "some query"])
         .current_dir(empty_dir.path())
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("aigent probe:"));
 }
 
@@ -2268,13 +4631,53 @@ fn validate_plugin_valid_manifest() {
         .stderr(predicate::str::contains("Plugin validation passed"));
 }
 
+#[test]
+fn validate_plugin_registry_collision() {
+    let (_dir, path) = make_plugin_dir(
+        r#"{ "name": "my-plugin", "description": "A test plugin", "version": "1.0.0", "author": "Test", "homepage": "https://example.com", "license": "MIT" }"#,
+    );
+    let registry = path.join("known-plugins.txt");
+    fs::write(
+        &registry,
+        "# fetched from the marketplace\nother-plugin\nmy-plugin\n",
+    )
+    .unwrap();
+    aigent()
+        .args(["validate-plugin", path.to_str().unwrap(), "--registry"])
+        .arg(&registry)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "plugin name `my-plugin` collides with a known plugin in the registry",
+        ));
+
+    fs::write(&registry, "other-plugin\n").unwrap();
+    aigent()
+        .args(["validate-plugin", path.to_str().unwrap(), "--registry"])
+        .arg(&registry)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Plugin validation passed"));
+}
+
+#[test]
+fn validate_plugin_missing_registry_file() {
+    let (_dir, path) = make_plugin_dir(r#"{ "name": "my-plugin" }"#);
+    aigent()
+        .args(["validate-plugin", path.to_str().unwrap(), "--registry"])
+        .arg(path.join("missing.txt"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("cannot read registry"));
+}
+
 #[test]
 fn validate_plugin_missing_plugin_json() {
     let dir = tempdir().unwrap();
     aigent()
         .args(["validate-plugin", dir.path().to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("cannot read plugin.json"));
 }
 
@@ -2284,7 +4687,7 @@ fn validate_plugin_invalid_json() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("invalid JSON syntax"));
 }
 
@@ -2294,7 +4697,7 @@ fn validate_plugin_missing_name() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("missing required field `name`"));
 }
 
@@ -2304,7 +4707,7 @@ fn validate_plugin_invalid_name() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("not valid kebab-case"));
 }
 
@@ -2362,7 +4765,7 @@ fn validate_plugin_credential_detection() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("credential"));
 }
 
@@ -2383,10 +4786,129 @@ fn validate_plugin_discovers_hooks() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("unknown event name"));
 }
 
+/// Write a plugin with one known and one unknown hook event.
+fn make_plugin_with_future_hook(parent: &std::path::Path, manifest_extra: &str) -> PathBuf {
+    let path = parent.join("future-plugin");
+    fs::create_dir(&path).unwrap();
+    fs::write(
+        path.join("plugin.json"),
+        format!(
+            r#"{{ "name": "test", "description": "t", "author": "x", "homepage": "x", "license": "MIT"{manifest_extra} }}"#
+        ),
+    )
+    .unwrap();
+    fs::write(
+        path.join("hooks.json"),
+        r#"{
+            "PreToolUse": [{ "hooks": [{ "type": "command", "command": "echo" }] }],
+            "FutureEvent": [{ "hooks": [{ "type": "command", "command": "echo" }] }]
+        }"#,
+    )
+    .unwrap();
+    path
+}
+
+#[test]
+fn validate_plugin_unknown_event_errors_by_default() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), "");
+    aigent()
+        .args(["validate-plugin", path.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "  unknown event name: \"FutureEvent\"",
+        ))
+        .stderr(predicate::str::contains("\"PreToolUse\"").not());
+}
+
+#[test]
+fn validate_plugin_schema_latest_keeps_unknown_event_error() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), r#", "minClaudeCodeVersion": "99.0.0""#);
+    aigent()
+        .args([
+            "validate-plugin",
+            path.to_str().unwrap(),
+            "--plugin-schema",
+            "latest",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "  unknown event name: \"FutureEvent\"",
+        ));
+}
+
+#[test]
+fn validate_plugin_schema_permissive_downgrades_unknown_event() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), "");
+    aigent()
+        .args([
+            "validate-plugin",
+            path.to_str().unwrap(),
+            "--plugin-schema",
+            "permissive",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("warning: unknown event name"))
+        .stderr(predicate::str::contains("possibly from a newer schema"));
+}
+
+#[test]
+fn validate_plugin_schema_version_without_a_table_stays_strict() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), "");
+    aigent()
+        .args([
+            "validate-plugin",
+            path.to_str().unwrap(),
+            "--plugin-schema",
+            "99.0",
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "  unknown event name: \"FutureEvent\"",
+        ))
+        .stderr(predicate::str::contains("newer schema").not());
+}
+
+#[test]
+fn validate_plugin_manifest_min_version_without_a_table_stays_strict() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), r#", "minClaudeCodeVersion": "99.0.0""#);
+    aigent()
+        .args(["validate-plugin", path.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "  unknown event name: \"FutureEvent\"",
+        ));
+}
+
+#[test]
+fn validate_plugin_rejects_invalid_schema_flag() {
+    let dir = tempdir().unwrap();
+    let path = make_plugin_with_future_hook(dir.path(), "");
+    aigent()
+        .args([
+            "validate-plugin",
+            path.to_str().unwrap(),
+            "--plugin-schema",
+            "newest",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid plugin schema"));
+}
+
 #[test]
 fn validate_plugin_discovers_agents() {
     let dir = tempdir().unwrap();
@@ -2406,7 +4928,7 @@ fn validate_plugin_discovers_agents() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("agents/bad.md"))
         .stderr(predicate::str::contains("`model` is not valid"));
 }
@@ -2426,7 +4948,7 @@ fn validate_plugin_discovers_commands() {
     aigent()
         .args(["validate-plugin", path.to_str().unwrap()])
         .assert()
-        .failure()
+        .code(1)
         .stderr(predicate::str::contains("commands/empty.md"))
         .stderr(predicate::str::contains("command body is empty"));
 }
@@ -2583,3 +5105,461 @@ fn test_strength_weak_passes_for_matching_query() {
         .assert()
         .success();
 }
+
+// ── workspace (aigent.toml) ─────────────────────────────────────────
+
+/// Skill content that is clean under `claude-code` but carries an
+/// extension field (`argument-hint`) that `standard` flags and the scorer
+/// penalizes.
+const WORKSPACE_SKILL: &str = "---\nname: processing-pdfs\ndescription: >-\n  Processes PDF files and generates detailed reports.\n  Use when working with documents.\nargument-hint: \"[file]\"\n---\nBody.\n";
+
+/// Create a fixture workspace with two collections whose settings conflict:
+/// `internal` accepts Claude Code fields with no score gate, while `public`
+/// uses the standard target, a perfect-score gate, and excludes `drafts/`.
+fn make_workspace() -> tempfile::TempDir {
+    let root = tempdir().unwrap();
+    for rel in [
+        "internal/processing-pdfs",
+        "public/processing-pdfs",
+        "public/drafts/broken",
+    ] {
+        let dir = root.path().join(rel);
+        fs::create_dir_all(&dir).unwrap();
+        let content = if rel.ends_with("broken") {
+            "not a skill".to_string()
+        } else {
+            WORKSPACE_SKILL.to_string()
+        };
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+    }
+    fs::write(
+        root.path().join("aigent.toml"),
+        r#"
+[[collection]]
+path = "internal"
+target = "claude-code"
+prompt_output = "dist/internal.xml"
+
+[[collection]]
+path = "public"
+target = "standard"
+profile = "strict"
+min_score = 100
+exclude = ["drafts"]
+prompt_output = "dist/public.xml"
+"#,
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn workspace_check_applies_per_collection_settings() {
+    let root = make_workspace();
+    let output = aigent()
+        .args(["workspace", "check", root.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "public collection should fail"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("internal: 1 skills: 1 ok, 0 errors, 0 warnings only"),
+        "internal uses claude-code target:\n{stderr}"
+    );
+    assert!(
+        stderr.contains("public: 1 skills: 0 ok, 0 errors, 1 warnings only, 1 below min score 100"),
+        "public uses standard target and score gate:\n{stderr}"
+    );
+    assert!(
+        !stderr.contains("broken"),
+        "excluded drafts must not be checked:\n{stderr}"
+    );
+    assert!(
+        stderr.contains("Workspace: 2 collections: 1 passed, 1 failed (public)"),
+        "combined summary:\n{stderr}"
+    );
+}
+
+#[test]
+fn workspace_check_passes_when_all_collections_pass() {
+    let root = make_workspace();
+    fs::write(
+        root.path().join("aigent.toml"),
+        "[[collection]]\npath = \"internal\"\ntarget = \"claude-code\"\n",
+    )
+    .unwrap();
+    aigent()
+        .args(["workspace", "check", root.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Workspace: 1 collections: 1 passed, 0 failed",
+        ));
+}
+
+#[test]
+fn workspace_prompt_writes_each_collection_output() {
+    let root = make_workspace();
+    aigent()
+        .args(["workspace", "prompt", root.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("updated"));
+    let internal = fs::read_to_string(root.path().join("dist/internal.xml")).unwrap();
+    let public = fs::read_to_string(root.path().join("dist/public.xml")).unwrap();
+    assert!(internal.contains("<name>processing-pdfs</name>"));
+    assert!(public.contains("<name>processing-pdfs</name>"));
+    assert!(!public.contains("broken"));
+
+    // Second run: outputs are current, so nothing changes and exit is 0.
+    aigent()
+        .args(["workspace", "prompt", root.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("unchanged"));
+}
+
+#[test]
+fn workspace_doc_prints_catalog_per_collection() {
+    let root = make_workspace();
+    aigent()
+        .args(["workspace", "doc", root.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("# Skill Catalog"))
+        .stderr(predicate::str::contains("== internal =="))
+        .stderr(predicate::str::contains("== public =="));
+}
+
+#[test]
+fn workspace_test_runs_fixtures_and_reports_missing() {
+    let root = make_workspace();
+    fs::write(
+        root.path().join("internal/processing-pdfs/tests.yml"),
+        "queries:\n  - input: \"process PDF files\"\n    should_match: true\n",
+    )
+    .unwrap();
+    aigent()
+        .args(["workspace", "test", root.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "internal: 1 suites passed, 0 skills without tests.yml",
+        ))
+        .stderr(predicate::str::contains(
+            "public: 0 suites passed, 1 skills without tests.yml",
+        ));
+}
+
+#[test]
+fn workspace_unknown_key_reports_accepted_keys() {
+    let root = make_workspace();
+    fs::write(
+        root.path().join("aigent.toml"),
+        "[[collection]]\npath = \"internal\"\nmin_scor = 80\n",
+    )
+    .unwrap();
+    aigent()
+        .args(["workspace", "check", root.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("aigent.toml"))
+        .stderr(predicate::str::contains("min_scor"))
+        .stderr(predicate::str::contains("min_score"));
+}
+
+#[test]
+fn workspace_missing_config_exits_nonzero() {
+    let root = tempdir().unwrap();
+    aigent()
+        .args(["workspace", "check", root.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("aigent workspace:"));
+}
+
+// ── Deprecated skills ───────────────────────────────────────────────
+
+/// Create a collection with a deprecated `old-skill` pointing at `successor`
+/// and an active `new-skill`. Returns the collection root.
+fn make_deprecated_collection(successor: &str) -> tempfile::TempDir {
+    let root = tempdir().unwrap();
+    for (name, content) in [
+        (
+            "old-skill",
+            format!(
+                "---\nname: old-skill\ndescription: Converts legacy reports. Use when converting reports.\nmetadata:\n  deprecated: true\n  superseded-by: {successor}\n---\nOld instructions.\n"
+            ),
+        ),
+        (
+            "new-skill",
+            "---\nname: new-skill\ndescription: Builds quarterly dashboards. Use when building dashboards.\n---\nNew instructions.\n".to_string(),
+        ),
+    ] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+    }
+    root
+}
+
+#[test]
+fn prompt_skips_deprecated_skills() {
+    let root = make_deprecated_collection("new-skill");
+    let old = root.path().join("old-skill");
+    let new = root.path().join("new-skill");
+    aigent()
+        .args(["prompt", old.to_str().unwrap(), new.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<name>new-skill</name>"))
+        .stdout(predicate::str::contains("old-skill").not())
+        .stderr(predicate::str::contains(
+            "skipped 1 deprecated skill: old-skill",
+        ));
+    aigent()
+        .args([
+            "prompt",
+            old.to_str().unwrap(),
+            new.to_str().unwrap(),
+            "--include-deprecated",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<name>old-skill</name>"));
+}
+
+#[test]
+fn prompt_exclude_drops_named_skills() {
+    let root = tempdir().unwrap();
+    let mut dirs = Vec::new();
+    for name in ["alpha-skill", "deprecated-skill", "gamma-skill"] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Does things\n---\nBody.\n"),
+        )
+        .unwrap();
+        dirs.push(dir.to_str().unwrap().to_string());
+    }
+    let output = aigent()
+        .arg("prompt")
+        .args(&dirs)
+        .args(["--format", "json", "--exclude", "deprecated-skill"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["alpha-skill", "gamma-skill"]);
+    assert!(output.stderr.is_empty(), "{:?}", output.stderr);
+
+    aigent()
+        .arg("prompt")
+        .args(&dirs)
+        .args(["--exclude", "no-such-skill"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("<name>deprecated-skill</name>"))
+        .stderr(predicate::str::contains(
+            "warning: --exclude no-such-skill: no skill with this name was found",
+        ));
+}
+
+#[test]
+fn prompt_sort_priority_orders_by_descending_priority() {
+    let root = tempdir().unwrap();
+    let mut dirs = Vec::new();
+    for (name, metadata) in [
+        ("unset-skill", ""),
+        ("five-skill", "metadata:\n  priority: 5\n"),
+        ("ten-skill", "metadata:\n  priority: 10\n"),
+    ] {
+        let dir = root.path().join(name);
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Does things\n{metadata}---\nBody.\n"),
+        )
+        .unwrap();
+        dirs.push(dir.to_str().unwrap().to_string());
+    }
+    let output = aigent()
+        .arg("prompt")
+        .args(&dirs)
+        .args(["--sort", "priority"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ten = stdout.find("<name>ten-skill</name>").unwrap();
+    let five = stdout.find("<name>five-skill</name>").unwrap();
+    let unset = stdout.find("<name>unset-skill</name>").unwrap();
+    assert!(ten < five && five < unset, "{stdout}");
+}
+
+#[test]
+fn prompt_groups_follow_argument_order() {
+    let (_p1, a) = make_skill_dir("skill-a", "---\nname: skill-a\ndescription: A\n---\n");
+    let (_p2, b) = make_skill_dir("skill-b", "---\nname: skill-b\ndescription: B\n---\n");
+    let (_p3, c) = make_skill_dir("skill-c", "---\nname: skill-c\ndescription: C\n---\n");
+    let output = aigent()
+        .args([
+            "prompt",
+            c.to_str().unwrap(),
+            "--group",
+            &format!("zeta={}", b.display()),
+            "--group",
+            &format!("alpha={}", a.display()),
+            "--budget",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let default = stdout.find("<skill_group name=\"default\">").unwrap();
+    let zeta = stdout.find("<skill_group name=\"zeta\">").unwrap();
+    let alpha = stdout.find("<skill_group name=\"alpha\">").unwrap();
+    assert!(default < zeta && zeta < alpha, "{stdout}");
+    assert!(stdout.find("<name>skill-b</name>").unwrap() > zeta);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let zeta = stderr.find("[zeta]").unwrap();
+    let alpha = stderr.find("[alpha]").unwrap();
+    assert!(zeta < alpha, "{stderr}");
+    assert_eq!(stderr.matches("Subtotal:").count(), 3);
+}
+
+#[test]
+fn prompt_only_groups_skips_current_directory() {
+    let (_parent, dir) = make_skill_dir("my-skill", "---\nname: my-skill\ndescription: A\n---\n");
+    aigent()
+        .args([
+            "prompt",
+            "--group",
+            &format!("org-library={}", dir.display()),
+            "--format",
+            "markdown",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## org-library\n\n### my-skill\n"))
+        .stdout(predicate::str::contains("## default").not());
+}
+
+#[test]
+fn prompt_without_groups_output_is_unchanged() {
+    let (_p1, d1) = make_skill_dir(
+        "skill-one",
+        "---\nname: skill-one\ndescription: First\n---\n",
+    );
+    let (_p2, d2) = make_skill_dir(
+        "skill-two",
+        "---\nname: skill-two\ndescription: Second\n---\n",
+    );
+    let skill_md = |d: &std::path::Path| d.join("SKILL.md").canonicalize().unwrap();
+    let expected = format!(
+        "<available_skills>\n  <skill>\n    <name>skill-one</name>\n    \
+         <description>First</description>\n    <location>{}</location>\n  </skill>\n  \
+         <skill>\n    <name>skill-two</name>\n    <description>Second</description>\n    \
+         <location>{}</location>\n  </skill>\n</available_skills>\n",
+        skill_md(&d1).display(),
+        skill_md(&d2).display()
+    );
+    aigent()
+        .args(["prompt", d1.to_str().unwrap(), d2.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(expected));
+}
+
+#[test]
+fn prompt_tools_summary_lists_union_of_allowed_tools() {
+    let (_p1, a) = make_skill_dir(
+        "skill-a",
+        "---\nname: skill-a\ndescription: A\nallowed-tools: Bash, Read\n---\n",
+    );
+    let (_p2, b) = make_skill_dir(
+        "skill-b",
+        "---\nname: skill-b\ndescription: B\nallowed-tools: Read Write\n---\n",
+    );
+    for flag in ["--tools-summary", "--dedupe-tools"] {
+        aigent()
+            .args(["prompt", a.to_str().unwrap(), b.to_str().unwrap(), flag])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Tools required").not())
+            .stderr(predicate::str::contains("Tools required (3):"))
+            .stderr(predicate::str::is_match(r"(?m)^  Read +skill-a, skill-b$").unwrap())
+            .stderr(predicate::str::is_match(r"(?m)^  Write +skill-b$").unwrap());
+    }
+}
+
+#[test]
+fn prompt_group_requires_label_and_path() {
+    aigent()
+        .args(["prompt", "--group", "=skills"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("expected LABEL=PATH"));
+}
+
+#[test]
+fn doc_renders_deprecated_section_with_successor_link() {
+    let root = make_deprecated_collection("new-skill");
+    let output = aigent()
+        .args(["doc", root.path().to_str().unwrap(), "--recursive"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let new_pos = stdout.find("## new-skill").unwrap();
+    let section_pos = stdout.find("## Deprecated").unwrap();
+    let old_pos = stdout.find("### old-skill").unwrap();
+    assert!(new_pos < section_pos && section_pos < old_pos, "{stdout}");
+    assert!(stdout.contains("**Superseded by**: [new-skill](#new-skill)"));
+}
+
+#[test]
+fn validate_recursive_accepts_known_successor() {
+    let root = make_deprecated_collection("new-skill");
+    aigent()
+        .args(["validate", root.path().to_str().unwrap(), "--recursive"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("W001").not())
+        .stderr(predicate::str::contains("superseded-by").not());
+}
+
+#[test]
+fn validate_recursive_flags_unknown_successor() {
+    let root = make_deprecated_collection("missing-skill");
+    aigent()
+        .args(["validate", root.path().to_str().unwrap(), "--recursive"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "superseded-by names unknown skill 'missing-skill'",
+        ));
+}
+
+#[test]
+fn check_reminds_deprecated_skill_needs_removal_date() {
+    let root = make_deprecated_collection("new-skill");
+    let old = root.path().join("old-skill");
+    aigent()
+        .args(["check", old.to_str().unwrap()])
+        .assert()
+        .stderr(predicate::str::contains(
+            "deprecated skill has no removal date",
+        ));
+}
@@ -0,0 +1,150 @@
+//! Property tests for frontmatter parsing and formatting.
+//!
+//! Arbitrary input must never panic `parse_frontmatter`, `format_content`,
+//! or `validate_content`; every parse failure must surface as a diagnostic;
+//! and formatting valid input must preserve its parsed meaning.
+
+use std::time::{Duration, Instant};
+
+use aigent::diagnostics::{ValidationTarget, E000, E022};
+use aigent::{format_content, parse_frontmatter, validate_content};
+use proptest::prelude::*;
+
+/// Inputs biased towards frontmatter syntax: delimiters, YAML indicators,
+/// quotes, CR/LF, and tabs.
+fn frontmatter_like() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        Just("---".to_string()),
+        Just("\n".to_string()),
+        Just("\r\n".to_string()),
+        Just("\r".to_string()),
+        Just("\t".to_string()),
+        Just("<!-- c -->".to_string()),
+        Just("name: ".to_string()),
+        Just("description: ".to_string()),
+        Just("metadata:\n  ".to_string()),
+        "[-:#|>'\"{}\\[\\],&*!%@` a-z0-9]{0,8}",
+        any::<char>().prop_map(String::from),
+    ];
+    prop::collection::vec(piece, 0..40).prop_map(|parts| parts.concat())
+}
+
+/// Any input: fully arbitrary strings or frontmatter-shaped ones.
+fn any_input() -> impl Strategy<Value = String> {
+    prop_oneof![any::<String>(), frontmatter_like()]
+}
+
+/// A valid `SKILL.md` with a plain name, description, and body.
+fn valid_skill() -> impl Strategy<Value = String> {
+    (
+        "[a-z][a-z0-9]{0,10}(-[a-z0-9]{1,8}){0,2}",
+        "[A-Za-z][A-Za-z0-9 ,.]{0,60}[A-Za-z.]",
+        prop::option::of("[A-Za-z0-9 ]{1,20}"),
+        "([A-Za-z#*` -]{0,30}\n){0,6}",
+    )
+        .prop_map(|(name, description, license, body)| {
+            let license = license
+                .map(|l| format!("license: '{}'\n", l.trim()))
+                .unwrap_or_default();
+            format!("---\nname: {name}\ndescription: {description}\n{license}---\n{body}")
+        })
+}
+
+/// Non-blank body lines with trailing whitespace removed.
+fn body_text(body: &str) -> Vec<&str> {
+    body.lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn parse_frontmatter_never_panics(input in any_input()) {
+        let _ = parse_frontmatter(&input);
+    }
+
+    #[test]
+    fn format_content_never_panics(input in any_input()) {
+        let _ = format_content(&input);
+    }
+
+    #[test]
+    fn parse_errors_become_diagnostics(input in any_input()) {
+        let diags = validate_content(&input, None, ValidationTarget::Standard);
+        // Tab indentation that breaks the YAML is reported as E022 instead.
+        if parse_frontmatter(&input).is_err() {
+            prop_assert!(
+                diags.iter().any(|d| (d.code == E000 || d.code == E022) && d.is_error()),
+                "parse error not reported as E000 or E022: {diags:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_content_never_panics_on_arbitrary_fields(
+        // Multibyte text around the length limits exercises truncation and snippets.
+        name in prop_oneof![any::<String>(), "[a-zA-Zé日_ -]{55,75}"],
+        description in prop_oneof![any::<String>(), "[a-zé日<> ]{1010,1040}"],
+        compatibility in any::<String>(),
+    ) {
+        // JSON strings are valid YAML double-quoted scalars.
+        let q = |s: &str| serde_json::to_string(s).unwrap();
+        let content = format!(
+            "---\nname: {}\ndescription: {}\ncompatibility: {}\n---\n",
+            q(&name),
+            q(&description),
+            q(&compatibility),
+        );
+        let _ = validate_content(&content, None, ValidationTarget::ClaudeCode);
+        let _ = format_content(&content);
+    }
+
+    #[test]
+    fn format_preserves_parsed_frontmatter(input in valid_skill()) {
+        let (meta, body) = parse_frontmatter(&input).unwrap();
+        let formatted = format_content(&input).unwrap();
+        let (meta2, body2) = parse_frontmatter(&formatted).unwrap();
+        prop_assert_eq!(meta, meta2);
+        // Formatting may trim trailing whitespace and collapse blank lines.
+        prop_assert_eq!(body_text(&body), body_text(&body2));
+    }
+
+    #[test]
+    fn format_is_idempotent(input in valid_skill()) {
+        let once = format_content(&input).unwrap();
+        let twice = format_content(&once).unwrap();
+        prop_assert_eq!(once, twice);
+    }
+}
+
+// ── Corpus regression ───────────────────────────────────────────────
+
+/// Upper bound for validating a single corpus file.
+const CORPUS_TIME_LIMIT: Duration = Duration::from_secs(2);
+
+#[test]
+fn corpus_validates_without_panics_within_time_bound() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "corpus is empty: {}", dir.display());
+
+    for path in paths {
+        let bytes = std::fs::read(&path).unwrap();
+        let content = String::from_utf8_lossy(&bytes);
+        let start = Instant::now();
+        let _ = validate_content(&content, None, ValidationTarget::Standard);
+        let _ = format_content(&content);
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed < CORPUS_TIME_LIMIT,
+            "{} took {elapsed:?}",
+            path.display()
+        );
+    }
+}